@@ -4,15 +4,25 @@
 //! and subscription channels.
 
 use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 
 use crate::loaders::HanLoaders;
+use crate::types::discovered_hook::DiscoveredHookInfo;
+use crate::types::enums::IndexProgressStatus;
+use crate::types::hook_dry_run::HookDryRunResult;
 
 /// Database change event for subscriptions.
 #[derive(Debug, Clone)]
 pub enum DbChangeEvent {
     /// A session was updated.
     SessionUpdated { session_id: String },
+    /// An index pass over a session's transcript finished.
+    SessionIndexed { session_id: String },
     /// A new message was added to a session.
     SessionMessageAdded {
         session_id: String,
@@ -61,6 +71,16 @@ pub enum DbChangeEvent {
         file_count: i32,
         tool_name: String,
     },
+    /// A task was recorded.
+    TaskCreated {
+        session_id: Option<String>,
+        task_id: String,
+    },
+    /// A hook execution was recorded by hand.
+    HookExecutionCreated {
+        session_id: String,
+        hook_execution_id: String,
+    },
     /// Session hooks changed.
     SessionHooksChanged {
         session_id: String,
@@ -70,6 +90,22 @@ pub enum DbChangeEvent {
     },
     /// Node updated (generic).
     NodeUpdated { id: String, typename: String },
+    /// A session file moved through a background index run.
+    IndexProgress {
+        session_id: String,
+        file_path: String,
+        status: IndexProgressStatus,
+        messages_indexed: i32,
+    },
+    /// A highly frustrated user message was indexed.
+    FrustrationAlert {
+        session_id: String,
+        message_id: String,
+        score: f64,
+        level: String,
+        content: String,
+        timestamp: String,
+    },
 }
 
 /// User role for access control (hosted mode).
@@ -90,6 +126,24 @@ pub struct UserContext {
     pub project_ids: Option<Vec<String>>,
 }
 
+/// Guard allowing only admins of an organization (hosted mode).
+///
+/// Reads the user from the request's [`GraphQLContext`], so local mode,
+/// which has no authenticated user, is always rejected.
+pub struct OrgAdminGuard;
+
+impl async_graphql::Guard for OrgAdminGuard {
+    async fn check(&self, ctx: &async_graphql::Context<'_>) -> async_graphql::Result<()> {
+        let user = ctx
+            .data_opt::<GraphQLContext>()
+            .and_then(|c| c.user.as_ref());
+        match user {
+            Some(user) if user.role == UserRole::Admin && user.org_id.is_some() => Ok(()),
+            _ => Err("Forbidden: organization admin required".into()),
+        }
+    }
+}
+
 /// Operating mode for the API.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OperatingMode {
@@ -97,6 +151,96 @@ pub enum OperatingMode {
     Hosted,
 }
 
+/// Counts reported by a [`SessionReindexer`].
+#[derive(Debug, Clone, Default)]
+pub struct ReindexStats {
+    pub messages_indexed: u32,
+    pub messages_skipped: u32,
+    pub errors: Vec<String>,
+}
+
+/// Session transcript to re-index.
+#[derive(Debug, Clone)]
+pub struct ReindexRequest {
+    pub transcript_path: String,
+    pub source_config_dir: Option<String>,
+    /// Delete and rebuild every message instead of only changed lines.
+    pub force: bool,
+}
+
+/// Re-indexes a session transcript.
+///
+/// Indexing runs in the host process, which supplies this through
+/// [`crate::schema::build_schema_with_services`].
+pub type SessionReindexer = Arc<
+    dyn Fn(ReindexRequest) -> Pin<Box<dyn Future<Output = Result<ReindexStats, String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Lists the hooks that would run for `(event, tool_name)` without running them.
+///
+/// Hooks are discovered by the coordinator, which supplies this through
+/// [`crate::schema::build_schema_with_services`].
+pub type HookDryRunner = Arc<
+    dyn Fn(String, Option<String>) -> Pin<Box<dyn Future<Output = Vec<HookDryRunResult>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Lists discovered hooks, optionally filtered by `(event, plugin)`.
+///
+/// Hooks are discovered by the coordinator, which supplies this through
+/// [`crate::schema::build_schema_with_services`].
+pub type HookLister = Arc<
+    dyn Fn(
+            Option<String>,
+            Option<String>,
+        ) -> Pin<Box<dyn Future<Output = Vec<DiscoveredHookInfo>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Lists the directories the file watcher is watching.
+///
+/// The watcher runs in the coordinator, which supplies this through
+/// [`crate::schema::build_schema_with_services`].
+pub type WatchedDirectoryLister = Arc<dyn Fn() -> Vec<String> + Send + Sync>;
+
+/// Per-session rate limit for on-demand re-indexing.
+#[derive(Debug, Default)]
+pub struct ReindexThrottle {
+    last_started: Mutex<HashMap<String, Instant>>,
+}
+
+impl ReindexThrottle {
+    /// Minimum time between re-indexes of the same session.
+    pub const INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Record a re-index of `session_id` at `now`, or return false if one
+    /// was already started within [`Self::INTERVAL`].
+    pub fn try_start(&self, session_id: &str, now: Instant) -> bool {
+        let mut last_started = self
+            .last_started
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        last_started.retain(|_, started| now.duration_since(*started) < Self::INTERVAL);
+        if last_started.contains_key(session_id) {
+            return false;
+        }
+        last_started.insert(session_id.to_string(), now);
+        true
+    }
+
+    /// Free `session_id`'s slot so a failed re-index can be retried at once.
+    pub fn release(&self, session_id: &str) {
+        self.last_started
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(session_id);
+    }
+}
+
 /// GraphQL context available to all resolvers.
 pub struct GraphQLContext {
     /// Database connection.
@@ -136,6 +280,25 @@ impl GraphQLContext {
 mod tests {
     use super::*;
 
+    #[test]
+    fn reindex_throttle_limits_per_session() {
+        let throttle = ReindexThrottle::default();
+        let start = Instant::now();
+        assert!(throttle.try_start("s1", start));
+        assert!(!throttle.try_start("s1", start + Duration::from_secs(5)));
+        assert!(throttle.try_start("s2", start + Duration::from_secs(5)));
+        assert!(throttle.try_start("s1", start + ReindexThrottle::INTERVAL));
+    }
+
+    #[test]
+    fn reindex_throttle_release_frees_slot() {
+        let throttle = ReindexThrottle::default();
+        let start = Instant::now();
+        assert!(throttle.try_start("s1", start));
+        throttle.release("s1");
+        assert!(throttle.try_start("s1", start + Duration::from_secs(1)));
+    }
+
     #[test]
     fn db_change_event_session_updated() {
         let e = DbChangeEvent::SessionUpdated {
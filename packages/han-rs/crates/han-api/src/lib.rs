@@ -16,4 +16,16 @@ pub mod subscription;
 pub mod types;
 
 pub use context::GraphQLContext;
-pub use schema::{build_schema, HanSchema};
+pub use schema::{build_schema, build_schema_with_services, HanSchema};
+
+/// In-memory SQLite database with all migrations applied.
+#[cfg(test)]
+pub(crate) async fn test_db() -> sea_orm::DatabaseConnection {
+    let db = han_db::establish_connection(han_db::DbConfig::Sqlite {
+        path: ":memory:".to_string(),
+    })
+    .await
+    .unwrap();
+    han_db::migration::run_migrations(&db).await.unwrap();
+    db
+}
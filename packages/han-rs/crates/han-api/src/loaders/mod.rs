@@ -6,12 +6,16 @@
 use std::collections::HashMap;
 
 use async_graphql::dataloader::*;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 
+use han_db::crud::file_changes::ACTION_READ;
 use han_db::entities::{
-    hook_executions, messages, native_tasks, session_file_changes, session_todos, tasks,
+    hook_executions, memories, messages, native_tasks, plugins, session_file_changes,
+    session_todos, sessions, tasks, tool_call_results,
 };
 
+use crate::types::sessions::SessionData;
+
 // ============================================================================
 // Session Messages Loader
 // ============================================================================
@@ -175,6 +179,7 @@ impl Loader<String> for SessionFileChangesLoader {
     async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
         let all_changes = session_file_changes::Entity::find()
             .filter(session_file_changes::Column::SessionId.is_in(keys.to_vec()))
+            .filter(session_file_changes::Column::Action.ne(ACTION_READ))
             .order_by_desc(session_file_changes::Column::RecordedAt)
             .all(&self.db)
             .await
@@ -195,6 +200,312 @@ impl Loader<String> for SessionFileChangesLoader {
     }
 }
 
+// ============================================================================
+// Session by ID Loader
+// ============================================================================
+
+/// Batch loads sessions by ID, enriched with message stats, project info and
+/// summary the same way `Query.session` is.
+pub struct SessionByIdLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionByIdLoader {
+    type Value = SessionData;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let models = sessions::Entity::find()
+            .filter(sessions::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let mut data: Vec<SessionData> = models
+            .into_iter()
+            .map(crate::query::session_model_to_data)
+            .collect();
+        crate::query::enrich_sessions(&self.db, &mut data).await?;
+
+        Ok(data
+            .into_iter()
+            .map(|s| (s.session_id.clone(), s))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Project Latest Session Loader
+// ============================================================================
+
+/// Batch loads the most recently active session per project path, enriched
+/// like [`SessionByIdLoader`]. Projects without sessions are absent.
+pub struct ProjectLatestSessionLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for ProjectLatestSessionLoader {
+    type Value = SessionData;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let latest = han_db::crud::sessions::get_latest_session_per_project(&self.db, keys)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let (dirs, models): (Vec<String>, Vec<sessions::Model>) = latest.into_iter().unzip();
+        let mut data: Vec<SessionData> = models
+            .into_iter()
+            .map(crate::query::session_model_to_data)
+            .collect();
+        crate::query::enrich_sessions(&self.db, &mut data).await?;
+
+        Ok(dirs.into_iter().zip(data).collect())
+    }
+}
+
+// ============================================================================
+// Message by ID Loader
+// ============================================================================
+
+/// Batch loads messages by ID.
+pub struct MessageByIdLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for MessageByIdLoader {
+    type Value = messages::Model;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let models = messages::Entity::find()
+            .filter(messages::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| (m.id.clone(), m)).collect())
+    }
+}
+
+// ============================================================================
+// Memory by ID Loader
+// ============================================================================
+
+/// Batch loads memories by ID.
+pub struct MemoryLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for MemoryLoader {
+    type Value = memories::Model;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let models = memories::Entity::find()
+            .filter(memories::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| (m.id.clone(), m)).collect())
+    }
+}
+
+// ============================================================================
+// Plugin by Name Loader
+// ============================================================================
+
+/// Batch loads installed plugins by name.
+pub struct PluginLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for PluginLoader {
+    type Value = plugins::Model;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let models = plugins::Entity::find()
+            .filter(plugins::Column::Name.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(models.into_iter().map(|m| (m.name.clone(), m)).collect())
+    }
+}
+
+// ============================================================================
+// Session Count Loaders
+// ============================================================================
+
+/// Batch counts hook executions for multiple sessions.
+pub struct SessionHookCountLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionHookCountLoader {
+    type Value = i32;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let counts: Vec<(String, i64)> = hook_executions::Entity::find()
+            .select_only()
+            .column(hook_executions::Column::SessionId)
+            .column_as(hook_executions::Column::Id.count(), "count")
+            .filter(hook_executions::Column::SessionId.is_in(keys.to_vec()))
+            .group_by(hook_executions::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(count_map(keys, counts))
+    }
+}
+
+/// Batch counts file changes for multiple sessions.
+pub struct SessionFileChangeCountLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionFileChangeCountLoader {
+    type Value = i32;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let counts: Vec<(String, i64)> = session_file_changes::Entity::find()
+            .select_only()
+            .column(session_file_changes::Column::SessionId)
+            .column_as(session_file_changes::Column::Id.count(), "count")
+            .filter(session_file_changes::Column::SessionId.is_in(keys.to_vec()))
+            .filter(session_file_changes::Column::Action.ne(ACTION_READ))
+            .group_by(session_file_changes::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(count_map(keys, counts))
+    }
+}
+
+/// Batch counts system messages for multiple sessions.
+pub struct SessionSystemMessageCountLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionSystemMessageCountLoader {
+    type Value = i32;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let counts: Vec<(String, i64)> = messages::Entity::find()
+            .select_only()
+            .column(messages::Column::SessionId)
+            .column_as(messages::Column::Id.count(), "count")
+            .filter(messages::Column::SessionId.is_in(keys.to_vec()))
+            .filter(messages::Column::MessageType.eq("system"))
+            .group_by(messages::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(count_map(keys, counts))
+    }
+}
+
+/// Batch counts tool results that reported an error, per session.
+pub struct SessionToolErrorCountLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionToolErrorCountLoader {
+    type Value = i32;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let counts: Vec<(String, i64)> = tool_call_results::Entity::find()
+            .select_only()
+            .column(tool_call_results::Column::SessionId)
+            .column_as(tool_call_results::Column::ToolCallId.count(), "count")
+            .filter(tool_call_results::Column::SessionId.is_in(keys.to_vec()))
+            .filter(tool_call_results::Column::IsError.eq(true))
+            .group_by(tool_call_results::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(count_map(keys, counts))
+    }
+}
+
+/// Batch counts tool results, successful or not, per session.
+pub struct SessionToolResultCountLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionToolResultCountLoader {
+    type Value = i32;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let counts: Vec<(String, i64)> = tool_call_results::Entity::find()
+            .select_only()
+            .column(tool_call_results::Column::SessionId)
+            .column_as(tool_call_results::Column::ToolCallId.count(), "count")
+            .filter(tool_call_results::Column::SessionId.is_in(keys.to_vec()))
+            .group_by(tool_call_results::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(count_map(keys, counts))
+    }
+}
+
+/// Batch loads the highest per-message frustration score per session.
+/// Sessions without a scored message have no entry.
+pub struct SessionMaxFrustrationLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionMaxFrustrationLoader {
+    type Value = f64;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let rows: Vec<(String, Option<f64>)> = messages::Entity::find()
+            .select_only()
+            .column(messages::Column::SessionId)
+            .column_as(messages::Column::FrustrationScore.max(), "max_score")
+            .filter(messages::Column::SessionId.is_in(keys.to_vec()))
+            .group_by(messages::Column::SessionId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(session_id, score)| Some((session_id, score?)))
+            .collect())
+    }
+}
+
+/// Map grouped counts by session, with zero for sessions that had no rows.
+fn count_map(keys: &[String], counts: Vec<(String, i64)>) -> HashMap<String, i32> {
+    let mut map: HashMap<String, i32> = keys.iter().map(|k| (k.clone(), 0)).collect();
+    for (session_id, count) in counts {
+        map.insert(session_id, count as i32);
+    }
+    map
+}
+
 // ============================================================================
 // Session Todos Loader
 // ============================================================================
@@ -258,36 +569,152 @@ impl Loader<String> for ToolResultByParentIdLoader {
 }
 
 // ============================================================================
-// Tool Result by Call ID Loader (MCP + exposed tool calls)
+// Session Token Totals Loader
 // ============================================================================
 
-/// Batch loads MCP/exposed tool result messages by call_id in raw_json.
-pub struct ToolResultByCallIdLoader {
+/// Batch loads assistant token usage sums for multiple sessions.
+pub struct SessionTokenTotalsLoader {
     pub db: DatabaseConnection,
 }
 
-impl Loader<String> for ToolResultByCallIdLoader {
+impl Loader<String> for SessionTokenTotalsLoader {
+    type Value = han_db::aggregates::TokenTotals;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        han_db::aggregates::session_token_totals(&self.db, keys)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))
+    }
+}
+
+/// Batch loads hook execution totals per plugin for multiple sessions,
+/// busiest plugin first. Sessions without hook executions have no entry.
+pub struct SessionHookPluginStatsLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionHookPluginStatsLoader {
+    type Value = Vec<han_db::aggregates::HookPluginRow>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        han_db::aggregates::session_hook_plugin_stats(&self.db, keys)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))
+    }
+}
+
+/// Batch loads task counts by status for multiple sessions.
+/// Sessions without tasks have no entry.
+pub struct SessionTaskCountsLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for SessionTaskCountsLoader {
+    type Value = han_db::aggregates::SessionTaskCounts;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        han_db::aggregates::session_task_counts(&self.db, keys)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tool Result Completion Time Loader
+// ============================================================================
+
+/// Batch loads the timestamp of the message carrying each tool call's result,
+/// keyed by tool_call_id.
+pub struct ToolResultCompletedAtLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for ToolResultCompletedAtLoader {
+    type Value = String;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let times = han_db::crud::tool_call_results::get_completion_times(&self.db, keys.to_vec())
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(times.into_iter().collect())
+    }
+}
+
+/// Batch loads the tool name of each tool call by `tool_call_id`.
+pub struct ToolUseByCallIdLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for ToolUseByCallIdLoader {
+    type Value = String;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let names = han_db::crud::messages::get_tool_names(&self.db, keys.to_vec())
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(names.into_iter().collect())
+    }
+}
+
+// ============================================================================
+// MCP / Exposed Tool Result Loaders
+// ============================================================================
+
+/// Batch loads mcp_tool_result messages by call_id in raw_json.
+pub struct McpToolResultLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for McpToolResultLoader {
+    type Value = messages::Model;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        load_results_by_call_id(&self.db, keys, "mcp_tool_result").await
+    }
+}
+
+/// Batch loads exposed_tool_result messages by call_id in raw_json.
+pub struct ExposedToolResultLoader {
+    pub db: DatabaseConnection,
+}
+
+impl Loader<String> for ExposedToolResultLoader {
     type Value = messages::Model;
     type Error = async_graphql::Error;
 
     async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
-        let results = han_db::crud::messages::find_results_by_call_ids(
-            &self.db,
-            keys.to_vec(),
-            &["mcp_tool_result", "exposed_tool_result"],
-        )
+        load_results_by_call_id(&self.db, keys, "exposed_tool_result").await
+    }
+}
+
+/// Fetch `tool_name` result messages in one query, keyed by `$.data.call_id`.
+/// Each kind gets its own loader so an MCP call never picks up an exposed
+/// tool's result that happens to share its call_id.
+async fn load_results_by_call_id(
+    db: &DatabaseConnection,
+    keys: &[String],
+    tool_name: &str,
+) -> Result<HashMap<String, messages::Model>, async_graphql::Error> {
+    let results = han_db::crud::messages::find_results_by_call_ids(db, keys.to_vec(), &[tool_name])
         .await
         .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
-        let mut map: HashMap<String, messages::Model> = HashMap::new();
-        for msg in results {
-            if let Some(call_id) = extract_data_field(&msg.raw_json, "call_id") {
-                map.entry(call_id).or_insert(msg);
-            }
+    let mut map: HashMap<String, messages::Model> = HashMap::new();
+    for msg in results {
+        if let Some(call_id) = extract_data_field(&msg.raw_json, "call_id") {
+            map.entry(call_id).or_insert(msg);
         }
-
-        Ok(map)
     }
+
+    Ok(map)
 }
 
 // ============================================================================
@@ -402,9 +829,26 @@ pub struct HanLoaders {
     pub session_native_tasks: DataLoader<SessionNativeTasksLoader>,
     pub session_tasks: DataLoader<SessionTasksLoader>,
     pub session_file_changes: DataLoader<SessionFileChangesLoader>,
+    pub session_hook_count: DataLoader<SessionHookCountLoader>,
+    pub session_file_change_count: DataLoader<SessionFileChangeCountLoader>,
+    pub session_system_message_count: DataLoader<SessionSystemMessageCountLoader>,
+    pub session_tool_error_count: DataLoader<SessionToolErrorCountLoader>,
+    pub session_tool_result_count: DataLoader<SessionToolResultCountLoader>,
+    pub session_max_frustration: DataLoader<SessionMaxFrustrationLoader>,
+    pub session_by_id: DataLoader<SessionByIdLoader>,
+    pub message_by_id: DataLoader<MessageByIdLoader>,
+    pub memory: DataLoader<MemoryLoader>,
+    pub plugin: DataLoader<PluginLoader>,
+    pub project_latest_session: DataLoader<ProjectLatestSessionLoader>,
     pub session_todos: DataLoader<SessionTodosLoader>,
+    pub session_token_totals: DataLoader<SessionTokenTotalsLoader>,
+    pub session_task_counts: DataLoader<SessionTaskCountsLoader>,
+    pub session_hook_plugin_stats: DataLoader<SessionHookPluginStatsLoader>,
     pub tool_result_by_parent_id: DataLoader<ToolResultByParentIdLoader>,
-    pub tool_result_by_call_id: DataLoader<ToolResultByCallIdLoader>,
+    pub tool_result_completed_at: DataLoader<ToolResultCompletedAtLoader>,
+    pub tool_use_by_call_id: DataLoader<ToolUseByCallIdLoader>,
+    pub mcp_tool_result: DataLoader<McpToolResultLoader>,
+    pub exposed_tool_result: DataLoader<ExposedToolResultLoader>,
     pub hook_result_by_run_id: DataLoader<HookResultByRunIdLoader>,
 }
 
@@ -429,16 +873,434 @@ impl HanLoaders {
                 SessionFileChangesLoader { db: db.clone() },
                 tokio::spawn,
             ),
+            session_hook_count: DataLoader::new(
+                SessionHookCountLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_file_change_count: DataLoader::new(
+                SessionFileChangeCountLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_system_message_count: DataLoader::new(
+                SessionSystemMessageCountLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_tool_error_count: DataLoader::new(
+                SessionToolErrorCountLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_tool_result_count: DataLoader::new(
+                SessionToolResultCountLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_max_frustration: DataLoader::new(
+                SessionMaxFrustrationLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_by_id: DataLoader::new(SessionByIdLoader { db: db.clone() }, tokio::spawn),
+            message_by_id: DataLoader::new(MessageByIdLoader { db: db.clone() }, tokio::spawn),
+            memory: DataLoader::new(MemoryLoader { db: db.clone() }, tokio::spawn),
+            plugin: DataLoader::new(PluginLoader { db: db.clone() }, tokio::spawn),
+            project_latest_session: DataLoader::new(
+                ProjectLatestSessionLoader { db: db.clone() },
+                tokio::spawn,
+            ),
             session_todos: DataLoader::new(SessionTodosLoader { db: db.clone() }, tokio::spawn),
+            session_token_totals: DataLoader::new(
+                SessionTokenTotalsLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_task_counts: DataLoader::new(
+                SessionTaskCountsLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            session_hook_plugin_stats: DataLoader::new(
+                SessionHookPluginStatsLoader { db: db.clone() },
+                tokio::spawn,
+            ),
             tool_result_by_parent_id: DataLoader::new(
                 ToolResultByParentIdLoader { db: db.clone() },
                 tokio::spawn,
             ),
-            tool_result_by_call_id: DataLoader::new(
-                ToolResultByCallIdLoader { db: db.clone() },
+            tool_result_completed_at: DataLoader::new(
+                ToolResultCompletedAtLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            tool_use_by_call_id: DataLoader::new(
+                ToolUseByCallIdLoader { db: db.clone() },
+                tokio::spawn,
+            ),
+            mcp_tool_result: DataLoader::new(McpToolResultLoader { db: db.clone() }, tokio::spawn),
+            exposed_tool_result: DataLoader::new(
+                ExposedToolResultLoader { db: db.clone() },
                 tokio::spawn,
             ),
             hook_result_by_run_id: DataLoader::new(HookResultByRunIdLoader { db }, tokio::spawn),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_db;
+    use crate::types::messages::ExposedToolResult;
+    use sea_orm::Set;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn han_event(line: i32, tool_name: &str, data: serde_json::Value) -> messages::ActiveModel {
+        messages::ActiveModel {
+            id: Set(format!("msg-{line}")),
+            session_id: Set("session-1".to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set("han_event".to_string()),
+            role: Set(None),
+            content: Set(None),
+            tool_name: Set(Some(tool_name.to_string())),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(Some(json!({ "type": tool_name, "data": data }).to_string())),
+            timestamp: Set("2026-04-20T10:00:00Z".to_string()),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(None),
+            output_tokens: Set(None),
+            cache_read_tokens: Set(None),
+            cache_creation_tokens: Set(None),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exposed_tool_results_batch_into_one_query() {
+        let mut db = test_db().await;
+        han_db::crud::sessions::upsert(&db, "session-1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+
+        // Even calls succeed, odd calls fail
+        let mut events: Vec<_> = (0..10)
+            .map(|i| {
+                let data = if i % 2 == 0 {
+                    json!({ "call_id": format!("call-{i}"), "success": true, "output": "ok", "duration_ms": 12 })
+                } else {
+                    json!({ "call_id": format!("call-{i}"), "success": false, "error": "boom" })
+                };
+                han_event(i, "exposed_tool_result", data)
+            })
+            .collect();
+        // An MCP result sharing a call_id must not leak into exposed tool results
+        events.push(han_event(
+            10,
+            "mcp_tool_result",
+            json!({ "call_id": "call-1", "success": true }),
+        ));
+        han_db::crud::messages::insert_batch(&db, events)
+            .await
+            .unwrap();
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let loader = Arc::new(DataLoader::new(
+            ExposedToolResultLoader { db },
+            tokio::spawn,
+        ));
+        let handles: Vec<_> = (0..10)
+            .map(|i| {
+                let loader = loader.clone();
+                tokio::spawn(async move { loader.load_one(format!("call-{i}")).await })
+            })
+            .collect();
+        let mut results = Vec::new();
+        for handle in handles {
+            let model = handle.await.unwrap().unwrap().expect("result should load");
+            results.push(ExposedToolResult::from_model(&model));
+        }
+
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+
+        assert_eq!(results[0].success, Some(true));
+        assert_eq!(results[0].output.as_deref(), Some("ok"));
+        assert_eq!(results[0].duration_ms, Some(12));
+        assert_eq!(results[1].success, Some(false));
+        assert_eq!(results[1].error.as_deref(), Some("boom"));
+        assert_eq!(results[1].output, None);
+    }
+
+    #[tokio::test]
+    async fn test_session_token_totals_batch_into_one_query() {
+        let mut db = test_db().await;
+
+        let mut rows = Vec::new();
+        for i in 0..5 {
+            let session_id = format!("session-{i}");
+            han_db::crud::sessions::upsert(&db, session_id.clone(), None, None, None, None, None)
+                .await
+                .unwrap();
+            // Session i has i + 1 assistant messages of 100 input tokens each
+            for line in 0..=i {
+                let mut row = han_event(line, "assistant", json!({}));
+                row.id = Set(format!("{session_id}-{line}"));
+                row.session_id = Set(session_id.clone());
+                row.message_type = Set("assistant".to_string());
+                row.tool_name = Set(None);
+                row.input_tokens = Set(Some(100));
+                row.output_tokens = Set(Some(10));
+                rows.push(row);
+            }
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let loader = Arc::new(DataLoader::new(
+            SessionTokenTotalsLoader { db },
+            tokio::spawn,
+        ));
+        let handles: Vec<_> = (0..6)
+            .map(|i| {
+                let loader = loader.clone();
+                tokio::spawn(async move { loader.load_one(format!("session-{i}")).await })
+            })
+            .collect();
+        let mut totals = Vec::new();
+        for handle in handles {
+            totals.push(handle.await.unwrap().unwrap());
+        }
+
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+
+        for (i, total) in totals.iter().take(5).enumerate() {
+            let total = total.as_ref().expect("session has assistant messages");
+            assert_eq!(total.input_tokens, 100 * (i as i64 + 1));
+            assert_eq!(total.output_tokens, 10 * (i as i64 + 1));
+        }
+        assert!(totals[5].is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_counts_batch_into_one_query() {
+        let mut db = test_db().await;
+
+        for i in 0..50 {
+            let session_id = format!("session-{i}");
+            han_db::crud::sessions::upsert(&db, session_id.clone(), None, None, None, None, None)
+                .await
+                .unwrap();
+            // Session i has i % 3 hook runs and one file change every fifth session
+            for _ in 0..i % 3 {
+                han_db::crud::hooks::record_execution(
+                    &db,
+                    Some(session_id.clone()),
+                    None,
+                    "Stop".to_string(),
+                    "lint".to_string(),
+                    None,
+                    None,
+                    10,
+                    0,
+                    true,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+            if i % 5 == 0 {
+                han_db::crud::file_changes::record(
+                    &db,
+                    session_id,
+                    "src/lib.rs".to_string(),
+                    "modified".to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+        }
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let loaders = Arc::new(HanLoaders::new(db.clone()));
+        let sessions = han_db::entities::sessions::Entity::find()
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 50);
+
+        let handles: Vec<_> = sessions
+            .into_iter()
+            .map(|session| {
+                let loaders = loaders.clone();
+                tokio::spawn(async move {
+                    let hooks = &loaders.session_hook_count;
+                    let count = hooks.load_one(session.id.clone()).await;
+                    (session.id, count.unwrap().unwrap())
+                })
+            })
+            .collect();
+        for handle in handles {
+            let (session_id, count) = handle.await.unwrap();
+            let i: i32 = session_id.trim_start_matches("session-").parse().unwrap();
+            assert_eq!(count, i % 3);
+        }
+
+        // One query for the sessions, one for all their hook counts
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+        let file_counts = loaders
+            .session_file_change_count
+            .load_many((0..50).map(|i| format!("session-{i}")))
+            .await
+            .unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 3);
+        assert_eq!(file_counts.len(), 50);
+        assert_eq!(file_counts["session-0"], 1);
+        assert_eq!(file_counts["session-1"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_and_messages_by_id_batch_loads() {
+        let mut db = test_db().await;
+
+        let mut rows = Vec::new();
+        for i in 0..5 {
+            let session_id = format!("session-{i}");
+            han_db::crud::sessions::upsert(&db, session_id.clone(), None, None, None, None, None)
+                .await
+                .unwrap();
+            // Session i has i + 1 messages
+            for line in 0..=i {
+                let mut row = han_event(line, "assistant", json!({}));
+                row.id = Set(format!("{session_id}-{line}"));
+                row.session_id = Set(session_id.clone());
+                row.message_type = Set("assistant".to_string());
+                row.tool_name = Set(None);
+                rows.push(row);
+            }
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let loaders = Arc::new(HanLoaders::new(db.clone()));
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let loaders = loaders.clone();
+                tokio::spawn(async move {
+                    let sessions = &loaders.session_by_id;
+                    sessions.load_one(format!("session-{i}")).await
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            let session = handle.await.unwrap().unwrap().unwrap();
+            assert_eq!(session.session_id, format!("session-{i}"));
+            assert_eq!(session.message_count, i as i32 + 1);
+        }
+
+        // Sessions, message stats and summaries; no projects to look up
+        assert_eq!(queries.load(Ordering::SeqCst), 3);
+
+        let messages = loaders
+            .message_by_id
+            .load_many(["session-2-0".to_string(), "missing".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 4);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages["session-2-0"].session_id, "session-2");
+    }
+
+    #[tokio::test]
+    async fn test_plugin_loader_batches_by_name() {
+        let mut db = test_db().await;
+        for (name, version) in [("core", "1.2.0"), ("typescript", "0.4.1")] {
+            han_db::crud::plugins::upsert(
+                &db,
+                name.to_string(),
+                version.to_string(),
+                None,
+                "2026-04-30T10:00:00Z".to_string(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Hook runs share plugin names, so keys repeat
+        let loader = Arc::new(DataLoader::new(PluginLoader { db }, tokio::spawn));
+        let names = ["core", "typescript", "core", "uninstalled", "typescript"];
+        let handles: Vec<_> = names
+            .iter()
+            .map(|name| {
+                let loader = loader.clone();
+                let name = name.to_string();
+                tokio::spawn(async move { loader.load_one(name).await })
+            })
+            .collect();
+        let mut versions = Vec::new();
+        for handle in handles {
+            let plugin = handle.await.unwrap().unwrap();
+            versions.push(plugin.map(|p| p.version));
+        }
+
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            versions,
+            [
+                Some("1.2.0".to_string()),
+                Some("0.4.1".to_string()),
+                Some("1.2.0".to_string()),
+                None,
+                Some("0.4.1".to_string()),
+            ]
+        );
+    }
+}
@@ -1,9 +1,18 @@
 //! GraphQL Mutation root.
 
 use async_graphql::*;
-use sea_orm::DatabaseConnection;
+use chrono::{DateTime, Utc};
+use han_db::entities::{hook_executions, sessions, tasks};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use std::time::Instant;
+use tokio::sync::broadcast;
 
-use crate::types::enums::PluginScope;
+use crate::context::{
+    DbChangeEvent, ReindexRequest, ReindexStats, ReindexThrottle, SessionReindexer,
+};
+use crate::types::enums::{PluginScope, TaskOutcome, TaskType};
+use crate::types::hook_execution::HookExecution;
+use crate::types::metrics::Task;
 
 /// Result of a plugin mutation.
 #[derive(Debug, Clone, SimpleObject)]
@@ -25,6 +34,214 @@ pub struct MemoryQueryStartResult {
     pub message: Option<String>,
 }
 
+/// Result of re-indexing a session.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ReindexResult {
+    /// Messages written by the re-index.
+    pub messages_indexed: Option<i32>,
+    /// Stored messages left untouched because their lines were unchanged.
+    pub messages_skipped: Option<i32>,
+    /// Wall-clock time spent re-indexing.
+    pub duration_ms: Option<i32>,
+    /// Errors encountered while re-indexing.
+    pub errors: Option<Vec<String>>,
+}
+
+/// Result of deleting a session.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DeleteResult {
+    /// Whether the session was deleted.
+    pub success: Option<bool>,
+    /// Messages removed along with the session.
+    pub deleted_message_count: Option<i32>,
+}
+
+/// Result of archiving stale sessions.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ArchiveResult {
+    /// Sessions moved to the archive.
+    pub archived_count: Option<i32>,
+}
+
+/// A task recorded by hand rather than through the MCP tools.
+#[derive(Debug, Clone, InputObject)]
+pub struct RecordTaskInput {
+    pub session_id: String,
+    pub description: String,
+    pub task_type: TaskType,
+    /// RFC 3339 start time. Defaults to now.
+    pub started_at: Option<String>,
+    /// RFC 3339 completion time. Leave unset for a task still in progress.
+    pub completed_at: Option<String>,
+    pub outcome: Option<TaskOutcome>,
+    /// Between 0.0 and 1.0.
+    pub confidence: Option<f64>,
+    pub notes: Option<String>,
+    pub files_modified: Option<Vec<String>>,
+}
+
+impl RecordTaskInput {
+    /// Validate the input and build the row to insert under a new task ID.
+    fn into_model(self, now: DateTime<Utc>) -> Result<tasks::Model> {
+        validate_confidence(self.confidence)?;
+        let started_at = match &self.started_at {
+            Some(s) => parse_task_time("startedAt", s)?,
+            None => now,
+        };
+        let completed_at = match &self.completed_at {
+            Some(s) => Some(parse_task_time("completedAt", s)?),
+            None => None,
+        };
+        if completed_at.is_some_and(|end| end < started_at) {
+            return Err(Error::new("completedAt must not be before startedAt"));
+        }
+
+        let files_modified = self
+            .files_modified
+            .map(|files| serde_json::to_string(&files))
+            .transpose()
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        Ok(tasks::Model {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: Some(self.session_id),
+            task_id: uuid::Uuid::new_v4().to_string(),
+            description: self.description,
+            task_type: self.task_type.as_str().to_string(),
+            outcome: self.outcome.map(|o| o.as_str().to_string()),
+            confidence: self.confidence,
+            notes: self.notes,
+            files_modified,
+            tests_added: None,
+            started_at: started_at.to_rfc3339(),
+            completed_at: completed_at.map(|t| t.to_rfc3339()),
+        })
+    }
+}
+
+fn validate_confidence(confidence: Option<f64>) -> Result<()> {
+    match confidence {
+        Some(c) if !(0.0..=1.0).contains(&c) => {
+            Err(Error::new("confidence must be between 0.0 and 1.0"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn parse_task_time(field: &str, value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| Error::new(format!("{field} is not an RFC 3339 timestamp: {e}")))
+}
+
+/// Result of a bulk import.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct BulkCreateResult {
+    pub created: Option<i32>,
+    /// Inputs not recorded. A bulk import is all or nothing, so this is
+    /// every input once any is rejected.
+    pub failed: Option<i32>,
+    /// One message per rejected input, prefixed with its index.
+    pub errors: Option<Vec<String>>,
+}
+
+/// A hook run recorded outside the coordinator, e.g. by a CI pipeline.
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateHookExecutionInput {
+    pub session_id: String,
+    pub hook_type: String,
+    pub plugin_name: String,
+    pub hook_name: String,
+    pub passed: bool,
+    pub duration_ms: i32,
+    /// Defaults to 0 for a passing run and 1 otherwise.
+    pub exit_code: Option<i32>,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    /// RFC 3339 time of the run. Defaults to now.
+    pub executed_at: Option<String>,
+}
+
+impl CreateHookExecutionInput {
+    /// Validate the input and build the row to insert under a new ID.
+    fn into_model(self, now: DateTime<Utc>) -> Result<hook_executions::Model> {
+        if self.duration_ms < 0 {
+            return Err(Error::new("durationMs must not be negative"));
+        }
+        let executed_at = match &self.executed_at {
+            Some(s) => parse_task_time("executedAt", s)?,
+            None => now,
+        };
+
+        Ok(hook_executions::Model {
+            id: uuid::Uuid::new_v4().to_string(),
+            orchestration_id: None,
+            session_id: Some(self.session_id),
+            task_id: None,
+            hook_type: self.hook_type,
+            hook_name: self.hook_name,
+            hook_source: Some(self.plugin_name),
+            directory: None,
+            duration_ms: self.duration_ms,
+            exit_code: self.exit_code.unwrap_or(if self.passed { 0 } else { 1 }),
+            passed: self.passed as i32,
+            output: self.output,
+            error: self.error,
+            if_changed: None,
+            command: None,
+            executed_at: executed_at.to_rfc3339(),
+            status: Some("completed".to_string()),
+            consecutive_failures: None,
+            max_attempts: None,
+            pid: None,
+            plugin_root: None,
+        })
+    }
+}
+
+/// Validate each input and check its session exists, in one query.
+async fn prepare_hook_executions(
+    db: &DatabaseConnection,
+    inputs: Vec<CreateHookExecutionInput>,
+    now: DateTime<Utc>,
+) -> Result<Vec<Result<hook_executions::Model>>> {
+    let session_ids: Vec<String> = inputs.iter().map(|i| i.session_id.clone()).collect();
+    let known: std::collections::HashSet<String> = sessions::Entity::find()
+        .select_only()
+        .column(sessions::Column::Id)
+        .filter(sessions::Column::Id.is_in(session_ids))
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?
+        .into_iter()
+        .collect();
+
+    Ok(inputs
+        .into_iter()
+        .map(|input| {
+            if !known.contains(&input.session_id) {
+                return Err(Error::new(format!(
+                    "Session not found: {}",
+                    input.session_id
+                )));
+            }
+            input.into_model(now)
+        })
+        .collect())
+}
+
+fn broadcast_hook_executions(ctx: &Context<'_>, models: &[hook_executions::Model]) -> Result<()> {
+    let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+    for model in models {
+        let _ = sender.send(DbChangeEvent::HookExecutionCreated {
+            session_id: model.session_id.clone().unwrap_or_default(),
+            hook_execution_id: model.id.clone(),
+        });
+    }
+    Ok(())
+}
+
 /// Mutation root type.
 pub struct MutationRoot;
 
@@ -90,6 +307,263 @@ impl MutationRoot {
         let _db = ctx.data::<DatabaseConnection>()?;
         Ok(true)
     }
+
+    /// Re-index a session's transcript after it changed on disk.
+    ///
+    /// By default only lines from the first changed one onwards are
+    /// re-indexed; `force` rebuilds every message. A session can be
+    /// re-indexed at most once every 10 seconds.
+    async fn reindex_session(
+        &self,
+        ctx: &Context<'_>,
+        session_id: String,
+        force: Option<bool>,
+    ) -> Result<ReindexResult> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let reindexer = ctx
+            .data_opt::<SessionReindexer>()
+            .ok_or_else(|| Error::new("Re-indexing is not available on this server"))?;
+
+        let session = han_db::crud::sessions::get(db, &session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+            .ok_or_else(|| Error::new(format!("Session not found: {session_id}")))?;
+        let transcript_path = session
+            .transcript_path
+            .ok_or_else(|| Error::new(format!("Session {session_id} has no transcript file")))?;
+
+        let throttle = ctx.data::<ReindexThrottle>()?;
+        if !throttle.try_start(&session_id, Instant::now()) {
+            return Err(Error::new(format!(
+                "Session {session_id} was re-indexed less than {}s ago",
+                ReindexThrottle::INTERVAL.as_secs()
+            )));
+        }
+
+        let started = Instant::now();
+        let stats = reindexer(ReindexRequest {
+            transcript_path,
+            source_config_dir: session.source_config_dir,
+            force: force.unwrap_or(false),
+        })
+        .await
+        .unwrap_or_else(|e| {
+            throttle.release(&session_id);
+            ReindexStats {
+                errors: vec![e],
+                ..Default::default()
+            }
+        });
+        let duration_ms = started.elapsed().as_millis() as i32;
+
+        let _ = ctx
+            .data::<broadcast::Sender<DbChangeEvent>>()?
+            .send(DbChangeEvent::SessionUpdated { session_id });
+
+        Ok(ReindexResult {
+            messages_indexed: Some(stats.messages_indexed as i32),
+            messages_skipped: Some(stats.messages_skipped as i32),
+            duration_ms: Some(duration_ms),
+            errors: Some(stats.errors),
+        })
+    }
+
+    /// Permanently delete a session and all of its data.
+    ///
+    /// `confirm` must be `true`; this cannot be undone.
+    async fn delete_session(
+        &self,
+        ctx: &Context<'_>,
+        session_id: String,
+        confirm: bool,
+    ) -> Result<DeleteResult> {
+        if !confirm {
+            return Err(Error::new("Deleting a session requires confirm: true"));
+        }
+        let db = ctx.data::<DatabaseConnection>()?;
+
+        han_db::crud::sessions::get(db, &session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+            .ok_or_else(|| Error::new(format!("Session not found: {session_id}")))?;
+        let message_count = han_db::crud::messages::get_count(db, &session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        han_db::crud::delete_session_cascade(db, &session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        let _ = ctx
+            .data::<broadcast::Sender<DbChangeEvent>>()?
+            .send(DbChangeEvent::SessionUpdated { session_id });
+
+        Ok(DeleteResult {
+            success: Some(true),
+            deleted_message_count: Some(message_count as i32),
+        })
+    }
+
+    /// Move sessions with no messages in the last `older_than_days` days, and
+    /// their messages, into the archive tables.
+    async fn archive_sessions(
+        &self,
+        ctx: &Context<'_>,
+        older_than_days: i32,
+    ) -> Result<ArchiveResult> {
+        let days = u32::try_from(older_than_days)
+            .ok()
+            .filter(|d| *d > 0)
+            .ok_or_else(|| Error::new("olderThanDays must be at least 1"))?;
+        let db = ctx.data::<DatabaseConnection>()?;
+
+        let archived = han_db::crud::archive_stale_sessions(db, days)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        for session_id in &archived {
+            let _ = sender.send(DbChangeEvent::SessionUpdated {
+                session_id: session_id.clone(),
+            });
+        }
+
+        Ok(ArchiveResult {
+            archived_count: Some(archived.len() as i32),
+        })
+    }
+
+    /// Record a task for a session by hand.
+    async fn record_task(&self, ctx: &Context<'_>, input: RecordTaskInput) -> Result<Task> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        han_db::crud::sessions::get(db, &input.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+            .ok_or_else(|| Error::new(format!("Session not found: {}", input.session_id)))?;
+
+        let model = input.into_model(Utc::now())?;
+        let task = han_db::crud::tasks::insert(db, model)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        if let Some(session_id) = &task.session_id {
+            han_db::crud::session_timelines::invalidate_task_timeline(db, session_id)
+                .await
+                .map_err(|e| Error::new(e.to_string()))?;
+        }
+
+        let _ = ctx
+            .data::<broadcast::Sender<DbChangeEvent>>()?
+            .send(DbChangeEvent::TaskCreated {
+                session_id: task.session_id.clone(),
+                task_id: task.task_id.clone(),
+            });
+
+        Ok(Task::from(task))
+    }
+
+    /// Finish an in-progress task.
+    ///
+    /// `confidence` is left as recorded when omitted.
+    async fn complete_task(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        outcome: TaskOutcome,
+        confidence: Option<f64>,
+    ) -> Result<Task> {
+        validate_confidence(confidence)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+
+        let existing = han_db::crud::tasks::get(db, &task_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+            .ok_or_else(|| Error::new(format!("Task not found: {task_id}")))?;
+        if existing.completed_at.is_some() {
+            return Err(Error::new(format!("Task {task_id} is already completed")));
+        }
+
+        let task = han_db::crud::tasks::set_outcome(
+            db,
+            &task_id,
+            outcome.as_str().to_string(),
+            confidence,
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?
+        .ok_or_else(|| Error::new(format!("Task not found: {task_id}")))?;
+        if let Some(session_id) = &task.session_id {
+            han_db::crud::session_timelines::invalidate_task_timeline(db, session_id)
+                .await
+                .map_err(|e| Error::new(e.to_string()))?;
+        }
+
+        let _ = ctx
+            .data::<broadcast::Sender<DbChangeEvent>>()?
+            .send(DbChangeEvent::NodeUpdated {
+                id: crate::node::encode_global_id("Task", &task.task_id).to_string(),
+                typename: "Task".to_string(),
+            });
+
+        Ok(Task::from(task))
+    }
+
+    /// Record a hook run that happened outside the coordinator.
+    async fn create_hook_execution(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateHookExecutionInput,
+    ) -> Result<HookExecution> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let model = prepare_hook_executions(db, vec![input], Utc::now())
+            .await?
+            .remove(0)?;
+        han_db::crud::hooks::insert_executions(db, vec![model.clone()])
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        broadcast_hook_executions(ctx, std::slice::from_ref(&model))?;
+        Ok(HookExecution::from(model))
+    }
+
+    /// Record many hook runs at once, e.g. from a CI pipeline.
+    ///
+    /// All or nothing: if any input is invalid, none are recorded and every
+    /// rejected input is reported.
+    async fn bulk_create_hook_executions(
+        &self,
+        ctx: &Context<'_>,
+        executions: Vec<CreateHookExecutionInput>,
+    ) -> Result<BulkCreateResult> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let total = executions.len() as i32;
+        let mut models = Vec::new();
+        let mut errors = Vec::new();
+        let prepared = prepare_hook_executions(db, executions, Utc::now()).await?;
+        for (i, result) in prepared.into_iter().enumerate() {
+            match result {
+                Ok(model) => models.push(model),
+                Err(e) => errors.push(format!("[{i}] {}", e.message)),
+            }
+        }
+        if !errors.is_empty() {
+            return Ok(BulkCreateResult {
+                created: Some(0),
+                failed: Some(total),
+                errors: Some(errors),
+            });
+        }
+
+        let created = han_db::crud::hooks::insert_executions(db, models.clone())
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+
+        broadcast_hook_executions(ctx, &models)?;
+        Ok(BulkCreateResult {
+            created: Some(created as i32),
+            failed: Some(0),
+            errors: Some(vec![]),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +585,79 @@ mod tests {
         assert_eq!(r.message, Some("ok".into()));
     }
 
+    fn record_input() -> RecordTaskInput {
+        RecordTaskInput {
+            session_id: "s1".into(),
+            description: "Write docs".into(),
+            task_type: TaskType::Implementation,
+            started_at: Some("2026-04-20T09:00:00+02:00".into()),
+            completed_at: Some("2026-04-20T08:30:00Z".into()),
+            outcome: Some(TaskOutcome::Partial),
+            confidence: Some(0.7),
+            notes: None,
+            files_modified: Some(vec!["README.md".into()]),
+        }
+    }
+
+    #[test]
+    fn record_task_input_into_model() {
+        let now = Utc::now();
+        let model = record_input().into_model(now).unwrap();
+        assert_eq!(model.session_id.as_deref(), Some("s1"));
+        assert_eq!(model.task_type, "implementation");
+        assert_eq!(model.outcome.as_deref(), Some("partial"));
+        assert_eq!(model.started_at, "2026-04-20T07:00:00+00:00");
+        assert_eq!(
+            model.completed_at.as_deref(),
+            Some("2026-04-20T08:30:00+00:00")
+        );
+        assert_eq!(model.files_modified.as_deref(), Some(r#"["README.md"]"#));
+        assert!(uuid::Uuid::parse_str(&model.task_id).is_ok());
+
+        let model = RecordTaskInput {
+            started_at: None,
+            completed_at: None,
+            ..record_input()
+        }
+        .into_model(now)
+        .unwrap();
+        assert_eq!(model.started_at, now.to_rfc3339());
+        assert!(model.completed_at.is_none());
+    }
+
+    #[test]
+    fn record_task_input_validation() {
+        let now = Utc::now();
+        let err = RecordTaskInput {
+            confidence: Some(1.5),
+            ..record_input()
+        }
+        .into_model(now)
+        .unwrap_err();
+        assert!(err.message.contains("confidence"));
+
+        let err = RecordTaskInput {
+            completed_at: Some("2026-04-20T06:59:59Z".into()),
+            ..record_input()
+        }
+        .into_model(now)
+        .unwrap_err();
+        assert!(err.message.contains("before startedAt"));
+
+        let err = RecordTaskInput {
+            started_at: Some("yesterday".into()),
+            ..record_input()
+        }
+        .into_model(now)
+        .unwrap_err();
+        assert!(err.message.contains("startedAt"));
+
+        assert!(validate_confidence(Some(-0.1)).is_err());
+        assert!(validate_confidence(Some(f64::NAN)).is_err());
+        assert!(validate_confidence(Some(0.0)).is_ok());
+        assert!(validate_confidence(None).is_ok());
+    }
+
     #[test]
     fn memory_query_start_result_fields() {
         let r = MemoryQueryStartResult {
@@ -122,4 +669,139 @@ mod tests {
         assert_eq!(r.success, Some(true));
         assert!(r.message.is_none());
     }
+
+    fn hook_input(session_id: &str) -> CreateHookExecutionInput {
+        CreateHookExecutionInput {
+            session_id: session_id.into(),
+            hook_type: "Stop".into(),
+            plugin_name: "validation/biome".into(),
+            hook_name: "lint".into(),
+            passed: false,
+            duration_ms: 1200,
+            exit_code: None,
+            output: Some("2 errors".into()),
+            error: None,
+            executed_at: Some("2026-04-20T09:00:00+02:00".into()),
+        }
+    }
+
+    #[test]
+    fn create_hook_execution_input_into_model() {
+        let model = hook_input("s1").into_model(Utc::now()).unwrap();
+        assert_eq!(model.session_id.as_deref(), Some("s1"));
+        assert_eq!(model.hook_source.as_deref(), Some("validation/biome"));
+        assert_eq!(model.exit_code, 1);
+        assert_eq!(model.passed, 0);
+        assert_eq!(model.executed_at, "2026-04-20T07:00:00+00:00");
+        assert!(uuid::Uuid::parse_str(&model.id).is_ok());
+
+        let err = CreateHookExecutionInput {
+            duration_ms: -1,
+            ..hook_input("s1")
+        }
+        .into_model(Utc::now())
+        .unwrap_err();
+        assert!(err.message.contains("durationMs"));
+    }
+
+    #[tokio::test]
+    async fn create_hook_executions_over_graphql() {
+        let db = crate::test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let (tx, mut rx) = broadcast::channel(16);
+        let schema = crate::schema::build_schema(db.clone(), tx);
+        let count = || han_db::entities::hook_executions::Entity::find().all(&db);
+
+        let create = |session_id: &str| {
+            format!(
+                r#"mutation {{ createHookExecution(input: {{
+                    sessionId: "{session_id}", hookType: "Stop", pluginName: "ci",
+                    hookName: "test", passed: true, durationMs: 30
+                }}) {{ hookName exitCode passed }} }}"#
+            )
+        };
+        let res = schema.execute(create("s1")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["createHookExecution"]["exitCode"], 0);
+        assert_eq!(data["createHookExecution"]["passed"], true);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(DbChangeEvent::HookExecutionCreated { session_id, .. }) if session_id == "s1"
+        ));
+
+        let res = schema.execute(create("missing")).await;
+        assert_eq!(res.errors[0].message, "Session not found: missing");
+        assert_eq!(count().await.unwrap().len(), 1);
+
+        // One bad input rejects the whole batch
+        let bulk = |second: &str| {
+            format!(
+                r#"mutation {{ bulkCreateHookExecutions(executions: [
+                    {{ sessionId: "s1", hookType: "Stop", pluginName: "ci",
+                       hookName: "a", passed: true, durationMs: 1 }},
+                    {{ sessionId: "{second}", hookType: "Stop", pluginName: "ci",
+                       hookName: "b", passed: false, durationMs: 2, exitCode: 3 }}
+                ]) {{ created failed errors }} }}"#
+            )
+        };
+        let res = schema.execute(bulk("missing")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["bulkCreateHookExecutions"]["created"], 0);
+        assert_eq!(data["bulkCreateHookExecutions"]["failed"], 2);
+        assert_eq!(
+            data["bulkCreateHookExecutions"]["errors"],
+            serde_json::json!(["[1] Session not found: missing"])
+        );
+        assert_eq!(count().await.unwrap().len(), 1);
+        assert!(rx.try_recv().is_err());
+
+        let res = schema.execute(bulk("s1")).await;
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["bulkCreateHookExecutions"]["created"], 2);
+        assert_eq!(data["bulkCreateHookExecutions"]["failed"], 0);
+        let rows = count().await.unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().any(|r| r.hook_name == "b" && r.exit_code == 3));
+        assert!(rx.try_recv().is_ok() && rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn archive_sessions_over_graphql() {
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let message = han_db::entities::messages::ActiveModel {
+            id: Set("m1".to_string()),
+            session_id: Set("s1".to_string()),
+            message_type: Set("user".to_string()),
+            timestamp: Set("2020-01-01T00:00:00Z".to_string()),
+            line_number: Set(1),
+            ..Default::default()
+        };
+        han_db::crud::messages::insert_batch(&db, vec![message])
+            .await
+            .unwrap();
+        let (tx, _rx) = broadcast::channel(16);
+        let schema = crate::schema::build_schema(db.clone(), tx);
+        let archive = |days: i32| {
+            format!("mutation {{ archiveSessions(olderThanDays: {days}) {{ archivedCount }} }}")
+        };
+
+        let res = schema.execute(archive(0)).await;
+        assert_eq!(res.errors[0].message, "olderThanDays must be at least 1");
+
+        let res = schema.execute(archive(30)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["archiveSessions"]["archivedCount"], 1);
+        let session = han_db::crud::sessions::get(&db, "s1").await.unwrap();
+        assert!(session.is_none());
+    }
 }
@@ -6,11 +6,13 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::Instant;
 
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::Datelike;
+use sea_orm::sea_query::{Expr, SimpleExpr};
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Statement,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Statement,
 };
 
 // TTL cache for expensive dashboard analytics queries.
@@ -21,6 +23,8 @@ static ANALYTICS_CACHE: std::sync::LazyLock<
 
 use han_db::entities::{config_dirs, hook_executions, native_tasks, projects, repos, sessions};
 
+use crate::context::{HookDryRunner, HookLister, WatchedDirectoryLister};
+use crate::loaders::ProjectLatestSessionLoader;
 use crate::node::decode_global_id;
 use crate::types::config_dir::ConfigDir;
 use crate::types::dashboard::{
@@ -30,12 +34,17 @@ use crate::types::dashboard::{
     ModelTokenEntry, ModelUsageStats, SessionCost, SessionPerformancePoint, StatsCache,
     TokenUsageStats, ToolTimeEstimate, ToolUsageStats, WeeklyCost,
 };
-use crate::types::enums::MetricsPeriod;
+use crate::types::discovered_hook::DiscoveredHookInfo;
+use crate::types::enums::{MetricsPeriod, ModelVersion};
+use crate::types::frustration::SessionFrustrationFilter;
+use crate::types::hook_dry_run::HookDryRunResult;
 use crate::types::metrics::{MetricsData, TaskOutcomeCount, TaskTypeCount};
-use crate::types::plugin::{Plugin, PluginCategory, PluginStats};
-use crate::types::project::Project;
+use crate::types::plugin::{Plugin, PluginCategory, PluginInfo, PluginStats};
+use crate::types::project::{Project, ProjectStats, ProjectWithStats};
 use crate::types::repo::Repo;
-use crate::types::sessions::{build_session_connection, SessionConnection, SessionData};
+use crate::types::sessions::{
+    build_session_connection, SessionConnection, SessionData, SessionSortInput,
+};
 
 // ============================================================================
 // Raw query result types for enrichment
@@ -350,6 +359,164 @@ impl QueryRoot {
         Ok(Some(crate::types::messages::discriminate_message(data)))
     }
 
+    /// Search messages across all sessions, ranked by weighted FTS and vector relevance.
+    ///
+    /// No vector index is available locally, so the vector component is
+    /// currently empty and only `ftsWeight` affects the ranking.
+    async fn hybrid_search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        fts_weight: Option<f64>,
+        vector_weight: Option<f64>,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::types::search_result::MessageSearchResult>> {
+        let fts_weight = search_weight("ftsWeight", fts_weight)?;
+        let vector_weight = search_weight("vectorWeight", vector_weight)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let results = han_db::search::hybrid_search(
+            db,
+            &query,
+            &[],
+            fts_weight,
+            vector_weight,
+            limit.unwrap_or(20).clamp(1, 100) as u32,
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    /// Full-text search over message content, optionally within one session.
+    async fn search_messages(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        session_id: Option<String>,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::types::search_result::MessageSearchResult>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        crate::types::search_result::search_messages(db, &query, session_id.as_deref(), limit).await
+    }
+
+    /// Search sessions by topic (summary, project name, first prompt).
+    ///
+    /// Each word matches as a prefix, so partial words find sessions too.
+    async fn search_sessions(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::types::search_result::SessionSearchResult>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let results = han_db::search::fts_search_sessions(
+            db,
+            &query,
+            limit.unwrap_or(20).clamp(1, 100) as usize,
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    /// Tool calls across all sessions grouped by tool name, most used first.
+    ///
+    /// `since` is inclusive, compared against each call's timestamp.
+    async fn global_tool_usage_stats(
+        &self,
+        ctx: &Context<'_>,
+        since: String,
+    ) -> Result<Vec<crate::types::dashboard::ToolUsageStat>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_tool_call_stats(db, None, Some(&since))
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Most-used MCP server tools across all sessions.
+    async fn top_mcp_servers(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::types::dashboard::McpServerUsage>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_mcp_server_usage(
+            db,
+            None,
+            Some(limit.unwrap_or(10).clamp(1, 100) as u64),
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Hook execution metrics across sessions.
+    ///
+    /// `projectDir` includes executions in subdirectories. `since` is
+    /// inclusive and `until` exclusive, compared against `executedAt`.
+    async fn hook_execution_stats(
+        &self,
+        ctx: &Context<'_>,
+        project_dir: Option<String>,
+        since: Option<String>,
+        until: Option<String>,
+    ) -> Result<crate::types::hook_execution::HookExecutionStats> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_hook_execution_stats(
+            db,
+            project_dir.as_deref(),
+            since.as_deref(),
+            until.as_deref(),
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into())
+    }
+
+    /// Plugins with the most hook executions across sessions, busiest first.
+    ///
+    /// `since` is inclusive, compared against `executedAt`. `limit` defaults
+    /// to 10, at most 100.
+    async fn top_hook_plugins(
+        &self,
+        ctx: &Context<'_>,
+        since: Option<String>,
+        limit: Option<i32>,
+    ) -> Result<Vec<crate::types::hook_execution::HookPluginStat>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::top_hook_plugins(
+            db,
+            since.as_deref(),
+            limit.unwrap_or(10).clamp(1, 100) as u64,
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Sessions, messages, tokens and hook results for each of the last
+    /// `weeks` complete weeks (default 12, at most 104), oldest first.
+    ///
+    /// Sessions count toward the week of their first message. `projectDir`
+    /// also matches worktrees nested under that directory.
+    async fn weekly_activity(
+        &self,
+        ctx: &Context<'_>,
+        project_dir: String,
+        weeks: Option<i32>,
+    ) -> Result<Vec<crate::types::dashboard::WeeklySummary>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::weekly_summary(
+            db,
+            Some(&project_dir),
+            weeks.unwrap_or(12).clamp(1, 104) as u32,
+        )
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     /// Memory query interface (stub for browse-client compat).
     async fn memory(&self) -> Option<crate::types::settings::MemoryQueryType> {
         Some(crate::types::settings::MemoryQueryType)
@@ -422,6 +589,45 @@ impl QueryRoot {
         Ok(model.map(Project::from))
     }
 
+    /// Aggregated metrics for the project at `projectDir`, or null if no
+    /// project has that path. Served from the project stats cache.
+    async fn project_stats(
+        &self,
+        ctx: &Context<'_>,
+        project_dir: String,
+    ) -> Result<Option<ProjectStats>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let Some(project) = han_db::crud::projects::get_by_path(db, &project_dir)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let stats = han_db::crud::project_stats::get_or_refresh(db, &project.id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(Some(stats.into()))
+    }
+
+    /// Every project with its metrics, most recently active first.
+    async fn all_project_stats(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ProjectWithStats>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::crud::project_stats::list(db, limit.map(|l| l.max(0) as u64))
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(project, stats)| ProjectWithStats {
+                project: Project::from(project),
+                stats: stats.into(),
+            })
+            .collect())
+    }
+
     /// All git repositories with sessions.
     async fn repos(
         &self,
@@ -490,6 +696,11 @@ impl QueryRoot {
     ///
     /// Filtering is done via the GreenFairy-style `filter` input type.
     /// Supports association filtering (e.g., `filter: { project: { repoId: { _eq: "..." } } }`).
+    /// `frustration` narrows by stored per-message frustration scores.
+    /// `worktreeName` keeps sessions run in a linked worktree of that name.
+    /// `sort` and then each `sortBy` key order by values derived from the
+    /// session's messages or project, ahead of `orderBy`; ties fall back to
+    /// session ID.
     async fn sessions(
         &self,
         ctx: &Context<'_>,
@@ -499,16 +710,34 @@ impl QueryRoot {
         before: Option<String>,
         filter: Option<crate::types::sessions::SessionFilter>,
         order_by: Option<crate::types::sessions::SessionOrderBy>,
+        model_version: Option<ModelVersion>,
+        frustration: Option<SessionFrustrationFilter>,
+        sort: Option<SessionSortInput>,
+        sort_by: Option<Vec<SessionSortInput>>,
+        worktree_name: Option<String>,
     ) -> Result<SessionConnection> {
         let db = ctx.data::<DatabaseConnection>()?;
+        let sort_keys: Vec<SessionSortInput> = sort
+            .into_iter()
+            .chain(sort_by.into_iter().flatten())
+            .collect();
 
         // Use SeaORM query builder with filter conditions
-        let mut count_query = sessions::Entity::find();
+        let mut condition = Condition::all();
         if let Some(ref f) = filter {
-            count_query = count_query.filter(f.to_condition());
+            condition = condition.add(f.to_condition());
+        }
+        if let Some(version) = model_version {
+            condition = condition.add(model_version_condition(version));
+        }
+        if let Some(ref f) = frustration {
+            condition = condition.add(f.to_condition());
         }
-        let total_count = count_query
-            .clone()
+        if let Some(name) = worktree_name {
+            condition = condition.add(worktree_name_condition(name));
+        }
+        let total_count = sessions::Entity::find()
+            .filter(condition.clone())
             .count(db)
             .await
             .map(|c| c as i32)
@@ -516,15 +745,12 @@ impl QueryRoot {
 
         let page_size = first.or(last).unwrap_or(20) as i64;
 
-        // When no explicit orderBy, use SQL-level sorting by last message timestamp
+        // When no explicit ordering, use SQL-level sorting by last message timestamp
         // to avoid loading ALL sessions just to sort by activity.
-        let mut session_data: Vec<SessionData> = if order_by.is_none() {
+        let mut session_data: Vec<SessionData> = if order_by.is_none() && sort_keys.is_empty() {
             // Fast path: get top N sessions ordered by latest message timestamp.
             // Uses idx_messages_session_ts_desc for efficient MAX(timestamp) per session.
-            let mut page_query = sessions::Entity::find();
-            if let Some(ref f) = filter {
-                page_query = page_query.filter(f.to_condition());
-            }
+            let page_query = sessions::Entity::find().filter(condition);
 
             // Get filtered session IDs, limited to a reasonable working set
             // that we can sort by activity. Cap at 200 to avoid loading thousands.
@@ -587,14 +813,17 @@ impl QueryRoot {
                 data
             }
         } else {
-            // Explicit orderBy: use SeaORM ordering with SQL-level LIMIT
-            let mut page_query = sessions::Entity::find();
-            if let Some(ref f) = filter {
-                page_query = page_query.filter(f.to_condition());
+            // Explicit sort/orderBy: use SeaORM ordering with SQL-level LIMIT
+            let mut page_query = sessions::Entity::find().filter(condition);
+            for key in &sort_keys {
+                page_query = key.apply(page_query);
             }
             if let Some(ref o) = order_by {
                 page_query = o.apply(page_query);
             }
+            if !sort_keys.is_empty() {
+                page_query = page_query.order_by_asc(sessions::Column::Id);
+            }
 
             let models = page_query
                 .limit(Some(page_size as u64))
@@ -617,6 +846,78 @@ impl QueryRoot {
         Ok(conn)
     }
 
+    /// Most recently active sessions, optionally scoped to a project.
+    ///
+    /// `projectDir` also matches worktrees nested under that directory.
+    /// Sessions without messages are skipped unless `includeEmpty` is true.
+    async fn recent_sessions(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+        project_dir: Option<String>,
+        include_empty: Option<bool>,
+    ) -> Result<Vec<SessionData>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let filter = han_db::crud::sessions::SessionFilter {
+            project_dir,
+            min_message_count: (!include_empty.unwrap_or(false)).then_some(1),
+            limit: Some(limit.unwrap_or(20).clamp(1, 100) as u32),
+            ..Default::default()
+        };
+        let models = han_db::crud::sessions::list_with_filters(db, filter)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        let mut data: Vec<SessionData> = models.into_iter().map(session_model_to_data).collect();
+        enrich_sessions(db, &mut data).await?;
+        Ok(data)
+    }
+
+    /// Most recently active session for each project directory, in the order
+    /// given. Entries are null for directories without sessions.
+    async fn latest_session_by_project(
+        &self,
+        ctx: &Context<'_>,
+        project_dirs: Vec<String>,
+    ) -> Result<Vec<Option<SessionData>>> {
+        let loader = ctx.data::<DataLoader<ProjectLatestSessionLoader>>()?;
+        let mut latest = loader.load_many(project_dirs.iter().cloned()).await?;
+        Ok(project_dirs.iter().map(|dir| latest.remove(dir)).collect())
+    }
+
+    /// Sessions with a message in the last 24 hours, most recent first.
+    ///
+    /// `first` defaults to 20, at most 100.
+    async fn active_sessions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+    ) -> Result<SessionConnection> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let first = first.unwrap_or(20).clamp(1, 100);
+        let since = (chrono::Utc::now() - chrono::Duration::hours(24))
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let filter = han_db::crud::sessions::SessionFilter {
+            after: Some(since),
+            ..Default::default()
+        };
+        let total_count = han_db::crud::sessions::count_with_filters(db, filter.clone())
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        // One extra row tells whether there is a next page
+        let page = han_db::crud::sessions::SessionFilter {
+            limit: Some(first as u32 + 1),
+            ..filter
+        };
+        let models = han_db::crud::sessions::list_with_filters(db, page)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        let mut data: Vec<SessionData> = models.into_iter().map(session_model_to_data).collect();
+        enrich_sessions(db, &mut data).await?;
+        let mut conn = build_session_connection(data, Some(first), None, None, None);
+        conn.total_count = total_count as i32;
+        Ok(conn)
+    }
+
     /// Coordinator status for version checking.
     async fn coordinator_status(&self, _client_version: Option<String>) -> CoordinatorStatus {
         CoordinatorStatus {
@@ -625,6 +926,62 @@ impl QueryRoot {
         }
     }
 
+    /// Hooks that would fire for an event, with their cache state. Nothing
+    /// is executed.
+    async fn hook_dry_run(
+        &self,
+        ctx: &Context<'_>,
+        event: String,
+        tool_name: Option<String>,
+    ) -> Result<Vec<HookDryRunResult>> {
+        let runner = ctx
+            .data_opt::<HookDryRunner>()
+            .ok_or_else(|| Error::new("Hook dry runs are not available on this server"))?;
+        Ok(runner(event, tool_name).await)
+    }
+
+    /// Hooks from installed plugins, optionally limited to one event and/or
+    /// plugin.
+    async fn discovered_hooks(
+        &self,
+        ctx: &Context<'_>,
+        event: Option<String>,
+        plugin: Option<String>,
+    ) -> Result<Vec<DiscoveredHookInfo>> {
+        let lister = ctx
+            .data_opt::<HookLister>()
+            .ok_or_else(|| Error::new("Hook discovery is not available on this server"))?;
+        Ok(lister(event, plugin).await)
+    }
+
+    /// Directories the coordinator's file watcher is watching for session
+    /// transcripts.
+    async fn watched_directories(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        let lister = ctx
+            .data_opt::<WatchedDirectoryLister>()
+            .ok_or_else(|| Error::new("File watching is not available on this server"))?;
+        Ok(lister())
+    }
+
+    /// Stripe subscription of the caller's organization (org admins only).
+    #[graphql(guard = "crate::context::OrgAdminGuard")]
+    async fn subscription(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<crate::types::team::Subscription>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let org_id = ctx
+            .data::<crate::context::GraphQLContext>()?
+            .user
+            .as_ref()
+            .and_then(|u| u.org_id.as_deref())
+            .ok_or("Forbidden: organization admin required")?;
+        let subscription = han_db::crud::subscriptions::get_subscription(db, org_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(subscription.map(Into::into))
+    }
+
     /// Team-level aggregate metrics for dashboard.
     async fn team_metrics(
         &self,
@@ -1084,6 +1441,29 @@ impl QueryRoot {
             .await
             .map_err(|e| Error::new(e.to_string()))?;
 
+        // Calibration over the sessions in scope that tracked tasks
+        let mut task_sessions_sql =
+            String::from("SELECT DISTINCT session_id FROM tasks WHERE session_id IS NOT NULL");
+        if let Some((ref scope_clause, _)) = scope {
+            task_sessions_sql.push_str(&format!(" AND {scope_clause}"));
+        }
+        let task_session_rows = db
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                task_sessions_sql,
+                scope.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>(),
+            ))
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        let task_session_ids: Vec<String> = task_session_rows
+            .iter()
+            .filter_map(|r| r.try_get("", "session_id").ok())
+            .collect();
+        let calibration_score =
+            han_db::aggregates::compute_calibration_score(db, &task_session_ids)
+                .await
+                .map_err(|e| Error::new(e.to_string()))?;
+
         let tasks_by_outcome: Vec<TaskOutcomeCount> = if let Some(r) = outcome_row {
             let success: i64 = r.try_get("", "success").unwrap_or(0);
             let partial: i64 = r.try_get("", "partial").unwrap_or(0);
@@ -1118,7 +1498,7 @@ impl QueryRoot {
             success_rate: Some(success_rate),
             average_confidence: Some(average_confidence),
             average_duration: None,
-            calibration_score: None,
+            calibration_score,
             significant_frustrations: Some(significant_frustrations),
             significant_frustration_rate: Some(frustration_rate),
             tasks_by_type: Some(tasks_by_type),
@@ -1126,6 +1506,33 @@ impl QueryRoot {
         }))
     }
 
+    /// How well task confidence predicted task success (0.0-1.0, 1.0 is
+    /// perfectly calibrated) across sessions. Null when no task has finished
+    /// with a confidence and an outcome.
+    ///
+    /// `projectDir` also matches worktrees nested under that directory.
+    /// `since` keeps sessions with a message at or after that timestamp.
+    async fn calibration_score(
+        &self,
+        ctx: &Context<'_>,
+        project_dir: Option<String>,
+        since: Option<String>,
+    ) -> Result<Option<f64>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let filter = han_db::crud::sessions::SessionFilter {
+            project_dir,
+            after: since,
+            ..Default::default()
+        };
+        let sessions = han_db::crud::sessions::list_with_filters(db, filter)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        let session_ids: Vec<String> = sessions.into_iter().map(|s| s.id).collect();
+        han_db::aggregates::compute_calibration_score(db, &session_ids)
+            .await
+            .map_err(|e| Error::new(e.to_string()))
+    }
+
     /// Installed plugins, optionally filtered by scope.
     async fn plugins(
         &self,
@@ -1134,6 +1541,15 @@ impl QueryRoot {
         Some(vec![])
     }
 
+    /// Plugins recorded in the database, by name.
+    async fn installed_plugins(&self, ctx: &Context<'_>) -> Result<Vec<PluginInfo>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let plugins = han_db::crud::plugins::list(db)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(plugins.into_iter().map(PluginInfo::from).collect())
+    }
+
     /// Aggregate plugin statistics.
     async fn plugin_stats(&self) -> Option<PluginStats> {
         // Read installed_plugins.json from ~/.claude/plugins/
@@ -2561,6 +2977,49 @@ fn load_stats_cache_model_data() -> (Vec<ModelUsageStats>, Vec<DailyModelTokens>
     (model_usage, daily_model_tokens)
 }
 
+/// Condition matching sessions with an assistant message from `version`.
+/// `Unknown` matches models outside every known family.
+fn model_version_condition(version: ModelVersion) -> SimpleExpr {
+    let model = han_db::aggregates::MESSAGE_MODEL_SQL;
+    let (matches, patterns): (String, Vec<String>) = match version.prefix() {
+        Some(prefix) => (format!("{model} LIKE ?"), vec![format!("{prefix}%")]),
+        None => {
+            let patterns: Vec<String> = ModelVersion::KNOWN
+                .iter()
+                .filter_map(|v| v.prefix())
+                .map(|p| format!("{p}%"))
+                .collect();
+            let known = vec![format!("{model} LIKE ?"); patterns.len()].join(" OR ");
+            (format!("{model} IS NOT NULL AND NOT ({known})"), patterns)
+        }
+    };
+    Expr::cust_with_values(
+        format!(
+            "sessions.id IN (SELECT session_id FROM messages \
+             WHERE message_type = 'assistant' AND {matches})"
+        ),
+        patterns,
+    )
+}
+
+/// A `hybridSearch` weight, defaulting to 0.5. Negative or NaN weights are
+/// rejected.
+fn search_weight(name: &str, weight: Option<f64>) -> Result<f64> {
+    match weight {
+        Some(w) if w.is_nan() || w < 0.0 => Err(Error::new(format!("{name} must be non-negative"))),
+        Some(w) => Ok(w),
+        None => Ok(0.5),
+    }
+}
+
+/// Sessions run in a linked worktree named `name`, in any project.
+fn worktree_name_condition(name: String) -> SimpleExpr {
+    Expr::cust_with_values(
+        "sessions.worktree_id IN (SELECT id FROM worktrees WHERE worktree_name = ?)",
+        [name],
+    )
+}
+
 /// Convert a database session model to GraphQL SessionData.
 pub fn session_model_to_data(m: sessions::Model) -> SessionData {
     SessionData {
@@ -2578,6 +3037,7 @@ pub fn session_model_to_data(m: sessions::Model) -> SessionData {
         git_branch: None,
         version: None,
         worktree_name: None,
+        worktree_id: m.worktree_id,
         source_config_dir: m.source_config_dir,
         status: m.status,
         pr_number: m.pr_number,
@@ -2602,6 +3062,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         }
     }
 
@@ -2674,6 +3135,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         };
         let sd = session_model_to_data(m);
         assert!(sd.project_id.is_none());
@@ -2681,4 +3143,173 @@ mod tests {
         assert!(sd.slug.is_none());
         assert!(sd.source_config_dir.is_none());
     }
+
+    #[test]
+    fn search_weight_rejects_negative() {
+        assert_eq!(search_weight("ftsWeight", None).unwrap(), 0.5);
+        assert_eq!(search_weight("ftsWeight", Some(0.0)).unwrap(), 0.0);
+        assert_eq!(search_weight("ftsWeight", Some(2.0)).unwrap(), 2.0);
+        assert!(search_weight("ftsWeight", Some(-0.1)).is_err());
+        assert!(search_weight("vectorWeight", Some(f64::NAN)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_model_version_condition() {
+        use han_db::entities::messages;
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+
+        let sessions_models = [
+            ("s-sonnet", Some("claude-3-5-sonnet-20241022")),
+            ("s-haiku", Some("claude-3-haiku-20240307")),
+            ("s-other", Some("gpt-4o")),
+            ("s-none", None),
+        ];
+        let mut rows = Vec::new();
+        for (line, (id, model)) in sessions_models.into_iter().enumerate() {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+            let raw = match model {
+                Some(m) => serde_json::json!({ "message": { "model": m } }),
+                None => serde_json::json!({ "message": {} }),
+            };
+            rows.push(messages::ActiveModel {
+                id: Set(format!("{id}-msg")),
+                session_id: Set(id.to_string()),
+                message_type: Set("assistant".to_string()),
+                raw_json: Set(Some(raw.to_string())),
+                timestamp: Set("2026-04-20T10:00:00Z".to_string()),
+                line_number: Set(line as i32),
+                ..Default::default()
+            });
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let matching = |version| {
+            let db = &db;
+            async move {
+                let models = sessions::Entity::find()
+                    .filter(model_version_condition(version))
+                    .all(db)
+                    .await
+                    .unwrap();
+                models.into_iter().map(|m| m.id).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(matching(ModelVersion::Claude35Sonnet).await, ["s-sonnet"]);
+        assert_eq!(matching(ModelVersion::Claude3Haiku).await, ["s-haiku"]);
+        assert!(matching(ModelVersion::Claude3Opus).await.is_empty());
+        // Messages without a model match no family, not even UNKNOWN
+        assert_eq!(matching(ModelVersion::Unknown).await, ["s-other"]);
+
+        let counts = han_db::aggregates::query_session_model_counts(&db, "s-sonnet")
+            .await
+            .unwrap();
+        assert_eq!(counts, [("claude-3-5-sonnet-20241022".to_string(), 1)]);
+        let counts = han_db::aggregates::query_session_model_counts(&db, "s-none")
+            .await
+            .unwrap();
+        assert!(counts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_worktree_name_condition() {
+        let db = crate::test_db().await;
+
+        for (id, worktree) in [
+            ("s-a", Some("alpha")),
+            ("s-b", Some("beta")),
+            ("s-main", None),
+        ] {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+            if let Some(name) = worktree {
+                let w = han_db::crud::worktrees::upsert(
+                    &db,
+                    format!("/src/han-{name}"),
+                    "/src/han".to_string(),
+                    None,
+                    name.to_string(),
+                )
+                .await
+                .unwrap();
+                han_db::crud::sessions::update_worktree(&db, id, &w.id)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let matching = |name: &str| {
+            let db = &db;
+            let condition = worktree_name_condition(name.to_string());
+            async move {
+                let models = sessions::Entity::find()
+                    .filter(condition)
+                    .all(db)
+                    .await
+                    .unwrap();
+                models.into_iter().map(|m| m.id).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(matching("alpha").await, ["s-a"]);
+        assert_eq!(matching("beta").await, ["s-b"]);
+        assert!(matching("gamma").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_active_sessions_paginates_recent_sessions() {
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+
+        // Minutes since each session's last message; s-old is past the window
+        let now = chrono::Utc::now();
+        let mut rows = Vec::new();
+        for (id, minutes_ago) in [("s-a", 5), ("s-b", 60), ("s-c", 120), ("s-old", 60 * 30)] {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+            // A non-UTC offset must still be compared as an instant
+            let at = (now - chrono::Duration::minutes(minutes_ago))
+                .with_timezone(&chrono::FixedOffset::west_opt(5 * 3600).unwrap())
+                .to_rfc3339();
+            rows.push(han_db::entities::messages::ActiveModel {
+                id: Set(format!("{id}-m1")),
+                session_id: Set(id.to_string()),
+                message_type: Set("user".to_string()),
+                timestamp: Set(at),
+                line_number: Set(1),
+                ..Default::default()
+            });
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let res = schema
+            .execute(
+                "{ activeSessions(first: 2) { totalCount pageInfo { hasNextPage } \
+                 edges { node { sessionId } } } }",
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let json = res.data.into_json().unwrap();
+        let active = &json["activeSessions"];
+        assert_eq!(active["totalCount"], 3);
+        assert_eq!(active["pageInfo"]["hasNextPage"], true);
+        let ids: Vec<_> = active["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["node"]["sessionId"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["s-a", "s-b"]);
+    }
 }
@@ -6,9 +6,17 @@ use tokio::sync::broadcast;
 
 use async_graphql::dataloader::DataLoader;
 
-use crate::context::DbChangeEvent;
+use crate::context::{
+    DbChangeEvent, HookDryRunner, HookLister, ReindexThrottle, SessionReindexer,
+    WatchedDirectoryLister,
+};
 use crate::loaders::{
-    HookResultByRunIdLoader, ToolResultByCallIdLoader, ToolResultByParentIdLoader,
+    ExposedToolResultLoader, HookResultByRunIdLoader, McpToolResultLoader, MemoryLoader,
+    MessageByIdLoader, PluginLoader, ProjectLatestSessionLoader, SessionByIdLoader,
+    SessionFileChangeCountLoader, SessionHookCountLoader, SessionHookPluginStatsLoader,
+    SessionMaxFrustrationLoader, SessionSystemMessageCountLoader, SessionTaskCountsLoader,
+    SessionTokenTotalsLoader, SessionToolErrorCountLoader, SessionToolResultCountLoader,
+    ToolResultByParentIdLoader, ToolResultCompletedAtLoader, ToolUseByCallIdLoader,
 };
 use crate::mutation::MutationRoot;
 use crate::query::QueryRoot;
@@ -22,26 +30,107 @@ pub fn build_schema(
     db: DatabaseConnection,
     event_sender: broadcast::Sender<DbChangeEvent>,
 ) -> HanSchema {
+    schema_builder(db, event_sender).finish()
+}
+
+/// Build the schema with every host-provided service: the reindexer, the
+/// hook dry-runner behind `Query.hookDryRun`, the hook lister behind
+/// `Query.discoveredHooks` and the watched directory lister behind
+/// `Query.watchedDirectories`.
+pub fn build_schema_with_services(
+    db: DatabaseConnection,
+    event_sender: broadcast::Sender<DbChangeEvent>,
+    reindexer: SessionReindexer,
+    hook_dry_runner: HookDryRunner,
+    hook_lister: HookLister,
+    watched_directory_lister: WatchedDirectoryLister,
+) -> HanSchema {
+    schema_builder(db, event_sender)
+        .data(reindexer)
+        .data(hook_dry_runner)
+        .data(hook_lister)
+        .data(watched_directory_lister)
+        .finish()
+}
+
+fn schema_builder(
+    db: DatabaseConnection,
+    event_sender: broadcast::Sender<DbChangeEvent>,
+) -> SchemaBuilder<QueryRoot, MutationRoot, SubscriptionRoot> {
     let tool_result_by_parent_id =
         DataLoader::new(ToolResultByParentIdLoader { db: db.clone() }, tokio::spawn);
-    let tool_result_by_call_id =
-        DataLoader::new(ToolResultByCallIdLoader { db: db.clone() }, tokio::spawn);
+    let tool_result_completed_at =
+        DataLoader::new(ToolResultCompletedAtLoader { db: db.clone() }, tokio::spawn);
+    let tool_use_by_call_id =
+        DataLoader::new(ToolUseByCallIdLoader { db: db.clone() }, tokio::spawn);
+    let mcp_tool_result = DataLoader::new(McpToolResultLoader { db: db.clone() }, tokio::spawn);
+    let exposed_tool_result =
+        DataLoader::new(ExposedToolResultLoader { db: db.clone() }, tokio::spawn);
     let hook_result_by_run_id =
         DataLoader::new(HookResultByRunIdLoader { db: db.clone() }, tokio::spawn);
+    let session_token_totals =
+        DataLoader::new(SessionTokenTotalsLoader { db: db.clone() }, tokio::spawn);
+    let session_task_counts =
+        DataLoader::new(SessionTaskCountsLoader { db: db.clone() }, tokio::spawn);
+    let session_hook_plugin_stats = DataLoader::new(
+        SessionHookPluginStatsLoader { db: db.clone() },
+        tokio::spawn,
+    );
+    let session_hook_count =
+        DataLoader::new(SessionHookCountLoader { db: db.clone() }, tokio::spawn);
+    let session_file_change_count = DataLoader::new(
+        SessionFileChangeCountLoader { db: db.clone() },
+        tokio::spawn,
+    );
+    let session_system_message_count = DataLoader::new(
+        SessionSystemMessageCountLoader { db: db.clone() },
+        tokio::spawn,
+    );
+    let session_tool_error_count =
+        DataLoader::new(SessionToolErrorCountLoader { db: db.clone() }, tokio::spawn);
+    let session_tool_result_count = DataLoader::new(
+        SessionToolResultCountLoader { db: db.clone() },
+        tokio::spawn,
+    );
+    let session_max_frustration =
+        DataLoader::new(SessionMaxFrustrationLoader { db: db.clone() }, tokio::spawn);
+    let session_by_id = DataLoader::new(SessionByIdLoader { db: db.clone() }, tokio::spawn);
+    let message_by_id = DataLoader::new(MessageByIdLoader { db: db.clone() }, tokio::spawn);
+    let memory = DataLoader::new(MemoryLoader { db: db.clone() }, tokio::spawn);
+    let plugin = DataLoader::new(PluginLoader { db: db.clone() }, tokio::spawn);
+    let project_latest_session =
+        DataLoader::new(ProjectLatestSessionLoader { db: db.clone() }, tokio::spawn);
 
     Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(db)
         .data(event_sender)
         .data(tool_result_by_parent_id)
-        .data(tool_result_by_call_id)
+        .data(tool_result_completed_at)
+        .data(tool_use_by_call_id)
+        .data(mcp_tool_result)
+        .data(exposed_tool_result)
         .data(hook_result_by_run_id)
+        .data(session_token_totals)
+        .data(session_task_counts)
+        .data(session_hook_plugin_stats)
+        .data(session_hook_count)
+        .data(session_file_change_count)
+        .data(session_system_message_count)
+        .data(session_tool_error_count)
+        .data(session_tool_result_count)
+        .data(session_max_frustration)
+        .data(session_by_id)
+        .data(message_by_id)
+        .data(memory)
+        .data(plugin)
+        .data(project_latest_session)
+        .data(ReindexThrottle::default())
         // Manually register types not directly reachable from root queries
         // but needed for fragments in browse-client.
         .register_output_type::<crate::types::messages::UserMessage>()
         .register_output_type::<crate::types::node::Node>()
         // Register enums used in browse-client queries but not referenced by root args
         .register_output_type::<crate::types::enums::Granularity>()
-        .finish()
 }
 
 /// Export the schema as SDL (Schema Definition Language).
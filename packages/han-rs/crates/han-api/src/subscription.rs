@@ -11,7 +11,8 @@ use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
 use crate::context::DbChangeEvent;
 use crate::node::{decode_global_id, encode_msg_cursor, encode_session_cursor};
 use crate::query::{enrich_single_session, session_model_to_data};
-use crate::types::messages::{discriminate_message, MessageData, MessageEdge};
+use crate::types::enums::IndexProgressStatus;
+use crate::types::messages::{MessageData, MessageEdge};
 use crate::types::sessions::{SessionData, SessionEdge};
 
 /// Subscription root type.
@@ -128,10 +129,7 @@ impl SessionMessageAddedPayload {
             Some(msg) => {
                 let data = MessageData::from_model(msg, &project_dir);
                 let cursor = encode_msg_cursor(&msg.timestamp, &msg.id);
-                Ok(Some(MessageEdge {
-                    node: discriminate_message(data),
-                    cursor,
-                }))
+                Ok(Some(MessageEdge::new(data, cursor)))
             }
             None => Ok(None),
         }
@@ -164,20 +162,27 @@ impl SessionAddedPayload {
     /// The new session edge for Relay @prependEdge.
     async fn new_session_edge(&self, ctx: &Context<'_>) -> Result<Option<SessionEdge>> {
         let db = ctx.data::<DatabaseConnection>()?;
-        let session = sessions::Entity::find_by_id(&self.session_id)
-            .one(db)
-            .await
-            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(load_session(db, &self.session_id).await?.map(|data| {
+            let cursor = encode_session_cursor(&data.session_id, &data.date);
+            SessionEdge { node: data, cursor }
+        }))
+    }
+}
 
-        match session {
-            Some(s) => {
-                let mut data = session_model_to_data(s);
-                enrich_single_session(db, &mut data).await?;
-                let cursor = encode_session_cursor(&data.session_id, &data.date);
-                Ok(Some(SessionEdge { node: data, cursor }))
-            }
-            None => Ok(None),
+/// Load a session with the same enrichment as `Query.session`.
+async fn load_session(db: &DatabaseConnection, session_id: &str) -> Result<Option<SessionData>> {
+    let session = sessions::Entity::find_by_id(session_id)
+        .one(db)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+
+    match session {
+        Some(s) => {
+            let mut data = session_model_to_data(s);
+            enrich_single_session(db, &mut data).await?;
+            Ok(Some(data))
         }
+        None => Ok(None),
     }
 }
 
@@ -240,6 +245,13 @@ pub struct SessionHooksChangedPayload {
     pub event_type: String,
 }
 
+/// Task created payload.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TaskCreatedPayload {
+    pub session_id: Option<String>,
+    pub task_id: String,
+}
+
 /// Repo added payload.
 #[derive(Debug, Clone, SimpleObject)]
 pub struct RepoAddedPayload {
@@ -253,6 +265,29 @@ pub struct ProjectAddedPayload {
     pub parent_id: Option<String>,
 }
 
+/// Index progress payload.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IndexProgressPayload {
+    /// Session ID, or agent ID for agent transcripts.
+    pub session_id: String,
+    pub file_path: String,
+    pub status: IndexProgressStatus,
+    /// New messages indexed; set once the file is done.
+    pub messages_indexed: i32,
+}
+
+/// A highly frustrated user message.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FrustrationAlertEvent {
+    pub session_id: String,
+    pub message_id: String,
+    pub frustration_score: f64,
+    pub frustration_level: String,
+    /// Text of the message that raised the alert.
+    pub content: String,
+    pub timestamp: String,
+}
+
 #[Subscription]
 impl SubscriptionRoot {
     /// Subscribe to updates for a specific node.
@@ -459,6 +494,33 @@ impl SubscriptionRoot {
         }))
     }
 
+    /// Subscribe to recorded tasks. Filter by sessionId or receive all.
+    async fn task_created(
+        &self,
+        ctx: &Context<'_>,
+        session_id: Option<ID>,
+    ) -> Result<impl Stream<Item = TaskCreatedPayload>> {
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        let receiver = sender.subscribe();
+        let target = session_id.map(|id| id.to_string());
+
+        Ok(BroadcastStream::new(receiver).filter_map(move |event| {
+            if let Ok(DbChangeEvent::TaskCreated {
+                session_id,
+                task_id,
+            }) = event
+            {
+                if target.is_none() || session_id == target {
+                    return Some(TaskCreatedPayload {
+                        session_id,
+                        task_id,
+                    });
+                }
+            }
+            None
+        }))
+    }
+
     /// Subscribe to new sessions. Filter by projectId or receive all.
     async fn session_added(
         &self,
@@ -496,6 +558,49 @@ impl SubscriptionRoot {
         }))
     }
 
+    /// Subscribe to newly discovered sessions, delivered as full sessions.
+    async fn session_created(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = SessionData>> {
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        let receiver = sender.subscribe();
+        let db = ctx.data::<DatabaseConnection>()?.clone();
+
+        Ok(BroadcastStream::new(receiver)
+            .filter_map(|event| match event {
+                Ok(DbChangeEvent::SessionAdded { session_id, .. }) => Some(session_id),
+                _ => None,
+            })
+            .then(move |session_id| {
+                let db = db.clone();
+                async move { load_session(&db, &session_id).await }
+            })
+            .filter_map(|loaded| loaded.ok().flatten()))
+    }
+
+    /// Subscribe to a session, receiving it again each time it changes.
+    async fn session_updated(
+        &self,
+        ctx: &Context<'_>,
+        session_id: ID,
+    ) -> Result<impl Stream<Item = SessionData>> {
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        let receiver = sender.subscribe();
+        let db = ctx.data::<DatabaseConnection>()?.clone();
+        let target = session_id.to_string();
+
+        Ok(BroadcastStream::new(receiver)
+            .filter_map(move |event| match event {
+                Ok(DbChangeEvent::SessionUpdated { session_id }) if session_id == target => {
+                    Some(session_id)
+                }
+                _ => None,
+            })
+            .then(move |session_id| {
+                let db = db.clone();
+                async move { load_session(&db, &session_id).await }
+            })
+            .filter_map(|loaded| loaded.ok().flatten()))
+    }
+
     /// Subscribe to new repos.
     async fn repo_added(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = RepoAddedPayload>> {
         let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
@@ -536,6 +641,69 @@ impl SubscriptionRoot {
         }))
     }
 
+    /// Subscribe to per-file progress of background index runs, such as the
+    /// coordinator's startup scan.
+    async fn index_progress(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = IndexProgressPayload>> {
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        let receiver = sender.subscribe();
+
+        Ok(BroadcastStream::new(receiver).filter_map(|event| {
+            if let Ok(DbChangeEvent::IndexProgress {
+                session_id,
+                file_path,
+                status,
+                messages_indexed,
+            }) = event
+            {
+                return Some(IndexProgressPayload {
+                    session_id,
+                    file_path,
+                    status,
+                    messages_indexed,
+                });
+            }
+            None
+        }))
+    }
+
+    /// Subscribe to highly frustrated user messages as they are indexed.
+    /// Filter by sessionId or receive all. A session alerts at most once
+    /// every 30 seconds.
+    async fn frustration_alert(
+        &self,
+        ctx: &Context<'_>,
+        session_id: Option<String>,
+    ) -> Result<impl Stream<Item = FrustrationAlertEvent>> {
+        let sender = ctx.data::<broadcast::Sender<DbChangeEvent>>()?;
+        let receiver = sender.subscribe();
+
+        Ok(
+            BroadcastStream::new(receiver).filter_map(move |event| match event {
+                Ok(DbChangeEvent::FrustrationAlert {
+                    session_id: id,
+                    message_id,
+                    score,
+                    level,
+                    content,
+                    timestamp,
+                }) if session_id.as_ref().is_none_or(|target| *target == id) => {
+                    Some(FrustrationAlertEvent {
+                        session_id: id,
+                        message_id,
+                        frustration_score: score,
+                        frustration_level: level,
+                        content,
+                        timestamp,
+                    })
+                }
+                _ => None,
+            }),
+        )
+    }
+
     // ========================================================================
     // Stub subscriptions for browse-client backwards compatibility
     // ========================================================================
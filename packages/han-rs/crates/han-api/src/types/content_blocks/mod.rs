@@ -5,16 +5,25 @@
 //!
 //! ContentBlock is a GraphQL **interface** (not union) because the browse-client
 //! queries `type` as a shared field before using inline fragments on concrete types.
+//!
+//! Every block has an `id` so clients can cache it. Tool blocks use their tool
+//! call ID; text and thinking blocks are keyed by session, message and position
+//! in the message; images by a hash of their data.
 
 use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 
 use super::enums::{ContentBlockType, ToolCategory};
-use crate::loaders::ToolResultByParentIdLoader;
+use crate::loaders::{
+    ToolResultByParentIdLoader, ToolResultCompletedAtLoader, ToolUseByCallIdLoader,
+};
 
-/// Content block interface - shared `type` field across all block types.
+/// Content block interface - shared `id` and `type` fields across all block types.
 #[derive(Debug, Clone, Interface)]
-#[graphql(field(name = "type", ty = "ContentBlockType", method = "block_type"))]
+#[graphql(
+    field(name = "id", ty = "ID"),
+    field(name = "type", ty = "ContentBlockType", method = "block_type")
+)]
 pub enum ContentBlock {
     Text(TextBlock),
     Thinking(ThinkingBlock),
@@ -26,12 +35,16 @@ pub enum ContentBlock {
 /// A text content block.
 #[derive(Debug, Clone)]
 pub struct TextBlock {
+    pub id: String,
     pub block_type: ContentBlockType,
     pub text: String,
 }
 
 #[Object]
 impl TextBlock {
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
     #[graphql(name = "type")]
     async fn block_type(&self) -> ContentBlockType {
         self.block_type
@@ -44,6 +57,7 @@ impl TextBlock {
 /// A thinking/reasoning block.
 #[derive(Debug, Clone)]
 pub struct ThinkingBlock {
+    pub id: String,
     pub block_type: ContentBlockType,
     pub thinking: String,
     pub preview: String,
@@ -52,6 +66,9 @@ pub struct ThinkingBlock {
 
 #[Object]
 impl ThinkingBlock {
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
     #[graphql(name = "type")]
     async fn block_type(&self) -> ContentBlockType {
         self.block_type
@@ -67,6 +84,71 @@ impl ThinkingBlock {
     }
 }
 
+/// A thinking block flattened for direct listing on assistant messages.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ThinkingBlockData {
+    pub thinking: String,
+    /// First 500 characters, cut at a word boundary.
+    pub preview: String,
+    pub signature: Option<String>,
+    pub word_count: i32,
+}
+
+/// A text block flattened for direct listing on assistant messages.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TextBlockData {
+    pub text: String,
+    pub word_count: i32,
+}
+
+/// Maximum preview length (in characters) for [`ThinkingBlockData`].
+const THINKING_DATA_PREVIEW_CHARS: usize = 500;
+
+impl From<&ThinkingBlock> for ThinkingBlockData {
+    fn from(b: &ThinkingBlock) -> Self {
+        Self {
+            thinking: b.thinking.clone(),
+            preview: word_boundary_preview(&b.thinking, THINKING_DATA_PREVIEW_CHARS),
+            signature: b.signature.clone(),
+            word_count: word_count(&b.thinking),
+        }
+    }
+}
+
+impl From<&TextBlock> for TextBlockData {
+    fn from(b: &TextBlock) -> Self {
+        Self {
+            text: b.text.clone(),
+            word_count: word_count(&b.text),
+        }
+    }
+}
+
+/// Count whitespace-separated words.
+pub fn word_count(s: &str) -> i32 {
+    s.split_whitespace().count() as i32
+}
+
+/// Truncate to at most `max_chars` characters, backing up to the last word
+/// boundary so words aren't split. Falls back to a hard cut when the prefix
+/// contains no whitespace.
+pub fn word_boundary_preview(s: &str, max_chars: usize) -> String {
+    let Some((cut, _)) = s.char_indices().nth(max_chars) else {
+        return s.to_string();
+    };
+    let prefix = &s[..cut];
+    // If the cut lands exactly on whitespace, the prefix ends on a whole word
+    let end = if s[cut..].starts_with(char::is_whitespace) {
+        prefix.len()
+    } else {
+        prefix
+            .rfind(char::is_whitespace)
+            .filter(|&i| i > 0)
+            .unwrap_or(prefix.len())
+    };
+    format!("{}...", prefix[..end].trim_end())
+}
+
 /// A tool use block (Claude calling a tool).
 #[derive(Debug, Clone)]
 pub struct ToolUseBlock {
@@ -80,10 +162,48 @@ pub struct ToolUseBlock {
     pub color: String,
     pub session_id: Option<String>,
     pub agent_task_id: Option<String>,
+    /// Timestamp of the assistant message that made the call, set by
+    /// [`stamp_tool_use_blocks`].
+    pub timestamp: Option<String>,
+}
+
+/// Record the issuing message's timestamp on each tool use block.
+pub fn stamp_tool_use_blocks(blocks: &mut [ContentBlock], timestamp: &str) {
+    for block in blocks {
+        if let ContentBlock::ToolUse(tool_use) = block {
+            tool_use.timestamp = Some(timestamp.to_string());
+        }
+    }
+}
+
+/// Milliseconds between two RFC 3339 timestamps, or `None` if either fails to
+/// parse or the result precedes the call.
+pub fn tool_duration_ms(started_at: &str, completed_at: &str) -> Option<i32> {
+    let started = chrono::DateTime::parse_from_rfc3339(started_at).ok()?;
+    let completed = chrono::DateTime::parse_from_rfc3339(completed_at).ok()?;
+    let ms = (completed - started).num_milliseconds();
+    (ms >= 0).then(|| ms.min(i32::MAX as i64) as i32)
+}
+
+impl ToolUseBlock {
+    /// The tool input, or `None` if the block had none.
+    pub fn input_value(&self) -> Option<serde_json::Value> {
+        serde_json::from_str(&self.input).ok()
+    }
+
+    /// Timestamp of the message carrying this call's result, if indexed.
+    async fn load_completed_at(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let loader = ctx.data::<DataLoader<ToolResultCompletedAtLoader>>()?;
+        loader.load_one(self.tool_call_id.clone()).await
+    }
 }
 
 #[Object]
 impl ToolUseBlock {
+    /// The tool call ID.
+    async fn id(&self) -> ID {
+        ID(self.tool_call_id.clone())
+    }
     #[graphql(name = "type")]
     async fn block_type(&self) -> ContentBlockType {
         self.block_type
@@ -97,6 +217,16 @@ impl ToolUseBlock {
     async fn input(&self) -> &str {
         &self.input
     }
+    /// Tool input as JSON, for clients that process it.
+    async fn input_json(&self) -> Option<Json<serde_json::Value>> {
+        self.input_value().map(Json)
+    }
+    /// Size in bytes of the input serialized as compact JSON, 0 without input.
+    async fn input_size(&self) -> i32 {
+        self.input_value()
+            .and_then(|v| serde_json::to_string(&v).ok())
+            .map_or(0, |s| s.len().min(i32::MAX as usize) as i32)
+    }
     async fn category(&self) -> ToolCategory {
         self.category
     }
@@ -140,6 +270,23 @@ impl ToolUseBlock {
         }))
     }
 
+    /// When the tool result was recorded. Null until the result is indexed.
+    async fn completed_at(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        self.load_completed_at(ctx).await
+    }
+
+    /// Milliseconds from the tool call to its result. Null until the result
+    /// is indexed.
+    async fn duration_ms(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let Some(started_at) = self.timestamp.as_deref() else {
+            return Ok(None);
+        };
+        Ok(self
+            .load_completed_at(ctx)
+            .await?
+            .and_then(|completed_at| tool_duration_ms(started_at, &completed_at)))
+    }
+
     /// Agent task reference (stub for backwards compatibility).
     async fn agent_task(&self) -> Option<AgentTask> {
         None
@@ -160,6 +307,10 @@ pub struct ToolResultBlock {
 
 #[Object]
 impl ToolResultBlock {
+    /// The ID of the tool call this result answers.
+    async fn id(&self) -> ID {
+        ID(self.tool_call_id.clone())
+    }
     #[graphql(name = "type")]
     async fn block_type(&self) -> ContentBlockType {
         self.block_type
@@ -182,11 +333,26 @@ impl ToolResultBlock {
     async fn has_image(&self) -> bool {
         self.has_image
     }
+
+    /// Name of the tool that produced this result, from the matching tool
+    /// use. Null until the call is indexed.
+    async fn tool_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let loader = ctx.data::<DataLoader<ToolUseByCallIdLoader>>()?;
+        loader.load_one(self.tool_call_id.clone()).await
+    }
+
+    /// Category of the tool that produced this result. Null until the call
+    /// is indexed.
+    async fn tool_category(&self, ctx: &Context<'_>) -> Result<Option<ToolCategory>> {
+        let name = self.tool_name(ctx).await?;
+        Ok(name.map(|n| get_tool_metadata(&n).0))
+    }
 }
 
 /// An image content block.
 #[derive(Debug, Clone)]
 pub struct ImageBlock {
+    pub id: String,
     pub block_type: ContentBlockType,
     pub media_type: String,
     pub data_url: String,
@@ -194,6 +360,9 @@ pub struct ImageBlock {
 
 #[Object]
 impl ImageBlock {
+    async fn id(&self) -> ID {
+        ID(self.id.clone())
+    }
     #[graphql(name = "type")]
     async fn block_type(&self) -> ContentBlockType {
         self.block_type
@@ -294,11 +463,18 @@ pub fn get_tool_metadata(tool_name: &str) -> (ToolCategory, &'static str, String
 }
 
 /// Parse raw content blocks from message content into typed ContentBlock.
+///
+/// `session_id` and `message_id` key the IDs of text and thinking blocks.
 pub fn parse_content_blocks(
     content: Option<&str>,
     raw_json: Option<&str>,
     session_id: Option<&str>,
+    message_id: Option<&str>,
 ) -> Vec<ContentBlock> {
+    let key = BlockKey {
+        session_id,
+        message_id,
+    };
     // Try to parse content blocks from raw JSON first (most reliable)
     if let Some(raw) = raw_json {
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(raw) {
@@ -309,14 +485,16 @@ pub fn parse_content_blocks(
             {
                 return blocks
                     .iter()
-                    .filter_map(|block| parse_single_block(block, session_id))
+                    .enumerate()
+                    .filter_map(|(i, block)| parse_single_block(block, &key, i))
                     .collect();
             }
             // Direct content array
             if let Some(blocks) = parsed.get("content").and_then(|c| c.as_array()) {
                 return blocks
                     .iter()
-                    .filter_map(|block| parse_single_block(block, session_id))
+                    .enumerate()
+                    .filter_map(|(i, block)| parse_single_block(block, &key, i))
                     .collect();
             }
         }
@@ -326,6 +504,7 @@ pub fn parse_content_blocks(
     if let Some(text) = content {
         if !text.is_empty() {
             return vec![ContentBlock::Text(TextBlock {
+                id: key.block_id("text", 0),
                 block_type: ContentBlockType::Text,
                 text: text.to_string(),
             })];
@@ -335,7 +514,35 @@ pub fn parse_content_blocks(
     vec![]
 }
 
-fn parse_single_block(block: &serde_json::Value, session_id: Option<&str>) -> Option<ContentBlock> {
+/// The message a block belongs to, for block IDs.
+struct BlockKey<'a> {
+    session_id: Option<&'a str>,
+    message_id: Option<&'a str>,
+}
+
+impl BlockKey<'_> {
+    /// `{kind}:{session_id}:{message_id}:{block_index}`, with missing parts
+    /// left empty.
+    fn block_id(&self, kind: &str, block_index: usize) -> String {
+        let session_id = self.session_id.unwrap_or_default();
+        let message_id = self.message_id.unwrap_or_default();
+        format!("{kind}:{session_id}:{message_id}:{block_index}")
+    }
+}
+
+/// `image:` followed by the first 8 hex digits of the data URL's SHA-256.
+fn image_block_id(data_url: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = format!("{:x}", Sha256::digest(data_url.as_bytes()));
+    format!("image:{}", &digest[..8])
+}
+
+fn parse_single_block(
+    block: &serde_json::Value,
+    key: &BlockKey<'_>,
+    block_index: usize,
+) -> Option<ContentBlock> {
     let block_type = block.get("type")?.as_str()?;
 
     match block_type {
@@ -347,6 +554,7 @@ fn parse_single_block(block: &serde_json::Value, session_id: Option<&str>) -> Op
                 thinking.clone()
             };
             Some(ContentBlock::Thinking(ThinkingBlock {
+                id: key.block_id("thinking", block_index),
                 block_type: ContentBlockType::Thinking,
                 thinking,
                 preview,
@@ -359,6 +567,7 @@ fn parse_single_block(block: &serde_json::Value, session_id: Option<&str>) -> Op
         "text" => {
             let text = block.get("text")?.as_str()?.to_string();
             Some(ContentBlock::Text(TextBlock {
+                id: key.block_id("text", block_index),
                 block_type: ContentBlockType::Text,
                 text,
             }))
@@ -380,8 +589,9 @@ fn parse_single_block(block: &serde_json::Value, session_id: Option<&str>) -> Op
                 icon: icon.to_string(),
                 display_name,
                 color: color.to_string(),
-                session_id: session_id.map(|s| s.to_string()),
+                session_id: key.session_id.map(|s| s.to_string()),
                 agent_task_id: None,
+                timestamp: None,
             }))
         }
         "tool_result" => {
@@ -418,10 +628,12 @@ fn parse_single_block(block: &serde_json::Value, session_id: Option<&str>) -> Op
             let source = block.get("source")?;
             let media_type = source.get("media_type")?.as_str()?.to_string();
             let data = source.get("data")?.as_str()?.to_string();
+            let data_url = format!("data:{media_type};base64,{data}");
             Some(ContentBlock::Image(ImageBlock {
+                id: image_block_id(&data_url),
                 block_type: ContentBlockType::Image,
-                media_type: media_type.clone(),
-                data_url: format!("data:{media_type};base64,{data}"),
+                media_type,
+                data_url,
             }))
         }
         _ => None,
@@ -459,7 +671,7 @@ mod tests {
     #[test]
     fn test_parse_text_block() {
         let raw = r#"{"message":{"content":[{"type":"text","text":"Hello world"}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::Text(b) => assert_eq!(b.text, "Hello world"),
@@ -470,7 +682,7 @@ mod tests {
     #[test]
     fn test_parse_thinking_block() {
         let raw = r#"{"message":{"content":[{"type":"thinking","thinking":"Let me think...","signature":"sig123"}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::Thinking(b) => {
@@ -484,7 +696,7 @@ mod tests {
     #[test]
     fn test_parse_tool_use_block() {
         let raw = r#"{"message":{"content":[{"type":"tool_use","id":"call_123","name":"Read","input":{"file_path":"/test.rs"}}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), Some("session-1"));
+        let blocks = parse_content_blocks(None, Some(raw), Some("session-1"), None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::ToolUse(b) => {
@@ -492,15 +704,51 @@ mod tests {
                 assert_eq!(b.name, "Read");
                 assert_eq!(b.display_name, "Read File");
                 assert_eq!(b.session_id.as_deref(), Some("session-1"));
+                assert_eq!(
+                    b.input_value(),
+                    Some(serde_json::json!({ "file_path": "/test.rs" }))
+                );
             }
             _ => panic!("Expected ToolUseBlock"),
         }
+
+        // A block without input has no JSON value
+        let raw = r#"{"message":{"content":[{"type":"tool_use","id":"call_1","name":"Read"}]}}"#;
+        match &parse_content_blocks(None, Some(raw), None, None)[0] {
+            ContentBlock::ToolUse(b) => assert_eq!(b.input_value(), None),
+            _ => panic!("Expected ToolUseBlock"),
+        }
+    }
+
+    #[test]
+    fn test_stamp_tool_use_blocks() {
+        let raw = r#"{"message":{"content":[{"type":"text","text":"Reading"},{"type":"tool_use","id":"call_1","name":"Read","input":{}}]}}"#;
+        let mut blocks = parse_content_blocks(None, Some(raw), None, None);
+        stamp_tool_use_blocks(&mut blocks, "2026-04-14T10:00:00Z");
+        match &blocks[1] {
+            ContentBlock::ToolUse(b) => {
+                assert_eq!(b.timestamp.as_deref(), Some("2026-04-14T10:00:00Z"))
+            }
+            _ => panic!("Expected ToolUseBlock"),
+        }
+    }
+
+    #[test]
+    fn test_tool_duration_ms() {
+        let duration = |completed_at| tool_duration_ms("2026-04-14T10:00:00.250Z", completed_at);
+        assert_eq!(duration("2026-04-14T10:00:01.750Z"), Some(1500));
+        assert_eq!(duration("2026-04-14T10:02:00.250Z"), Some(120_000));
+        // Offsets are normalized
+        assert_eq!(duration("2026-04-14T12:00:00.250+02:00"), Some(0));
+        // Result recorded before the call
+        assert_eq!(duration("2026-04-14T09:59:59Z"), None);
+        assert_eq!(duration("not a timestamp"), None);
     }
 
     #[test]
     fn test_parse_tool_result_block() {
         let raw = r#"{"message":{"content":[{"type":"tool_result","tool_use_id":"call_123","content":"file contents here"}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::ToolResult(b) => {
@@ -516,7 +764,7 @@ mod tests {
     #[test]
     fn test_parse_image_block() {
         let raw = r#"{"message":{"content":[{"type":"image","source":{"media_type":"image/png","data":"iVBOR..."}}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::Image(b) => {
@@ -534,7 +782,7 @@ mod tests {
             {"type":"text","text":"Here's the answer"},
             {"type":"tool_use","id":"c1","name":"Bash","input":{"command":"ls"}}
         ]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 3);
         assert!(matches!(&blocks[0], ContentBlock::Thinking(_)));
         assert!(matches!(&blocks[1], ContentBlock::Text(_)));
@@ -543,7 +791,7 @@ mod tests {
 
     #[test]
     fn test_fallback_to_content_text() {
-        let blocks = parse_content_blocks(Some("plain text content"), None, None);
+        let blocks = parse_content_blocks(Some("plain text content"), None, None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::Text(b) => assert_eq!(b.text, "plain text content"),
@@ -553,13 +801,13 @@ mod tests {
 
     #[test]
     fn test_empty_content() {
-        let blocks = parse_content_blocks(None, None, None);
+        let blocks = parse_content_blocks(None, None, None, None);
         assert!(blocks.is_empty());
     }
 
     #[test]
     fn test_empty_string_content() {
-        let blocks = parse_content_blocks(Some(""), None, None);
+        let blocks = parse_content_blocks(Some(""), None, None, None);
         assert!(blocks.is_empty());
     }
 
@@ -569,7 +817,7 @@ mod tests {
         let raw = format!(
             r#"{{"message":{{"content":[{{"type":"tool_result","tool_use_id":"c1","content":"{long_content}"}}]}}}}"#
         );
-        let blocks = parse_content_blocks(None, Some(&raw), None);
+        let blocks = parse_content_blocks(None, Some(&raw), None, None);
         match &blocks[0] {
             ContentBlock::ToolResult(b) => {
                 assert!(b.is_long);
@@ -583,7 +831,7 @@ mod tests {
     #[test]
     fn test_tool_result_with_error() {
         let raw = r#"{"message":{"content":[{"type":"tool_result","tool_use_id":"c1","content":"error msg","is_error":true}]}}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         match &blocks[0] {
             ContentBlock::ToolResult(b) => assert!(b.is_error),
             _ => panic!("Expected ToolResultBlock"),
@@ -633,7 +881,7 @@ mod tests {
         let raw = format!(
             r#"{{"message":{{"content":[{{"type":"thinking","thinking":"{long_thinking}"}}]}}}}"#
         );
-        let blocks = parse_content_blocks(None, Some(&raw), None);
+        let blocks = parse_content_blocks(None, Some(&raw), None, None);
         match &blocks[0] {
             ContentBlock::Thinking(b) => {
                 assert_eq!(b.thinking.len(), 300);
@@ -647,11 +895,219 @@ mod tests {
     #[test]
     fn test_direct_content_array_format() {
         let raw = r#"{"content":[{"type":"text","text":"direct format"}]}"#;
-        let blocks = parse_content_blocks(None, Some(raw), None);
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
         assert_eq!(blocks.len(), 1);
         match &blocks[0] {
             ContentBlock::Text(b) => assert_eq!(b.text, "direct format"),
             _ => panic!("Expected TextBlock"),
         }
     }
+
+    #[test]
+    fn test_block_data_from_mixed_blocks() {
+        let raw = r#"{"message":{"content":[
+            {"type":"thinking","thinking":"first consider the edge cases","signature":"sig1"},
+            {"type":"text","text":"Done."},
+            {"type":"tool_use","id":"c1","name":"Bash","input":{"command":"ls"}},
+            {"type":"thinking","thinking":""}
+        ]}}"#;
+        let blocks = parse_content_blocks(None, Some(raw), None, None);
+        let thinking: Vec<ThinkingBlockData> = blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Thinking(t) => Some(t.into()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(thinking.len(), 2);
+        assert_eq!(thinking[0].word_count, 5);
+        assert_eq!(thinking[0].signature.as_deref(), Some("sig1"));
+        assert_eq!(thinking[0].preview, "first consider the edge cases");
+        assert_eq!(thinking[1].word_count, 0);
+        assert_eq!(thinking[1].preview, "");
+
+        let text: Vec<TextBlockData> = blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Text(t) => Some(t.into()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text.len(), 1);
+        assert_eq!(text[0].word_count, 1);
+    }
+
+    #[test]
+    fn test_word_boundary_preview() {
+        assert_eq!(word_boundary_preview("short", 10), "short");
+        assert_eq!(
+            word_boundary_preview("hello brave world", 13),
+            "hello brave..."
+        );
+        // Cut lands on a space: keep the whole preceding word
+        assert_eq!(
+            word_boundary_preview("hello brave world", 11),
+            "hello brave..."
+        );
+        // No whitespace: hard cut
+        assert_eq!(word_boundary_preview("abcdefghij", 4), "abcd...");
+    }
+
+    #[test]
+    fn test_word_boundary_preview_unicode() {
+        // Multi-byte characters must not be split
+        let s = "日本語 テキスト です";
+        assert_eq!(word_boundary_preview(s, 6), "日本語...");
+        assert_eq!(word_count(s), 3);
+        let emoji = "🎉🎉🎉🎉 party";
+        assert_eq!(word_boundary_preview(emoji, 2), "🎉🎉...");
+        // Non-ASCII whitespace counts as a boundary
+        assert_eq!(
+            word_boundary_preview("café\u{00A0}crème brûlée", 8),
+            "café..."
+        );
+    }
+
+    #[test]
+    fn test_word_boundary_preview_long_thinking() {
+        let thinking = "word ".repeat(200);
+        let preview = word_boundary_preview(&thinking, THINKING_DATA_PREVIEW_CHARS);
+        assert!(preview.ends_with("word..."));
+        assert!(preview.chars().count() <= THINKING_DATA_PREVIEW_CHARS + 3);
+    }
+
+    fn block_id(block: &ContentBlock) -> &str {
+        match block {
+            ContentBlock::Text(b) => &b.id,
+            ContentBlock::Thinking(b) => &b.id,
+            ContentBlock::ToolUse(b) => &b.tool_call_id,
+            ContentBlock::ToolResult(b) => &b.tool_call_id,
+            ContentBlock::Image(b) => &b.id,
+        }
+    }
+
+    #[test]
+    fn test_block_ids() {
+        let image = |data: &str| {
+            serde_json::json!({
+                "type": "image",
+                "source": { "type": "base64", "media_type": "image/png", "data": data },
+            })
+        };
+        let raw = serde_json::json!({ "message": { "content": [
+            { "type": "thinking", "thinking": "hmm" },
+            { "type": "text", "text": "same" },
+            { "type": "unknown" },
+            { "type": "text", "text": "same" },
+            { "type": "tool_use", "id": "call_1", "name": "Read", "input": {} },
+            image("AAAA"),
+            image("BBBB"),
+        ] } })
+        .to_string();
+        let parse = |message_id| parse_content_blocks(None, Some(&raw), Some("s1"), message_id);
+
+        let blocks = parse(Some("m1"));
+        let ids: Vec<&str> = blocks.iter().map(block_id).collect();
+        // Positions count skipped blocks, so IDs survive parser changes
+        assert_eq!(
+            &ids[..4],
+            ["thinking:s1:m1:0", "text:s1:m1:1", "text:s1:m1:3", "call_1"]
+        );
+        assert!(ids[4].starts_with("image:") && ids[4].len() == "image:".len() + 8);
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "{ids:?}");
+
+        // Stable across parses, and distinct between messages
+        let again = parse(Some("m1"));
+        assert_eq!(again.iter().map(block_id).collect::<Vec<_>>(), ids);
+        let other = parse(Some("m2"));
+        assert_eq!(block_id(&other[1]), "text:s1:m2:1");
+        // Identical images share an ID wherever they appear
+        assert_eq!(block_id(&other[4]), ids[4]);
+
+        // Plain-content fallback is the message's first block
+        let fallback = parse_content_blocks(Some("hi"), None, Some("s1"), Some("m1"));
+        assert_eq!(block_id(&fallback[0]), "text:s1:m1:0");
+    }
+
+    #[tokio::test]
+    async fn test_tool_result_tool_name() {
+        use han_db::entities::messages;
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let raw = |role: &str, block: serde_json::Value| {
+            serde_json::json!({ "message": { "role": role, "content": [block] } }).to_string()
+        };
+        let result = |id: &str| {
+            let block = serde_json::json!({ "type": "tool_result", "tool_use_id": id });
+            raw("user", block)
+        };
+        let row = |line: i32, id: &str, kind: &str, raw_json: String| messages::ActiveModel {
+            id: Set(id.to_string()),
+            session_id: Set("s1".to_string()),
+            message_type: Set(kind.to_string()),
+            content: Set(Some("ok".to_string())),
+            raw_json: Set(Some(raw_json)),
+            timestamp: Set(format!("2026-04-20T10:00:0{line}Z")),
+            line_number: Set(line),
+            ..Default::default()
+        };
+
+        let call = serde_json::json!({
+            "type": "tool_use", "id": "call_w", "name": "Write",
+            "input": { "file_path": "/a.rs", "content": "fn a() {}" },
+        });
+        // The indexer stores each tool_use block as its own message row
+        let mut tool_use = row(1, "call_w", "tool_use", String::new());
+        tool_use.content = Set(None);
+        tool_use.tool_name = Set(Some("Write".to_string()));
+        let models = vec![
+            row(0, "a1", "assistant", raw("assistant", call)),
+            tool_use,
+            row(2, "u1", "user", result("call_w")),
+            row(3, "u2", "user", result("call_gone")),
+        ];
+        han_db::crud::messages::insert_batch(&db, models)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let res = schema
+            .execute(
+                r#"{ session(id: "s1") { messages { edges { node {
+                    ... on UserMessage { contentBlocks {
+                        ... on ToolResultBlock { toolCallId toolName toolCategory }
+                    } }
+                } } } } }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let mut results: Vec<_> = data["session"]["messages"]["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|e| e["node"]["contentBlocks"].as_array().cloned())
+            .flatten()
+            .filter_map(|b| {
+                let id = b.get("toolCallId")?.as_str()?.to_string();
+                Some((id, b["toolName"].clone(), b["toolCategory"].clone()))
+            })
+            .collect();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        let null = serde_json::Value::Null;
+        assert_eq!(
+            results,
+            [
+                ("call_gone".to_string(), null.clone(), null),
+                ("call_w".to_string(), "Write".into(), "FILE".into()),
+            ]
+        );
+    }
 }
@@ -271,6 +271,92 @@ pub struct ToolUsageStats {
     pub count: Option<i32>,
 }
 
+/// MCP tool usage for a single server/tool pair.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct McpServerUsage {
+    pub server_name: String,
+    pub tool_name: String,
+    pub call_count: i32,
+    pub success_count: i32,
+    pub total_duration_ms: i32,
+    pub error_count: i32,
+}
+
+impl From<han_db::aggregates::McpServerUsageRow> for McpServerUsage {
+    fn from(row: han_db::aggregates::McpServerUsageRow) -> Self {
+        Self {
+            server_name: row.server_name,
+            tool_name: row.tool_name,
+            call_count: row.call_count as i32,
+            success_count: row.success_count as i32,
+            total_duration_ms: row.total_duration_ms as i32,
+            error_count: row.error_count as i32,
+        }
+    }
+}
+
+/// Call counts and timings for a single tool.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ToolUsageStat {
+    pub tool_name: String,
+    pub call_count: i32,
+    pub success_count: i32,
+    pub error_count: i32,
+    pub total_duration_ms: i32,
+    /// Mean over calls that have a result; 0 when none do.
+    pub average_duration_ms: f64,
+}
+
+impl From<han_db::aggregates::ToolCallStatsRow> for ToolUsageStat {
+    fn from(row: han_db::aggregates::ToolCallStatsRow) -> Self {
+        let average_duration_ms = if row.timed_count > 0 {
+            row.total_duration_ms as f64 / row.timed_count as f64
+        } else {
+            0.0
+        };
+        Self {
+            tool_name: row.tool_name,
+            call_count: row.call_count as i32,
+            success_count: row.success_count as i32,
+            error_count: row.error_count as i32,
+            total_duration_ms: row.total_duration_ms as i32,
+            average_duration_ms,
+        }
+    }
+}
+
+/// Activity for one Monday-to-Sunday week.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WeeklySummary {
+    /// Monday of the week (YYYY-MM-DD).
+    pub week_start: String,
+    /// ISO 8601 week number.
+    pub week_number: i32,
+    /// ISO 8601 week-numbering year.
+    pub year: i32,
+    pub session_count: i32,
+    pub message_count: i32,
+    pub total_tokens: i64,
+    pub unique_tools: i32,
+    /// Fraction of hook runs that passed, 0 when no hooks ran.
+    pub hook_pass_rate: f64,
+}
+
+impl From<han_db::aggregates::WeeklySummary> for WeeklySummary {
+    fn from(row: han_db::aggregates::WeeklySummary) -> Self {
+        Self {
+            week_start: row.week_start,
+            week_number: row.week_number as i32,
+            year: row.year,
+            session_count: row.session_count,
+            message_count: row.message_count,
+            total_tokens: row.total_tokens,
+            unique_tools: row.unique_tools,
+            hook_pass_rate: row.hook_pass_rate,
+        }
+    }
+}
+
 // ============================================================================
 // Human Time Estimation
 // ============================================================================
@@ -517,6 +603,28 @@ mod tests {
         assert!(da.tool_usage.unwrap().is_empty());
     }
 
+    #[test]
+    fn tool_usage_stat_averages_timed_calls() {
+        let row = han_db::aggregates::ToolCallStatsRow {
+            tool_name: "Bash".into(),
+            call_count: 3,
+            success_count: 1,
+            error_count: 1,
+            total_duration_ms: 3000,
+            timed_count: 2,
+        };
+        let stat = ToolUsageStat::from(row.clone());
+        assert_eq!(stat.call_count, 3);
+        assert_eq!(stat.average_duration_ms, 1500.0);
+
+        let untimed = ToolUsageStat::from(han_db::aggregates::ToolCallStatsRow {
+            timed_count: 0,
+            total_duration_ms: 0,
+            ..row
+        });
+        assert_eq!(untimed.average_duration_ms, 0.0);
+    }
+
     #[test]
     fn estimate_cost_usd_zero_tokens() {
         assert_eq!(estimate_cost_usd(0, 0, 0), 0.0);
@@ -0,0 +1,19 @@
+//! Discovered hook GraphQL type.
+
+use async_graphql::*;
+
+/// A hook found in an installed plugin's configuration.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DiscoveredHookInfo {
+    pub plugin_name: String,
+    /// Directory the plugin was loaded from.
+    pub plugin_root: String,
+    pub event: String,
+    /// "command" or "prompt".
+    pub hook_type: String,
+    pub command: Option<String>,
+    pub prompt: Option<String>,
+    /// Tool name pattern the hook is limited to, if any.
+    pub matcher: Option<String>,
+    pub timeout_ms: Option<u32>,
+}
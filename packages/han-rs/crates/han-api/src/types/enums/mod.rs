@@ -117,6 +117,18 @@ pub enum TaskType {
     Research,
 }
 
+impl TaskType {
+    /// Value stored in `tasks.task_type`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskType::Fix => "fix",
+            TaskType::Implementation => "implementation",
+            TaskType::Refactor => "refactor",
+            TaskType::Research => "research",
+        }
+    }
+}
+
 /// Task outcome.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 pub enum TaskOutcome {
@@ -128,6 +140,17 @@ pub enum TaskOutcome {
     Failure,
 }
 
+impl TaskOutcome {
+    /// Value stored in `tasks.outcome`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskOutcome::Success => "success",
+            TaskOutcome::Partial => "partial",
+            TaskOutcome::Failure => "failure",
+        }
+    }
+}
+
 /// Memory layer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 pub enum MemoryLayer {
@@ -150,6 +173,18 @@ pub enum MemorySource {
     Hybrid,
 }
 
+/// Frustration level of a user message or a whole session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(remote = "han_indexer::sentiment::FrustrationLevel")]
+pub enum FrustrationLevel {
+    #[graphql(name = "low")]
+    Low,
+    #[graphql(name = "moderate")]
+    Moderate,
+    #[graphql(name = "high")]
+    High,
+}
+
 /// Confidence level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 pub enum Confidence {
@@ -198,6 +233,68 @@ pub enum Granularity {
     Month,
 }
 
+/// Stage of a session file during a background index run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum IndexProgressStatus {
+    #[graphql(name = "PENDING")]
+    Pending,
+    #[graphql(name = "IN_PROGRESS")]
+    InProgress,
+    #[graphql(name = "DONE")]
+    Done,
+    #[graphql(name = "ERROR")]
+    Error,
+}
+
+/// Claude model family, normalized from a message's model ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum ModelVersion {
+    #[graphql(name = "CLAUDE_3_5_SONNET")]
+    Claude35Sonnet,
+    #[graphql(name = "CLAUDE_3_5_HAIKU")]
+    Claude35Haiku,
+    #[graphql(name = "CLAUDE_3_OPUS")]
+    Claude3Opus,
+    #[graphql(name = "CLAUDE_3_HAIKU")]
+    Claude3Haiku,
+    #[graphql(name = "CLAUDE_INSTANT")]
+    ClaudeInstant,
+    /// A model ID outside the known families; see `modelVersionRaw`.
+    #[graphql(name = "UNKNOWN")]
+    Unknown,
+}
+
+impl ModelVersion {
+    /// Every family with a known model ID prefix.
+    pub const KNOWN: [ModelVersion; 5] = [
+        ModelVersion::Claude35Sonnet,
+        ModelVersion::Claude35Haiku,
+        ModelVersion::Claude3Opus,
+        ModelVersion::Claude3Haiku,
+        ModelVersion::ClaudeInstant,
+    ];
+
+    /// Model ID prefix of this family, or `None` for `Unknown`.
+    pub fn prefix(&self) -> Option<&'static str> {
+        match self {
+            ModelVersion::Claude35Sonnet => Some("claude-3-5-sonnet"),
+            ModelVersion::Claude35Haiku => Some("claude-3-5-haiku"),
+            ModelVersion::Claude3Opus => Some("claude-3-opus"),
+            ModelVersion::Claude3Haiku => Some("claude-3-haiku"),
+            ModelVersion::ClaudeInstant => Some("claude-instant"),
+            ModelVersion::Unknown => None,
+        }
+    }
+
+    /// Family of a model ID such as `claude-3-5-sonnet-20241022`.
+    pub fn from_model_id(model: &str) -> Self {
+        Self::KNOWN
+            .into_iter()
+            .find(|v| v.prefix().is_some_and(|p| model.starts_with(p)))
+            .unwrap_or(ModelVersion::Unknown)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,6 +443,25 @@ mod tests {
         assert_ne!(Granularity::Week, Granularity::Month);
     }
 
+    #[test]
+    fn model_version_from_model_id() {
+        let cases = [
+            ("claude-3-5-sonnet-20241022", ModelVersion::Claude35Sonnet),
+            ("claude-3-5-haiku-20241022", ModelVersion::Claude35Haiku),
+            ("claude-3-opus-20240229", ModelVersion::Claude3Opus),
+            ("claude-3-haiku-20240307", ModelVersion::Claude3Haiku),
+            ("claude-instant-1.2", ModelVersion::ClaudeInstant),
+            ("claude-sonnet-4-5", ModelVersion::Unknown),
+            ("gpt-4o", ModelVersion::Unknown),
+            ("", ModelVersion::Unknown),
+        ];
+        for (model, expected) in cases {
+            assert_eq!(ModelVersion::from_model_id(model), expected, "{model}");
+        }
+        assert!(ModelVersion::KNOWN.iter().all(|v| v.prefix().is_some()));
+        assert_eq!(ModelVersion::Unknown.prefix(), None);
+    }
+
     #[test]
     fn metrics_period_variants() {
         assert_ne!(MetricsPeriod::Day, MetricsPeriod::Week);
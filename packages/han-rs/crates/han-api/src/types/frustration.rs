@@ -1,6 +1,11 @@
 //! Frustration summary GraphQL types.
 
 use async_graphql::*;
+use han_indexer::sentiment::{self, FrustrationPoint};
+use sea_orm::sea_query::Expr;
+use sea_orm::Condition;
+
+use crate::types::enums::FrustrationLevel;
 
 /// Aggregated frustration metrics for a session.
 #[derive(Debug, Clone, SimpleObject)]
@@ -12,6 +17,49 @@ pub struct FrustrationSummary {
     pub average_score: Option<f64>,
     pub peak_score: Option<f64>,
     pub top_signals: Option<Vec<String>>,
+    /// Per-message frustration scores and the trend across the session.
+    pub timeline: Option<SessionFrustrationTimeline>,
+}
+
+/// Frustration score of a single analyzed user message.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FrustrationDataPoint {
+    pub timestamp: String,
+    pub frustration_score: f64,
+    pub frustration_level: Option<String>,
+}
+
+/// How frustration evolved over the course of a session.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FrustrationTrend {
+    /// Change in frustration score per hour (least-squares fit).
+    pub slope: f64,
+    pub peak_frustration_score: f64,
+    pub peak_timestamp: Option<String>,
+    /// Frustration dropped by at least 0.3 after the peak.
+    pub recovery_detected: bool,
+    /// Highest per-message frustration level, null if no message showed
+    /// frustration.
+    pub session_level: Option<FrustrationLevel>,
+}
+
+impl From<sentiment::FrustrationTrend> for FrustrationTrend {
+    fn from(t: sentiment::FrustrationTrend) -> Self {
+        Self {
+            slope: t.slope,
+            peak_frustration_score: t.peak_frustration_score,
+            peak_timestamp: t.peak_timestamp,
+            recovery_detected: t.recovery_detected,
+            session_level: t.session_level.map(Into::into),
+        }
+    }
+}
+
+/// Frustration scores over time for a session.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SessionFrustrationTimeline {
+    pub data_points: Vec<FrustrationDataPoint>,
+    pub trend: FrustrationTrend,
 }
 
 impl Default for FrustrationSummary {
@@ -24,7 +72,93 @@ impl Default for FrustrationSummary {
             average_score: Some(0.0),
             peak_score: Some(0.0),
             top_signals: Some(vec![]),
+            timeline: None,
+        }
+    }
+}
+
+impl FrustrationSummary {
+    /// Summarize stored per-message frustration scores, oldest first.
+    pub fn from_points(points: Vec<FrustrationPoint>) -> Self {
+        if points.is_empty() {
+            return Self::default();
+        }
+
+        let count_level = |level: sentiment::FrustrationLevel| {
+            points
+                .iter()
+                .filter(|p| p.frustration_level == Some(level))
+                .count() as i32
+        };
+        let average = points.iter().map(|p| p.frustration_score).sum::<f64>() / points.len() as f64;
+        let trend = sentiment::frustration_trend(&points);
+        let overall_level = trend.session_level.map_or("none", |l| l.as_str());
+
+        Self {
+            total_analyzed: Some(points.len() as i32),
+            moderate_count: Some(count_level(sentiment::FrustrationLevel::Moderate)),
+            high_count: Some(count_level(sentiment::FrustrationLevel::High)),
+            overall_level: Some(overall_level.to_string()),
+            average_score: Some(average),
+            peak_score: Some(trend.peak_frustration_score),
+            top_signals: Some(vec![]),
+            timeline: Some(SessionFrustrationTimeline {
+                data_points: points
+                    .into_iter()
+                    .map(|p| FrustrationDataPoint {
+                        timestamp: p.timestamp,
+                        frustration_score: p.frustration_score,
+                        frustration_level: p.frustration_level.map(|l| l.as_str().to_string()),
+                    })
+                    .collect(),
+                trend: trend.into(),
+            }),
+        }
+    }
+}
+
+/// Session filters on stored per-message frustration.
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct SessionFrustrationFilter {
+    /// Sessions with (true) or without (false) a high-frustration message.
+    pub has_frustration: Option<bool>,
+    /// Lower bound on the mean frustration score of analyzed user messages.
+    pub min_frustration_score: Option<f64>,
+    /// Upper bound on the mean frustration score of analyzed user messages.
+    pub max_frustration_score: Option<f64>,
+}
+
+impl SessionFrustrationFilter {
+    /// Condition on `sessions.id`. Sessions with no analyzed messages never
+    /// match a score bound.
+    pub fn to_condition(&self) -> Condition {
+        let mut cond = Condition::all();
+        if let Some(has) = self.has_frustration {
+            let negate = if has { "" } else { "NOT " };
+            cond = cond.add(Expr::cust(format!(
+                "sessions.id {negate}IN (SELECT session_id FROM messages \
+                 WHERE frustration_level = 'high')"
+            )));
+        }
+        let bounds = [
+            (self.min_frustration_score, ">="),
+            (self.max_frustration_score, "<="),
+        ];
+        for (bound, op) in bounds {
+            if let Some(score) = bound {
+                // Same population and scoring as FrustrationSummary.averageScore
+                cond = cond.add(Expr::cust_with_values(
+                    format!(
+                        "sessions.id IN (SELECT session_id FROM messages \
+                         WHERE message_type = 'user' AND sentiment_score IS NOT NULL \
+                         GROUP BY session_id \
+                         HAVING AVG(COALESCE(frustration_score, 0.0)) {op} ?)"
+                    ),
+                    [score],
+                ));
+            }
         }
+        cond
     }
 }
 
@@ -38,4 +172,126 @@ mod tests {
         assert_eq!(fs.total_analyzed, Some(0));
         assert_eq!(fs.overall_level, Some("none".into()));
     }
+
+    fn point(ts: &str, score: f64, level: Option<&str>) -> FrustrationPoint {
+        FrustrationPoint {
+            timestamp: ts.into(),
+            frustration_score: score,
+            frustration_level: level.and_then(sentiment::FrustrationLevel::parse),
+        }
+    }
+
+    #[test]
+    fn frustration_summary_from_points() {
+        let fs = FrustrationSummary::from_points(vec![
+            point("2026-01-01T00:00:00Z", 0.0, None),
+            point("2026-01-01T01:00:00Z", 7.0, Some("high")),
+            point("2026-01-01T02:00:00Z", 3.5, Some("moderate")),
+            point("2026-01-01T03:00:00Z", 2.5, Some("low")),
+        ]);
+        assert_eq!(fs.total_analyzed, Some(4));
+        assert_eq!(fs.high_count, Some(1));
+        assert_eq!(fs.moderate_count, Some(1));
+        assert_eq!(fs.overall_level.as_deref(), Some("high"));
+        assert_eq!(fs.peak_score, Some(7.0));
+        assert_eq!(fs.average_score, Some(3.25));
+
+        let timeline = fs.timeline.unwrap();
+        assert_eq!(timeline.data_points.len(), 4);
+        assert_eq!(
+            timeline.trend.peak_timestamp.as_deref(),
+            Some("2026-01-01T01:00:00Z")
+        );
+        assert!(timeline.trend.recovery_detected);
+        assert_eq!(timeline.trend.session_level, Some(FrustrationLevel::High));
+    }
+
+    #[test]
+    fn frustration_summary_from_no_points() {
+        let fs = FrustrationSummary::from_points(vec![]);
+        assert_eq!(fs.total_analyzed, Some(0));
+        assert!(fs.timeline.is_none());
+    }
+
+    #[tokio::test]
+    async fn session_frustration_filter_and_max_score() {
+        use crate::loaders::SessionMaxFrustrationLoader;
+        use async_graphql::dataloader::Loader;
+        use han_db::entities::{messages, sessions};
+        use sea_orm::{EntityTrait, QueryFilter, QueryOrder, Set};
+
+        let db = crate::test_db().await;
+
+        // (session, [(score, level)]); s-none has no analyzed messages
+        let scored: [(&str, Vec<(f64, &str)>); 4] = [
+            ("s-calm", vec![(0.5, "low"), (1.5, "low")]),
+            ("s-mixed", vec![(1.0, "low"), (7.0, "high")]),
+            ("s-tense", vec![(4.0, "moderate"), (5.0, "moderate")]),
+            ("s-none", vec![]),
+        ];
+        let mut rows = Vec::new();
+        for (id, points) in &scored {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+            for (line, (score, level)) in points.iter().enumerate() {
+                rows.push(messages::ActiveModel {
+                    id: Set(format!("{id}-{line}")),
+                    session_id: Set(id.to_string()),
+                    message_type: Set("user".to_string()),
+                    timestamp: Set(format!("2026-04-20T10:00:0{line}Z")),
+                    line_number: Set(line as i32),
+                    sentiment_score: Set(Some(0.0)),
+                    frustration_score: Set(Some(*score)),
+                    frustration_level: Set(Some(level.to_string())),
+                    ..Default::default()
+                });
+            }
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let matching = |filter: SessionFrustrationFilter| {
+            let db = &db;
+            async move {
+                let models = sessions::Entity::find()
+                    .filter(filter.to_condition())
+                    .order_by_asc(sessions::Column::Id)
+                    .all(db)
+                    .await
+                    .unwrap();
+                models.into_iter().map(|m| m.id).collect::<Vec<_>>()
+            }
+        };
+        let has = |has_frustration| SessionFrustrationFilter {
+            has_frustration: Some(has_frustration),
+            ..Default::default()
+        };
+        assert_eq!(matching(has(true)).await, ["s-mixed"]);
+        assert_eq!(matching(has(false)).await, ["s-calm", "s-none", "s-tense"]);
+
+        // Means: calm 1.0, mixed 4.0, tense 4.5
+        let between = SessionFrustrationFilter {
+            min_frustration_score: Some(1.0),
+            max_frustration_score: Some(4.0),
+            ..Default::default()
+        };
+        assert_eq!(matching(between).await, ["s-calm", "s-mixed"]);
+        let tense_without_spike = SessionFrustrationFilter {
+            has_frustration: Some(false),
+            min_frustration_score: Some(2.0),
+            ..Default::default()
+        };
+        assert_eq!(matching(tense_without_spike).await, ["s-tense"]);
+        assert_eq!(matching(SessionFrustrationFilter::default()).await.len(), 4);
+
+        let loader = SessionMaxFrustrationLoader { db: db.clone() };
+        let keys: Vec<String> = scored.iter().map(|(id, _)| id.to_string()).collect();
+        let max = loader.load(&keys).await.unwrap();
+        assert_eq!(max.get("s-calm"), Some(&1.5));
+        assert_eq!(max.get("s-mixed"), Some(&7.0));
+        assert_eq!(max.get("s-tense"), Some(&5.0));
+        assert!(!max.contains_key("s-none"));
+    }
 }
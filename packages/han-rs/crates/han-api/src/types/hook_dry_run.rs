@@ -0,0 +1,18 @@
+//! Hook dry-run GraphQL type.
+
+use async_graphql::*;
+
+/// A hook that would run for an event, reported without executing it.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HookDryRunResult {
+    /// Identifier shared with the hook's real executions.
+    pub hook_id: String,
+    pub plugin_name: String,
+    pub hook_name: String,
+    /// Shell command to run, or null for prompt-only hooks.
+    pub command: Option<String>,
+    /// Whether a valid cache entry exists from an earlier successful run.
+    pub is_cached: bool,
+    pub cache_key_hash: String,
+    pub timeout_ms: Option<u32>,
+}
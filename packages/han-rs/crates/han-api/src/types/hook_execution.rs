@@ -90,6 +90,150 @@ pub struct HookTypeStat {
     pub passed: Option<i32>,
 }
 
+/// Hook execution metrics aggregated across sessions.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HookExecutionStats {
+    pub total_executions: i32,
+    pub pass_rate: f64,
+    pub average_duration_ms: f64,
+    pub p50_duration_ms: i32,
+    pub p95_duration_ms: i32,
+    pub failed_hooks: Vec<FailedHookSummary>,
+    pub by_plugin: Vec<PluginHookStats>,
+    pub by_hook_type: Vec<HookTypeStats>,
+}
+
+/// A hook that failed at least once, with its most recent error.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FailedHookSummary {
+    pub hook_name: String,
+    pub plugin_name: String,
+    pub failure_count: i32,
+    pub last_error: Option<String>,
+}
+
+/// Execution totals for one plugin's hooks.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PluginHookStats {
+    pub plugin_name: String,
+    pub total_executions: i32,
+    pub failed: i32,
+    pub pass_rate: f64,
+    pub average_duration_ms: f64,
+}
+
+/// Execution totals for one hook type.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HookTypeStats {
+    pub hook_type: String,
+    pub total_executions: i32,
+    pub failed: i32,
+    pub pass_rate: f64,
+    pub average_duration_ms: f64,
+}
+
+/// Hook executions run by one plugin.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HookPluginStat {
+    pub plugin_name: String,
+    pub execution_count: i32,
+    pub pass_count: i32,
+    pub total_duration_ms: i32,
+}
+
+impl From<han_db::aggregates::HookPluginRow> for HookPluginStat {
+    fn from(row: han_db::aggregates::HookPluginRow) -> Self {
+        Self {
+            plugin_name: row.plugin_name,
+            execution_count: row.execution_count as i32,
+            pass_count: row.pass_count as i32,
+            total_duration_ms: row.total_duration_ms as i32,
+        }
+    }
+}
+
+/// (total, passed, total duration) summed per key, busiest key first.
+fn totals_by<'a>(
+    groups: &'a [han_db::aggregates::HookExecutionGroupRow],
+    key: impl Fn(&'a han_db::aggregates::HookExecutionGroupRow) -> &'a str,
+) -> Vec<(String, i64, i64, i64)> {
+    let mut totals: Vec<(String, i64, i64, i64)> = Vec::new();
+    for g in groups {
+        let k = key(g);
+        match totals.iter_mut().find(|t| t.0 == k) {
+            Some(t) => {
+                t.1 += g.total;
+                t.2 += g.passed;
+                t.3 += g.total_duration_ms;
+            }
+            None => totals.push((k.to_string(), g.total, g.passed, g.total_duration_ms)),
+        }
+    }
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+fn ratio(part: i64, whole: i64) -> f64 {
+    if whole > 0 {
+        part as f64 / whole as f64
+    } else {
+        0.0
+    }
+}
+
+impl From<han_db::aggregates::HookExecutionStatsRows> for HookExecutionStats {
+    fn from(rows: han_db::aggregates::HookExecutionStatsRows) -> Self {
+        let total: i64 = rows.groups.iter().map(|g| g.total).sum();
+        let passed: i64 = rows.groups.iter().map(|g| g.passed).sum();
+        let duration: i64 = rows.groups.iter().map(|g| g.total_duration_ms).sum();
+
+        let mut failed_hooks: Vec<FailedHookSummary> = rows
+            .groups
+            .iter()
+            .filter(|g| g.passed < g.total)
+            .map(|g| FailedHookSummary {
+                hook_name: g.hook_name.clone(),
+                plugin_name: g.plugin_name.clone(),
+                failure_count: (g.total - g.passed) as i32,
+                last_error: g.last_error.clone(),
+            })
+            .collect();
+        failed_hooks.sort_by_key(|f| std::cmp::Reverse(f.failure_count));
+
+        let by_plugin = totals_by(&rows.groups, |g| &g.plugin_name)
+            .into_iter()
+            .map(|(plugin_name, total, passed, duration)| PluginHookStats {
+                plugin_name,
+                total_executions: total as i32,
+                failed: (total - passed) as i32,
+                pass_rate: ratio(passed, total),
+                average_duration_ms: ratio(duration, total),
+            })
+            .collect();
+        let by_hook_type = totals_by(&rows.groups, |g| &g.hook_type)
+            .into_iter()
+            .map(|(hook_type, total, passed, duration)| HookTypeStats {
+                hook_type,
+                total_executions: total as i32,
+                failed: (total - passed) as i32,
+                pass_rate: ratio(passed, total),
+                average_duration_ms: ratio(duration, total),
+            })
+            .collect();
+
+        Self {
+            total_executions: total as i32,
+            pass_rate: ratio(passed, total),
+            average_duration_ms: ratio(duration, total),
+            p50_duration_ms: rows.p50_duration_ms as i32,
+            p95_duration_ms: rows.p95_duration_ms as i32,
+            failed_hooks,
+            by_plugin,
+            by_hook_type,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,4 +387,52 @@ mod tests {
         };
         let _cond = f.to_condition();
     }
+
+    #[test]
+    fn hook_execution_stats_rolls_up_groups() {
+        use han_db::aggregates::{HookExecutionGroupRow, HookExecutionStatsRows};
+
+        let group = |plugin: &str, kind: &str, name: &str, total, passed| HookExecutionGroupRow {
+            plugin_name: plugin.into(),
+            hook_type: kind.into(),
+            hook_name: name.into(),
+            total,
+            passed,
+            total_duration_ms: total * 100,
+            last_error: (passed < total).then(|| format!("{name} failed")),
+        };
+        let stats = HookExecutionStats::from(HookExecutionStatsRows {
+            groups: vec![
+                group("biome", "Stop", "lint", 4, 3),
+                group("jest", "Stop", "test", 3, 1),
+                group("jest", "PostToolUse", "test-changed", 1, 1),
+            ],
+            p50_duration_ms: 100,
+            p95_duration_ms: 250,
+        });
+
+        assert_eq!(stats.total_executions, 8);
+        assert_eq!(stats.pass_rate, 5.0 / 8.0);
+        assert_eq!(stats.average_duration_ms, 100.0);
+        assert_eq!(stats.p95_duration_ms, 250);
+
+        let failed: Vec<_> = stats
+            .failed_hooks
+            .iter()
+            .map(|f| (f.hook_name.as_str(), f.failure_count))
+            .collect();
+        assert_eq!(failed, vec![("test", 2), ("lint", 1)]);
+        assert_eq!(
+            stats.failed_hooks[0].last_error.as_deref(),
+            Some("test failed")
+        );
+
+        assert_eq!(stats.by_plugin[0].plugin_name, "biome");
+        assert_eq!(stats.by_plugin[1].plugin_name, "jest");
+        assert_eq!(stats.by_plugin[1].total_executions, 4);
+        assert_eq!(stats.by_plugin[1].failed, 2);
+        assert_eq!(stats.by_hook_type[0].hook_type, "Stop");
+        assert_eq!(stats.by_hook_type[0].total_executions, 7);
+        assert_eq!(stats.by_hook_type[1].pass_rate, 1.0);
+    }
 }
@@ -0,0 +1,40 @@
+//! Session indexing error GraphQL type.
+
+use async_graphql::*;
+
+/// A transcript line that could not be indexed.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IndexError {
+    /// Zero-based line number in the JSONL transcript.
+    pub line_number: i32,
+    pub error_message: String,
+    pub indexed_at: String,
+}
+
+impl From<han_db::entities::session_index_errors::Model> for IndexError {
+    fn from(m: han_db::entities::session_index_errors::Model) -> Self {
+        Self {
+            line_number: m.line_number,
+            error_message: m.error_message,
+            indexed_at: m.indexed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_model() {
+        let err = IndexError::from(han_db::entities::session_index_errors::Model {
+            id: "e1".into(),
+            session_id: "s1".into(),
+            line_number: 7,
+            error_message: "expected value at line 1 column 1".into(),
+            indexed_at: "2026-04-19T00:00:00Z".into(),
+        });
+        assert_eq!(err.line_number, 7);
+        assert_eq!(err.error_message, "expected value at line 1 column 1");
+    }
+}
@@ -0,0 +1,43 @@
+//! Session indexing warning GraphQL type.
+
+use async_graphql::*;
+
+/// A transcript line that indexed but failed a sanity check.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IndexWarning {
+    /// Zero-based line number in the JSONL transcript.
+    pub line_number: i32,
+    /// Check that failed, e.g. `missing_uuid` or `future_timestamp`.
+    pub warning_type: String,
+    pub detail: String,
+}
+
+impl From<han_db::entities::session_index_warnings::Model> for IndexWarning {
+    fn from(m: han_db::entities::session_index_warnings::Model) -> Self {
+        Self {
+            line_number: m.line_number,
+            warning_type: m.warning_type,
+            detail: m.detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_model() {
+        let warning = IndexWarning::from(han_db::entities::session_index_warnings::Model {
+            id: "w1".into(),
+            session_id: "s1".into(),
+            line_number: 3,
+            warning_type: "missing_uuid".into(),
+            detail: "message has no uuid".into(),
+            indexed_at: "2026-04-22T00:00:00Z".into(),
+        });
+        assert_eq!(warning.line_number, 3);
+        assert_eq!(warning.warning_type, "missing_uuid");
+        assert_eq!(warning.detail, "message has no uuid");
+    }
+}
@@ -1,7 +1,37 @@
 //! Memory-related GraphQL types.
 
+use crate::node::encode_global_id;
 use crate::types::enums::{Confidence, MemoryAgentProgressType};
 use async_graphql::*;
+use han_graphql_derive::GraphQLEntity;
+
+/// A memory recorded by a `memory_learn` event.
+#[derive(Debug, Clone, SimpleObject, GraphQLEntity)]
+#[graphql(complex, name = "Memory")]
+#[graphql_entity(
+    model = "han_db::entities::memories::Model",
+    entity = "han_db::entities::memories::Entity",
+    columns = "han_db::entities::memories::Column",
+    type_name = "Memory"
+)]
+pub struct Memory {
+    #[graphql(skip)]
+    #[graphql_entity(skip, source_field = "id")]
+    pub raw_id: String,
+
+    /// Domain the memory was filed under.
+    pub scope: Option<String>,
+    pub content: String,
+    pub created_at: String,
+}
+
+#[ComplexObject]
+impl Memory {
+    /// Global ID.
+    pub async fn id(&self) -> ID {
+        encode_global_id("Memory", &self.raw_id)
+    }
+}
 
 /// Memory query type (namespace for memory operations).
 #[derive(Debug, Clone, SimpleObject)]
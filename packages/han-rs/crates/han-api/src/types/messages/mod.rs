@@ -14,9 +14,18 @@ use async_graphql::*;
 use han_db::entities::messages;
 
 use crate::connection::PageInfo;
-use crate::loaders::{HookResultByRunIdLoader, ToolResultByCallIdLoader};
+use crate::loaders::{
+    ExposedToolResultLoader, HookResultByRunIdLoader, McpToolResultLoader, MemoryLoader,
+    PluginLoader, ToolResultCompletedAtLoader,
+};
 use crate::node::{encode_global_id, encode_msg_cursor};
-use crate::types::content_blocks::{parse_content_blocks, ContentBlock};
+use crate::types::content_blocks::{
+    parse_content_blocks, stamp_tool_use_blocks, tool_duration_ms, word_boundary_preview,
+    ContentBlock, TextBlockData, ThinkingBlockData,
+};
+use crate::types::enums::ModelVersion;
+use crate::types::memory::Memory;
+use crate::types::plugin::PluginInfo;
 use crate::types::sentiment::SentimentAnalysis;
 
 // ============================================================================
@@ -30,6 +39,7 @@ pub struct MessageData {
     pub session_id: String,
     pub project_dir: String,
     pub line_number: i32,
+    pub byte_offset: Option<i64>,
     pub timestamp: String,
     pub raw_json: Option<String>,
     pub agent_id: Option<String>,
@@ -42,6 +52,9 @@ pub struct MessageData {
     pub sentiment_level: Option<String>,
     pub frustration_score: Option<f64>,
     pub frustration_level: Option<String>,
+    pub duration_ms: Option<i32>,
+    pub memory_id: Option<String>,
+    pub plugin_name: Option<String>,
 }
 
 impl MessageData {
@@ -52,6 +65,7 @@ impl MessageData {
             session_id: model.session_id.clone(),
             project_dir: project_dir.to_string(),
             line_number: model.line_number,
+            byte_offset: model.byte_offset,
             timestamp: model.timestamp.clone(),
             raw_json: model.raw_json.clone(),
             agent_id: model.agent_id.clone(),
@@ -64,6 +78,9 @@ impl MessageData {
             sentiment_level: model.sentiment_level.clone(),
             frustration_score: model.frustration_score,
             frustration_level: model.frustration_level.clone(),
+            duration_ms: model.duration_ms,
+            memory_id: model.memory_id.clone(),
+            plugin_name: model.plugin_name.clone(),
         }
     }
 
@@ -217,6 +234,7 @@ impl RegularUserMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     async fn sentiment_analysis(&self) -> Option<SentimentAnalysis> {
@@ -261,6 +279,7 @@ impl CommandUserMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     async fn sentiment_analysis(&self) -> Option<SentimentAnalysis> {
@@ -309,6 +328,7 @@ impl InterruptUserMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     async fn sentiment_analysis(&self) -> Option<SentimentAnalysis> {
@@ -353,6 +373,7 @@ impl MetaUserMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     async fn sentiment_analysis(&self) -> Option<SentimentAnalysis> {
@@ -397,6 +418,7 @@ impl ToolResultUserMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     async fn sentiment_analysis(&self) -> Option<SentimentAnalysis> {
@@ -429,6 +451,45 @@ pub struct AssistantMessage {
     pub data: MessageData,
 }
 
+impl AssistantMessage {
+    /// Estimated cost of this message's token usage, and whether the model was
+    /// priced by family prefix. `None` when the model has no known pricing.
+    fn cost_estimate(&self) -> Option<(f64, bool)> {
+        let model = self.model_id()?;
+        let m = crate::types::pricing::lookup_price(&model)?;
+        let tokens = |field| {
+            parse_json_field_i64(&self.data.raw_json, &["message", "usage", field])
+                .or_else(|| parse_json_field_i64(&self.data.raw_json, &["usage", field]))
+                .unwrap_or(0)
+        };
+        let cost = m.price.cost(
+            tokens("input_tokens"),
+            tokens("output_tokens"),
+            tokens("cache_read_input_tokens"),
+            tokens("cache_creation_input_tokens"),
+        );
+        Some((cost, m.is_estimated))
+    }
+
+    /// Model ID from a transcript line's `message`, or a bare message object.
+    fn model_id(&self) -> Option<String> {
+        parse_json_field(&self.data.raw_json, &["message", "model"])
+            .or_else(|| parse_json_field(&self.data.raw_json, &["model"]))
+    }
+}
+
+/// Total milliseconds from `started_at` to each completion time, or `None` if
+/// no duration could be computed.
+fn sum_tool_durations<'a>(
+    started_at: &str,
+    completed_at: impl IntoIterator<Item = &'a String>,
+) -> Option<i32> {
+    completed_at
+        .into_iter()
+        .filter_map(|c| tool_duration_ms(started_at, c))
+        .reduce(|a, b| a.saturating_add(b))
+}
+
 #[Object]
 impl AssistantMessage {
     async fn id(&self) -> ID {
@@ -460,11 +521,14 @@ impl AssistantMessage {
 
     /// Parsed content blocks (text, thinking, tool_use, etc.).
     async fn content_blocks(&self) -> Option<Vec<ContentBlock>> {
-        Some(parse_content_blocks(
+        let mut blocks = parse_content_blocks(
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
-        ))
+            Some(&self.data.id),
+        );
+        stamp_tool_use_blocks(&mut blocks, &self.data.timestamp);
+        Some(blocks)
     }
 
     /// Model ID that generated this message.
@@ -472,6 +536,16 @@ impl AssistantMessage {
         parse_json_field(&self.data.raw_json, &["model"])
     }
 
+    /// Model family that generated this message.
+    async fn model_version(&self) -> Option<ModelVersion> {
+        self.model_id().map(|m| ModelVersion::from_model_id(&m))
+    }
+
+    /// Model ID behind `modelVersion`, for telling `UNKNOWN` models apart.
+    async fn model_version_raw(&self) -> Option<String> {
+        self.model_id()
+    }
+
     /// Stop reason.
     async fn stop_reason(&self) -> Option<String> {
         parse_json_field(&self.data.raw_json, &["stop_reason"])
@@ -483,6 +557,7 @@ impl AssistantMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         );
         if blocks.is_empty() {
             return Some(false);
@@ -496,6 +571,7 @@ impl AssistantMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         );
         Some(
             blocks
@@ -510,6 +586,7 @@ impl AssistantMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         );
         Some(
             blocks
@@ -519,12 +596,72 @@ impl AssistantMessage {
         )
     }
 
+    /// Thinking blocks with full text, word-boundary preview, and word count.
+    async fn thinking_blocks(&self) -> Option<Vec<ThinkingBlockData>> {
+        let blocks = parse_content_blocks(
+            self.data.content.as_deref(),
+            self.data.raw_json.as_deref(),
+            Some(&self.data.session_id),
+            Some(&self.data.id),
+        );
+        Some(
+            blocks
+                .iter()
+                .filter_map(|b| match b {
+                    ContentBlock::Thinking(t) => Some(t.into()),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Total word count across all thinking blocks.
+    async fn thinking_word_count(&self) -> Option<i32> {
+        let blocks = parse_content_blocks(
+            self.data.content.as_deref(),
+            self.data.raw_json.as_deref(),
+            Some(&self.data.session_id),
+            Some(&self.data.id),
+        );
+        Some(
+            blocks
+                .iter()
+                .map(|b| match b {
+                    ContentBlock::Thinking(t) => {
+                        crate::types::content_blocks::word_count(&t.thinking)
+                    }
+                    _ => 0,
+                })
+                .sum(),
+        )
+    }
+
+    /// Text blocks with word counts.
+    async fn text_blocks(&self) -> Option<Vec<TextBlockData>> {
+        let blocks = parse_content_blocks(
+            self.data.content.as_deref(),
+            self.data.raw_json.as_deref(),
+            Some(&self.data.session_id),
+            Some(&self.data.id),
+        );
+        Some(
+            blocks
+                .iter()
+                .filter_map(|b| match b {
+                    ContentBlock::Text(t) => Some(t.into()),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
     /// Whether this message contains tool use blocks.
     async fn has_tool_use(&self) -> Option<bool> {
         let blocks = parse_content_blocks(
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         );
         Some(blocks.iter().any(|b| matches!(b, ContentBlock::ToolUse(_))))
     }
@@ -535,6 +672,7 @@ impl AssistantMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         );
         Some(
             blocks
@@ -544,6 +682,30 @@ impl AssistantMessage {
         )
     }
 
+    /// Summed durations of this message's tool calls whose results are
+    /// indexed. Null when none are.
+    async fn total_tool_duration_ms(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let tool_call_ids: Vec<String> = parse_content_blocks(
+            self.data.content.as_deref(),
+            self.data.raw_json.as_deref(),
+            Some(&self.data.session_id),
+            Some(&self.data.id),
+        )
+        .into_iter()
+        .filter_map(|b| match b {
+            ContentBlock::ToolUse(t) => Some(t.tool_call_id),
+            _ => None,
+        })
+        .collect();
+        if tool_call_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let loader = ctx.data::<DataLoader<ToolResultCompletedAtLoader>>()?;
+        let completed = loader.load_many(tool_call_ids).await?;
+        Ok(sum_tool_durations(&self.data.timestamp, completed.values()))
+    }
+
     /// Input tokens used.
     async fn input_tokens(&self) -> Option<i64> {
         parse_json_field_i64(&self.data.raw_json, &["usage", "input_tokens"])
@@ -565,6 +727,17 @@ impl AssistantMessage {
             },
         )
     }
+
+    /// Estimated USD cost of this message's tokens, if the model is priced.
+    async fn estimated_cost_usd(&self) -> Option<f64> {
+        self.cost_estimate().map(|(cost, _)| cost)
+    }
+
+    /// Whether `estimatedCostUsd` used family pricing rather than an exact
+    /// model match, so may be approximate.
+    async fn is_estimated(&self) -> Option<bool> {
+        self.cost_estimate().map(|(_, estimated)| estimated)
+    }
 }
 
 // ============================================================================
@@ -610,6 +783,7 @@ impl SummaryMessage {
             self.data.content.as_deref(),
             self.data.raw_json.as_deref(),
             Some(&self.data.session_id),
+            Some(&self.data.id),
         ))
     }
     /// Whether this is a context compaction summary.
@@ -624,6 +798,32 @@ pub struct SystemMessage {
     pub data: MessageData,
 }
 
+/// Maximum preview length (in characters) for [`SystemMessage`] content.
+const SYSTEM_CONTENT_PREVIEW_CHARS: usize = 200;
+
+impl SystemMessage {
+    /// A string field from `data`, falling back to the top level of the line
+    /// (where Claude Code transcripts record `level` and `subtype`).
+    fn system_field(&self, field: &str) -> Option<String> {
+        parse_data_field(&self.data.raw_json, field)
+            .or_else(|| parse_json_field(&self.data.raw_json, &[field]))
+    }
+
+    /// Base search text plus level and subtype, so `warning` or
+    /// `compact_boundary` find the message.
+    fn system_search_text(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            self.data.search_text(),
+            self.system_field("level"),
+            self.system_field("subtype"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        Some(parts.join(" ").to_lowercase())
+    }
+}
+
 #[Object]
 impl SystemMessage {
     async fn id(&self) -> ID {
@@ -645,23 +845,37 @@ impl SystemMessage {
         self.data.parent_id.as_deref()
     }
     async fn search_text(&self) -> Option<String> {
-        self.data.search_text()
+        self.system_search_text()
     }
     /// System message text content.
     async fn content(&self) -> Option<String> {
         self.data.content_text()
     }
-    /// Whether this is a meta message.
+    /// First 200 characters of the content, cut at a word boundary.
+    async fn content_preview(&self) -> Option<String> {
+        let content = self.data.content_text()?;
+        Some(word_boundary_preview(
+            &content,
+            SYSTEM_CONTENT_PREVIEW_CHARS,
+        ))
+    }
+    /// Whether this is a meta message (`isMeta` on the transcript line).
     async fn is_meta(&self) -> Option<bool> {
-        Some(false)
+        Some(parse_json_field_bool(&self.data.raw_json, &["isMeta"]).unwrap_or(false))
     }
     /// Message severity level.
     async fn level(&self) -> Option<String> {
-        None
+        self.system_field("level")
     }
     /// System message subtype.
     async fn subtype(&self) -> Option<String> {
-        None
+        self.system_field("subtype")
+    }
+    /// When the message was injected into the conversation. Falls back to the
+    /// message timestamp when no injection time was recorded.
+    async fn injected_at(&self) -> String {
+        parse_data_field(&self.data.raw_json, "injected_at")
+            .unwrap_or_else(|| self.data.timestamp.clone())
     }
 }
 
@@ -751,8 +965,16 @@ impl HookRunMessage {
     async fn hook(&self) -> Option<String> {
         parse_data_field(&self.data.raw_json, "hook")
     }
-    async fn plugin(&self) -> Option<String> {
-        parse_data_field(&self.data.raw_json, "plugin")
+    async fn plugin(&self) -> Option<&str> {
+        self.data.plugin_name.as_deref()
+    }
+    /// The installed plugin that ran this hook, if it is recorded.
+    async fn plugin_info(&self, ctx: &Context<'_>) -> Result<Option<PluginInfo>> {
+        let Some(plugin) = self.data.plugin_name.clone() else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<PluginLoader>>()?;
+        Ok(loader.load_one(plugin).await?.map(PluginInfo::from))
     }
     async fn hook_type(&self) -> Option<String> {
         parse_data_field(&self.data.raw_json, "hook_type")
@@ -1279,14 +1501,12 @@ impl McpToolCallMessage {
         parse_data_field(&self.data.raw_json, "prefixed_name")
     }
     async fn input(&self) -> Option<String> {
-        if let Some(ref raw) = self.data.raw_json {
-            if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(raw) {
-                if let Some(args) = parsed.get("data").and_then(|d| d.get("arguments")) {
-                    return Some(serde_json::to_string_pretty(args).unwrap_or_default());
-                }
-            }
-        }
-        None
+        parse_data_value(&self.data.raw_json, "arguments")
+            .map(|args| serde_json::to_string_pretty(&args).unwrap_or_default())
+    }
+    /// Call arguments as JSON, for clients that process them.
+    async fn input_json(&self) -> Option<Json<serde_json::Value>> {
+        parse_data_value(&self.data.raw_json, "arguments").map(Json)
     }
     /// Tool result resolved inline via DataLoader.
     async fn result(&self, ctx: &Context<'_>) -> Result<Option<McpToolResult>> {
@@ -1294,10 +1514,14 @@ impl McpToolCallMessage {
         let Some(call_id) = call_id else {
             return Ok(None);
         };
-        let loader = ctx.data::<DataLoader<ToolResultByCallIdLoader>>()?;
+        let loader = ctx.data::<DataLoader<McpToolResultLoader>>()?;
         let model = loader.load_one(call_id).await?;
         Ok(model.map(|m| McpToolResult::from_model(&m)))
     }
+    /// Milliseconds from this call to its result, set by the indexer.
+    async fn duration_ms(&self) -> Option<i32> {
+        self.data.duration_ms
+    }
 }
 
 /// MCP tool result data.
@@ -1378,6 +1602,27 @@ impl McpToolResultMessage {
     async fn error(&self) -> Option<String> {
         parse_data_field(&self.data.raw_json, "error")
     }
+    /// Input tokens reported by the MCP server in `data.usage`.
+    async fn input_tokens(&self) -> Option<i64> {
+        parse_json_field_i64(&self.data.raw_json, &["data", "usage", "input_tokens"])
+    }
+    /// Output tokens reported by the MCP server in `data.usage`.
+    async fn output_tokens(&self) -> Option<i64> {
+        parse_json_field_i64(&self.data.raw_json, &["data", "usage", "output_tokens"])
+    }
+    /// Estimated USD cost of the reported usage. Null without usage or when
+    /// `data.model` is missing or has no known pricing.
+    async fn cost_usd(&self) -> Option<f64> {
+        let raw = &self.data.raw_json;
+        let input = parse_json_field_i64(raw, &["data", "usage", "input_tokens"]);
+        let output = parse_json_field_i64(raw, &["data", "usage", "output_tokens"]);
+        if input.is_none() && output.is_none() {
+            return None;
+        }
+        let model = parse_json_field(raw, &["data", "model"])?;
+        let price = crate::types::pricing::lookup_price(&model)?.price;
+        Some(price.cost(input.unwrap_or(0), output.unwrap_or(0), 0, 0))
+    }
 }
 
 // ============================================================================
@@ -1426,14 +1671,12 @@ impl ExposedToolCallMessage {
         parse_data_field(&self.data.raw_json, "prefixed_name")
     }
     async fn input(&self) -> Option<String> {
-        if let Some(ref raw) = self.data.raw_json {
-            if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(raw) {
-                if let Some(args) = parsed.get("data").and_then(|d| d.get("arguments")) {
-                    return Some(serde_json::to_string_pretty(args).unwrap_or_default());
-                }
-            }
-        }
-        None
+        parse_data_value(&self.data.raw_json, "arguments")
+            .map(|args| serde_json::to_string_pretty(&args).unwrap_or_default())
+    }
+    /// Call arguments as JSON, for clients that process them.
+    async fn input_json(&self) -> Option<Json<serde_json::Value>> {
+        parse_data_value(&self.data.raw_json, "arguments").map(Json)
     }
     /// Tool result resolved inline via DataLoader.
     async fn result(&self, ctx: &Context<'_>) -> Result<Option<ExposedToolResult>> {
@@ -1441,10 +1684,14 @@ impl ExposedToolCallMessage {
         let Some(call_id) = call_id else {
             return Ok(None);
         };
-        let loader = ctx.data::<DataLoader<ToolResultByCallIdLoader>>()?;
+        let loader = ctx.data::<DataLoader<ExposedToolResultLoader>>()?;
         let model = loader.load_one(call_id).await?;
         Ok(model.map(|m| ExposedToolResult::from_model(&m)))
     }
+    /// Milliseconds from this call to its result, set by the indexer.
+    async fn duration_ms(&self) -> Option<i32> {
+        self.data.duration_ms
+    }
 }
 
 /// Exposed tool result data.
@@ -1625,6 +1872,18 @@ impl MemoryLearnMessage {
                 .collect(),
         )
     }
+    /// ID of the memory this message recorded.
+    async fn learned_memory_id(&self) -> Option<&str> {
+        self.data.memory_id.as_deref()
+    }
+    /// The memory this message recorded.
+    async fn learned_memory(&self, ctx: &Context<'_>) -> Result<Option<Memory>> {
+        let Some(memory_id) = self.data.memory_id.clone() else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<MemoryLoader>>()?;
+        Ok(loader.load_one(memory_id).await?.map(Memory::from))
+    }
 }
 
 // ============================================================================
@@ -1745,6 +2004,22 @@ pub struct MessageEdge {
     pub node: Message,
     /// Cursor for this edge.
     pub cursor: String,
+    /// Line number of the message in its source JSONL file.
+    pub line_number: i32,
+    /// Byte offset of that line in the source JSONL file, if recorded.
+    pub byte_offset: Option<i64>,
+}
+
+impl MessageEdge {
+    /// Build an edge for a message, exposing its position in the source file.
+    pub fn new(data: MessageData, cursor: String) -> Self {
+        Self {
+            line_number: data.line_number,
+            byte_offset: data.byte_offset,
+            node: discriminate_message(data),
+            cursor,
+        }
+    }
 }
 
 /// Message connection with pagination.
@@ -1756,6 +2031,107 @@ pub struct MessageConnection {
     pub page_info: PageInfo,
     /// Total number of messages.
     pub total_count: i32,
+    /// Message type breakdown over the messages this connection loaded.
+    pub stats: MessageConnectionStats,
+}
+
+/// Per-type message counts for a connection, computed without extra queries.
+#[derive(Debug, Clone, Default, PartialEq, SimpleObject)]
+pub struct MessageConnectionStats {
+    /// User messages of every kind, including tool results.
+    pub user_message_count: i32,
+    /// Assistant messages.
+    pub assistant_message_count: i32,
+    /// Hook events (runs, results, validations, ...).
+    pub hook_event_count: i32,
+    /// MCP tool calls.
+    pub mcp_tool_call_count: i32,
+    /// System messages.
+    pub system_message_count: i32,
+    /// Thinking blocks across all assistant messages.
+    pub total_thinking_blocks: i32,
+    /// Tool use blocks across all assistant messages.
+    pub total_tool_use_calls: i32,
+    /// Sentiment over the user messages.
+    pub sentiment_stats: SentimentStats,
+}
+
+/// Sentiment summary over a set of user messages.
+#[derive(Debug, Clone, Default, PartialEq, SimpleObject)]
+pub struct SentimentStats {
+    /// Mean sentiment score of analyzed messages. Null when none are analyzed.
+    pub average_score: Option<f64>,
+    /// Messages at moderate or high frustration.
+    pub frustration_message_count: i32,
+}
+
+impl MessageConnectionStats {
+    /// Tally already-discriminated edges.
+    pub fn from_edges(edges: &[MessageEdge]) -> Self {
+        let mut stats = Self::default();
+        let mut sentiment_scores = Vec::new();
+
+        for edge in edges {
+            let data = match &edge.node {
+                Message::RegularUser(m) => &m.data,
+                Message::CommandUser(m) => &m.data,
+                Message::InterruptUser(m) => &m.data,
+                Message::MetaUser(m) => &m.data,
+                Message::ToolResultUser(m) => &m.data,
+                Message::Assistant(m) => {
+                    stats.assistant_message_count += 1;
+                    let blocks = parse_content_blocks(
+                        m.data.content.as_deref(),
+                        m.data.raw_json.as_deref(),
+                        Some(&m.data.session_id),
+                        Some(&m.data.id),
+                    );
+                    for block in &blocks {
+                        match block {
+                            ContentBlock::Thinking(_) => stats.total_thinking_blocks += 1,
+                            ContentBlock::ToolUse(_) => stats.total_tool_use_calls += 1,
+                            _ => {}
+                        }
+                    }
+                    continue;
+                }
+                Message::HookRun(_)
+                | Message::HookResult(_)
+                | Message::HookCheckState(_)
+                | Message::HookReference(_)
+                | Message::HookValidation(_)
+                | Message::HookScript(_)
+                | Message::HookDatetime(_)
+                | Message::HookFileChange(_)
+                | Message::HookValidationCache(_) => {
+                    stats.hook_event_count += 1;
+                    continue;
+                }
+                Message::McpToolCall(_) => {
+                    stats.mcp_tool_call_count += 1;
+                    continue;
+                }
+                Message::System(_) => {
+                    stats.system_message_count += 1;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            // Every other variant has continued, so this is a user message
+            stats.user_message_count += 1;
+            sentiment_scores.extend(data.sentiment_score);
+            if matches!(data.frustration_level.as_deref(), Some("moderate" | "high")) {
+                stats.sentiment_stats.frustration_message_count += 1;
+            }
+        }
+
+        if !sentiment_scores.is_empty() {
+            stats.sentiment_stats.average_score =
+                Some(sentiment_scores.iter().sum::<f64>() / sentiment_scores.len() as f64);
+        }
+        stats
+    }
 }
 
 // ============================================================================
@@ -1913,7 +2289,7 @@ fn is_tool_result_user_message(data: &MessageData) -> bool {
     false
 }
 
-fn is_tool_result_user_model(msg: &messages::Model) -> bool {
+pub(crate) fn is_tool_result_user_model(msg: &messages::Model) -> bool {
     if msg.message_type != "user" {
         return false;
     }
@@ -1964,6 +2340,15 @@ fn parse_json_field_i64(raw_json: &Option<String>, path: &[&str]) -> Option<i64>
     parsed.as_i64()
 }
 
+fn parse_json_field_bool(raw_json: &Option<String>, path: &[&str]) -> Option<bool> {
+    let raw = raw_json.as_ref()?;
+    let mut parsed: serde_json::Value = serde_json::from_str(raw).ok()?;
+    for key in path {
+        parsed = parsed.get(*key)?.clone();
+    }
+    parsed.as_bool()
+}
+
 fn parse_data_field(raw_json: &Option<String>, field: &str) -> Option<String> {
     let raw = raw_json.as_ref()?;
     let parsed: serde_json::Value = serde_json::from_str(raw).ok()?;
@@ -1974,6 +2359,15 @@ fn parse_data_field(raw_json: &Option<String>, field: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+fn parse_data_value(raw_json: &Option<String>, field: &str) -> Option<serde_json::Value> {
+    let raw = raw_json.as_ref()?;
+    let mut parsed: serde_json::Value = serde_json::from_str(raw).ok()?;
+    parsed
+        .get_mut("data")
+        .and_then(|d| d.get_mut(field))
+        .map(serde_json::Value::take)
+}
+
 fn parse_data_field_bool(raw_json: &Option<String>, field: &str) -> Option<bool> {
     let raw = raw_json.as_ref()?;
     let parsed: serde_json::Value = serde_json::from_str(raw).ok()?;
@@ -2047,6 +2441,7 @@ mod tests {
             session_id: "session-1".into(),
             project_dir: "/project".into(),
             line_number: 1,
+            byte_offset: None,
             timestamp: "2024-01-01T00:00:00Z".into(),
             raw_json: None,
             agent_id: None,
@@ -2059,6 +2454,9 @@ mod tests {
             sentiment_level: None,
             frustration_score: None,
             frustration_level: None,
+            duration_ms: None,
+            memory_id: None,
+            plugin_name: None,
         }
     }
 
@@ -2081,6 +2479,7 @@ mod tests {
             raw_json: raw_json.map(|s| s.into()),
             timestamp: "2024-01-01T00:00:00Z".into(),
             line_number: 1,
+            byte_offset: None,
             source_file_name: None,
             source_file_type: None,
             sentiment_score: None,
@@ -2095,6 +2494,9 @@ mod tests {
             lines_removed: None,
             files_changed: None,
             human_time_ms: None,
+            duration_ms: None,
+            memory_id: None,
+            plugin_name: None,
             indexed_at: None,
         }
     }
@@ -2108,6 +2510,68 @@ mod tests {
         ));
     }
 
+    fn make_assistant(raw_json: &str) -> AssistantMessage {
+        let mut data = make_data("assistant", None);
+        data.raw_json = Some(raw_json.into());
+        AssistantMessage { data }
+    }
+
+    #[test]
+    fn test_assistant_cost_estimate() {
+        // 1M input + 1M output + 1M cache read + 1M cache creation at Opus 4 rates
+        let exact = make_assistant(
+            r#"{"model":"claude-opus-4-20250514","usage":{"input_tokens":1000000,"output_tokens":1000000,"cache_read_input_tokens":1000000,"cache_creation_input_tokens":1000000}}"#,
+        );
+        let (cost, estimated) = exact.cost_estimate().unwrap();
+        assert!((cost - 110.25).abs() < 0.0001);
+        assert!(!estimated);
+
+        // Unlisted snapshots are priced by their model family
+        let unlisted = make_assistant(
+            r#"{"model":"claude-opus-4-6","usage":{"input_tokens":1000000,"output_tokens":0}}"#,
+        );
+        let (cost, estimated) = unlisted.cost_estimate().unwrap();
+        assert!((cost - 15.0).abs() < 0.0001);
+        assert!(estimated);
+
+        let zero = make_assistant(r#"{"model":"claude-3-haiku-20240307","usage":{}}"#);
+        assert_eq!(zero.cost_estimate(), Some((0.0, false)));
+
+        let unknown = make_assistant(r#"{"model":"gpt-4o","usage":{"input_tokens":10}}"#);
+        assert!(unknown.cost_estimate().is_none());
+        assert!(make_assistant("{}").cost_estimate().is_none());
+    }
+
+    #[test]
+    fn test_assistant_cost_estimate_from_transcript_line() {
+        // Indexed rows store the full transcript line, with model and usage under `message`
+        let line = make_assistant(
+            r#"{"type":"assistant","uuid":"a1","message":{"role":"assistant","model":"claude-3-5-haiku-20241022","content":[],"usage":{"input_tokens":1000000,"output_tokens":1000000}}}"#,
+        );
+        let (cost, estimated) = line.cost_estimate().unwrap();
+        assert!((cost - 4.80).abs() < 0.0001);
+        assert!(!estimated);
+    }
+
+    #[test]
+    fn test_assistant_model_id() {
+        let line = make_assistant(r#"{"message":{"model":"claude-3-5-sonnet-20241022"}}"#);
+        let version = ModelVersion::from_model_id(&line.model_id().unwrap());
+        assert_eq!(version, ModelVersion::Claude35Sonnet);
+
+        let bare = make_assistant(r#"{"model":"claude-3-opus-20240229"}"#);
+        assert_eq!(bare.model_id().as_deref(), Some("claude-3-opus-20240229"));
+
+        // Unknown models keep their raw ID
+        let other = make_assistant(r#"{"message":{"model":"gpt-4o"}}"#);
+        let model = other.model_id().unwrap();
+        assert_eq!(model, "gpt-4o");
+        assert_eq!(ModelVersion::from_model_id(&model), ModelVersion::Unknown);
+
+        assert_eq!(make_assistant(r#"{"message":{}}"#).model_id(), None);
+        assert_eq!(make_assistant(r#"{"model":null}"#).model_id(), None);
+    }
+
     #[test]
     fn test_discriminate_assistant() {
         let data = make_data("assistant", None);
@@ -2120,6 +2584,37 @@ mod tests {
         assert!(matches!(discriminate_message(data), Message::System(_)));
     }
 
+    #[test]
+    fn test_system_message_fields() {
+        let mut data = make_data("system", None);
+        data.raw_json = Some(
+            r#"{"type":"system","subtype":"compact_boundary","level":"warning","isMeta":true}"#
+                .into(),
+        );
+        assert_eq!(
+            parse_json_field_bool(&data.raw_json, &["isMeta"]),
+            Some(true)
+        );
+        let msg = SystemMessage { data };
+        assert_eq!(
+            msg.system_field("subtype").as_deref(),
+            Some("compact_boundary")
+        );
+        assert_eq!(msg.system_field("level").as_deref(), Some("warning"));
+        assert_eq!(
+            msg.system_search_text().as_deref(),
+            Some("test content system warning compact_boundary")
+        );
+
+        // Fields under `data` take precedence over the top level.
+        let mut data = make_data("system", None);
+        data.raw_json = Some(r#"{"level":"error","data":{"level":"info"}}"#.into());
+        assert_eq!(parse_json_field_bool(&data.raw_json, &["isMeta"]), None);
+        let msg = SystemMessage { data };
+        assert_eq!(msg.system_field("level").as_deref(), Some("info"));
+        assert_eq!(msg.system_field("subtype"), None);
+    }
+
     #[test]
     fn test_discriminate_summary() {
         let data = make_data("summary", None);
@@ -2192,6 +2687,106 @@ mod tests {
         assert!(conn.page_info.has_next_page);
     }
 
+    #[test]
+    fn test_build_message_connection_edge_position() {
+        let models: Vec<_> = (0..3)
+            .map(|i| {
+                let mut m = make_model("user", None, None);
+                m.id = format!("uuid-{i}");
+                m.content = Some(format!("message {i}"));
+                m.line_number = 10 + i;
+                m.byte_offset = Some(i as i64 * 128);
+                m
+            })
+            .collect();
+        let conn = build_message_connection(&models, "/proj", None, None, None, None);
+        let positions: Vec<_> = conn
+            .edges
+            .iter()
+            .map(|e| (e.line_number, e.byte_offset))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(10, Some(0)), (11, Some(128)), (12, Some(256))]
+        );
+    }
+
+    #[test]
+    fn test_build_message_connection_stats() {
+        let fixtures: Vec<(&str, Option<&str>, Option<&str>)> = vec![
+            ("user", None, None),
+            ("user", None, Some(r#"{"isCommand":true}"#)),
+            (
+                "user",
+                None,
+                Some(r#"{"message":{"content":[{"type":"tool_result","tool_use_id":"t1"}]}}"#),
+            ),
+            (
+                "assistant",
+                None,
+                Some(
+                    r#"{"message":{"content":[
+                        {"type":"thinking","thinking":"hmm"},
+                        {"type":"thinking","thinking":"ok"},
+                        {"type":"tool_use","id":"t1","name":"Read","input":{}},
+                        {"type":"text","text":"done"}
+                    ]}}"#,
+                ),
+            ),
+            (
+                "assistant",
+                None,
+                Some(
+                    r#"{"message":{"content":[{"type":"tool_use","id":"t2","name":"Bash","input":{}}]}}"#,
+                ),
+            ),
+            ("han_event", Some("hook_run"), None),
+            ("han_event", Some("hook_result"), None),
+            ("han_event", Some("hook_validation_cache"), None),
+            ("han_event", Some("mcp_tool_call"), None),
+            ("han_event", Some("mcp_tool_result"), None),
+            ("system", None, None),
+            ("summary", None, None),
+        ];
+        let mut models: Vec<_> = fixtures
+            .into_iter()
+            .enumerate()
+            .map(|(i, (message_type, tool_name, raw_json))| {
+                let mut m = make_model(message_type, tool_name, raw_json);
+                m.id = format!("uuid-{i}");
+                m
+            })
+            .collect();
+        models[0].sentiment_score = Some(0.5);
+        models[0].frustration_level = Some("low".into());
+        models[1].sentiment_score = Some(-0.3);
+        models[1].frustration_level = Some("high".into());
+
+        // Stats cover every message, not just the requested page
+        let conn = build_message_connection(&models, "/proj", Some(1), None, None, None);
+        assert_eq!(conn.edges.len(), 1);
+        assert_eq!(
+            conn.stats,
+            MessageConnectionStats {
+                user_message_count: 3,
+                assistant_message_count: 2,
+                hook_event_count: 3,
+                mcp_tool_call_count: 1,
+                system_message_count: 1,
+                total_thinking_blocks: 2,
+                total_tool_use_calls: 2,
+                sentiment_stats: SentimentStats {
+                    average_score: Some(0.1),
+                    frustration_message_count: 1,
+                },
+            }
+        );
+
+        let empty = build_message_connection(&[], "/proj", None, None, None, None);
+        assert_eq!(empty.stats, MessageConnectionStats::default());
+        assert_eq!(empty.stats.sentiment_stats.average_score, None);
+    }
+
     #[test]
     fn message_filter_default_is_empty() {
         let f = MessageFilter::default();
@@ -2252,6 +2847,196 @@ mod tests {
         };
         let _cond = f.to_condition();
     }
+
+    #[test]
+    fn sum_tool_durations_skips_unparseable() {
+        let completed = [
+            "2024-01-01T00:00:01.500Z".to_string(),
+            "2024-01-01T00:00:03Z".to_string(),
+            "garbage".to_string(),
+        ];
+        assert_eq!(
+            sum_tool_durations("2024-01-01T00:00:00Z", &completed),
+            Some(4500)
+        );
+        assert_eq!(sum_tool_durations("2024-01-01T00:00:00Z", &[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_tool_input_json_over_graphql() {
+        use han_db::entities::messages;
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let args = serde_json::json!({ "query": "caf\u{e9}", "limit": 5, "tags": ["a", "b"] });
+        let han_event = |kind: &str| {
+            serde_json::json!({ "type": kind, "data": { "tool": "search", "arguments": args } })
+                .to_string()
+        };
+        let tool_use = serde_json::json!({
+            "message": { "role": "assistant", "content": [
+                { "type": "tool_use", "id": "call_1", "name": "Grep", "input": args },
+            ] }
+        });
+        // Han events are stored with their event type as the tool name
+        let row = |line: i32, event: Option<&str>, raw_json: String| messages::ActiveModel {
+            id: Set(format!("m{line}")),
+            session_id: Set("s1".to_string()),
+            message_type: Set(event.map_or("assistant", |_| "han_event").to_string()),
+            tool_name: Set(event.map(str::to_string)),
+            content: Set(Some("ok".to_string())),
+            raw_json: Set(Some(raw_json)),
+            timestamp: Set(format!("2026-04-20T10:00:0{line}Z")),
+            line_number: Set(line),
+            ..Default::default()
+        };
+        let models = vec![
+            row(0, None, tool_use.to_string()),
+            row(1, Some("mcp_tool_call"), han_event("mcp_tool_call")),
+            row(2, Some("exposed_tool_call"), han_event("exposed_tool_call")),
+        ];
+        han_db::crud::messages::insert_batch(&db, models)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let res = schema
+            .execute(
+                r#"{ session(id: "s1") { messages { edges { node {
+                    __typename
+                    ... on McpToolCallMessage { input inputJson }
+                    ... on ExposedToolCallMessage { input inputJson }
+                    ... on AssistantMessage { contentBlocks {
+                        ... on ToolUseBlock { input inputJson inputSize }
+                    } }
+                } } } } }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let nodes: Vec<_> = data["session"]["messages"]["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                let node = &e["node"];
+                let typename = node["__typename"].as_str().unwrap().to_string();
+                let fields = match typename.as_str() {
+                    "AssistantMessage" => &node["contentBlocks"][0],
+                    _ => node,
+                };
+                (typename, fields.clone())
+            })
+            .collect();
+        assert_eq!(nodes.len(), 3);
+
+        for (typename, fields) in nodes {
+            // The scalar is the arguments object itself, not a string
+            assert_eq!(fields["inputJson"], args, "{typename}");
+            let input: serde_json::Value =
+                serde_json::from_str(fields["input"].as_str().unwrap()).unwrap();
+            assert_eq!(input, fields["inputJson"], "{typename}");
+            if typename == "AssistantMessage" {
+                let size = serde_json::to_string(&args).unwrap().len();
+                assert_eq!(fields["inputSize"], size);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mcp_result_token_usage_over_graphql() {
+        use han_db::entities::messages;
+        use sea_orm::Set;
+
+        let db = crate::test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let results = [
+            // Priced model
+            serde_json::json!({
+                "model": "claude-3-5-haiku-20241022",
+                "usage": { "input_tokens": 1_000_000, "output_tokens": 500_000 },
+            }),
+            // Model with no known pricing
+            serde_json::json!({
+                "model": "gpt-4o",
+                "usage": { "input_tokens": 200, "output_tokens": 50 },
+            }),
+            // Usage without a model
+            serde_json::json!({ "usage": { "input_tokens": 10, "output_tokens": 5 } }),
+            // No usage at all
+            serde_json::json!({ "model": "claude-3-5-haiku-20241022", "success": true }),
+        ];
+        let models = results
+            .iter()
+            .enumerate()
+            .map(|(i, data)| {
+                let line = i as i32;
+                let raw_json = serde_json::json!({ "type": "mcp_tool_result", "data": data });
+                messages::ActiveModel {
+                    id: Set(format!("m{line}")),
+                    session_id: Set("s1".to_string()),
+                    message_type: Set("han_event".to_string()),
+                    tool_name: Set(Some("mcp_tool_result".to_string())),
+                    raw_json: Set(Some(raw_json.to_string())),
+                    timestamp: Set(format!("2026-04-20T10:00:0{line}Z")),
+                    line_number: Set(line),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        han_db::crud::messages::insert_batch(&db, models)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let res = schema
+            .execute(
+                r#"{ session(id: "s1") {
+                    messages { edges { node {
+                        ... on McpToolResultMessage { uuid inputTokens outputTokens costUsd }
+                    } } }
+                    mcpTokenUsage { totalInputTokens totalOutputTokens totalTokens estimatedCostUsd }
+                } }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let nodes: std::collections::HashMap<_, _> = data["session"]["messages"]["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["node"]["uuid"].as_str().unwrap().to_string(), &e["node"]))
+            .collect();
+        assert_eq!(nodes.len(), 4);
+
+        // 1M input at $0.80/M + 500K output at $4/M = $2.80
+        assert_eq!(nodes["m0"]["inputTokens"], 1_000_000);
+        assert_eq!(nodes["m0"]["outputTokens"], 500_000);
+        assert!((nodes["m0"]["costUsd"].as_f64().unwrap() - 2.8).abs() < 1e-9);
+        assert_eq!(nodes["m1"]["inputTokens"], 200);
+        assert!(nodes["m1"]["costUsd"].is_null());
+        assert_eq!(nodes["m2"]["outputTokens"], 5);
+        assert!(nodes["m2"]["costUsd"].is_null());
+        for field in ["inputTokens", "outputTokens", "costUsd"] {
+            assert!(nodes["m3"][field].is_null(), "{field}");
+        }
+
+        let usage = &data["session"]["mcpTokenUsage"];
+        assert_eq!(usage["totalInputTokens"], 1_000_210);
+        assert_eq!(usage["totalOutputTokens"], 500_055);
+        assert_eq!(usage["totalTokens"], 1_500_265);
+        // Only the priced model contributes to cost
+        assert!((usage["estimatedCostUsd"].as_f64().unwrap() - 2.8).abs() < 1e-9);
+    }
 }
 
 /// Build a MessageConnection from database messages, filtering paired events.
@@ -2287,10 +3072,7 @@ pub fn build_message_connection(
 
     let all_edges: Vec<MessageEdge> = items
         .into_iter()
-        .map(|(data, cursor)| MessageEdge {
-            node: discriminate_message(data),
-            cursor,
-        })
+        .map(|(data, cursor)| MessageEdge::new(data, cursor))
         .collect();
 
     let start_idx = if let Some(ref after_cursor) = after {
@@ -2352,5 +3134,6 @@ pub fn build_message_connection(
             end_cursor,
         },
         total_count,
+        stats: MessageConnectionStats::from_edges(&all_edges),
     }
 }
@@ -114,6 +114,19 @@ impl From<han_db::entities::tasks::Model> for Task {
     }
 }
 
+/// Default developer hourly rate in USD for task cost estimates.
+pub const DEFAULT_DEVELOPER_HOURLY_RATE: f64 = 100.0;
+
+/// Developer hourly rate in USD from `HAN_DEVELOPER_HOURLY_RATE`, falling
+/// back to [`DEFAULT_DEVELOPER_HOURLY_RATE`] when unset or not positive.
+pub fn developer_hourly_rate() -> f64 {
+    std::env::var("HAN_DEVELOPER_HOURLY_RATE")
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .unwrap_or(DEFAULT_DEVELOPER_HOURLY_RATE)
+}
+
 /// Task edge.
 #[derive(Debug, Clone, SimpleObject)]
 pub struct TaskEdge {
@@ -12,13 +12,19 @@ pub mod team;
 
 pub mod config_dir;
 pub mod dashboard;
+pub mod discovered_hook;
 pub mod file_change;
 pub mod frustration;
+pub mod hook_dry_run;
 pub mod hook_execution;
+pub mod index_error;
+pub mod index_warning;
 pub mod native_task;
 pub mod plugin;
+pub mod pricing;
 pub mod project;
 pub mod repo;
 pub mod search_result;
 pub mod settings;
 pub mod todo;
+pub mod worktree;
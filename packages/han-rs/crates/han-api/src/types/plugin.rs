@@ -36,6 +36,26 @@ impl Plugin {
     }
 }
 
+/// An installed plugin recorded in the database.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PluginInfo {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub installed_at: String,
+}
+
+impl From<han_db::entities::plugins::Model> for PluginInfo {
+    fn from(m: han_db::entities::plugins::Model) -> Self {
+        Self {
+            name: m.name,
+            version: m.version,
+            description: m.description,
+            installed_at: m.installed_at,
+        }
+    }
+}
+
 /// Plugin statistics matching browse-client schema.
 #[derive(Debug, Clone, SimpleObject)]
 pub struct PluginStats {
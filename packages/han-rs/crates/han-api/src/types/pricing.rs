@@ -0,0 +1,282 @@
+//! Per-model token pricing.
+//!
+//! Prices are USD per 1M tokens, taken from Anthropic's published rates.
+//! Model IDs are matched against [`PRICING_TABLE`] by the longest entry that
+//! equals the ID or prefixes it at a `-` boundary. Anything other than an
+//! exact match is flagged as estimated.
+
+/// Token prices for a single model (USD per 1M tokens).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_creation: f64,
+}
+
+impl ModelPrice {
+    /// USD cost of a token breakdown at this price.
+    pub fn cost(&self, input: i64, output: i64, cache_read: i64, cache_creation: i64) -> f64 {
+        (input as f64 * self.input
+            + output as f64 * self.output
+            + cache_read as f64 * self.cache_read
+            + cache_creation as f64 * self.cache_creation)
+            / 1_000_000.0
+    }
+}
+
+/// Model used for pricing when the model ID is missing or unrecognized.
+pub const DEFAULT_MODEL: &str = "claude-3-5-sonnet-20241022";
+
+const OPUS_4_5: ModelPrice = ModelPrice {
+    input: 5.0,
+    output: 25.0,
+    cache_read: 0.50,
+    cache_creation: 6.25,
+};
+
+const OPUS: ModelPrice = ModelPrice {
+    input: 15.0,
+    output: 75.0,
+    cache_read: 1.50,
+    cache_creation: 18.75,
+};
+
+const SONNET: ModelPrice = ModelPrice {
+    input: 3.0,
+    output: 15.0,
+    cache_read: 0.30,
+    cache_creation: 3.75,
+};
+
+const HAIKU_4_5: ModelPrice = ModelPrice {
+    input: 1.0,
+    output: 5.0,
+    cache_read: 0.10,
+    cache_creation: 1.25,
+};
+
+const HAIKU_3_5: ModelPrice = ModelPrice {
+    input: 0.80,
+    output: 4.0,
+    cache_read: 0.08,
+    cache_creation: 1.0,
+};
+
+const HAIKU_3: ModelPrice = ModelPrice {
+    input: 0.25,
+    output: 1.25,
+    cache_read: 0.03,
+    cache_creation: 0.30,
+};
+
+/// Known model IDs, aliases and family prefixes with their pricing. The
+/// longest matching entry wins, so `claude-opus-4-5` takes precedence over
+/// `claude-opus-4`.
+pub const PRICING_TABLE: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-5-20251101", OPUS_4_5),
+    ("claude-opus-4-5", OPUS_4_5),
+    ("claude-opus-4-1-20250805", OPUS),
+    ("claude-opus-4-1", OPUS),
+    ("claude-opus-4-20250514", OPUS),
+    ("claude-opus-4-0", OPUS),
+    ("claude-opus-4", OPUS),
+    ("claude-sonnet-4-5-20250929", SONNET),
+    ("claude-sonnet-4-5", SONNET),
+    ("claude-sonnet-4-20250514", SONNET),
+    ("claude-sonnet-4-0", SONNET),
+    ("claude-sonnet-4", SONNET),
+    ("claude-haiku-4-5-20251001", HAIKU_4_5),
+    ("claude-haiku-4-5", HAIKU_4_5),
+    ("claude-3-7-sonnet-20250219", SONNET),
+    ("claude-3-7-sonnet-latest", SONNET),
+    ("claude-3-7-sonnet", SONNET),
+    ("claude-3-5-sonnet-20241022", SONNET),
+    ("claude-3-5-sonnet-20240620", SONNET),
+    ("claude-3-5-sonnet-latest", SONNET),
+    ("claude-3-5-sonnet", SONNET),
+    ("claude-3-5-haiku-20241022", HAIKU_3_5),
+    ("claude-3-5-haiku-latest", HAIKU_3_5),
+    ("claude-3-5-haiku", HAIKU_3_5),
+    ("claude-3-opus-20240229", OPUS),
+    ("claude-3-opus-latest", OPUS),
+    ("claude-3-opus", OPUS),
+    ("claude-3-sonnet-20240229", SONNET),
+    ("claude-3-sonnet", SONNET),
+    ("claude-3-haiku-20240307", HAIKU_3),
+    ("claude-3-haiku", HAIKU_3),
+];
+
+/// Pricing found for a model ID.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceMatch {
+    pub price: ModelPrice,
+    /// True when the model matched a [`PRICING_TABLE`] entry by prefix rather
+    /// than exactly.
+    pub is_estimated: bool,
+}
+
+fn exact_price(model_id: &str) -> Option<ModelPrice> {
+    PRICING_TABLE
+        .iter()
+        .find(|(id, _)| *id == model_id)
+        .map(|(_, price)| *price)
+}
+
+/// Look up pricing for a model ID by its longest matching [`PRICING_TABLE`]
+/// entry. `None` when no entry matches.
+pub fn lookup_price(model_id: &str) -> Option<PriceMatch> {
+    if let Some(price) = exact_price(model_id) {
+        return Some(PriceMatch {
+            price,
+            is_estimated: false,
+        });
+    }
+    PRICING_TABLE
+        .iter()
+        .filter(|(prefix, _)| {
+            model_id
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| PriceMatch {
+            price: *price,
+            is_estimated: true,
+        })
+}
+
+fn default_price() -> ModelPrice {
+    exact_price(DEFAULT_MODEL).expect("DEFAULT_MODEL must be present in PRICING_TABLE")
+}
+
+/// Look up pricing for a model ID, falling back to [`DEFAULT_MODEL`].
+pub fn price_for_model(model_id: Option<&str>) -> ModelPrice {
+    model_id
+        .and_then(lookup_price)
+        .map_or_else(default_price, |m| m.price)
+}
+
+/// Estimate the USD cost of a token breakdown for the given model.
+pub fn estimate_cost(
+    model_id: Option<&str>,
+    input: i64,
+    output: i64,
+    cache_read: i64,
+    cache_creation: i64,
+) -> f64 {
+    price_for_model(model_id).cost(input, output, cache_read, cache_creation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_model_is_priced() {
+        assert!(PRICING_TABLE.iter().any(|(id, _)| *id == DEFAULT_MODEL));
+    }
+
+    #[test]
+    fn unknown_model_falls_back_to_default() {
+        assert_eq!(
+            price_for_model(Some("claude-unknown")),
+            price_for_model(Some(DEFAULT_MODEL))
+        );
+        assert_eq!(price_for_model(None), price_for_model(Some(DEFAULT_MODEL)));
+    }
+
+    #[test]
+    fn sonnet_cost() {
+        // 1M input + 1M output + 1M cache read + 1M cache creation
+        // = $3 + $15 + $0.30 + $3.75 = $22.05
+        let cost = estimate_cost(
+            Some("claude-3-5-sonnet-20241022"),
+            1_000_000,
+            1_000_000,
+            1_000_000,
+            1_000_000,
+        );
+        assert!((cost - 22.05).abs() < 0.0001);
+    }
+
+    #[test]
+    fn haiku_cost() {
+        // 2M input + 400K output = $0.50 + $0.50 = $1.00
+        let cost = estimate_cost(Some("claude-3-haiku-20240307"), 2_000_000, 400_000, 0, 0);
+        assert!((cost - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn opus_cost() {
+        // 10K input + 2K output + 100K cache read
+        // = $0.15 + $0.15 + $0.15 = $0.45
+        let cost = estimate_cost(Some("claude-3-opus-20240229"), 10_000, 2_000, 100_000, 0);
+        assert!((cost - 0.45).abs() < 0.0001);
+    }
+
+    #[test]
+    fn exact_ids_are_not_estimated() {
+        for (id, price) in PRICING_TABLE {
+            let m = lookup_price(id).unwrap();
+            assert_eq!(m.price, *price, "{id}");
+            assert!(!m.is_estimated, "{id}");
+        }
+    }
+
+    #[test]
+    fn aliases_are_priced_exactly() {
+        // (model ID, expected input price, expected output price)
+        let cases = [
+            ("claude-opus-4-5", 5.0, 25.0),
+            ("claude-opus-4-1", 15.0, 75.0),
+            ("claude-sonnet-4-5", 3.0, 15.0),
+            ("claude-haiku-4-5", 1.0, 5.0),
+            ("claude-3-5-haiku-latest", 0.80, 4.0),
+            ("claude-3-opus-latest", 15.0, 75.0),
+        ];
+        for (id, input, output) in cases {
+            let m = lookup_price(id).unwrap_or_else(|| panic!("{id} not priced"));
+            assert!(!m.is_estimated, "{id}");
+            assert_eq!((m.price.input, m.price.output), (input, output), "{id}");
+        }
+    }
+
+    #[test]
+    fn unlisted_snapshots_use_longest_family_prefix() {
+        // (model ID, expected input price, expected output price)
+        let cases = [
+            ("claude-opus-4-6", 15.0, 75.0),
+            ("claude-opus-4-5-20990101", 5.0, 25.0),
+            ("claude-sonnet-4-20990101", 3.0, 15.0),
+            ("claude-haiku-4-5-20990101", 1.0, 5.0),
+            ("claude-3-5-haiku-20990101", 0.80, 4.0),
+            ("claude-3-haiku-20990101", 0.25, 1.25),
+        ];
+        for (id, input, output) in cases {
+            let m = lookup_price(id).unwrap_or_else(|| panic!("{id} not priced"));
+            assert!(m.is_estimated, "{id}");
+            assert_eq!((m.price.input, m.price.output), (input, output), "{id}");
+        }
+    }
+
+    #[test]
+    fn prefix_must_end_at_a_dash() {
+        assert!(lookup_price("claude-opus-4x").is_none());
+        assert!(lookup_price("claude-unknown").is_none());
+    }
+
+    #[test]
+    fn unknown_model_has_no_price() {
+        assert!(lookup_price("gpt-4o").is_none());
+        assert!(lookup_price("").is_none());
+    }
+
+    #[test]
+    fn zero_tokens_cost_nothing() {
+        assert_eq!(
+            estimate_cost(Some("claude-3-opus-20240229"), 0, 0, 0, 0),
+            0.0
+        );
+    }
+}
@@ -3,7 +3,10 @@
 use async_graphql::*;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
 
+use han_db::aggregates::ModelTokenTotals;
+
 use crate::node::encode_global_id;
+use crate::types::worktree::Worktree;
 use han_graphql_derive::GraphQLEntity;
 
 /// Transform: nonzero Option<i32> → bool.
@@ -71,9 +74,13 @@ impl Project {
     async fn last_activity(&self) -> Option<&str> {
         Some(&self.updated_at)
     }
-    /// Worktrees (stub).
-    async fn worktrees(&self) -> Option<Vec<Project>> {
-        Some(vec![])
+    /// Linked git worktrees added from this project's checkout.
+    async fn worktrees(&self, ctx: &Context<'_>) -> Result<Vec<Worktree>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let worktrees = han_db::crud::worktrees::list_for_main_project(db, &self.path)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(worktrees.into_iter().map(Worktree::from).collect())
     }
     /// Subdirectory projects (stub).
     async fn subdirs(&self) -> Option<Vec<Project>> {
@@ -85,6 +92,67 @@ impl Project {
     }
 }
 
+/// Aggregated metrics for a project's sessions.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectStats {
+    pub total_sessions: i32,
+    pub total_messages: i32,
+    pub total_tokens: i64,
+    /// Priced per model; tokens from unknown models at the default rates.
+    pub estimated_cost_usd: f64,
+    pub unique_tools_used: Vec<String>,
+    /// Fraction of hook runs that passed, 0 when no hooks ran.
+    pub hook_pass_rate: f64,
+    pub average_session_duration_minutes: f64,
+    /// Days with at least one message in the project.
+    pub active_days: i32,
+    pub first_session_at: Option<String>,
+    pub last_session_at: Option<String>,
+}
+
+impl From<han_db::entities::project_stats_cache::Model> for ProjectStats {
+    fn from(m: han_db::entities::project_stats_cache::Model) -> Self {
+        Self {
+            total_sessions: m.total_sessions as i32,
+            total_messages: m.total_messages as i32,
+            total_tokens: m.input_tokens
+                + m.output_tokens
+                + m.cache_read_tokens
+                + m.cache_creation_tokens,
+            estimated_cost_usd: serde_json::from_str::<Vec<ModelTokenTotals>>(&m.tokens_by_model)
+                .unwrap_or_default()
+                .iter()
+                .map(|t| {
+                    crate::types::pricing::estimate_cost(
+                        t.model.as_deref(),
+                        t.input_tokens,
+                        t.output_tokens,
+                        t.cache_read_tokens,
+                        t.cache_creation_tokens,
+                    )
+                })
+                .sum(),
+            unique_tools_used: serde_json::from_str(&m.unique_tools).unwrap_or_default(),
+            hook_pass_rate: if m.hook_runs > 0 {
+                m.hook_passes as f64 / m.hook_runs as f64
+            } else {
+                0.0
+            },
+            average_session_duration_minutes: m.average_session_duration_minutes,
+            active_days: m.active_days as i32,
+            first_session_at: m.first_session_at,
+            last_session_at: m.last_session_at,
+        }
+    }
+}
+
+/// A project together with its aggregated metrics.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectWithStats {
+    pub project: Project,
+    pub stats: ProjectStats,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,4 +268,36 @@ mod tests {
         };
         let _cond = f.to_condition();
     }
+
+    #[test]
+    fn project_stats_from_cache_row() {
+        let stats = ProjectStats::from(han_db::entities::project_stats_cache::Model {
+            project_id: "proj-1".into(),
+            total_sessions: 3,
+            total_messages: 40,
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_tokens: 2_000_000,
+            cache_creation_tokens: 0,
+            tokens_by_model: serde_json::json!([
+                {"model": "claude-3-5-sonnet-20241022", "input_tokens": 1_000_000, "output_tokens": 0, "cache_read_tokens": 2_000_000, "cache_creation_tokens": 0},
+                {"model": "claude-3-haiku-20240307", "input_tokens": 0, "output_tokens": 100_000, "cache_read_tokens": 0, "cache_creation_tokens": 0},
+            ])
+            .to_string(),
+            unique_tools: r#"["Bash","Edit"]"#.into(),
+            hook_runs: 4,
+            hook_passes: 3,
+            average_session_duration_minutes: 12.5,
+            active_days: 2,
+            first_session_at: Some("2026-04-01T09:00:00Z".into()),
+            last_session_at: Some("2026-04-03T17:00:00Z".into()),
+            computed_at: "2026-04-03T17:05:00Z".into(),
+        });
+        assert_eq!(stats.total_tokens, 3_100_000);
+        assert_eq!(stats.unique_tools_used, vec!["Bash", "Edit"]);
+        assert_eq!(stats.hook_pass_rate, 0.75);
+        // Sonnet: $3 + $0.60, Haiku: $0.125
+        assert!((stats.estimated_cost_usd - 3.725).abs() < 0.0001);
+        assert_eq!(stats.active_days, 2);
+    }
 }
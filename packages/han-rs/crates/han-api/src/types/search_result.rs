@@ -1,12 +1,246 @@
-//! Message search result GraphQL types.
+//! Message and session search result GraphQL types.
 
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
+use han_db::entities::{messages, projects, sessions};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::HashMap;
+
+use crate::loaders::{MessageByIdLoader, SessionByIdLoader};
+use crate::types::messages::{discriminate_message, Message, MessageData};
+use crate::types::sessions::SessionData;
 
 /// A search result matching a message in a session.
 #[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
 pub struct MessageSearchResult {
     pub message_id: Option<String>,
     pub message_index: Option<i32>,
     pub preview: Option<String>,
     pub match_context: Option<String>,
+    /// Session containing the message (set by cross-session searches).
+    pub session_id: Option<String>,
+    /// Relevance score, higher is better (set by ranked searches).
+    pub score: Option<f64>,
+    /// The matched message, when the search already loaded it.
+    #[graphql(skip)]
+    pub message: Option<Message>,
+    /// Excerpt around the match.
+    pub snippet: Option<String>,
+    /// BM25 relevance, higher is better (set by message FTS searches).
+    pub rank: Option<f64>,
+    /// Byte offset of the message's line in its JSONL file, for seeking
+    /// straight to it (set by message FTS searches).
+    pub byte_offset: Option<i64>,
+}
+
+impl From<han_db::search::HybridSearchResult> for MessageSearchResult {
+    fn from(r: han_db::search::HybridSearchResult) -> Self {
+        Self {
+            message_id: Some(r.message_id),
+            message_index: None,
+            preview: Some(r.snippet.clone()),
+            match_context: Some(r.snippet.clone()),
+            session_id: Some(r.session_id),
+            score: Some(r.combined_score),
+            message: None,
+            snippet: Some(r.snippet),
+            rank: None,
+            byte_offset: None,
+        }
+    }
+}
+
+#[ComplexObject]
+impl MessageSearchResult {
+    /// Session containing the matched message.
+    async fn session(&self, ctx: &Context<'_>) -> Result<Option<SessionData>> {
+        let Some(session_id) = self.session_id.clone() else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<SessionByIdLoader>>()?;
+        loader.load_one(session_id).await
+    }
+
+    /// The matched message.
+    async fn message(&self, ctx: &Context<'_>) -> Result<Option<Message>> {
+        if let Some(ref message) = self.message {
+            return Ok(Some(message.clone()));
+        }
+        let Some(message_id) = self.message_id.clone() else {
+            return Ok(None);
+        };
+        let loader = ctx.data::<DataLoader<MessageByIdLoader>>()?;
+        let Some(model) = loader.load_one(message_id).await? else {
+            return Ok(None);
+        };
+        let session = self.session(ctx).await?;
+        let project_dir = session.map(|s| s.project_dir).unwrap_or_default();
+        Ok(Some(discriminate_message(MessageData::from_model(
+            &model,
+            &project_dir,
+        ))))
+    }
+}
+
+/// Minimum query length for message search.
+pub const MIN_QUERY_LEN: usize = 2;
+
+/// Project directory of each session in `session_ids` that has a project.
+async fn project_dirs(
+    db: &DatabaseConnection,
+    session_ids: Vec<String>,
+) -> Result<HashMap<String, String>> {
+    Ok(sessions::Entity::find()
+        .filter(sessions::Column::Id.is_in(session_ids))
+        .find_also_related(projects::Entity)
+        .all(db)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?
+        .into_iter()
+        .filter_map(|(session, project)| Some((session.id, project?.path)))
+        .collect())
+}
+
+/// Full-text search over messages, resolving each hit to its message.
+///
+/// Hits whose message has since been deleted are dropped. Each message
+/// gets its session's project directory.
+pub async fn search_messages(
+    db: &DatabaseConnection,
+    query: &str,
+    session_id: Option<&str>,
+    limit: Option<i32>,
+) -> Result<Vec<MessageSearchResult>> {
+    if query.trim().chars().count() < MIN_QUERY_LEN {
+        return Err(Error::new(format!(
+            "Search query must be at least {MIN_QUERY_LEN} characters"
+        )));
+    }
+
+    let hits = han_db::search::fts_search_messages(
+        db,
+        query,
+        session_id,
+        limit.unwrap_or(20).clamp(1, 100) as usize,
+    )
+    .await
+    .map_err(|e| Error::new(e.to_string()))?;
+
+    let ids: Vec<&str> = hits.iter().map(|h| h.message_id.as_str()).collect();
+    let mut models: HashMap<String, messages::Model> = messages::Entity::find()
+        .filter(messages::Column::Id.is_in(ids))
+        .all(db)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?
+        .into_iter()
+        .map(|m| (m.id.clone(), m))
+        .collect();
+    let session_ids: std::collections::HashSet<String> =
+        hits.iter().map(|h| h.session_id.clone()).collect();
+    let project_dirs = project_dirs(db, session_ids.into_iter().collect()).await?;
+
+    Ok(hits
+        .into_iter()
+        .filter_map(|hit| {
+            let model = models.remove(&hit.message_id)?;
+            let project_dir = project_dirs
+                .get(&hit.session_id)
+                .map(String::as_str)
+                .unwrap_or_default();
+            let data = MessageData::from_model(&model, project_dir);
+            Some(MessageSearchResult {
+                message_id: Some(hit.message_id),
+                message_index: None,
+                preview: Some(hit.snippet.clone()),
+                match_context: Some(hit.snippet.clone()),
+                session_id: Some(hit.session_id),
+                score: Some(hit.rank),
+                message: Some(discriminate_message(data)),
+                snippet: Some(hit.snippet),
+                rank: Some(hit.rank),
+                byte_offset: hit.byte_offset,
+            })
+        })
+        .collect())
+}
+
+/// A session matched by cross-session topic search.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SessionSearchResult {
+    pub session_id: String,
+    pub project_dir: Option<String>,
+    /// Relevance, higher is better.
+    pub rank: f64,
+    pub snippet: String,
+}
+
+impl From<han_db::search::SessionSearchResult> for SessionSearchResult {
+    fn from(r: han_db::search::SessionSearchResult) -> Self {
+        Self {
+            session_id: r.session_id,
+            project_dir: r.project_dir,
+            rank: r.rank,
+            snippet: r.snippet,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::Set;
+
+    #[tokio::test]
+    async fn test_search_messages_resolves_project_dir() {
+        let db = crate::test_db().await;
+        let project = han_db::crud::projects::upsert(
+            &db,
+            None,
+            "-home-user-app".to_string(),
+            "/home/user/app".to_string(),
+            None,
+            "app".to_string(),
+            Some(false),
+            None,
+        )
+        .await
+        .unwrap();
+        han_db::crud::sessions::upsert(
+            &db,
+            "session-1".to_string(),
+            Some(project.id),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        han_db::crud::messages::insert_batch(
+            &db,
+            vec![messages::ActiveModel {
+                id: Set("msg-1".to_string()),
+                session_id: Set("session-1".to_string()),
+                message_type: Set("user".to_string()),
+                content: Set(Some("日本語 migration plan".to_string())),
+                timestamp: Set("2026-04-01T09:00:00Z".to_string()),
+                line_number: Set(1),
+                ..Default::default()
+            }],
+        )
+        .await
+        .unwrap();
+
+        let results = search_messages(&db, "migration", None, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        let Some(Message::RegularUser(message)) = &results[0].message else {
+            panic!("expected a user message, got {:?}", results[0].message);
+        };
+        assert_eq!(message.data.project_dir, "/home/user/app");
+
+        // Two characters are enough even when they take six bytes
+        assert!(search_messages(&db, "日本", None, None).await.is_ok());
+        assert!(search_messages(&db, " 日 ", None, None).await.is_err());
+    }
 }
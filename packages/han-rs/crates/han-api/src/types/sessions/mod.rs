@@ -1,25 +1,41 @@
 //! Session GraphQL type.
 
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
+use han_db::aggregates::ModelTokenTotals;
 use han_db::entities::messages;
+use han_indexer::sentiment::{FrustrationLevel, FrustrationPoint};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
     ColumnTrait, Condition, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Statement,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Statement,
 };
 
-use crate::connection::PageInfo;
+use crate::connection::{ConnectionArgs, PageInfo};
+use crate::filters::ordering::OrderDirection;
+use crate::loaders::{
+    SessionFileChangeCountLoader, SessionHookCountLoader, SessionHookPluginStatsLoader,
+    SessionMaxFrustrationLoader, SessionSystemMessageCountLoader, SessionTaskCountsLoader,
+    SessionTokenTotalsLoader, SessionToolErrorCountLoader, SessionToolResultCountLoader,
+};
 use crate::node::{decode_msg_cursor, encode_global_id, encode_msg_cursor};
-use crate::types::content_blocks::ToolResultBlock;
+use crate::types::content_blocks::{parse_content_blocks, ContentBlock, ToolResultBlock};
+use crate::types::enums::{ModelVersion, TodoStatus};
 use crate::types::file_change::{FileChange, FileChangeConnection, FileChangeEdge};
 use crate::types::frustration::FrustrationSummary;
 use crate::types::hook_execution::{
-    HookExecution, HookExecutionConnection, HookExecutionEdge, HookStats, HookTypeStat,
+    HookExecution, HookExecutionConnection, HookExecutionEdge, HookPluginStat, HookStats,
+    HookTypeStat,
+};
+use crate::types::index_error::IndexError;
+use crate::types::index_warning::IndexWarning;
+use crate::types::messages::{
+    is_tool_result_user_model, MessageConnection, MessageConnectionStats, MessageData, MessageEdge,
 };
-use crate::types::messages::{discriminate_message, MessageConnection, MessageData, MessageEdge};
 use crate::types::metrics::{Task, TaskConnection, TaskEdge};
 use crate::types::native_task::NativeTask;
 use crate::types::search_result::MessageSearchResult;
-use crate::types::team::User;
+use crate::types::team::{TokenUsageAggregation, User};
 use crate::types::todo::{Todo, TodoConnection, TodoCounts};
 
 /// Session data for GraphQL resolution.
@@ -39,6 +55,7 @@ pub struct SessionData {
     pub git_branch: Option<String>,
     pub version: Option<String>,
     pub worktree_name: Option<String>,
+    pub worktree_id: Option<String>,
     pub source_config_dir: Option<String>,
     pub status: Option<String>,
     pub pr_number: Option<i32>,
@@ -108,6 +125,21 @@ impl SessionData {
         self.worktree_name.as_deref()
     }
 
+    /// The linked git worktree this session ran in, if any.
+    async fn worktree(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<crate::types::worktree::Worktree>> {
+        let Some(id) = &self.worktree_id else {
+            return Ok(None);
+        };
+        let db = ctx.data::<DatabaseConnection>()?;
+        let worktree = han_db::crud::worktrees::get(db, id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(worktree.map(Into::into))
+    }
+
     /// First user message as summary.
     async fn summary(&self) -> Option<&str> {
         self.summary.as_deref()
@@ -280,10 +312,7 @@ impl SessionData {
             .map(|msg| {
                 let data = MessageData::from_model(msg, &self.project_dir);
                 let cursor = encode_msg_cursor(&msg.timestamp, &msg.id);
-                MessageEdge {
-                    node: discriminate_message(data),
-                    cursor,
-                }
+                MessageEdge::new(data, cursor)
             })
             .collect();
 
@@ -297,6 +326,8 @@ impl SessionData {
         let start_cursor = edges.first().map(|e| e.cursor.clone());
         let end_cursor = edges.last().map(|e| e.cursor.clone());
 
+        let stats = MessageConnectionStats::from_edges(&edges);
+
         Ok(MessageConnection {
             edges,
             page_info: PageInfo {
@@ -306,6 +337,7 @@ impl SessionData {
                 end_cursor,
             },
             total_count,
+            stats,
         })
     }
 
@@ -331,6 +363,25 @@ impl SessionData {
         Ok(tasks.into_iter().map(NativeTask::from).collect())
     }
 
+    /// Transcript lines that failed to parse when this session was indexed.
+    async fn indexing_errors(&self, ctx: &Context<'_>) -> Result<Vec<IndexError>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let errors = han_db::crud::index_errors::list_by_session(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(errors.into_iter().map(IndexError::from).collect())
+    }
+
+    /// Transcript lines that indexed but failed a sanity check, such as a
+    /// missing UUID or a timestamp in the future.
+    async fn indexing_warnings(&self, ctx: &Context<'_>) -> Result<Vec<IndexWarning>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let warnings = han_db::crud::index_warnings::list_by_session(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(warnings.into_iter().map(IndexWarning::from).collect())
+    }
+
     // ========================================================================
     // Stub fields for browse-client backwards compatibility
     // ========================================================================
@@ -351,8 +402,12 @@ impl SessionData {
     }
 
     /// The currently in-progress todo, if any.
-    async fn current_todo(&self) -> Option<Todo> {
-        None
+    async fn current_todo(&self, ctx: &Context<'_>) -> Result<Option<Todo>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let todos = load_session_todos(db, &self.session_id).await?;
+        Ok(todos
+            .into_iter()
+            .find(|t| t.status == Some(TodoStatus::InProgress)))
     }
 
     /// The most recently started active task, if any.
@@ -365,6 +420,63 @@ impl SessionData {
         Some(vec![])
     }
 
+    /// Sub-agent sessions spawned from this session via the Task tool, oldest first.
+    async fn agent_sessions(&self, ctx: &Context<'_>) -> Result<Vec<SessionData>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let links = han_db::crud::agent_sessions::list_children(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        if links.is_empty() {
+            return Ok(vec![]);
+        }
+        let child_ids: Vec<String> = links.iter().map(|l| l.child_session_id.clone()).collect();
+        let models = han_db::entities::sessions::Entity::find()
+            .filter(han_db::entities::sessions::Column::Id.is_in(child_ids))
+            .all(db)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        // Keep spawn order from the link table
+        let mut data: Vec<SessionData> = links
+            .iter()
+            .filter_map(|l| models.iter().find(|m| m.id == l.child_session_id).cloned())
+            .map(crate::query::session_model_to_data)
+            .collect();
+        crate::query::enrich_sessions(db, &mut data).await?;
+        Ok(data)
+    }
+
+    /// Whether this session was spawned by another session's Task tool call.
+    async fn is_agent_session(&self, ctx: &Context<'_>) -> Result<bool> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let link = han_db::crud::agent_sessions::get_parent(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(link.is_some())
+    }
+
+    /// The session that spawned this one, if this is a sub-agent session.
+    async fn parent_session(&self, ctx: &Context<'_>) -> Result<Option<SessionData>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let Some(link) = han_db::crud::agent_sessions::get_parent(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let model = han_db::entities::sessions::Entity::find_by_id(&link.parent_session_id)
+            .one(db)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        match model {
+            Some(m) => {
+                let mut data = crate::query::session_model_to_data(m);
+                crate::query::enrich_single_session(db, &mut data).await?;
+                Ok(Some(data))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// All tasks tracked in this session via start_task MCP tool.
     async fn tasks(
         &self,
@@ -457,31 +569,103 @@ impl SessionData {
         }))
     }
 
+    /// Number of tasks still in progress.
+    async fn active_task_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let counts = load_task_counts(ctx, &self.session_id).await?;
+        Ok(Some(counts.active as i32))
+    }
+
+    /// Number of tasks completed without a failure outcome.
+    async fn completed_task_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let counts = load_task_counts(ctx, &self.session_id).await?;
+        Ok(Some(counts.completed as i32))
+    }
+
+    /// Number of tasks completed with a failure outcome.
+    async fn failed_task_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let counts = load_task_counts(ctx, &self.session_id).await?;
+        Ok(Some(counts.failed as i32))
+    }
+
+    /// Share of finished tasks that succeeded (0.0-1.0). Null until a task
+    /// has completed or failed.
+    async fn task_success_rate(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let counts = load_task_counts(ctx, &self.session_id).await?;
+        let finished = counts.completed + counts.failed;
+        if finished == 0 {
+            return Ok(None);
+        }
+        Ok(Some(counts.completed as f64 / finished as f64))
+    }
+
+    /// How well task confidence predicted task success (0.0-1.0, 1.0 is
+    /// perfectly calibrated). Null until a task finishes with a confidence
+    /// and an outcome.
+    async fn calibration_score(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        han_db::aggregates::compute_calibration_score(db, std::slice::from_ref(&self.session_id))
+            .await
+            .map_err(|e| Error::new(e.to_string()))
+    }
+
+    /// Developer cost in USD of the completed tasks: confidence-weighted
+    /// hours times `HAN_DEVELOPER_HOURLY_RATE` (default $100/h).
+    async fn estimated_task_cost(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let counts = load_task_counts(ctx, &self.session_id).await?;
+        Ok(Some(
+            counts.completed_hours * crate::types::metrics::developer_hourly_rate(),
+        ))
+    }
+
     /// All todos from the most recent TodoWrite in this session.
     async fn todos(
         &self,
-        _first: Option<i32>,
-        _after: Option<String>,
-        _last: Option<i32>,
-        _before: Option<String>,
-    ) -> Option<TodoConnection> {
-        Some(TodoConnection::default())
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> Result<Option<TodoConnection>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let todos = load_session_todos(db, &self.session_id).await?;
+        let args = ConnectionArgs {
+            first,
+            after,
+            last,
+            before,
+        };
+        Ok(Some(TodoConnection::from_todos(&todos, &args)))
     }
 
     /// Non-completed todos (pending or in-progress).
     async fn active_todos(
         &self,
-        _first: Option<i32>,
-        _after: Option<String>,
-        _last: Option<i32>,
-        _before: Option<String>,
-    ) -> Option<TodoConnection> {
-        Some(TodoConnection::default())
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> Result<Option<TodoConnection>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let todos: Vec<Todo> = load_session_todos(db, &self.session_id)
+            .await?
+            .into_iter()
+            .filter(|t| t.status != Some(TodoStatus::Completed))
+            .collect();
+        let args = ConnectionArgs {
+            first,
+            after,
+            last,
+            before,
+        };
+        Ok(Some(TodoConnection::from_todos(&todos, &args)))
     }
 
     /// Counts of todos by status.
-    async fn todo_counts(&self) -> Option<TodoCounts> {
-        Some(TodoCounts::default())
+    async fn todo_counts(&self, ctx: &Context<'_>) -> Result<Option<TodoCounts>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let todos = load_session_todos(db, &self.session_id).await?;
+        Ok(Some(TodoCounts::from_todos(&todos)))
     }
 
     /// Files that were changed during this session.
@@ -496,6 +680,10 @@ impl SessionData {
         let db = ctx.data::<DatabaseConnection>()?;
         let models = han_db::entities::session_file_changes::Entity::find()
             .filter(han_db::entities::session_file_changes::Column::SessionId.eq(&self.session_id))
+            .filter(
+                han_db::entities::session_file_changes::Column::Action
+                    .ne(han_db::crud::file_changes::ACTION_READ),
+            )
             .order_by_desc(han_db::entities::session_file_changes::Column::RecordedAt)
             .all(db)
             .await
@@ -548,14 +736,36 @@ impl SessionData {
 
     /// Number of unique files changed in this session.
     async fn file_change_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
-        let db = ctx.data::<DatabaseConnection>()?;
-        let count = han_db::entities::session_file_changes::Entity::find()
-            .filter(han_db::entities::session_file_changes::Column::SessionId.eq(&self.session_id))
-            .all(db)
-            .await
-            .map(|v| v.len() as i32)
-            .map_err(|e| Error::new(e.to_string()))?;
-        Ok(Some(count))
+        let loader = ctx.data::<DataLoader<SessionFileChangeCountLoader>>()?;
+        loader.load_one(self.session_id.clone()).await
+    }
+
+    /// Number of system messages in this session.
+    async fn system_message_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let loader = ctx.data::<DataLoader<SessionSystemMessageCountLoader>>()?;
+        loader.load_one(self.session_id.clone()).await
+    }
+
+    /// Number of hook executions that occurred during this session.
+    async fn hook_execution_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let loader = ctx.data::<DataLoader<SessionHookCountLoader>>()?;
+        loader.load_one(self.session_id.clone()).await
+    }
+
+    /// Hook executions in this session per plugin, busiest first.
+    async fn hook_plugins(&self, ctx: &Context<'_>) -> Result<Option<Vec<HookPluginStat>>> {
+        let loader = ctx.data::<DataLoader<SessionHookPluginStatsLoader>>()?;
+        let rows = loader.load_one(self.session_id.clone()).await?;
+        let stats = rows.unwrap_or_default().into_iter().map(Into::into);
+        Ok(Some(stats.collect()))
+    }
+
+    /// Plugin with the most hook executions in this session.
+    async fn most_active_plugin(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let loader = ctx.data::<DataLoader<SessionHookPluginStatsLoader>>()?;
+        let rows = loader.load_one(self.session_id.clone()).await?;
+        let busiest = rows.and_then(|rows| rows.into_iter().next());
+        Ok(busiest.map(|r| r.plugin_name))
     }
 
     /// Hook executions that occurred during this session.
@@ -677,18 +887,95 @@ impl SessionData {
         }))
     }
 
-    /// Aggregated frustration metrics for this session.
-    async fn frustration_summary(&self) -> Option<FrustrationSummary> {
-        Some(FrustrationSummary::default())
+    /// MCP tool calls in this session, grouped by server and tool.
+    async fn mcp_tool_usage(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Vec<crate::types::dashboard::McpServerUsage>>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_mcp_server_usage(db, Some(&self.session_id), None)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(Some(rows.into_iter().map(Into::into).collect()))
+    }
+
+    /// Token usage reported by MCP servers in this session, kept apart from
+    /// `tokenUsage`. Cost covers only results whose model has known pricing
+    /// and is null when none do. Null when no MCP result reported usage.
+    async fn mcp_token_usage(&self, ctx: &Context<'_>) -> Result<Option<TokenUsageAggregation>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_mcp_token_totals(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(aggregate_mcp_token_usage(&rows))
+    }
+
+    /// Tool calls in this session grouped by tool name, most used first.
+    async fn tool_usage_stats(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Vec<crate::types::dashboard::ToolUsageStat>>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_tool_call_stats(db, Some(&self.session_id), None)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(Some(rows.into_iter().map(Into::into).collect()))
+    }
+
+    /// Name of the tool called most often in this session.
+    async fn top_tool(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_tool_call_stats(db, Some(&self.session_id), None)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(rows.into_iter().next().map(|r| r.tool_name))
+    }
+
+    /// Model family behind most of this session's assistant messages.
+    async fn dominant_model(&self, ctx: &Context<'_>) -> Result<Option<ModelVersion>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let rows = han_db::aggregates::query_session_model_counts(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?;
+        Ok(dominant_model_version(&rows))
+    }
+
+    /// Aggregated frustration metrics for this session, with a per-message
+    /// timeline and trend.
+    async fn frustration_summary(&self, ctx: &Context<'_>) -> Result<Option<FrustrationSummary>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let points = han_db::aggregates::query_frustration_points(db, &self.session_id)
+            .await
+            .map_err(|e| Error::new(e.to_string()))?
+            .into_iter()
+            .map(|(timestamp, frustration_score, level)| FrustrationPoint {
+                timestamp,
+                frustration_score,
+                frustration_level: level.as_deref().and_then(FrustrationLevel::parse),
+            })
+            .collect();
+        Ok(Some(FrustrationSummary::from_points(points)))
+    }
+
+    /// Highest per-message frustration score in this session. Null until a
+    /// message has been scored.
+    async fn max_frustration_score(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let loader = ctx.data::<DataLoader<SessionMaxFrustrationLoader>>()?;
+        loader.load_one(self.session_id.clone()).await
     }
 
     /// Search all messages in this session using FTS.
     async fn search_messages(
         &self,
-        _query: String,
-        _limit: Option<i32>,
-    ) -> Option<Vec<MessageSearchResult>> {
-        Some(vec![])
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<i32>,
+    ) -> Result<Option<Vec<MessageSearchResult>>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let results =
+            crate::types::search_result::search_messages(db, &query, Some(&self.session_id), limit)
+                .await?;
+        Ok(Some(results))
     }
 
     /// All tool results from this session.
@@ -696,6 +983,33 @@ impl SessionData {
         Some(vec![])
     }
 
+    /// Tool results in this session that reported an error, oldest first.
+    async fn tool_result_errors(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ToolResultBlock>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let limit = limit.unwrap_or(100).clamp(1, 1000) as usize;
+        load_tool_result_errors(db, &self.session_id, limit).await
+    }
+
+    /// Number of tool results in this session that reported an error.
+    async fn tool_error_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let loader = ctx.data::<DataLoader<SessionToolErrorCountLoader>>()?;
+        loader.load_one(self.session_id.clone()).await
+    }
+
+    /// Fraction of tool calls whose result was an error. Null until the
+    /// session has a tool result.
+    async fn tool_error_rate(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let errors = ctx.data::<DataLoader<SessionToolErrorCountLoader>>()?;
+        let totals = ctx.data::<DataLoader<SessionToolResultCountLoader>>()?;
+        let errors = errors.load_one(self.session_id.clone()).await?;
+        let total = totals.load_one(self.session_id.clone()).await?;
+        Ok(tool_error_rate(errors.unwrap_or(0), total.unwrap_or(0)))
+    }
+
     /// Number of user turns (user-role messages) in this session.
     async fn turn_count(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
         let db = ctx.data::<DatabaseConnection>()?;
@@ -762,6 +1076,13 @@ impl SessionData {
         }))
     }
 
+    /// Token usage totals across assistant messages, with per-model cost.
+    async fn token_usage(&self, ctx: &Context<'_>) -> Result<Option<TokenUsageAggregation>> {
+        let loader = ctx.data::<DataLoader<SessionTokenTotalsLoader>>()?;
+        let totals = loader.load_one(self.session_id.clone()).await?;
+        Ok(totals.and_then(|t| aggregate_token_usage(&t.by_model)))
+    }
+
     /// Session duration in seconds (first to last message).
     async fn duration(&self) -> Option<i32> {
         let start = self.started_at.as_ref()?;
@@ -782,6 +1103,173 @@ impl SessionData {
     }
 }
 
+/// Task counts for a session via the batched loader. Sessions without tasks
+/// get zero counts.
+async fn load_task_counts(
+    ctx: &Context<'_>,
+    session_id: &str,
+) -> Result<han_db::aggregates::SessionTaskCounts> {
+    let loader = ctx.data::<DataLoader<SessionTaskCountsLoader>>()?;
+    Ok(loader
+        .load_one(session_id.to_string())
+        .await?
+        .unwrap_or_default())
+}
+
+/// Errored tool results in a session, in transcript order, up to `limit`.
+///
+/// Only tool-result user messages the indexer recorded an error for are
+/// parsed.
+async fn load_tool_result_errors(
+    db: &DatabaseConnection,
+    session_id: &str,
+    limit: usize,
+) -> Result<Vec<ToolResultBlock>> {
+    use han_db::entities::tool_call_results;
+    use sea_orm::sea_query::Query;
+
+    let errored = Query::select()
+        .column(tool_call_results::Column::MessageId)
+        .from(tool_call_results::Entity)
+        .and_where(tool_call_results::Column::SessionId.eq(session_id))
+        .and_where(tool_call_results::Column::IsError.eq(true))
+        .to_owned();
+    let msgs = messages::Entity::find()
+        .filter(messages::Column::SessionId.eq(session_id))
+        .filter(messages::Column::MessageType.eq("user"))
+        .filter(messages::Column::Id.in_subquery(errored))
+        .order_by_asc(messages::Column::LineNumber)
+        .all(db)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+
+    let mut errors = Vec::new();
+    for msg in msgs.iter().filter(|m| is_tool_result_user_model(m)) {
+        let blocks = parse_content_blocks(
+            msg.content.as_deref(),
+            msg.raw_json.as_deref(),
+            Some(session_id),
+            Some(&msg.id),
+        );
+        errors.extend(blocks.into_iter().filter_map(|block| match block {
+            ContentBlock::ToolResult(result) if result.is_error => Some(result),
+            _ => None,
+        }));
+        if errors.len() >= limit {
+            break;
+        }
+    }
+    errors.truncate(limit);
+    Ok(errors)
+}
+
+/// Fraction of tool calls that errored, or `None` when there were none.
+fn tool_error_rate(errors: i32, total: i32) -> Option<f64> {
+    (total > 0).then(|| errors as f64 / total as f64)
+}
+
+/// Most common model family across `(model ID, message count)` rows. Ties go
+/// to the family seen first.
+fn dominant_model_version(rows: &[(String, i64)]) -> Option<ModelVersion> {
+    let mut counts: Vec<(ModelVersion, i64)> = Vec::new();
+    for (model, n) in rows {
+        let version = ModelVersion::from_model_id(model);
+        match counts.iter_mut().find(|(v, _)| *v == version) {
+            Some(entry) => entry.1 += n,
+            None => counts.push((version, *n)),
+        }
+    }
+    counts
+        .into_iter()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .map(|(version, _)| version)
+}
+
+/// Fold per-model token sums into a single aggregation, pricing each model
+/// separately. Returns `None` when the session has no assistant messages.
+fn aggregate_token_usage(rows: &[ModelTokenTotals]) -> Option<TokenUsageAggregation> {
+    if rows.is_empty() {
+        return None;
+    }
+
+    let (mut input, mut output, mut cache_read, mut cache_creation) = (0i64, 0i64, 0i64, 0i64);
+    let mut cost = 0.0;
+    for r in rows {
+        let (i, o, cr, cc) = (
+            r.input_tokens,
+            r.output_tokens,
+            r.cache_read_tokens,
+            r.cache_creation_tokens,
+        );
+        input += i;
+        output += o;
+        cache_read += cr;
+        cache_creation += cc;
+        cost += crate::types::pricing::estimate_cost(r.model.as_deref(), i, o, cr, cc);
+    }
+
+    Some(TokenUsageAggregation {
+        total_input_tokens: Some(input),
+        total_output_tokens: Some(output),
+        total_cache_read_tokens: Some(cache_read),
+        total_cached_tokens: Some(cache_read),
+        total_cache_creation_tokens: Some(cache_creation),
+        total_tokens: Some(input + output + cache_read + cache_creation),
+        estimated_cost_usd: Some(cost),
+    })
+}
+
+/// Fold MCP-reported token sums into a single aggregation. Unlike
+/// [`aggregate_token_usage`], unknown or missing models are left unpriced
+/// rather than charged at the default Claude rate.
+fn aggregate_mcp_token_usage(rows: &[ModelTokenTotals]) -> Option<TokenUsageAggregation> {
+    if rows.is_empty() {
+        return None;
+    }
+
+    let (mut input, mut output) = (0i64, 0i64);
+    let mut cost: Option<f64> = None;
+    for r in rows {
+        input += r.input_tokens;
+        output += r.output_tokens;
+        let price = r
+            .model
+            .as_deref()
+            .and_then(crate::types::pricing::lookup_price);
+        if let Some(m) = price {
+            let row_cost = m.price.cost(r.input_tokens, r.output_tokens, 0, 0);
+            *cost.get_or_insert(0.0) += row_cost;
+        }
+    }
+
+    Some(TokenUsageAggregation {
+        total_input_tokens: Some(input),
+        total_output_tokens: Some(output),
+        total_cache_read_tokens: Some(0),
+        total_cached_tokens: Some(0),
+        total_cache_creation_tokens: Some(0),
+        total_tokens: Some(input + output),
+        estimated_cost_usd: cost,
+    })
+}
+
+/// Load a session's todo list, built from TodoWrite, TaskCreate and
+/// TaskUpdate calls.
+async fn load_session_todos(db: &DatabaseConnection, session_id: &str) -> Result<Vec<Todo>> {
+    let row = han_db::crud::session_todos::get(db, session_id)
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+    Ok(row
+        .map(|r| {
+            han_db::crud::session_todos::parse_todos(&r.todos_json)
+                .into_iter()
+                .enumerate()
+                .map(|(i, t)| Todo::from_input(session_id, i, t))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
 /// Session edge for connections.
 #[derive(Debug, Clone, SimpleObject)]
 pub struct SessionEdge {
@@ -828,9 +1316,65 @@ pub struct SessionFilterSource {
     pub project: (),
 }
 
+// -- Session sorting on derived values --
+
+/// Session value to sort by. Everything but `PROJECT_NAME` is derived from
+/// the session's messages; sessions without messages sort as null.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum SessionSortField {
+    /// Number of messages in the session.
+    MessageCount,
+    /// Timestamp of the first message.
+    StartedAt,
+    /// Timestamp of the latest message.
+    UpdatedAt,
+    /// Name of the session's project.
+    ProjectName,
+}
+
+impl SessionSortField {
+    /// SQL expression for this value, correlated on `sessions.id`.
+    fn sql(self) -> &'static str {
+        match self {
+            Self::MessageCount => {
+                "(SELECT COUNT(*) FROM messages WHERE messages.session_id = sessions.id)"
+            }
+            Self::StartedAt => {
+                "(SELECT MIN(timestamp) FROM messages WHERE messages.session_id = sessions.id)"
+            }
+            Self::UpdatedAt => {
+                "(SELECT MAX(timestamp) FROM messages WHERE messages.session_id = sessions.id)"
+            }
+            Self::ProjectName => {
+                "(SELECT name FROM projects WHERE projects.id = sessions.project_id)"
+            }
+        }
+    }
+}
+
+/// One sort key for `Query.sessions`.
+#[derive(Debug, Clone, Copy, InputObject)]
+pub struct SessionSortInput {
+    pub field: SessionSortField,
+    /// Defaults to DESC.
+    pub direction: Option<OrderDirection>,
+}
+
+impl SessionSortInput {
+    /// Append this key to the query's ORDER BY.
+    pub fn apply(
+        &self,
+        query: Select<han_db::entities::sessions::Entity>,
+    ) -> Select<han_db::entities::sessions::Entity> {
+        let direction = self.direction.unwrap_or(OrderDirection::Desc);
+        query.order_by(Expr::cust(self.field.sql()), direction.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_db;
 
     fn make_session(id: &str, date: &str) -> SessionData {
         SessionData {
@@ -848,6 +1392,7 @@ mod tests {
             git_branch: Some("main".into()),
             version: Some("1.0.0".into()),
             worktree_name: None,
+            worktree_id: None,
             source_config_dir: None,
             status: Some("active".into()),
             pr_number: None,
@@ -925,6 +1470,60 @@ mod tests {
         assert_eq!(conn.edges.len(), 3); // s2, s3, s4
     }
 
+    #[test]
+    fn dominant_model_version_sums_each_family() {
+        assert_eq!(dominant_model_version(&[]), None);
+
+        // Two Haiku snapshots outweigh the single most used model ID
+        let rows = vec![
+            ("claude-3-5-sonnet-20241022".to_string(), 5),
+            ("claude-3-haiku-20240307".to_string(), 3),
+            ("claude-3-haiku-20240101".to_string(), 3),
+            ("gpt-4o".to_string(), 1),
+        ];
+        let dominant = dominant_model_version(&rows);
+        assert_eq!(dominant, Some(ModelVersion::Claude3Haiku));
+
+        let unknown = vec![("claude-sonnet-4-5".to_string(), 2)];
+        let dominant = dominant_model_version(&unknown);
+        assert_eq!(dominant, Some(ModelVersion::Unknown));
+    }
+
+    #[test]
+    fn aggregate_token_usage_empty_is_none() {
+        assert!(aggregate_token_usage(&[]).is_none());
+    }
+
+    #[test]
+    fn aggregate_token_usage_prices_each_model() {
+        let rows = vec![
+            ModelTokenTotals {
+                model: Some("claude-3-5-sonnet-20241022".into()),
+                input_tokens: 1_000_000,
+                output_tokens: 100_000,
+                cache_read_tokens: 2_000_000,
+                cache_creation_tokens: 0,
+            },
+            ModelTokenTotals {
+                model: Some("claude-3-haiku-20240307".into()),
+                input_tokens: 4_000_000,
+                output_tokens: 800_000,
+                cache_read_tokens: 0,
+                cache_creation_tokens: 1_000_000,
+            },
+        ];
+        let agg = aggregate_token_usage(&rows).unwrap();
+        assert_eq!(agg.total_input_tokens, Some(5_000_000));
+        assert_eq!(agg.total_output_tokens, Some(900_000));
+        assert_eq!(agg.total_cache_read_tokens, Some(2_000_000));
+        assert_eq!(agg.total_cache_creation_tokens, Some(1_000_000));
+        assert_eq!(agg.total_tokens, Some(8_900_000));
+        // Sonnet: $3 + $1.50 + $0.60 = $5.10
+        // Haiku:  $1 + $1.00 + $0.30 = $2.30
+        let cost = agg.estimated_cost_usd.unwrap();
+        assert!((cost - 7.40).abs() < 0.0001);
+    }
+
     #[test]
     fn session_filter_default_is_empty() {
         let f = SessionFilter::default();
@@ -989,6 +1588,350 @@ mod tests {
         // to_condition() should not panic — it generates an IN subquery
         let _cond = f.to_condition();
     }
+
+    #[tokio::test]
+    async fn test_tool_result_errors_and_counts() {
+        use han_db::entities::tool_call_results;
+        use sea_orm::Set;
+
+        let db = test_db().await;
+        han_db::crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+
+        // (message, [(tool_call_id, is_error)])
+        let results = [
+            ("m1", vec![("c1", false)]),
+            ("m2", vec![("c2", true), ("c3", true)]),
+            ("m3", vec![("c4", false)]),
+            ("m4", vec![("c5", true)]),
+        ];
+        let mut rows = Vec::new();
+        let mut tool_rows = Vec::new();
+        for (line, (id, blocks)) in results.iter().enumerate() {
+            let content: Vec<serde_json::Value> = blocks
+                .iter()
+                .map(|(call, is_error)| {
+                    serde_json::json!({
+                        "type": "tool_result",
+                        "tool_use_id": call,
+                        "content": format!("output of {call}"),
+                        "is_error": is_error,
+                    })
+                })
+                .collect();
+            let raw = serde_json::json!({ "message": { "role": "user", "content": content } });
+            rows.push(messages::ActiveModel {
+                id: Set(id.to_string()),
+                session_id: Set("s1".to_string()),
+                message_type: Set("user".to_string()),
+                raw_json: Set(Some(raw.to_string())),
+                timestamp: Set(format!("2026-04-20T10:00:0{line}Z")),
+                line_number: Set(line as i32),
+                ..Default::default()
+            });
+            for (call, is_error) in blocks {
+                tool_rows.push(tool_call_results::ActiveModel {
+                    tool_call_id: Set(call.to_string()),
+                    session_id: Set("s1".to_string()),
+                    message_id: Set(id.to_string()),
+                    content: Set(format!("output of {call}")),
+                    is_error: Set(*is_error),
+                    has_image: Set(false),
+                });
+            }
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+        han_db::crud::tool_call_results::insert_batch(&db, tool_rows)
+            .await
+            .unwrap();
+
+        let errors = load_tool_result_errors(&db, "s1", 100).await.unwrap();
+        let ids: Vec<&str> = errors.iter().map(|e| e.tool_call_id.as_str()).collect();
+        assert_eq!(ids, ["c2", "c3", "c5"]);
+        assert_eq!(errors[0].content, "output of c2");
+        assert!(errors.iter().all(|e| e.is_error));
+
+        let errors = load_tool_result_errors(&db, "s1", 2).await.unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(load_tool_result_errors(&db, "missing", 100)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let loaders = crate::loaders::HanLoaders::new(db.clone());
+        let keys = ["s1".to_string(), "missing".to_string()];
+        let error_counts = loaders
+            .session_tool_error_count
+            .load_many(keys.clone())
+            .await
+            .unwrap();
+        let totals = loaders
+            .session_tool_result_count
+            .load_many(keys)
+            .await
+            .unwrap();
+        assert_eq!(error_counts["s1"], 3);
+        assert_eq!(totals["s1"], 5);
+        assert_eq!(error_counts["missing"], 0);
+        assert_eq!(tool_error_rate(error_counts["s1"], totals["s1"]), Some(0.6));
+        assert_eq!(tool_error_rate(0, 0), None);
+    }
+
+    #[tokio::test]
+    async fn test_task_counts_over_graphql() {
+        let db = test_db().await;
+        for id in ["s1", "s2"] {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+        }
+
+        // (task, completed_at, outcome, confidence); all start at 10:00
+        let tasks = [
+            ("t1", None, None, None),
+            ("t2", Some("12:00"), Some("success"), Some(0.5)),
+            ("t3", Some("11:00"), None, None),
+            ("t4", Some("10:30"), Some("failure"), Some(0.9)),
+        ];
+        for (task_id, completed, outcome, confidence) in tasks {
+            let task = han_db::entities::tasks::Model {
+                id: task_id.to_string(),
+                session_id: Some("s1".to_string()),
+                task_id: task_id.to_string(),
+                description: format!("task {task_id}"),
+                task_type: "fix".to_string(),
+                outcome: outcome.map(str::to_string),
+                confidence,
+                notes: None,
+                files_modified: None,
+                tests_added: None,
+                started_at: "2026-04-20T10:00:00Z".to_string(),
+                completed_at: completed.map(|t| format!("2026-04-20T{t}:00Z")),
+            };
+            han_db::crud::tasks::insert(&db, task).await.unwrap();
+        }
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let query = |id: &str| {
+            format!(
+                r#"{{ session(id: "{id}") {{
+                    activeTaskCount completedTaskCount failedTaskCount
+                    taskSuccessRate calibrationScore estimatedTaskCost tasks {{ totalCount }}
+                }} }}"#
+            )
+        };
+        let res = schema.execute(query("s1")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let session = &res.data.into_json().unwrap()["session"];
+        assert_eq!(session["activeTaskCount"], 1);
+        assert_eq!(session["completedTaskCount"], 2);
+        assert_eq!(session["failedTaskCount"], 1);
+        let total = ["activeTaskCount", "completedTaskCount", "failedTaskCount"]
+            .iter()
+            .map(|f| session[f].as_i64().unwrap())
+            .sum::<i64>();
+        assert_eq!(session["tasks"]["totalCount"], total);
+        let rate = session["taskSuccessRate"].as_f64().unwrap();
+        assert!((rate - 2.0 / 3.0).abs() < 1e-9);
+        // 0.5 succeeded (bucket midpoint 0.55), 0.9 failed (midpoint 0.95):
+        // 1 - (0.45² + 0.95²) / 2
+        let calibration = session["calibrationScore"].as_f64().unwrap();
+        assert!((calibration - 0.4475).abs() < 1e-9, "{calibration}");
+        // 2h at 0.5 confidence plus 1h at the default 1.0
+        let cost = session["estimatedTaskCost"].as_f64().unwrap();
+        let expected = 2.0 * crate::types::metrics::developer_hourly_rate();
+        assert!((cost - expected).abs() < 1e-6, "{cost} != {expected}");
+
+        let res = schema.execute(query("s2")).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let session = &res.data.into_json().unwrap()["session"];
+        assert_eq!(session["activeTaskCount"], 0);
+        assert_eq!(session["tasks"]["totalCount"], 0);
+        assert!(session["taskSuccessRate"].is_null());
+        assert!(session["calibrationScore"].is_null());
+
+        // Root fields cover every session, and only s1 has scored tasks
+        let res = schema
+            .execute("{ calibrationScore metrics { calibrationScore } }")
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["calibrationScore"], calibration);
+        assert_eq!(data["metrics"]["calibrationScore"], calibration);
+        assert_eq!(session["estimatedTaskCost"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_hook_plugins_over_graphql() {
+        let db = test_db().await;
+        for id in ["s1", "s2"] {
+            han_db::crud::sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+                .await
+                .unwrap();
+        }
+
+        // (plugin, duration, passed), all in s1
+        let runs = [
+            ("jest", 40, true),
+            ("biome", 10, true),
+            ("jest", 60, false),
+            ("jest", 20, true),
+            ("biome", 30, false),
+        ];
+        for (plugin, duration, passed) in runs {
+            han_db::crud::hooks::record_execution(
+                &db,
+                Some("s1".to_string()),
+                None,
+                "Stop".to_string(),
+                "check".to_string(),
+                Some(plugin.to_string()),
+                None,
+                duration,
+                if passed { 0 } else { 1 },
+                passed,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let query = r#"{
+            s1: session(id: "s1") {
+                mostActivePlugin
+                hookPlugins { pluginName executionCount passCount totalDurationMs }
+            }
+            s2: session(id: "s2") { mostActivePlugin hookPlugins { pluginName } }
+            topHookPlugins(limit: 1) { pluginName executionCount }
+        }"#;
+        let res = schema.execute(query).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["s1"]["mostActivePlugin"], "jest");
+        assert_eq!(
+            data["s1"]["hookPlugins"],
+            serde_json::json!([
+                { "pluginName": "jest", "executionCount": 3, "passCount": 2, "totalDurationMs": 120 },
+                { "pluginName": "biome", "executionCount": 2, "passCount": 1, "totalDurationMs": 40 },
+            ])
+        );
+        assert!(data["s2"]["mostActivePlugin"].is_null());
+        assert_eq!(data["s2"]["hookPlugins"], serde_json::json!([]));
+        assert_eq!(
+            data["topHookPlugins"],
+            serde_json::json!([{ "pluginName": "jest", "executionCount": 3 }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sessions_sort() {
+        use sea_orm::Set;
+
+        let db = test_db().await;
+
+        // (session, project, message times); s2 and s3 start together and
+        // share a project, so those keys tie and fall back to session ID
+        let sessions = [
+            ("s1", "beta", vec!["10:00", "10:05", "10:10"]),
+            ("s2", "alpha", vec!["09:00"]),
+            ("s3", "alpha", vec!["09:00", "11:00"]),
+        ];
+        let mut rows = Vec::new();
+        for (id, project, times) in &sessions {
+            let project = han_db::crud::projects::upsert(
+                &db,
+                None,
+                format!("{project}-{id}"),
+                format!("/{project}/{id}"),
+                None,
+                project.to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            han_db::crud::sessions::upsert(
+                &db,
+                id.to_string(),
+                Some(project.id),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            for (line, time) in times.iter().enumerate() {
+                rows.push(messages::ActiveModel {
+                    id: Set(format!("{id}-m{line}")),
+                    session_id: Set(id.to_string()),
+                    message_type: Set("user".to_string()),
+                    timestamp: Set(format!("2026-04-20T{time}:00Z")),
+                    line_number: Set(line as i32),
+                    ..Default::default()
+                });
+            }
+        }
+        han_db::crud::messages::insert_batch(&db, rows)
+            .await
+            .unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db, tx);
+        let order = |args: &str| {
+            let schema = schema.clone();
+            let query = format!("{{ sessions({args}) {{ edges {{ node {{ sessionId }} }} }} }}");
+            async move {
+                let res = schema.execute(query).await;
+                assert!(res.errors.is_empty(), "{:?}", res.errors);
+                let json = res.data.into_json().unwrap();
+                json["sessions"]["edges"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|e| e["node"]["sessionId"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let cases = [
+            ("sort: { field: MESSAGE_COUNT }", ["s1", "s3", "s2"]),
+            ("sort: { field: UPDATED_AT }", ["s3", "s1", "s2"]),
+            (
+                "sort: { field: STARTED_AT, direction: ASC }",
+                ["s2", "s3", "s1"],
+            ),
+            (
+                "sort: { field: STARTED_AT, direction: DESC }",
+                ["s1", "s2", "s3"],
+            ),
+            (
+                "sort: { field: PROJECT_NAME, direction: ASC }",
+                ["s2", "s3", "s1"],
+            ),
+            (
+                "sortBy: [{ field: PROJECT_NAME, direction: ASC }, { field: MESSAGE_COUNT }]",
+                ["s3", "s2", "s1"],
+            ),
+            (
+                "sort: { field: STARTED_AT, direction: ASC }, \
+                 sortBy: [{ field: UPDATED_AT }]",
+                ["s3", "s2", "s1"],
+            ),
+        ];
+        for (args, expected) in cases {
+            assert_eq!(order(args).await, expected, "{args}");
+        }
+    }
 }
 
 /// Build a SessionConnection from database models.
@@ -49,6 +49,48 @@ impl Org {
     }
 }
 
+/// Stripe subscription of an organization (hosted mode).
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub plan: String,
+    pub status: String,
+    pub seats: i32,
+    pub current_period_end: Option<String>,
+    pub cancel_at: Option<String>,
+}
+
+impl From<han_db::entities::subscriptions::Model> for Subscription {
+    fn from(m: han_db::entities::subscriptions::Model) -> Self {
+        Self {
+            plan: m.plan,
+            status: m.status,
+            seats: m.seats,
+            current_period_end: m.current_period_end,
+            cancel_at: m.cancel_at,
+        }
+    }
+}
+
+#[Object]
+impl Subscription {
+    async fn plan(&self) -> &str {
+        &self.plan
+    }
+    async fn status(&self) -> &str {
+        &self.status
+    }
+    async fn seats(&self) -> i32 {
+        self.seats
+    }
+    async fn current_period_end(&self) -> Option<&str> {
+        self.current_period_end.as_deref()
+    }
+    /// Whether the subscription has ended or is scheduled to.
+    async fn is_canceled(&self) -> bool {
+        self.status == "canceled" || self.cancel_at.is_some()
+    }
+}
+
 /// Team member data (hosted mode).
 #[derive(Debug, Clone)]
 pub struct TeamMember {
@@ -340,4 +382,81 @@ mod tests {
         let m2 = m.clone();
         assert_eq!(m.total_sessions, m2.total_sessions);
     }
+
+    #[tokio::test]
+    async fn subscription_requires_org_admin() {
+        use crate::context::{GraphQLContext, UserContext, UserRole};
+        use han_db::crud::subscriptions::{upsert_subscription, StripeSubscriptionData};
+        use han_db::entities::{teams, users};
+        use sea_orm::{EntityTrait, Set};
+
+        let db = crate::test_db().await;
+        let now = "2026-04-25T00:00:00Z".to_string();
+        users::Entity::insert(users::ActiveModel {
+            id: Set("u-1".into()),
+            role: Set("admin".into()),
+            created_at: Set(now.clone()),
+            updated_at: Set(now.clone()),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .unwrap();
+        teams::Entity::insert(teams::ActiveModel {
+            id: Set("org-1".into()),
+            name: Set("Org".into()),
+            slug: Set("org".into()),
+            owner_id: Set("u-1".into()),
+            created_at: Set(now.clone()),
+            updated_at: Set(now),
+        })
+        .exec(&db)
+        .await
+        .unwrap();
+        let data = StripeSubscriptionData {
+            stripe_subscription_id: "sub_1".into(),
+            plan: "pro".into(),
+            status: "active".into(),
+            seats: 5,
+            current_period_end: Some("2026-05-25T00:00:00+00:00".into()),
+            cancel_at: Some("2026-05-25T00:00:00+00:00".into()),
+        };
+        upsert_subscription(&db, "org-1", data).await.unwrap();
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(16);
+        let schema = crate::schema::build_schema(db.clone(), tx.clone());
+        let query = "{ subscription { plan status seats currentPeriodEnd isCanceled } }";
+        let run = |role: Option<UserRole>| {
+            let mut ctx = GraphQLContext::new(db.clone(), tx.clone());
+            if let Some(role) = role {
+                ctx = ctx.with_user(UserContext {
+                    id: "u-1".into(),
+                    display_name: None,
+                    role,
+                    org_id: Some("org-1".into()),
+                    project_ids: None,
+                });
+            }
+            schema.execute(Request::new(query).data(ctx))
+        };
+
+        let res = run(Some(UserRole::Admin)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap()["subscription"],
+            serde_json::json!({
+                "plan": "pro",
+                "status": "active",
+                "seats": 5,
+                "currentPeriodEnd": "2026-05-25T00:00:00+00:00",
+                "isCanceled": true,
+            })
+        );
+
+        for role in [None, Some(UserRole::Ic), Some(UserRole::Manager)] {
+            let res = run(role).await;
+            assert_eq!(res.errors.len(), 1);
+            assert!(res.errors[0].message.contains("Forbidden"));
+        }
+    }
 }
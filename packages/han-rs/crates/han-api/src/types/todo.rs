@@ -1,7 +1,10 @@
 //! Todo GraphQL types.
 
-use crate::connection::PageInfo;
+use crate::connection::{apply_connection_args, ConnectionArgs, PageInfo};
+use crate::node::encode_global_id;
+use crate::types::enums::TodoStatus;
 use async_graphql::*;
+use han_db::crud::session_todos::TodoInput;
 
 /// Todo item from Claude Code's TodoWrite tool.
 #[derive(Debug, Clone, SimpleObject)]
@@ -62,6 +65,64 @@ impl Default for TodoConnection {
     }
 }
 
+impl Todo {
+    /// Build a Todo from a stored TodoWrite item.
+    ///
+    /// TodoWrite items usually lack an ID, so the list position is used.
+    pub fn from_input(session_id: &str, index: usize, input: TodoInput) -> Self {
+        let local_id = input.id.unwrap_or_else(|| index.to_string());
+        let status = match input.status.as_str() {
+            "pending" => Some(TodoStatus::Pending),
+            "in_progress" => Some(TodoStatus::InProgress),
+            "completed" => Some(TodoStatus::Completed),
+            _ => None,
+        };
+        Self {
+            id: Some(encode_global_id(
+                "Todo",
+                &format!("{session_id}:{local_id}"),
+            )),
+            content: Some(input.content),
+            status,
+            active_form: input.active_form,
+        }
+    }
+}
+
+impl TodoCounts {
+    /// Count todos by status.
+    pub fn from_todos(todos: &[Todo]) -> Self {
+        let count = |s: TodoStatus| todos.iter().filter(|t| t.status == Some(s)).count() as i32;
+        Self {
+            total: Some(todos.len() as i32),
+            pending: Some(count(TodoStatus::Pending)),
+            in_progress: Some(count(TodoStatus::InProgress)),
+            completed: Some(count(TodoStatus::Completed)),
+        }
+    }
+}
+
+impl TodoConnection {
+    /// Paginate an in-memory todo list.
+    pub fn from_todos(todos: &[Todo], args: &ConnectionArgs) -> Self {
+        let conn = apply_connection_args(todos, args, |t| {
+            t.id.as_ref().map(|id| id.to_string()).unwrap_or_default()
+        });
+        Self {
+            edges: conn
+                .edges
+                .into_iter()
+                .map(|e| TodoEdge {
+                    node: e.node,
+                    cursor: e.cursor,
+                })
+                .collect(),
+            page_info: conn.page_info,
+            total_count: conn.total_count,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +140,54 @@ mod tests {
         assert_eq!(tc.total_count, 0);
         assert!(tc.edges.is_empty());
     }
+
+    fn todo(content: &str, status: &str) -> TodoInput {
+        TodoInput {
+            id: None,
+            content: content.to_string(),
+            status: status.to_string(),
+            active_form: None,
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn todo_from_input_maps_status_and_id() {
+        let t = Todo::from_input("sess-1", 2, todo("Ship it", "in_progress"));
+        assert_eq!(t.id, Some(ID("Todo:sess-1:2".to_string())));
+        assert_eq!(t.status, Some(TodoStatus::InProgress));
+        assert_eq!(t.content.as_deref(), Some("Ship it"));
+
+        let t = Todo::from_input("sess-1", 0, todo("Odd", "blocked"));
+        assert_eq!(t.status, None);
+    }
+
+    #[test]
+    fn todo_counts_from_todos() {
+        let todos: Vec<Todo> = [("a", "pending"), ("b", "completed"), ("c", "completed")]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (c, s))| Todo::from_input("s", i, todo(c, s)))
+            .collect();
+        let counts = TodoCounts::from_todos(&todos);
+        assert_eq!(counts.total, Some(3));
+        assert_eq!(counts.pending, Some(1));
+        assert_eq!(counts.in_progress, Some(0));
+        assert_eq!(counts.completed, Some(2));
+    }
+
+    #[test]
+    fn todo_connection_paginates() {
+        let todos: Vec<Todo> = (0..3)
+            .map(|i| Todo::from_input("s", i, todo("x", "pending")))
+            .collect();
+        let args = ConnectionArgs {
+            first: Some(2),
+            ..Default::default()
+        };
+        let conn = TodoConnection::from_todos(&todos, &args);
+        assert_eq!(conn.total_count, 3);
+        assert_eq!(conn.edges.len(), 2);
+        assert!(conn.page_info.has_next_page);
+    }
 }
@@ -0,0 +1,37 @@
+//! Worktree GraphQL type.
+
+use crate::node::encode_global_id;
+use async_graphql::*;
+use han_graphql_derive::GraphQLEntity;
+
+/// A linked git worktree that sessions ran in.
+#[derive(Debug, Clone, SimpleObject, GraphQLEntity)]
+#[graphql(complex, name = "Worktree")]
+#[graphql_entity(
+    model = "han_db::entities::worktrees::Model",
+    entity = "han_db::entities::worktrees::Entity",
+    columns = "han_db::entities::worktrees::Column",
+    type_name = "Worktree"
+)]
+pub struct Worktree {
+    #[graphql(skip)]
+    #[graphql_entity(skip, source_field = "id")]
+    pub raw_id: String,
+
+    /// Checkout directory of the worktree.
+    pub project_dir: String,
+    /// Directory of the main checkout the worktree was added from.
+    pub main_project_dir: String,
+    pub branch_name: Option<String>,
+    pub worktree_name: String,
+    pub created_at: String,
+    pub last_seen_at: String,
+}
+
+#[ComplexObject]
+impl Worktree {
+    /// Global ID.
+    pub async fn id(&self) -> ID {
+        encode_global_id("Worktree", &self.raw_id)
+    }
+}
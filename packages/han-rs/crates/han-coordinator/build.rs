@@ -0,0 +1,64 @@
+//! Embed build metadata reported by `CoordinatorService.GetVersion`.
+//!
+//! Emits the same variables vergen would (`VERGEN_GIT_SHA`,
+//! `VERGEN_BUILD_DATE`, `VERGEN_RUSTC_SEMVER`) without pulling in the crate.
+//! Values fall back to "unknown" when git or rustc cannot be queried, e.g.
+//! when building from a source tarball.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    let git_sha = git(&manifest_dir, &["rev-parse", "HEAD"]);
+    println!(
+        "cargo:rustc-env=VERGEN_GIT_SHA={}",
+        git_sha.as_deref().unwrap_or("unknown")
+    );
+
+    // Re-run when HEAD moves so the embedded SHA stays current
+    if let Some(git_dir) = git(&manifest_dir, &["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(head_ref) = git(&manifest_dir, &["symbolic-ref", "-q", "HEAD"]) {
+            println!(
+                "cargo:rerun-if-changed={}",
+                git_dir.join(head_ref).display()
+            );
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+
+    println!(
+        "cargo:rustc-env=VERGEN_BUILD_DATE={}",
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .and_then(|v| v.split_whitespace().nth(1).map(str::to_string));
+    println!(
+        "cargo:rustc-env=VERGEN_RUSTC_SEMVER={}",
+        rustc_version.as_deref().unwrap_or("unknown")
+    );
+}
+
+/// Run a git command in `dir`, returning trimmed stdout on success.
+fn git(dir: &str, args: &[&str]) -> Option<String> {
+    let out = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(out.stdout).ok()?.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
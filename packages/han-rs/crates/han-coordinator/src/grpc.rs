@@ -5,7 +5,7 @@
 //! - SessionService: han-db CRUD
 //! - IndexerService: han-indexer processor
 //! - HookService: hook engine with streaming
-//! - SlotService: in-memory HashMap
+//! - SlotService: in-memory HashMaps for ownership and values
 //! - MemoryService: han-db FTS search
 
 use han_proto::coordinator::*;
@@ -18,13 +18,18 @@ use han_proto::coordinator::memory_service_server::MemoryService as MemoryServic
 
 use crate::hooks::executor::HookOutputLine;
 use crate::hooks::HookEngine;
+use crate::state::SessionCache;
+use han_api::context::DbChangeEvent;
 use han_db::crud;
 use han_db::search::SqliteSearch;
-use sea_orm::DatabaseConnection;
+use han_indexer::WatcherHandle;
+use han_indexer::watcher::WatcherError;
+use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::{Mutex, RwLock, mpsc};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock, broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
@@ -40,12 +45,150 @@ pub struct SlotEntry {
     ttl_seconds: Option<i32>,
 }
 
+/// A slot value, optionally expiring after a TTL.
+#[derive(Debug, Clone)]
+struct SlotValue {
+    value: String,
+    expires_at: Option<tokio::time::Instant>,
+}
+
+impl SlotValue {
+    fn is_expired(&self, now: tokio::time::Instant) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+/// Named value cells behind SlotService Set/Get/Delete/Watch.
+///
+/// Every change is broadcast to watchers. Expired values are hidden from
+/// reads and evicted (notifying watchers) on the next Get or Delete.
+pub struct SlotValueStore {
+    values: RwLock<HashMap<String, SlotValue>>,
+    events: broadcast::Sender<SlotEvent>,
+}
+
+impl Default for SlotValueStore {
+    fn default() -> Self {
+        Self {
+            values: RwLock::new(HashMap::new()),
+            events: broadcast::channel(256).0,
+        }
+    }
+}
+
+impl SlotValueStore {
+    pub async fn set(&self, slot_name: String, value: String, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| tokio::time::Instant::now() + ttl);
+        let mut values = self.values.write().await;
+        values.insert(
+            slot_name.clone(),
+            SlotValue {
+                value: value.clone(),
+                expires_at,
+            },
+        );
+        // Notify under the lock so watchers see changes in write order
+        self.notify(slot_name, Some(value));
+    }
+
+    pub async fn get(&self, slot_name: &str) -> Option<String> {
+        self.purge_expired().await;
+        let values = self.values.read().await;
+        values.get(slot_name).map(|v| v.value.clone())
+    }
+
+    /// Remove a value, returning it if it was still live.
+    pub async fn delete(&self, slot_name: &str) -> Option<String> {
+        self.purge_expired().await;
+        let mut values = self.values.write().await;
+        let removed = values.remove(slot_name)?;
+        self.notify(slot_name.to_string(), None);
+        Some(removed.value)
+    }
+
+    /// Names of all live values, sorted.
+    pub async fn names(&self) -> Vec<String> {
+        let now = tokio::time::Instant::now();
+        let values = self.values.read().await;
+        let mut names: Vec<String> = values
+            .iter()
+            .filter(|(_, v)| !v.is_expired(now))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SlotEvent> {
+        self.events.subscribe()
+    }
+
+    async fn purge_expired(&self) {
+        let now = tokio::time::Instant::now();
+        let mut values = self.values.write().await;
+        let expired: Vec<String> = values
+            .iter()
+            .filter(|(_, v)| v.is_expired(now))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for slot_name in expired {
+            values.remove(&slot_name);
+            self.notify(slot_name, None);
+        }
+    }
+
+    fn notify(&self, slot_name: String, value: Option<String>) {
+        // Sending only fails when nobody is watching
+        let _ = self.events.send(SlotEvent { slot_name, value });
+    }
+}
+
 /// Shared coordinator state passed to all gRPC services.
 pub struct CoordinatorState {
     pub db: DatabaseConnection,
     pub start_time: Instant,
     pub hook_engine: Arc<Mutex<HookEngine>>,
     pub slots: Arc<RwLock<HashMap<String, SlotEntry>>>,
+    pub slot_values: SlotValueStore,
+    /// Database change events, shared with GraphQL subscriptions.
+    pub events: broadcast::Sender<DbChangeEvent>,
+    /// File watcher, absent when started with `--no-watcher`.
+    pub watcher: Option<WatcherHandle>,
+    /// Open GraphQL WebSocket connections, counted by the HTTP server.
+    pub websocket_connections: Arc<AtomicU32>,
+    /// Server-streaming RPCs still producing output.
+    pub grpc_streams: Arc<AtomicU32>,
+    /// Sessions recently served by `SessionService.Get`.
+    pub session_cache: SessionCache,
+}
+
+impl CoordinatorState {
+    /// Count a server stream as active until the returned guard is dropped.
+    fn stream_opened(&self) -> StreamGuard {
+        self.grpc_streams.fetch_add(1, Ordering::Relaxed);
+        StreamGuard(self.grpc_streams.clone())
+    }
+
+    /// Drop the cached copy of a session an RPC just indexed and tell
+    /// subscribers about it.
+    fn session_indexed(&self, session_id: &str) {
+        if session_id.is_empty() {
+            return;
+        }
+        self.session_cache.invalidate(session_id);
+        let _ = self.events.send(DbChangeEvent::SessionIndexed {
+            session_id: session_id.to_string(),
+        });
+    }
+}
+
+/// Decrements the active gRPC stream count when dropped.
+struct StreamGuard(Arc<AtomicU32>);
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 // ============================================================================
@@ -122,6 +265,74 @@ impl CoordinatorServiceTrait for CoordinatorServiceImpl {
             watched_paths: Vec::new(),
         }))
     }
+
+    async fn get_version(
+        &self,
+        _request: Request<VersionRequest>,
+    ) -> Result<Response<VersionResponse>, Status> {
+        Ok(Response::new(VersionResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            build_date: env!("VERGEN_BUILD_DATE").to_string(),
+            git_commit: env!("VERGEN_GIT_SHA").to_string(),
+            rust_version: env!("VERGEN_RUSTC_SEMVER").to_string(),
+            features: enabled_features(),
+        }))
+    }
+
+    async fn get_status(
+        &self,
+        _request: Request<StatusRequest>,
+    ) -> Result<Response<GetStatusResponse>, Status> {
+        let db = &self.state.db;
+        let indexed_sessions = han_db::entities::sessions::Entity::find()
+            .count(db)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let indexed_messages = han_db::entities::messages::Entity::find()
+            .count(db)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let (hook_cache_size, discovered_hooks) = {
+            let engine = self.state.hook_engine.lock().await;
+            (engine.cache_len().await, engine.all_hooks().len())
+        };
+        let pool = han_db::connection::pool_stats(db).await;
+
+        Ok(Response::new(GetStatusResponse {
+            uptime_seconds: self.state.start_time.elapsed().as_secs() as i64,
+            indexed_sessions: indexed_sessions as i64,
+            indexed_messages: indexed_messages as i64,
+            active_websocket_connections: count_i32(
+                self.state.websocket_connections.load(Ordering::Relaxed),
+            ),
+            hook_cache_size: count_i32(hook_cache_size),
+            discovered_hooks: count_i32(discovered_hooks),
+            db_pool_active: count_i32(pool.active),
+            db_pool_idle: count_i32(pool.idle),
+            grpc_active_streams: count_i32(self.state.grpc_streams.load(Ordering::Relaxed)),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }))
+    }
+}
+
+/// Clamp a count into an `int32` proto field.
+fn count_i32(count: impl TryInto<i32>) -> i32 {
+    count.try_into().unwrap_or(i32::MAX)
+}
+
+/// Capabilities compiled into this binary, as reported by `GetVersion`.
+///
+/// gRPC and TLS are always built in; the rest follow Cargo features.
+fn enabled_features() -> Vec<String> {
+    let mut features = vec!["grpc", "tls"];
+    if cfg!(feature = "sqlite") {
+        // Full-text search is backed by SQLite FTS5
+        features.extend(["sqlite", "fts"]);
+    }
+    if cfg!(feature = "postgres") {
+        features.push("postgres");
+    }
+    features.into_iter().map(String::from).collect()
 }
 
 // ============================================================================
@@ -146,6 +357,81 @@ fn model_to_session_data(s: &han_db::entities::sessions::Model) -> SessionData {
     }
 }
 
+/// Messages read per query while streaming a session.
+const STREAM_PAGE_SIZE: u64 = 500;
+
+/// Characters of message content included in `MessageEvent.content_preview`.
+const CONTENT_PREVIEW_CHARS: usize = 200;
+
+fn model_to_message_event(m: han_db::entities::messages::Model) -> MessageEvent {
+    let content_preview = m
+        .content
+        .as_deref()
+        .unwrap_or_default()
+        .chars()
+        .take(CONTENT_PREVIEW_CHARS)
+        .collect();
+    MessageEvent {
+        message_id: m.id,
+        message_type: m.message_type,
+        tool_name: m.tool_name,
+        timestamp: m.timestamp,
+        content_preview,
+        raw_json: m.raw_json,
+        line_number: m.line_number,
+    }
+}
+
+/// Messages already sent on a `StreamMessages` stream.
+struct MessageCursor {
+    from_line: i32,
+    /// Last `(line_number, message_id)` sent from each source file.
+    seen: HashMap<Option<String>, (i32, String)>,
+}
+
+/// Send every stored message past `cursor`, advancing it as they go out.
+///
+/// Returns `false` once the stream should end: the client disconnected or
+/// the database query failed (the error is sent to the client).
+async fn send_messages_after(
+    db: &DatabaseConnection,
+    session_id: &str,
+    cursor: &mut MessageCursor,
+    tx: &mpsc::Sender<Result<MessageEvent, Status>>,
+) -> bool {
+    loop {
+        let page = crud::messages::list_page_after(
+            db,
+            session_id,
+            cursor.from_line,
+            &cursor.seen,
+            STREAM_PAGE_SIZE,
+        )
+        .await;
+        let page = match page {
+            Ok(page) => page,
+            Err(e) => {
+                let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                return false;
+            }
+        };
+
+        let last_page = (page.len() as u64) < STREAM_PAGE_SIZE;
+        for message in page {
+            cursor.seen.insert(
+                message.source_file_name.clone(),
+                (message.line_number, message.id.clone()),
+            );
+            if tx.send(Ok(model_to_message_event(message))).await.is_err() {
+                return false;
+            }
+        }
+        if last_page {
+            return true;
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl SessionServiceTrait for SessionServiceImpl {
     async fn get_active(
@@ -172,11 +458,20 @@ impl SessionServiceTrait for SessionServiceImpl {
         request: Request<GetSessionRequest>,
     ) -> Result<Response<SessionResponse>, Status> {
         let req = request.into_inner();
+        if let Some(session) = self.state.session_cache.get(&req.session_id) {
+            return Ok(Response::new(SessionResponse {
+                session: Some(session),
+            }));
+        }
+        let generation = self.state.session_cache.generation();
         let result = crud::sessions::get(&self.state.db, &req.session_id)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
         let session = result.as_ref().map(model_to_session_data);
+        if let Some(ref session) = session {
+            self.state.session_cache.insert(generation, session.clone());
+        }
         Ok(Response::new(SessionResponse { session }))
     }
 
@@ -204,6 +499,61 @@ impl SessionServiceTrait for SessionServiceImpl {
             total,
         }))
     }
+
+    type StreamMessagesStream = ReceiverStream<Result<MessageEvent, Status>>;
+
+    async fn stream_messages(
+        &self,
+        request: Request<StreamMessagesRequest>,
+    ) -> Result<Response<Self::StreamMessagesStream>, Status> {
+        let req = request.into_inner();
+        let db = self.state.db.clone();
+        // Subscribe before reading history so messages indexed meanwhile aren't missed
+        let events = req.follow.then(|| self.state.events.subscribe());
+        let (tx, rx) = mpsc::channel(64);
+        let stream = self.state.stream_opened();
+
+        tokio::spawn(async move {
+            let _stream = stream;
+            let session_id = req.session_id;
+            let mut cursor = MessageCursor {
+                from_line: req.from_line_number,
+                seen: HashMap::new(),
+            };
+            if !send_messages_after(&db, &session_id, &mut cursor, &tx).await {
+                return;
+            }
+            let Some(mut events) = events else {
+                return;
+            };
+
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    event = events.recv() => {
+                        let caught_up = match event {
+                            Ok(DbChangeEvent::SessionMessageAdded { session_id: id, .. })
+                                if id == session_id =>
+                            {
+                                send_messages_after(&db, &session_id, &mut cursor, &tx).await
+                            }
+                            Ok(_) => true,
+                            // Missed events may include ours; the cursor makes re-reading safe
+                            Err(broadcast::error::RecvError::Lagged(_)) => {
+                                send_messages_after(&db, &session_id, &mut cursor, &tx).await
+                            }
+                            Err(broadcast::error::RecvError::Closed) => false,
+                        };
+                        if !caught_up {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
 }
 
 // ============================================================================
@@ -223,6 +573,10 @@ impl IndexerServiceTrait for IndexerServiceImpl {
         let results = han_indexer::full_scan_and_index(&self.state.db)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
+        for result in &results {
+            crate::telemetry::record_session_indexed(result.messages_indexed);
+            self.state.session_indexed(&result.session_id);
+        }
 
         let sessions_indexed = results.len() as i32;
         let messages_indexed: i32 = results.iter().map(|r| r.messages_indexed as i32).sum();
@@ -247,6 +601,8 @@ impl IndexerServiceTrait for IndexerServiceImpl {
         )
         .await
         .map_err(|e| Status::internal(e.to_string()))?;
+        crate::telemetry::record_session_indexed(result.messages_indexed);
+        self.state.session_indexed(&result.session_id);
 
         Ok(Response::new(IndexFileResponse {
             session_id: result.session_id,
@@ -256,6 +612,64 @@ impl IndexerServiceTrait for IndexerServiceImpl {
             error: result.error,
         }))
     }
+
+    async fn watch_directory(
+        &self,
+        request: Request<WatchDirectoryRequest>,
+    ) -> Result<Response<WatchDirectoryResponse>, Status> {
+        let path = request.into_inner().path;
+        if path.is_empty() {
+            return Err(Status::invalid_argument("path is required"));
+        }
+        let watcher = self
+            .state
+            .watcher
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("File watcher is not running"))?;
+        let changed = watcher
+            .add_path(path.into())
+            .await
+            .map_err(watcher_status)?;
+        Ok(Response::new(watch_directory_response(watcher, changed)))
+    }
+
+    async fn unwatch_directory(
+        &self,
+        request: Request<WatchDirectoryRequest>,
+    ) -> Result<Response<WatchDirectoryResponse>, Status> {
+        let path = request.into_inner().path;
+        if path.is_empty() {
+            return Err(Status::invalid_argument("path is required"));
+        }
+        let watcher = self
+            .state
+            .watcher
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("File watcher is not running"))?;
+        let changed = watcher
+            .remove_path(std::path::Path::new(&path))
+            .await
+            .map_err(watcher_status)?;
+        Ok(Response::new(watch_directory_response(watcher, changed)))
+    }
+}
+
+fn watcher_status(e: WatcherError) -> Status {
+    match e {
+        WatcherError::NotRunning => Status::unavailable(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+fn watch_directory_response(watcher: &WatcherHandle, changed: bool) -> WatchDirectoryResponse {
+    WatchDirectoryResponse {
+        changed,
+        watched_paths: watcher
+            .watched_paths()
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+    }
 }
 
 // ============================================================================
@@ -269,6 +683,7 @@ pub struct HookServiceImpl {
 #[tonic::async_trait]
 impl HookServiceTrait for HookServiceImpl {
     type ExecuteHooksStream = ReceiverStream<Result<HookOutput, Status>>;
+    type ListHooksStream = ReceiverStream<Result<DiscoveredHookInfo, Status>>;
 
     async fn execute_hooks(
         &self,
@@ -280,8 +695,10 @@ impl HookServiceTrait for HookServiceImpl {
         let engine = self.state.hook_engine.clone();
         let cwd = req.cwd.map(std::path::PathBuf::from);
         let env: Vec<(String, String)> = req.env.into_iter().collect();
+        let stream = self.state.stream_opened();
 
         tokio::spawn(async move {
+            let _stream = stream;
             let (output_tx, mut output_rx) = mpsc::channel(256);
 
             let event = req.event.clone();
@@ -360,29 +777,73 @@ impl HookServiceTrait for HookServiceImpl {
     async fn list_hooks(
         &self,
         request: Request<ListHooksRequest>,
-    ) -> Result<Response<ListHooksResponse>, Status> {
+    ) -> Result<Response<Self::ListHooksStream>, Status> {
+        let req = request.into_inner();
+        let hooks = self
+            .state
+            .hook_engine
+            .lock()
+            .await
+            .filter_hooks(req.event_filter.as_deref(), req.plugin_filter.as_deref());
+
+        let (tx, rx) = mpsc::channel(64);
+        let stream = self.state.stream_opened();
+        tokio::spawn(async move {
+            let _stream = stream;
+            for hook in hooks {
+                let info = DiscoveredHookInfo {
+                    plugin_name: hook.plugin_name,
+                    plugin_root: hook.plugin_root.to_string_lossy().to_string(),
+                    event: hook.event,
+                    hook_type: hook.hook_type,
+                    command: hook.command,
+                    prompt: hook.prompt,
+                    matcher: hook.matcher,
+                    timeout_ms: hook.timeout.map(|t| u32::try_from(t).unwrap_or(u32::MAX)),
+                };
+                if tx.send(Ok(info)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn refresh_hooks(
+        &self,
+        _request: Request<RefreshRequest>,
+    ) -> Result<Response<RefreshResponse>, Status> {
+        let mut engine = self.state.hook_engine.lock().await;
+        engine.refresh();
+        Ok(Response::new(RefreshResponse {
+            hook_count: engine.all_hooks().len() as u32,
+        }))
+    }
+
+    async fn dry_run_event(
+        &self,
+        request: Request<DryRunEventRequest>,
+    ) -> Result<Response<DryRunEventResponse>, Status> {
         let req = request.into_inner();
         let engine = self.state.hook_engine.lock().await;
 
-        let hooks: Vec<HookDefinition> = engine
-            .all_hooks()
-            .iter()
-            .filter(|h| {
-                req.event_filter
-                    .as_ref()
-                    .map_or(true, |filter| h.event == *filter)
-            })
-            .map(|h| HookDefinition {
-                plugin_name: h.plugin_name.clone(),
-                hook_name: h.event.clone(),
-                event: h.event.clone(),
-                command: h.command.clone().unwrap_or_default(),
-                matcher: h.matcher.clone(),
-                timeout_ms: h.timeout.map(|t| t as i32),
+        let hooks = engine
+            .execute_event_dry_run(&req.event, req.tool_name.as_deref())
+            .await
+            .into_iter()
+            .map(|h| HookDryRunResult {
+                hook_id: h.hook_id,
+                plugin_name: h.plugin_name,
+                hook_name: h.hook_name,
+                command: h.command,
+                is_cached: h.is_cached,
+                cache_key_hash: h.cache_key_hash,
+                timeout_ms: h.timeout_ms,
             })
             .collect();
 
-        Ok(Response::new(ListHooksResponse { hooks }))
+        Ok(Response::new(DryRunEventResponse { hooks }))
     }
 }
 
@@ -463,6 +924,104 @@ impl SlotServiceTrait for SlotServiceImpl {
 
         Ok(Response::new(ListSlotsResponse { slots: slot_infos }))
     }
+
+    async fn set(
+        &self,
+        request: Request<SetSlotRequest>,
+    ) -> Result<Response<SlotResponse>, Status> {
+        let req = request.into_inner();
+        let ttl = req
+            .ttl_secs
+            .filter(|&secs| secs > 0)
+            .map(|secs| Duration::from_secs(secs.into()));
+
+        self.state
+            .slot_values
+            .set(req.slot_name.clone(), req.value.clone(), ttl)
+            .await;
+
+        Ok(Response::new(SlotResponse {
+            slot_name: req.slot_name,
+            value: req.value,
+        }))
+    }
+
+    async fn get(
+        &self,
+        request: Request<GetSlotRequest>,
+    ) -> Result<Response<SlotResponse>, Status> {
+        let slot_name = request.into_inner().slot_name;
+
+        match self.state.slot_values.get(&slot_name).await {
+            Some(value) => Ok(Response::new(SlotResponse { slot_name, value })),
+            None => Err(Status::not_found(format!(
+                "Slot '{}' not found or expired",
+                slot_name
+            ))),
+        }
+    }
+
+    async fn delete(
+        &self,
+        request: Request<DeleteSlotRequest>,
+    ) -> Result<Response<SlotResponse>, Status> {
+        let slot_name = request.into_inner().slot_name;
+
+        match self.state.slot_values.delete(&slot_name).await {
+            Some(value) => Ok(Response::new(SlotResponse { slot_name, value })),
+            None => Err(Status::not_found(format!(
+                "Slot '{}' not found or expired",
+                slot_name
+            ))),
+        }
+    }
+
+    async fn list_values(
+        &self,
+        _request: Request<ListSlotValuesRequest>,
+    ) -> Result<Response<SlotListResponse>, Status> {
+        let slot_names = self.state.slot_values.names().await;
+        Ok(Response::new(SlotListResponse { slot_names }))
+    }
+
+    type WatchStream = ReceiverStream<Result<SlotEvent, Status>>;
+
+    async fn watch(
+        &self,
+        request: Request<WatchSlotRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let slot_name = request.into_inner().slot_name;
+        let mut events = self.state.slot_values.subscribe();
+        let (tx, rx) = mpsc::channel(16);
+        let stream = self.state.stream_opened();
+
+        tokio::spawn(async move {
+            let _stream = stream;
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    event = events.recv() => match event {
+                        Ok(event) if event.slot_name == slot_name => {
+                            if tx.send(Ok(event)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                "Slot watcher for '{}' skipped {} events",
+                                slot_name,
+                                skipped
+                            );
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
 }
 
 // ============================================================================
@@ -513,26 +1072,137 @@ impl MemoryServiceTrait for MemoryServiceImpl {
     }
 }
 
+// ============================================================================
+// Server assembly
+// ============================================================================
+
+/// Rejects requests whose `authorization` metadata isn't `Bearer <token>`.
+/// With no token configured every request passes.
+#[derive(Clone, Default)]
+pub struct BearerAuth {
+    token: Option<Arc<str>>,
+}
+
+impl BearerAuth {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.map(Arc::from),
+        }
+    }
+}
+
+impl tonic::service::Interceptor for BearerAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = &self.token else {
+            return Ok(request);
+        };
+        let presented = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match presented {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(request),
+            _ => Err(Status::unauthenticated("missing or invalid bearer token")),
+        }
+    }
+}
+
+/// Compare without returning early, so timing doesn't reveal the token.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// All six coordinator services behind `auth`, plus server reflection
+/// (v1 and v1alpha, for older clients) when `reflection` is set.
+pub fn router(
+    state: Arc<CoordinatorState>,
+    auth: BearerAuth,
+    reflection: bool,
+) -> Result<tonic::transport::server::Router, tonic_reflection::server::Error> {
+    use han_proto::coordinator::coordinator_service_server::CoordinatorServiceServer;
+    use han_proto::coordinator::hook_service_server::HookServiceServer;
+    use han_proto::coordinator::indexer_service_server::IndexerServiceServer;
+    use han_proto::coordinator::memory_service_server::MemoryServiceServer;
+    use han_proto::coordinator::session_service_server::SessionServiceServer;
+    use han_proto::coordinator::slot_service_server::SlotServiceServer;
+    use tonic::service::interceptor::InterceptedService;
+    use tonic_reflection::server::Builder as ReflectionBuilder;
+
+    let reflection_builder = || {
+        ReflectionBuilder::configure()
+            .register_encoded_file_descriptor_set(han_proto::FILE_DESCRIPTOR_SET)
+    };
+    let (reflection_v1, reflection_v1alpha) = if reflection {
+        (
+            Some(InterceptedService::new(
+                reflection_builder().build_v1()?,
+                auth.clone(),
+            )),
+            Some(InterceptedService::new(
+                reflection_builder().build_v1alpha()?,
+                auth.clone(),
+            )),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(tonic::transport::Server::builder()
+        .add_service(CoordinatorServiceServer::with_interceptor(
+            CoordinatorServiceImpl {
+                state: state.clone(),
+            },
+            auth.clone(),
+        ))
+        .add_service(SessionServiceServer::with_interceptor(
+            SessionServiceImpl {
+                state: state.clone(),
+            },
+            auth.clone(),
+        ))
+        .add_service(IndexerServiceServer::with_interceptor(
+            IndexerServiceImpl {
+                state: state.clone(),
+            },
+            auth.clone(),
+        ))
+        .add_service(HookServiceServer::with_interceptor(
+            HookServiceImpl {
+                state: state.clone(),
+            },
+            auth.clone(),
+        ))
+        .add_service(SlotServiceServer::with_interceptor(
+            SlotServiceImpl {
+                state: state.clone(),
+            },
+            auth.clone(),
+        ))
+        .add_service(MemoryServiceServer::with_interceptor(
+            MemoryServiceImpl { state },
+            auth,
+        ))
+        .add_optional_service(reflection_v1)
+        .add_optional_service(reflection_v1alpha))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn test_state() -> Arc<CoordinatorState> {
+    async fn test_state() -> Arc<CoordinatorState> {
         Arc::new(CoordinatorState {
-            db: {
-                // Use a dummy connection - tests that need real DB should set this up
-                // For unit tests of gRPC handlers that don't hit DB, this is sufficient
-                futures::executor::block_on(async {
-                    han_db::establish_connection(han_db::DbConfig::Sqlite {
-                        path: ":memory:".to_string(),
-                    })
-                    .await
-                    .unwrap()
-                })
-            },
+            db: crate::test_db().await,
             start_time: Instant::now(),
             hook_engine: Arc::new(Mutex::new(HookEngine::new(None))),
             slots: Arc::new(RwLock::new(HashMap::new())),
+            slot_values: SlotValueStore::default(),
+            events: broadcast::channel(16).0,
+            watcher: None,
+            websocket_connections: Arc::default(),
+            grpc_streams: Arc::default(),
+            session_cache: SessionCache::new(crate::state::DEFAULT_SESSION_CACHE_SIZE),
         })
     }
 
@@ -549,6 +1219,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         };
 
         let data = model_to_session_data(&model);
@@ -563,7 +1234,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_coordinator_health() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = CoordinatorServiceImpl {
             state: state.clone(),
         };
@@ -576,7 +1247,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_coordinator_status() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = CoordinatorServiceImpl {
             state: state.clone(),
         };
@@ -586,9 +1257,392 @@ mod tests {
         assert!(status.watcher_active);
     }
 
+    #[tokio::test]
+    async fn test_coordinator_get_status() {
+        let state = test_state().await;
+        let svc = CoordinatorServiceImpl {
+            state: state.clone(),
+        };
+        let get_status = || svc.get_status(Request::new(StatusRequest {}));
+
+        let first = get_status().await.unwrap().into_inner();
+        assert_eq!(first.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(first.indexed_sessions, 0);
+        assert_eq!(first.indexed_messages, 0);
+        assert_eq!(first.active_websocket_connections, 0);
+        assert_eq!(first.grpc_active_streams, 0);
+
+        // Uptime has whole-second resolution
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let slots = SlotServiceImpl {
+            state: state.clone(),
+        };
+        let watch = slots
+            .watch(Request::new(WatchSlotRequest {
+                slot_name: "status".to_string(),
+            }))
+            .await
+            .unwrap();
+
+        let second = get_status().await.unwrap().into_inner();
+        assert!(second.uptime_seconds > first.uptime_seconds);
+        assert_eq!(second.grpc_active_streams, 1);
+
+        drop(watch);
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while state.grpc_streams.load(Ordering::Relaxed) > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("stream count should drop once the client goes away");
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_get_version_over_grpc() {
+        use han_proto::coordinator::coordinator_service_client::CoordinatorServiceClient;
+        use han_proto::coordinator::coordinator_service_server::CoordinatorServiceServer;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let svc = CoordinatorServiceImpl {
+            state: test_state().await,
+        };
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(CoordinatorServiceServer::new(svc))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let mut client = CoordinatorServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let version = client
+            .get_version(VersionRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert!(!version.git_commit.is_empty());
+        assert!(!version.build_date.is_empty());
+        assert!(!version.rust_version.is_empty());
+        assert!(version.features.iter().any(|f| f == "grpc"));
+        #[cfg(feature = "sqlite")]
+        assert!(version.features.iter().any(|f| f == "fts"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_reflection_lists_services_with_auth() {
+        use han_proto::coordinator::coordinator_service_client::CoordinatorServiceClient;
+        use tokio_stream::StreamExt;
+        use tokio_stream::wrappers::TcpListenerStream;
+        use tonic_reflection::pb::v1::ServerReflectionRequest;
+        use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+        use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+        use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let auth = BearerAuth::new(Some("s3cret".to_string()));
+        let server = tokio::spawn(
+            router(test_state().await, auth, true)
+                .unwrap()
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let url = format!("http://{}", addr);
+
+        let list_services = |token: Option<&'static str>| {
+            let url = url.clone();
+            async move {
+                let channel = tonic::transport::Endpoint::from_shared(url).unwrap();
+                let mut client = ServerReflectionClient::new(channel.connect().await.unwrap());
+                let list = ServerReflectionRequest {
+                    host: String::new(),
+                    message_request: Some(MessageRequest::ListServices(String::new())),
+                };
+                let mut request = Request::new(tokio_stream::once(list));
+                if let Some(token) = token {
+                    let value = format!("Bearer {token}").parse().unwrap();
+                    request.metadata_mut().insert("authorization", value);
+                }
+                let mut responses = client.server_reflection_info(request).await?.into_inner();
+                let response = responses.next().await.unwrap()?;
+                let Some(MessageResponse::ListServicesResponse(list)) = response.message_response
+                else {
+                    panic!("unexpected reflection response");
+                };
+                let mut names: Vec<String> = list.service.into_iter().map(|s| s.name).collect();
+                names.sort();
+                Ok::<_, Status>(names)
+            }
+        };
+
+        let names = list_services(Some("s3cret")).await.unwrap();
+        let coordinator: Vec<&str> = names
+            .iter()
+            .map(String::as_str)
+            .filter(|n| n.starts_with("han.coordinator."))
+            .collect();
+        assert_eq!(
+            coordinator,
+            [
+                "han.coordinator.CoordinatorService",
+                "han.coordinator.HookService",
+                "han.coordinator.IndexerService",
+                "han.coordinator.MemoryService",
+                "han.coordinator.SessionService",
+                "han.coordinator.SlotService",
+            ]
+        );
+
+        let err = list_services(None).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        let err = list_services(Some("wrong")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        // Regular services are behind the same check
+        let mut client = CoordinatorServiceClient::connect(url.clone())
+            .await
+            .unwrap();
+        let err = client.get_version(VersionRequest {}).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        let mut request = Request::new(VersionRequest {});
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer s3cret".parse().unwrap());
+        assert!(client.get_version(request).await.is_ok());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_router_without_reflection_or_auth() {
+        use han_proto::coordinator::coordinator_service_client::CoordinatorServiceClient;
+        use tokio_stream::wrappers::TcpListenerStream;
+        use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(
+            router(test_state().await, BearerAuth::default(), false)
+                .unwrap()
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let url = format!("http://{}", addr);
+
+        let mut client = CoordinatorServiceClient::connect(url.clone())
+            .await
+            .unwrap();
+        assert!(client.get_version(VersionRequest {}).await.is_ok());
+
+        let channel = tonic::transport::Endpoint::from_shared(url).unwrap();
+        let mut reflection = ServerReflectionClient::new(channel.connect().await.unwrap());
+        let err = reflection
+            .server_reflection_info(tokio_stream::empty())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unimplemented);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_stream_messages_over_grpc() {
+        use han_db::entities::messages;
+        use han_proto::coordinator::session_service_client::SessionServiceClient;
+        use han_proto::coordinator::session_service_server::SessionServiceServer;
+        use sea_orm::Set;
+        use tokio_stream::StreamExt;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let state = test_state().await;
+        crud::sessions::upsert(&state.db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let message = |line: i32| messages::ActiveModel {
+            id: Set(format!("msg-{line:03}")),
+            session_id: Set("s1".to_string()),
+            message_type: Set("user".to_string()),
+            content: Set(Some(format!("message {line}"))),
+            timestamp: Set(format!("2026-04-20T10:00:{:02}Z", line % 60)),
+            line_number: Set(line),
+            ..Default::default()
+        };
+        // Inserted newest first so ordering comes from the query
+        let rows: Vec<_> = (0..100).rev().map(message).collect();
+        crud::messages::insert_batch(&state.db, rows).await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let svc = SessionServiceImpl {
+            state: state.clone(),
+        };
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(SessionServiceServer::new(svc))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let mut client = SessionServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+
+        // History only: the stream ends after the last stored message
+        let request = StreamMessagesRequest {
+            session_id: "s1".to_string(),
+            from_line_number: 0,
+            follow: false,
+        };
+        let stream = client.stream_messages(request).await.unwrap().into_inner();
+        let events: Vec<MessageEvent> = stream.map(|e| e.unwrap()).collect().await;
+        let lines: Vec<i32> = events.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, (0..100).collect::<Vec<_>>());
+        assert_eq!(events[7].message_id, "msg-007");
+        assert_eq!(events[7].content_preview, "message 7");
+
+        // Following picks up messages indexed after the history is sent
+        let request = StreamMessagesRequest {
+            session_id: "s1".to_string(),
+            from_line_number: 98,
+            follow: true,
+        };
+        let mut stream = client.stream_messages(request).await.unwrap().into_inner();
+        for line in [98, 99] {
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event.line_number, line);
+        }
+        crud::messages::insert_batch(&state.db, vec![message(100), message(101)])
+            .await
+            .unwrap();
+        for session_id in ["other", "s1"] {
+            let _ = state.events.send(DbChangeEvent::SessionMessageAdded {
+                session_id: session_id.to_string(),
+                message_index: 102,
+            });
+        }
+        for line in [100, 101] {
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event.line_number, line);
+        }
+
+        drop(stream);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_stream_messages_follow_after_han_event() {
+        use han_db::entities::messages;
+        use han_proto::coordinator::session_service_client::SessionServiceClient;
+        use han_proto::coordinator::session_service_server::SessionServiceServer;
+        use sea_orm::Set;
+        use tokio_stream::StreamExt;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let state = test_state().await;
+        crud::sessions::upsert(&state.db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let message = |file: &str, line: i32| messages::ActiveModel {
+            id: Set(format!("{file}:{line}")),
+            session_id: Set("s1".to_string()),
+            message_type: Set("user".to_string()),
+            timestamp: Set("2026-04-20T10:00:00Z".to_string()),
+            line_number: Set(line),
+            source_file_name: Set(Some(file.to_string())),
+            ..Default::default()
+        };
+        let rows = vec![
+            message("s1.jsonl", 0),
+            message("s1.jsonl", 1),
+            message("s1-han.jsonl", 1_000_000),
+        ];
+        crud::messages::insert_batch(&state.db, rows).await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let svc = SessionServiceImpl {
+            state: state.clone(),
+        };
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(SessionServiceServer::new(svc))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let mut client = SessionServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+
+        let request = StreamMessagesRequest {
+            session_id: "s1".to_string(),
+            from_line_number: 0,
+            follow: true,
+        };
+        let mut stream = client.stream_messages(request).await.unwrap().into_inner();
+        for line in [0, 1, 1_000_000] {
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event.line_number, line);
+        }
+
+        // Transcript lines written after a han event was sent still arrive
+        let rows = vec![message("s1.jsonl", 2), message("s1-han.jsonl", 1_000_001)];
+        crud::messages::insert_batch(&state.db, rows).await.unwrap();
+        let _ = state.events.send(DbChangeEvent::SessionMessageAdded {
+            session_id: "s1".to_string(),
+            message_index: 3,
+        });
+        for id in ["s1.jsonl:2", "s1-han.jsonl:1000001"] {
+            let event = stream.next().await.unwrap().unwrap();
+            assert_eq!(event.message_id, id);
+        }
+
+        drop(stream);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_get_queries_db_once() {
+        use std::sync::atomic::AtomicUsize;
+
+        let mut db = crate::test_db().await;
+        crud::sessions::upsert(&db, "s1".to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        db.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let state = Arc::into_inner(test_state().await).unwrap();
+        let svc = SessionServiceImpl {
+            state: Arc::new(CoordinatorState { db, ..state }),
+        };
+        let get = || {
+            Request::new(GetSessionRequest {
+                session_id: "s1".to_string(),
+            })
+        };
+
+        for _ in 0..100 {
+            let resp = svc.get(get()).await.unwrap().into_inner();
+            assert_eq!(resp.session.unwrap().session_id, "s1");
+        }
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+        let stats = svc.state.session_cache.stats();
+        assert_eq!((stats.hits, stats.misses), (99, 1));
+
+        // Once invalidated, the next call reads the DB again
+        svc.state.session_cache.invalidate("s1");
+        svc.get(get()).await.unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn test_slot_acquire_release() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = SlotServiceImpl {
             state: state.clone(),
         };
@@ -631,7 +1685,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_slot_release_wrong_owner() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = SlotServiceImpl {
             state: state.clone(),
         };
@@ -660,7 +1714,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_slot_release_not_found() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = SlotServiceImpl {
             state: state.clone(),
         };
@@ -677,7 +1731,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_slot_list() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = SlotServiceImpl {
             state: state.clone(),
         };
@@ -714,45 +1768,279 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_hook_list_hooks() {
-        let state = test_state();
-        let svc = HookServiceImpl {
-            state: state.clone(),
+    async fn test_slot_value_ttl_expiry() {
+        let svc = SlotServiceImpl {
+            state: test_state().await,
         };
+        // Pause only once the database is up, so pool timeouts don't fire
+        tokio::time::pause();
+        let set = |name: &str, ttl_secs: Option<u32>| SetSlotRequest {
+            slot_name: name.to_string(),
+            value: format!("{}-value", name),
+            ttl_secs,
+        };
+        svc.set(Request::new(set("short", Some(5)))).await.unwrap();
+        svc.set(Request::new(set("forever", None))).await.unwrap();
 
-        // Should return empty list when no plugins installed
-        let resp = svc
-            .list_hooks(Request::new(ListHooksRequest {
-                event_filter: None,
+        let get = |name: &str| {
+            Request::new(GetSlotRequest {
+                slot_name: name.to_string(),
+            })
+        };
+        let resp = svc.get(get("short")).await.unwrap().into_inner();
+        assert_eq!(resp.value, "short-value");
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+
+        let err = svc.get(get("short")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+        assert!(svc.get(get("forever")).await.is_ok());
+
+        let names = svc
+            .list_values(Request::new(ListSlotValuesRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .slot_names;
+        assert_eq!(names, vec!["forever".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_slot_value_concurrent_set_get() {
+        let svc = Arc::new(SlotServiceImpl {
+            state: test_state().await,
+        });
+
+        let handles: Vec<_> = (0..20)
+            .map(|i| {
+                let svc = svc.clone();
+                tokio::spawn(async move {
+                    let slot_name = format!("slot-{}", i % 4);
+                    svc.set(Request::new(SetSlotRequest {
+                        slot_name: slot_name.clone(),
+                        value: i.to_string(),
+                        ttl_secs: None,
+                    }))
+                    .await
+                    .unwrap();
+                    svc.get(Request::new(GetSlotRequest { slot_name }))
+                        .await
+                        .unwrap()
+                        .into_inner()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let resp = handle.await.unwrap();
+            // Another writer may have won, but only with a value for this slot
+            let writer: usize = resp.value.parse().unwrap();
+            assert_eq!(format!("slot-{}", writer % 4), resp.slot_name);
+        }
+
+        let names = svc
+            .list_values(Request::new(ListSlotValuesRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .slot_names;
+        assert_eq!(names, vec!["slot-0", "slot-1", "slot-2", "slot-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_slot_watch_notifies_changes() {
+        use tokio_stream::StreamExt;
+
+        let svc = SlotServiceImpl {
+            state: test_state().await,
+        };
+        let mut stream = svc
+            .watch(Request::new(WatchSlotRequest {
+                slot_name: "build".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        for name in ["other", "build"] {
+            svc.set(Request::new(SetSlotRequest {
+                slot_name: name.to_string(),
+                value: "running".to_string(),
+                ttl_secs: None,
             }))
             .await
             .unwrap();
-        // May have hooks from installed plugins, but should not error
-        let _ = resp.into_inner().hooks;
+        }
+        svc.delete(Request::new(DeleteSlotRequest {
+            slot_name: "build".to_string(),
+        }))
+        .await
+        .unwrap();
+
+        let changed = stream.next().await.unwrap().unwrap();
+        assert_eq!(changed.slot_name, "build");
+        assert_eq!(changed.value.as_deref(), Some("running"));
+        let deleted = stream.next().await.unwrap().unwrap();
+        assert_eq!(deleted.slot_name, "build");
+        assert_eq!(deleted.value, None);
+    }
+
+    async fn hook_state(hooks: &[(&str, &str)]) -> Arc<CoordinatorState> {
+        let hooks = hooks
+            .iter()
+            .map(|(plugin, event)| crate::hooks::discovery::DiscoveredHook {
+                plugin_name: plugin.to_string(),
+                plugin_root: std::path::PathBuf::from(format!("/plugins/{plugin}")),
+                event: event.to_string(),
+                matcher: None,
+                hook_type: "command".to_string(),
+                command: Some(format!("{plugin}-{event}")),
+                prompt: None,
+                timeout: Some(5000),
+            })
+            .collect();
+        let state = test_state().await;
+        *state.hook_engine.lock().await = HookEngine::with_hooks(hooks);
+        state
+    }
+
+    async fn list_hooks(
+        svc: &HookServiceImpl,
+        event: Option<&str>,
+        plugin: Option<&str>,
+    ) -> Vec<DiscoveredHookInfo> {
+        use tokio_stream::StreamExt;
+
+        let stream = svc
+            .list_hooks(Request::new(ListHooksRequest {
+                event_filter: event.map(str::to_string),
+                plugin_filter: plugin.map(str::to_string),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        stream.map(|h| h.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_hook_list_hooks() {
+        let svc = HookServiceImpl {
+            state: hook_state(&[("biome", "Stop"), ("biome", "PreToolUse"), ("jest", "Stop")])
+                .await,
+        };
+
+        let hooks = list_hooks(&svc, None, None).await;
+        let names: Vec<_> = hooks
+            .iter()
+            .map(|h| (h.plugin_name.as_str(), h.event.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [("biome", "Stop"), ("biome", "PreToolUse"), ("jest", "Stop")]
+        );
+        assert_eq!(hooks[0].plugin_root, "/plugins/biome");
+        assert_eq!(hooks[0].hook_type, "command");
+        assert_eq!(hooks[0].command.as_deref(), Some("biome-Stop"));
+        assert_eq!(hooks[0].timeout_ms, Some(5000));
     }
 
     #[tokio::test]
     async fn test_hook_list_hooks_with_filter() {
-        let state = test_state();
+        let svc = HookServiceImpl {
+            state: hook_state(&[("biome", "Stop"), ("biome", "PreToolUse"), ("jest", "Stop")])
+                .await,
+        };
+
+        let stop = list_hooks(&svc, Some("Stop"), None).await;
+        assert_eq!(stop.len(), 2);
+        assert!(stop.iter().all(|h| h.event == "Stop"));
+
+        let biome = list_hooks(&svc, None, Some("biome")).await;
+        assert_eq!(biome.len(), 2);
+        assert!(biome.iter().all(|h| h.plugin_name == "biome"));
+
+        let both = list_hooks(&svc, Some("Stop"), Some("jest")).await;
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].command.as_deref(), Some("jest-Stop"));
+
+        assert!(list_hooks(&svc, Some("Nope"), None).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hook_refresh_hooks() {
+        let svc = HookServiceImpl {
+            state: hook_state(&[("biome", "Stop")]).await,
+        };
+
+        // Refresh re-runs discovery, replacing the injected hooks
+        let resp = svc
+            .refresh_hooks(Request::new(RefreshRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let hooks = list_hooks(&svc, None, None).await;
+        assert_eq!(resp.hook_count as usize, hooks.len());
+    }
+
+    #[tokio::test]
+    async fn test_indexer_watch_directory() {
+        let initial = tempfile::TempDir::new().unwrap();
+        let extra = tempfile::TempDir::new().unwrap();
+        let watcher = han_indexer::WatcherService::new(Some(initial.path().to_path_buf())).unwrap();
+        let state = Arc::into_inner(test_state().await).unwrap();
+        let svc = IndexerServiceImpl {
+            state: Arc::new(CoordinatorState {
+                watcher: Some(watcher.handle()),
+                ..state
+            }),
+        };
+        let path = extra.path().to_string_lossy().to_string();
+        let request = || Request::new(WatchDirectoryRequest { path: path.clone() });
+
+        let resp = svc.watch_directory(request()).await.unwrap().into_inner();
+        assert!(resp.changed);
+        assert!(resp.watched_paths.contains(&path));
+
+        let resp = svc.watch_directory(request()).await.unwrap().into_inner();
+        assert!(!resp.changed);
+
+        let resp = svc.unwatch_directory(request()).await.unwrap().into_inner();
+        assert!(resp.changed);
+        assert!(!resp.watched_paths.contains(&path));
+    }
+
+    #[tokio::test]
+    async fn test_indexer_watch_directory_without_watcher() {
+        let svc = IndexerServiceImpl {
+            state: test_state().await,
+        };
+        let request = Request::new(WatchDirectoryRequest {
+            path: "/tmp".to_string(),
+        });
+        let status = svc.watch_directory(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_hook_dry_run_event_no_matching_hooks() {
+        let state = test_state().await;
         let svc = HookServiceImpl {
             state: state.clone(),
         };
 
         let resp = svc
-            .list_hooks(Request::new(ListHooksRequest {
-                event_filter: Some("Stop".to_string()),
+            .dry_run_event(Request::new(DryRunEventRequest {
+                event: "NonExistentEvent".to_string(),
+                tool_name: None,
             }))
             .await
             .unwrap();
-        // All returned hooks should match the filter
-        for hook in resp.into_inner().hooks {
-            assert_eq!(hook.event, "Stop");
-        }
+        assert!(resp.into_inner().hooks.is_empty());
     }
 
     #[tokio::test]
     async fn test_memory_search() {
-        let state = test_state();
+        let state = test_state().await;
         let svc = MemoryServiceImpl {
             state: state.clone(),
         };
@@ -783,6 +2071,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         };
 
         let data = model_to_session_data(&model);
@@ -807,6 +2096,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         };
 
         let data = model_to_session_data(&model);
@@ -834,6 +2124,7 @@ mod tests {
             pr_number: None,
             pr_url: None,
             team_name: None,
+            worktree_id: None,
         };
 
         let data = model_to_session_data(&model);
@@ -15,6 +15,16 @@ pub struct CacheKey {
     pub command_hash: String,
 }
 
+impl CacheKey {
+    /// SHA256 digest identifying this key, for reporting.
+    pub fn digest(&self) -> String {
+        hash_string(&format!(
+            "{}\0{}\0{}",
+            self.plugin_name, self.hook_name, self.command_hash
+        ))
+    }
+}
+
 /// Cache entry tracking file hashes for a hook run.
 #[derive(Debug, Clone)]
 pub struct CacheEntry {
@@ -173,6 +183,36 @@ mod tests {
         assert!(!cache.is_valid(&key, &files));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_dangling_symlink_is_always_changed() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("target.ts");
+        let link = dir.path().join("link.ts");
+        std::fs::write(&target, "const x = 1;").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let mut cache = HookCache::new();
+        let key = CacheKey {
+            plugin_name: "biome".into(),
+            hook_name: "lint".into(),
+            command_hash: "abc".into(),
+        };
+
+        let files = vec![link.to_string_lossy().to_string()];
+        cache.update(key.clone(), &files);
+        assert!(cache.is_valid(&key, &files));
+
+        // Target vanishes after the cache was filled
+        std::fs::remove_file(&target).unwrap();
+        assert_eq!(compute_file_hash(&files[0]), None);
+        assert!(!cache.is_valid(&key, &files));
+
+        // Recording a dangling link stores no hash, so it never validates
+        cache.update(key.clone(), &files);
+        assert!(!cache.is_valid(&key, &files));
+    }
+
     #[test]
     fn test_invalidate() {
         let mut cache = HookCache::new();
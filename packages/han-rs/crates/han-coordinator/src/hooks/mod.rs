@@ -33,6 +33,32 @@ pub struct HookExecutionResult {
     pub error: Option<String>,
 }
 
+/// A hook that would run for an event, reported without executing it.
+#[derive(Debug, Clone)]
+pub struct DryRunResult {
+    pub hook_id: String,
+    pub plugin_name: String,
+    pub hook_name: String,
+    /// `None` for prompt-only hooks, which Claude evaluates itself.
+    pub command: Option<String>,
+    /// A cache entry from an earlier successful run is still valid.
+    pub is_cached: bool,
+    pub cache_key_hash: String,
+    pub timeout_ms: Option<u32>,
+}
+
+/// Hook id and cache key for running `command` on `event`.
+fn hook_identity(hook: &DiscoveredHook, event: &str, command: &str) -> (String, CacheKey) {
+    let command_hash = hash_string(command);
+    let hook_id = format!("{}:{}:{}", hook.plugin_name, event, &command_hash[..8]);
+    let cache_key = CacheKey {
+        plugin_name: hook.plugin_name.clone(),
+        hook_name: event.to_string(),
+        command_hash,
+    };
+    (hook_id, cache_key)
+}
+
 impl HookEngine {
     /// Create a new hook engine, discovering all available hooks.
     pub fn new(project_path: Option<PathBuf>) -> Self {
@@ -61,6 +87,61 @@ impl HookEngine {
         &self.hooks
     }
 
+    /// Discovered hooks for `event` from `plugin`, each filter applying only
+    /// when set.
+    pub fn filter_hooks(&self, event: Option<&str>, plugin: Option<&str>) -> Vec<DiscoveredHook> {
+        self.hooks
+            .iter()
+            .filter(|h| event.is_none_or(|e| h.event == e))
+            .filter(|h| plugin.is_none_or(|p| h.plugin_name == p))
+            .cloned()
+            .collect()
+    }
+
+    /// Engine serving a fixed set of hooks, skipping discovery.
+    #[cfg(test)]
+    pub(crate) fn with_hooks(hooks: Vec<DiscoveredHook>) -> Self {
+        Self {
+            hooks,
+            cache: Arc::new(Mutex::new(HookCache::new())),
+            project_path: None,
+        }
+    }
+
+    /// Number of entries in the file validation cache.
+    pub async fn cache_len(&self) -> usize {
+        self.cache.lock().await.len()
+    }
+
+    /// List the hooks matching an event and their cache state, without
+    /// spawning any process.
+    pub async fn execute_event_dry_run(
+        &self,
+        event: &str,
+        tool_name: Option<&str>,
+    ) -> Vec<DryRunResult> {
+        let matching = find_matching_hooks(&self.hooks, event, tool_name);
+        let cache = self.cache.lock().await;
+
+        matching
+            .into_iter()
+            .map(|hook| {
+                let source = hook.command.as_deref().or(hook.prompt.as_deref());
+                let (hook_id, cache_key) = hook_identity(&hook, event, source.unwrap_or_default());
+                let is_cached = hook.command.is_some() && cache.is_valid(&cache_key, &[]);
+                DryRunResult {
+                    hook_id,
+                    plugin_name: hook.plugin_name,
+                    hook_name: event.to_string(),
+                    command: hook.command,
+                    is_cached,
+                    cache_key_hash: cache_key.digest(),
+                    timeout_ms: hook.timeout.map(|t| u32::try_from(t).unwrap_or(u32::MAX)),
+                }
+            })
+            .collect()
+    }
+
     /// Execute all hooks matching an event, streaming output through the channel.
     ///
     /// Each hook sends `HookOutputLine` messages tagged with a hook_id.
@@ -95,18 +176,7 @@ impl HookEngine {
                 None => continue, // Skip prompt-only hooks
             };
 
-            let hook_id = format!(
-                "{}:{}:{}",
-                hook.plugin_name,
-                event,
-                hash_string(&command)[..8].to_string()
-            );
-
-            let cache_key = CacheKey {
-                plugin_name: hook.plugin_name.clone(),
-                hook_name: event.to_string(),
-                command_hash: hash_string(&command),
-            };
+            let (hook_id, cache_key) = hook_identity(&hook, event, &command);
 
             // Check cache: skip if all affected files are unchanged
             let affected_files: Vec<String> = Vec::new(); // Files populated by caller or from cwd
@@ -179,6 +249,7 @@ impl HookEngine {
                 }
             });
 
+            let started = std::time::Instant::now();
             let exec_result = execute_hook(
                 &command,
                 working_dir,
@@ -187,12 +258,13 @@ impl HookEngine {
                 line_tx,
             )
             .await;
+            let duration_ms = started.elapsed().as_millis() as u64;
 
             let _ = forward_handle.await;
 
-            let (exit_code, error, duration_ms) = match exec_result {
-                Ok(code) => (code, None, 0u64),
-                Err(e) => (-1, Some(e.to_string()), 0),
+            let (exit_code, error) = match exec_result {
+                Ok(code) => (code, None),
+                Err(e) => (-1, Some(e.to_string())),
             };
 
             // Update cache on success
@@ -212,6 +284,9 @@ impl HookEngine {
             });
         }
 
+        for result in &results {
+            crate::telemetry::record_hook_execution(result);
+        }
         results
     }
 }
@@ -524,4 +599,54 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].plugin_name, "manual-2");
     }
+
+    #[tokio::test]
+    async fn test_execute_event_dry_run_reports_cache_state() {
+        let mut engine = HookEngine::new(None);
+        let hook = |plugin_name: &str, command: &str| DiscoveredHook {
+            plugin_name: plugin_name.to_string(),
+            plugin_root: PathBuf::from("/tmp"),
+            event: "PostToolUse".to_string(),
+            hook_type: "command".to_string(),
+            command: Some(command.to_string()),
+            prompt: None,
+            matcher: Some("Edit|Write".to_string()),
+            timeout: Some(5000),
+        };
+        engine.hooks = vec![hook("ran", "echo ran")];
+
+        // A successful run leaves a cache entry
+        let (tx, _rx) = mpsc::channel(256);
+        let results = engine
+            .execute_event(
+                "PostToolUse",
+                Some("Edit"),
+                Some(Path::new("/tmp")),
+                &[],
+                tx,
+            )
+            .await;
+        assert_eq!(results[0].exit_code, 0);
+
+        engine.hooks.push(hook("never-ran", "echo never"));
+
+        let dry_run = engine
+            .execute_event_dry_run("PostToolUse", Some("Edit"))
+            .await;
+        assert_eq!(dry_run.len(), 2);
+        assert_eq!(dry_run[0].plugin_name, "ran");
+        assert!(dry_run[0].is_cached);
+        assert_eq!(dry_run[0].hook_id, results[0].hook_id);
+        assert_eq!(dry_run[1].plugin_name, "never-ran");
+        assert!(!dry_run[1].is_cached);
+        assert_eq!(dry_run[1].command.as_deref(), Some("echo never"));
+        assert_eq!(dry_run[1].timeout_ms, Some(5000));
+        assert_ne!(dry_run[0].cache_key_hash, dry_run[1].cache_key_hash);
+
+        // Matcher still applies
+        assert!(engine
+            .execute_event_dry_run("PostToolUse", Some("Read"))
+            .await
+            .is_empty());
+    }
 }
@@ -1,15 +1,24 @@
 //! File-based coordinator lock to prevent multiple instances.
 //!
-//! Lock file at `~/.han/coordinator.lock` contains JSON with pid, timestamps.
-//! A lock is considered stale after 30 seconds without a heartbeat or if the
-//! owning process no longer exists.
+//! Lock file at `~/.han/coordinator.lock` contains JSON with the pid, the
+//! HTTPS and gRPC ports and timestamps, so the `han` CLI can discover a
+//! coordinator started on non-default ports. A file that isn't valid JSON is
+//! a PID-only lock from an older coordinator and is always treated as stale.
+//! A lock is considered stale once its heartbeat is older than twice the
+//! heartbeat interval (20 seconds by default) or if the owning process no
+//! longer exists. Stale locks are force-unlocked on acquire so a crashed
+//! coordinator never blocks the next one.
 
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-const STALE_TIMEOUT_SECS: i64 = 30;
+/// How often a running coordinator refreshes its lock's heartbeat.
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 10;
+
+/// Heartbeat age after which a lock is considered stale.
+const DEFAULT_HEARTBEAT_TIMEOUT_SECS: i64 = 2 * HEARTBEAT_INTERVAL_SECS as i64;
 
 #[derive(Error, Debug)]
 pub enum LockError {
@@ -24,16 +33,22 @@ pub enum LockError {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LockData {
+pub struct LockInfo {
     pub pid: u32,
-    pub acquired_at: String,
+    /// Written as `acquired_at` by older coordinators.
+    #[serde(alias = "acquired_at")]
+    pub started_at: String,
     pub heartbeat_at: String,
     pub port: Option<u16>,
+    /// Absent in locks written before the gRPC port was recorded.
+    #[serde(default)]
+    pub grpc_port: Option<u16>,
 }
 
 /// Coordinator lock manager.
 pub struct CoordinatorLock {
     lock_path: PathBuf,
+    heartbeat_timeout_secs: i64,
 }
 
 impl CoordinatorLock {
@@ -41,41 +56,96 @@ impl CoordinatorLock {
     pub fn new() -> Result<Self, LockError> {
         let home = dirs::home_dir().ok_or(LockError::NoHomeDir)?;
         let lock_path = home.join(".han").join("coordinator.lock");
-        Ok(Self { lock_path })
+        Ok(Self::with_path(lock_path))
     }
 
     /// Create a new lock manager with a custom path.
     pub fn with_path(path: PathBuf) -> Self {
-        Self { lock_path: path }
+        Self {
+            lock_path: path,
+            heartbeat_timeout_secs: DEFAULT_HEARTBEAT_TIMEOUT_SECS,
+        }
+    }
+
+    /// Override how old a heartbeat may get before the lock is stale.
+    #[cfg(test)]
+    pub fn with_heartbeat_timeout(mut self, secs: i64) -> Self {
+        self.heartbeat_timeout_secs = secs;
+        self
     }
 
     /// Try to acquire the lock. Returns Ok(()) if acquired, Err if held by another process.
-    pub fn acquire(&self, port: Option<u16>) -> Result<(), LockError> {
+    pub fn acquire(&self, port: Option<u16>, grpc_port: Option<u16>) -> Result<(), LockError> {
         if let Some(parent) = self.lock_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         // Check for existing lock
         if self.lock_path.exists() {
-            if let Ok(existing) = self.read_lock() {
-                if !self.is_stale(&existing) {
+            match self.read_lock() {
+                Ok(existing) if !self.data_is_stale(&existing) => {
                     return Err(LockError::AlreadyLocked { pid: existing.pid });
                 }
-                tracing::info!(
-                    "Stale lock found (pid={}), removing",
-                    existing.pid
-                );
+                Ok(existing) => {
+                    tracing::warn!("Stale lock found (pid={}), force-unlocking", existing.pid);
+                }
+                Err(e) => tracing::warn!("Unreadable lock file ({}), force-unlocking", e),
+            }
+            self.force_unlock()?;
+        }
+
+        self.write_new_lock(port, grpc_port)
+    }
+
+    /// Acquire the lock even if another live process holds it.
+    ///
+    /// Skips the staleness check entirely; used by `--force`.
+    pub fn acquire_forced(
+        &self,
+        port: Option<u16>,
+        grpc_port: Option<u16>,
+    ) -> Result<(), LockError> {
+        if let Some(parent) = self.lock_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Ok(existing) = self.read_lock() {
+            tracing::warn!("Forcibly taking lock held by pid={}", existing.pid);
+        }
+        self.remove_lock_file()?;
+        self.write_new_lock(port, grpc_port)
+    }
+
+    /// Remove a stale lock left behind by a crashed coordinator.
+    ///
+    /// Fails with [`LockError::AlreadyLocked`] if the lock is still live.
+    /// A missing lock file is not an error.
+    pub fn force_unlock(&self) -> Result<(), LockError> {
+        match self.read_lock() {
+            Ok(data) if !self.data_is_stale(&data) => {
+                Err(LockError::AlreadyLocked { pid: data.pid })
             }
-            // Remove stale or corrupted lock
-            let _ = fs::remove_file(&self.lock_path);
+            _ => self.remove_lock_file(),
         }
+    }
 
+    /// Check if the lock file belongs to a dead process or has stopped
+    /// heartbeating. A missing lock is not stale; an unreadable one is.
+    pub fn is_stale(&self) -> bool {
+        match self.read_lock() {
+            Ok(data) => self.data_is_stale(&data),
+            Err(LockError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => false,
+            Err(_) => true,
+        }
+    }
+
+    fn write_new_lock(&self, port: Option<u16>, grpc_port: Option<u16>) -> Result<(), LockError> {
         let now = chrono::Utc::now().to_rfc3339();
-        let data = LockData {
+        let data = LockInfo {
             pid: std::process::id(),
-            acquired_at: now.clone(),
+            started_at: now.clone(),
             heartbeat_at: now,
             port,
+            grpc_port,
         };
 
         let json = serde_json::to_string_pretty(&data)?;
@@ -95,6 +165,13 @@ impl CoordinatorLock {
         Ok(())
     }
 
+    fn remove_lock_file(&self) -> Result<(), LockError> {
+        match fs::remove_file(&self.lock_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
     /// Release the lock (remove the file).
     pub fn release(&self) -> Result<(), LockError> {
         if self.lock_path.exists() {
@@ -107,26 +184,41 @@ impl CoordinatorLock {
     /// Check if a lock exists and is held by a running process.
     pub fn is_locked(&self) -> bool {
         if let Ok(data) = self.read_lock() {
-            !self.is_stale(&data)
+            !self.data_is_stale(&data)
         } else {
             false
         }
     }
 
     /// Get the current lock data if it exists.
-    pub fn read_lock(&self) -> Result<LockData, LockError> {
+    pub fn read_lock(&self) -> Result<LockInfo, LockError> {
         let contents = fs::read_to_string(&self.lock_path)?;
-        let data: LockData = serde_json::from_str(&contents)?;
+        let data: LockInfo = serde_json::from_str(&contents)?;
         Ok(data)
     }
 
+    /// The lock holder's details, if the lock file exists and is readable.
+    pub fn lock_info(&self) -> Option<LockInfo> {
+        self.read_lock().ok()
+    }
+
+    /// HTTPS port of the coordinator holding the lock.
+    pub fn port(&self) -> Option<u16> {
+        self.lock_info()?.port
+    }
+
+    /// gRPC port of the coordinator holding the lock.
+    pub fn grpc_port(&self) -> Option<u16> {
+        self.lock_info()?.grpc_port
+    }
+
     /// Get the lock file path.
     pub fn lock_path(&self) -> &Path {
         &self.lock_path
     }
 
     /// Check if a lock is stale (process dead or heartbeat too old).
-    fn is_stale(&self, data: &LockData) -> bool {
+    fn data_is_stale(&self, data: &LockInfo) -> bool {
         // Check if process still exists
         if !process_exists(data.pid) {
             return true;
@@ -136,7 +228,7 @@ impl CoordinatorLock {
         if let Ok(heartbeat) = chrono::DateTime::parse_from_rfc3339(&data.heartbeat_at) {
             let age = chrono::Utc::now()
                 .signed_duration_since(heartbeat.with_timezone(&chrono::Utc));
-            if age.num_seconds() > STALE_TIMEOUT_SECS {
+            if age.num_seconds() > self.heartbeat_timeout_secs {
                 return true;
             }
         } else {
@@ -188,12 +280,13 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let lock = test_lock(&dir);
 
-        lock.acquire(Some(41956)).unwrap();
+        lock.acquire(Some(41956), Some(41958)).unwrap();
         assert!(lock.is_locked());
 
         let data = lock.read_lock().unwrap();
         assert_eq!(data.pid, std::process::id());
         assert_eq!(data.port, Some(41956));
+        assert_eq!(data.grpc_port, Some(41958));
 
         lock.release().unwrap();
         assert!(!lock.is_locked());
@@ -204,10 +297,10 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let lock = test_lock(&dir);
 
-        lock.acquire(None).unwrap();
+        lock.acquire(None, None).unwrap();
 
         // Second acquire should fail since our process owns it
-        let result = lock.acquire(None);
+        let result = lock.acquire(None, None);
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), LockError::AlreadyLocked { .. }));
 
@@ -220,17 +313,18 @@ mod tests {
         let lock = test_lock(&dir);
 
         // Write a lock with a non-existent PID
-        let stale_data = LockData {
+        let stale_data = LockInfo {
             pid: 99999999,
-            acquired_at: chrono::Utc::now().to_rfc3339(),
+            started_at: chrono::Utc::now().to_rfc3339(),
             heartbeat_at: chrono::Utc::now().to_rfc3339(),
             port: None,
+            grpc_port: None,
         };
         let json = serde_json::to_string_pretty(&stale_data).unwrap();
         fs::write(lock.lock_path(), json).unwrap();
 
         // Should be able to acquire since the PID doesn't exist
-        lock.acquire(None).unwrap();
+        lock.acquire(None, None).unwrap();
         assert!(lock.is_locked());
         lock.release().unwrap();
     }
@@ -240,7 +334,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let lock = test_lock(&dir);
 
-        lock.acquire(None).unwrap();
+        lock.acquire(None, None).unwrap();
 
         let before = lock.read_lock().unwrap().heartbeat_at;
         std::thread::sleep(std::time::Duration::from_millis(10));
@@ -260,11 +354,136 @@ mod tests {
         fs::write(lock.lock_path(), "not json").unwrap();
 
         // Should acquire despite corruption
-        lock.acquire(None).unwrap();
+        lock.acquire(None, None).unwrap();
         assert!(lock.is_locked());
         lock.release().unwrap();
     }
 
+    fn write_lock(lock: &CoordinatorLock, pid: u32, heartbeat_age_secs: i64) {
+        let at = chrono::Utc::now() - chrono::Duration::seconds(heartbeat_age_secs);
+        let data = LockInfo {
+            pid,
+            started_at: at.to_rfc3339(),
+            heartbeat_at: at.to_rfc3339(),
+            port: None,
+            grpc_port: None,
+        };
+        fs::write(lock.lock_path(), serde_json::to_string(&data).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_is_stale_crashed_pid() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+
+        assert!(!lock.is_stale());
+
+        write_lock(&lock, 99999999, 0);
+        assert!(lock.is_stale());
+
+        write_lock(&lock, std::process::id(), 0);
+        assert!(!lock.is_stale());
+        fs::remove_file(lock.lock_path()).unwrap();
+    }
+
+    #[test]
+    fn test_is_stale_missed_heartbeats() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir).with_heartbeat_timeout(5);
+
+        // Live process, but no heartbeat for longer than the timeout
+        write_lock(&lock, std::process::id(), 10);
+        assert!(lock.is_stale());
+
+        write_lock(&lock, std::process::id(), 2);
+        assert!(!lock.is_stale());
+        fs::remove_file(lock.lock_path()).unwrap();
+    }
+
+    #[test]
+    fn test_force_unlock() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+
+        // Missing lock is fine
+        lock.force_unlock().unwrap();
+
+        write_lock(&lock, std::process::id(), 0);
+        let result = lock.force_unlock();
+        assert!(matches!(result, Err(LockError::AlreadyLocked { .. })));
+        assert!(lock.lock_path().exists());
+
+        write_lock(&lock, 99999999, 0);
+        lock.force_unlock().unwrap();
+        assert!(!lock.lock_path().exists());
+    }
+
+    #[test]
+    fn test_acquire_forced_takes_live_lock() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+
+        lock.acquire(Some(1), None).unwrap();
+        assert!(lock.acquire(Some(2), None).is_err());
+
+        lock.acquire_forced(Some(2), None).unwrap();
+        assert_eq!(lock.read_lock().unwrap().port, Some(2));
+        lock.release().unwrap();
+    }
+
+    #[test]
+    fn test_ports_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+        assert!(lock.lock_info().is_none());
+        assert_eq!(lock.port(), None);
+
+        lock.acquire(Some(8443), Some(9443)).unwrap();
+        assert_eq!(lock.port(), Some(8443));
+        assert_eq!(lock.grpc_port(), Some(9443));
+        let info = lock.lock_info().unwrap();
+        assert_eq!(info.pid, std::process::id());
+
+        // Heartbeats keep the ports
+        lock.heartbeat().unwrap();
+        assert_eq!(lock.grpc_port(), Some(9443));
+        lock.release().unwrap();
+    }
+
+    #[test]
+    fn test_lock_without_grpc_port_still_parses() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+        let json = format!(
+            r#"{{"pid":{},"acquired_at":"{now}","heartbeat_at":"{now}","port":41957}}"#,
+            std::process::id(),
+            now = chrono::Utc::now().to_rfc3339(),
+        );
+        fs::write(lock.lock_path(), json).unwrap();
+
+        assert_eq!(lock.port(), Some(41957));
+        assert_eq!(lock.grpc_port(), None);
+        assert!(!lock.lock_info().unwrap().started_at.is_empty());
+        assert!(!lock.is_stale());
+        fs::remove_file(lock.lock_path()).unwrap();
+    }
+
+    #[test]
+    fn test_pid_only_lock_is_stale() {
+        let dir = TempDir::new().unwrap();
+        let lock = test_lock(&dir);
+
+        // Older coordinators wrote just the PID, here a live one
+        fs::write(lock.lock_path(), std::process::id().to_string()).unwrap();
+        assert!(lock.lock_info().is_none());
+        assert_eq!(lock.port(), None);
+        assert!(lock.is_stale());
+
+        lock.acquire(Some(41957), Some(41958)).unwrap();
+        assert_eq!(lock.port(), Some(41957));
+        lock.release().unwrap();
+    }
+
     #[test]
     fn test_process_exists_current() {
         assert!(process_exists(std::process::id()));
@@ -0,0 +1,222 @@
+//! Size-rotated log file for daemon mode.
+//!
+//! The daemonized coordinator has no terminal, so tracing output and stderr
+//! go to `~/.han/coordinator.log`. Once the file would grow past its size
+//! limit it is renamed to `coordinator.log.1`, replacing any older rotation,
+//! and a fresh file is started. Stderr is pointed at each fresh file so
+//! panics land next to the tracing output.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Default rotation threshold in megabytes.
+pub const DEFAULT_MAX_SIZE_MB: u64 = 50;
+
+/// Default log path: `~/.han/coordinator.log`.
+pub fn default_log_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|home| home.join(".han"))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("coordinator.log")
+}
+
+/// Path a log file is renamed to when rotated.
+pub fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+/// Open a log file for appending, creating it and its directory if needed.
+pub fn open_append(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Log file writer that rotates once the file would exceed `max_bytes`.
+pub struct RotatingLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    /// Descriptor re-pointed at the current file after each rotation.
+    #[cfg(unix)]
+    follow_fd: Option<std::os::fd::RawFd>,
+}
+
+impl RotatingLogFile {
+    /// Open `path` for appending. Existing content counts toward the limit.
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64) -> io::Result<Self> {
+        let path = path.into();
+        let file = open_append(&path)?;
+        Ok(Self {
+            path,
+            max_bytes,
+            file,
+            #[cfg(unix)]
+            follow_fd: None,
+        })
+    }
+
+    /// Send stderr to the log file, following it across rotations.
+    #[cfg(unix)]
+    pub fn redirect_stderr(self) -> io::Result<Self> {
+        self.redirect_fd(nix::libc::STDERR_FILENO)
+    }
+
+    #[cfg(unix)]
+    fn redirect_fd(mut self, fd: std::os::fd::RawFd) -> io::Result<Self> {
+        self.follow_fd = Some(fd);
+        self.point_follow_fd()?;
+        Ok(self)
+    }
+
+    #[cfg(unix)]
+    fn point_follow_fd(&self) -> io::Result<()> {
+        use std::os::fd::AsRawFd;
+
+        if let Some(fd) = self.follow_fd {
+            nix::unistd::dup2(self.file.as_raw_fd(), fd)?;
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, rotated_path(&self.path))?;
+        self.file = open_append(&self.path)?;
+        #[cfg(unix)]
+        self.point_follow_fd()?;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Measured rather than counted, as stderr also appends to the file.
+        // A single oversized write still lands in a fresh file
+        let len = self.file.metadata()?.len();
+        if len > 0 && len + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Last `lines` lines of the file at `path`, oldest first.
+///
+/// Reads backward from the end in chunks, so large logs aren't loaded whole.
+pub fn tail(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    const CHUNK: u64 = 64 * 1024;
+
+    let mut file = File::open(path)?;
+    let mut pos = file.metadata()?.len();
+    let mut buf: Vec<u8> = Vec::new();
+
+    // One newline more than requested guarantees the first kept line is whole
+    while pos > 0 && buf.iter().filter(|&&b| b == b'\n').count() <= lines {
+        let start = pos.saturating_sub(CHUNK);
+        let mut chunk = vec![0; (pos - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+        pos = start;
+    }
+
+    let text = String::from_utf8_lossy(&buf);
+    let all: Vec<&str> = text.lines().collect();
+    let skip = all.len().saturating_sub(lines);
+    Ok(all[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rotates_past_max_size() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("coordinator.log");
+        let mut log = RotatingLogFile::open(&path, 100).unwrap();
+
+        log.write_all(&[b'a'; 60]).unwrap();
+        assert!(!rotated_path(&path).exists());
+
+        // Crossing the threshold moves the old content aside
+        log.write_all(&[b'b'; 60]).unwrap();
+        log.flush().unwrap();
+        assert_eq!(fs::read(rotated_path(&path)).unwrap(), vec![b'a'; 60]);
+        assert_eq!(fs::read(&path).unwrap(), vec![b'b'; 60]);
+
+        // A second rotation replaces the first
+        log.write_all(&[b'c'; 60]).unwrap();
+        assert_eq!(fs::read(rotated_path(&path)).unwrap(), vec![b'b'; 60]);
+        assert_eq!(fs::read(&path).unwrap(), vec![b'c'; 60]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_redirected_fd_follows_rotation() {
+        use std::os::fd::AsRawFd;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("coordinator.log");
+        // Stands in for stderr, which the test harness needs
+        let mut stderr = File::create(dir.path().join("stderr")).unwrap();
+        let mut log = RotatingLogFile::open(&path, 100)
+            .unwrap()
+            .redirect_fd(stderr.as_raw_fd())
+            .unwrap();
+
+        stderr.write_all(&[b'p'; 60]).unwrap();
+        // Raw writes count toward the limit
+        log.write_all(&[b'a'; 60]).unwrap();
+        assert_eq!(fs::read(rotated_path(&path)).unwrap(), vec![b'p'; 60]);
+
+        stderr.write_all(&[b'q'; 10]).unwrap();
+        let mut expected = vec![b'a'; 60];
+        expected.extend([b'q'; 10]);
+        assert_eq!(fs::read(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_existing_content_counts_toward_limit() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("coordinator.log");
+        fs::write(&path, [b'x'; 80]).unwrap();
+
+        let mut log = RotatingLogFile::open(&path, 100).unwrap();
+        log.write_all(&[b'y'; 30]).unwrap();
+        assert_eq!(fs::read(rotated_path(&path)).unwrap(), vec![b'x'; 80]);
+        assert_eq!(fs::read(&path).unwrap(), vec![b'y'; 30]);
+    }
+
+    #[test]
+    fn test_tail() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("coordinator.log");
+        // Long lines so the tail spans several chunks
+        let lines: Vec<String> = (0..500)
+            .map(|i| format!("{i:04} {}", "x".repeat(400)))
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let last = tail(&path, 200).unwrap();
+        assert_eq!(last.len(), 200);
+        assert_eq!(last[0], lines[300]);
+        assert_eq!(last[199], lines[499]);
+
+        assert_eq!(tail(&path, 1000).unwrap().len(), 500);
+        assert!(tail(&path, 0).unwrap().is_empty());
+        assert!(tail(&dir.path().join("missing.log"), 10).is_err());
+    }
+}
@@ -6,36 +6,40 @@
 mod grpc;
 mod hooks;
 mod lock;
+mod logfile;
 mod server;
+mod signals;
+mod state;
+mod telemetry;
 mod tls;
 mod watcher_bridge;
 
 use clap::Parser;
-use grpc::{
-    CoordinatorServiceImpl, CoordinatorState, HookServiceImpl, IndexerServiceImpl,
-    MemoryServiceImpl, SessionServiceImpl, SlotServiceImpl,
+use grpc::{CoordinatorState, SlotValueStore};
+use han_api::context::{
+    DbChangeEvent, HookDryRunner, HookLister, ReindexRequest, ReindexStats, SessionReindexer,
+    WatchedDirectoryLister,
 };
-use han_api::context::DbChangeEvent;
+use han_api::types::discovered_hook::DiscoveredHookInfo;
+use han_api::types::hook_dry_run::HookDryRunResult;
 use han_db::{DbConfig, establish_connection};
 use han_db::migration::Migrator;
-use han_proto::coordinator::{
-    coordinator_service_server::CoordinatorServiceServer,
-    hook_service_server::HookServiceServer,
-    indexer_service_server::IndexerServiceServer,
-    memory_service_server::MemoryServiceServer,
-    session_service_server::SessionServiceServer,
-    slot_service_server::SlotServiceServer,
-};
 use hooks::HookEngine;
 use lock::CoordinatorLock;
 use sea_orm_migration::MigratorTrait;
+use signals::{Diagnostics, SignalAction, SignalListener};
+use state::SessionCache;
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
 use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::sync::{Mutex, RwLock, broadcast};
 use tokio_rustls::TlsAcceptor;
-use tonic::transport::Server as TonicServer;
 
 #[derive(Parser, Debug)]
 #[command(name = "han-coordinator", version, about = "Han coordinator daemon")]
@@ -64,41 +68,133 @@ struct Cli {
     #[arg(long)]
     no_grpc: bool,
 
+    /// Serve gRPC server reflection so tools like grpcurl can list services
+    /// without the proto files. Requires --grpc-auth-token.
+    #[arg(long, requires = "grpc_auth_token")]
+    grpc_reflection: bool,
+
+    /// Reject gRPC requests without `authorization: Bearer <TOKEN>` metadata.
+    /// Prefer setting HAN_GRPC_AUTH_TOKEN, which keeps the token out of the
+    /// process list.
+    #[arg(
+        long,
+        value_name = "TOKEN",
+        env = "HAN_GRPC_AUTH_TOKEN",
+        hide_env_values = true
+    )]
+    grpc_auth_token: Option<String>,
+
     /// Skip file watcher.
     #[arg(long)]
     no_watcher: bool,
 
+    /// Also report changes to files matching this glob, even ones the
+    /// watcher skips by default. Repeatable.
+    #[arg(long, value_name = "GLOB", value_parser = glob::Pattern::new)]
+    watcher_include_pattern: Vec<glob::Pattern>,
+
+    /// Skip changes to files matching this glob, on top of the default
+    /// ignores. Repeatable.
+    #[arg(long, value_name = "GLOB", value_parser = glob::Pattern::new)]
+    watcher_exclude_pattern: Vec<glob::Pattern>,
+
     /// Run initial full scan on startup.
     #[arg(long)]
     scan_on_start: bool,
 
+    /// Discard checkpoints left by interrupted index passes, so those
+    /// sessions re-index from the end of their last completed pass.
+    #[arg(long)]
+    force_reindex: bool,
+
     /// Write PID to file (daemon mode).
     #[arg(long)]
     pid_file: Option<String>,
+
+    /// PEM certificate chain to serve instead of the managed certificate.
+    #[arg(long, requires = "tls_key_path")]
+    tls_cert_path: Option<std::path::PathBuf>,
+
+    /// PKCS#8 PEM private key for --tls-cert-path.
+    #[arg(long, requires = "tls_cert_path")]
+    tls_key_path: Option<std::path::PathBuf>,
+
+    /// Require clients to present a certificate issued by one of the CA
+    /// certificates in this PEM file (mutual TLS).
+    #[arg(long, value_name = "PATH")]
+    tls_client_ca: Option<std::path::PathBuf>,
+
+    /// Serve plain HTTP instead of HTTPS. For local development only; also
+    /// enabled by COORDINATOR_NO_TLS=1. Requires --foreground and refuses
+    /// --pid-file, which only daemon deployments use.
+    #[arg(long, conflicts_with_all = ["tls_cert_path", "tls_key_path", "tls_client_ca"])]
+    no_tls: bool,
+
+    /// Archive sessions with no messages in this many days, checked hourly.
+    #[arg(long, value_name = "DAYS", value_parser = clap::value_parser!(u32).range(1..))]
+    auto_archive_days: Option<u32>,
+
+    /// Take the coordinator lock even if another live instance holds it.
+    #[arg(long)]
+    force: bool,
+
+    /// Roll the database schema back to this migration version, then exit.
+    #[arg(long, value_name = "VERSION")]
+    migrate_down_to: Option<u64>,
+
+    /// Check the database schema against the expected tables and columns,
+    /// then exit with status 0 if it matches or 1 if it doesn't.
+    #[arg(long)]
+    verify_schema: bool,
+
+    /// Write logs and stderr to this file instead of the terminal. Daemon
+    /// mode defaults to ~/.han/coordinator.log.
+    #[arg(long)]
+    log_file: Option<std::path::PathBuf>,
+
+    /// Rotate the log file once it would grow past this many megabytes.
+    #[arg(long, default_value_t = logfile::DEFAULT_MAX_SIZE_MB)]
+    log_max_size_mb: u64,
+
+    /// Number of sessions kept in memory for gRPC session lookups.
+    #[arg(long, value_name = "SESSIONS", default_value_t = state::DEFAULT_SESSION_CACHE_SIZE)]
+    memory_cache_size: NonZeroUsize,
 }
 
 /// TLS-wrapped TCP listener for axum::serve.
 ///
 /// Accepts TCP connections, performs TLS handshake, and yields TLS streams.
 /// Failed handshakes are logged and retried (the listener keeps accepting).
+/// The acceptor sits behind a lock so SIGHUP can swap in new certificates.
 struct TlsListener {
     inner: tokio::net::TcpListener,
-    acceptor: TlsAcceptor,
+    acceptor: Arc<RwLock<TlsAcceptor>>,
+    active_connections: Arc<AtomicUsize>,
 }
 
 impl axum::serve::Listener for TlsListener {
-    type Io = tokio_rustls::server::TlsStream<tokio::net::TcpStream>;
+    type Io = CountedStream;
     type Addr = SocketAddr;
 
     async fn accept(&mut self) -> (Self::Io, Self::Addr) {
         loop {
             match self.inner.accept().await {
-                Ok((tcp_stream, addr)) => match self.acceptor.accept(tcp_stream).await {
-                    Ok(tls_stream) => return (tls_stream, addr),
-                    Err(e) => {
-                        tracing::debug!("TLS handshake failed from {}: {}", addr, e);
+                Ok((tcp_stream, addr)) => {
+                    let acceptor = self.acceptor.read().await.clone();
+                    match acceptor.accept(tcp_stream).await {
+                        Ok(tls_stream) => {
+                            self.active_connections.fetch_add(1, Ordering::Relaxed);
+                            let stream = CountedStream {
+                                inner: tls_stream,
+                                active_connections: self.active_connections.clone(),
+                            };
+                            return (stream, addr);
+                        }
+                        Err(e) => {
+                            tracing::debug!("TLS handshake failed from {}: {}", addr, e);
+                        }
                     }
-                },
+                }
                 Err(e) => {
                     tracing::error!("TCP accept error: {}", e);
                     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -112,6 +208,73 @@ impl axum::serve::Listener for TlsListener {
     }
 }
 
+/// TLS stream that counts towards the active connection total while open.
+struct CountedStream {
+    inner: tokio_rustls::server::TlsStream<tokio::net::TcpStream>,
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl Drop for CountedStream {
+    fn drop(&mut self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl AsyncRead for CountedStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for CountedStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+/// Resolve `--no-tls`, or `COORDINATOR_NO_TLS=1` given as `env`.
+///
+/// Plain HTTP is refused with a pid file: daemon mode always writes one, so
+/// its presence marks a long-running deployment rather than a dev session.
+fn resolve_no_tls(cli: &Cli, env: Option<&str>) -> Result<bool, String> {
+    let no_tls = cli.no_tls || env == Some("1");
+    if no_tls && (cli.pid_file.is_some() || !cli.foreground) {
+        return Err(
+            "--no-tls is for local development; run with --foreground and without --pid-file"
+                .to_string(),
+        );
+    }
+    Ok(no_tls)
+}
+
 /// Resolve the database path.
 fn resolve_db_path(cli_path: Option<&str>) -> String {
     if let Some(path) = cli_path {
@@ -131,12 +294,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .init();
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    if let Some(ref path) = cli.log_file {
+        let max_bytes = cli.log_max_size_mb.saturating_mul(1024 * 1024);
+        let log = logfile::RotatingLogFile::open(path, max_bytes)?;
+        #[cfg(unix)]
+        let log = log.redirect_stderr()?;
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .with_ansi(false)
+            .with_writer(std::sync::Mutex::new(log))
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(env_filter).init();
+    }
 
     // Install ring crypto provider for rustls (must be before any TLS operations)
     let _ = rustls::crypto::ring::default_provider().install_default();
@@ -146,6 +318,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         env!("CARGO_PKG_VERSION")
     );
 
+    // Schema downgrade is a one-shot maintenance task; don't start the server
+    if let Some(version) = cli.migrate_down_to {
+        let db_path = resolve_db_path(cli.db_path.as_deref());
+        let db = establish_connection(DbConfig::Sqlite { path: db_path }).await?;
+        han_db::migration::rollback_to_version(&db, version).await?;
+        tracing::info!("Database schema rolled back to version {}", version);
+        return Ok(());
+    }
+
+    if cli.verify_schema {
+        let db_path = resolve_db_path(cli.db_path.as_deref());
+        let db = establish_connection(DbConfig::Sqlite { path: db_path }).await?;
+        let report = han_db::migration::verify_schema(&db).await?;
+        for table in &report.missing_tables {
+            tracing::error!("Schema mismatch: missing table {}", table);
+        }
+        for (table, column) in &report.missing_columns {
+            tracing::error!("Schema mismatch: missing column {}.{}", table, column);
+        }
+        for table in &report.extra_tables {
+            tracing::warn!("Schema has unexpected table {}", table);
+        }
+        if !report.is_valid {
+            std::process::exit(1);
+        }
+        tracing::info!("Database schema verified");
+        return Ok(());
+    }
+
+    let no_tls = resolve_no_tls(&cli, std::env::var("COORDINATOR_NO_TLS").ok().as_deref())?;
+
+    // Load certificates before daemonizing so bad --tls-* paths fail fast
+    let certs = if no_tls {
+        None
+    } else {
+        Some(tls::load_or_generate_certs(
+            cli.tls_cert_path.as_deref(),
+            cli.tls_key_path.as_deref(),
+        )?)
+    };
+    if let Some(ref ca_path) = cli.tls_client_ca {
+        tls::load_client_ca(ca_path)?;
+    }
+
     // Daemon mode: fork if not --foreground
     if !cli.foreground {
         tracing::info!("Daemonizing...");
@@ -155,14 +371,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Acquire coordinator lock
     let lock = CoordinatorLock::new()?;
-    lock.acquire(Some(cli.port))?;
+    let grpc_port = (!cli.no_grpc).then_some(cli.grpc_port);
+    if cli.force {
+        lock.acquire_forced(Some(cli.port), grpc_port)?;
+    } else {
+        lock.acquire(Some(cli.port), grpc_port)?;
+    }
 
     // Start heartbeat task
     let lock_path = lock.lock_path().to_path_buf();
     tokio::spawn(async move {
         let heartbeat_lock = lock::CoordinatorLock::with_path(lock_path);
         loop {
-            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+            tokio::time::sleep(std::time::Duration::from_secs(
+                lock::HEARTBEAT_INTERVAL_SECS,
+            ))
+            .await;
             if let Err(e) = heartbeat_lock.heartbeat() {
                 tracing::warn!("Heartbeat failed: {}", e);
             }
@@ -173,36 +397,138 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let db_path = resolve_db_path(cli.db_path.as_deref());
     tracing::info!("Database: {}", db_path);
 
-    let db = establish_connection(DbConfig::Sqlite {
+    let metrics_handle = telemetry::install();
+    let mut db = establish_connection(DbConfig::Sqlite {
         path: db_path.clone(),
     })
     .await?;
+    telemetry::observe_db_queries(&mut db);
 
     // Run migrations
     Migrator::up(&db, None).await?;
     tracing::info!("Migrations applied");
 
+    if cli.force_reindex {
+        let cleared = han_db::crud::indexing_checkpoints::clear_all(&db).await?;
+        tracing::info!("Discarded {} indexing checkpoints", cleared);
+    }
+
     // Build GraphQL schema
     let (event_tx, _) = broadcast::channel::<DbChangeEvent>(1024);
-    let schema = han_api::build_schema(db.clone(), event_tx.clone());
+    let reindex_db = db.clone();
+    let reindex_events = event_tx.clone();
+    let reindexer: SessionReindexer = Arc::new(move |request: ReindexRequest| {
+        let db = reindex_db.clone();
+        let events = reindex_events.clone();
+        Box::pin(async move {
+            let result = han_indexer::reindex_session_file(
+                &db,
+                &request.transcript_path,
+                request.source_config_dir.as_deref(),
+                request.force,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            telemetry::record_session_indexed(result.messages_indexed);
+            if !result.session_id.is_empty() {
+                let _ = events.send(DbChangeEvent::SessionIndexed {
+                    session_id: result.session_id.clone(),
+                });
+            }
+            Ok(ReindexStats {
+                messages_indexed: result.messages_indexed,
+                messages_skipped: result.messages_skipped,
+                errors: result.error.into_iter().collect(),
+            })
+        })
+    });
 
-    // Defer initial scan to after server starts (runs in background)
-    let scan_on_start = cli.scan_on_start;
-    let scan_db = db.clone();
+    // Build hook engine
+    let project_path = cli.project_path.map(std::path::PathBuf::from);
+    let hook_engine = Arc::new(Mutex::new(HookEngine::new(project_path)));
+
+    let dry_run_engine = hook_engine.clone();
+    let hook_dry_runner: HookDryRunner =
+        Arc::new(move |event: String, tool_name: Option<String>| {
+            let engine = dry_run_engine.clone();
+            Box::pin(async move {
+                let engine = engine.lock().await;
+                engine
+                    .execute_event_dry_run(&event, tool_name.as_deref())
+                    .await
+                    .into_iter()
+                    .map(|h| HookDryRunResult {
+                        hook_id: h.hook_id,
+                        plugin_name: h.plugin_name,
+                        hook_name: h.hook_name,
+                        command: h.command,
+                        is_cached: h.is_cached,
+                        cache_key_hash: h.cache_key_hash,
+                        timeout_ms: h.timeout_ms,
+                    })
+                    .collect()
+            })
+        });
+
+    let list_engine = hook_engine.clone();
+    let hook_lister: HookLister = Arc::new(move |event: Option<String>, plugin: Option<String>| {
+        let engine = list_engine.clone();
+        Box::pin(async move {
+            let engine = engine.lock().await;
+            engine
+                .filter_hooks(event.as_deref(), plugin.as_deref())
+                .into_iter()
+                .map(|h| DiscoveredHookInfo {
+                    plugin_name: h.plugin_name,
+                    plugin_root: h.plugin_root.to_string_lossy().to_string(),
+                    event: h.event,
+                    hook_type: h.hook_type,
+                    command: h.command,
+                    prompt: h.prompt,
+                    matcher: h.matcher,
+                    timeout_ms: h.timeout.map(|t| u32::try_from(t).unwrap_or(u32::MAX)),
+                })
+                .collect()
+        })
+    });
 
     // Start file watcher bridge
-    let watcher_handle = if !cli.no_watcher {
-        Some(watcher_bridge::start_watcher_bridge(
-            db.clone(),
-            event_tx.clone(),
-        ))
+    let (watcher_task, watcher) = if !cli.no_watcher {
+        let ignore = han_indexer::IgnorePatterns::new(
+            cli.watcher_include_pattern.clone(),
+            cli.watcher_exclude_pattern.clone(),
+        );
+        watcher_bridge::start_watcher_bridge(db.clone(), event_tx.clone(), ignore)
+            .await
+            .unzip()
     } else {
-        None
+        (None, None)
     };
 
-    // Build hook engine
-    let project_path = cli.project_path.map(std::path::PathBuf::from);
-    let hook_engine = Arc::new(Mutex::new(HookEngine::new(project_path)));
+    let lister_watcher = watcher.clone();
+    let watched_directory_lister: WatchedDirectoryLister = Arc::new(move || {
+        lister_watcher
+            .iter()
+            .flat_map(|w| w.watched_paths())
+            .map(|p| p.to_string_lossy().to_string())
+            .collect()
+    });
+    let schema = han_api::build_schema_with_services(
+        db.clone(),
+        event_tx.clone(),
+        reindexer,
+        hook_dry_runner,
+        hook_lister,
+        watched_directory_lister,
+    );
+
+    // Defer initial scan to after server starts (runs in background)
+    let scan_on_start = cli.scan_on_start;
+    let scan_db = db.clone();
+    let scan_event_tx = event_tx.clone();
+
+    let session_cache = SessionCache::new(cli.memory_cache_size);
+    session_cache.spawn_invalidation(event_tx.subscribe());
 
     // Shared gRPC state
     let coordinator_state = Arc::new(CoordinatorState {
@@ -210,28 +536,58 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         start_time: Instant::now(),
         hook_engine: hook_engine.clone(),
         slots: Arc::new(RwLock::new(HashMap::new())),
+        slot_values: SlotValueStore::default(),
+        events: event_tx.clone(),
+        watcher,
+        websocket_connections: Arc::default(),
+        grpc_streams: Arc::default(),
+        session_cache: session_cache.clone(),
     });
 
-    // Start HTTPS server
+    // Start HTTPS (or, with --no-tls, plain HTTP) server
     let server_addr: SocketAddr = ([0, 0, 0, 0], cli.port).into();
-    let router = server::build_router(schema.clone(), coordinator_state.start_time);
-
-    let certs = tls::ensure_certificates()?;
-    let tls_config = tls::build_tls_config(&certs)?;
-    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
-    let tls_listener = tokio::net::TcpListener::bind(server_addr).await?;
+    let router = server::build_router(
+        schema.clone(),
+        db.clone(),
+        coordinator_state.start_time,
+        metrics_handle,
+        cli.log_file.clone(),
+        coordinator_state.websocket_connections.clone(),
+        session_cache,
+    );
 
-    tracing::info!("HTTPS server listening on {}", server_addr);
+    let client_ca = cli.tls_client_ca.as_deref();
+    let acceptor = match certs {
+        Some(ref certs) => {
+            let tls_config = Arc::new(tls::build_tls_config(certs, client_ca)?);
+            Some(Arc::new(RwLock::new(TlsAcceptor::from(tls_config))))
+        }
+        None => None,
+    };
+    let active_connections = Arc::new(AtomicUsize::new(0));
+    let tcp_listener = tokio::net::TcpListener::bind(server_addr).await?;
 
-    let server_handle = tokio::spawn(async move {
+    let mut server_handle = if let Some(ref acceptor) = acceptor {
+        tracing::info!("HTTPS server listening on {}", server_addr);
         let listener = TlsListener {
-            inner: tls_listener,
-            acceptor,
+            inner: tcp_listener,
+            acceptor: acceptor.clone(),
+            active_connections: active_connections.clone(),
         };
-        if let Err(e) = axum::serve(listener, router).await {
-            tracing::error!("HTTPS server error: {}", e);
-        }
-    });
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                tracing::error!("HTTPS server error: {}", e);
+            }
+        })
+    } else {
+        tracing::warn!("Running without TLS — do not use in production");
+        tracing::info!("HTTP server listening on {}", server_addr);
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(tcp_listener, router).await {
+                tracing::error!("HTTP server error: {}", e);
+            }
+        })
+    };
 
     // Start gRPC server
     let grpc_handle = if !cli.no_grpc {
@@ -239,29 +595,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let state = coordinator_state.clone();
 
         tracing::info!("gRPC server listening on {}", grpc_addr);
+        let auth = grpc::BearerAuth::new(cli.grpc_auth_token.clone());
+        let grpc_router = grpc::router(state, auth, cli.grpc_reflection)?;
+        if cli.grpc_reflection {
+            tracing::info!("gRPC server reflection enabled");
+        }
         Some(tokio::spawn(async move {
-            TonicServer::builder()
-                .add_service(CoordinatorServiceServer::new(CoordinatorServiceImpl {
-                    state: state.clone(),
-                }))
-                .add_service(SessionServiceServer::new(SessionServiceImpl {
-                    state: state.clone(),
-                }))
-                .add_service(IndexerServiceServer::new(IndexerServiceImpl {
-                    state: state.clone(),
-                }))
-                .add_service(HookServiceServer::new(HookServiceImpl {
-                    state: state.clone(),
-                }))
-                .add_service(SlotServiceServer::new(SlotServiceImpl {
-                    state: state.clone(),
-                }))
-                .add_service(MemoryServiceServer::new(MemoryServiceImpl {
-                    state: state.clone(),
-                }))
-                .serve(grpc_addr)
-                .await
-                .unwrap();
+            grpc_router.serve(grpc_addr).await.unwrap();
         }))
     } else {
         None
@@ -273,7 +613,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     tracing::info!(
-        "Coordinator ready (HTTPS={}, gRPC={})",
+        "Coordinator ready ({}={}, gRPC={})",
+        if no_tls { "HTTP" } else { "HTTPS" },
         cli.port,
         if cli.no_grpc {
             "disabled".to_string()
@@ -286,8 +627,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if scan_on_start {
         tokio::spawn(async move {
             tracing::info!("Running initial full scan in background...");
-            match han_indexer::full_scan_and_index(&scan_db).await {
+            let progress_tx = Some(watcher_bridge::forward_index_progress(scan_event_tx));
+            match han_indexer::full_scan_and_index_with_progress(&scan_db, progress_tx).await {
                 Ok(results) => {
+                    for result in &results {
+                        telemetry::record_session_indexed(result.messages_indexed);
+                    }
                     let total: u32 = results.iter().map(|r| r.messages_indexed).sum();
                     tracing::info!(
                         "Initial scan complete: {} sessions, {} messages indexed",
@@ -302,21 +647,101 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // Recompute project stats periodically; indexing drops a project's
+    // cached row in between so queries never serve numbers from before it
+    let stats_db = db.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            if let Err(e) = han_db::crud::project_stats::refresh_all(&stats_db).await {
+                tracing::warn!("Project stats refresh failed: {}", e);
+            }
+        }
+    });
+
+    if let Some(days) = cli.auto_archive_days {
+        let archive_db = db.clone();
+        let archive_events = event_tx.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match han_db::crud::archive_stale_sessions(&archive_db, days).await {
+                    Ok(archived) if archived.is_empty() => {}
+                    Ok(archived) => {
+                        tracing::info!(
+                            "Archived {} sessions idle for {}+ days",
+                            archived.len(),
+                            days
+                        );
+                        for session_id in archived {
+                            let _ =
+                                archive_events.send(DbChangeEvent::SessionUpdated { session_id });
+                        }
+                    }
+                    Err(e) => tracing::warn!("Session archiving failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Sample pool occupancy; exhaustion otherwise shows up only as timeouts
+    han_db::connection::start_pool_monitor(db.clone(), 60).await;
+
     // Setup signal handling
-    let shutdown = tokio::signal::ctrl_c();
+    let mut signals = SignalListener::new()?;
 
-    tokio::select! {
-        _ = shutdown => {
-            tracing::info!("Received shutdown signal");
-        }
-        _ = server_handle => {
-            tracing::info!("Server stopped");
+    loop {
+        tokio::select! {
+            action = signals.recv_reporting(std::io::stderr(), || async {
+                let engine = hook_engine.lock().await;
+                Diagnostics {
+                    uptime_secs: coordinator_state.start_time.elapsed().as_secs(),
+                    active_connections: active_connections.load(Ordering::Relaxed),
+                    hooks_discovered: engine.all_hooks().len(),
+                    hook_cache_entries: engine.cache_len().await,
+                    db_pool: han_db::connection::pool_stats(&db).await,
+                }
+            }) => match action {
+                SignalAction::Shutdown => {
+                    tracing::info!("Received shutdown signal");
+                    break;
+                }
+                SignalAction::Reload => {
+                    tracing::info!("Received SIGHUP, reloading hooks and certificates");
+                    hook_engine.lock().await.refresh();
+                    if let Some(ref acceptor) = acceptor {
+                        match tls::load_or_generate_certs(
+                            cli.tls_cert_path.as_deref(),
+                            cli.tls_key_path.as_deref(),
+                        )
+                        .and_then(|certs| tls::build_tls_config(&certs, client_ca))
+                        {
+                            Ok(config) => {
+                                *acceptor.write().await = TlsAcceptor::from(Arc::new(config));
+                                tracing::info!("TLS certificates reloaded");
+                            }
+                            Err(e) => {
+                                tracing::warn!("Keeping current TLS certificates: {}", e);
+                            }
+                        }
+                    }
+                }
+                SignalAction::Diagnostics => {
+                    tracing::info!("Received SIGUSR1, wrote diagnostics to stderr");
+                }
+            },
+            _ = &mut server_handle => {
+                tracing::info!("Server stopped");
+                break;
+            }
         }
     }
 
     // Cleanup
     tracing::info!("Shutting down...");
-    if let Some(handle) = watcher_handle {
+    if let Some(handle) = watcher_task {
         handle.abort();
     }
     if let Some(handle) = grpc_handle {
@@ -331,11 +756,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// Daemonize by forking and waiting for the child to be healthy.
-fn daemonize(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
-    use std::process::Command;
-
-    let exe = std::env::current_exe()?;
+/// Arguments for the `--foreground` child, carrying over every option the
+/// daemon was started with.
+fn daemon_args(cli: &Cli, log_path: &std::path::Path, pid_path: &str) -> Vec<String> {
     let mut args = vec!["--foreground".to_string()];
     args.push("--port".to_string());
     args.push(cli.port.to_string());
@@ -353,12 +776,70 @@ fn daemonize(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
     if cli.no_grpc {
         args.push("--no-grpc".to_string());
     }
+    if cli.grpc_reflection {
+        args.push("--grpc-reflection".to_string());
+    }
     if cli.no_watcher {
         args.push("--no-watcher".to_string());
     }
+    for pattern in &cli.watcher_include_pattern {
+        args.push("--watcher-include-pattern".to_string());
+        args.push(pattern.as_str().to_string());
+    }
+    for pattern in &cli.watcher_exclude_pattern {
+        args.push("--watcher-exclude-pattern".to_string());
+        args.push(pattern.as_str().to_string());
+    }
     if cli.scan_on_start {
         args.push("--scan-on-start".to_string());
     }
+    if cli.force_reindex {
+        args.push("--force-reindex".to_string());
+    }
+    if let Some(ref cert) = cli.tls_cert_path {
+        args.push("--tls-cert-path".to_string());
+        args.push(cert.to_string_lossy().to_string());
+    }
+    if let Some(ref key) = cli.tls_key_path {
+        args.push("--tls-key-path".to_string());
+        args.push(key.to_string_lossy().to_string());
+    }
+    if let Some(ref ca) = cli.tls_client_ca {
+        args.push("--tls-client-ca".to_string());
+        args.push(ca.to_string_lossy().to_string());
+    }
+    if let Some(days) = cli.auto_archive_days {
+        args.push("--auto-archive-days".to_string());
+        args.push(days.to_string());
+    }
+    if cli.force {
+        args.push("--force".to_string());
+    }
+
+    args.push("--log-file".to_string());
+    args.push(log_path.to_string_lossy().to_string());
+    args.push("--log-max-size-mb".to_string());
+    args.push(cli.log_max_size_mb.to_string());
+    args.push("--memory-cache-size".to_string());
+    args.push(cli.memory_cache_size.to_string());
+    args.push("--pid-file".to_string());
+    args.push(pid_path.to_string());
+    args
+}
+
+/// Daemonize by forking and waiting for the child to be healthy.
+fn daemonize(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
+    use std::process::Command;
+
+    let exe = std::env::current_exe()?;
+
+    // The daemon has no terminal, so log to a file; stderr catches panics
+    // and anything written before tracing starts
+    let log_path = cli
+        .log_file
+        .clone()
+        .unwrap_or_else(logfile::default_log_path);
+    let stderr_log = logfile::open_append(&log_path)?;
 
     // Write PID file for daemon tracking
     let pid_path = if let Some(home) = dirs::home_dir() {
@@ -368,13 +849,17 @@ fn daemonize(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
     } else {
         "coordinator.pid".to_string()
     };
-    args.push("--pid-file".to_string());
-    args.push(pid_path.clone());
+    let args = daemon_args(cli, &log_path, &pid_path);
 
-    let child = Command::new(exe)
+    let mut command = Command::new(exe);
+    // Passed through the environment so the token doesn't show up in `ps`
+    if let Some(ref token) = cli.grpc_auth_token {
+        command.env("HAN_GRPC_AUTH_TOKEN", token);
+    }
+    let child = command
         .args(&args)
         .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
+        .stderr(stderr_log)
         .stdin(std::process::Stdio::null())
         .spawn()?;
 
@@ -404,6 +889,18 @@ fn daemonize(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// In-memory SQLite database with all migrations applied.
+#[cfg(test)]
+pub(crate) async fn test_db() -> sea_orm::DatabaseConnection {
+    let db = establish_connection(DbConfig::Sqlite {
+        path: ":memory:".to_string(),
+    })
+    .await
+    .unwrap();
+    han_db::migration::run_migrations(&db).await.unwrap();
+    db
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,6 +924,36 @@ mod tests {
         assert!(cli.foreground);
         assert!(!cli.no_grpc);
         assert!(!cli.no_watcher);
+        assert!(!cli.grpc_reflection);
+        assert!(cli.grpc_auth_token.is_none());
+    }
+
+    #[test]
+    fn test_cli_grpc_reflection_requires_auth_token() {
+        assert!(Cli::try_parse_from(["han-coordinator", "--grpc-reflection"]).is_err());
+        let cli = Cli::parse_from([
+            "han-coordinator",
+            "--grpc-reflection",
+            "--grpc-auth-token",
+            "secret",
+        ]);
+        assert!(cli.grpc_reflection);
+        assert_eq!(cli.grpc_auth_token.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn test_cli_grpc_auth_token_from_env() {
+        use clap::CommandFactory;
+
+        let command = Cli::command();
+        let arg = command
+            .get_arguments()
+            .find(|a| a.get_id() == "grpc_auth_token")
+            .unwrap();
+        assert_eq!(
+            arg.get_env(),
+            Some(std::ffi::OsStr::new("HAN_GRPC_AUTH_TOKEN"))
+        );
     }
 
     #[test]
@@ -445,6 +972,131 @@ mod tests {
         assert_eq!(cli.db_path, Some("/tmp/test.db".to_string()));
     }
 
+    #[test]
+    fn test_cli_auto_archive_days() {
+        let cli = Cli::parse_from(["han-coordinator", "--foreground"]);
+        assert!(cli.auto_archive_days.is_none());
+        let cli = Cli::parse_from(["han-coordinator", "--auto-archive-days", "90"]);
+        assert_eq!(cli.auto_archive_days, Some(90));
+        assert!(Cli::try_parse_from(["han-coordinator", "--auto-archive-days", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_watcher_patterns() {
+        let cli = Cli::parse_from(["han-coordinator"]);
+        assert!(cli.watcher_include_pattern.is_empty());
+        assert!(cli.watcher_exclude_pattern.is_empty());
+
+        let cli = Cli::parse_from([
+            "han-coordinator",
+            "--watcher-include-pattern",
+            "*.ndjson",
+            "--watcher-exclude-pattern",
+            "*/scratch/*",
+            "--watcher-exclude-pattern",
+            "*.bak",
+        ]);
+        assert_eq!(cli.watcher_include_pattern[0].as_str(), "*.ndjson");
+        let excluded: Vec<&str> = cli
+            .watcher_exclude_pattern
+            .iter()
+            .map(|p| p.as_str())
+            .collect();
+        assert_eq!(excluded, ["*/scratch/*", "*.bak"]);
+
+        let invalid = ["han-coordinator", "--watcher-exclude-pattern", "[unclosed"];
+        assert!(Cli::try_parse_from(invalid).is_err());
+    }
+
+    #[test]
+    fn test_cli_memory_cache_size() {
+        let cli = Cli::parse_from(["han-coordinator"]);
+        assert_eq!(cli.memory_cache_size.get(), 256);
+
+        let cli = Cli::parse_from(["han-coordinator", "--memory-cache-size", "1024"]);
+        assert_eq!(cli.memory_cache_size.get(), 1024);
+
+        let zero = ["han-coordinator", "--memory-cache-size", "0"];
+        assert!(Cli::try_parse_from(zero).is_err());
+    }
+
+    #[test]
+    fn test_daemon_args_forward_options() {
+        let cli = Cli::parse_from([
+            "han-coordinator",
+            "--port",
+            "5000",
+            "--db-path",
+            "/tmp/han.db",
+            "--auto-archive-days",
+            "30",
+            "--watcher-include-pattern",
+            "*.env",
+            "--watcher-exclude-pattern",
+            "*.tmp",
+            "--watcher-exclude-pattern",
+            "build/**",
+            "--memory-cache-size",
+            "1024",
+        ]);
+        let args = daemon_args(&cli, std::path::Path::new("/tmp/c.log"), "/tmp/c.pid");
+
+        let child = Cli::parse_from(std::iter::once("han-coordinator".to_string()).chain(args));
+        assert!(child.foreground);
+        assert_eq!(child.port, 5000);
+        assert_eq!(child.db_path.as_deref(), Some("/tmp/han.db"));
+        assert_eq!(child.auto_archive_days, Some(30));
+        assert_eq!(child.watcher_include_pattern, cli.watcher_include_pattern);
+        assert_eq!(child.watcher_exclude_pattern, cli.watcher_exclude_pattern);
+        assert_eq!(child.memory_cache_size.get(), 1024);
+        assert_eq!(child.pid_file.as_deref(), Some("/tmp/c.pid"));
+    }
+
+    #[test]
+    fn test_resolve_no_tls() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from(std::iter::once(&"han-coordinator").chain(args));
+        let resolve = |args: &[&str], env| resolve_no_tls(&parse(args).unwrap(), env);
+
+        assert_eq!(resolve(&["--foreground"], None), Ok(false));
+        assert_eq!(resolve(&["--foreground"], Some("0")), Ok(false));
+        assert_eq!(resolve(&["--foreground"], Some("1")), Ok(true));
+        assert_eq!(resolve(&["--foreground", "--no-tls"], None), Ok(true));
+
+        // Daemon mode and pid files mark a deployment; plain HTTP is refused
+        assert!(resolve(&["--no-tls"], None).is_err());
+        assert!(resolve(&["--foreground", "--pid-file", "c.pid"], Some("1")).is_err());
+        let with_pid_file = ["--foreground", "--no-tls", "--pid-file", "c.pid"];
+        assert!(resolve(&with_pid_file, None).is_err());
+
+        let with_certs = ["--no-tls", "--tls-cert-path", "c", "--tls-key-path", "k"];
+        assert!(parse(&with_certs).is_err());
+        assert!(parse(&["--no-tls", "--tls-client-ca", "ca.pem"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_no_tls_serves_plain_http() {
+        let db = test_db().await;
+        let (event_tx, _) = broadcast::channel::<DbChangeEvent>(16);
+        let router = server::build_router(
+            han_api::build_schema(db.clone(), event_tx),
+            db,
+            Instant::now(),
+            telemetry::install(),
+            None,
+            Arc::default(),
+            SessionCache::new(state::DEFAULT_SESSION_CACHE_SIZE),
+        );
+
+        // Same listener setup as the --no-tls branch of main
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let response = reqwest::get(format!("http://{addr}/health")).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+    }
+
     #[test]
     fn test_resolve_db_path_explicit_absolute() {
         assert_eq!(
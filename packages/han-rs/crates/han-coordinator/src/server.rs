@@ -2,39 +2,126 @@
 //!
 //! Uses Axum for HTTP routing with async-graphql handlers.
 //! POST /graphql for queries/mutations, GET /graphql (WS upgrade) for subscriptions,
-//! GET /graphiql for IDE.
+//! GET /graphiql for IDE, GET /metrics for Prometheus scraping,
+//! GET /logs/tail for the end of the daemon log. Debug builds also serve
+//! GET /debug/cache with session cache statistics.
 
 use async_graphql::http::GraphiQLSource;
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
     Router,
-    extract::{State, WebSocketUpgrade},
+    extract::{Query, State, WebSocketUpgrade},
+    http::StatusCode,
     response::{Html, IntoResponse},
     routing::{get, post},
 };
+use crate::state::SessionCache;
 use han_api::HanSchema;
+use metrics_exporter_prometheus::PrometheusHandle;
+use sea_orm::DatabaseConnection;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::AtomicU32;
+use std::time::{Duration, Instant};
 use tower_http::cors::{Any, CorsLayer};
 
 /// Shared server state.
 #[derive(Clone)]
 pub struct AppState {
     pub schema: HanSchema,
+    pub db: DatabaseConnection,
     pub start_time: Instant,
+    pub metrics: PrometheusHandle,
+    /// Log file written by this process, if it isn't logging to a terminal.
+    pub log_file: Option<PathBuf>,
+    /// Open GraphQL WebSocket connections, shared with the gRPC status RPC.
+    pub websocket_connections: Arc<AtomicU32>,
+    /// Sessions cached for gRPC lookups.
+    pub session_cache: SessionCache,
 }
 
+/// How long the health check waits for the database before reporting unhealthy.
+const HEALTH_DB_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Health check response.
 ///
 /// Returns `pid` and `uptime` so the TypeScript daemon manager can track the process.
+/// Responds 503 when the database does not answer `SELECT 1` within
+/// [`HEALTH_DB_TIMEOUT`].
 async fn health_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let uptime_secs = state.start_time.elapsed().as_secs();
-    axum::Json(serde_json::json!({
-        "status": "ok",
+    let ping = tokio::time::timeout(
+        HEALTH_DB_TIMEOUT,
+        han_db::connection::test_connection(&state.db),
+    )
+    .await;
+    let pool = han_db::connection::pool_stats(&state.db).await;
+
+    let (status, db_ping_ms, db_error) = match ping {
+        Ok(Ok(elapsed)) => (StatusCode::OK, Some(elapsed.as_secs_f64() * 1000.0), None),
+        Ok(Err(e)) => (StatusCode::SERVICE_UNAVAILABLE, None, Some(e.to_string())),
+        Err(_) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            None,
+            Some(format!("timed out after {}s", HEALTH_DB_TIMEOUT.as_secs())),
+        ),
+    };
+
+    let body = axum::Json(serde_json::json!({
+        "status": if status == StatusCode::OK { "ok" } else { "unavailable" },
         "version": env!("CARGO_PKG_VERSION"),
         "pid": std::process::id(),
         "uptime": uptime_secs,
-    }))
+        "db_ping_ms": db_ping_ms,
+        "db_pool_active": pool.active,
+        "db_pool_idle": pool.idle,
+        "db_pool_max": pool.max,
+        "db_pool_wait": pool.wait_count,
+        "db_error": db_error,
+    }));
+    (status, body)
+}
+
+/// Prometheus metrics in text exposition format.
+async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        crate::telemetry::render(&state.metrics),
+    )
+}
+
+/// Session cache hit rate and current entries, as JSON.
+async fn debug_cache_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    axum::Json(state.session_cache.stats())
+}
+
+/// Most lines `/logs/tail` returns at once.
+const MAX_TAIL_LINES: usize = 10_000;
+
+#[derive(serde::Deserialize)]
+struct TailParams {
+    lines: Option<usize>,
+}
+
+/// Last `lines` lines of the log file (default 100), as plain text.
+///
+/// Responds 404 when the coordinator is logging to a terminal.
+async fn logs_tail_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TailParams>,
+) -> impl IntoResponse {
+    let Some(path) = state.log_file.clone() else {
+        return (StatusCode::NOT_FOUND, "not logging to a file".to_string());
+    };
+    let lines = params.lines.unwrap_or(100).min(MAX_TAIL_LINES);
+    match tokio::task::spawn_blocking(move || crate::logfile::tail(&path, lines)).await {
+        Ok(Ok(tail)) => (StatusCode::OK, tail.join("\n")),
+        Ok(Err(e)) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
 }
 
 /// GraphQL POST handler for queries and mutations.
@@ -55,12 +142,15 @@ async fn graphql_ws_handler(
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     let schema = state.schema.clone();
+    let connections = state.websocket_connections.clone();
 
     ws.protocols(["graphql-transport-ws", "graphql-ws"])
         .on_upgrade(move |socket| async move {
             use axum::extract::ws::Message;
             use futures_util::{SinkExt, StreamExt};
 
+            let _connection = crate::telemetry::websocket_opened(connections);
+
             let (mut sink, mut stream) = socket.split();
 
             // Simple graphql-ws protocol handler
@@ -173,10 +263,23 @@ async fn graphiql_handler() -> impl IntoResponse {
 }
 
 /// Build the Axum router with GraphQL endpoints.
-pub fn build_router(schema: HanSchema, start_time: Instant) -> Router {
+pub fn build_router(
+    schema: HanSchema,
+    db: DatabaseConnection,
+    start_time: Instant,
+    metrics: PrometheusHandle,
+    log_file: Option<PathBuf>,
+    websocket_connections: Arc<AtomicU32>,
+    session_cache: SessionCache,
+) -> Router {
     let state = Arc::new(AppState {
         schema: schema.clone(),
+        db,
         start_time,
+        metrics,
+        log_file,
+        websocket_connections,
+        session_cache,
     });
 
     let cors = CorsLayer::new()
@@ -188,23 +291,30 @@ pub fn build_router(schema: HanSchema, start_time: Instant) -> Router {
         ])
         .allow_headers(Any);
 
-    Router::new()
+    let mut router = Router::new()
         .route("/health", get(health_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/logs/tail", get(logs_tail_handler))
         .route(
             "/graphql",
             post(graphql_handler).get(graphql_ws_handler),
         )
-        .route("/graphiql", get(graphiql_handler))
-        .layer(cors)
-        .with_state(state)
+        .route("/graphiql", get(graphiql_handler));
+    if cfg!(debug_assertions) {
+        router = router.route("/debug/cache", get(debug_cache_handler));
+    }
+    router.layer(cors).with_state(state)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::DEFAULT_SESSION_CACHE_SIZE;
+    use crate::test_db;
     use axum::body::Body;
-    use axum::http::{Request, StatusCode};
+    use axum::http::Request;
     use han_api::context::DbChangeEvent;
+    use std::collections::HashMap;
     use tokio::sync::broadcast;
     use tower::ServiceExt;
 
@@ -219,24 +329,106 @@ mod tests {
         .finish()
     }
 
-    #[tokio::test]
-    async fn test_health_endpoint() {
-        let schema = test_schema();
-        let app = build_router(schema, Instant::now());
+    fn test_router(db: DatabaseConnection) -> Router {
+        build_router(
+            test_schema(),
+            db,
+            Instant::now(),
+            crate::telemetry::install(),
+            None,
+            Arc::default(),
+            SessionCache::new(DEFAULT_SESSION_CACHE_SIZE),
+        )
+    }
 
+    async fn get_health(app: Router) -> (StatusCode, serde_json::Value) {
         let req = Request::builder()
             .uri("/health")
             .body(Body::empty())
             .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), 1_000_000)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint() {
+        let (status, body) = get_health(test_router(test_db().await)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+        assert!(body["db_ping_ms"].as_f64().unwrap() >= 0.0);
+        assert!(body["db_pool_active"].is_u64());
+        assert!(body["db_pool_idle"].is_u64());
+        assert_eq!(body["db_pool_max"], 5);
+        assert_eq!(body["db_pool_wait"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint_db_unavailable() {
+        let (status, body) = get_health(test_router(DatabaseConnection::Disconnected)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unavailable");
+        assert!(body["db_ping_ms"].is_null());
+        assert!(body["db_error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        use crate::telemetry;
+
+        let app = test_router(test_db().await);
+        telemetry::record_session_indexed(3);
+        telemetry::record_hook_execution(&crate::hooks::HookExecutionResult {
+            hook_id: "core:Stop:abcd1234".into(),
+            plugin_name: "core".into(),
+            hook_name: "Stop".into(),
+            exit_code: 1,
+            cached: false,
+            duration_ms: 5,
+            error: None,
+        });
+        metrics::histogram!(telemetry::DB_QUERY_DURATION).record(0.002);
 
+        let req = Request::builder()
+            .uri("/metrics")
+            .body(Body::empty())
+            .unwrap();
         let response = app.oneshot(req).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers()["content-type"].to_str().unwrap();
+        assert!(content_type.starts_with("text/plain"));
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), 1_000_000)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+        // Every sample line is `name{labels} value` with a numeric value
+        let mut samples = HashMap::new();
+        for line in body
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        {
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            let value: f64 = value.parse().expect("sample value is numeric");
+            samples.insert(series.to_string(), value);
+        }
+
+        assert!(body.contains("# TYPE han_messages_indexed_total counter"));
+        assert!(samples["han_messages_indexed_total"] >= 3.0);
+        assert!(samples["han_sessions_indexed_total"] >= 1.0);
+        assert!(samples[r#"han_hook_executions_total{status="failure"}"#] >= 1.0);
+        assert!(body.contains("# TYPE han_db_query_duration_seconds histogram"));
+        assert!(samples.contains_key(r#"han_db_query_duration_seconds_bucket{le="+Inf"}"#));
+        assert!(samples["han_indexer_lag_seconds"] >= 0.0);
     }
 
     #[tokio::test]
     async fn test_graphql_post() {
-        let schema = test_schema();
-        let app = build_router(schema, Instant::now());
+        let app = test_router(test_db().await);
 
         let req = Request::builder()
             .method(axum::http::Method::POST)
@@ -249,10 +441,85 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_debug_cache_endpoint() {
+        let cache = SessionCache::new(DEFAULT_SESSION_CACHE_SIZE);
+        cache.insert(
+            cache.generation(),
+            han_proto::coordinator::SessionData {
+                session_id: "s1".to_string(),
+                ..Default::default()
+            },
+        );
+        cache.get("s1");
+        cache.get("s2");
+        let app = build_router(
+            test_schema(),
+            test_db().await,
+            Instant::now(),
+            crate::telemetry::install(),
+            None,
+            Arc::default(),
+            cache,
+        );
+
+        let req = Request::builder()
+            .uri("/debug/cache")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = axum::body::to_bytes(response.into_body(), 1_000_000)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["capacity"], 256);
+        assert_eq!(body["hits"], 1);
+        assert_eq!(body["misses"], 1);
+        assert_eq!(body["hit_rate"], 0.5);
+        assert_eq!(body["entries"], serde_json::json!(["s1"]));
+    }
+
+    #[tokio::test]
+    async fn test_logs_tail() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("coordinator.log");
+        std::fs::write(&path, "one\ntwo\nthree\n").unwrap();
+        let metrics = crate::telemetry::install();
+        let app = build_router(
+            test_schema(),
+            test_db().await,
+            Instant::now(),
+            metrics,
+            Some(path),
+            Arc::default(),
+            SessionCache::new(DEFAULT_SESSION_CACHE_SIZE),
+        );
+
+        let req = Request::builder()
+            .uri("/logs/tail?lines=2")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = axum::body::to_bytes(response.into_body(), 1_000_000)
+            .await
+            .unwrap();
+        assert_eq!(&body_bytes[..], b"two\nthree");
+
+        // Foreground coordinators log to the terminal
+        let app = test_router(test_db().await);
+        let req = Request::builder()
+            .uri("/logs/tail")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_graphiql_handler_returns_html() {
-        let schema = test_schema();
-        let app = build_router(schema, Instant::now());
+        let app = test_router(test_db().await);
 
         let req = Request::builder()
             .uri("/graphiql")
@@ -285,4 +552,91 @@ mod tests {
             "Expected GraphiQL content in HTML body"
         );
     }
+
+    #[tokio::test]
+    async fn test_session_created_subscription_over_websocket() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+        let db = test_db().await;
+        let (tx, _) = broadcast::channel::<DbChangeEvent>(16);
+        let schema = han_api::build_schema(db.clone(), tx.clone());
+        let metrics = crate::telemetry::install();
+        let connections = Arc::new(AtomicU32::new(0));
+        let app = build_router(
+            schema,
+            db.clone(),
+            Instant::now(),
+            metrics,
+            None,
+            connections.clone(),
+            SessionCache::new(DEFAULT_SESSION_CACHE_SIZE),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut request = format!("ws://{addr}/graphql")
+            .into_client_request()
+            .unwrap();
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            "graphql-transport-ws".parse().unwrap(),
+        );
+        let (mut ws, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        let send = |value: serde_json::Value| Message::text(value.to_string());
+        ws.send(send(serde_json::json!({"type": "connection_init"})))
+            .await
+            .unwrap();
+        ws.send(send(serde_json::json!({
+            "id": "1",
+            "type": "subscribe",
+            "payload": {"query": "subscription { sessionCreated { sessionId status } }"}
+        })))
+        .await
+        .unwrap();
+
+        let fired = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+            // The subscription listens once the server has polled its stream
+            while tx.receiver_count() == 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            han_db::crud::sessions::upsert(
+                &db,
+                "ws-session".to_string(),
+                None,
+                Some("active".to_string()),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            tx.send(DbChangeEvent::SessionAdded {
+                session_id: "ws-session".to_string(),
+                parent_id: None,
+                project_id: None,
+            })
+            .unwrap();
+
+            while let Some(msg) = ws.next().await {
+                let text = msg.unwrap().into_text().unwrap();
+                let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if value["type"] == "next" {
+                    return value;
+                }
+            }
+            panic!("WebSocket closed before the subscription fired");
+        })
+        .await
+        .expect("subscription should fire within 2 seconds");
+
+        let session = &fired["payload"]["data"]["sessionCreated"];
+        assert_eq!(fired["id"], "1");
+        assert_eq!(session["sessionId"], "ws-session");
+        assert_eq!(session["status"], "active");
+        assert_eq!(connections.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
 }
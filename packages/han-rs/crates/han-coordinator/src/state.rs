@@ -0,0 +1,221 @@
+//! In-process caches shared by the coordinator's services.
+
+use han_api::context::DbChangeEvent;
+use han_proto::coordinator::SessionData;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::broadcast;
+
+/// Default number of sessions kept by [`SessionCache`].
+pub const DEFAULT_SESSION_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(256).unwrap();
+
+/// Least-recently-used cache of sessions served by `SessionService.Get`.
+///
+/// Clones share the same entries and counters. Entries are dropped when the
+/// session changes; see [`SessionCache::spawn_invalidation`].
+#[derive(Clone)]
+pub struct SessionCache {
+    entries: Arc<Mutex<LruCache<String, SessionData>>>,
+    /// Bumped under the entries lock by every invalidation.
+    generation: Arc<AtomicU64>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+/// Snapshot of a [`SessionCache`] for `/debug/cache`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SessionCacheStats {
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Share of lookups served from the cache, 0 before the first lookup.
+    pub hit_rate: f64,
+    /// Cached session IDs, most recently used first.
+    pub entries: Vec<String>,
+}
+
+impl SessionCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+            generation: Arc::default(),
+            hits: Arc::default(),
+            misses: Arc::default(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<String, SessionData>> {
+        // Entries are plain data, so a panic elsewhere can't leave them torn
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached session `session_id`, counting the lookup as a hit or miss.
+    pub fn get(&self, session_id: &str) -> Option<SessionData> {
+        let cached = self.lock().get(session_id).cloned();
+        let counter = if cached.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Token to take before loading a session for [`SessionCache::insert`].
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Cache `session`, loaded after `generation` was taken. Nothing is
+    /// cached if an invalidation happened since, as the load may predate it.
+    pub fn insert(&self, generation: u64, session: SessionData) {
+        let mut entries = self.lock();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            entries.put(session.session_id.clone(), session);
+        }
+    }
+
+    pub fn invalidate(&self, session_id: &str) {
+        let mut entries = self.lock();
+        entries.pop(session_id);
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.clear();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn stats(&self) -> SessionCacheStats {
+        let entries = self.lock();
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+        SessionCacheStats {
+            capacity: entries.cap().get(),
+            hits,
+            misses,
+            hit_rate: if lookups == 0 {
+                0.0
+            } else {
+                hits as f64 / lookups as f64
+            },
+            entries: entries.iter().map(|(id, _)| id.clone()).collect(),
+        }
+    }
+
+    /// Spawn a task dropping sessions from the cache as `events` reports
+    /// them updated or re-indexed. Missed events clear the whole cache.
+    ///
+    /// The task ends once every sender of `events` is dropped.
+    pub fn spawn_invalidation(&self, mut events: broadcast::Receiver<DbChangeEvent>) {
+        let cache = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(DbChangeEvent::SessionUpdated { session_id })
+                    | Ok(DbChangeEvent::SessionIndexed { session_id }) => {
+                        cache.invalidate(&session_id);
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(_)) => cache.clear(),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str) -> SessionData {
+        SessionData {
+            id: id.to_string(),
+            session_id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn cache(capacity: usize) -> SessionCache {
+        SessionCache::new(NonZeroUsize::new(capacity).unwrap())
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = cache(2);
+        cache.insert(cache.generation(), session("a"));
+        cache.insert(cache.generation(), session("b"));
+        assert!(cache.get("a").is_some());
+        cache.insert(cache.generation(), session("c"));
+
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.stats().entries, ["c", "a"]);
+    }
+
+    #[test]
+    fn test_stats_hit_rate() {
+        let cache = cache(4);
+        assert_eq!(cache.stats().hit_rate, 0.0);
+
+        cache.insert(cache.generation(), session("a"));
+        cache.get("a");
+        cache.get("a");
+        cache.get("a");
+        cache.get("missing");
+
+        let stats = cache.stats();
+        assert_eq!(stats.capacity, 4);
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!(stats.hit_rate, 0.75);
+    }
+
+    #[test]
+    fn test_insert_skipped_after_invalidation() {
+        let cache = cache(4);
+        let generation = cache.generation();
+        // Invalidated while the caller was reading the database
+        cache.invalidate("a");
+        cache.insert(generation, session("a"));
+        assert!(cache.get("a").is_none());
+
+        cache.insert(cache.generation(), session("a"));
+        assert!(cache.get("a").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalidates_on_session_events() {
+        let (tx, rx) = broadcast::channel(16);
+        let cache = cache(4);
+        for id in ["updated", "indexed", "untouched"] {
+            cache.insert(cache.generation(), session(id));
+        }
+        cache.spawn_invalidation(rx);
+
+        tx.send(DbChangeEvent::SessionUpdated {
+            session_id: "updated".into(),
+        })
+        .unwrap();
+        tx.send(DbChangeEvent::SessionIndexed {
+            session_id: "indexed".into(),
+        })
+        .unwrap();
+        tx.send(DbChangeEvent::RepoAdded {
+            repo_id: "untouched".into(),
+        })
+        .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while cache.stats().entries.len() > 1 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("session events should invalidate their entries");
+        assert_eq!(cache.stats().entries, ["untouched"]);
+    }
+}
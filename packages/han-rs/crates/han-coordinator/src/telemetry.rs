@@ -0,0 +1,135 @@
+//! Prometheus metrics for operational monitoring.
+//!
+//! A process-wide recorder is installed once via [`install`]; the returned
+//! handle renders the text exposition format served at `GET /metrics`.
+
+use crate::hooks::HookExecutionResult;
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use sea_orm::DatabaseConnection;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const MESSAGES_INDEXED: &str = "han_messages_indexed_total";
+pub const SESSIONS_INDEXED: &str = "han_sessions_indexed_total";
+pub const HOOK_EXECUTIONS: &str = "han_hook_executions_total";
+pub const DB_QUERY_DURATION: &str = "han_db_query_duration_seconds";
+pub const ACTIVE_WEBSOCKETS: &str = "han_active_websocket_connections";
+pub const INDEXER_LAG: &str = "han_indexer_lag_seconds";
+
+/// Histogram buckets for database query latency, in seconds.
+const DB_QUERY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Unix time in milliseconds of the last completed indexing pass, 0 if none.
+static LAST_INDEXED_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Install the global Prometheus recorder, returning its render handle.
+///
+/// Safe to call more than once; later calls return the same handle.
+pub fn install() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let recorder = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(DB_QUERY_DURATION.to_string()),
+                    DB_QUERY_BUCKETS,
+                )
+                .expect("DB_QUERY_BUCKETS is not empty")
+                .build_recorder();
+            let handle = recorder.handle();
+            if metrics::set_global_recorder(recorder).is_err() {
+                tracing::warn!("A metrics recorder was already installed; /metrics will be empty");
+            }
+
+            describe_counter!(MESSAGES_INDEXED, "Messages written by the indexer");
+            describe_counter!(SESSIONS_INDEXED, "Session files indexed");
+            describe_counter!(HOOK_EXECUTIONS, "Hook executions by outcome");
+            describe_histogram!(DB_QUERY_DURATION, "Database query latency");
+            describe_gauge!(ACTIVE_WEBSOCKETS, "Open GraphQL WebSocket connections");
+            describe_gauge!(INDEXER_LAG, "Seconds since indexing last completed");
+            handle
+        })
+        .clone()
+}
+
+/// Render the current metrics in Prometheus text format.
+pub fn render(handle: &PrometheusHandle) -> String {
+    let last = LAST_INDEXED_MS.load(Ordering::Relaxed);
+    if last > 0 {
+        let lag_ms = now_ms().saturating_sub(last);
+        gauge!(INDEXER_LAG).set(lag_ms as f64 / 1000.0);
+    }
+    handle.render()
+}
+
+/// Time every query run through `db` and warn about slow ones. Clones made
+/// afterwards share the callback.
+pub fn observe_db_queries(db: &mut DatabaseConnection) {
+    let slow_queries = han_db::connection::SlowQueryLogger::default();
+    db.set_metric_callback(move |info| {
+        histogram!(DB_QUERY_DURATION).record(info.elapsed.as_secs_f64());
+        slow_queries.observe(info);
+    });
+}
+
+/// Count one indexed session file and the messages it wrote.
+pub fn record_session_indexed(messages_indexed: u32) {
+    counter!(SESSIONS_INDEXED).increment(1);
+    counter!(MESSAGES_INDEXED).increment(u64::from(messages_indexed));
+    LAST_INDEXED_MS.store(now_ms(), Ordering::Relaxed);
+}
+
+/// Count a hook execution as `cached`, `success` or `failure`, and log how
+/// long it took.
+pub fn record_hook_execution(result: &HookExecutionResult) {
+    let status = if result.cached {
+        "cached"
+    } else if result.exit_code == 0 {
+        "success"
+    } else {
+        "failure"
+    };
+    counter!(HOOK_EXECUTIONS, "status" => status).increment(1);
+    tracing::debug!(
+        "Hook {} ({}:{}) {} in {}ms",
+        result.hook_id,
+        result.plugin_name,
+        result.hook_name,
+        status,
+        result.duration_ms
+    );
+    if let Some(error) = &result.error {
+        tracing::warn!("Hook {} failed to run: {}", result.hook_id, error);
+    }
+}
+
+/// Track an open WebSocket connection until the returned guard is dropped.
+///
+/// `connections` is the live count reported by `CoordinatorService.GetStatus`.
+pub fn websocket_opened(connections: Arc<AtomicU32>) -> WebSocketGuard {
+    gauge!(ACTIVE_WEBSOCKETS).increment(1.0);
+    connections.fetch_add(1, Ordering::Relaxed);
+    WebSocketGuard(connections)
+}
+
+/// Decrements the active WebSocket gauge and count when dropped.
+pub struct WebSocketGuard(Arc<AtomicU32>);
+
+impl Drop for WebSocketGuard {
+    fn drop(&mut self) {
+        gauge!(ACTIVE_WEBSOCKETS).decrement(1.0);
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
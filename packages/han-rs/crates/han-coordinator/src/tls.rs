@@ -1,11 +1,16 @@
 //! TLS certificate management for the coordinator.
 //!
-//! Loads Let's Encrypt certificates from the cert server cache (~/.claude/han/certs/).
-//! Falls back to self-signed certificates if real certs are not available.
+//! Uses operator-supplied certificates when `--tls-cert-path`/`--tls-key-path`
+//! are given. Otherwise loads Let's Encrypt certificates from the cert server
+//! cache (~/.claude/han/certs/), falling back to self-signed certificates if
+//! real certs are not available. Client certificates are only required when
+//! `--tls-client-ca` names the CAs allowed to issue them.
 
 use rcgen::{CertificateParams, DistinguishedName, KeyPair, SanType};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
 const CERT_DOMAIN: &str = "coordinator.local.han.guru";
@@ -22,6 +27,15 @@ pub enum TlsError {
     NoHomeDir,
     #[error("No certificates available")]
     NoCertificates,
+    #[error("--tls-cert-path and --tls-key-path must be provided together")]
+    IncompletePaths,
+    #[error("Invalid certificate in {path}: {reason}")]
+    InvalidCertificate { path: PathBuf, reason: String },
+    #[error("Certificate {path} expired at {not_after}")]
+    Expired {
+        path: PathBuf,
+        not_after: time::OffsetDateTime,
+    },
 }
 
 /// TLS certificate pair (cert + key in PEM format).
@@ -30,6 +44,57 @@ pub struct CertPair {
     pub key_pem: String,
 }
 
+/// Load TLS certificates from the given paths, or fall back to
+/// [`ensure_certificates`] when neither is provided.
+///
+/// Custom certificates must be a PEM X.509 chain (leaf first) and a PEM
+/// PKCS#8 private key. Fails if only one path is given or the leaf
+/// certificate has already expired.
+pub fn load_or_generate_certs(
+    cert_path: Option<&Path>,
+    key_path: Option<&Path>,
+) -> Result<CertPair, TlsError> {
+    match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => load_custom_certs(cert_path, key_path),
+        (None, None) => ensure_certificates(),
+        _ => Err(TlsError::IncompletePaths),
+    }
+}
+
+/// Load and validate an operator-supplied certificate and key.
+fn load_custom_certs(cert_path: &Path, key_path: &Path) -> Result<CertPair, TlsError> {
+    let invalid = |path: &Path, reason: &str| TlsError::InvalidCertificate {
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    };
+
+    let cert_pem = fs::read_to_string(cert_path)?;
+    let key_pem = fs::read_to_string(key_path)?;
+
+    let certs = parse_certs(&cert_pem).map_err(|_| invalid(cert_path, "malformed PEM"))?;
+    let leaf = certs
+        .first()
+        .ok_or_else(|| invalid(cert_path, "no X.509 certificate found"))?;
+
+    match rustls_pemfile::private_key(&mut key_pem.as_bytes()) {
+        Ok(Some(PrivateKeyDer::Pkcs8(_))) => {}
+        Ok(Some(_)) => return Err(invalid(key_path, "private key is not PKCS#8")),
+        Ok(None) | Err(_) => return Err(invalid(key_path, "no private key found")),
+    }
+
+    let not_after =
+        cert_not_after(leaf).ok_or_else(|| invalid(cert_path, "could not read validity period"))?;
+    if not_after <= time::OffsetDateTime::now_utc() {
+        return Err(TlsError::Expired {
+            path: cert_path.to_path_buf(),
+            not_after,
+        });
+    }
+
+    tracing::info!("Using TLS certificate from {:?}", cert_path);
+    Ok(CertPair { cert_pem, key_pem })
+}
+
 /// Load TLS certificates.
 ///
 /// Priority:
@@ -120,11 +185,29 @@ fn generate_self_signed() -> Result<CertPair, TlsError> {
     })
 }
 
+/// Load the CA certificates trusted to issue client certificates.
+pub fn load_client_ca(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let invalid = |reason: &str| TlsError::InvalidCertificate {
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    };
+    let certs = parse_certs(&fs::read_to_string(path)?).map_err(|_| invalid("malformed PEM"))?;
+    if certs.is_empty() {
+        return Err(invalid("no X.509 certificate found"));
+    }
+    Ok(certs)
+}
+
 /// Build a rustls ServerConfig from PEM certificates.
-pub fn build_tls_config(pair: &CertPair) -> Result<rustls::ServerConfig, TlsError> {
-    let certs = rustls_pemfile::certs(&mut pair.cert_pem.as_bytes())
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| TlsError::Io(e))?;
+///
+/// With `client_ca`, clients must present a certificate issued by one of the
+/// CA certificates in that PEM file. Without it, client certificates are not
+/// requested.
+pub fn build_tls_config(
+    pair: &CertPair,
+    client_ca: Option<&Path>,
+) -> Result<rustls::ServerConfig, TlsError> {
+    let certs = parse_certs(&pair.cert_pem)?;
 
     let key = rustls_pemfile::private_key(&mut pair.key_pem.as_bytes())
         .map_err(|e| TlsError::Io(e))?
@@ -135,13 +218,38 @@ pub fn build_tls_config(pair: &CertPair) -> Result<rustls::ServerConfig, TlsErro
             ))
         })?;
 
-    let config = rustls::ServerConfig::builder()
-        .with_no_client_auth()
-        .with_single_cert(certs, key)?;
+    let builder = rustls::ServerConfig::builder();
+    let config = if let Some(ca_path) = client_ca {
+        let mut roots = rustls::RootCertStore::empty();
+        for ca in load_client_ca(ca_path)? {
+            roots.add(ca)?;
+        }
+        let verifier = rustls::server::WebPkiClientVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(|e| TlsError::Rustls(rustls::Error::General(e.to_string())))?;
+        builder
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(certs, key)?
+    } else {
+        builder.with_no_client_auth().with_single_cert(certs, key)?
+    };
 
     Ok(config)
 }
 
+/// Parse every certificate in a PEM bundle.
+fn parse_certs(pem: &str) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    rustls_pemfile::certs(&mut pem.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(TlsError::Io)
+}
+
+/// Extract `notAfter` from a DER-encoded X.509 certificate.
+fn cert_not_after(cert: &[u8]) -> Option<time::OffsetDateTime> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert).ok()?;
+    Some(cert.validity().not_after.to_datetime())
+}
+
 /// Fallback self-signed certs directory (~/.han/certs/).
 fn get_fallback_certs_dir() -> Result<PathBuf, TlsError> {
     let home = dirs::home_dir().ok_or(TlsError::NoHomeDir)?;
@@ -163,7 +271,104 @@ mod tests {
     fn test_build_tls_config() {
         let _ = rustls::crypto::ring::default_provider().install_default();
         let pair = generate_self_signed().unwrap();
-        let config = build_tls_config(&pair).unwrap();
+        let config = build_tls_config(&pair, None).unwrap();
         assert!(config.alpn_protocols.is_empty() || true);
     }
+
+    #[test]
+    fn test_build_tls_config_client_ca() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let dir = tempfile::tempdir().unwrap();
+        let pair = generate_self_signed().unwrap();
+        let ca = generate_self_signed().unwrap();
+        let ca_path = dir.path().join("client-ca.pem");
+        fs::write(&ca_path, &ca.cert_pem).unwrap();
+        assert!(build_tls_config(&pair, Some(&ca_path)).is_ok());
+
+        // A CA file without certificates must not silently disable mTLS
+        fs::write(&ca_path, "not a certificate").unwrap();
+        assert!(matches!(
+            build_tls_config(&pair, Some(&ca_path)),
+            Err(TlsError::InvalidCertificate { .. })
+        ));
+    }
+
+    fn write_pair(dir: &Path, pair: &CertPair) -> (PathBuf, PathBuf) {
+        let cert_path = dir.join("cert.pem");
+        let key_path = dir.join("key.pem");
+        fs::write(&cert_path, &pair.cert_pem).unwrap();
+        fs::write(&key_path, &pair.key_pem).unwrap();
+        (cert_path, key_path)
+    }
+
+    #[test]
+    fn test_load_custom_certs() {
+        let dir = tempfile::tempdir().unwrap();
+        let pair = generate_self_signed().unwrap();
+        let (cert_path, key_path) = write_pair(dir.path(), &pair);
+
+        let loaded = load_or_generate_certs(Some(&cert_path), Some(&key_path)).unwrap();
+        assert_eq!(loaded.cert_pem, pair.cert_pem);
+        assert_eq!(loaded.key_pem, pair.key_pem);
+    }
+
+    #[test]
+    fn test_load_requires_both_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let pair = generate_self_signed().unwrap();
+        let (cert_path, key_path) = write_pair(dir.path(), &pair);
+
+        assert!(matches!(
+            load_or_generate_certs(Some(&cert_path), None),
+            Err(TlsError::IncompletePaths)
+        ));
+        assert!(matches!(
+            load_or_generate_certs(None, Some(&key_path)),
+            Err(TlsError::IncompletePaths)
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_expired_cert() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.not_before = time::OffsetDateTime::now_utc() - time::Duration::days(30);
+        params.not_after = time::OffsetDateTime::now_utc() - time::Duration::days(1);
+        let key_pair = KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key_pair).unwrap();
+        let pair = CertPair {
+            cert_pem: cert.pem(),
+            key_pem: key_pair.serialize_pem(),
+        };
+        let (cert_path, key_path) = write_pair(dir.path(), &pair);
+
+        assert!(matches!(
+            load_or_generate_certs(Some(&cert_path), Some(&key_path)),
+            Err(TlsError::Expired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_pem() {
+        let dir = tempfile::tempdir().unwrap();
+        let pair = CertPair {
+            cert_pem: "not a certificate".to_string(),
+            key_pem: generate_self_signed().unwrap().key_pem,
+        };
+        let (cert_path, key_path) = write_pair(dir.path(), &pair);
+
+        assert!(matches!(
+            load_or_generate_certs(Some(&cert_path), Some(&key_path)),
+            Err(TlsError::InvalidCertificate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cert_not_after() {
+        let pair = generate_self_signed().unwrap();
+        let certs = parse_certs(&pair.cert_pem).unwrap();
+        let not_after = cert_not_after(&certs[0]).unwrap();
+        let expected = time::OffsetDateTime::now_utc() + time::Duration::days(365);
+        assert!((expected - not_after).abs() < time::Duration::minutes(5));
+    }
 }
@@ -4,67 +4,79 @@
 //! content via the processor, and emits `DbChangeEvent`s for GraphQL subscriptions.
 
 use han_api::context::DbChangeEvent;
-use han_db::entities::{projects, sessions};
-use han_indexer::{WatcherService, handle_file_event};
+use han_api::types::enums::IndexProgressStatus;
+use han_db::crud::agent_sessions;
+use han_db::entities::{messages, projects, sessions};
+use han_indexer::{
+    IgnorePatterns, IndexProgress, IndexResult, IndexStatus, WatcherHandle, WatcherService,
+    handle_file_event,
+};
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
-use tokio::sync::broadcast;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// Minimum time between frustration alerts for one session.
+const FRUSTRATION_ALERT_WINDOW: Duration = Duration::from_secs(30);
 
 /// Start the watcher bridge in a background task.
 ///
 /// Watches `~/.claude/projects` by default plus any additional config
 /// directories registered in the database (e.g. `~/.claude-work/projects`).
 ///
-/// Returns a handle that can be used to abort the bridge.
-pub fn start_watcher_bridge(
+/// Only files `ignore` lets through are indexed.
+///
+/// Returns a handle that can be used to abort the bridge, and a handle for
+/// changing the watch set while it runs. Returns None if the watcher could
+/// not be started.
+pub async fn start_watcher_bridge(
     db: DatabaseConnection,
     event_tx: broadcast::Sender<DbChangeEvent>,
-) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        // Query registered config directories from the database so we can
-        // watch all of them, not just the default ~/.claude/projects.
-        let extra_config_dirs: Vec<String> = match han_db::crud::config_dirs::list(&db).await {
-            Ok(dirs) => dirs.into_iter().map(|d| d.path).collect(),
-            Err(e) => {
-                tracing::warn!("Failed to query config dirs: {}", e);
-                vec![]
-            }
-        };
+    ignore: IgnorePatterns,
+) -> Option<(tokio::task::JoinHandle<()>, WatcherHandle)> {
+    // Query registered config directories from the database so we can
+    // watch all of them, not just the default ~/.claude/projects.
+    let extra_config_dirs: Vec<String> = match han_db::crud::config_dirs::list(&db).await {
+        Ok(dirs) => dirs.into_iter().map(|d| d.path).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to query config dirs: {}", e);
+            vec![]
+        }
+    };
 
-        // WatcherService::new is synchronous and may block while setting up
-        // OS-level file watchers. Use spawn_blocking to avoid stalling the
-        // tokio runtime.
-        let watcher = match tokio::task::spawn_blocking(|| {
-            WatcherService::new(None)
-        }).await {
-            Ok(Ok(w)) => {
-                tracing::info!(
-                    "Watcher bridge started, watching: {:?}",
-                    w.watched_paths()
-                );
-                w
-            }
-            Ok(Err(e)) => {
-                tracing::error!("Failed to start file watcher: {}", e);
-                return;
-            }
-            Err(e) => {
-                tracing::error!("Watcher initialization task panicked: {}", e);
-                return;
-            }
-        };
+    // WatcherService::new is synchronous and may block while setting up
+    // OS-level file watchers. Use spawn_blocking to avoid stalling the
+    // tokio runtime.
+    let mut watcher = match tokio::task::spawn_blocking(|| {
+        WatcherService::with_ignore_patterns(None, ignore)
+    }).await {
+        Ok(Ok(w)) => {
+            tracing::info!(
+                "Watcher bridge started, watching: {:?}",
+                w.watched_paths()
+            );
+            w
+        }
+        Ok(Err(e)) => {
+            tracing::error!("Failed to start file watcher: {}", e);
+            return None;
+        }
+        Err(e) => {
+            tracing::error!("Watcher initialization task panicked: {}", e);
+            return None;
+        }
+    };
 
-        // Add watch paths for additional config directories
-        add_extra_watch_paths(watcher, extra_config_dirs, db, event_tx).await;
-    })
+    // Add watch paths for additional config directories
+    add_extra_watch_paths(&mut watcher, extra_config_dirs);
+
+    let handle = watcher.handle();
+    let task = tokio::spawn(run_watcher_loop(watcher, db, event_tx));
+    Some((task, handle))
 }
 
-/// Register additional config directory watch paths, then start the watcher loop.
-async fn add_extra_watch_paths(
-    mut watcher: WatcherService,
-    extra_config_dirs: Vec<String>,
-    db: DatabaseConnection,
-    event_tx: broadcast::Sender<DbChangeEvent>,
-) {
+/// Register additional config directory watch paths.
+fn add_extra_watch_paths(watcher: &mut WatcherService, extra_config_dirs: Vec<String>) {
     let default_claude = dirs::home_dir()
         .map(|h| h.join(".claude").to_string_lossy().to_string())
         .unwrap_or_default();
@@ -90,7 +102,6 @@ async fn add_extra_watch_paths(
     }
 
     tracing::info!("Watching {} paths total: {:?}", watcher.watched_paths().len(), watcher.watched_paths());
-    run_watcher_loop(watcher, db, event_tx).await;
 }
 
 /// Run the main watcher loop: receive file events, index, emit subscription events.
@@ -99,6 +110,7 @@ async fn run_watcher_loop(
     db: DatabaseConnection,
     event_tx: broadcast::Sender<DbChangeEvent>,
 ) {
+    let mut frustration_alerts = FrustrationAlerts::default();
     while let Some(file_event) = watcher.next_event().await {
         tracing::debug!(
             "File event: {:?} {}",
@@ -116,6 +128,7 @@ async fn run_watcher_loop(
 
         match result {
             Ok(Some(index_result)) => {
+                crate::telemetry::record_session_indexed(index_result.messages_indexed);
                 tracing::info!(
                     "Indexed {} messages for session {}",
                     index_result.messages_indexed,
@@ -134,24 +147,41 @@ async fn run_watcher_loop(
 
                 // Emit subscription events
                 if index_result.is_new_session {
+                    // Sub-agent sessions carry the session that launched them
+                    let parent_id = agent_sessions::get_parent(&db, &index_result.session_id)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|link| link.parent_session_id);
                     let _ = event_tx.send(DbChangeEvent::SessionAdded {
                         session_id: index_result.session_id.clone(),
-                        parent_id: None,
+                        parent_id,
                         project_id: project_id.clone(),
                     });
                 }
 
+                let _ = event_tx.send(DbChangeEvent::SessionIndexed {
+                    session_id: index_result.session_id.clone(),
+                });
+
                 if index_result.messages_indexed > 0 {
                     let _ = event_tx.send(DbChangeEvent::SessionMessageAdded {
                         session_id: index_result.session_id.clone(),
                         message_index: index_result.total_messages as i32,
                     });
 
+                    let _ = event_tx.send(DbChangeEvent::SessionUpdated {
+                        session_id: index_result.session_id.clone(),
+                    });
+
                     let _ = event_tx.send(DbChangeEvent::NodeUpdated {
                         id: global_id,
                         typename: "SessionData".to_string(),
                     });
                 }
+
+                emit_frustration_alert(&db, &event_tx, &mut frustration_alerts, &index_result)
+                    .await;
             }
             Ok(None) => {}
             Err(e) => {
@@ -163,6 +193,63 @@ async fn run_watcher_loop(
     tracing::info!("Watcher bridge stopped");
 }
 
+/// Last alert time per session, so a flurry of frustrated messages raises a
+/// single alert.
+#[derive(Debug, Default)]
+struct FrustrationAlerts {
+    last_alerted: HashMap<String, Instant>,
+}
+
+impl FrustrationAlerts {
+    /// Whether `session_id` may alert at `now`. Sessions whose last alert is
+    /// outside [`FRUSTRATION_ALERT_WINDOW`] are forgotten.
+    fn can_alert(&mut self, session_id: &str, now: Instant) -> bool {
+        self.last_alerted
+            .retain(|_, last| now.duration_since(*last) < FRUSTRATION_ALERT_WINDOW);
+        !self.last_alerted.contains_key(session_id)
+    }
+
+    /// Record that `session_id` alerted at `now`.
+    fn record(&mut self, session_id: &str, now: Instant) {
+        self.last_alerted.insert(session_id.to_string(), now);
+    }
+}
+
+/// Alert on the latest highly frustrated message of an index pass, unless its
+/// session alerted within [`FRUSTRATION_ALERT_WINDOW`].
+async fn emit_frustration_alert(
+    db: &DatabaseConnection,
+    event_tx: &broadcast::Sender<DbChangeEvent>,
+    alerts: &mut FrustrationAlerts,
+    index_result: &IndexResult,
+) {
+    let Some(message_id) = index_result.frustrated_messages.last() else {
+        return;
+    };
+    let now = Instant::now();
+    if !alerts.can_alert(&index_result.session_id, now) {
+        return;
+    }
+    let message = messages::Entity::find_by_id(message_id.as_str()).one(db);
+    let message = match message.await {
+        Ok(Some(message)) => message,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to load frustrated message {}: {}", message_id, e);
+            return;
+        }
+    };
+    alerts.record(&index_result.session_id, now);
+    let _ = event_tx.send(DbChangeEvent::FrustrationAlert {
+        session_id: index_result.session_id.clone(),
+        message_id: message.id,
+        score: message.frustration_score.unwrap_or_default(),
+        level: message.frustration_level.unwrap_or_default(),
+        content: message.content.unwrap_or_default(),
+        timestamp: message.timestamp,
+    });
+}
+
 /// Look up the project_dir and project_id for a session from the database.
 /// Returns (project_dir, project_id). Both may be empty/None if not found.
 async fn lookup_session_project(db: &DatabaseConnection, session_id: &str) -> (String, Option<String>) {
@@ -188,9 +275,41 @@ async fn lookup_session_project(db: &DatabaseConnection, session_id: &str) -> (S
     (project_dir, project_id)
 }
 
+/// Convert an indexer progress update into a subscription event.
+pub fn index_progress_event(progress: IndexProgress) -> DbChangeEvent {
+    let status = match progress.status {
+        IndexStatus::Pending => IndexProgressStatus::Pending,
+        IndexStatus::InProgress => IndexProgressStatus::InProgress,
+        IndexStatus::Done => IndexProgressStatus::Done,
+        IndexStatus::Error => IndexProgressStatus::Error,
+    };
+    DbChangeEvent::IndexProgress {
+        session_id: progress.session_id,
+        file_path: progress.file_path,
+        status,
+        messages_indexed: progress.messages_indexed as i32,
+    }
+}
+
+/// Spawn a task relaying index progress to GraphQL subscribers.
+///
+/// The task ends once every clone of the returned sender is dropped.
+pub fn forward_index_progress(
+    event_tx: broadcast::Sender<DbChangeEvent>,
+) -> mpsc::Sender<IndexProgress> {
+    let (progress_tx, mut progress_rx) = mpsc::channel(256);
+    tokio::spawn(async move {
+        while let Some(progress) = progress_rx.recv().await {
+            let _ = event_tx.send(index_progress_event(progress));
+        }
+    });
+    progress_tx
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::future::Future;
 
     /// Verify that the module compiles and key types are accessible.
     /// The watcher bridge is heavily async and depends on real file system
@@ -198,10 +317,13 @@ mod tests {
     #[test]
     fn test_start_watcher_bridge_compiles() {
         // Verify the function signature is correct by referencing it
-        let _fn_ptr: fn(
-            DatabaseConnection,
-            broadcast::Sender<DbChangeEvent>,
-        ) -> tokio::task::JoinHandle<()> = start_watcher_bridge;
+        fn returns_handles<S, F>(_start: S)
+        where
+            S: Fn(DatabaseConnection, broadcast::Sender<DbChangeEvent>, IgnorePatterns) -> F,
+            F: Future<Output = Option<(tokio::task::JoinHandle<()>, WatcherHandle)>>,
+        {
+        }
+        returns_handles(start_watcher_bridge);
     }
 
     #[test]
@@ -245,4 +367,123 @@ mod tests {
             _ => panic!("Expected NodeUpdated variant"),
         }
     }
+
+    #[test]
+    fn test_frustration_alerts_dedupe_per_session() {
+        let mut alerts = FrustrationAlerts::default();
+        let start = Instant::now();
+
+        assert!(alerts.can_alert("s1", start));
+        alerts.record("s1", start);
+        assert!(!alerts.can_alert("s1", start + Duration::from_secs(10)));
+        assert!(alerts.can_alert("s2", start + Duration::from_secs(10)));
+        assert!(alerts.can_alert("s1", start + FRUSTRATION_ALERT_WINDOW));
+        // Expired sessions are pruned
+        assert!(alerts.last_alerted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_frustration_alert_not_recorded_when_message_missing() {
+        let db = crate::test_db().await;
+        let (event_tx, mut event_rx) = broadcast::channel(16);
+        let index_result = IndexResult {
+            session_id: "s1".to_string(),
+            messages_indexed: 1,
+            total_messages: 1,
+            is_new_session: false,
+            error: None,
+            errors: vec![],
+            error_count: 0,
+            warning_count: 0,
+            warnings: vec![],
+            frustrated_messages: vec!["missing-message".to_string()],
+        };
+
+        let mut alerts = FrustrationAlerts::default();
+        emit_frustration_alert(&db, &event_tx, &mut alerts, &index_result).await;
+
+        assert!(event_rx.try_recv().is_err());
+        assert!(alerts.can_alert("s1", Instant::now()));
+    }
+
+    #[tokio::test]
+    async fn test_frustration_alert_subscription_fires() {
+        use futures::StreamExt;
+
+        let db = crate::test_db().await;
+        let (event_tx, _) = broadcast::channel(16);
+        let schema = han_api::build_schema(db.clone(), event_tx.clone());
+
+        let session_id = "3b8d1f6a-2c4e-4a7b-9d1e-5f3a7c9b2d4e";
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-frustrated",
+            "sessionId": session_id,
+            "timestamp": "2026-04-20T10:00:00Z",
+            "message": {"role": "user", "content": "STOP. THIS IS TERRIBLE AND BROKEN!! forget it"}
+        });
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+
+        let mut stream = schema.execute_stream(format!(
+            r#"subscription {{ frustrationAlert(sessionId: "{session_id}") {{
+                messageId frustrationLevel content timestamp
+            }} }}"#
+        ));
+        let alert = tokio::spawn(async move { stream.next().await });
+        // The subscription listens once its stream has been polled
+        while event_tx.receiver_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let index_result = han_indexer::index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        let mut alerts = FrustrationAlerts::default();
+        emit_frustration_alert(&db, &event_tx, &mut alerts, &index_result).await;
+
+        let response = tokio::time::timeout(Duration::from_secs(2), alert)
+            .await
+            .expect("alert should fire within 2 seconds")
+            .unwrap()
+            .expect("subscription stream ended");
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let alert = &data["frustrationAlert"];
+        assert_eq!(alert["messageId"], "msg-frustrated");
+        assert_eq!(alert["frustrationLevel"], "high");
+        assert_eq!(alert["timestamp"], "2026-04-20T10:00:00Z");
+        assert!(alert["content"].as_str().unwrap().contains("TERRIBLE"));
+    }
+
+    #[tokio::test]
+    async fn test_forward_index_progress() {
+        let (event_tx, mut event_rx) = broadcast::channel(16);
+        let progress_tx = forward_index_progress(event_tx);
+
+        progress_tx
+            .send(IndexProgress {
+                session_id: "test-session".to_string(),
+                file_path: "/tmp/test-session.jsonl".to_string(),
+                status: IndexStatus::Done,
+                messages_indexed: 7,
+            })
+            .await
+            .unwrap();
+
+        match event_rx.recv().await.unwrap() {
+            DbChangeEvent::IndexProgress {
+                session_id,
+                status,
+                messages_indexed,
+                ..
+            } => {
+                assert_eq!(session_id, "test-session");
+                assert_eq!(status, IndexProgressStatus::Done);
+                assert_eq!(messages_indexed, 7);
+            }
+            _ => panic!("Expected IndexProgress variant"),
+        }
+    }
 }
@@ -3,8 +3,10 @@
 //! These use raw SQL since they are too complex for the SeaORM query builder.
 
 use crate::error::{DbError, DbResult};
+use chrono::{Datelike, Duration, NaiveDate};
 use sea_orm::{ConnectionTrait, DatabaseConnection, Statement, Value};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ToolUsageRow {
@@ -62,6 +64,86 @@ pub struct SessionCompactionRow {
     pub compaction_count: i64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpServerUsageRow {
+    pub server_name: String,
+    pub tool_name: String,
+    pub call_count: i64,
+    pub success_count: i64,
+    pub error_count: i64,
+    pub total_duration_ms: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolCallStatsRow {
+    pub tool_name: String,
+    pub call_count: i64,
+    pub success_count: i64,
+    pub error_count: i64,
+    pub total_duration_ms: i64,
+    /// Calls with a recorded result, i.e. those counted in the duration.
+    pub timed_count: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HookExecutionGroupRow {
+    pub plugin_name: String,
+    pub hook_type: String,
+    pub hook_name: String,
+    pub total: i64,
+    pub passed: i64,
+    pub total_duration_ms: i64,
+    /// Error of the most recent failed run, if any run failed.
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HookExecutionStatsRows {
+    /// One row per plugin, hook type and hook name, busiest first.
+    pub groups: Vec<HookExecutionGroupRow>,
+    pub p50_duration_ms: i64,
+    pub p95_duration_ms: i64,
+}
+
+/// Hook executions run by one plugin.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HookPluginRow {
+    pub plugin_name: String,
+    pub execution_count: i64,
+    pub pass_count: i64,
+    pub total_duration_ms: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModelTokenTotals {
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TokenTotals {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    /// The same sums split by `message.model`, for per-model pricing.
+    pub by_model: Vec<ModelTokenTotals>,
+}
+
+/// Task counts by status for one session.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionTaskCounts {
+    pub active: i64,
+    pub completed: i64,
+    pub failed: i64,
+    /// Hours from start to completion of completed tasks, each weighted by
+    /// its confidence (1.0 when unset).
+    pub completed_hours: f64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DashboardAggregates {
     pub tool_usage: Vec<ToolUsageRow>,
@@ -111,6 +193,40 @@ pub struct ActivityAggregates {
     pub total_sessions: i64,
 }
 
+/// Activity for one Monday-to-Sunday week.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WeeklySummary {
+    /// Monday of the week (YYYY-MM-DD).
+    pub week_start: String,
+    /// ISO 8601 week number.
+    pub week_number: u32,
+    /// ISO 8601 week-numbering year, which differs from the calendar year
+    /// for some weeks around New Year.
+    pub year: i32,
+    pub session_count: i32,
+    pub message_count: i32,
+    pub total_tokens: i64,
+    pub unique_tools: i32,
+    /// Fraction of hook runs that passed, 0 when no hooks ran.
+    pub hook_pass_rate: f64,
+}
+
+impl WeeklySummary {
+    fn empty(monday: NaiveDate) -> Self {
+        let week = monday.iso_week();
+        Self {
+            week_start: monday.format("%Y-%m-%d").to_string(),
+            week_number: week.week(),
+            year: week.year(),
+            session_count: 0,
+            message_count: 0,
+            total_tokens: 0,
+            unique_tools: 0,
+            hook_pass_rate: 0.0,
+        }
+    }
+}
+
 /// Query dashboard aggregates using raw SQL.
 pub async fn query_dashboard_aggregates(
     db: &DatabaseConnection,
@@ -266,3 +382,693 @@ pub async fn query_activity_aggregates(
         total_sessions,
     })
 }
+
+/// Activity for the last `weeks` complete weeks, oldest first.
+///
+/// See [`weekly_summary_before`].
+pub async fn weekly_summary(
+    db: &DatabaseConnection,
+    project_dir: Option<&str>,
+    weeks: u32,
+) -> DbResult<Vec<WeeklySummary>> {
+    weekly_summary_before(db, project_dir, weeks, chrono::Utc::now().date_naive()).await
+}
+
+/// Activity for the `weeks` complete weeks before the one containing
+/// `today`, oldest first. Weeks without sessions are included with zeros.
+///
+/// Each session and everything in it counts toward the week of its first
+/// message. Tokens and tools follow the project stats: tokens are summed
+/// over assistant messages and tools are distinct `tool_use` names.
+/// `project_dir` matches the project path exactly or as a parent directory.
+///
+/// Only SQLite is supported: the query relies on SQLite date modifiers and
+/// `?` placeholders.
+pub async fn weekly_summary_before(
+    db: &DatabaseConnection,
+    project_dir: Option<&str>,
+    weeks: u32,
+    today: NaiveDate,
+) -> DbResult<Vec<WeeklySummary>> {
+    let current_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let first_week = current_week - Duration::weeks(weeks as i64);
+
+    let backend = db.get_database_backend();
+    // Grouping by Monday keeps weeks that span New Year together
+    let week_expr = "date(started, '-6 days', 'weekday 1')";
+    let mut project_filter = String::new();
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(dir) = project_dir {
+        let dir = dir.trim_end_matches('/');
+        project_filter.push_str(
+            " AND s.project_id IN (SELECT id FROM projects \
+             WHERE path = ? OR substr(path, 1, length(?) + 1) = ? || '/')",
+        );
+        values.extend([dir.into(), dir.into(), dir.into()]);
+    }
+    values.push(first_week.format("%Y-%m-%d").to_string().into());
+    values.push(current_week.format("%Y-%m-%d").to_string().into());
+
+    let sql = format!(
+        "WITH span AS (SELECT s.id, MIN(m.timestamp) AS started FROM sessions s \
+         JOIN messages m ON m.session_id = s.id WHERE 1 = 1{project_filter} GROUP BY s.id), \
+         ws AS (SELECT id, {week_expr} AS week_start FROM span WHERE started >= ? AND started < ?), \
+         wm AS (SELECT ws.week_start, m.* FROM messages m JOIN ws ON ws.id = m.session_id), \
+         wh AS (SELECT ws.week_start, h.passed FROM hook_executions h JOIN ws ON ws.id = h.session_id) \
+         SELECT ws.week_start AS week_start, COUNT(*) AS sessions, \
+           (SELECT COUNT(*) FROM wm WHERE wm.week_start = ws.week_start) AS messages, \
+           (SELECT COALESCE(SUM(COALESCE(input_tokens, 0) + COALESCE(output_tokens, 0) \
+              + COALESCE(cache_read_tokens, 0) + COALESCE(cache_creation_tokens, 0)), 0) \
+              FROM wm WHERE wm.week_start = ws.week_start AND message_type = 'assistant') AS tokens, \
+           (SELECT COUNT(DISTINCT tool_name) FROM wm \
+              WHERE wm.week_start = ws.week_start AND message_type = 'tool_use') AS tools, \
+           (SELECT COUNT(*) FROM wh WHERE wh.week_start = ws.week_start) AS hook_runs, \
+           (SELECT COALESCE(SUM(CASE WHEN passed != 0 THEN 1 ELSE 0 END), 0) FROM wh \
+              WHERE wh.week_start = ws.week_start) AS hook_passes \
+         FROM ws GROUP BY ws.week_start"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+
+    let mut by_week: HashMap<String, WeeklySummary> = HashMap::new();
+    for r in &rows {
+        let Ok(week_start) = r.try_get::<String>("", "week_start") else {
+            continue;
+        };
+        let Ok(monday) = NaiveDate::parse_from_str(&week_start, "%Y-%m-%d") else {
+            continue;
+        };
+        let hook_runs = r.try_get::<i64>("", "hook_runs").unwrap_or(0);
+        let hook_passes = r.try_get::<i64>("", "hook_passes").unwrap_or(0);
+        by_week.insert(
+            week_start,
+            WeeklySummary {
+                session_count: r.try_get::<i64>("", "sessions").unwrap_or(0) as i32,
+                message_count: r.try_get::<i64>("", "messages").unwrap_or(0) as i32,
+                total_tokens: r.try_get::<i64>("", "tokens").unwrap_or(0),
+                unique_tools: r.try_get::<i64>("", "tools").unwrap_or(0) as i32,
+                hook_pass_rate: if hook_runs > 0 {
+                    hook_passes as f64 / hook_runs as f64
+                } else {
+                    0.0
+                },
+                ..WeeklySummary::empty(monday)
+            },
+        );
+    }
+
+    Ok((0..weeks as i64)
+        .map(|i| {
+            let monday = first_week + Duration::weeks(i);
+            by_week
+                .remove(&monday.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| WeeklySummary::empty(monday))
+        })
+        .collect())
+}
+
+/// Query MCP tool usage grouped by server and tool.
+///
+/// Calls are counted from `mcp_tool_call` events; success, error and duration
+/// totals come from the matching `mcp_tool_result` events. Pass a `session_id`
+/// to scope to one session, or `None` to aggregate across all sessions.
+/// Rows are ordered by call count, busiest first.
+pub async fn query_mcp_server_usage(
+    db: &DatabaseConnection,
+    session_id: Option<&str>,
+    limit: Option<u64>,
+) -> DbResult<Vec<McpServerUsageRow>> {
+    let backend = db.get_database_backend();
+    let mut sql = String::from(
+        "SELECT COALESCE(json_extract(raw_json, '$.data.server_name'), json_extract(raw_json, '$.data.server'), 'unknown') as server_name, \
+         COALESCE(json_extract(raw_json, '$.data.tool'), 'unknown') as tool, \
+         SUM(CASE WHEN tool_name = 'mcp_tool_call' THEN 1 ELSE 0 END) as calls, \
+         SUM(CASE WHEN tool_name = 'mcp_tool_result' AND json_extract(raw_json, '$.data.success') = 1 THEN 1 ELSE 0 END) as successes, \
+         SUM(CASE WHEN tool_name = 'mcp_tool_result' AND COALESCE(json_extract(raw_json, '$.data.success'), 0) != 1 THEN 1 ELSE 0 END) as errors, \
+         CAST(COALESCE(SUM(CASE WHEN tool_name = 'mcp_tool_result' THEN json_extract(raw_json, '$.data.duration_ms') END), 0) AS INTEGER) as duration \
+         FROM messages WHERE tool_name IN ('mcp_tool_call', 'mcp_tool_result')",
+    );
+    let mut values = Vec::new();
+    if let Some(session_id) = session_id {
+        sql.push_str(" AND session_id = ?");
+        values.push(Value::String(Some(Box::new(session_id.to_string()))));
+    }
+    sql.push_str(" GROUP BY server_name, tool ORDER BY calls DESC, server_name, tool");
+    if let Some(limit) = limit {
+        sql.push_str(" LIMIT ?");
+        values.push(Value::BigUnsigned(Some(limit)));
+    }
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            Some(McpServerUsageRow {
+                server_name: r.try_get::<String>("", "server_name").ok()?,
+                tool_name: r.try_get::<String>("", "tool").ok()?,
+                call_count: r.try_get::<i64>("", "calls").ok()?,
+                success_count: r.try_get::<i64>("", "successes").ok()?,
+                error_count: r.try_get::<i64>("", "errors").ok()?,
+                total_duration_ms: r.try_get::<i64>("", "duration").ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Sum token usage reported by MCP servers in one session, split by model.
+///
+/// Only `mcp_tool_result` events carrying `data.usage` are counted; the model
+/// comes from `data.model` and is `None` when the server did not report one.
+/// MCP servers report no cache usage, so cache totals are always zero.
+pub async fn query_mcp_token_totals(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Vec<ModelTokenTotals>> {
+    let sql = "SELECT json_extract(raw_json, '$.data.model') as model, \
+         CAST(COALESCE(SUM(json_extract(raw_json, '$.data.usage.input_tokens')), 0) AS INTEGER) as it, \
+         CAST(COALESCE(SUM(json_extract(raw_json, '$.data.usage.output_tokens')), 0) AS INTEGER) as ot \
+         FROM messages WHERE session_id = ? AND tool_name = 'mcp_tool_result' \
+         AND json_extract(raw_json, '$.data.usage') IS NOT NULL \
+         GROUP BY model ORDER BY model";
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            vec![session_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .map(|r| ModelTokenTotals {
+            model: r.try_get::<Option<String>>("", "model").ok().flatten(),
+            input_tokens: r.try_get::<i64>("", "it").unwrap_or(0),
+            output_tokens: r.try_get::<i64>("", "ot").unwrap_or(0),
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+        })
+        .collect())
+}
+
+/// Query tool calls grouped by tool name.
+///
+/// Calls are `tool_use` message rows. Each is matched to its result in
+/// `tool_call_results`; calls without a result count as neither success nor
+/// error. Duration runs from the call's timestamp to that of the message
+/// carrying its result. Tool names group case-sensitively. `since` is
+/// inclusive and compared against the call's timestamp. Rows are ordered by
+/// call count, busiest first.
+pub async fn query_tool_call_stats(
+    db: &DatabaseConnection,
+    session_id: Option<&str>,
+    since: Option<&str>,
+) -> DbResult<Vec<ToolCallStatsRow>> {
+    let backend = db.get_database_backend();
+    let mut sql = String::from(
+        "SELECT m.tool_name as tool, COUNT(*) as calls, \
+         SUM(CASE WHEN r.is_error = 0 THEN 1 ELSE 0 END) as successes, \
+         SUM(CASE WHEN r.is_error = 1 THEN 1 ELSE 0 END) as errors, \
+         CAST(ROUND(COALESCE(SUM(MAX(julianday(rm.timestamp) - julianday(m.timestamp), 0) * 86400000.0), 0)) AS INTEGER) as duration, \
+         COUNT(rm.id) as timed \
+         FROM messages m \
+         LEFT JOIN tool_call_results r ON r.tool_call_id = m.id \
+         LEFT JOIN messages rm ON rm.id = r.message_id \
+         WHERE m.message_type = 'tool_use' AND m.tool_name IS NOT NULL",
+    );
+    let mut values = Vec::new();
+    if let Some(session_id) = session_id {
+        sql.push_str(" AND m.session_id = ?");
+        values.push(Value::String(Some(Box::new(session_id.to_string()))));
+    }
+    if let Some(since) = since {
+        sql.push_str(" AND m.timestamp >= ?");
+        values.push(Value::String(Some(Box::new(since.to_string()))));
+    }
+    sql.push_str(" GROUP BY m.tool_name ORDER BY calls DESC, m.tool_name");
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            Some(ToolCallStatsRow {
+                tool_name: r.try_get::<String>("", "tool").ok()?,
+                call_count: r.try_get::<i64>("", "calls").ok()?,
+                success_count: r.try_get::<i64>("", "successes").ok()?,
+                error_count: r.try_get::<i64>("", "errors").ok()?,
+                total_duration_ms: r.try_get::<i64>("", "duration").ok()?,
+                timed_count: r.try_get::<i64>("", "timed").ok()?,
+            })
+        })
+        .collect())
+}
+
+/// SQL expression for a message's model ID: `message.model` in transcript
+/// lines, or a top-level `model` on bare message objects.
+pub const MESSAGE_MODEL_SQL: &str =
+    "COALESCE(json_extract(raw_json, '$.message.model'), json_extract(raw_json, '$.model'))";
+
+/// Count a session's assistant messages per model ID, most used first.
+/// Messages without a model are skipped.
+pub async fn query_session_model_counts(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Vec<(String, i64)>> {
+    let sql = format!(
+        "SELECT {MESSAGE_MODEL_SQL} as model, COUNT(*) as n FROM messages \
+         WHERE session_id = ? AND message_type = 'assistant' AND {MESSAGE_MODEL_SQL} IS NOT NULL \
+         GROUP BY model ORDER BY n DESC, model"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            vec![session_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            let model = r.try_get::<String>("", "model").ok()?;
+            Some((model, r.try_get::<i64>("", "n").ok()?))
+        })
+        .collect())
+}
+
+/// Aggregate hook executions by plugin, hook type and hook name.
+///
+/// `project_dir` matches executions run in that directory or below it.
+/// `since` is inclusive and `until` exclusive, both compared against
+/// `executed_at`. Executions without a `hook_source` are grouped under the
+/// "unknown" plugin. Percentiles interpolate over the sorted durations the
+/// same way `PERCENTILE_CONT` does.
+pub async fn query_hook_execution_stats(
+    db: &DatabaseConnection,
+    project_dir: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> DbResult<HookExecutionStatsRows> {
+    let backend = db.get_database_backend();
+    let mut filter = String::from("1 = 1");
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(dir) = project_dir {
+        let dir = dir.trim_end_matches('/');
+        filter.push_str(" AND (directory = ? OR substr(directory, 1, length(?) + 1) = ? || '/')");
+        values.extend([dir.into(), dir.into(), dir.into()]);
+    }
+    if let Some(since) = since {
+        filter.push_str(" AND executed_at >= ?");
+        values.push(since.into());
+    }
+    if let Some(until) = until {
+        filter.push_str(" AND executed_at < ?");
+        values.push(until.into());
+    }
+
+    let group_sql = format!(
+        "SELECT COALESCE(hook_source, 'unknown') as plugin_name, hook_type, hook_name, \
+         COUNT(*) as total, \
+         SUM(CASE WHEN passed != 0 THEN 1 ELSE 0 END) as passed, \
+         COALESCE(SUM(duration_ms), 0) as total_duration \
+         FROM hook_executions WHERE {filter} \
+         GROUP BY plugin_name, hook_type, hook_name \
+         ORDER BY total DESC, plugin_name, hook_type, hook_name"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            &group_sql,
+            values.clone(),
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    let mut groups: Vec<HookExecutionGroupRow> = rows
+        .iter()
+        .filter_map(|r| {
+            Some(HookExecutionGroupRow {
+                plugin_name: r.try_get::<String>("", "plugin_name").ok()?,
+                hook_type: r.try_get::<String>("", "hook_type").ok()?,
+                hook_name: r.try_get::<String>("", "hook_name").ok()?,
+                total: r.try_get::<i64>("", "total").ok()?,
+                passed: r.try_get::<i64>("", "passed").ok()?,
+                total_duration_ms: r.try_get::<i64>("", "total_duration").ok()?,
+                last_error: None,
+            })
+        })
+        .collect();
+
+    // Failures are rare, so the latest error per hook is picked in Rust
+    let failure_sql = format!(
+        "SELECT COALESCE(hook_source, 'unknown') as plugin_name, hook_type, hook_name, error \
+         FROM hook_executions WHERE {filter} AND passed = 0 ORDER BY executed_at"
+    );
+    let failures = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            &failure_sql,
+            values.clone(),
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    for r in &failures {
+        let plugin_name = r.try_get::<String>("", "plugin_name").unwrap_or_default();
+        let hook_type = r.try_get::<String>("", "hook_type").unwrap_or_default();
+        let hook_name = r.try_get::<String>("", "hook_name").unwrap_or_default();
+        if let Some(group) = groups.iter_mut().find(|g| {
+            g.plugin_name == plugin_name && g.hook_type == hook_type && g.hook_name == hook_name
+        }) {
+            group.last_error = r.try_get::<Option<String>>("", "error").ok().flatten();
+        }
+    }
+
+    let duration_sql =
+        format!("SELECT duration_ms FROM hook_executions WHERE {filter} ORDER BY duration_ms");
+    let durations: Vec<i64> = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            &duration_sql,
+            values,
+        ))
+        .await
+        .map_err(DbError::Database)?
+        .iter()
+        .filter_map(|r| r.try_get::<i64>("", "duration_ms").ok())
+        .collect();
+
+    Ok(HookExecutionStatsRows {
+        groups,
+        p50_duration_ms: percentile_cont(&durations, 0.5).round() as i64,
+        p95_duration_ms: percentile_cont(&durations, 0.95).round() as i64,
+    })
+}
+
+/// Percentile of ascending `sorted` values, interpolating between the two
+/// nearest ranks like SQL `PERCENTILE_CONT`. Returns 0 for no values.
+pub fn percentile_cont(sorted: &[i64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = fraction.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] as f64 + (sorted[upper] - sorted[lower]) as f64 * weight
+}
+
+/// Sum assistant message token usage for each of `session_ids` in one query.
+///
+/// Sessions without assistant messages are absent from the map.
+pub async fn session_token_totals(
+    db: &DatabaseConnection,
+    session_ids: &[String],
+) -> DbResult<HashMap<String, TokenTotals>> {
+    if session_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let backend = db.get_database_backend();
+    let placeholders = vec!["?"; session_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT session_id, {MESSAGE_MODEL_SQL} as model, \
+         COALESCE(SUM(input_tokens), 0) as it, COALESCE(SUM(output_tokens), 0) as ot, \
+         COALESCE(SUM(cache_read_tokens), 0) as crt, COALESCE(SUM(cache_creation_tokens), 0) as cct \
+         FROM messages WHERE session_id IN ({placeholders}) AND message_type = 'assistant' \
+         GROUP BY session_id, model ORDER BY session_id, model"
+    );
+    let values: Vec<Value> = session_ids
+        .iter()
+        .map(|id| Value::String(Some(Box::new(id.clone()))))
+        .collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+
+    let mut totals: HashMap<String, TokenTotals> = HashMap::new();
+    for r in &rows {
+        let Ok(session_id) = r.try_get::<String>("", "session_id") else {
+            continue;
+        };
+        let model = ModelTokenTotals {
+            model: r.try_get::<Option<String>>("", "model").ok().flatten(),
+            input_tokens: r.try_get::<i64>("", "it").unwrap_or(0),
+            output_tokens: r.try_get::<i64>("", "ot").unwrap_or(0),
+            cache_read_tokens: r.try_get::<i64>("", "crt").unwrap_or(0),
+            cache_creation_tokens: r.try_get::<i64>("", "cct").unwrap_or(0),
+        };
+        let entry = totals.entry(session_id).or_default();
+        entry.input_tokens += model.input_tokens;
+        entry.output_tokens += model.output_tokens;
+        entry.cache_read_tokens += model.cache_read_tokens;
+        entry.cache_creation_tokens += model.cache_creation_tokens;
+        entry.by_model.push(model);
+    }
+    Ok(totals)
+}
+
+/// Count tasks by status for each of `session_ids` in one query.
+///
+/// Tasks without `completed_at` are active. Completed tasks whose outcome is
+/// "failure" or "failed" count as failed, matching `Task.status`. Sessions
+/// without tasks are absent from the map.
+pub async fn session_task_counts(
+    db: &DatabaseConnection,
+    session_ids: &[String],
+) -> DbResult<HashMap<String, SessionTaskCounts>> {
+    if session_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let backend = db.get_database_backend();
+    let placeholders = vec!["?"; session_ids.len()].join(", ");
+    let done = "completed_at IS NOT NULL AND COALESCE(outcome, '') NOT IN ('failure', 'failed')";
+    let sql = format!(
+        "SELECT session_id, \
+         SUM(CASE WHEN completed_at IS NULL THEN 1 ELSE 0 END) as active, \
+         SUM(CASE WHEN {done} THEN 1 ELSE 0 END) as completed, \
+         SUM(CASE WHEN completed_at IS NOT NULL AND outcome IN ('failure', 'failed') THEN 1 ELSE 0 END) as failed, \
+         COALESCE(SUM(CASE WHEN {done} THEN COALESCE(confidence, 1.0) \
+           * MAX(julianday(completed_at) - julianday(started_at), 0) * 24.0 END), 0.0) as hours \
+         FROM tasks WHERE session_id IN ({placeholders}) GROUP BY session_id"
+    );
+    let values: Vec<Value> = session_ids
+        .iter()
+        .map(|id| Value::String(Some(Box::new(id.clone()))))
+        .collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            let session_id = r.try_get::<String>("", "session_id").ok()?;
+            let counts = SessionTaskCounts {
+                active: r.try_get::<i64>("", "active").unwrap_or(0),
+                completed: r.try_get::<i64>("", "completed").unwrap_or(0),
+                failed: r.try_get::<i64>("", "failed").unwrap_or(0),
+                completed_hours: r.try_get::<f64>("", "hours").unwrap_or(0.0),
+            };
+            Some((session_id, counts))
+        })
+        .collect())
+}
+
+/// Sessions bound per statement when scoring calibration, to stay well
+/// under SQLite's bound parameter limit.
+const CALIBRATION_BATCH_SIZE: usize = 500;
+
+/// Score how well task confidence predicted outcome across `session_ids`.
+///
+/// Completed tasks with a confidence and an outcome are scored by
+/// [`calibration_score`], counting outcome "success" as a success. Returns
+/// `None` when no task qualifies.
+pub async fn compute_calibration_score(
+    db: &DatabaseConnection,
+    session_ids: &[String],
+) -> DbResult<Option<f64>> {
+    let backend = db.get_database_backend();
+    let mut samples = Vec::new();
+    for batch in session_ids.chunks(CALIBRATION_BATCH_SIZE) {
+        let placeholders = vec!["?"; batch.len()].join(", ");
+        let sql = format!(
+            "SELECT confidence, outcome = 'success' as succeeded FROM tasks \
+             WHERE session_id IN ({placeholders}) AND completed_at IS NOT NULL \
+             AND confidence IS NOT NULL AND outcome IS NOT NULL"
+        );
+        let values: Vec<Value> = batch
+            .iter()
+            .map(|id| Value::String(Some(Box::new(id.clone()))))
+            .collect();
+        let rows = db
+            .query_all(Statement::from_sql_and_values(backend, &sql, values))
+            .await
+            .map_err(DbError::Database)?;
+        samples.extend(rows.iter().filter_map(|r| {
+            let confidence = r.try_get::<f64>("", "confidence").ok()?;
+            let succeeded = r.try_get::<bool>("", "succeeded").ok()?;
+            Some((confidence, succeeded))
+        }));
+    }
+    Ok(calibration_score(samples))
+}
+
+/// Calibration of `(confidence, succeeded)` samples, from 0 to 1.
+///
+/// Samples are bucketed into confidence deciles (1.0 joins the top one).
+/// The score is one minus the mean, over non-empty buckets, of the squared
+/// difference between the bucket midpoint and its success rate. Returns
+/// `None` for no samples.
+pub fn calibration_score(samples: impl IntoIterator<Item = (f64, bool)>) -> Option<f64> {
+    // (samples, successes) per decile
+    let mut buckets = [(0u32, 0u32); 10];
+    for (confidence, succeeded) in samples {
+        let decile = (confidence.clamp(0.0, 1.0) * 10.0) as usize;
+        let bucket = &mut buckets[decile.min(9)];
+        bucket.0 += 1;
+        bucket.1 += u32::from(succeeded);
+    }
+
+    let errors: Vec<f64> = buckets
+        .iter()
+        .enumerate()
+        .filter(|(_, (n, _))| *n > 0)
+        .map(|(i, (n, successes))| {
+            let midpoint = (i as f64 + 0.5) / 10.0;
+            let rate = *successes as f64 / *n as f64;
+            (midpoint - rate).powi(2)
+        })
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+    let mse = errors.iter().sum::<f64>() / errors.len() as f64;
+    Some((1.0 - mse).clamp(0.0, 1.0))
+}
+
+/// Per-plugin columns for [`HookPluginRow`]. Executions without a
+/// `hook_source` are grouped under the "unknown" plugin.
+const HOOK_PLUGIN_COLUMNS: &str = "COALESCE(hook_source, 'unknown') as plugin_name, \
+     COUNT(*) as total, \
+     SUM(CASE WHEN passed != 0 THEN 1 ELSE 0 END) as passed, \
+     COALESCE(SUM(duration_ms), 0) as total_duration";
+
+fn hook_plugin_row(r: &sea_orm::QueryResult) -> Option<HookPluginRow> {
+    Some(HookPluginRow {
+        plugin_name: r.try_get::<String>("", "plugin_name").ok()?,
+        execution_count: r.try_get::<i64>("", "total").ok()?,
+        pass_count: r.try_get::<i64>("", "passed").unwrap_or(0),
+        total_duration_ms: r.try_get::<i64>("", "total_duration").unwrap_or(0),
+    })
+}
+
+/// Hook executions per plugin for each of `session_ids` in one query,
+/// busiest plugin first. Sessions without hook executions are absent from
+/// the map.
+pub async fn session_hook_plugin_stats(
+    db: &DatabaseConnection,
+    session_ids: &[String],
+) -> DbResult<HashMap<String, Vec<HookPluginRow>>> {
+    if session_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let backend = db.get_database_backend();
+    let placeholders = vec!["?"; session_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT session_id, {HOOK_PLUGIN_COLUMNS} \
+         FROM hook_executions WHERE session_id IN ({placeholders}) \
+         GROUP BY session_id, plugin_name \
+         ORDER BY session_id, total DESC, plugin_name"
+    );
+    let values: Vec<Value> = session_ids
+        .iter()
+        .map(|id| Value::String(Some(Box::new(id.clone()))))
+        .collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+
+    let mut stats: HashMap<String, Vec<HookPluginRow>> = HashMap::new();
+    for r in &rows {
+        let Ok(session_id) = r.try_get::<String>("", "session_id") else {
+            continue;
+        };
+        if let Some(row) = hook_plugin_row(r) {
+            stats.entry(session_id).or_default().push(row);
+        }
+    }
+    Ok(stats)
+}
+
+/// Hook executions per plugin across all sessions, busiest first.
+///
+/// `since` is inclusive, compared against `executed_at`.
+pub async fn top_hook_plugins(
+    db: &DatabaseConnection,
+    since: Option<&str>,
+    limit: u64,
+) -> DbResult<Vec<HookPluginRow>> {
+    let backend = db.get_database_backend();
+    let mut values: Vec<Value> = Vec::new();
+    let filter = match since {
+        Some(since) => {
+            values.push(since.into());
+            "WHERE executed_at >= ?"
+        }
+        None => "",
+    };
+    values.push((limit as i64).into());
+    let sql = format!(
+        "SELECT {HOOK_PLUGIN_COLUMNS} FROM hook_executions {filter} \
+         GROUP BY plugin_name ORDER BY total DESC, plugin_name LIMIT ?"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(backend, &sql, values))
+        .await
+        .map_err(DbError::Database)?;
+
+    Ok(rows.iter().filter_map(hook_plugin_row).collect())
+}
+
+/// `(timestamp, frustration_score, frustration_level)` of analyzed user
+/// messages in a session, oldest first.
+///
+/// Analyzed messages without frustration signals get a score of 0.
+pub async fn query_frustration_points(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Vec<(String, f64, Option<String>)>> {
+    let backend = db.get_database_backend();
+    let sql = "SELECT timestamp, COALESCE(frustration_score, 0.0) as score, frustration_level \
+               FROM messages WHERE session_id = ? AND message_type = 'user' AND sentiment_score IS NOT NULL \
+               ORDER BY timestamp, line_number";
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            backend,
+            sql,
+            vec![Value::String(Some(Box::new(session_id.to_string())))],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            Some((
+                r.try_get::<String>("", "timestamp").ok()?,
+                r.try_get::<f64>("", "score").ok()?,
+                r.try_get::<Option<String>>("", "frustration_level").ok()?,
+            ))
+        })
+        .collect())
+}
@@ -1,7 +1,11 @@
 //! Database connection factory with SQLite PRAGMAs and Postgres support.
 
-use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
-use std::time::Duration;
+use crate::error::DbError;
+use sea_orm::metric::Info;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbErr, Statement, Value,
+};
+use std::time::{Duration, Instant};
 
 /// Database configuration supporting SQLite and PostgreSQL.
 pub enum DbConfig {
@@ -84,11 +88,158 @@ pub async fn establish_connection(config: DbConfig) -> Result<DatabaseConnection
     Ok(db)
 }
 
+/// Connection pool occupancy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Connections currently checked out.
+    pub active: u32,
+    /// Open connections waiting in the pool.
+    pub idle: u32,
+    /// Configured pool size limit.
+    pub max: u32,
+    /// Tasks waiting for a connection. Always 0: sqlx does not expose it.
+    pub wait_count: u32,
+}
+
+/// Run `SELECT 1` and return the round-trip time.
+///
+/// Touches no tables, so it is safe for health checks.
+pub async fn test_connection(db: &DatabaseConnection) -> Result<Duration, DbError> {
+    let started = Instant::now();
+    db.execute_unprepared("SELECT 1").await?;
+    Ok(started.elapsed())
+}
+
+/// Current pool occupancy. All zeros for connections without a pool.
+pub async fn pool_stats(db: &DatabaseConnection) -> PoolStats {
+    match db {
+        #[cfg(feature = "sqlite")]
+        DatabaseConnection::SqlxSqlitePoolConnection(_) => {
+            let pool = db.get_sqlite_connection_pool();
+            let idle = pool.num_idle() as u32;
+            PoolStats {
+                active: pool.size().saturating_sub(idle),
+                idle,
+                max: pool.options().get_max_connections(),
+                wait_count: 0,
+            }
+        }
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+            let pool = db.get_postgres_connection_pool();
+            let idle = pool.num_idle() as u32;
+            PoolStats {
+                active: pool.size().saturating_sub(idle),
+                idle,
+                max: pool.options().get_max_connections(),
+                wait_count: 0,
+            }
+        }
+        _ => PoolStats::default(),
+    }
+}
+
+/// Pool occupancy above which the monitor warns.
+const POOL_PRESSURE_RATIO: f64 = 0.9;
+
+/// Whether `stats` shows the pool close to exhaustion.
+pub fn pool_under_pressure(stats: &PoolStats) -> bool {
+    stats.max > 0 && f64::from(stats.active) / f64::from(stats.max) > POOL_PRESSURE_RATIO
+}
+
+/// Log pool occupancy every `interval_secs` seconds until the task is aborted.
+///
+/// Each sample is logged at DEBUG; a pool more than 90% checked out also
+/// logs a WARN, since exhaustion otherwise surfaces only as timeouts.
+pub async fn start_pool_monitor(
+    db: DatabaseConnection,
+    interval_secs: u64,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            let stats = pool_stats(&db).await;
+            tracing::debug!(
+                "[pool] active={} idle={} wait={}",
+                stats.active,
+                stats.idle,
+                stats.wait_count
+            );
+            if pool_under_pressure(&stats) {
+                tracing::warn!(
+                    "[pool] {} of {} connections in use",
+                    stats.active,
+                    stats.max
+                );
+            }
+        }
+    })
+}
+
+/// Default threshold for [`SlowQueryLogger`].
+pub const SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Warns about queries slower than `threshold`.
+///
+/// Feed it from the connection's metric callback, which SeaORM invokes
+/// after every statement. Parameters are logged without their string
+/// contents, which may hold transcript text.
+#[derive(Debug, Clone, Copy)]
+pub struct SlowQueryLogger {
+    pub threshold: Duration,
+}
+
+impl Default for SlowQueryLogger {
+    fn default() -> Self {
+        Self {
+            threshold: SLOW_QUERY_THRESHOLD,
+        }
+    }
+}
+
+impl SlowQueryLogger {
+    /// Log `info` if the statement took longer than the threshold.
+    pub fn observe(&self, info: &Info<'_>) {
+        if info.elapsed <= self.threshold {
+            return;
+        }
+        let params = info
+            .statement
+            .values
+            .as_ref()
+            .map(|values| values.0.iter().map(sanitize_value).collect::<Vec<_>>())
+            .unwrap_or_default();
+        tracing::warn!(
+            elapsed_ms = info.elapsed.as_millis() as u64,
+            failed = info.failed,
+            "Slow query: {} params=[{}]",
+            info.statement.sql,
+            params.join(", ")
+        );
+    }
+
+    /// Make this logger the metric callback of `db`. Replaces any existing
+    /// callback; clones made afterwards share it.
+    pub fn install(self, db: &mut DatabaseConnection) {
+        db.set_metric_callback(move |info| self.observe(info));
+    }
+}
+
+/// Render a bound parameter for logs, hiding string and binary contents.
+fn sanitize_value(value: &Value) -> String {
+    match value {
+        Value::String(Some(s)) => format!("<string len={}>", s.len()),
+        Value::Bytes(Some(b)) => format!("<bytes len={}>", b.len()),
+        Value::Json(Some(_)) => "<json>".to_string(),
+        Value::String(None) | Value::Bytes(None) | Value::Json(None) => "NULL".to_string(),
+        other => other.to_string(),
+    }
+}
+
 /// Apply SQLite PRAGMAs for optimal performance.
 #[cfg(feature = "sqlite")]
 async fn apply_sqlite_pragmas(db: &DatabaseConnection) -> Result<(), DbErr> {
-    use sea_orm::{ConnectionTrait, Statement};
-
     let pragmas = [
         "PRAGMA journal_mode=WAL;",
         "PRAGMA synchronous=NORMAL;",
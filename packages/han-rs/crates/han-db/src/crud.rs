@@ -21,3 +21,272 @@ pub mod file_validations;
 pub mod orchestrations;
 pub mod async_hooks;
 pub mod tool_call_results;
+pub mod agent_sessions;
+pub mod index_errors;
+pub mod index_warnings;
+pub mod project_stats;
+pub mod embedding_cache;
+pub mod session_timelines;
+pub mod subscriptions;
+pub mod indexing_checkpoints;
+pub mod worktrees;
+pub mod memories;
+pub mod plugins;
+
+use crate::entities::{self, archived_messages, archived_sessions};
+use crate::error::{DbError, DbResult};
+use sea_orm::sea_query::{Alias, Expr, Query};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+    EntityTrait, IdenStatic, Iterable, QueryFilter, QuerySelect, TransactionTrait,
+};
+
+/// Tables holding per-session rows as `(table, session column)`, in the
+/// order they are deleted. `sessions` itself is deleted last.
+const SESSION_CHILD_TABLES: &[(&str, &str)] = &[
+    ("tool_call_results", "session_id"),
+    ("messages", "session_id"),
+    ("session_todos", "session_id"),
+    ("session_file_changes", "session_id"),
+    ("session_file_validations", "session_id"),
+    ("session_files", "session_id"),
+    ("session_summaries", "session_id"),
+    ("session_compacts", "session_id"),
+    ("generated_session_summaries", "session_id"),
+    ("session_index_errors", "session_id"),
+    ("session_index_warnings", "session_id"),
+    ("frustration_events", "session_id"),
+    ("hook_executions", "session_id"),
+    ("async_hook_queue", "session_id"),
+    ("orchestrations", "session_id"),
+    ("native_tasks", "session_id"),
+    ("tasks", "session_id"),
+    ("session_timelines", "session_id"),
+    ("indexing_checkpoints", "session_id"),
+    ("synced_sessions", "session_id"),
+    ("agent_sessions", "parent_session_id"),
+    ("agent_sessions", "child_session_id"),
+];
+
+/// Delete a session and every row that belongs to it.
+///
+/// Runs in a single transaction, so either everything is removed or nothing
+/// is. Sub-agent links are removed whether the session is the parent or the
+/// child, but linked sessions themselves are kept. Returns the total number of
+/// rows deleted, including the session row.
+pub async fn delete_session_cascade(db: &DatabaseConnection, session_id: &str) -> DbResult<u64> {
+    let txn = db.begin().await.map_err(DbError::Database)?;
+    let deleted = delete_sessions_in(&txn, &[session_id.to_string()]).await?;
+    txn.commit().await.map_err(DbError::Database)?;
+    Ok(deleted)
+}
+
+/// Delete `session_ids` and their rows in [`SESSION_CHILD_TABLES`], returning
+/// the number of rows deleted.
+async fn delete_sessions_in(txn: &DatabaseTransaction, session_ids: &[String]) -> DbResult<u64> {
+    let backend = txn.get_database_backend();
+    let mut deleted = 0;
+
+    let tables = SESSION_CHILD_TABLES
+        .iter()
+        .copied()
+        .chain(std::iter::once(("sessions", "id")));
+    for (table, column) in tables {
+        let stmt = Query::delete()
+            .from_table(Alias::new(table))
+            .and_where(Expr::col(Alias::new(column)).is_in(session_ids.iter().cloned()))
+            .to_owned();
+        deleted += txn
+            .execute(backend.build(&stmt))
+            .await
+            .map_err(DbError::Database)?
+            .rows_affected();
+    }
+
+    // FTS tables are SQLite only and not counted as session data
+    if backend == DbBackend::Sqlite {
+        let stmt = Query::delete()
+            .from_table(Alias::new("sessions_fts"))
+            .and_where(Expr::col(Alias::new("session_id")).is_in(session_ids.iter().cloned()))
+            .to_owned();
+        txn.execute(backend.build(&stmt))
+            .await
+            .map_err(DbError::Database)?;
+    }
+
+    Ok(deleted)
+}
+
+/// Sessions copied per statement when archiving, to stay well under SQLite's
+/// bound parameter limit.
+const ARCHIVE_BATCH_SIZE: usize = 500;
+
+/// Archive sessions whose last message is more than `days` days old,
+/// returning how many were archived.
+///
+/// See [`archive_stale_sessions`], which returns their IDs instead.
+pub async fn archive_sessions_older_than(db: &DatabaseConnection, days: u32) -> DbResult<u64> {
+    Ok(archive_stale_sessions(db, days).await?.len() as u64)
+}
+
+/// Archive sessions whose last message is more than `days` days old.
+///
+/// Each session and its messages are copied to `archived_sessions` and
+/// `archived_messages`, then deleted like [`delete_session_cascade`]. Other
+/// per-session rows (summaries, todos, file changes, hook runs) are not
+/// archived; re-index a session after [`restore_session`] to rebuild them.
+/// Sessions without messages are kept, and an older archived copy of a
+/// session is replaced. Runs in a single transaction, so a failure archives
+/// nothing. Returns the IDs of the sessions archived.
+pub async fn archive_stale_sessions(db: &DatabaseConnection, days: u32) -> DbResult<Vec<String>> {
+    let now = chrono::Utc::now();
+    let cutoff = (now - chrono::Duration::days(days.into()))
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let archived_at = now.to_rfc3339();
+
+    let txn = db.begin().await.map_err(DbError::Database)?;
+    let session_ids: Vec<String> = entities::sessions::Entity::find()
+        .select_only()
+        .column(entities::sessions::Column::Id)
+        .filter(Expr::cust_with_values(
+            format!("{} < ?", sessions::LAST_MESSAGE_AT),
+            [cutoff],
+        ))
+        .into_tuple()
+        .all(&txn)
+        .await
+        .map_err(DbError::Database)?;
+
+    for batch in session_ids.chunks(ARCHIVE_BATCH_SIZE) {
+        // A session re-created after an earlier archive replaces that copy
+        archived_messages::Entity::delete_many()
+            .filter(archived_messages::Column::SessionId.is_in(batch.iter().cloned()))
+            .exec(&txn)
+            .await
+            .map_err(DbError::Database)?;
+        archived_sessions::Entity::delete_many()
+            .filter(archived_sessions::Column::Id.is_in(batch.iter().cloned()))
+            .exec(&txn)
+            .await
+            .map_err(DbError::Database)?;
+        let at = Some(archived_at.as_str());
+        copy_rows::<entities::sessions::Entity>(
+            &txn,
+            "sessions",
+            "archived_sessions",
+            "id",
+            batch,
+            at,
+        )
+        .await?;
+        copy_rows::<entities::messages::Entity>(
+            &txn,
+            "messages",
+            "archived_messages",
+            "session_id",
+            batch,
+            at,
+        )
+        .await?;
+        delete_sessions_in(&txn, batch).await?;
+    }
+
+    txn.commit().await.map_err(DbError::Database)?;
+    Ok(session_ids)
+}
+
+/// Whether `session_id` has been archived and not restored.
+///
+/// The indexer skips archived sessions, since their transcripts usually
+/// still exist on disk.
+pub async fn is_archived(db: &DatabaseConnection, session_id: &str) -> DbResult<bool> {
+    let archived = archived_sessions::Entity::find_by_id(session_id)
+        .select_only()
+        .column(archived_sessions::Column::Id)
+        .into_tuple::<String>()
+        .one(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(archived.is_some())
+}
+
+/// Move an archived session and its messages back into `sessions` and
+/// `messages`.
+///
+/// Fails without changing anything if the session isn't archived or a live
+/// session with the same ID exists.
+pub async fn restore_session(db: &DatabaseConnection, session_id: &str) -> DbResult<()> {
+    let txn = db.begin().await.map_err(DbError::Database)?;
+    archived_sessions::Entity::find_by_id(session_id)
+        .one(&txn)
+        .await
+        .map_err(DbError::Database)?
+        .ok_or_else(|| DbError::NotFound(format!("Archived session {session_id}")))?;
+
+    let ids = [session_id.to_string()];
+    copy_rows::<entities::sessions::Entity>(
+        &txn,
+        "archived_sessions",
+        "sessions",
+        "id",
+        &ids,
+        None,
+    )
+    .await?;
+    copy_rows::<entities::messages::Entity>(
+        &txn,
+        "archived_messages",
+        "messages",
+        "session_id",
+        &ids,
+        None,
+    )
+    .await?;
+    archived_messages::Entity::delete_many()
+        .filter(archived_messages::Column::SessionId.eq(session_id))
+        .exec(&txn)
+        .await
+        .map_err(DbError::Database)?;
+    archived_sessions::Entity::delete_by_id(session_id)
+        .exec(&txn)
+        .await
+        .map_err(DbError::Database)?;
+
+    txn.commit().await.map_err(DbError::Database)?;
+    Ok(())
+}
+
+/// Copy the rows of table `from` whose `key` is in `ids` into table `to`,
+/// using the columns of the live entity `E`. `archived_at`, when given, fills
+/// the archive table's extra `archived_at` column.
+async fn copy_rows<E: EntityTrait>(
+    txn: &DatabaseTransaction,
+    from: &str,
+    to: &str,
+    key: &str,
+    ids: &[String],
+    archived_at: Option<&str>,
+) -> DbResult<()> {
+    let mut columns: Vec<Alias> = E::Column::iter().map(|c| Alias::new(c.as_str())).collect();
+    let mut select = Query::select();
+    select
+        .columns(columns.clone())
+        .from(Alias::new(from))
+        .and_where(Expr::col(Alias::new(key)).is_in(ids.iter().cloned()));
+    if let Some(at) = archived_at {
+        select.expr(Expr::val(at));
+        columns.push(Alias::new("archived_at"));
+    }
+
+    let stmt = Query::insert()
+        .into_table(Alias::new(to))
+        .columns(columns)
+        .select_from(select)
+        .map_err(|e| DbError::Database(DbErr::Custom(e.to_string())))?
+        .to_owned();
+    let backend = txn.get_database_backend();
+    txn.execute(backend.build(&stmt))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(())
+}
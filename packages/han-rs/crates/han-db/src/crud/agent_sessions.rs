@@ -0,0 +1,100 @@
+//! CRUD operations for agent_sessions.
+
+use crate::entities::agent_sessions;
+use crate::error::{DbError, DbResult};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+
+/// Link a child (sub-agent) session to its parent.
+///
+/// Each child has at most one parent, so this upserts on `child_session_id`.
+/// A `None` `task_tool_call_id` never overwrites a previously recorded one,
+/// so the agent file and the parent's Task result can be indexed in any order.
+pub async fn link(
+    db: &DatabaseConnection,
+    parent_session_id: String,
+    child_session_id: String,
+    task_tool_call_id: Option<String>,
+    spawned_at: String,
+) -> DbResult<agent_sessions::Model> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let child_clone = child_session_id.clone();
+
+    agent_sessions::Entity::insert(agent_sessions::ActiveModel {
+        id: Set(id),
+        parent_session_id: Set(parent_session_id),
+        child_session_id: Set(child_session_id),
+        task_tool_call_id: Set(task_tool_call_id),
+        spawned_at: Set(spawned_at),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(agent_sessions::Column::ChildSessionId)
+            .update_column(agent_sessions::Column::ParentSessionId)
+            .value(
+                agent_sessions::Column::TaskToolCallId,
+                Expr::cust(
+                    "COALESCE(excluded.task_tool_call_id, agent_sessions.task_tool_call_id)",
+                ),
+            )
+            .to_owned(),
+    )
+    .exec(db)
+    .await
+    .map_err(DbError::Database)?;
+
+    // Fetch the row after upsert
+    get_parent(db, &child_clone)
+        .await?
+        .ok_or(DbError::NotFound("agent_session".to_string()))
+}
+
+/// Get the link row for a child session (i.e. who spawned it).
+pub async fn get_parent(
+    db: &DatabaseConnection,
+    child_session_id: &str,
+) -> DbResult<Option<agent_sessions::Model>> {
+    agent_sessions::Entity::find()
+        .filter(agent_sessions::Column::ChildSessionId.eq(child_session_id))
+        .one(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// List sub-agent sessions spawned by a parent session, oldest first.
+pub async fn list_children(
+    db: &DatabaseConnection,
+    parent_session_id: &str,
+) -> DbResult<Vec<agent_sessions::Model>> {
+    agent_sessions::Entity::find()
+        .filter(agent_sessions::Column::ParentSessionId.eq(parent_session_id))
+        .order_by_asc(agent_sessions::Column::SpawnedAt)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// Find the session whose messages reference the given agent ID.
+///
+/// The parent transcript records the spawned agent's ID on the Task tool
+/// result (`toolUseResult.agentId`), so the first session other than the
+/// agent's own that mentions it is the parent.
+pub async fn find_parent_by_agent_id(
+    db: &DatabaseConnection,
+    agent_id: &str,
+) -> DbResult<Option<String>> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            // `instr` cheaply skips rows that can't mention the agent before
+            // the JSON is parsed
+            "SELECT session_id FROM messages \
+             WHERE session_id != ?1 AND message_type = 'user' AND instr(raw_json, ?1) > 0 \
+             AND json_extract(raw_json, '$.toolUseResult.agentId') = ?1 \
+             ORDER BY timestamp ASC LIMIT 1",
+            vec![agent_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+
+    Ok(row.and_then(|r| r.try_get::<String>("", "session_id").ok()))
+}
@@ -0,0 +1,100 @@
+//! CRUD operations for embedding_cache.
+
+use crate::entities::embedding_cache;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Size of the cached embeddings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EmbeddingCacheStats {
+    pub cached_count: u64,
+    /// Total size of the stored vectors, excluding row overhead.
+    pub cache_size_bytes: u64,
+}
+
+/// Encode an embedding as little-endian `f32` bytes.
+pub fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode little-endian `f32` bytes, or `None` if the length is not a
+/// multiple of four.
+pub fn decode_embedding(bytes: &[u8]) -> Option<Vec<f32>> {
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    )
+}
+
+/// Cached embedding of `content_hash` produced by `model_name`.
+///
+/// An entry from another model, or one whose blob does not match its
+/// recorded dimension, counts as a miss.
+pub async fn get_cached_embedding(
+    db: &DatabaseConnection,
+    content_hash: &str,
+    model_name: &str,
+) -> DbResult<Option<Vec<f32>>> {
+    let cached = embedding_cache::Entity::find_by_id(content_hash)
+        .filter(embedding_cache::Column::ModelName.eq(model_name))
+        .one(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(cached.and_then(|row| {
+        decode_embedding(&row.embedding).filter(|v| v.len() == row.dimension as usize)
+    }))
+}
+
+/// Store the embedding of `content_hash`, replacing any earlier entry.
+pub async fn store_embedding(
+    db: &DatabaseConnection,
+    content_hash: &str,
+    model_name: &str,
+    embedding: &[f32],
+) -> DbResult<()> {
+    let model = embedding_cache::ActiveModel {
+        content_hash: Set(content_hash.to_string()),
+        embedding: Set(encode_embedding(embedding)),
+        model_name: Set(model_name.to_string()),
+        dimension: Set(embedding.len() as i32),
+        created_at: Set(chrono::Utc::now().to_rfc3339()),
+    };
+    embedding_cache::Entity::insert(model)
+        .on_conflict(
+            sea_query::OnConflict::column(embedding_cache::Column::ContentHash)
+                .update_columns([
+                    embedding_cache::Column::Embedding,
+                    embedding_cache::Column::ModelName,
+                    embedding_cache::Column::Dimension,
+                    embedding_cache::Column::CreatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(())
+}
+
+/// Number and total size of cached embeddings.
+pub async fn stats(db: &DatabaseConnection) -> DbResult<EmbeddingCacheStats> {
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT COUNT(*) AS cached, COALESCE(SUM(LENGTH(embedding)), 0) AS bytes \
+             FROM embedding_cache",
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(row
+        .map(|r| EmbeddingCacheStats {
+            cached_count: r.try_get::<i64>("", "cached").unwrap_or(0) as u64,
+            cache_size_bytes: r.try_get::<i64>("", "bytes").unwrap_or(0) as u64,
+        })
+        .unwrap_or_default())
+}
@@ -4,6 +4,10 @@ use crate::entities::session_file_changes;
 use crate::error::{DbError, DbResult};
 use sea_orm::*;
 
+/// Action recorded for files a tool only read. Read rows are kept for
+/// auditing and never count as changes.
+pub const ACTION_READ: &str = "read";
+
 pub async fn record(
     db: &DatabaseConnection,
     session_id: String,
@@ -35,6 +39,70 @@ pub async fn record(
     Ok(result)
 }
 
+/// A file access made by one tool call, for [`upsert_file_change`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileChangeInput {
+    /// Stable row id, such as the tool call's id.
+    pub id: String,
+    pub session_id: String,
+    pub file_path: String,
+    pub action: String,
+    pub file_hash_after: Option<String>,
+    pub tool_name: Option<String>,
+    pub agent_id: Option<String>,
+}
+
+/// Record a file access under a stable `id`, such as the tool call that
+/// made it, so indexing the same call again updates the row in place.
+///
+/// On conflict the hash, tool and agent are refreshed while the original
+/// action and `recorded_at` are kept.
+pub async fn upsert_file_change(
+    db: &DatabaseConnection,
+    change: FileChangeInput,
+) -> DbResult<session_file_changes::Model> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    session_file_changes::Entity::insert(session_file_changes::ActiveModel {
+        id: Set(change.id),
+        session_id: Set(change.session_id),
+        file_path: Set(change.file_path),
+        action: Set(change.action),
+        file_hash_before: Set(None),
+        file_hash_after: Set(change.file_hash_after),
+        tool_name: Set(change.tool_name),
+        agent_id: Set(change.agent_id),
+        recorded_at: Set(now),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(session_file_changes::Column::Id)
+            .update_columns([
+                session_file_changes::Column::FileHashAfter,
+                session_file_changes::Column::ToolName,
+                session_file_changes::Column::AgentId,
+            ])
+            .to_owned(),
+    )
+    .exec_with_returning(db)
+    .await
+    .map_err(DbError::Database)
+}
+
+/// Whether the session already has any row, read or change, for `file_path`.
+pub async fn has_path(
+    db: &DatabaseConnection,
+    session_id: &str,
+    file_path: &str,
+) -> DbResult<bool> {
+    let count = session_file_changes::Entity::find()
+        .filter(session_file_changes::Column::SessionId.eq(session_id))
+        .filter(session_file_changes::Column::FilePath.eq(file_path))
+        .count(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(count > 0)
+}
+
 pub async fn get_by_session(
     db: &DatabaseConnection,
     session_id: &str,
@@ -56,7 +124,8 @@ pub async fn get_by_session(
 
 pub async fn has_changes(db: &DatabaseConnection, session_id: &str, agent_id: Option<&str>) -> DbResult<bool> {
     let mut query = session_file_changes::Entity::find()
-        .filter(session_file_changes::Column::SessionId.eq(session_id));
+        .filter(session_file_changes::Column::SessionId.eq(session_id))
+        .filter(session_file_changes::Column::Action.ne(ACTION_READ));
 
     if let Some(aid) = agent_id {
         query = query.filter(session_file_changes::Column::AgentId.eq(aid));
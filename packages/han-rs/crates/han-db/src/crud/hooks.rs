@@ -53,6 +53,34 @@ pub async fn record_execution(
     Ok(result)
 }
 
+/// Insert fully-formed executions, e.g. imported from CI, in one
+/// transaction: either every row is written or none is.
+pub async fn insert_executions(
+    db: &DatabaseConnection,
+    executions: Vec<hook_executions::Model>,
+) -> DbResult<u64> {
+    if executions.is_empty() {
+        return Ok(0);
+    }
+
+    let count = executions.len() as u64;
+    let rows: Vec<hook_executions::ActiveModel> = executions
+        .into_iter()
+        .map(IntoActiveModel::into_active_model)
+        .collect();
+
+    let txn = db.begin().await.map_err(DbError::Database)?;
+    for chunk in rows.chunks(50) {
+        hook_executions::Entity::insert_many(chunk.to_vec())
+            .exec(&txn)
+            .await
+            .map_err(DbError::Database)?;
+    }
+    txn.commit().await.map_err(DbError::Database)?;
+
+    Ok(count)
+}
+
 pub async fn queue_pending_hook(
     db: &DatabaseConnection,
     orchestration_id: String,
@@ -0,0 +1,78 @@
+//! CRUD operations for session_index_errors.
+
+use crate::entities::session_index_errors;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Record lines that failed to index as `(line_number, error_message)`.
+///
+/// Upserts on `(session_id, line_number)`, so re-reading a line replaces its
+/// earlier error.
+pub async fn record(
+    db: &DatabaseConnection,
+    session_id: &str,
+    errors: &[(u32, String)],
+) -> DbResult<()> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let rows = errors
+        .iter()
+        .map(|(line_number, message)| session_index_errors::ActiveModel {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            session_id: Set(session_id.to_string()),
+            line_number: Set(*line_number as i32),
+            error_message: Set(message.clone()),
+            indexed_at: Set(now.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    for chunk in rows.chunks(50) {
+        session_index_errors::Entity::insert_many(chunk.to_vec())
+            .on_conflict(
+                sea_query::OnConflict::columns([
+                    session_index_errors::Column::SessionId,
+                    session_index_errors::Column::LineNumber,
+                ])
+                .update_columns([
+                    session_index_errors::Column::ErrorMessage,
+                    session_index_errors::Column::IndexedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(db)
+            .await
+            .map_err(DbError::Database)?;
+    }
+    Ok(())
+}
+
+/// List a session's indexing errors in line order.
+pub async fn list_by_session(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Vec<session_index_errors::Model>> {
+    session_index_errors::Entity::find()
+        .filter(session_index_errors::Column::SessionId.eq(session_id))
+        .order_by_asc(session_index_errors::Column::LineNumber)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// Clear errors recorded for `from_line` onwards, ahead of re-reading those lines.
+pub async fn delete_from_line(
+    db: &DatabaseConnection,
+    session_id: &str,
+    from_line: i32,
+) -> DbResult<u64> {
+    session_index_errors::Entity::delete_many()
+        .filter(session_index_errors::Column::SessionId.eq(session_id))
+        .filter(session_index_errors::Column::LineNumber.gte(from_line))
+        .exec(db)
+        .await
+        .map(|r| r.rows_affected)
+        .map_err(DbError::Database)
+}
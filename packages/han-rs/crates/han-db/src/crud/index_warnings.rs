@@ -0,0 +1,83 @@
+//! CRUD operations for session_index_warnings.
+
+use crate::entities::session_index_warnings;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Record suspicious lines as `(line_number, warning_type, detail)`.
+///
+/// Upserts on `(session_id, line_number, warning_type)`, so re-reading a line
+/// replaces its earlier warning of the same type.
+pub async fn record(
+    db: &DatabaseConnection,
+    session_id: &str,
+    warnings: &[(u32, String, String)],
+) -> DbResult<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let rows = warnings
+        .iter()
+        .map(
+            |(line_number, warning_type, detail)| session_index_warnings::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                session_id: Set(session_id.to_string()),
+                line_number: Set(*line_number as i32),
+                warning_type: Set(warning_type.clone()),
+                detail: Set(detail.clone()),
+                indexed_at: Set(now.clone()),
+            },
+        )
+        .collect::<Vec<_>>();
+
+    for chunk in rows.chunks(50) {
+        session_index_warnings::Entity::insert_many(chunk.to_vec())
+            .on_conflict(
+                sea_query::OnConflict::columns([
+                    session_index_warnings::Column::SessionId,
+                    session_index_warnings::Column::LineNumber,
+                    session_index_warnings::Column::WarningType,
+                ])
+                .update_columns([
+                    session_index_warnings::Column::Detail,
+                    session_index_warnings::Column::IndexedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(db)
+            .await
+            .map_err(DbError::Database)?;
+    }
+    Ok(())
+}
+
+/// List a session's indexing warnings in line order.
+pub async fn list_by_session(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Vec<session_index_warnings::Model>> {
+    session_index_warnings::Entity::find()
+        .filter(session_index_warnings::Column::SessionId.eq(session_id))
+        .order_by_asc(session_index_warnings::Column::LineNumber)
+        .order_by_asc(session_index_warnings::Column::WarningType)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// Clear warnings recorded for `from_line` onwards, ahead of re-reading those lines.
+pub async fn delete_from_line(
+    db: &DatabaseConnection,
+    session_id: &str,
+    from_line: i32,
+) -> DbResult<u64> {
+    session_index_warnings::Entity::delete_many()
+        .filter(session_index_warnings::Column::SessionId.eq(session_id))
+        .filter(session_index_warnings::Column::LineNumber.gte(from_line))
+        .exec(db)
+        .await
+        .map(|r| r.rows_affected)
+        .map_err(DbError::Database)
+}
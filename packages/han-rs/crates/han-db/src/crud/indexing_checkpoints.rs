@@ -0,0 +1,59 @@
+//! CRUD operations for indexing_checkpoints.
+
+use crate::entities::indexing_checkpoints;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Last line written by an unfinished index pass of a session.
+pub async fn get(db: &DatabaseConnection, session_id: &str) -> DbResult<Option<i32>> {
+    let row = indexing_checkpoints::Entity::find_by_id(session_id)
+        .one(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(row.map(|r| r.last_line_number))
+}
+
+/// Record that every message up to `last_line_number` has been written.
+pub async fn save(
+    db: &DatabaseConnection,
+    session_id: &str,
+    last_line_number: i32,
+) -> DbResult<()> {
+    let model = indexing_checkpoints::ActiveModel {
+        session_id: Set(session_id.to_string()),
+        last_line_number: Set(last_line_number),
+        last_indexed_at: Set(chrono::Utc::now().to_rfc3339()),
+    };
+    indexing_checkpoints::Entity::insert(model)
+        .on_conflict(
+            sea_query::OnConflict::column(indexing_checkpoints::Column::SessionId)
+                .update_columns([
+                    indexing_checkpoints::Column::LastLineNumber,
+                    indexing_checkpoints::Column::LastIndexedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(())
+}
+
+/// Drop the checkpoint of a session whose index pass completed.
+pub async fn clear(db: &DatabaseConnection, session_id: &str) -> DbResult<u64> {
+    let result = indexing_checkpoints::Entity::delete_by_id(session_id)
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
+
+/// Drop every checkpoint, so the next pass of each session starts from its
+/// last completed pass.
+pub async fn clear_all(db: &DatabaseConnection) -> DbResult<u64> {
+    let result = indexing_checkpoints::Entity::delete_many()
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,54 @@
+//! CRUD operations for memories.
+
+use crate::entities::memories;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Find the memory recorded with this `scope`, `content` and `created_at`,
+/// inserting it if there is none.
+///
+/// Re-indexing a `memory_learn` event resolves to the same row instead of
+/// recording the memory twice, even when two indexers race on it.
+pub async fn find_or_create(
+    db: &DatabaseConnection,
+    scope: Option<String>,
+    content: String,
+    created_at: String,
+) -> DbResult<memories::Model> {
+    let result = memories::Entity::insert(memories::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        scope: Set(scope.clone()),
+        content: Set(content.clone()),
+        created_at: Set(created_at.clone()),
+    })
+    // No conflict target: the unique index is on an expression
+    .on_conflict(sea_query::OnConflict::new().do_nothing().to_owned())
+    .exec(db)
+    .await;
+    // SeaORM returns RecordNotInserted when do_nothing skips an existing record
+    match result {
+        Ok(_) => {}
+        Err(DbErr::RecordNotInserted) => {}
+        Err(e) => return Err(DbError::Database(e)),
+    }
+
+    let scope_condition = match &scope {
+        Some(scope) => memories::Column::Scope.eq(scope.as_str()),
+        None => memories::Column::Scope.is_null(),
+    };
+    memories::Entity::find()
+        .filter(scope_condition)
+        .filter(memories::Column::Content.eq(content.as_str()))
+        .filter(memories::Column::CreatedAt.eq(created_at.as_str()))
+        .one(db)
+        .await
+        .map_err(DbError::Database)?
+        .ok_or(DbError::NotFound("memory".to_string()))
+}
+
+pub async fn get(db: &DatabaseConnection, id: &str) -> DbResult<Option<memories::Model>> {
+    memories::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(DbError::Database)
+}
@@ -2,7 +2,9 @@
 
 use crate::entities::messages;
 use crate::error::{DbError, DbResult};
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
+use std::collections::HashMap;
 
 pub async fn insert_batch(db: &DatabaseConnection, msgs: Vec<messages::ActiveModel>) -> DbResult<u64> {
     if msgs.is_empty() {
@@ -33,6 +35,217 @@ pub async fn insert_batch(db: &DatabaseConnection, msgs: Vec<messages::ActiveMod
     Ok(count)
 }
 
+/// Rows per INSERT statement for the bulk upsert functions.
+const BULK_CHUNK_SIZE: usize = 500;
+
+/// Columns refreshed by [`bulk_upsert`] when a message already exists.
+/// `id` and `session_id` identify the row and are never rewritten.
+/// `duration_ms` and `memory_id` are left alone; they are derived after the
+/// upsert and written with [`set_duration_ms`] and [`set_memory_ids`].
+const UPSERT_UPDATE_COLUMNS: [messages::Column; 28] = [
+    messages::Column::AgentId,
+    messages::Column::ParentId,
+    messages::Column::MessageType,
+    messages::Column::Role,
+    messages::Column::Content,
+    messages::Column::ToolName,
+    messages::Column::ToolInput,
+    messages::Column::ToolResult,
+    messages::Column::RawJson,
+    messages::Column::Timestamp,
+    messages::Column::LineNumber,
+    messages::Column::ByteOffset,
+    messages::Column::SourceFileName,
+    messages::Column::SourceFileType,
+    messages::Column::SentimentScore,
+    messages::Column::SentimentLevel,
+    messages::Column::FrustrationScore,
+    messages::Column::FrustrationLevel,
+    messages::Column::InputTokens,
+    messages::Column::OutputTokens,
+    messages::Column::CacheReadTokens,
+    messages::Column::CacheCreationTokens,
+    messages::Column::LinesAdded,
+    messages::Column::LinesRemoved,
+    messages::Column::FilesChanged,
+    messages::Column::HumanTimeMs,
+    messages::Column::PluginName,
+    messages::Column::IndexedAt,
+];
+
+/// Insert messages, overwriting existing rows with the same ID.
+///
+/// Safe to re-run over the same transcript: derived columns (sentiment,
+/// tokens, human time, ...) are refreshed rather than duplicated.
+/// Returns the number of rows newly inserted, not counting updates.
+pub async fn bulk_upsert(
+    db: &DatabaseConnection,
+    msgs: Vec<messages::ActiveModel>,
+) -> DbResult<u64> {
+    bulk_write(
+        db,
+        msgs,
+        sea_query::OnConflict::column(messages::Column::Id)
+            .update_columns(UPSERT_UPDATE_COLUMNS)
+            .to_owned(),
+    )
+    .await
+}
+
+/// Insert messages, leaving existing rows with the same ID untouched.
+///
+/// Cheaper than [`bulk_upsert`] when re-running over content that hasn't
+/// changed. Returns the number of rows newly inserted.
+pub async fn bulk_upsert_skip_existing(
+    db: &DatabaseConnection,
+    msgs: Vec<messages::ActiveModel>,
+) -> DbResult<u64> {
+    bulk_write(
+        db,
+        msgs,
+        sea_query::OnConflict::column(messages::Column::Id)
+            .do_nothing()
+            .to_owned(),
+    )
+    .await
+}
+
+async fn bulk_write(
+    db: &DatabaseConnection,
+    msgs: Vec<messages::ActiveModel>,
+    on_conflict: sea_query::OnConflict,
+) -> DbResult<u64> {
+    use std::collections::HashSet;
+
+    let mut inserted = 0u64;
+    for chunk in msgs.chunks(BULK_CHUNK_SIZE) {
+        let ids: HashSet<String> = chunk
+            .iter()
+            .filter_map(|m| match &m.id {
+                ActiveValue::Set(id) | ActiveValue::Unchanged(id) => Some(id.clone()),
+                ActiveValue::NotSet => None,
+            })
+            .collect();
+        let existing: HashSet<String> = messages::Entity::find()
+            .select_only()
+            .column(messages::Column::Id)
+            .filter(messages::Column::Id.is_in(ids.iter().cloned()))
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map_err(DbError::Database)?
+            .into_iter()
+            .collect();
+
+        let result = messages::Entity::insert_many(chunk.to_vec())
+            .on_conflict(on_conflict.clone())
+            .exec(db)
+            .await;
+        match result {
+            Ok(_) => {}
+            Err(DbErr::RecordNotInserted) => {} // All records already exist, skip
+            Err(e) => return Err(DbError::Database(e)),
+        }
+
+        inserted += ids.difference(&existing).count() as u64;
+    }
+
+    Ok(inserted)
+}
+
+/// Delete messages read from `source_file_name` that belong to a session other
+/// than `session_id`. Used to move agent transcripts out of the parent session.
+pub async fn delete_by_source_file_outside_session(
+    db: &DatabaseConnection,
+    source_file_name: &str,
+    session_id: &str,
+) -> DbResult<u64> {
+    let result = messages::Entity::delete_many()
+        .filter(messages::Column::SourceFileName.eq(source_file_name))
+        .filter(messages::Column::SessionId.ne(session_id))
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
+
+/// Set `source_file_type` on every message of a session.
+pub async fn set_source_file_type(
+    db: &DatabaseConnection,
+    session_id: &str,
+    source_file_type: &str,
+) -> DbResult<u64> {
+    let result = messages::Entity::update_many()
+        .col_expr(
+            messages::Column::SourceFileType,
+            Expr::value(source_file_type),
+        )
+        .filter(messages::Column::SessionId.eq(session_id))
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
+
+/// Set `duration_ms` for each `(message_id, duration_ms)` pair.
+pub async fn set_duration_ms(db: &DatabaseConnection, durations: &[(String, i32)]) -> DbResult<()> {
+    for (message_id, duration_ms) in durations {
+        messages::Entity::update_many()
+            .col_expr(messages::Column::DurationMs, Expr::value(*duration_ms))
+            .filter(messages::Column::Id.eq(message_id.as_str()))
+            .exec(db)
+            .await
+            .map_err(DbError::Database)?;
+    }
+    Ok(())
+}
+
+/// Set `memory_id` for each `(message_id, memory_id)` pair.
+pub async fn set_memory_ids(db: &DatabaseConnection, links: &[(String, String)]) -> DbResult<()> {
+    for (message_id, memory_id) in links {
+        messages::Entity::update_many()
+            .col_expr(messages::Column::MemoryId, Expr::value(memory_id.as_str()))
+            .filter(messages::Column::Id.eq(message_id.as_str()))
+            .exec(db)
+            .await
+            .map_err(DbError::Database)?;
+    }
+    Ok(())
+}
+
+/// Messages of a session read from one source file, in file order.
+pub async fn list_by_source_file(
+    db: &DatabaseConnection,
+    session_id: &str,
+    source_file_name: &str,
+) -> DbResult<Vec<messages::Model>> {
+    messages::Entity::find()
+        .filter(messages::Column::SessionId.eq(session_id))
+        .filter(messages::Column::SourceFileName.eq(source_file_name))
+        .order_by_asc(messages::Column::LineNumber)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// Delete a session's messages read from `source_file_name` at or after
+/// `from_line`, so that part of the file can be indexed again.
+pub async fn delete_by_source_file_from_line(
+    db: &DatabaseConnection,
+    session_id: &str,
+    source_file_name: &str,
+    from_line: i32,
+) -> DbResult<u64> {
+    let result = messages::Entity::delete_many()
+        .filter(messages::Column::SessionId.eq(session_id))
+        .filter(messages::Column::SourceFileName.eq(source_file_name))
+        .filter(messages::Column::LineNumber.gte(from_line))
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
+
 pub async fn get(db: &DatabaseConnection, message_id: &str) -> DbResult<Option<messages::Model>> {
     messages::Entity::find_by_id(message_id)
         .one(db)
@@ -78,6 +291,48 @@ pub async fn list_by_session(
     query.all(db).await.map_err(DbError::Database)
 }
 
+/// Page of a session's messages from `from_line` on, in `(line_number, id)`
+/// order, skipping those already sent: `seen` holds the last
+/// `(line_number, id)` sent from each source file.
+///
+/// Line numbers only increase within a source file (han events sit past
+/// `HAN_LINE_OFFSET`), so one position for the whole session would skip
+/// transcript lines appended after a han event. Each page takes a prefix of
+/// every file's remaining messages, so recording the last row per file
+/// resumes the next page.
+pub async fn list_page_after(
+    db: &DatabaseConnection,
+    session_id: &str,
+    from_line: i32,
+    seen: &HashMap<Option<String>, (i32, String)>,
+    limit: u64,
+) -> DbResult<Vec<messages::Model>> {
+    let mut unseen = Condition::all().add(messages::Column::LineNumber.gte(from_line));
+    for (file, (line, id)) in seen {
+        let same_file = match file {
+            Some(name) => messages::Column::SourceFileName.eq(name.as_str()),
+            None => messages::Column::SourceFileName.is_null(),
+        };
+        let sent = Condition::any()
+            .add(messages::Column::LineNumber.lt(*line))
+            .add(
+                Condition::all()
+                    .add(messages::Column::LineNumber.eq(*line))
+                    .add(messages::Column::Id.lte(id.as_str())),
+            );
+        unseen = unseen.add(Condition::all().add(same_file).add(sent).not());
+    }
+    messages::Entity::find()
+        .filter(messages::Column::SessionId.eq(session_id))
+        .filter(unseen)
+        .order_by_asc(messages::Column::LineNumber)
+        .order_by_asc(messages::Column::Id)
+        .limit(limit)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
 pub async fn get_count(db: &DatabaseConnection, session_id: &str) -> DbResult<u64> {
     messages::Entity::find()
         .filter(messages::Column::SessionId.eq(session_id))
@@ -112,6 +367,29 @@ pub async fn get_counts_batch(db: &DatabaseConnection, session_ids: Vec<String>)
     Ok(results)
 }
 
+/// Tool name of each indexed tool call, as `(tool_call_id, tool_name)` pairs.
+/// The indexer stores every `tool_use` block as a message keyed by its call ID.
+pub async fn get_tool_names(
+    db: &DatabaseConnection,
+    tool_call_ids: Vec<String>,
+) -> DbResult<Vec<(String, String)>> {
+    if tool_call_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    messages::Entity::find()
+        .select_only()
+        .column(messages::Column::Id)
+        .column(messages::Column::ToolName)
+        .filter(messages::Column::Id.is_in(tool_call_ids))
+        .filter(messages::Column::MessageType.eq("tool_use"))
+        .filter(messages::Column::ToolName.is_not_null())
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
+
 /// Find Han event result messages by call_id in raw JSON.
 /// Used for mcp_tool_result and exposed_tool_result events.
 pub async fn find_results_by_call_ids(
@@ -227,6 +505,7 @@ fn rows_to_models(rows: Vec<sea_orm::QueryResult>) -> DbResult<Vec<messages::Mod
             raw_json: row.try_get("", "raw_json").ok(),
             timestamp: row.try_get("", "timestamp").unwrap_or_default(),
             line_number: row.try_get("", "line_number").unwrap_or(0),
+            byte_offset: row.try_get("", "byte_offset").ok(),
             source_file_name: row.try_get("", "source_file_name").ok(),
             source_file_type: row.try_get("", "source_file_type").ok(),
             sentiment_score: row.try_get("", "sentiment_score").ok(),
@@ -241,6 +520,9 @@ fn rows_to_models(rows: Vec<sea_orm::QueryResult>) -> DbResult<Vec<messages::Mod
             lines_removed: row.try_get("", "lines_removed").ok(),
             files_changed: row.try_get("", "files_changed").ok(),
             human_time_ms: row.try_get("", "human_time_ms").ok(),
+            duration_ms: row.try_get("", "duration_ms").ok(),
+            memory_id: row.try_get("", "memory_id").ok(),
+            plugin_name: row.try_get("", "plugin_name").ok(),
             indexed_at: row.try_get("", "indexed_at").ok(),
         });
     }
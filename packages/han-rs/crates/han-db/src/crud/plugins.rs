@@ -0,0 +1,46 @@
+//! CRUD operations for plugins.
+
+use crate::entities::plugins;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Insert or update the plugin named `name`.
+///
+/// Reinstalling a plugin refreshes its version, description and
+/// `installed_at`.
+pub async fn upsert(
+    db: &DatabaseConnection,
+    name: String,
+    version: String,
+    description: Option<String>,
+    installed_at: String,
+) -> DbResult<()> {
+    plugins::Entity::insert(plugins::ActiveModel {
+        name: Set(name),
+        version: Set(version),
+        description: Set(description),
+        installed_at: Set(installed_at),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(plugins::Column::Name)
+            .update_columns([
+                plugins::Column::Version,
+                plugins::Column::Description,
+                plugins::Column::InstalledAt,
+            ])
+            .to_owned(),
+    )
+    .exec(db)
+    .await
+    .map_err(DbError::Database)?;
+    Ok(())
+}
+
+/// Every installed plugin, by name.
+pub async fn list(db: &DatabaseConnection) -> DbResult<Vec<plugins::Model>> {
+    plugins::Entity::find()
+        .order_by_asc(plugins::Column::Name)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
@@ -0,0 +1,204 @@
+//! CRUD operations for project_stats_cache.
+
+use crate::aggregates::{ModelTokenTotals, MESSAGE_MODEL_SQL};
+use crate::entities::{project_stats_cache, projects};
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Aggregate a project's sessions, messages, tool uses and hook runs.
+///
+/// Token sums cover assistant messages only. Session duration is measured
+/// from a session's first to last message; active days count distinct
+/// message dates.
+const PROJECT_STATS_SQL: &str = "WITH ps AS (SELECT id FROM sessions WHERE project_id = ?1), \
+     pm AS (SELECT m.* FROM messages m JOIN ps ON ps.id = m.session_id), \
+     span AS (SELECT session_id, MIN(timestamp) AS started, MAX(timestamp) AS ended FROM pm GROUP BY session_id) \
+     SELECT \
+       (SELECT COUNT(*) FROM ps) AS total_sessions, \
+       (SELECT COUNT(*) FROM pm) AS total_messages, \
+       (SELECT COALESCE(SUM(input_tokens), 0) FROM pm WHERE message_type = 'assistant') AS it, \
+       (SELECT COALESCE(SUM(output_tokens), 0) FROM pm WHERE message_type = 'assistant') AS ot, \
+       (SELECT COALESCE(SUM(cache_read_tokens), 0) FROM pm WHERE message_type = 'assistant') AS crt, \
+       (SELECT COALESCE(SUM(cache_creation_tokens), 0) FROM pm WHERE message_type = 'assistant') AS cct, \
+       (SELECT json_group_array(tool_name) FROM (SELECT DISTINCT tool_name FROM pm WHERE message_type = 'tool_use' AND tool_name IS NOT NULL ORDER BY tool_name)) AS tools, \
+       (SELECT COUNT(*) FROM hook_executions h JOIN ps ON ps.id = h.session_id) AS hook_runs, \
+       (SELECT COALESCE(SUM(h.passed), 0) FROM hook_executions h JOIN ps ON ps.id = h.session_id) AS hook_passes, \
+       (SELECT COALESCE(AVG((julianday(ended) - julianday(started)) * 1440.0), 0.0) FROM span) AS avg_minutes, \
+       (SELECT COUNT(DISTINCT DATE(timestamp)) FROM pm) AS active_days, \
+       (SELECT MIN(started) FROM span) AS first_at, \
+       (SELECT MAX(ended) FROM span) AS last_at";
+
+/// Sum a project's assistant message tokens per model ID.
+async fn query_model_tokens(
+    db: &DatabaseConnection,
+    project_id: &str,
+) -> DbResult<Vec<ModelTokenTotals>> {
+    let sql = format!(
+        "SELECT {MESSAGE_MODEL_SQL} as model, \
+         COALESCE(SUM(m.input_tokens), 0) as it, COALESCE(SUM(m.output_tokens), 0) as ot, \
+         COALESCE(SUM(m.cache_read_tokens), 0) as crt, COALESCE(SUM(m.cache_creation_tokens), 0) as cct \
+         FROM messages m JOIN sessions s ON s.id = m.session_id \
+         WHERE s.project_id = ? AND m.message_type = 'assistant' \
+         GROUP BY model ORDER BY model"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            vec![project_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(rows
+        .iter()
+        .map(|r| ModelTokenTotals {
+            model: r.try_get::<Option<String>>("", "model").ok().flatten(),
+            input_tokens: r.try_get::<i64>("", "it").unwrap_or(0),
+            output_tokens: r.try_get::<i64>("", "ot").unwrap_or(0),
+            cache_read_tokens: r.try_get::<i64>("", "crt").unwrap_or(0),
+            cache_creation_tokens: r.try_get::<i64>("", "cct").unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Compute a project's stats without touching the cache.
+pub async fn compute(
+    db: &DatabaseConnection,
+    project_id: &str,
+) -> DbResult<project_stats_cache::Model> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            PROJECT_STATS_SQL,
+            vec![project_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?
+        .ok_or_else(|| DbError::NotFound(format!("stats for project {project_id}")))?;
+    let by_model = query_model_tokens(db, project_id).await?;
+
+    let count = |col: &str| row.try_get::<i64>("", col).unwrap_or(0);
+    Ok(project_stats_cache::Model {
+        project_id: project_id.to_string(),
+        total_sessions: count("total_sessions"),
+        total_messages: count("total_messages"),
+        input_tokens: count("it"),
+        output_tokens: count("ot"),
+        cache_read_tokens: count("crt"),
+        cache_creation_tokens: count("cct"),
+        tokens_by_model: serde_json::to_string(&by_model)?,
+        unique_tools: row
+            .try_get::<String>("", "tools")
+            .unwrap_or_else(|_| "[]".to_string()),
+        hook_runs: count("hook_runs"),
+        hook_passes: count("hook_passes"),
+        average_session_duration_minutes: row.try_get::<f64>("", "avg_minutes").unwrap_or(0.0),
+        active_days: count("active_days"),
+        first_session_at: row.try_get::<Option<String>>("", "first_at").ok().flatten(),
+        last_session_at: row.try_get::<Option<String>>("", "last_at").ok().flatten(),
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Recompute a project's stats and store them in the cache.
+pub async fn refresh(
+    db: &DatabaseConnection,
+    project_id: &str,
+) -> DbResult<project_stats_cache::Model> {
+    let stats = compute(db, project_id).await?;
+    let model: project_stats_cache::ActiveModel = stats.clone().into();
+    project_stats_cache::Entity::insert(model)
+        .on_conflict(
+            sea_query::OnConflict::column(project_stats_cache::Column::ProjectId)
+                .update_columns([
+                    project_stats_cache::Column::TotalSessions,
+                    project_stats_cache::Column::TotalMessages,
+                    project_stats_cache::Column::InputTokens,
+                    project_stats_cache::Column::OutputTokens,
+                    project_stats_cache::Column::CacheReadTokens,
+                    project_stats_cache::Column::CacheCreationTokens,
+                    project_stats_cache::Column::TokensByModel,
+                    project_stats_cache::Column::UniqueTools,
+                    project_stats_cache::Column::HookRuns,
+                    project_stats_cache::Column::HookPasses,
+                    project_stats_cache::Column::AverageSessionDurationMinutes,
+                    project_stats_cache::Column::ActiveDays,
+                    project_stats_cache::Column::FirstSessionAt,
+                    project_stats_cache::Column::LastSessionAt,
+                    project_stats_cache::Column::ComputedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(stats)
+}
+
+/// Cached stats for a project, computing them first if none are cached.
+pub async fn get_or_refresh(
+    db: &DatabaseConnection,
+    project_id: &str,
+) -> DbResult<project_stats_cache::Model> {
+    let cached = project_stats_cache::Entity::find_by_id(project_id)
+        .one(db)
+        .await
+        .map_err(DbError::Database)?;
+    match cached {
+        Some(stats) => Ok(stats),
+        None => refresh(db, project_id).await,
+    }
+}
+
+/// Cached stats for every project, most recently active first.
+///
+/// Projects missing from the cache are computed on the way. Projects
+/// without sessions sort last.
+pub async fn list(
+    db: &DatabaseConnection,
+    limit: Option<u64>,
+) -> DbResult<Vec<(projects::Model, project_stats_cache::Model)>> {
+    let all = projects::Entity::find()
+        .all(db)
+        .await
+        .map_err(DbError::Database)?;
+
+    let mut rows = Vec::with_capacity(all.len());
+    for project in all {
+        let stats = get_or_refresh(db, &project.id).await?;
+        rows.push((project, stats));
+    }
+    rows.sort_by(|a, b| b.1.last_session_at.cmp(&a.1.last_session_at));
+    if let Some(limit) = limit {
+        rows.truncate(limit as usize);
+    }
+    Ok(rows)
+}
+
+/// Recompute stats for every project. Returns the number refreshed.
+pub async fn refresh_all(db: &DatabaseConnection) -> DbResult<usize> {
+    let ids: Vec<String> = projects::Entity::find()
+        .select_only()
+        .column(projects::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(DbError::Database)?;
+    for id in &ids {
+        refresh(db, id).await?;
+    }
+    Ok(ids.len())
+}
+
+/// Drop the cached stats of the project a session belongs to.
+pub async fn invalidate_for_session(db: &DatabaseConnection, session_id: &str) -> DbResult<u64> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "DELETE FROM project_stats_cache WHERE project_id = (SELECT project_id FROM sessions WHERE id = ?)",
+            vec![session_id.into()],
+        ))
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected())
+}
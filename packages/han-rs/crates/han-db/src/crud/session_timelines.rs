@@ -0,0 +1,55 @@
+//! CRUD operations for session_timelines.
+//!
+//! The timeline itself lives in han-indexer, so rows are stored and returned
+//! as JSON.
+
+use crate::entities::session_timelines;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Cached task timeline JSON for a session.
+pub async fn get_task_timeline(
+    db: &DatabaseConnection,
+    session_id: &str,
+) -> DbResult<Option<String>> {
+    let row = session_timelines::Entity::find_by_id(session_id)
+        .one(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(row.map(|r| r.timeline_json))
+}
+
+/// Store the task timeline JSON for a session, replacing any earlier entry.
+pub async fn store_task_timeline(
+    db: &DatabaseConnection,
+    session_id: &str,
+    timeline_json: String,
+) -> DbResult<()> {
+    let model = session_timelines::ActiveModel {
+        session_id: Set(session_id.to_string()),
+        timeline_json: Set(timeline_json),
+        computed_at: Set(chrono::Utc::now().to_rfc3339()),
+    };
+    session_timelines::Entity::insert(model)
+        .on_conflict(
+            sea_query::OnConflict::column(session_timelines::Column::SessionId)
+                .update_columns([
+                    session_timelines::Column::TimelineJson,
+                    session_timelines::Column::ComputedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(())
+}
+
+/// Drop the cached timeline of a session whose tasks changed.
+pub async fn invalidate_task_timeline(db: &DatabaseConnection, session_id: &str) -> DbResult<u64> {
+    let result = session_timelines::Entity::delete_by_id(session_id)
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+    Ok(result.rows_affected)
+}
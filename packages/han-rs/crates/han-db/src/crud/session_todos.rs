@@ -3,6 +3,39 @@
 use crate::entities::session_todos;
 use crate::error::{DbError, DbResult};
 use sea_orm::*;
+use serde::{Deserialize, Serialize};
+
+/// A single item from a TodoWrite tool call's `todos` array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoInput {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub content: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_form: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+}
+
+/// Parse a stored `todos_json` array, skipping items that don't match [`TodoInput`].
+pub fn parse_todos(todos_json: &str) -> Vec<TodoInput> {
+    let items: Vec<serde_json::Value> = serde_json::from_str(todos_json).unwrap_or_default();
+    items
+        .into_iter()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect()
+}
+
+/// The tool call that last changed a session's todo list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TodoSource {
+    pub message_id: String,
+    pub tool_call_id: Option<String>,
+    pub timestamp: String,
+    pub line_number: i32,
+}
 
 pub async fn upsert(
     db: &DatabaseConnection,
@@ -11,30 +44,53 @@ pub async fn upsert(
     todos_json: String,
     timestamp: String,
     line_number: i32,
+) -> DbResult<session_todos::Model> {
+    let source = TodoSource {
+        message_id,
+        tool_call_id: None,
+        timestamp,
+        line_number,
+    };
+    upsert_row(db, session_id, source, Some(todos_json)).await
+}
+
+/// Write a session's todo row. With `todos_json` of `None` the stored list is
+/// kept (or started empty) and only the source columns change.
+async fn upsert_row(
+    db: &DatabaseConnection,
+    session_id: String,
+    source: TodoSource,
+    todos_json: Option<String>,
 ) -> DbResult<session_todos::Model> {
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     let session_id_clone = session_id.clone();
 
+    let mut update_columns = vec![
+        session_todos::Column::MessageId,
+        session_todos::Column::Timestamp,
+        session_todos::Column::LineNumber,
+        session_todos::Column::IndexedAt,
+        session_todos::Column::ToolCallId,
+    ];
+    if todos_json.is_some() {
+        update_columns.push(session_todos::Column::TodosJson);
+    }
+
     session_todos::Entity::insert(session_todos::ActiveModel {
         id: Set(id),
         session_id: Set(session_id),
-        message_id: Set(message_id),
-        todos_json: Set(todos_json),
-        timestamp: Set(timestamp),
-        line_number: Set(line_number),
+        message_id: Set(source.message_id),
+        todos_json: Set(todos_json.unwrap_or_else(|| "[]".to_string())),
+        timestamp: Set(source.timestamp),
+        line_number: Set(source.line_number),
         indexed_at: Set(Some(now)),
+        tool_call_id: Set(source.tool_call_id),
     })
     .on_conflict(
         sea_query::OnConflict::column(session_todos::Column::SessionId)
-            .update_columns([
-                session_todos::Column::MessageId,
-                session_todos::Column::TodosJson,
-                session_todos::Column::Timestamp,
-                session_todos::Column::LineNumber,
-                session_todos::Column::IndexedAt,
-            ])
+            .update_columns(update_columns)
             .to_owned(),
     )
     .exec(db)
@@ -50,6 +106,82 @@ pub async fn upsert(
         .ok_or(DbError::NotFound("session_todo".to_string()))
 }
 
+/// Replace a session's todo list with the items from a TodoWrite call.
+///
+/// TodoWrite always sends the full list, so the latest call wins.
+/// Returns the number of todos stored.
+pub async fn replace_todos(
+    db: &DatabaseConnection,
+    session_id: String,
+    source: TodoSource,
+    todos: Vec<TodoInput>,
+) -> DbResult<u32> {
+    let todos_json = serde_json::to_string(&todos)?;
+    upsert_row(db, session_id, source, Some(todos_json)).await?;
+    Ok(todos.len() as u32)
+}
+
+/// Point a session's todo row at `source` without touching the list,
+/// creating an empty list if the session has none yet.
+pub async fn record_source(
+    db: &DatabaseConnection,
+    session_id: String,
+    source: TodoSource,
+) -> DbResult<()> {
+    upsert_row(db, session_id, source, None).await?;
+    Ok(())
+}
+
+/// Merge todos from TaskCreate/TaskUpdate calls into a session's list.
+///
+/// An item whose `id` matches a stored todo updates it in place: non-empty
+/// `content` and `status` and any `active_form`/`priority` overwrite the
+/// stored values. Other items are appended unless their `content` is empty.
+/// The session's row must exist, see [`record_source`]. Returns the number
+/// of todos merged.
+pub async fn upsert_todos(
+    db: &DatabaseConnection,
+    session_id: String,
+    todos: Vec<TodoInput>,
+) -> DbResult<u32> {
+    let row = get(db, &session_id)
+        .await?
+        .ok_or(DbError::NotFound("session_todo".to_string()))?;
+    let mut stored = parse_todos(&row.todos_json);
+
+    let mut merged = 0u32;
+    for todo in todos {
+        let existing = todo
+            .id
+            .as_ref()
+            .and_then(|id| stored.iter_mut().find(|t| t.id.as_ref() == Some(id)));
+        match existing {
+            Some(t) => {
+                if !todo.content.is_empty() {
+                    t.content = todo.content;
+                }
+                if !todo.status.is_empty() {
+                    t.status = todo.status;
+                }
+                if todo.active_form.is_some() {
+                    t.active_form = todo.active_form;
+                }
+                if todo.priority.is_some() {
+                    t.priority = todo.priority;
+                }
+            }
+            None if todo.content.is_empty() => continue,
+            None => stored.push(todo),
+        }
+        merged += 1;
+    }
+
+    let mut active: session_todos::ActiveModel = row.into();
+    active.todos_json = Set(serde_json::to_string(&stored)?);
+    active.update(db).await.map_err(DbError::Database)?;
+    Ok(merged)
+}
+
 pub async fn get(db: &DatabaseConnection, session_id: &str) -> DbResult<Option<session_todos::Model>> {
     session_todos::Entity::find()
         .filter(session_todos::Column::SessionId.eq(session_id))
@@ -1,9 +1,10 @@
 //! CRUD operations for sessions.
 
-use crate::entities::sessions;
+use crate::entities::{projects, sessions};
 use crate::error::{DbError, DbResult};
 use sea_orm::*;
 use sea_orm::sea_query::Expr;
+use std::collections::HashMap;
 
 pub async fn upsert(
     db: &DatabaseConnection,
@@ -27,6 +28,7 @@ pub async fn upsert(
         pr_number: Set(None),
         pr_url: Set(None),
         team_name: Set(None),
+        worktree_id: Set(None),
     })
     .on_conflict(
         sea_query::OnConflict::column(sessions::Column::Id)
@@ -101,6 +103,153 @@ pub async fn list(
     query.all(db).await.map_err(DbError::Database)
 }
 
+/// Filters for [`list_with_filters`] and [`count_with_filters`]. Unset
+/// fields don't filter.
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    /// Project path, matched exactly or as a parent directory so worktrees
+    /// nested under a project are included.
+    pub project_dir: Option<String>,
+    /// Keep sessions whose last message is at or after this timestamp.
+    /// Timestamps are compared as instants, so any UTC offset works.
+    pub after: Option<String>,
+    /// Keep sessions whose first message is before this timestamp.
+    pub before: Option<String>,
+    /// Keep sessions with a message recorded on this git branch.
+    pub git_branch: Option<String>,
+    pub status: Option<String>,
+    pub min_message_count: Option<i32>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+pub(crate) const LAST_MESSAGE_AT: &str =
+    "(SELECT MAX(timestamp) FROM messages WHERE messages.session_id = sessions.id)";
+const FIRST_MESSAGE_AT: &str =
+    "(SELECT MIN(timestamp) FROM messages WHERE messages.session_id = sessions.id)";
+
+fn filtered(filter: &SessionFilter) -> Select<sessions::Entity> {
+    let mut query = sessions::Entity::find();
+
+    if let Some(dir) = &filter.project_dir {
+        let dir = dir.trim_end_matches('/');
+        query = query.filter(Expr::cust_with_values(
+            "sessions.project_id IN (SELECT id FROM projects \
+             WHERE path = ? OR substr(path, 1, length(?) + 1) = ? || '/')",
+            [dir, dir, dir],
+        ));
+    }
+    if let Some(after) = &filter.after {
+        let sql = format!("julianday({LAST_MESSAGE_AT}) >= julianday(?)");
+        query = query.filter(Expr::cust_with_values(sql, [after.clone()]));
+    }
+    if let Some(before) = &filter.before {
+        let sql = format!("julianday({FIRST_MESSAGE_AT}) < julianday(?)");
+        query = query.filter(Expr::cust_with_values(sql, [before.clone()]));
+    }
+    if let Some(branch) = &filter.git_branch {
+        query = query.filter(Expr::cust_with_values(
+            "EXISTS (SELECT 1 FROM messages WHERE messages.session_id = sessions.id \
+             AND json_extract(messages.raw_json, '$.gitBranch') = ?)",
+            [branch.clone()],
+        ));
+    }
+    if let Some(status) = &filter.status {
+        query = query.filter(sessions::Column::Status.eq(status.as_str()));
+    }
+    if let Some(min) = filter.min_message_count {
+        query = query.filter(Expr::cust_with_values(
+            "(SELECT COUNT(*) FROM messages WHERE messages.session_id = sessions.id) >= ?",
+            [min],
+        ));
+    }
+    query
+}
+
+/// List sessions matching `filter`, most recently active first.
+///
+/// Sessions without messages sort last, and sessions sharing a timestamp are
+/// ordered by ID so results are stable.
+pub async fn list_with_filters(
+    db: &DatabaseConnection,
+    filter: SessionFilter,
+) -> DbResult<Vec<sessions::Model>> {
+    let mut query = filtered(&filter)
+        .order_by(Expr::cust(LAST_MESSAGE_AT), Order::Desc)
+        .order_by_asc(sessions::Column::Id);
+    if let Some(l) = filter.limit {
+        query = query.limit(l as u64);
+    }
+    if let Some(o) = filter.offset {
+        query = query.offset(o as u64);
+    }
+
+    query.all(db).await.map_err(DbError::Database)
+}
+
+/// Count sessions matching `filter`, ignoring its limit and offset.
+pub async fn count_with_filters(db: &DatabaseConnection, filter: SessionFilter) -> DbResult<u64> {
+    filtered(&filter).count(db).await.map_err(DbError::Database)
+}
+
+/// List sessions ordered by their latest message, most recent first.
+///
+/// `project_dir` matches the project path exactly or as a parent directory,
+/// so worktrees nested under a project are included. Sessions without
+/// messages are skipped unless `include_empty` is set; `active_since`
+/// keeps only sessions with a message at or after that timestamp.
+pub async fn list_recent(
+    db: &DatabaseConnection,
+    project_dir: Option<&str>,
+    include_empty: bool,
+    active_since: Option<&str>,
+    limit: Option<u64>,
+) -> DbResult<Vec<sessions::Model>> {
+    let filter = SessionFilter {
+        project_dir: project_dir.map(String::from),
+        after: active_since.map(String::from),
+        min_message_count: (!include_empty).then_some(1),
+        limit: limit.map(|l| l as u32),
+        ..Default::default()
+    };
+    list_with_filters(db, filter).await
+}
+
+/// Most recently active session for each project path in `project_dirs`,
+/// keyed by that path.
+///
+/// Paths are matched exactly. Activity is measured by latest message like
+/// [`list_with_filters`], with ties going to the lowest session ID. Paths
+/// without sessions are absent from the map.
+pub async fn get_latest_session_per_project(
+    db: &DatabaseConnection,
+    project_dirs: &[String],
+) -> DbResult<HashMap<String, sessions::Model>> {
+    if project_dirs.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let placeholders = vec!["?"; project_dirs.len()].join(", ");
+    let latest = format!(
+        "sessions.id IN (SELECT id FROM (\
+         SELECT s.id, ROW_NUMBER() OVER (PARTITION BY p.path ORDER BY \
+         (SELECT MAX(timestamp) FROM messages WHERE messages.session_id = s.id) DESC, s.id) AS rn \
+         FROM sessions s JOIN projects p ON p.id = s.project_id \
+         WHERE p.path IN ({placeholders})) ranked WHERE rn = 1)"
+    );
+    let rows = sessions::Entity::find()
+        .filter(Expr::cust_with_values(latest, project_dirs.iter().cloned()))
+        .find_also_related(projects::Entity)
+        .all(db)
+        .await
+        .map_err(DbError::Database)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(session, project)| project.map(|p| (p.path, session)))
+        .collect())
+}
+
 pub async fn update_last_indexed_line(db: &DatabaseConnection, session_id: &str, line_number: i32) -> DbResult<bool> {
     let res = sessions::Entity::update_many()
         .col_expr(sessions::Column::LastIndexedLine, Expr::value(line_number))
@@ -144,6 +293,22 @@ pub async fn update_team_name(
     Ok(res.rows_affected > 0)
 }
 
+/// Link a session to the worktree it ran in.
+pub async fn update_worktree(
+    db: &DatabaseConnection,
+    session_id: &str,
+    worktree_id: &str,
+) -> DbResult<bool> {
+    let res = sessions::Entity::update_many()
+        .col_expr(sessions::Column::WorktreeId, Expr::value(worktree_id))
+        .filter(sessions::Column::Id.eq(session_id))
+        .exec(db)
+        .await
+        .map_err(DbError::Database)?;
+
+    Ok(res.rows_affected > 0)
+}
+
 pub async fn reset_all_for_reindex(db: &DatabaseConnection) -> DbResult<u64> {
     let res = sessions::Entity::update_many()
         .col_expr(sessions::Column::LastIndexedLine, Expr::value(0))
@@ -0,0 +1,80 @@
+//! CRUD operations for subscriptions.
+
+use crate::entities::subscriptions;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Subscription fields taken from a Stripe subscription object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StripeSubscriptionData {
+    pub stripe_subscription_id: String,
+    pub plan: String,
+    pub status: String,
+    pub seats: i32,
+    /// RFC 3339 timestamps.
+    pub current_period_end: Option<String>,
+    pub cancel_at: Option<String>,
+}
+
+/// Insert or update the subscription identified by its Stripe id.
+///
+/// Stripe retries webhooks and may deliver the same event more than once, so
+/// a repeated call updates the existing row and keeps its id and
+/// `created_at`.
+pub async fn upsert_subscription(
+    db: &DatabaseConnection,
+    org_id: &str,
+    stripe_data: StripeSubscriptionData,
+) -> DbResult<subscriptions::Model> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let stripe_subscription_id = stripe_data.stripe_subscription_id.clone();
+
+    subscriptions::Entity::insert(subscriptions::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        org_id: Set(org_id.to_string()),
+        stripe_subscription_id: Set(stripe_data.stripe_subscription_id),
+        plan: Set(stripe_data.plan),
+        status: Set(stripe_data.status),
+        seats: Set(stripe_data.seats),
+        current_period_end: Set(stripe_data.current_period_end),
+        cancel_at: Set(stripe_data.cancel_at),
+        created_at: Set(now.clone()),
+        updated_at: Set(now),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(subscriptions::Column::StripeSubscriptionId)
+            .update_columns([
+                subscriptions::Column::OrgId,
+                subscriptions::Column::Plan,
+                subscriptions::Column::Status,
+                subscriptions::Column::Seats,
+                subscriptions::Column::CurrentPeriodEnd,
+                subscriptions::Column::CancelAt,
+                subscriptions::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(db)
+    .await
+    .map_err(DbError::Database)?;
+
+    subscriptions::Entity::find()
+        .filter(subscriptions::Column::StripeSubscriptionId.eq(&stripe_subscription_id))
+        .one(db)
+        .await
+        .map_err(DbError::Database)?
+        .ok_or(DbError::NotFound("subscription".to_string()))
+}
+
+/// Most recently updated subscription of `org_id`.
+pub async fn get_subscription(
+    db: &DatabaseConnection,
+    org_id: &str,
+) -> DbResult<Option<subscriptions::Model>> {
+    subscriptions::Entity::find()
+        .filter(subscriptions::Column::OrgId.eq(org_id))
+        .order_by_desc(subscriptions::Column::UpdatedAt)
+        .one(db)
+        .await
+        .map_err(DbError::Database)
+}
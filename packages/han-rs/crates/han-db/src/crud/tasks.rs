@@ -35,6 +35,15 @@ pub async fn create(
     Ok(result)
 }
 
+/// Insert a fully specified task, e.g. one recorded by hand rather than
+/// through the MCP tools.
+pub async fn insert(db: &DatabaseConnection, task: tasks::Model) -> DbResult<tasks::Model> {
+    tasks::Entity::insert(tasks::ActiveModel::from(task))
+        .exec_with_returning(db)
+        .await
+        .map_err(DbError::Database)
+}
+
 pub async fn complete(
     db: &DatabaseConnection,
     task_id: &str,
@@ -98,6 +107,30 @@ pub async fn fail(
     Ok(Some(result))
 }
 
+/// Mark a task finished with `outcome`, keeping its notes and files.
+///
+/// `confidence` replaces the stored value only when given.
+pub async fn set_outcome(
+    db: &DatabaseConnection,
+    task_id: &str,
+    outcome: String,
+    confidence: Option<f64>,
+) -> DbResult<Option<tasks::Model>> {
+    let Some(existing) = get(db, task_id).await? else {
+        return Ok(None);
+    };
+
+    let mut active: tasks::ActiveModel = existing.into();
+    active.outcome = Set(Some(outcome));
+    if confidence.is_some() {
+        active.confidence = Set(confidence);
+    }
+    active.completed_at = Set(Some(chrono::Utc::now().to_rfc3339()));
+
+    let result = active.update(db).await.map_err(DbError::Database)?;
+    Ok(Some(result))
+}
+
 pub async fn get(db: &DatabaseConnection, task_id: &str) -> DbResult<Option<tasks::Model>> {
     tasks::Entity::find()
         .filter(tasks::Column::TaskId.eq(task_id))
@@ -1,6 +1,6 @@
 //! CRUD operations for tool_call_results.
 
-use crate::entities::tool_call_results;
+use crate::entities::{messages, tool_call_results};
 use crate::error::{DbError, DbResult};
 use sea_orm::*;
 
@@ -50,3 +50,32 @@ pub async fn get_batch(
         .await
         .map_err(DbError::Database)
 }
+
+/// Timestamps of the messages carrying each tool call's result, as
+/// `(tool_call_id, timestamp)` pairs. Results whose message isn't indexed are
+/// omitted.
+pub async fn get_completion_times(
+    db: &DatabaseConnection,
+    tool_call_ids: Vec<String>,
+) -> DbResult<Vec<(String, String)>> {
+    if tool_call_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    tool_call_results::Entity::find()
+        .select_only()
+        .column(tool_call_results::Column::ToolCallId)
+        .column(messages::Column::Timestamp)
+        .join(
+            JoinType::InnerJoin,
+            tool_call_results::Entity::belongs_to(messages::Entity)
+                .from(tool_call_results::Column::MessageId)
+                .to(messages::Column::Id)
+                .into(),
+        )
+        .filter(tool_call_results::Column::ToolCallId.is_in(tool_call_ids))
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
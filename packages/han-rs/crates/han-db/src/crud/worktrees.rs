@@ -0,0 +1,70 @@
+//! CRUD operations for worktrees.
+
+use crate::entities::worktrees;
+use crate::error::{DbError, DbResult};
+use sea_orm::*;
+
+/// Insert or update the worktree checked out at `project_dir`.
+///
+/// A worktree seen again keeps its id and `created_at`; its main checkout,
+/// branch and name are refreshed and `last_seen_at` moves to now.
+pub async fn upsert(
+    db: &DatabaseConnection,
+    project_dir: String,
+    main_project_dir: String,
+    branch_name: Option<String>,
+    worktree_name: String,
+) -> DbResult<worktrees::Model> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let project_dir_clone = project_dir.clone();
+
+    worktrees::Entity::insert(worktrees::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        project_dir: Set(project_dir),
+        main_project_dir: Set(main_project_dir),
+        branch_name: Set(branch_name),
+        worktree_name: Set(worktree_name),
+        created_at: Set(now.clone()),
+        last_seen_at: Set(now),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(worktrees::Column::ProjectDir)
+            .update_columns([
+                worktrees::Column::MainProjectDir,
+                worktrees::Column::BranchName,
+                worktrees::Column::WorktreeName,
+                worktrees::Column::LastSeenAt,
+            ])
+            .to_owned(),
+    )
+    .exec(db)
+    .await
+    .map_err(DbError::Database)?;
+
+    worktrees::Entity::find()
+        .filter(worktrees::Column::ProjectDir.eq(&project_dir_clone))
+        .one(db)
+        .await
+        .map_err(DbError::Database)?
+        .ok_or(DbError::NotFound("worktree".to_string()))
+}
+
+pub async fn get(db: &DatabaseConnection, id: &str) -> DbResult<Option<worktrees::Model>> {
+    worktrees::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(DbError::Database)
+}
+
+/// Worktrees added from the checkout at `main_project_dir`, by name.
+pub async fn list_for_main_project(
+    db: &DatabaseConnection,
+    main_project_dir: &str,
+) -> DbResult<Vec<worktrees::Model>> {
+    worktrees::Entity::find()
+        .filter(worktrees::Column::MainProjectDir.eq(main_project_dir))
+        .order_by_asc(worktrees::Column::WorktreeName)
+        .all(db)
+        .await
+        .map_err(DbError::Database)
+}
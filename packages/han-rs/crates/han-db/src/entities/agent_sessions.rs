@@ -0,0 +1,34 @@
+//! Entity: agent_sessions (links sub-agent sessions to the session that spawned them)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "agent_sessions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub parent_session_id: String,
+    #[sea_orm(unique)]
+    pub child_session_id: String,
+    pub task_tool_call_id: Option<String>,
+    pub spawned_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::sessions::Entity",
+        from = "Column::ParentSessionId",
+        to = "super::sessions::Column::Id"
+    )]
+    ParentSession,
+}
+
+impl Related<super::sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ParentSession.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,28 @@
+//! Entity: archived_sessions (sessions moved out of `sessions` by age)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A `sessions` row plus the time it was archived.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "archived_sessions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub project_id: Option<String>,
+    pub status: Option<String>,
+    pub slug: Option<String>,
+    pub transcript_path: Option<String>,
+    pub source_config_dir: Option<String>,
+    pub last_indexed_line: Option<i32>,
+    pub pr_number: Option<i32>,
+    pub pr_url: Option<String>,
+    pub team_name: Option<String>,
+    pub worktree_id: Option<String>,
+    pub archived_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
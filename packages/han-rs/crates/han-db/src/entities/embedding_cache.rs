@@ -0,0 +1,22 @@
+//! Entity: embedding_cache (embeddings memoized by content hash)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "embedding_cache")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub content_hash: String,
+    /// Little-endian `f32` values, `dimension` of them.
+    #[sea_orm(column_type = "Blob")]
+    pub embedding: Vec<u8>,
+    pub model_name: String,
+    pub dimension: i32,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -24,6 +24,7 @@ pub struct Model {
     pub raw_json: Option<String>,
     pub timestamp: String,
     pub line_number: i32,
+    pub byte_offset: Option<i64>,
     pub source_file_name: Option<String>,
     pub source_file_type: Option<String>,
     pub sentiment_score: Option<f64>,
@@ -38,6 +39,10 @@ pub struct Model {
     pub lines_removed: Option<i32>,
     pub files_changed: Option<i32>,
     pub human_time_ms: Option<i32>,
+    pub duration_ms: Option<i32>,
+    pub memory_id: Option<String>,
+    /// Plugin a hook event ran for, from its `data.plugin`.
+    pub plugin_name: Option<String>,
     pub indexed_at: Option<String>,
 }
 
@@ -49,6 +54,12 @@ pub enum Relation {
         to = "super::sessions::Column::Id"
     )]
     Session,
+    #[sea_orm(
+        belongs_to = "super::memories::Entity",
+        from = "Column::MemoryId",
+        to = "super::memories::Column::Id"
+    )]
+    Memory,
 }
 
 impl Related<super::sessions::Entity> for Entity {
@@ -57,4 +68,10 @@ impl Related<super::sessions::Entity> for Entity {
     }
 }
 
+impl Related<super::memories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Memory.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -22,6 +22,18 @@ pub mod async_hook_queue;
 pub mod generated_session_summaries;
 
 pub mod tool_call_results;
+pub mod agent_sessions;
+pub mod session_index_errors;
+pub mod session_index_warnings;
+pub mod project_stats_cache;
+pub mod embedding_cache;
+pub mod session_timelines;
+pub mod indexing_checkpoints;
+pub mod worktrees;
+pub mod memories;
+pub mod plugins;
+pub mod archived_sessions;
+pub mod archived_messages;
 
 // Team/hosted mode entities
 pub mod users;
@@ -31,3 +43,4 @@ pub mod api_keys;
 pub mod synced_sessions;
 pub mod team_invites;
 pub mod encryption_keys;
+pub mod subscriptions;
@@ -0,0 +1,21 @@
+//! Entity: plugins (installed Han plugins)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "plugins")]
+pub struct Model {
+    /// Plugin name, as recorded in hook events' `plugin` field.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    pub version: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub installed_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+//! Entity: project_stats_cache (aggregated metrics per project)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "project_stats_cache")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub project_id: String,
+    pub total_sessions: i64,
+    pub total_messages: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    /// JSON array of per-model token totals, for pricing.
+    #[sea_orm(column_type = "Text")]
+    pub tokens_by_model: String,
+    /// JSON array of distinct tool names, sorted.
+    #[sea_orm(column_type = "Text")]
+    pub unique_tools: String,
+    pub hook_runs: i64,
+    pub hook_passes: i64,
+    pub average_session_duration_minutes: f64,
+    pub active_days: i64,
+    pub first_session_at: Option<String>,
+    pub last_session_at: Option<String>,
+    pub computed_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::projects::Entity",
+        from = "Column::ProjectId",
+        to = "super::projects::Column::Id"
+    )]
+    Project,
+}
+
+impl Related<super::projects::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
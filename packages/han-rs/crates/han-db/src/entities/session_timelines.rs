@@ -0,0 +1,20 @@
+//! Entity: session_timelines (cached task timeline per session)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "session_timelines")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub session_id: String,
+    /// Serialized `han_indexer::TaskTimeline`.
+    #[sea_orm(column_type = "Text")]
+    pub timeline_json: String,
+    pub computed_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -16,6 +16,7 @@ pub struct Model {
     pub timestamp: String,
     pub line_number: i32,
     pub indexed_at: Option<String>,
+    pub tool_call_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
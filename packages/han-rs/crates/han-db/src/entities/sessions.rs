@@ -17,6 +17,7 @@ pub struct Model {
     pub pr_number: Option<i32>,
     pub pr_url: Option<String>,
     pub team_name: Option<String>,
+    pub worktree_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,6 +28,12 @@ pub enum Relation {
         to = "super::projects::Column::Id"
     )]
     Project,
+    #[sea_orm(
+        belongs_to = "super::worktrees::Entity",
+        from = "Column::WorktreeId",
+        to = "super::worktrees::Column::Id"
+    )]
+    Worktree,
     #[sea_orm(has_many = "super::session_files::Entity")]
     SessionFiles,
     #[sea_orm(has_many = "super::messages::Entity")]
@@ -63,6 +70,12 @@ impl Related<super::projects::Entity> for Entity {
     }
 }
 
+impl Related<super::worktrees::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Worktree.def()
+    }
+}
+
 impl Related<super::messages::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Messages.def()
@@ -0,0 +1,41 @@
+//! Stripe subscription state for a team (hosted mode).
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "subscriptions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Team that owns the subscription.
+    pub org_id: String,
+    #[sea_orm(unique)]
+    pub stripe_subscription_id: String,
+    pub plan: String,
+    /// Raw Stripe status (`active`, `past_due`, `canceled`, ...).
+    pub status: String,
+    pub seats: i32,
+    pub current_period_end: Option<String>,
+    pub cancel_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::teams::Entity",
+        from = "Column::OrgId",
+        to = "super::teams::Column::Id"
+    )]
+    Org,
+}
+
+impl Related<super::teams::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Org.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+//! Entity: worktrees (git worktrees that sessions ran in)
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "worktrees")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    /// Checkout directory of the worktree.
+    #[sea_orm(unique)]
+    pub project_dir: String,
+    /// Directory of the main checkout the worktree was added from.
+    pub main_project_dir: String,
+    /// Branch checked out when the worktree was last seen, if any.
+    pub branch_name: Option<String>,
+    /// Name git registered the worktree under in `.git/worktrees/`.
+    pub worktree_name: String,
+    pub created_at: String,
+    pub last_seen_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::sessions::Entity")]
+    Sessions,
+}
+
+impl Related<super::sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -6,9 +6,31 @@ pub mod m20260220_add_session_pr_team;
 pub mod m20260222_tool_call_results;
 pub mod m20260223_performance_indexes;
 pub mod m20260401_human_time_estimation;
+pub mod m20260410_agent_sessions;
+pub mod m20260412_session_todos_tool_call_id;
+pub mod m20260414_message_byte_offset;
+pub mod m20260416_sessions_fts;
+pub mod m20260418_session_delete_cascade;
+pub mod m20260419_session_index_errors;
+pub mod m20260420_message_duration_ms;
+pub mod m20260421_project_stats_cache;
+pub mod m20260422_session_index_warnings;
+pub mod m20260423_embedding_cache;
+pub mod m20260424_archived_sessions;
+pub mod m20260425_subscriptions;
+pub mod m20260426_session_timelines;
+pub mod m20260427_indexing_checkpoints;
+pub mod m20260428_worktrees;
+pub mod m20260429_memories;
+pub mod m20260430_plugins;
 
-use sea_orm::DatabaseConnection;
+use crate::entities;
+use crate::error::{DbError, DbResult};
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, Iterable, Statement,
+};
 use sea_orm_migration::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub struct Migrator;
 
@@ -22,6 +44,23 @@ impl MigratorTrait for Migrator {
             Box::new(m20260222_tool_call_results::Migration),
             Box::new(m20260223_performance_indexes::Migration),
             Box::new(m20260401_human_time_estimation::Migration),
+            Box::new(m20260410_agent_sessions::Migration),
+            Box::new(m20260412_session_todos_tool_call_id::Migration),
+            Box::new(m20260414_message_byte_offset::Migration),
+            Box::new(m20260416_sessions_fts::Migration),
+            Box::new(m20260418_session_delete_cascade::Migration),
+            Box::new(m20260419_session_index_errors::Migration),
+            Box::new(m20260420_message_duration_ms::Migration),
+            Box::new(m20260421_project_stats_cache::Migration),
+            Box::new(m20260422_session_index_warnings::Migration),
+            Box::new(m20260423_embedding_cache::Migration),
+            Box::new(m20260424_archived_sessions::Migration),
+            Box::new(m20260425_subscriptions::Migration),
+            Box::new(m20260426_session_timelines::Migration),
+            Box::new(m20260427_indexing_checkpoints::Migration),
+            Box::new(m20260428_worktrees::Migration),
+            Box::new(m20260429_memories::Migration),
+            Box::new(m20260430_plugins::Migration),
         ]
     }
 }
@@ -30,3 +69,168 @@ impl MigratorTrait for Migrator {
 pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
     Migrator::up(db, None).await
 }
+
+/// Version of a migration, parsed from its `mYYYYMMDD[_NNNNNN]_name` name.
+///
+/// The optional six-digit sequence keeps same-day migrations ordered:
+/// `m20260215_000002_team_entities` is `20260215000002`, while
+/// `m20260220_add_session_pr_team` is `20260220000000`.
+pub fn migration_version(name: &str) -> Option<u64> {
+    let mut parts = name.strip_prefix('m')?.split('_');
+    let date = parts.next().filter(|d| d.len() == 8)?.parse::<u64>().ok()?;
+    let seq = parts
+        .next()
+        .filter(|s| s.len() == 6)
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(0);
+    Some(date * 1_000_000 + seq)
+}
+
+/// Version of the most recently applied migration, or 0 if none are applied.
+pub async fn get_current_version(db: &DatabaseConnection) -> DbResult<u64> {
+    let applied = Migrator::get_applied_migrations(db).await?;
+    Ok(applied
+        .iter()
+        .filter_map(|m| migration_version(m.name()))
+        .max()
+        .unwrap_or(0))
+}
+
+/// Roll back applied migrations, newest first, until `target_version` is current.
+///
+/// `target_version` must be 0 (roll back everything) or the version of a
+/// known migration as returned by [`migration_version`].
+pub async fn rollback_to_version(db: &DatabaseConnection, target_version: u64) -> DbResult<()> {
+    let known = Migrator::migrations()
+        .iter()
+        .any(|m| migration_version(m.name()) == Some(target_version));
+    if target_version != 0 && !known {
+        return Err(DbError::NotFound(format!(
+            "migration version {target_version}"
+        )));
+    }
+
+    while get_current_version(db).await? > target_version {
+        Migrator::down(db, Some(1)).await?;
+    }
+    Ok(())
+}
+
+/// Differences between the live schema and the one the entities expect.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaVerificationReport {
+    /// True when no expected table or column is missing.
+    pub is_valid: bool,
+    pub missing_tables: Vec<String>,
+    /// `(table, column)` pairs absent from tables that do exist.
+    pub missing_columns: Vec<(String, String)>,
+    /// Tables no entity maps to. Informational; they don't fail verification.
+    pub extra_tables: Vec<String>,
+}
+
+/// Table name and columns of an entity.
+fn entity_schema<E: EntityTrait>() -> (String, Vec<String>) {
+    let columns = E::Column::iter()
+        .map(|c| sea_orm::IdenStatic::as_str(&c).to_string())
+        .collect();
+    (E::default().table_name().to_string(), columns)
+}
+
+/// Tables and columns the entity definitions expect.
+fn expected_schema() -> Vec<(String, Vec<String>)> {
+    vec![
+        entity_schema::<entities::han_metadata::Entity>(),
+        entity_schema::<entities::repos::Entity>(),
+        entity_schema::<entities::config_dirs::Entity>(),
+        entity_schema::<entities::projects::Entity>(),
+        entity_schema::<entities::sessions::Entity>(),
+        entity_schema::<entities::session_files::Entity>(),
+        entity_schema::<entities::messages::Entity>(),
+        entity_schema::<entities::session_summaries::Entity>(),
+        entity_schema::<entities::session_compacts::Entity>(),
+        entity_schema::<entities::session_todos::Entity>(),
+        entity_schema::<entities::native_tasks::Entity>(),
+        entity_schema::<entities::tasks::Entity>(),
+        entity_schema::<entities::orchestrations::Entity>(),
+        entity_schema::<entities::hook_executions::Entity>(),
+        entity_schema::<entities::pending_hooks::Entity>(),
+        entity_schema::<entities::frustration_events::Entity>(),
+        entity_schema::<entities::session_file_changes::Entity>(),
+        entity_schema::<entities::session_file_validations::Entity>(),
+        entity_schema::<entities::async_hook_queue::Entity>(),
+        entity_schema::<entities::generated_session_summaries::Entity>(),
+        entity_schema::<entities::tool_call_results::Entity>(),
+        entity_schema::<entities::agent_sessions::Entity>(),
+        entity_schema::<entities::session_index_errors::Entity>(),
+        entity_schema::<entities::session_index_warnings::Entity>(),
+        entity_schema::<entities::project_stats_cache::Entity>(),
+        entity_schema::<entities::embedding_cache::Entity>(),
+        entity_schema::<entities::session_timelines::Entity>(),
+        entity_schema::<entities::indexing_checkpoints::Entity>(),
+        entity_schema::<entities::worktrees::Entity>(),
+        entity_schema::<entities::memories::Entity>(),
+        entity_schema::<entities::plugins::Entity>(),
+        entity_schema::<entities::archived_sessions::Entity>(),
+        entity_schema::<entities::archived_messages::Entity>(),
+        entity_schema::<entities::users::Entity>(),
+        entity_schema::<entities::teams::Entity>(),
+        entity_schema::<entities::team_members::Entity>(),
+        entity_schema::<entities::api_keys::Entity>(),
+        entity_schema::<entities::synced_sessions::Entity>(),
+        entity_schema::<entities::team_invites::Entity>(),
+        entity_schema::<entities::encryption_keys::Entity>(),
+        entity_schema::<entities::subscriptions::Entity>(),
+    ]
+}
+
+/// Compare the live schema against the entity definitions without running
+/// migrations.
+///
+/// Reads `sqlite_master`/`pragma_table_info` on SQLite and
+/// `information_schema.columns` on PostgreSQL. The migrations table and
+/// full-text search tables are never reported as extra.
+pub async fn verify_schema(db: &DatabaseConnection) -> DbResult<SchemaVerificationReport> {
+    let backend = db.get_database_backend();
+    let sql = match backend {
+        DatabaseBackend::Postgres => {
+            "SELECT table_name AS tbl, column_name AS col FROM information_schema.columns \
+             WHERE table_schema = current_schema()"
+        }
+        _ => {
+            "SELECT m.name AS tbl, p.name AS col FROM sqlite_master m \
+             JOIN pragma_table_info(m.name) p WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'"
+        }
+    };
+    let rows = db
+        .query_all(Statement::from_string(backend, sql.to_string()))
+        .await?;
+
+    let mut live: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for row in &rows {
+        let table = row.try_get::<String>("", "tbl")?;
+        let column = row.try_get::<String>("", "col")?;
+        live.entry(table).or_default().insert(column);
+    }
+
+    let mut report = SchemaVerificationReport::default();
+    let expected = expected_schema();
+    for (table, columns) in &expected {
+        let Some(live_columns) = live.get(table) else {
+            report.missing_tables.push(table.clone());
+            continue;
+        };
+        for column in columns {
+            if !live_columns.contains(column) {
+                report.missing_columns.push((table.clone(), column.clone()));
+            }
+        }
+    }
+    report.extra_tables = live
+        .keys()
+        .filter(|t| !expected.iter().any(|(name, _)| name == *t))
+        .filter(|t| *t != "seaql_migrations" && !t.contains("_fts"))
+        .cloned()
+        .collect();
+    report.is_valid = report.missing_tables.is_empty() && report.missing_columns.is_empty();
+    Ok(report)
+}
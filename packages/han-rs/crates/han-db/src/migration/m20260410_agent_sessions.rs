@@ -0,0 +1,76 @@
+//! Migration: Create agent_sessions table.
+//!
+//! Links sub-agent sessions (spawned via the Task tool, stored in
+//! `agent-{id}.jsonl` files) to the session that spawned them. Agent
+//! transcripts are indexed as their own session keyed by agent ID.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AgentSessions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AgentSessions::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AgentSessions::ParentSessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AgentSessions::ChildSessionId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AgentSessions::TaskToolCallId)
+                            .string()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(AgentSessions::SpawnedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_agent_sessions_parent_session_id")
+                    .table(AgentSessions::Table)
+                    .col(AgentSessions::ParentSessionId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AgentSessions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AgentSessions {
+    Table,
+    Id,
+    ParentSessionId,
+    ChildSessionId,
+    TaskToolCallId,
+    SpawnedAt,
+}
@@ -0,0 +1,39 @@
+//! Migration: Add tool_call_id to session_todos.
+//!
+//! Records which TodoWrite tool call produced the current todo list.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SessionTodos::Table)
+                    .add_column(ColumnDef::new(SessionTodos::ToolCallId).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SessionTodos::Table)
+                    .drop_column(SessionTodos::ToolCallId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SessionTodos {
+    Table,
+    ToolCallId,
+}
@@ -0,0 +1,50 @@
+//! Migration: Add sessions_fts for cross-session topic search.
+//!
+//! Indexes each session's summary, project name and first user message.
+//! Rows are maintained by `search::refresh_session_fts` after indexing;
+//! this migration backfills sessions that already have a summary.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // FTS5 is SQLite only
+        #[cfg(feature = "sqlite")]
+        {
+            let db = _manager.get_connection();
+            db.execute_unprepared(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS sessions_fts USING fts5(\
+                 session_id UNINDEXED, summary, project_name, first_user_message)",
+            )
+            .await?;
+            // Frozen copy of `search::SESSIONS_FTS_SOURCE_SQL` as of this
+            // migration, so later changes to it don't alter the backfill
+            db.execute_unprepared(
+                "INSERT INTO sessions_fts(session_id, summary, project_name, first_user_message) \
+                 SELECT ss.session_id, ss.content, p.name, \
+                 (SELECT m.content FROM messages m \
+                  WHERE m.session_id = ss.session_id AND m.message_type = 'user' AND m.content IS NOT NULL \
+                  ORDER BY m.line_number LIMIT 1) \
+                 FROM session_summaries ss \
+                 LEFT JOIN sessions s ON s.id = ss.session_id \
+                 LEFT JOIN projects p ON p.id = s.project_id",
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        #[cfg(feature = "sqlite")]
+        {
+            let db = _manager.get_connection();
+            db.execute_unprepared("DROP TABLE IF EXISTS sessions_fts")
+                .await?;
+        }
+        Ok(())
+    }
+}
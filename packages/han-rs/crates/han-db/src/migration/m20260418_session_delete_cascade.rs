@@ -0,0 +1,67 @@
+//! Migration: Cascade session deletes to per-session tables on PostgreSQL.
+//!
+//! SQLite relies on `crud::delete_session_cascade` removing child rows
+//! explicitly. On PostgreSQL, tables filled from session transcripts get a
+//! foreign key with `ON DELETE CASCADE`. Hook, task and sync tables are left
+//! out because their rows can be written before the session is indexed, as
+//! is `agent_sessions`: a parent and its sub-agents are indexed in any
+//! order, and either side records the link.
+//! The constraints are added `NOT VALID` so rows left by earlier versions
+//! without a session don't block the migration.
+
+use sea_orm::DatabaseBackend;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// `(table, column)` pairs that reference `sessions.id`.
+const CASCADE_TABLES: &[(&str, &str)] = &[
+    ("tool_call_results", "session_id"),
+    ("messages", "session_id"),
+    ("session_todos", "session_id"),
+    ("session_file_changes", "session_id"),
+    ("session_files", "session_id"),
+    ("session_summaries", "session_id"),
+    ("session_compacts", "session_id"),
+    ("generated_session_summaries", "session_id"),
+    ("native_tasks", "session_id"),
+];
+
+fn constraint_name(table: &str, column: &str) -> String {
+    format!("fk_{table}_{column}_sessions_cascade")
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        for (table, column) in CASCADE_TABLES {
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {table} ADD CONSTRAINT {} FOREIGN KEY ({column}) \
+                 REFERENCES sessions(id) ON DELETE CASCADE NOT VALID",
+                constraint_name(table, column)
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        for (table, column) in CASCADE_TABLES {
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {table} DROP CONSTRAINT IF EXISTS {}",
+                constraint_name(table, column)
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Migration: Create session_index_errors table.
+//!
+//! Records JSONL lines the indexer could not parse so they can be surfaced
+//! per session instead of being dropped silently. Rows are removed with their
+//! session.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SessionIndexErrors::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SessionIndexErrors::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexErrors::SessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexErrors::LineNumber)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexErrors::ErrorMessage)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexErrors::IndexedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SessionIndexErrors::Table, SessionIndexErrors::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_session_index_errors_session_line")
+                    .table(SessionIndexErrors::Table)
+                    .col(SessionIndexErrors::SessionId)
+                    .col(SessionIndexErrors::LineNumber)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SessionIndexErrors::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SessionIndexErrors {
+    Table,
+    Id,
+    SessionId,
+    LineNumber,
+    ErrorMessage,
+    IndexedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sessions {
+    Table,
+    Id,
+}
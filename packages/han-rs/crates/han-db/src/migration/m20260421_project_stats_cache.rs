@@ -0,0 +1,119 @@
+//! Migration: Create project_stats_cache table.
+//!
+//! Holds one row of aggregated session, token and hook metrics per project.
+//! Rows are recomputed periodically by the coordinator and dropped when one
+//! of the project's sessions is re-indexed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectStatsCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::ProjectId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(big_integer(ProjectStatsCache::TotalSessions))
+                    .col(big_integer(ProjectStatsCache::TotalMessages))
+                    .col(big_integer(ProjectStatsCache::InputTokens))
+                    .col(big_integer(ProjectStatsCache::OutputTokens))
+                    .col(big_integer(ProjectStatsCache::CacheReadTokens))
+                    .col(big_integer(ProjectStatsCache::CacheCreationTokens))
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::TokensByModel)
+                            .text()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::UniqueTools)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(big_integer(ProjectStatsCache::HookRuns))
+                    .col(big_integer(ProjectStatsCache::HookPasses))
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::AverageSessionDurationMinutes)
+                            .double()
+                            .not_null()
+                            .default(0.0),
+                    )
+                    .col(big_integer(ProjectStatsCache::ActiveDays))
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::FirstSessionAt)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::LastSessionAt)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectStatsCache::ComputedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProjectStatsCache::Table, ProjectStatsCache::ProjectId)
+                            .to(Projects::Table, Projects::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectStatsCache::Table).to_owned())
+            .await
+    }
+}
+
+/// A non-null counter column defaulting to zero.
+fn big_integer(column: ProjectStatsCache) -> ColumnDef {
+    ColumnDef::new(column)
+        .big_integer()
+        .not_null()
+        .default(0)
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum ProjectStatsCache {
+    Table,
+    ProjectId,
+    TotalSessions,
+    TotalMessages,
+    InputTokens,
+    OutputTokens,
+    CacheReadTokens,
+    CacheCreationTokens,
+    TokensByModel,
+    UniqueTools,
+    HookRuns,
+    HookPasses,
+    AverageSessionDurationMinutes,
+    ActiveDays,
+    FirstSessionAt,
+    LastSessionAt,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum Projects {
+    Table,
+    Id,
+}
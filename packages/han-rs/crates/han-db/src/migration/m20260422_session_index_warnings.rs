@@ -0,0 +1,101 @@
+//! Migration: Create session_index_warnings table.
+//!
+//! Records JSONL lines that parsed but failed the indexer's sanity checks
+//! (missing UUID, future timestamp, unknown type, oversized content). A line
+//! can carry several warnings, one row per type. Rows are removed with their
+//! session.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SessionIndexWarnings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::SessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::LineNumber)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::WarningType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::Detail)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionIndexWarnings::IndexedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SessionIndexWarnings::Table, SessionIndexWarnings::SessionId)
+                            .to(Sessions::Table, Sessions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_session_index_warnings_session_line_type")
+                    .table(SessionIndexWarnings::Table)
+                    .col(SessionIndexWarnings::SessionId)
+                    .col(SessionIndexWarnings::LineNumber)
+                    .col(SessionIndexWarnings::WarningType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SessionIndexWarnings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SessionIndexWarnings {
+    Table,
+    Id,
+    SessionId,
+    LineNumber,
+    WarningType,
+    Detail,
+    IndexedAt,
+}
+
+#[derive(DeriveIden)]
+enum Sessions {
+    Table,
+    Id,
+}
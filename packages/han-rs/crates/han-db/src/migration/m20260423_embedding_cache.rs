@@ -0,0 +1,62 @@
+//! Migration: Create embedding_cache table.
+//!
+//! Memoizes text embeddings by content hash so unchanged content is not
+//! embedded again. Each row holds the vector from the model that last
+//! embedded that content.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmbeddingCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmbeddingCache::ContentHash)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EmbeddingCache::Embedding).blob().not_null())
+                    .col(
+                        ColumnDef::new(EmbeddingCache::ModelName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmbeddingCache::Dimension)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmbeddingCache::CreatedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EmbeddingCache::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EmbeddingCache {
+    Table,
+    ContentHash,
+    Embedding,
+    ModelName,
+    Dimension,
+    CreatedAt,
+}
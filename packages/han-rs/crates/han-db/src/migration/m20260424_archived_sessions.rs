@@ -0,0 +1,248 @@
+//! Migration: Create archived_sessions and archived_messages tables.
+//!
+//! `crud::archive_sessions_older_than` moves stale sessions and their
+//! messages here so long-running installations keep `sessions` small.
+//! Both tables mirror their source table plus an `archived_at` column, and
+//! have no foreign keys so archived rows outlive their project.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArchivedSessions::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ArchivedSessions::Id).string().primary_key())
+                    .col(ColumnDef::new(ArchivedSessions::ProjectId).string().null())
+                    .col(ColumnDef::new(ArchivedSessions::Status).string().null())
+                    .col(ColumnDef::new(ArchivedSessions::Slug).string().null())
+                    .col(
+                        ColumnDef::new(ArchivedSessions::TranscriptPath)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedSessions::SourceConfigDir)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedSessions::LastIndexedLine)
+                            .integer()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(ArchivedSessions::PrNumber).integer().null())
+                    .col(ColumnDef::new(ArchivedSessions::PrUrl).string().null())
+                    .col(ColumnDef::new(ArchivedSessions::TeamName).string().null())
+                    .col(
+                        ColumnDef::new(ArchivedSessions::ArchivedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArchivedMessages::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ArchivedMessages::Id).string().primary_key())
+                    .col(
+                        ColumnDef::new(ArchivedMessages::SessionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ArchivedMessages::AgentId).string().null())
+                    .col(ColumnDef::new(ArchivedMessages::ParentId).string().null())
+                    .col(
+                        ColumnDef::new(ArchivedMessages::MessageType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ArchivedMessages::Role).string().null())
+                    .col(ColumnDef::new(ArchivedMessages::Content).text().null())
+                    .col(ColumnDef::new(ArchivedMessages::ToolName).string().null())
+                    .col(ColumnDef::new(ArchivedMessages::ToolInput).text().null())
+                    .col(ColumnDef::new(ArchivedMessages::ToolResult).text().null())
+                    .col(ColumnDef::new(ArchivedMessages::RawJson).text().null())
+                    .col(
+                        ColumnDef::new(ArchivedMessages::Timestamp)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::LineNumber)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::ByteOffset)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::SourceFileName)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::SourceFileType)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::SentimentScore)
+                            .double()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::SentimentLevel)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::FrustrationScore)
+                            .double()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::FrustrationLevel)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::InputTokens)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::OutputTokens)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::CacheReadTokens)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::CacheCreationTokens)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::LinesAdded)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::LinesRemoved)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::FilesChanged)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::HumanTimeMs)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ArchivedMessages::DurationMs)
+                            .integer()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(ArchivedMessages::IndexedAt).string().null())
+                    .col(
+                        ColumnDef::new(ArchivedMessages::ArchivedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_archived_messages_session")
+                    .table(ArchivedMessages::Table)
+                    .col(ArchivedMessages::SessionId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArchivedMessages::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ArchivedSessions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArchivedSessions {
+    Table,
+    Id,
+    ProjectId,
+    Status,
+    Slug,
+    TranscriptPath,
+    SourceConfigDir,
+    LastIndexedLine,
+    PrNumber,
+    PrUrl,
+    TeamName,
+    ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum ArchivedMessages {
+    Table,
+    Id,
+    SessionId,
+    AgentId,
+    ParentId,
+    MessageType,
+    Role,
+    Content,
+    ToolName,
+    ToolInput,
+    ToolResult,
+    RawJson,
+    Timestamp,
+    LineNumber,
+    ByteOffset,
+    SourceFileName,
+    SourceFileType,
+    SentimentScore,
+    SentimentLevel,
+    FrustrationScore,
+    FrustrationLevel,
+    InputTokens,
+    OutputTokens,
+    CacheReadTokens,
+    CacheCreationTokens,
+    LinesAdded,
+    LinesRemoved,
+    FilesChanged,
+    HumanTimeMs,
+    DurationMs,
+    IndexedAt,
+    ArchivedAt,
+}
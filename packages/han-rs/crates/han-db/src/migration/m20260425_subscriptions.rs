@@ -0,0 +1,104 @@
+//! Migration: Create the subscriptions table.
+//!
+//! One row per Stripe subscription, upserted by the han-server billing
+//! webhook. `stripe_subscription_id` is unique so replayed events update the
+//! existing row instead of inserting a duplicate.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Subscriptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Subscriptions::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Subscriptions::OrgId).string().not_null())
+                    .col(
+                        ColumnDef::new(Subscriptions::StripeSubscriptionId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Subscriptions::Plan).string().not_null())
+                    .col(ColumnDef::new(Subscriptions::Status).string().not_null())
+                    .col(
+                        ColumnDef::new(Subscriptions::Seats)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(ColumnDef::new(Subscriptions::CurrentPeriodEnd).string())
+                    .col(ColumnDef::new(Subscriptions::CancelAt).string())
+                    .col(ColumnDef::new(Subscriptions::CreatedAt).string().not_null())
+                    .col(ColumnDef::new(Subscriptions::UpdatedAt).string().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Subscriptions::Table, Subscriptions::OrgId)
+                            .to(Teams::Table, Teams::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_subscriptions_stripe_id")
+                    .table(Subscriptions::Table)
+                    .col(Subscriptions::StripeSubscriptionId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_subscriptions_org")
+                    .table(Subscriptions::Table)
+                    .col(Subscriptions::OrgId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Subscriptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Subscriptions {
+    Table,
+    Id,
+    OrgId,
+    StripeSubscriptionId,
+    Plan,
+    Status,
+    Seats,
+    CurrentPeriodEnd,
+    CancelAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Teams {
+    Table,
+    Id,
+}
@@ -0,0 +1,54 @@
+//! Migration: Create session_timelines table.
+//!
+//! Caches each session's serialized task timeline so loading a session does
+//! not rebuild it from the tasks table. Rows are rewritten by the indexer
+//! after it processes task events and dropped when tasks change elsewhere.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SessionTimelines::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SessionTimelines::SessionId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionTimelines::TimelineJson)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SessionTimelines::ComputedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SessionTimelines::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SessionTimelines {
+    Table,
+    SessionId,
+    TimelineJson,
+    ComputedAt,
+}
@@ -0,0 +1,54 @@
+//! Migration: Create indexing_checkpoints table.
+//!
+//! The indexer records how far an index pass has written so a pass cut short
+//! by a crash resumes there instead of re-reading the whole transcript. Rows
+//! are dropped once the pass completes.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IndexingCheckpoints::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IndexingCheckpoints::SessionId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexingCheckpoints::LastLineNumber)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexingCheckpoints::LastIndexedAt)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IndexingCheckpoints::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IndexingCheckpoints {
+    Table,
+    SessionId,
+    LastLineNumber,
+    LastIndexedAt,
+}
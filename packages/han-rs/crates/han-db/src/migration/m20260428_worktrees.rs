@@ -0,0 +1,171 @@
+//! Migration: Create worktrees table and link sessions to it.
+//!
+//! Sessions run in a linked git worktree point at a `worktrees` row so
+//! sessions from every worktree of a checkout can be grouped by
+//! `main_project_dir`. `archived_sessions` gets the same column because it
+//! mirrors `sessions`.
+//!
+//! Like `m20260418_session_delete_cascade`, the foreign key is only added on
+//! PostgreSQL; SQLite can't add one to an existing table.
+
+use sea_orm::DatabaseBackend;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const FK_NAME: &str = "fk_sessions_worktree_id";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Worktrees::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Worktrees::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Worktrees::ProjectDir)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Worktrees::MainProjectDir)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Worktrees::BranchName).string().null())
+                    .col(ColumnDef::new(Worktrees::WorktreeName).string().not_null())
+                    .col(ColumnDef::new(Worktrees::CreatedAt).string().not_null())
+                    .col(ColumnDef::new(Worktrees::LastSeenAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_worktrees_main_project_dir")
+                    .table(Worktrees::Table)
+                    .col(Worktrees::MainProjectDir)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            Sessions::Table.into_iden(),
+            ArchivedSessions::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(Sessions::WorktreeId).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_sessions_worktree_id")
+                    .table(Sessions::Table)
+                    .col(Sessions::WorktreeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DatabaseBackend::Postgres {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Sessions::Table)
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(FK_NAME)
+                                .from_tbl(Sessions::Table)
+                                .from_col(Sessions::WorktreeId)
+                                .to_tbl(Worktrees::Table)
+                                .to_col(Worktrees::Id),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DatabaseBackend::Postgres {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Sessions::Table)
+                        .drop_foreign_key(Alias::new(FK_NAME))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sessions_worktree_id")
+                    .table(Sessions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            Sessions::Table.into_iden(),
+            ArchivedSessions::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Sessions::WorktreeId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Worktrees::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Worktrees {
+    Table,
+    Id,
+    ProjectDir,
+    MainProjectDir,
+    BranchName,
+    WorktreeName,
+    CreatedAt,
+    LastSeenAt,
+}
+
+#[derive(DeriveIden)]
+enum Sessions {
+    Table,
+    WorktreeId,
+}
+
+#[derive(DeriveIden)]
+enum ArchivedSessions {
+    Table,
+}
@@ -0,0 +1,118 @@
+//! Migration: Create memories table and link memory_learn messages to it.
+//!
+//! Each `memory_learn` event gets a `memories` row, and the message records
+//! it in `memory_id`. `archived_messages` gets the same column because it
+//! mirrors `messages`. A unique index on `(scope, content, created_at)` keeps
+//! concurrent indexing from recording one event twice.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Memories::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Memories::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Memories::Scope).string().null())
+                    .col(ColumnDef::new(Memories::Content).text().not_null())
+                    .col(ColumnDef::new(Memories::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // COALESCE so memories without a scope conflict too; unique indexes
+        // treat NULLs as distinct
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_memories_scope_content \
+                 ON memories (COALESCE(scope, ''), content, created_at)",
+            )
+            .await?;
+
+        for table in [
+            Messages::Table.into_iden(),
+            ArchivedMessages::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(Messages::MemoryId).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_messages_memory_id")
+                    .table(Messages::Table)
+                    .col(Messages::MemoryId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_messages_memory_id")
+                    .table(Messages::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            Messages::Table.into_iden(),
+            ArchivedMessages::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Messages::MemoryId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Memories::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Memories {
+    Table,
+    Id,
+    Scope,
+    Content,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    MemoryId,
+}
+
+#[derive(DeriveIden)]
+enum ArchivedMessages {
+    Table,
+}
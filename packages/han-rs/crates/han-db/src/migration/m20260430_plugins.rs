@@ -0,0 +1,89 @@
+//! Migration: Create plugins table and record each message's plugin.
+//!
+//! Records installed plugins so hook runs can be resolved to the plugin
+//! version and description that produced them. Hook events store the plugin
+//! they ran for in `messages.plugin_name`; `archived_messages` gets the same
+//! column because it mirrors `messages`.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Plugins::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Plugins::Name)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Plugins::Version).string().not_null())
+                    .col(ColumnDef::new(Plugins::Description).text().null())
+                    .col(ColumnDef::new(Plugins::InstalledAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            Messages::Table.into_iden(),
+            ArchivedMessages::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(Messages::PluginName).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            Messages::Table.into_iden(),
+            ArchivedMessages::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Messages::PluginName)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Plugins::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Plugins {
+    Table,
+    Name,
+    Version,
+    Description,
+    InstalledAt,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    PluginName,
+}
+
+#[derive(DeriveIden)]
+enum ArchivedMessages {
+    Table,
+}
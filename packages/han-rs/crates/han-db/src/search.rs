@@ -27,6 +27,28 @@ pub struct GeneratedSummarySearchResult {
     pub score: f64,
 }
 
+/// A session matched by cross-session topic search.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionSearchResult {
+    pub session_id: String,
+    pub project_dir: Option<String>,
+    /// BM25 relevance, higher is better.
+    pub rank: f64,
+    pub snippet: String,
+}
+
+/// A message matched by [`fts_search_messages`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FtsMessageResult {
+    pub message_id: String,
+    pub session_id: String,
+    /// BM25 relevance, higher is better.
+    pub rank: f64,
+    pub snippet: String,
+    /// Byte offset of the message's line in its JSONL file, if recorded.
+    pub byte_offset: Option<i64>,
+}
+
 /// SQLite FTS5 search implementation.
 pub struct SqliteSearch {
     db: DatabaseConnection,
@@ -54,6 +76,16 @@ pub fn escape_fts5_query(query: &str) -> String {
         .join(" ")
 }
 
+/// Build an FTS5 prefix query: each word is quoted and matched as a prefix,
+/// so `auth midd` matches "authentication middleware".
+pub fn fts5_prefix_query(query: &str) -> String {
+    escape_fts5_query(query)
+        .split_whitespace()
+        .map(|term| format!("{term}*"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl SqliteSearch {
     /// Search messages using FTS5 MATCH.
     pub async fn search_messages(
@@ -163,3 +195,292 @@ impl SqliteSearch {
         Ok(results)
     }
 }
+
+/// A vector similarity hit supplied by an external vector index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VectorHit {
+    pub message_id: String,
+    /// Cosine similarity in `[-1, 1]`.
+    pub similarity: f64,
+}
+
+/// A message ranked by combined FTS and vector relevance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HybridSearchResult {
+    pub message_id: String,
+    pub session_id: String,
+    pub combined_score: f64,
+    /// BM25 score normalized to `[0, 1]` across the candidate set.
+    pub fts_score: f64,
+    /// Cosine similarity normalized to `[0, 1]`.
+    pub vector_score: f64,
+    pub snippet: String,
+}
+
+/// Maximum snippet length for messages matched only by vector similarity.
+const VECTOR_SNIPPET_CHARS: usize = 200;
+
+/// Rank messages by `fts_weight * bm25 + vector_weight * cosine`.
+///
+/// BM25 scores come from the SQLite FTS5 index and are normalized against the
+/// best match. Cosine similarities come from `vector_hits`, produced by
+/// whichever vector index the caller has (none ships with han-db), and are
+/// mapped from `[-1, 1]` to `[0, 1]`. A message missing from one side scores
+/// 0 on that side.
+pub async fn hybrid_search(
+    db: &DatabaseConnection,
+    query_text: &str,
+    vector_hits: &[VectorHit],
+    fts_weight: f64,
+    vector_weight: f64,
+    limit: u32,
+) -> DbResult<Vec<HybridSearchResult>> {
+    use sea_orm::{ConnectionTrait, Statement};
+    use std::collections::HashMap;
+
+    // Pull a wider FTS candidate set than `limit` so vector scores can reorder it
+    let candidate_limit = limit.saturating_mul(4).max(limit) as i32;
+    let mut results: HashMap<String, HybridSearchResult> = HashMap::new();
+
+    let escaped = escape_fts5_query(query_text);
+    if !escaped.is_empty() {
+        let stmt = Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "SELECT m.id, m.session_id, \
+             snippet(messages_fts, 1, '', '', '…', 24) AS snippet, \
+             bm25(messages_fts) AS score
+             FROM messages_fts
+             JOIN messages m ON messages_fts.id = m.id
+             WHERE messages_fts MATCH ?1
+             ORDER BY score
+             LIMIT ?2",
+            vec![
+                sea_orm::Value::String(Some(Box::new(escaped))),
+                sea_orm::Value::Int(Some(candidate_limit)),
+            ],
+        );
+        let rows = db
+            .query_all(stmt)
+            .await
+            .map_err(crate::error::DbError::Database)?;
+
+        // FTS5 bm25() is negative with lower = better; use magnitudes
+        let scored: Vec<_> = rows
+            .iter()
+            .map(|row| (row, row.try_get::<f64>("", "score").unwrap_or(0.0).abs()))
+            .collect();
+        let max_bm25 = scored.iter().map(|(_, s)| *s).fold(0.0, f64::max);
+
+        for (row, bm25) in scored {
+            let message_id = row.try_get::<String>("", "id").unwrap_or_default();
+            let fts_score = if max_bm25 > 0.0 { bm25 / max_bm25 } else { 1.0 };
+            results.insert(
+                message_id.clone(),
+                HybridSearchResult {
+                    message_id,
+                    session_id: row.try_get::<String>("", "session_id").unwrap_or_default(),
+                    combined_score: 0.0,
+                    fts_score,
+                    vector_score: 0.0,
+                    snippet: row.try_get::<String>("", "snippet").unwrap_or_default(),
+                },
+            );
+        }
+    }
+
+    // Vector-only hits need their session and content looked up
+    let missing: Vec<String> = vector_hits
+        .iter()
+        .filter(|h| !results.contains_key(&h.message_id))
+        .map(|h| h.message_id.clone())
+        .collect();
+    if !missing.is_empty() {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+        let rows = crate::entities::messages::Entity::find()
+            .filter(crate::entities::messages::Column::Id.is_in(missing))
+            .all(db)
+            .await
+            .map_err(crate::error::DbError::Database)?;
+        for m in rows {
+            let snippet = m
+                .content
+                .unwrap_or_default()
+                .chars()
+                .take(VECTOR_SNIPPET_CHARS)
+                .collect();
+            results.insert(
+                m.id.clone(),
+                HybridSearchResult {
+                    message_id: m.id,
+                    session_id: m.session_id,
+                    combined_score: 0.0,
+                    fts_score: 0.0,
+                    vector_score: 0.0,
+                    snippet,
+                },
+            );
+        }
+    }
+
+    for hit in vector_hits {
+        if let Some(r) = results.get_mut(&hit.message_id) {
+            r.vector_score = ((hit.similarity.clamp(-1.0, 1.0) + 1.0) / 2.0).max(r.vector_score);
+        }
+    }
+
+    let mut ranked: Vec<HybridSearchResult> = results
+        .into_values()
+        .map(|mut r| {
+            r.combined_score = fts_weight * r.fts_score + vector_weight * r.vector_score;
+            r
+        })
+        .filter(|r| r.combined_score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.combined_score
+            .partial_cmp(&a.combined_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.message_id.cmp(&b.message_id))
+    });
+    ranked.truncate(limit as usize);
+    Ok(ranked)
+}
+
+/// Rows for `sessions_fts`: one per session with a summary.
+const SESSIONS_FTS_SOURCE_SQL: &str = "SELECT ss.session_id, ss.content, p.name, \
+     (SELECT m.content FROM messages m \
+      WHERE m.session_id = ss.session_id AND m.message_type = 'user' AND m.content IS NOT NULL \
+      ORDER BY m.line_number LIMIT 1) \
+     FROM session_summaries ss \
+     LEFT JOIN sessions s ON s.id = ss.session_id \
+     LEFT JOIN projects p ON p.id = s.project_id";
+
+/// Rebuild the `sessions_fts` row for one session.
+///
+/// Called after a session is indexed so the summary, project name and first
+/// user message stay searchable as they arrive.
+pub async fn refresh_session_fts(db: &DatabaseConnection, session_id: &str) -> DbResult<()> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    db.execute(Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "DELETE FROM sessions_fts WHERE session_id = ?1",
+        vec![session_id.into()],
+    ))
+    .await
+    .map_err(crate::error::DbError::Database)?;
+    db.execute(Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        format!(
+            "INSERT INTO sessions_fts(session_id, summary, project_name, first_user_message) \
+             {SESSIONS_FTS_SOURCE_SQL} WHERE ss.session_id = ?1"
+        ),
+        vec![session_id.into()],
+    ))
+    .await
+    .map_err(crate::error::DbError::Database)?;
+    Ok(())
+}
+
+/// Search sessions by topic across summaries, project names and first user
+/// messages.
+///
+/// Every word is matched as a prefix (see [`fts5_prefix_query`]). Summary
+/// matches weigh double. Ties are broken by session ID so ordering is stable.
+pub async fn fts_search_sessions(
+    db: &DatabaseConnection,
+    query: &str,
+    limit: usize,
+) -> DbResult<Vec<SessionSearchResult>> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let fts_query = fts5_prefix_query(query);
+    if fts_query.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let stmt = Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "SELECT f.session_id, p.path AS project_dir, \
+         snippet(sessions_fts, -1, '', '', '…', 16) AS snippet, \
+         bm25(sessions_fts, 0.0, 2.0, 1.0, 1.0) AS score
+         FROM sessions_fts f
+         LEFT JOIN sessions s ON s.id = f.session_id
+         LEFT JOIN projects p ON p.id = s.project_id
+         WHERE sessions_fts MATCH ?1
+         ORDER BY score, f.session_id
+         LIMIT ?2",
+        vec![
+            sea_orm::Value::String(Some(Box::new(fts_query))),
+            sea_orm::Value::BigUnsigned(Some(limit as u64)),
+        ],
+    );
+    let rows = db
+        .query_all(stmt)
+        .await
+        .map_err(crate::error::DbError::Database)?;
+
+    Ok(rows
+        .iter()
+        .map(|row| SessionSearchResult {
+            session_id: row.try_get::<String>("", "session_id").unwrap_or_default(),
+            project_dir: row
+                .try_get::<Option<String>>("", "project_dir")
+                .unwrap_or_default(),
+            // FTS5 bm25() is negative with lower = better
+            rank: row.try_get::<f64>("", "score").unwrap_or(0.0).abs(),
+            snippet: row.try_get::<String>("", "snippet").unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Full-text search over message content, optionally within one session.
+///
+/// Words are matched exactly (see [`escape_fts5_query`]). Ties are broken by
+/// message ID so ordering is stable.
+pub async fn fts_search_messages(
+    db: &DatabaseConnection,
+    query: &str,
+    session_id: Option<&str>,
+    limit: usize,
+) -> DbResult<Vec<FtsMessageResult>> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let escaped = escape_fts5_query(query);
+    if escaped.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let stmt = Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "SELECT m.id, m.session_id, m.byte_offset, \
+         snippet(messages_fts, 1, '', '', '…', 24) AS snippet, \
+         bm25(messages_fts) AS score
+         FROM messages_fts
+         JOIN messages m ON messages_fts.id = m.id
+         WHERE messages_fts MATCH ?1 AND (?2 IS NULL OR m.session_id = ?2)
+         ORDER BY score, m.id
+         LIMIT ?3",
+        vec![
+            sea_orm::Value::String(Some(Box::new(escaped))),
+            sea_orm::Value::String(session_id.map(|s| Box::new(s.to_string()))),
+            sea_orm::Value::BigUnsigned(Some(limit as u64)),
+        ],
+    );
+    let rows = db
+        .query_all(stmt)
+        .await
+        .map_err(crate::error::DbError::Database)?;
+
+    Ok(rows
+        .iter()
+        .map(|row| FtsMessageResult {
+            message_id: row.try_get::<String>("", "id").unwrap_or_default(),
+            session_id: row.try_get::<String>("", "session_id").unwrap_or_default(),
+            // FTS5 bm25() is negative with lower = better
+            rank: row.try_get::<f64>("", "score").unwrap_or(0.0).abs(),
+            snippet: row.try_get::<String>("", "snippet").unwrap_or_default(),
+            byte_offset: row.try_get("", "byte_offset").unwrap_or(None),
+        })
+        .collect())
+}
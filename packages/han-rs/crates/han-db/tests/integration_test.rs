@@ -81,6 +81,192 @@ async fn test_sqlite_pragmas_applied() {
     assert_eq!(fk, 1, "Foreign keys should be enabled");
 }
 
+#[tokio::test]
+async fn test_connection_health() {
+    let db = setup_db().await;
+    use han_db::connection::{pool_stats, test_connection};
+
+    test_connection(&db).await.expect("SELECT 1 should succeed");
+
+    let stats = pool_stats(&db).await;
+    assert_eq!(stats.max, 5);
+    assert!(stats.active + stats.idle <= stats.max);
+
+    let broken = DatabaseConnection::Disconnected;
+    assert!(test_connection(&broken).await.is_err());
+    assert_eq!(pool_stats(&broken).await, Default::default());
+}
+
+/// Log output captured by [`capture_logs`].
+#[derive(Clone, Default)]
+struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl LogBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+/// Capture DEBUG and above on this thread until the guard is dropped.
+fn capture_logs() -> (LogBuffer, tracing::subscriber::DefaultGuard) {
+    let logs = LogBuffer::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    (logs, tracing::subscriber::set_default(subscriber))
+}
+
+#[tokio::test]
+async fn test_pool_monitor_warns_under_pressure() {
+    let db = setup_db().await;
+    use han_db::connection::{pool_stats, pool_under_pressure, start_pool_monitor};
+
+    let (logs, _guard) = capture_logs();
+
+    // Check out every connection the pool allows
+    let pool = db.get_sqlite_connection_pool();
+    let mut held = Vec::new();
+    for _ in 0..pool_stats(&db).await.max {
+        held.push(pool.acquire().await.unwrap());
+    }
+    let stats = pool_stats(&db).await;
+    assert_eq!(stats.active, stats.max);
+    assert!(pool_under_pressure(&stats));
+
+    // The first sample is taken immediately
+    let monitor = start_pool_monitor(db.clone(), 60).await;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    monitor.abort();
+
+    let output = logs.contents();
+    assert!(output.contains("[pool] active=5 idle=0 wait=0"), "{output}");
+    assert!(output.contains("WARN"), "{output}");
+    assert!(output.contains("5 of 5 connections in use"), "{output}");
+
+    drop(held);
+    let mut relaxed = pool_stats(&db).await;
+    relaxed.active = 1;
+    assert!(!pool_under_pressure(&relaxed));
+}
+
+#[tokio::test]
+async fn test_slow_query_logger_hides_string_params() {
+    use han_db::connection::SlowQueryLogger;
+    use sea_orm::metric::Info;
+    use sea_orm::{DbBackend, Statement};
+    use std::time::Duration;
+
+    let (logs, _guard) = capture_logs();
+    let logger = SlowQueryLogger::default();
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "SELECT * FROM messages WHERE session_id = ? AND line_number > ?",
+        ["secret transcript text".into(), 7.into()],
+    );
+
+    logger.observe(&Info {
+        elapsed: Duration::from_millis(10),
+        statement: &statement,
+        failed: false,
+    });
+    assert!(logs.contents().is_empty());
+
+    logger.observe(&Info {
+        elapsed: Duration::from_secs(2),
+        statement: &statement,
+        failed: false,
+    });
+    let output = logs.contents();
+    assert!(
+        output.contains("Slow query: SELECT * FROM messages"),
+        "{output}"
+    );
+    assert!(output.contains("params=[<string len=22>, 7]"), "{output}");
+    assert!(!output.contains("secret"), "{output}");
+}
+
+#[tokio::test]
+async fn test_rollback_to_version() {
+    use han_db::migration::{get_current_version, migration_version, rollback_to_version};
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let db = establish_connection(DbConfig::Sqlite {
+        path: ":memory:".to_string(),
+    })
+    .await
+    .expect("Failed to connect to in-memory SQLite");
+    assert_eq!(get_current_version(&db).await.unwrap(), 0);
+
+    // initial schema, team entities, session PR/team columns
+    Migrator::up(&db, Some(3)).await.expect("Migration failed");
+    let v1 = migration_version("m20260215_000001_initial").unwrap();
+    let v3 = migration_version("m20260220_add_session_pr_team").unwrap();
+    assert_eq!(get_current_version(&db).await.unwrap(), v3);
+
+    rollback_to_version(&db, v1).await.expect("Rollback failed");
+    assert_eq!(get_current_version(&db).await.unwrap(), v1);
+
+    let names = |sql: &str| {
+        let stmt = Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql.to_string());
+        let db = &db;
+        async move {
+            let rows = db.query_all(stmt).await.expect("Failed to query schema");
+            rows.iter()
+                .map(|row| row.try_get::<String>("", "name").unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+    let tables = names("SELECT name FROM sqlite_master WHERE type = 'table'").await;
+    assert!(tables.iter().any(|t| t == "sessions"));
+    assert!(!tables.iter().any(|t| t == "teams"));
+    let columns = names("SELECT name FROM pragma_table_info('sessions')").await;
+    assert!(!columns.iter().any(|c| c == "pr_number"));
+
+    // Unknown versions are rejected rather than rolling back everything
+    assert!(rollback_to_version(&db, 20260216000000).await.is_err());
+    assert_eq!(get_current_version(&db).await.unwrap(), v1);
+}
+
+#[tokio::test]
+async fn test_verify_schema() {
+    let db = setup_db().await;
+    use han_db::migration::verify_schema;
+    use sea_orm::ConnectionTrait;
+
+    let report = verify_schema(&db).await.expect("Verification failed");
+    assert!(report.is_valid, "{report:?}");
+    assert!(report.extra_tables.is_empty(), "{report:?}");
+
+    for sql in [
+        "ALTER TABLE messages DROP COLUMN duration_ms",
+        "DROP TABLE team_invites",
+        "CREATE TABLE scratch (id INTEGER)",
+    ] {
+        db.execute_unprepared(sql).await.unwrap();
+    }
+
+    let report = verify_schema(&db).await.unwrap();
+    assert!(!report.is_valid);
+    let missing = ("messages".to_string(), "duration_ms".to_string());
+    assert_eq!(report.missing_columns, vec![missing]);
+    assert_eq!(report.missing_tables, vec!["team_invites".to_string()]);
+    assert_eq!(report.extra_tables, vec!["scratch".to_string()]);
+}
+
 // ============================================================================
 // Repos CRUD Tests
 // ============================================================================
@@ -176,10 +362,207 @@ async fn test_projects_crud() {
     assert_eq!(all.len(), 1);
 }
 
+#[tokio::test]
+async fn test_project_stats_cache() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, project_stats, projects, sessions};
+    use han_db::entities::{hook_executions, messages as msg_entity};
+    use sea_orm::{EntityTrait, Set};
+
+    let project = projects::upsert(
+        &db,
+        None,
+        "stats-project".to_string(),
+        "/home/user/stats".to_string(),
+        None,
+        "Stats".to_string(),
+        Some(false),
+        None,
+    )
+    .await
+    .unwrap();
+    for id in ["stats-a", "stats-b"] {
+        let project_id = Some(project.id.clone());
+        sessions::upsert(&db, id.to_string(), project_id, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    // `at` is "DDTHH:MM" in April; `tokens` is used for input and output
+    let msg = |session: &str, line: i32, kind: &str, tool: Option<&str>, at: &str, tokens: i32| {
+        msg_entity::ActiveModel {
+            id: Set(format!("{session}-{line}")),
+            session_id: Set(session.to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set(kind.to_string()),
+            role: Set(None),
+            content: Set(None),
+            tool_name: Set(tool.map(str::to_string)),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            // Each session's assistant replies come from a different model
+            raw_json: Set((kind == "assistant")
+                .then(|| format!(r#"{{"message":{{"model":"model-{session}"}}}}"#))),
+            timestamp: Set(format!("2026-04-{at}:00Z")),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(Some(tokens)),
+            output_tokens: Set(Some(tokens)),
+            cache_read_tokens: Set(None),
+            cache_creation_tokens: Set(None),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            msg("stats-a", 1, "user", None, "01T09:00", 0),
+            msg("stats-a", 2, "assistant", None, "01T09:05", 100),
+            msg("stats-a", 3, "tool_use", Some("Edit"), "01T09:10", 0),
+            msg("stats-b", 1, "user", None, "02T10:00", 0),
+            msg("stats-b", 2, "tool_use", Some("Bash"), "02T10:10", 0),
+            msg("stats-b", 3, "tool_use", Some("Edit"), "02T10:20", 0),
+            msg("stats-b", 4, "assistant", None, "02T10:30", 50),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let hook = |id: &str, passed: bool| hook_executions::ActiveModel {
+        id: Set(id.to_string()),
+        orchestration_id: Set(None),
+        session_id: Set(Some("stats-a".to_string())),
+        task_id: Set(None),
+        hook_type: Set("Stop".to_string()),
+        hook_name: Set("lint".to_string()),
+        hook_source: Set(None),
+        directory: Set(None),
+        duration_ms: Set(10),
+        exit_code: Set(if passed { 0 } else { 1 }),
+        passed: Set(passed as i32),
+        output: Set(None),
+        error: Set(None),
+        if_changed: Set(None),
+        command: Set(None),
+        executed_at: Set("2026-04-01T09:10:00Z".to_string()),
+        status: Set(None),
+        consecutive_failures: Set(None),
+        max_attempts: Set(None),
+        pid: Set(None),
+        plugin_root: Set(None),
+    };
+    hook_executions::Entity::insert_many(vec![hook("h1", true), hook("h2", false)])
+        .exec(&db)
+        .await
+        .unwrap();
+
+    let stats = project_stats::get_or_refresh(&db, &project.id)
+        .await
+        .unwrap();
+    assert_eq!(stats.total_sessions, 2);
+    assert_eq!(stats.total_messages, 7);
+    assert_eq!((stats.input_tokens, stats.output_tokens), (150, 150));
+    let by_model: Vec<han_db::aggregates::ModelTokenTotals> =
+        serde_json::from_str(&stats.tokens_by_model).unwrap();
+    let by_model: Vec<_> = by_model
+        .iter()
+        .map(|t| (t.model.as_deref(), t.input_tokens, t.output_tokens))
+        .collect();
+    assert_eq!(
+        by_model,
+        [
+            (Some("model-stats-a"), 100, 100),
+            (Some("model-stats-b"), 50, 50)
+        ]
+    );
+    assert_eq!(stats.unique_tools, r#"["Bash","Edit"]"#);
+    assert_eq!((stats.hook_runs, stats.hook_passes), (2, 1));
+    // Sessions ran 10 and 30 minutes
+    assert!((stats.average_session_duration_minutes - 20.0).abs() < 1e-6);
+    assert_eq!(stats.active_days, 2);
+    assert_eq!(
+        stats.first_session_at.as_deref(),
+        Some("2026-04-01T09:00:00Z")
+    );
+    assert_eq!(
+        stats.last_session_at.as_deref(),
+        Some("2026-04-02T10:30:00Z")
+    );
+
+    // Cached rows are served until invalidated
+    messages::insert_batch(&db, vec![msg("stats-b", 5, "user", None, "05T08:00", 0)])
+        .await
+        .unwrap();
+    let cached = project_stats::get_or_refresh(&db, &project.id)
+        .await
+        .unwrap();
+    assert_eq!(cached.total_messages, 7);
+
+    project_stats::invalidate_for_session(&db, "stats-b")
+        .await
+        .unwrap();
+    let listed = project_stats::list(&db, Some(10)).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0.id, project.id);
+    assert_eq!(listed[0].1.total_messages, 8);
+    assert_eq!(listed[0].1.active_days, 3);
+}
+
 // ============================================================================
 // Sessions CRUD Tests
 // ============================================================================
 
+#[tokio::test]
+async fn test_embedding_cache() {
+    let db = setup_db().await;
+    use han_db::crud::embedding_cache::{
+        EmbeddingCacheStats, decode_embedding, encode_embedding, get_cached_embedding, stats,
+        store_embedding,
+    };
+
+    let vector = vec![0.25, -1.5, f32::MIN_POSITIVE, 3.0e7];
+    let bytes = encode_embedding(&vector);
+    assert_eq!(&bytes[..4], &0.25f32.to_le_bytes());
+    assert_eq!(decode_embedding(&bytes), Some(vector.clone()));
+    assert_eq!(decode_embedding(&bytes[..5]), None);
+
+    let get = |hash, model| get_cached_embedding(&db, hash, model);
+    assert_eq!(get("h1", "minilm").await.unwrap(), None);
+    assert_eq!(stats(&db).await.unwrap(), EmbeddingCacheStats::default());
+
+    store_embedding(&db, "h1", "minilm", &vector).await.unwrap();
+    store_embedding(&db, "h2", "minilm", &[1.0]).await.unwrap();
+    assert_eq!(get("h1", "minilm").await.unwrap(), Some(vector));
+    // Another model's embedding of the same content is a miss
+    assert_eq!(get("h1", "bge").await.unwrap(), None);
+
+    // Storing again replaces the entry
+    let other = vec![0.5, 2.0];
+    store_embedding(&db, "h1", "bge", &other).await.unwrap();
+    assert_eq!(get("h1", "bge").await.unwrap(), Some(other));
+    assert_eq!(get("h1", "minilm").await.unwrap(), None);
+
+    let expected = EmbeddingCacheStats {
+        cached_count: 2,
+        cache_size_bytes: 12,
+    };
+    assert_eq!(stats(&db).await.unwrap(), expected);
+}
+
 #[tokio::test]
 async fn test_sessions_crud() {
     let db = setup_db().await;
@@ -249,115 +632,845 @@ async fn test_sessions_crud() {
     assert_eq!(reset, 1);
 }
 
-// ============================================================================
-// Config Dirs CRUD Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_config_dirs_crud() {
+async fn test_sessions_list_recent() {
     let db = setup_db().await;
-    use han_db::crud::config_dirs;
+    use han_db::crud::{messages, projects, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
 
-    // Register
-    let dir = config_dirs::register(
+    let mut project_ids = Vec::new();
+    for (slug, path) in [
+        ("myproject", "/home/user/myproject"),
+        ("feat", "/home/user/myproject/.git/worktrees/feat"),
+        ("other", "/home/user/myproject-other"),
+    ] {
+        let project = projects::upsert(
+            &db,
+            None,
+            slug.to_string(),
+            path.to_string(),
+            None,
+            slug.to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        project_ids.push(project.id);
+    }
+
+    for (id, project) in [
+        ("session-b", 0),
+        ("session-a", 1),
+        ("session-c", 2),
+        ("session-empty", 0),
+    ] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            Some(project_ids[project].clone()),
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let make = |session_id: &str, timestamp: &str| msg_entity::ActiveModel {
+        id: Set(format!("{session_id}-msg")),
+        session_id: Set(session_id.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some("hi".to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set(timestamp.to_string()),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
         &db,
-        "/home/user/.claude".to_string(),
-        Some("default".to_string()),
-        Some(true),
+        vec![
+            make("session-a", "2026-04-14T10:00:00Z"),
+            make("session-b", "2026-04-14T10:00:00Z"),
+            make("session-c", "2026-04-14T11:00:00Z"),
+        ],
     )
     .await
-    .expect("Failed to register config dir");
+    .unwrap();
 
-    assert_eq!(dir.path, "/home/user/.claude");
-    assert_eq!(dir.is_default, 1);
+    let ids = |models: Vec<han_db::entities::sessions::Model>| -> Vec<String> {
+        models.into_iter().map(|m| m.id).collect()
+    };
 
-    // Get default
-    let default = config_dirs::get_default(&db)
+    // Shared timestamps fall back to ID order, identically on every call
+    for _ in 0..3 {
+        let recent = sessions::list_recent(&db, None, false, None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids(recent), vec!["session-c", "session-a", "session-b"]);
+    }
+
+    // Worktrees under the project match; sibling directories with the same prefix don't
+    let scoped = sessions::list_recent(&db, Some("/home/user/myproject/"), false, None, None)
         .await
-        .expect("Failed to get default")
         .unwrap();
-    assert_eq!(default.path, "/home/user/.claude");
+    assert_eq!(ids(scoped), vec!["session-a", "session-b"]);
 
-    // Get by path
-    let found = config_dirs::get_by_path(&db, "/home/user/.claude")
+    let with_empty = sessions::list_recent(&db, Some("/home/user/myproject"), true, None, None)
         .await
-        .expect("Failed to get by path");
-    assert!(found.is_some());
+        .unwrap();
+    assert_eq!(
+        ids(with_empty),
+        vec!["session-a", "session-b", "session-empty"]
+    );
 
-    // List
-    let all = config_dirs::list(&db).await.expect("Failed to list");
-    assert_eq!(all.len(), 1);
+    let active = sessions::list_recent(&db, None, false, Some("2026-04-14T10:30:00Z"), None)
+        .await
+        .unwrap();
+    assert_eq!(ids(active), vec!["session-c"]);
 
-    // Update indexed
-    let updated = config_dirs::update_last_indexed(&db, "/home/user/.claude")
+    let limited = sessions::list_recent(&db, None, false, None, Some(2))
         .await
-        .expect("Failed to update");
-    assert!(updated);
+        .unwrap();
+    assert_eq!(ids(limited), vec!["session-c", "session-a"]);
+}
 
-    // Unregister
-    let removed = config_dirs::unregister(&db, "/home/user/.claude")
+#[tokio::test]
+async fn test_sessions_list_with_filters() {
+    let db = setup_db().await;
+    use han_db::crud::sessions::{SessionFilter, count_with_filters, list_with_filters};
+    use han_db::crud::{messages, projects, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    let mut project_ids = Vec::new();
+    for (slug, path) in [
+        ("app", "/home/user/app"),
+        ("wt", "/home/user/app/.git/worktrees/wt"),
+        ("lib", "/home/user/lib"),
+    ] {
+        let project = projects::upsert(
+            &db,
+            None,
+            slug.to_string(),
+            path.to_string(),
+            None,
+            slug.to_string(),
+            None,
+            None,
+        )
         .await
-        .expect("Failed to unregister");
-    assert!(removed);
+        .unwrap();
+        project_ids.push(project.id);
+    }
 
-    let after = config_dirs::list(&db).await.expect("Failed to list");
-    assert_eq!(after.len(), 0);
-}
+    for (id, project, status) in [
+        ("s1", 0, "active"),
+        ("s2", 1, "completed"),
+        ("s3", 2, "active"),
+        ("s4", 0, "active"),
+    ] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            Some(project_ids[project].clone()),
+            Some(status.to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
 
-// ============================================================================
-// Messages CRUD Tests
-// ============================================================================
+    let make = |sid: &str, line: i32, ts: &str, branch: &str| msg_entity::ActiveModel {
+        id: Set(format!("{sid}-{line}")),
+        session_id: Set(sid.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some("hi".to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(Some(format!(r#"{{"gitBranch":"{branch}"}}"#))),
+        timestamp: Set(ts.to_string()),
+        line_number: Set(line),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    // s1: 3 messages on main, s2: 1 on feat, s3: 2 on main, s4: none
+    messages::insert_batch(
+        &db,
+        vec![
+            make("s1", 1, "2026-04-10T10:00:00Z", "main"),
+            make("s1", 2, "2026-04-10T10:01:00Z", "main"),
+            make("s1", 3, "2026-04-10T10:02:00Z", "main"),
+            make("s2", 1, "2026-04-12T10:00:00Z", "feat"),
+            make("s3", 1, "2026-04-14T10:00:00Z", "main"),
+            make("s3", 2, "2026-04-14T10:01:00Z", "main"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let list = |filter: SessionFilter| {
+        let db = &db;
+        async move {
+            let models = list_with_filters(db, filter).await.unwrap();
+            models.into_iter().map(|m| m.id).collect::<Vec<_>>()
+        }
+    };
+
+    // Sessions without messages sort last
+    let all = list(SessionFilter::default()).await;
+    assert_eq!(all, ["s3", "s2", "s1", "s4"]);
+
+    let filter = SessionFilter {
+        project_dir: Some("/home/user/app".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s2", "s1", "s4"]);
+
+    let filter = SessionFilter {
+        after: Some("2026-04-11T00:00:00Z".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s3", "s2"]);
+
+    // 09:00 UTC, before s2's message though it sorts after it as text
+    let filter = SessionFilter {
+        after: Some("2026-04-12T11:00:00+02:00".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s3", "s2"]);
+
+    let filter = SessionFilter {
+        before: Some("2026-04-11T00:00:00Z".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s1"]);
+
+    let filter = SessionFilter {
+        after: Some("2026-04-11T00:00:00Z".to_string()),
+        before: Some("2026-04-13T00:00:00Z".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s2"]);
+
+    let filter = SessionFilter {
+        git_branch: Some("main".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s3", "s1"]);
+
+    let filter = SessionFilter {
+        status: Some("completed".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s2"]);
+
+    let filter = SessionFilter {
+        min_message_count: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s3", "s1"]);
+
+    let filter = SessionFilter {
+        project_dir: Some("/home/user/app".to_string()),
+        git_branch: Some("main".to_string()),
+        status: Some("active".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list(filter).await, ["s1"]);
+
+    // Counts ignore limit and offset
+    let filter = SessionFilter {
+        limit: Some(2),
+        offset: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(list(filter.clone()).await, ["s2", "s1"]);
+    assert_eq!(count_with_filters(&db, filter).await.unwrap(), 4);
+
+    let filter = SessionFilter {
+        git_branch: Some("main".to_string()),
+        min_message_count: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(count_with_filters(&db, filter).await.unwrap(), 1);
+}
 
 #[tokio::test]
-async fn test_messages_crud() {
+async fn test_latest_session_per_project() {
     let db = setup_db().await;
-    use han_db::crud::{messages, sessions};
+    use han_db::crud::{messages, projects, sessions};
     use han_db::entities::messages as msg_entity;
     use sea_orm::Set;
 
-    // Create parent session first
-    sessions::upsert(
+    let mut project_ids = Vec::new();
+    for (slug, path) in [("app", "/home/user/app"), ("lib", "/home/user/lib")] {
+        let project = projects::upsert(
+            &db,
+            None,
+            slug.to_string(),
+            path.to_string(),
+            None,
+            slug.to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        project_ids.push(project.id);
+    }
+
+    // app has three sessions, lib one
+    for (id, project) in [("s1", 0), ("s2", 0), ("s3", 0), ("s4", 1)] {
+        let project_id = Some(project_ids[project].clone());
+        sessions::upsert(&db, id.to_string(), project_id, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    let make = |sid: &str, ts: &str| msg_entity::ActiveModel {
+        id: Set(format!("{sid}-{ts}")),
+        session_id: Set(sid.to_string()),
+        message_type: Set("user".to_string()),
+        timestamp: Set(ts.to_string()),
+        line_number: Set(1),
+        ..Default::default()
+    };
+    messages::insert_batch(
         &db,
-        "session-msg".to_string(),
-        None,
-        Some("active".to_string()),
-        None,
-        None,
-        None,
+        vec![
+            make("s1", "2026-04-10T10:00:00Z"),
+            make("s2", "2026-04-09T10:00:00Z"),
+            make("s2", "2026-04-12T10:00:00Z"),
+            make("s3", "2026-04-11T10:00:00Z"),
+            make("s4", "2026-04-01T10:00:00Z"),
+        ],
     )
     .await
     .unwrap();
 
-    // Insert batch
-    let msgs = vec![
-        msg_entity::ActiveModel {
-            id: Set("msg-001".to_string()),
-            session_id: Set("session-msg".to_string()),
-            agent_id: Set(None),
-            parent_id: Set(None),
-            message_type: Set("assistant".to_string()),
-            role: Set(Some("assistant".to_string())),
-            content: Set(Some("Hello, I can help you with that.".to_string())),
-            tool_name: Set(None),
-            tool_input: Set(None),
-            tool_result: Set(None),
-            raw_json: Set(None),
-            timestamp: Set("2026-02-15T10:00:00Z".to_string()),
-            line_number: Set(1),
-            source_file_name: Set(None),
-            source_file_type: Set(None),
-            sentiment_score: Set(None),
-            sentiment_level: Set(None),
-            frustration_score: Set(None),
-            frustration_level: Set(None),
-            input_tokens: Set(Some(100)),
-            output_tokens: Set(Some(50)),
+    let dirs = [
+        "/home/user/app".to_string(),
+        "/home/user/lib".to_string(),
+        "/home/user/missing".to_string(),
+    ];
+    let latest = sessions::get_latest_session_per_project(&db, &dirs)
+        .await
+        .unwrap();
+    assert_eq!(latest.len(), 2);
+    assert_eq!(latest["/home/user/app"].id, "s2");
+    assert_eq!(latest["/home/user/lib"].id, "s4");
+
+    let latest = sessions::get_latest_session_per_project(&db, &dirs[1..2])
+        .await
+        .unwrap();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest["/home/user/lib"].id, "s4");
+
+    let none = sessions::get_latest_session_per_project(&db, &[]).await;
+    assert!(none.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_session_cascade() {
+    let db = setup_db().await;
+    use han_db::crud::{
+        agent_sessions, delete_session_cascade, file_changes, hooks, messages, native_tasks,
+        session_todos, sessions, tasks,
+    };
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::{ConnectionTrait, DbBackend, Set, Statement};
+
+    for id in ["session-doomed", "session-keep"] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            None,
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let make = |session_id: &str| msg_entity::ActiveModel {
+        id: Set(format!("{session_id}-msg")),
+        session_id: Set(session_id.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some("hi".to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set("2026-04-18T10:00:00Z".to_string()),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(&db, vec![make("session-doomed"), make("session-keep")])
+        .await
+        .unwrap();
+
+    let doomed = || "session-doomed".to_string();
+    session_todos::upsert(
+        &db,
+        doomed(),
+        "session-doomed-msg".to_string(),
+        "[]".to_string(),
+        "2026-04-18T10:00:00Z".to_string(),
+        1,
+    )
+    .await
+    .unwrap();
+    file_changes::record(
+        &db,
+        doomed(),
+        "/tmp/file.rs".to_string(),
+        "modified".to_string(),
+        None,
+        None,
+        Some("Edit".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    hooks::record_execution(
+        &db,
+        Some(doomed()),
+        None,
+        "Stop".to_string(),
+        "lint".to_string(),
+        None,
+        None,
+        10,
+        0,
+        true,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    native_tasks::create(
+        &db,
+        "1".to_string(),
+        doomed(),
+        "session-doomed-msg".to_string(),
+        "Subject".to_string(),
+        None,
+        None,
+        "2026-04-18T10:00:00Z".to_string(),
+        1,
+    )
+    .await
+    .unwrap();
+    tasks::create(
+        &db,
+        Some(doomed()),
+        "task-1".to_string(),
+        "Task".to_string(),
+        "implementation".to_string(),
+    )
+    .await
+    .unwrap();
+    // Linked both as a parent and as a child
+    agent_sessions::link(
+        &db,
+        doomed(),
+        "session-child".to_string(),
+        None,
+        "2026-04-18T10:00:00Z".to_string(),
+    )
+    .await
+    .unwrap();
+    agent_sessions::link(
+        &db,
+        "session-keep".to_string(),
+        doomed(),
+        None,
+        "2026-04-18T10:00:00Z".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let count = |table: &'static str, column: &'static str| {
+        let db = &db;
+        async move {
+            let row = db
+                .query_one(Statement::from_sql_and_values(
+                    DbBackend::Sqlite,
+                    format!("SELECT COUNT(*) AS cnt FROM {table} WHERE {column} = ?"),
+                    vec!["session-doomed".into()],
+                ))
+                .await
+                .unwrap()
+                .unwrap();
+            row.try_get::<i64>("", "cnt").unwrap()
+        }
+    };
+    let owned = [
+        ("messages", "session_id"),
+        ("session_todos", "session_id"),
+        ("session_file_changes", "session_id"),
+        ("hook_executions", "session_id"),
+        ("native_tasks", "session_id"),
+        ("tasks", "session_id"),
+        ("agent_sessions", "parent_session_id"),
+        ("agent_sessions", "child_session_id"),
+        ("sessions", "id"),
+    ];
+
+    // A failure partway through rolls the whole delete back
+    db.execute_unprepared(
+        "CREATE TRIGGER fail_session_delete BEFORE DELETE ON sessions \
+         BEGIN SELECT RAISE(ABORT, 'boom'); END",
+    )
+    .await
+    .unwrap();
+    assert!(delete_session_cascade(&db, "session-doomed").await.is_err());
+    for (table, column) in owned {
+        assert_eq!(count(table, column).await, 1, "{table}.{column}");
+    }
+    db.execute_unprepared("DROP TRIGGER fail_session_delete")
+        .await
+        .unwrap();
+
+    let deleted = delete_session_cascade(&db, "session-doomed").await.unwrap();
+    assert_eq!(deleted, owned.len() as u64);
+    for (table, column) in owned {
+        assert_eq!(count(table, column).await, 0, "{table}.{column}");
+    }
+
+    // Other sessions are untouched
+    assert!(sessions::get(&db, "session-keep").await.unwrap().is_some());
+    assert_eq!(messages::get_count(&db, "session-keep").await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_archive_sessions_older_than() {
+    let db = setup_db().await;
+    use han_db::crud::{
+        archive_sessions_older_than, archive_stale_sessions, messages, restore_session, sessions,
+    };
+    use han_db::entities::{archived_messages, archived_sessions, messages as msg_entity};
+    use sea_orm::{ConnectionTrait, EntityTrait, Set};
+
+    for id in ["session-old", "session-recent", "session-empty"] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            None,
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    let recent = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let make = |session_id: &str, line: i32, timestamp: &str| msg_entity::ActiveModel {
+        id: Set(format!("{session_id}-msg-{line}")),
+        session_id: Set(session_id.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some("hi".to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set(timestamp.to_string()),
+        line_number: Set(line),
+        byte_offset: Set(Some(i64::from(line) * 100)),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(Some(12)),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            make("session-old", 1, "2020-01-01T00:00:00Z"),
+            make("session-old", 2, "2020-01-02T00:00:00Z"),
+            make("session-recent", 1, "2020-01-01T00:00:00Z"),
+            make("session-recent", 2, &recent),
+        ],
+    )
+    .await
+    .unwrap();
+    let exists = |id: &'static str| {
+        let db = &db;
+        async move { sessions::get(db, id).await.unwrap().is_some() }
+    };
+    let original = messages::list_by_session(&db, "session-old", None, None, None, None, false)
+        .await
+        .unwrap();
+
+    // A failure partway through leaves everything in place
+    db.execute_unprepared(
+        "CREATE TRIGGER fail_session_delete BEFORE DELETE ON sessions \
+         BEGIN SELECT RAISE(ABORT, 'boom'); END",
+    )
+    .await
+    .unwrap();
+    assert!(archive_sessions_older_than(&db, 30).await.is_err());
+    assert!(exists("session-old").await);
+    assert_eq!(messages::get_count(&db, "session-old").await.unwrap(), 2);
+    let archived = archived_sessions::Entity::find().all(&db).await.unwrap();
+    assert!(archived.is_empty());
+    let archived = archived_messages::Entity::find().all(&db).await.unwrap();
+    assert!(archived.is_empty());
+    db.execute_unprepared("DROP TRIGGER fail_session_delete")
+        .await
+        .unwrap();
+
+    assert_eq!(archive_sessions_older_than(&db, 30).await.unwrap(), 1);
+    assert!(!exists("session-old").await);
+    assert_eq!(messages::get_count(&db, "session-old").await.unwrap(), 0);
+    let archived = archived_sessions::Entity::find().all(&db).await.unwrap();
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived[0].id, "session-old");
+    assert_eq!(archived[0].status.as_deref(), Some("active"));
+    let archived = archived_messages::Entity::find().all(&db).await.unwrap();
+    assert_eq!(archived.len(), 2);
+    assert!(archived.iter().all(|m| m.session_id == "session-old"));
+
+    // Recent sessions and sessions without messages are kept
+    assert!(exists("session-recent").await);
+    assert_eq!(messages::get_count(&db, "session-recent").await.unwrap(), 2);
+    assert!(exists("session-empty").await);
+    assert_eq!(archive_sessions_older_than(&db, 30).await.unwrap(), 0);
+
+    restore_session(&db, "session-old").await.unwrap();
+    assert!(exists("session-old").await);
+    let restored = messages::list_by_session(&db, "session-old", None, None, None, None, false)
+        .await
+        .unwrap();
+    assert_eq!(restored, original);
+    let archived = archived_sessions::Entity::find().all(&db).await.unwrap();
+    assert!(archived.is_empty());
+    let archived = archived_messages::Entity::find().all(&db).await.unwrap();
+    assert!(archived.is_empty());
+    assert!(matches!(
+        restore_session(&db, "session-old").await,
+        Err(han_db::DbError::NotFound(_))
+    ));
+
+    // A session re-created while an archived copy exists replaces that copy
+    assert_eq!(archive_stale_sessions(&db, 30).await.unwrap(), ["session-old"]);
+    sessions::upsert(&db, "session-old".to_string(), None, None, None, None, None)
+        .await
+        .unwrap();
+    messages::insert_batch(&db, vec![make("session-old", 3, "2020-01-03T00:00:00Z")])
+        .await
+        .unwrap();
+    assert_eq!(archive_stale_sessions(&db, 30).await.unwrap(), ["session-old"]);
+    let archived = archived_messages::Entity::find().all(&db).await.unwrap();
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived[0].line_number, 3);
+}
+
+// ============================================================================
+// Config Dirs CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_config_dirs_crud() {
+    let db = setup_db().await;
+    use han_db::crud::config_dirs;
+
+    // Register
+    let dir = config_dirs::register(
+        &db,
+        "/home/user/.claude".to_string(),
+        Some("default".to_string()),
+        Some(true),
+    )
+    .await
+    .expect("Failed to register config dir");
+
+    assert_eq!(dir.path, "/home/user/.claude");
+    assert_eq!(dir.is_default, 1);
+
+    // Get default
+    let default = config_dirs::get_default(&db)
+        .await
+        .expect("Failed to get default")
+        .unwrap();
+    assert_eq!(default.path, "/home/user/.claude");
+
+    // Get by path
+    let found = config_dirs::get_by_path(&db, "/home/user/.claude")
+        .await
+        .expect("Failed to get by path");
+    assert!(found.is_some());
+
+    // List
+    let all = config_dirs::list(&db).await.expect("Failed to list");
+    assert_eq!(all.len(), 1);
+
+    // Update indexed
+    let updated = config_dirs::update_last_indexed(&db, "/home/user/.claude")
+        .await
+        .expect("Failed to update");
+    assert!(updated);
+
+    // Unregister
+    let removed = config_dirs::unregister(&db, "/home/user/.claude")
+        .await
+        .expect("Failed to unregister");
+    assert!(removed);
+
+    let after = config_dirs::list(&db).await.expect("Failed to list");
+    assert_eq!(after.len(), 0);
+}
+
+// ============================================================================
+// Messages CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_messages_crud() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    // Create parent session first
+    sessions::upsert(
+        &db,
+        "session-msg".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Insert batch
+    let msgs = vec![
+        msg_entity::ActiveModel {
+            id: Set("msg-001".to_string()),
+            session_id: Set("session-msg".to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set("assistant".to_string()),
+            role: Set(Some("assistant".to_string())),
+            content: Set(Some("Hello, I can help you with that.".to_string())),
+            tool_name: Set(None),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(None),
+            timestamp: Set("2026-02-15T10:00:00Z".to_string()),
+            line_number: Set(1),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(Some(100)),
+            output_tokens: Set(Some(50)),
             cache_read_tokens: Set(Some(10)),
             cache_creation_tokens: Set(None),
             lines_added: Set(None),
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
         msg_entity::ActiveModel {
@@ -374,6 +1487,7 @@ async fn test_messages_crud() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:01:00Z".to_string()),
             line_number: Set(2),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -388,6 +1502,9 @@ async fn test_messages_crud() {
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
         msg_entity::ActiveModel {
@@ -404,6 +1521,7 @@ async fn test_messages_crud() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:02:00Z".to_string()),
             line_number: Set(3),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -418,6 +1536,9 @@ async fn test_messages_crud() {
             lines_removed: Set(Some(3)),
             files_changed: Set(Some(1)),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
     ];
@@ -485,15 +1606,201 @@ async fn test_messages_crud() {
     assert_eq!(timestamps[0].3, 3); // message_count
 }
 
-// ============================================================================
-// Tasks (Metrics) CRUD Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_tasks_crud() {
+async fn test_messages_bulk_upsert_idempotent() {
     let db = setup_db().await;
-    use han_db::crud::tasks;
-
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    sessions::upsert(
+        &db,
+        "session-bulk".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let make = |i: i32, content: &str| msg_entity::ActiveModel {
+        id: Set(format!("bulk-{i:04}")),
+        session_id: Set("session-bulk".to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some(content.to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set("2026-04-12T10:00:00Z".to_string()),
+        line_number: Set(i),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+
+    // More than one chunk
+    const N: i32 = 620;
+    let batch = |content: &str| (0..N).map(|i| make(i, content)).collect::<Vec<_>>();
+
+    let inserted = messages::bulk_upsert(&db, batch("first")).await.unwrap();
+    assert_eq!(inserted, N as u64);
+    let inserted = messages::bulk_upsert(&db, batch("second")).await.unwrap();
+    assert_eq!(inserted, 0, "Re-run should only update");
+    assert_eq!(messages::get_count(&db, "session-bulk").await.unwrap(), N as u64);
+
+    // Upsert overwrites content
+    let msg = messages::get(&db, "bulk-0001").await.unwrap().unwrap();
+    assert_eq!(msg.content.as_deref(), Some("second"));
+
+    // Skip-existing leaves content alone but still inserts new rows
+    let mut rerun = batch("third");
+    rerun.push(make(N, "third"));
+    let inserted = messages::bulk_upsert_skip_existing(&db, rerun).await.unwrap();
+    assert_eq!(inserted, 1);
+    assert_eq!(messages::get_count(&db, "session-bulk").await.unwrap(), N as u64 + 1);
+    let msg = messages::get(&db, "bulk-0001").await.unwrap().unwrap();
+    assert_eq!(msg.content.as_deref(), Some("second"));
+
+    assert_eq!(messages::bulk_upsert(&db, vec![]).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_tool_call_results_completion_times() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, sessions, tool_call_results};
+    use han_db::entities::{messages as msg_entity, tool_call_results as tcr_entity};
+    use sea_orm::Set;
+
+    sessions::upsert(
+        &db,
+        "session-tools".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let make = |id: &str, timestamp: &str| msg_entity::ActiveModel {
+        id: Set(id.to_string()),
+        session_id: Set("session-tools".to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(None),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set(timestamp.to_string()),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            make("result-1", "2026-04-14T10:00:02.500Z"),
+            make("result-2", "2026-04-14T10:00:05Z"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let result = |tool_call_id: &str, message_id: &str| tcr_entity::ActiveModel {
+        tool_call_id: Set(tool_call_id.to_string()),
+        session_id: Set("session-tools".to_string()),
+        message_id: Set(message_id.to_string()),
+        content: Set("ok".to_string()),
+        is_error: Set(false),
+        has_image: Set(false),
+    };
+    tool_call_results::insert_batch(
+        &db,
+        vec![
+            result("toolu_1", "result-1"),
+            result("toolu_2", "result-2"),
+            // Result whose message hasn't been indexed
+            result("toolu_3", "result-missing"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let mut times = tool_call_results::get_completion_times(
+        &db,
+        vec![
+            "toolu_1".to_string(),
+            "toolu_3".to_string(),
+            "toolu_unknown".to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+    times.sort();
+    let expected = (
+        "toolu_1".to_string(),
+        "2026-04-14T10:00:02.500Z".to_string(),
+    );
+    assert_eq!(times, vec![expected]);
+
+    let none = tool_call_results::get_completion_times(&db, vec![])
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+// ============================================================================
+// Tasks (Metrics) CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_tasks_crud() {
+    let db = setup_db().await;
+    use han_db::crud::tasks;
+
     // Create
     let task = tasks::create(
         &db,
@@ -560,6 +1867,70 @@ async fn test_tasks_crud() {
     assert!(failed.completed_at.is_some());
 }
 
+#[tokio::test]
+async fn test_tasks_insert_and_set_outcome() {
+    let db = setup_db().await;
+    use han_db::crud::{sessions, tasks};
+    use han_db::entities::tasks as task_entity;
+
+    sessions::upsert(
+        &db,
+        "session-manual-task".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let inserted = tasks::insert(
+        &db,
+        task_entity::Model {
+            id: "row-manual-1".to_string(),
+            session_id: Some("session-manual-task".to_string()),
+            task_id: "task-manual-1".to_string(),
+            description: "Write release notes".to_string(),
+            task_type: "implementation".to_string(),
+            outcome: None,
+            confidence: Some(0.6),
+            notes: Some("Drafted by hand".to_string()),
+            files_modified: Some(r#"["CHANGELOG.md"]"#.to_string()),
+            tests_added: None,
+            started_at: "2026-04-20T09:00:00Z".to_string(),
+            completed_at: None,
+        },
+    )
+    .await
+    .expect("Failed to insert task");
+    assert_eq!(inserted.task_id, "task-manual-1");
+    assert_eq!(inserted.started_at, "2026-04-20T09:00:00Z");
+
+    // Outcome without confidence keeps the recorded confidence and notes
+    let done = tasks::set_outcome(&db, "task-manual-1", "success".to_string(), None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(done.outcome.as_deref(), Some("success"));
+    assert_eq!(done.confidence, Some(0.6));
+    assert_eq!(done.notes.as_deref(), Some("Drafted by hand"));
+    assert!(done.completed_at.is_some());
+
+    let done = tasks::set_outcome(&db, "task-manual-1", "partial".to_string(), Some(0.4))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(done.confidence, Some(0.4));
+
+    assert!(
+        tasks::set_outcome(&db, "missing", "success".to_string(), None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
 // ============================================================================
 // Native Tasks CRUD Tests
 // ============================================================================
@@ -696,6 +2067,55 @@ async fn test_hooks_crud() {
     assert_eq!(exec.duration_ms, 150);
 }
 
+#[tokio::test]
+async fn test_insert_hook_executions_is_all_or_nothing() {
+    let db = setup_db().await;
+    use han_db::crud::{hooks, sessions};
+    use han_db::entities::hook_executions;
+    use sea_orm::EntityTrait;
+
+    sessions::upsert(&db, "s-ci".to_string(), None, None, None, None, None)
+        .await
+        .unwrap();
+    let execution = |id: &str| hook_executions::Model {
+        id: id.to_string(),
+        orchestration_id: None,
+        session_id: Some("s-ci".to_string()),
+        task_id: None,
+        hook_type: "Stop".to_string(),
+        hook_name: "lint".to_string(),
+        hook_source: Some("ci".to_string()),
+        directory: None,
+        duration_ms: 5,
+        exit_code: 0,
+        passed: 1,
+        output: None,
+        error: None,
+        if_changed: None,
+        command: None,
+        executed_at: "2026-04-20T10:00:00Z".to_string(),
+        status: Some("completed".to_string()),
+        consecutive_failures: None,
+        max_attempts: None,
+        pid: None,
+        plugin_root: None,
+    };
+
+    assert_eq!(hooks::insert_executions(&db, vec![]).await.unwrap(), 0);
+
+    // 60 rows span two insert chunks; the duplicate in the second fails it
+    let mut batch: Vec<_> = (0..60).map(|i| execution(&format!("ci-{i}"))).collect();
+    batch.push(execution("ci-0"));
+    assert!(hooks::insert_executions(&db, batch).await.is_err());
+    let rows = hook_executions::Entity::find().all(&db).await.unwrap();
+    assert!(rows.is_empty());
+
+    let batch: Vec<_> = (0..60).map(|i| execution(&format!("ci-{i}"))).collect();
+    assert_eq!(hooks::insert_executions(&db, batch).await.unwrap(), 60);
+    let rows = hook_executions::Entity::find().all(&db).await.unwrap();
+    assert_eq!(rows.len(), 60);
+}
+
 // ============================================================================
 // Orchestrations CRUD Tests
 // ============================================================================
@@ -872,6 +2292,31 @@ async fn test_session_files_crud() {
     assert!(updated);
 }
 
+// ============================================================================
+// Indexing Checkpoints CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_indexing_checkpoint_roundtrip() {
+    let db = setup_db().await;
+    use han_db::crud::indexing_checkpoints;
+
+    assert_eq!(indexing_checkpoints::get(&db, "s1").await.unwrap(), None);
+
+    indexing_checkpoints::save(&db, "s1", 49).await.unwrap();
+    indexing_checkpoints::save(&db, "s1", 99).await.unwrap();
+    indexing_checkpoints::save(&db, "s2", 10).await.unwrap();
+    assert_eq!(
+        indexing_checkpoints::get(&db, "s1").await.unwrap(),
+        Some(99)
+    );
+
+    assert_eq!(indexing_checkpoints::clear(&db, "s1").await.unwrap(), 1);
+    assert_eq!(indexing_checkpoints::get(&db, "s1").await.unwrap(), None);
+    assert_eq!(indexing_checkpoints::clear_all(&db).await.unwrap(), 1);
+    assert_eq!(indexing_checkpoints::get(&db, "s2").await.unwrap(), None);
+}
+
 // ============================================================================
 // FTS5 Search Tests
 // ============================================================================
@@ -923,6 +2368,7 @@ async fn test_fts5_search_messages() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:00:00Z".to_string()),
             line_number: Set(1),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -937,6 +2383,9 @@ async fn test_fts5_search_messages() {
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
         msg_entity::ActiveModel {
@@ -953,6 +2402,7 @@ async fn test_fts5_search_messages() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:01:00Z".to_string()),
             line_number: Set(2),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -967,6 +2417,9 @@ async fn test_fts5_search_messages() {
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
     ];
@@ -1014,6 +2467,353 @@ async fn test_fts5_search_messages() {
     assert_eq!(results.len(), 0);
 }
 
+#[tokio::test]
+async fn test_fts_search_messages_ranked() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use han_db::search::fts_search_messages;
+    use sea_orm::Set;
+
+    for session_id in ["session-msg-a", "session-msg-b"] {
+        sessions::upsert(
+            &db,
+            session_id.to_string(),
+            None,
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let msgs = (0..50)
+        .map(|i| {
+            let content = match i {
+                17 => "The flamingo parser crashes on flamingo input".to_string(),
+                33 => "Renamed the flamingo module".to_string(),
+                _ => format!("Routine progress update number {i}"),
+            };
+            let session_id = if i < 25 {
+                "session-msg-a"
+            } else {
+                "session-msg-b"
+            };
+            msg_entity::ActiveModel {
+                id: Set(format!("ranked-msg-{i:02}")),
+                session_id: Set(session_id.to_string()),
+                agent_id: Set(None),
+                parent_id: Set(None),
+                message_type: Set("assistant".to_string()),
+                role: Set(Some("assistant".to_string())),
+                content: Set(Some(content)),
+                tool_name: Set(None),
+                tool_input: Set(None),
+                tool_result: Set(None),
+                raw_json: Set(None),
+                timestamp: Set(format!("2026-04-20T10:{i:02}:00Z")),
+                line_number: Set(i + 1),
+                byte_offset: Set(None),
+                source_file_name: Set(None),
+                source_file_type: Set(None),
+                sentiment_score: Set(None),
+                sentiment_level: Set(None),
+                frustration_score: Set(None),
+                frustration_level: Set(None),
+                input_tokens: Set(None),
+                output_tokens: Set(None),
+                cache_read_tokens: Set(None),
+                cache_creation_tokens: Set(None),
+                lines_added: Set(None),
+                lines_removed: Set(None),
+                files_changed: Set(None),
+                human_time_ms: Set(None),
+                duration_ms: Set(None),
+                memory_id: Set(None),
+                plugin_name: Set(None),
+                indexed_at: Set(None),
+            }
+        })
+        .collect();
+    messages::insert_batch(&db, msgs).await.unwrap();
+
+    // The message mentioning the term twice ranks first
+    let results = fts_search_messages(&db, "flamingo", None, 10)
+        .await
+        .unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.message_id.as_str()).collect();
+    assert_eq!(ids, vec!["ranked-msg-17", "ranked-msg-33"]);
+    assert!(results[0].rank >= results[1].rank);
+    assert_eq!(results[0].session_id, "session-msg-a");
+    assert!(results[0].snippet.contains("flamingo"));
+
+    // Session filter
+    let results = fts_search_messages(&db, "flamingo", Some("session-msg-b"), 10)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].message_id, "ranked-msg-33");
+
+    // Limit
+    let results = fts_search_messages(&db, "routine", None, 5).await.unwrap();
+    assert_eq!(results.len(), 5);
+
+    assert!(fts_search_messages(&db, "  ", None, 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_hybrid_search() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use han_db::search::{hybrid_search, VectorHit};
+    use sea_orm::Set;
+
+    sessions::upsert(
+        &db,
+        "session-hybrid".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let msg = |id: &str, content: &str, line: i32| msg_entity::ActiveModel {
+        id: Set(id.to_string()),
+        session_id: Set("session-hybrid".to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("assistant".to_string()),
+        role: Set(Some("assistant".to_string())),
+        content: Set(Some(content.to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set("2026-04-12T10:00:00Z".to_string()),
+        line_number: Set(line),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            msg("hyb-001", "The websocket reconnect logic drops frames", 1),
+            msg("hyb-002", "Refactor the billing export into its own module", 2),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // FTS only: the document containing the term outranks the one without it
+    let results = hybrid_search(&db, "websocket", &[], 1.0, 0.0, 10)
+        .await
+        .expect("Hybrid search failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].message_id, "hyb-001");
+    assert_eq!(results[0].session_id, "session-hybrid");
+    assert!((results[0].fts_score - 1.0).abs() < 1e-9);
+    assert!(results[0].snippet.contains("websocket"));
+
+    // The vector side favors the billing message
+    let hits = vec![
+        VectorHit {
+            message_id: "hyb-001".to_string(),
+            similarity: 0.0,
+        },
+        VectorHit {
+            message_id: "hyb-002".to_string(),
+            similarity: 0.9,
+        },
+    ];
+
+    let fts_heavy = hybrid_search(&db, "websocket", &hits, 0.9, 0.1, 10)
+        .await
+        .unwrap();
+    assert_eq!(fts_heavy[0].message_id, "hyb-001");
+    assert_eq!(fts_heavy.len(), 2);
+
+    let vector_heavy = hybrid_search(&db, "websocket", &hits, 0.1, 0.9, 10)
+        .await
+        .unwrap();
+    assert_eq!(vector_heavy[0].message_id, "hyb-002");
+    assert!((vector_heavy[0].vector_score - 0.95).abs() < 1e-9);
+    assert_eq!(vector_heavy[0].fts_score, 0.0);
+    assert!(vector_heavy[0].snippet.contains("billing"));
+
+    // Limit applies after ranking
+    let top = hybrid_search(&db, "websocket", &hits, 0.1, 0.9, 1)
+        .await
+        .unwrap();
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].message_id, "hyb-002");
+}
+
+#[tokio::test]
+async fn test_fts_search_sessions() {
+    let db = setup_db().await;
+    use han_db::crud::{messages, projects, session_summaries, sessions};
+    use han_db::entities::messages as msg_entity;
+    use han_db::search::{fts_search_sessions, refresh_session_fts};
+    use sea_orm::Set;
+
+    let project = projects::upsert(
+        &db,
+        None,
+        "billing-service".to_string(),
+        "/home/user/billing-service".to_string(),
+        None,
+        "billing-service".to_string(),
+        Some(false),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let fixtures = [
+        (
+            "session-fts-a",
+            "Added authentication middleware to the API",
+            "Please add auth to the API",
+        ),
+        (
+            "session-fts-b",
+            "Fixed flaky tests in the invoice module",
+            "The authentication tests keep failing",
+        ),
+        (
+            "session-fts-c",
+            "Refactored logging",
+            "Clean up the log output",
+        ),
+    ];
+    for (i, (session_id, summary, prompt)) in fixtures.iter().enumerate() {
+        sessions::upsert(
+            &db,
+            session_id.to_string(),
+            Some(project.id.clone()),
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        session_summaries::upsert(
+            &db,
+            session_id.to_string(),
+            format!("summary-{i}"),
+            Some(summary.to_string()),
+            None,
+            "2026-04-16T10:00:00Z".to_string(),
+            1,
+        )
+        .await
+        .unwrap();
+        messages::insert_batch(
+            &db,
+            vec![msg_entity::ActiveModel {
+                id: Set(format!("fts-prompt-{i}")),
+                session_id: Set(session_id.to_string()),
+                agent_id: Set(None),
+                parent_id: Set(None),
+                message_type: Set("user".to_string()),
+                role: Set(Some("user".to_string())),
+                content: Set(Some(prompt.to_string())),
+                tool_name: Set(None),
+                tool_input: Set(None),
+                tool_result: Set(None),
+                raw_json: Set(None),
+                timestamp: Set("2026-04-16T09:00:00Z".to_string()),
+                line_number: Set(2),
+                byte_offset: Set(None),
+                source_file_name: Set(None),
+                source_file_type: Set(None),
+                sentiment_score: Set(None),
+                sentiment_level: Set(None),
+                frustration_score: Set(None),
+                frustration_level: Set(None),
+                input_tokens: Set(None),
+                output_tokens: Set(None),
+                cache_read_tokens: Set(None),
+                cache_creation_tokens: Set(None),
+                lines_added: Set(None),
+                lines_removed: Set(None),
+                files_changed: Set(None),
+                human_time_ms: Set(None),
+                duration_ms: Set(None),
+                memory_id: Set(None),
+                plugin_name: Set(None),
+                indexed_at: Set(None),
+            }],
+        )
+        .await
+        .unwrap();
+        refresh_session_fts(&db, session_id).await.unwrap();
+    }
+
+    // Prefix match: "authent" finds both sessions; the summary hit ranks first
+    let results = fts_search_sessions(&db, "authent", 10).await.unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.session_id.as_str()).collect();
+    assert_eq!(ids, vec!["session-fts-a", "session-fts-b"]);
+    assert!(results[0].rank >= results[1].rank);
+    assert_eq!(
+        results[0].project_dir.as_deref(),
+        Some("/home/user/billing-service")
+    );
+    assert!(results[0].snippet.contains("authentication"));
+
+    // Ranking is stable across repeated queries
+    for _ in 0..3 {
+        let again = fts_search_sessions(&db, "authent", 10).await.unwrap();
+        let again_ids: Vec<&str> = again.iter().map(|r| r.session_id.as_str()).collect();
+        assert_eq!(again_ids, ids);
+    }
+
+    // Project name is searchable
+    let by_project = fts_search_sessions(&db, "billing", 10).await.unwrap();
+    assert_eq!(by_project.len(), 3);
+
+    // Refreshing replaces rather than duplicates
+    refresh_session_fts(&db, "session-fts-a").await.unwrap();
+    let results = fts_search_sessions(&db, "middleware", 10).await.unwrap();
+    assert_eq!(results.len(), 1);
+
+    assert!(fts_search_sessions(&db, "   ", 10)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(fts_search_sessions(&db, "nonexistent", 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
 // ============================================================================
 // Aggregates Tests
 // ============================================================================
@@ -1054,6 +2854,7 @@ async fn test_dashboard_aggregates() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:00:00Z".to_string()),
             line_number: Set(1),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -1068,6 +2869,9 @@ async fn test_dashboard_aggregates() {
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
         msg_entity::ActiveModel {
@@ -1084,6 +2888,7 @@ async fn test_dashboard_aggregates() {
             raw_json: Set(None),
             timestamp: Set("2026-02-15T10:01:00Z".to_string()),
             line_number: Set(2),
+            byte_offset: Set(None),
             source_file_name: Set(None),
             source_file_type: Set(None),
             sentiment_score: Set(None),
@@ -1098,6 +2903,9 @@ async fn test_dashboard_aggregates() {
             lines_removed: Set(None),
             files_changed: Set(None),
             human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
             indexed_at: Set(None),
         },
     ];
@@ -1134,62 +2942,1061 @@ async fn test_activity_aggregates() {
     use han_db::entities::messages as msg_entity;
     use sea_orm::Set;
 
-    sessions::upsert(
+    sessions::upsert(
+        &db,
+        "session-act".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let msgs = vec![msg_entity::ActiveModel {
+        id: Set("act-msg-001".to_string()),
+        session_id: Set("session-act".to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(Some("user".to_string())),
+        content: Set(Some("Fix the thing".to_string())),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set("2026-02-15T10:00:00Z".to_string()),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(Some(50)),
+        output_tokens: Set(Some(25)),
+        cache_read_tokens: Set(Some(5)),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(Some(10)),
+        lines_removed: Set(Some(3)),
+        files_changed: Set(Some(2)),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    }];
+
+    messages::insert_batch(&db, msgs).await.unwrap();
+
+    let agg = query_activity_aggregates(&db, "2020-01-01")
+        .await
+        .expect("Failed to query activity aggregates");
+
+    assert_eq!(agg.total_messages, 1);
+    assert_eq!(agg.total_sessions, 1);
+    assert_eq!(agg.total_input_tokens, 50);
+    assert_eq!(agg.daily_activity.len(), 1);
+    assert_eq!(agg.daily_activity[0].lines_added, 10);
+    assert_eq!(agg.hourly_activity.len(), 1);
+    assert_eq!(agg.hourly_activity[0].hour, 10); // 10:00 UTC
+}
+
+#[tokio::test]
+async fn test_mcp_server_usage() {
+    let db = setup_db().await;
+    use han_db::aggregates::query_mcp_server_usage;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    for id in ["session-mcp-a", "session-mcp-b"] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            None,
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let make = |id: &str, session_id: &str, event_type: &str, raw_json: &str, line: i32| {
+        msg_entity::ActiveModel {
+            id: Set(id.to_string()),
+            session_id: Set(session_id.to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set("han_event".to_string()),
+            role: Set(None),
+            content: Set(None),
+            tool_name: Set(Some(event_type.to_string())),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(Some(raw_json.to_string())),
+            timestamp: Set("2026-04-14T10:00:00Z".to_string()),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(None),
+            output_tokens: Set(None),
+            cache_read_tokens: Set(None),
+            cache_creation_tokens: Set(None),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    };
+
+    let msgs = vec![
+        make(
+            "mcp-call-1",
+            "session-mcp-a",
+            "mcp_tool_call",
+            r#"{"type":"mcp_tool_call","data":{"server_name":"github","tool":"search"}}"#,
+            1,
+        ),
+        make(
+            "mcp-result-1",
+            "session-mcp-a",
+            "mcp_tool_result",
+            r#"{"type":"mcp_tool_result","data":{"server_name":"github","tool":"search","call_id":"mcp-call-1","success":true,"duration_ms":120}}"#,
+            2,
+        ),
+        make(
+            "mcp-call-2",
+            "session-mcp-a",
+            "mcp_tool_call",
+            r#"{"type":"mcp_tool_call","data":{"server_name":"github","tool":"search"}}"#,
+            3,
+        ),
+        make(
+            "mcp-result-2",
+            "session-mcp-a",
+            "mcp_tool_result",
+            r#"{"type":"mcp_tool_result","data":{"server_name":"github","tool":"search","call_id":"mcp-call-2","success":false,"duration_ms":30,"error":"rate limited"}}"#,
+            4,
+        ),
+        make(
+            "mcp-call-3",
+            "session-mcp-b",
+            "mcp_tool_call",
+            r#"{"type":"mcp_tool_call","data":{"server_name":"memory","tool":"recall"}}"#,
+            1,
+        ),
+        make(
+            "mcp-result-3",
+            "session-mcp-b",
+            "mcp_tool_result",
+            r#"{"type":"mcp_tool_result","data":{"server_name":"memory","tool":"recall","call_id":"mcp-call-3","success":true,"duration_ms":5}}"#,
+            2,
+        ),
+    ];
+    messages::insert_batch(&db, msgs).await.unwrap();
+
+    // Scoped to one session
+    let usage = query_mcp_server_usage(&db, Some("session-mcp-a"), None)
+        .await
+        .expect("Failed to query MCP usage");
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].server_name, "github");
+    assert_eq!(usage[0].tool_name, "search");
+    assert_eq!(usage[0].call_count, 2);
+    assert_eq!(usage[0].success_count, 1);
+    assert_eq!(usage[0].error_count, 1);
+    assert_eq!(usage[0].total_duration_ms, 150);
+
+    // Across all sessions, busiest first
+    let all = query_mcp_server_usage(&db, None, None).await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].server_name, "github");
+    assert_eq!(all[1].server_name, "memory");
+    assert_eq!(all[1].call_count, 1);
+
+    let top = query_mcp_server_usage(&db, None, Some(1)).await.unwrap();
+    assert_eq!(top.len(), 1);
+
+    let none = query_mcp_server_usage(&db, Some("session-none"), None)
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn test_mcp_token_totals() {
+    let db = setup_db().await;
+    use han_db::aggregates::query_mcp_token_totals;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    for id in ["session-mcp-tokens", "session-mcp-other"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    let make = |id: &str, session_id: &str, event_type: &str, data: &str, line: i32| {
+        msg_entity::ActiveModel {
+            id: Set(id.to_string()),
+            session_id: Set(session_id.to_string()),
+            message_type: Set("han_event".to_string()),
+            tool_name: Set(Some(event_type.to_string())),
+            raw_json: Set(Some(format!(r#"{{"type":"{event_type}","data":{data}}}"#))),
+            timestamp: Set("2026-04-14T10:00:00Z".to_string()),
+            line_number: Set(line),
+            ..Default::default()
+        }
+    };
+    let msgs = vec![
+        make(
+            "tok-1",
+            "session-mcp-tokens",
+            "mcp_tool_result",
+            r#"{"model":"m-a","usage":{"input_tokens":100,"output_tokens":10}}"#,
+            1,
+        ),
+        make(
+            "tok-2",
+            "session-mcp-tokens",
+            "mcp_tool_result",
+            r#"{"model":"m-a","usage":{"input_tokens":50,"output_tokens":5}}"#,
+            2,
+        ),
+        make(
+            "tok-3",
+            "session-mcp-tokens",
+            "mcp_tool_result",
+            r#"{"usage":{"input_tokens":7}}"#,
+            3,
+        ),
+        // No usage: not counted
+        make(
+            "tok-4",
+            "session-mcp-tokens",
+            "mcp_tool_result",
+            r#"{"model":"m-b","success":true}"#,
+            4,
+        ),
+        // Calls never carry usage
+        make(
+            "tok-5",
+            "session-mcp-tokens",
+            "mcp_tool_call",
+            r#"{"model":"m-a","usage":{"input_tokens":999}}"#,
+            5,
+        ),
+        make(
+            "tok-6",
+            "session-mcp-other",
+            "mcp_tool_result",
+            r#"{"model":"m-a","usage":{"input_tokens":1000,"output_tokens":1000}}"#,
+            1,
+        ),
+    ];
+    messages::insert_batch(&db, msgs).await.unwrap();
+
+    let totals = query_mcp_token_totals(&db, "session-mcp-tokens")
+        .await
+        .expect("Failed to query MCP token totals");
+    let rows: Vec<_> = totals
+        .iter()
+        .map(|t| (t.model.as_deref(), t.input_tokens, t.output_tokens))
+        .collect();
+    assert_eq!(rows, vec![(None, 7, 0), (Some("m-a"), 150, 15)]);
+
+    let none = query_mcp_token_totals(&db, "session-none").await.unwrap();
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn test_tool_call_stats() {
+    let db = setup_db().await;
+    use han_db::aggregates::query_tool_call_stats;
+    use han_db::crud::{messages, sessions, tool_call_results};
+    use han_db::entities::{messages as msg_entity, tool_call_results as tcr_entity};
+    use sea_orm::Set;
+
+    for id in ["tcs-a", "tcs-b"] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            None,
+            Some("active".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    // `tool` is None for the user messages carrying results
+    let make = |session: &str, id: &str, tool: Option<&str>, at: &str| msg_entity::ActiveModel {
+        id: Set(id.to_string()),
+        session_id: Set(session.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set(if tool.is_some() { "tool_use" } else { "user" }.to_string()),
+        role: Set(None),
+        content: Set(None),
+        tool_name: Set(tool.map(str::to_string)),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(None),
+        timestamp: Set(format!("2026-04-14T{at}Z")),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            make("tcs-a", "tu-1", Some("Bash"), "10:00:00"),
+            make("tcs-a", "res-1", None, "10:00:01.500"),
+            make("tcs-a", "tu-2", Some("Bash"), "10:00:10"),
+            make("tcs-a", "res-2", None, "10:00:12"),
+            make("tcs-a", "tu-3", Some("bash"), "10:00:20"),
+            make("tcs-a", "res-3", None, "10:00:21"),
+            // Call still waiting on its result
+            make("tcs-a", "tu-4", Some("Read"), "10:00:30"),
+            make("tcs-b", "tu-5", Some("Bash"), "09:00:00"),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let result = |tool_call_id: &str, message_id: &str, is_error: bool| tcr_entity::ActiveModel {
+        tool_call_id: Set(tool_call_id.to_string()),
+        session_id: Set("tcs-a".to_string()),
+        message_id: Set(message_id.to_string()),
+        content: Set("out".to_string()),
+        is_error: Set(is_error),
+        has_image: Set(false),
+    };
+    tool_call_results::insert_batch(
+        &db,
+        vec![
+            result("tu-1", "res-1", false),
+            result("tu-2", "res-2", true),
+            result("tu-3", "res-3", false),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let stats = query_tool_call_stats(&db, Some("tcs-a"), None)
+        .await
+        .expect("Failed to query tool call stats");
+    let names: Vec<&str> = stats.iter().map(|s| s.tool_name.as_str()).collect();
+    assert_eq!(names, vec!["Bash", "Read", "bash"]);
+
+    let bash = &stats[0];
+    assert_eq!(bash.call_count, 2);
+    assert_eq!(bash.success_count, 1);
+    assert_eq!(bash.error_count, 1);
+    assert_eq!(bash.total_duration_ms, 3500);
+    assert_eq!(bash.timed_count, 2);
+
+    let read = &stats[1];
+    assert_eq!(read.call_count, 1);
+    assert_eq!(read.success_count + read.error_count, 0);
+    assert_eq!(read.timed_count, 0);
+    assert_eq!(read.total_duration_ms, 0);
+
+    // Across sessions, `since` drops the earlier call in tcs-b
+    let all = query_tool_call_stats(&db, None, None).await.unwrap();
+    assert_eq!(all[0].call_count, 3);
+    let since = query_tool_call_stats(&db, None, Some("2026-04-14T10:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(since[0].call_count, 2);
+}
+
+#[tokio::test]
+async fn test_frustration_points() {
+    let db = setup_db().await;
+    use han_db::aggregates::query_frustration_points;
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    sessions::upsert(
+        &db,
+        "session-frustration".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let make = |line: i32,
+                message_type: &str,
+                sentiment: Option<f64>,
+                frustration: Option<(f64, &str)>| {
+        msg_entity::ActiveModel {
+            id: Set(format!("frustration-{line}")),
+            session_id: Set("session-frustration".to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set(message_type.to_string()),
+            role: Set(Some(message_type.to_string())),
+            content: Set(Some("text".to_string())),
+            tool_name: Set(None),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(None),
+            timestamp: Set(format!("2026-04-14T1{line}:00:00Z")),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(sentiment),
+            sentiment_level: Set(None),
+            frustration_score: Set(frustration.map(|(s, _)| s)),
+            frustration_level: Set(frustration.map(|(_, l)| l.to_string())),
+            input_tokens: Set(None),
+            output_tokens: Set(None),
+            cache_read_tokens: Set(None),
+            cache_creation_tokens: Set(None),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    };
+
+    messages::insert_batch(
+        &db,
+        vec![
+            make(1, "user", Some(1.0), None),
+            make(2, "assistant", None, None),
+            make(3, "user", Some(-4.0), Some((6.5, "high"))),
+            make(4, "user", None, None),
+            make(5, "user", Some(-1.0), Some((2.0, "low"))),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let points = query_frustration_points(&db, "session-frustration")
+        .await
+        .unwrap();
+    // Assistant and unanalyzed user messages are skipped
+    let scores: Vec<f64> = points.iter().map(|p| p.1).collect();
+    assert_eq!(scores, vec![0.0, 6.5, 2.0]);
+    assert_eq!(points[1].2.as_deref(), Some("high"));
+}
+
+#[tokio::test]
+async fn test_hook_execution_stats() {
+    let db = setup_db().await;
+    use han_db::aggregates::{percentile_cont, query_hook_execution_stats};
+    use han_db::entities::hook_executions;
+    use sea_orm::{EntityTrait, Set};
+
+    // hook is "plugin:type:name", run on the given day of April; no error means it passed
+    let run = |hook: &str, dir: &str, duration: i32, error: Option<&str>, day: u32| {
+        let mut parts = hook.split(':');
+        let (plugin, hook_type, hook_name) = (parts.next(), parts.next(), parts.next());
+        let executed_at = format!("2026-04-{day:02}T10:00:00Z");
+        hook_executions::ActiveModel {
+            id: Set(format!("{hook}-{dir}-{executed_at}")),
+            orchestration_id: Set(None),
+            session_id: Set(None),
+            task_id: Set(None),
+            hook_type: Set(hook_type.unwrap().to_string()),
+            hook_name: Set(hook_name.unwrap().to_string()),
+            hook_source: Set(plugin.map(str::to_string)),
+            directory: Set(Some(dir.to_string())),
+            duration_ms: Set(duration),
+            exit_code: Set(if error.is_some() { 1 } else { 0 }),
+            passed: Set(error.is_none() as i32),
+            output: Set(None),
+            error: Set(error.map(str::to_string)),
+            if_changed: Set(None),
+            command: Set(None),
+            executed_at: Set(executed_at),
+            status: Set(Some("completed".to_string())),
+            consecutive_failures: Set(None),
+            max_attempts: Set(None),
+            pid: Set(None),
+            plugin_root: Set(None),
+        }
+    };
+    hook_executions::Entity::insert_many(vec![
+        run("biome:Stop:lint", "/proj", 100, None, 1),
+        run("biome:Stop:lint", "/proj/pkg", 300, Some("old"), 2),
+        run("biome:Stop:lint", "/proj", 200, Some("lint failed"), 3),
+        run("jest:Stop:test", "/proj", 400, None, 3),
+        run("jest:PostToolUse:test-changed", "/proj", 500, None, 4),
+        // Outside the project and outside the date range
+        run("biome:Stop:lint", "/project-b", 900, Some("other"), 3),
+        run("jest:Stop:test", "/proj", 900, Some("too late"), 20),
+    ])
+    .exec(&db)
+    .await
+    .unwrap();
+
+    let stats = query_hook_execution_stats(
+        &db,
+        Some("/proj/"),
+        Some("2026-04-01T00:00:00Z"),
+        Some("2026-04-20T00:00:00Z"),
+    )
+    .await
+    .unwrap();
+
+    let groups: Vec<_> = stats
+        .groups
+        .iter()
+        .map(|g| (g.hook_name.as_str(), g.total, g.passed))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![("lint", 3, 1), ("test-changed", 1, 1), ("test", 1, 1)]
+    );
+    assert_eq!(stats.groups[0].plugin_name, "biome");
+    assert_eq!(stats.groups[0].total_duration_ms, 600);
+    assert_eq!(stats.groups[0].last_error.as_deref(), Some("lint failed"));
+    assert_eq!(stats.groups[1].last_error, None);
+
+    // Durations 100..=500 in steps of 100
+    assert_eq!(stats.p50_duration_ms, 300);
+    assert_eq!(stats.p95_duration_ms, 480);
+    assert_eq!(percentile_cont(&[], 0.5), 0.0);
+
+    let everything = query_hook_execution_stats(&db, None, None, None)
+        .await
+        .unwrap();
+    let total: i64 = everything.groups.iter().map(|g| g.total).sum();
+    assert_eq!(total, 7);
+}
+
+#[tokio::test]
+async fn test_hook_plugin_stats() {
+    let db = setup_db().await;
+    use han_db::aggregates::{HookPluginRow, session_hook_plugin_stats, top_hook_plugins};
+    use han_db::crud::{hooks, sessions};
+
+    for id in ["session-a", "session-b"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+    // (session, plugin, duration, passed)
+    let runs = [
+        ("session-a", Some("biome"), 100, true),
+        ("session-a", Some("biome"), 200, false),
+        ("session-a", Some("jest"), 50, true),
+        ("session-b", Some("jest"), 10, true),
+        ("session-b", Some("jest"), 20, true),
+        ("session-b", None, 5, false),
+    ];
+    for (session_id, plugin, duration, passed) in runs {
+        hooks::record_execution(
+            &db,
+            Some(session_id.to_string()),
+            None,
+            "Stop".to_string(),
+            "check".to_string(),
+            plugin.map(str::to_string),
+            None,
+            duration,
+            if passed { 0 } else { 1 },
+            passed,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    let row = |plugin: &str, executions, passes, duration| HookPluginRow {
+        plugin_name: plugin.to_string(),
+        execution_count: executions,
+        pass_count: passes,
+        total_duration_ms: duration,
+    };
+
+    let ids = ["session-a", "session-b", "session-none"].map(String::from);
+    let stats = session_hook_plugin_stats(&db, &ids).await.unwrap();
+    assert_eq!(
+        stats["session-a"],
+        vec![row("biome", 2, 1, 300), row("jest", 1, 1, 50)]
+    );
+    assert_eq!(
+        stats["session-b"],
+        vec![row("jest", 2, 2, 30), row("unknown", 1, 0, 5)]
+    );
+    assert!(!stats.contains_key("session-none"));
+    let none = session_hook_plugin_stats(&db, &[]).await.unwrap();
+    assert!(none.is_empty());
+
+    let top = top_hook_plugins(&db, None, 2).await.unwrap();
+    assert_eq!(top, vec![row("jest", 3, 3, 80), row("biome", 2, 1, 300)]);
+    let top = top_hook_plugins(&db, Some("2000-01-01T00:00:00Z"), 10)
+        .await
+        .unwrap();
+    assert_eq!(top.len(), 3);
+    let top = top_hook_plugins(&db, Some("2999-01-01T00:00:00Z"), 10)
+        .await
+        .unwrap();
+    assert!(top.is_empty());
+}
+
+#[tokio::test]
+async fn test_session_token_totals() {
+    let db = setup_db().await;
+    use han_db::aggregates::{TokenTotals, session_token_totals};
+    use han_db::crud::{messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    for id in ["tok-a", "tok-b", "tok-c"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    // (session, line, message_type, model, input, output, cache_read, cache_creation)
+    let msg = |session: &str, line: i32, kind: &str, model: &str, tokens: [i32; 4]| {
+        let raw = serde_json::json!({ "message": { "model": model } });
+        msg_entity::ActiveModel {
+            id: Set(format!("{session}-{line}")),
+            session_id: Set(session.to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set(kind.to_string()),
+            role: Set(Some(kind.to_string())),
+            content: Set(None),
+            tool_name: Set(None),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(Some(raw.to_string())),
+            timestamp: Set(format!("2026-04-20T10:00:{line:02}Z")),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(Some(tokens[0])),
+            output_tokens: Set(Some(tokens[1])),
+            cache_read_tokens: Set(Some(tokens[2])),
+            cache_creation_tokens: Set(Some(tokens[3])),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    };
+    // Bare message objects carry the model at the top level
+    let mut bare = msg("tok-b", 2, "assistant", "", [1, 1, 1, 1]);
+    bare.raw_json = Set(Some(r#"{"model":"sonnet"}"#.to_string()));
+    messages::insert_batch(
+        &db,
+        vec![
+            msg("tok-a", 1, "assistant", "opus", [100, 10, 1000, 5]),
+            msg("tok-a", 2, "assistant", "opus", [200, 20, 2000, 0]),
+            msg("tok-a", 3, "assistant", "haiku", [1, 2, 3, 4]),
+            // User messages never count towards usage
+            msg("tok-a", 4, "user", "opus", [9999, 9999, 9999, 9999]),
+            msg("tok-b", 1, "assistant", "opus", [7, 8, 9, 10]),
+            bare,
+            msg("tok-c", 1, "user", "opus", [5, 5, 5, 5]),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let ids: Vec<String> = ["tok-a", "tok-b", "tok-c", "tok-missing"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let totals = session_token_totals(&db, &ids).await.unwrap();
+    assert_eq!(totals.len(), 2);
+
+    let sums = |t: &TokenTotals| {
+        let (i, o) = (t.input_tokens, t.output_tokens);
+        (i, o, t.cache_read_tokens, t.cache_creation_tokens)
+    };
+
+    let a = &totals["tok-a"];
+    assert_eq!(sums(a), (301, 32, 3003, 9));
+    let models: Vec<_> = a
+        .by_model
+        .iter()
+        .map(|m| (m.model.as_deref(), m.input_tokens))
+        .collect();
+    assert_eq!(models, vec![(Some("haiku"), 1), (Some("opus"), 300)]);
+
+    let b = &totals["tok-b"];
+    assert_eq!(sums(b), (8, 9, 10, 11));
+    let models: Vec<_> = b.by_model.iter().map(|m| m.model.as_deref()).collect();
+    assert_eq!(models, vec![Some("opus"), Some("sonnet")]);
+
+    assert!(session_token_totals(&db, &[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_session_task_counts() {
+    let db = setup_db().await;
+    use han_db::aggregates::session_task_counts;
+    use han_db::crud::{sessions, tasks};
+
+    for id in ["task-a", "task-b"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+    // (session, task, completed_at hour, outcome, confidence); all start at 09:00
+    let rows = [
+        ("task-a", "a1", None, None, None),
+        ("task-a", "a2", Some(13), Some("success"), Some(0.25)),
+        ("task-a", "a3", Some(11), Some("failed"), None),
+        ("task-a", "a4", Some(10), Some("failure"), Some(1.0)),
+        ("task-b", "b1", Some(12), None, None),
+    ];
+    for (session, task_id, hour, outcome, confidence) in rows {
+        let task = han_db::entities::tasks::Model {
+            id: task_id.to_string(),
+            session_id: Some(session.to_string()),
+            task_id: task_id.to_string(),
+            description: task_id.to_string(),
+            task_type: "fix".to_string(),
+            outcome: outcome.map(str::to_string),
+            confidence,
+            notes: None,
+            files_modified: None,
+            tests_added: None,
+            started_at: "2026-04-20T09:00:00Z".to_string(),
+            completed_at: hour.map(|h| format!("2026-04-20T{h}:00:00Z")),
+        };
+        tasks::insert(&db, task).await.unwrap();
+    }
+
+    let ids: Vec<String> = ["task-a", "task-b", "task-missing"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let counts = session_task_counts(&db, &ids).await.unwrap();
+    assert_eq!(counts.len(), 2);
+
+    let a = &counts["task-a"];
+    assert_eq!((a.active, a.completed, a.failed), (1, 1, 2));
+    assert!((a.completed_hours - 1.0).abs() < 1e-6);
+
+    let b = &counts["task-b"];
+    assert_eq!((b.active, b.completed, b.failed), (0, 1, 0));
+    assert!((b.completed_hours - 3.0).abs() < 1e-6);
+
+    assert!(!counts.contains_key("task-missing"));
+    assert!(session_task_counts(&db, &[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_calibration_score() {
+    let db = setup_db().await;
+    use han_db::aggregates::{calibration_score, compute_calibration_score};
+    use han_db::crud::{sessions, tasks};
+
+    for id in ["cal-a", "cal-b", "cal-empty"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+    // (session, task, completed, outcome, confidence)
+    let rows = [
+        ("cal-a", "c1", true, Some("failure"), Some(0.12)),
+        ("cal-a", "c2", true, Some("failure"), Some(0.18)),
+        ("cal-a", "c3", true, Some("success"), Some(0.9)),
+        ("cal-a", "c4", true, Some("success"), Some(1.0)),
+        // Not scored: still active, no outcome, no confidence
+        ("cal-a", "c5", false, None, Some(0.5)),
+        ("cal-a", "c6", true, None, Some(0.5)),
+        ("cal-a", "c7", true, Some("success"), None),
+        ("cal-b", "c8", true, Some("failure"), Some(0.95)),
+    ];
+    for (session, task_id, completed, outcome, confidence) in rows {
+        let task = han_db::entities::tasks::Model {
+            id: task_id.to_string(),
+            session_id: Some(session.to_string()),
+            task_id: task_id.to_string(),
+            description: task_id.to_string(),
+            task_type: "fix".to_string(),
+            outcome: outcome.map(str::to_string),
+            confidence,
+            notes: None,
+            files_modified: None,
+            tests_added: None,
+            started_at: "2026-04-20T09:00:00Z".to_string(),
+            completed_at: completed.then(|| "2026-04-20T10:00:00Z".to_string()),
+        };
+        tasks::insert(&db, task).await.unwrap();
+    }
+    let score = |ids: &[&str]| {
+        let ids: Vec<String> = ids.iter().map(|s| s.to_string()).collect();
+        let db = &db;
+        async move { compute_calibration_score(db, &ids).await.unwrap() }
+    };
+
+    // 0.1-0.2 bucket: 0% success vs 0.15; 0.9-1.0 bucket: 100% vs 0.95
+    let a = score(&["cal-a"]).await.unwrap();
+    assert!((a - (1.0 - (0.15f64.powi(2) + 0.05f64.powi(2)) / 2.0)).abs() < 1e-9);
+    // One confident failure
+    let b = score(&["cal-b"]).await.unwrap();
+    assert!((b - (1.0 - 0.95f64.powi(2))).abs() < 1e-9);
+    // Both sessions share the top bucket: 2 of 3 succeeded
+    let both = score(&["cal-a", "cal-b"]).await.unwrap();
+    let top = (0.95 - 2.0 / 3.0f64).powi(2);
+    assert!((both - (1.0 - (0.15f64.powi(2) + top) / 2.0)).abs() < 1e-9);
+
+    assert_eq!(score(&["cal-empty"]).await, None);
+    assert_eq!(score(&[]).await, None);
+
+    // Success rates matching every bucket midpoint are perfectly calibrated
+    let calibrated = (0..20).map(|i| (0.55, i < 11));
+    assert_eq!(calibration_score(calibrated), Some(1.0));
+    assert_eq!(calibration_score([]), None);
+}
+
+#[tokio::test]
+async fn test_weekly_summary() {
+    let db = setup_db().await;
+    use chrono::NaiveDate;
+    use han_db::aggregates::weekly_summary_before;
+    use han_db::crud::{hooks, messages, projects, sessions};
+    use han_db::entities::{hook_executions, messages as msg_entity};
+    use sea_orm::Set;
+
+    let mut project_ids = Vec::new();
+    for name in ["app", "other"] {
+        let project = projects::upsert(
+            &db,
+            None,
+            name.to_string(),
+            format!("/home/user/{name}"),
+            None,
+            name.to_string(),
+            Some(false),
+            None,
+        )
+        .await
+        .unwrap();
+        project_ids.push(project.id);
+    }
+    for (id, project) in [
+        ("wk-old", 0),
+        ("wk-a", 0),
+        ("wk-b", 0),
+        ("wk-c", 0),
+        ("wk-current", 0),
+        ("wk-other", 1),
+    ] {
+        sessions::upsert(
+            &db,
+            id.to_string(),
+            Some(project_ids[project].clone()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    // (session, line, timestamp, message_type, tool_name, input tokens)
+    let msg = |session: &str, line: i32, ts: &str, kind: &str, tool: Option<&str>, tokens: i32| {
+        msg_entity::ActiveModel {
+            id: Set(format!("{session}-{line}")),
+            session_id: Set(session.to_string()),
+            agent_id: Set(None),
+            parent_id: Set(None),
+            message_type: Set(kind.to_string()),
+            role: Set(None),
+            content: Set(None),
+            tool_name: Set(tool.map(str::to_string)),
+            tool_input: Set(None),
+            tool_result: Set(None),
+            raw_json: Set(None),
+            timestamp: Set(ts.to_string()),
+            line_number: Set(line),
+            byte_offset: Set(None),
+            source_file_name: Set(None),
+            source_file_type: Set(None),
+            sentiment_score: Set(None),
+            sentiment_level: Set(None),
+            frustration_score: Set(None),
+            frustration_level: Set(None),
+            input_tokens: Set(Some(tokens)),
+            output_tokens: Set(Some(tokens)),
+            cache_read_tokens: Set(None),
+            cache_creation_tokens: Set(None),
+            lines_added: Set(None),
+            lines_removed: Set(None),
+            files_changed: Set(None),
+            human_time_ms: Set(None),
+            duration_ms: Set(None),
+            memory_id: Set(None),
+            plugin_name: Set(None),
+            indexed_at: Set(None),
+        }
+    };
+    let tool = |session: &str, line: i32, ts: &str, name: &str| {
+        msg(session, line, ts, "tool_use", Some(name), 0)
+    };
+    messages::insert_batch(
         &db,
-        "session-act".to_string(),
-        None,
-        Some("active".to_string()),
-        None,
-        None,
-        None,
+        vec![
+            // The Sunday before the window
+            msg("wk-old", 1, "2025-12-14T23:59:59Z", "user", None, 0),
+            // The first Monday of the window, at midnight
+            msg("wk-a", 1, "2025-12-15T00:00:00Z", "user", None, 0),
+            msg("wk-a", 2, "2025-12-15T00:01:00Z", "assistant", None, 50),
+            tool("wk-a", 3, "2025-12-15T00:02:00Z", "Read"),
+            // The week of Dec 29 runs into the new year
+            msg("wk-b", 1, "2025-12-31T12:00:00Z", "user", None, 0),
+            tool("wk-b", 2, "2025-12-31T12:01:00Z", "Read"),
+            tool("wk-b", 3, "2025-12-31T12:02:00Z", "Bash"),
+            msg("wk-b", 4, "2025-12-31T12:03:00Z", "assistant", None, 1),
+            // Later messages stay with the week the session started in
+            tool("wk-c", 1, "2026-01-04T23:00:00Z", "Read"),
+            msg("wk-c", 2, "2026-01-13T09:00:00Z", "assistant", None, 2),
+            // The current, incomplete week
+            msg("wk-current", 1, "2026-01-12T00:00:00Z", "user", None, 0),
+            msg("wk-other", 1, "2026-01-01T12:00:00Z", "user", None, 0),
+        ],
     )
     .await
     .unwrap();
 
-    let msgs = vec![msg_entity::ActiveModel {
-        id: Set("act-msg-001".to_string()),
-        session_id: Set("session-act".to_string()),
-        agent_id: Set(None),
-        parent_id: Set(None),
-        message_type: Set("user".to_string()),
-        role: Set(Some("user".to_string())),
-        content: Set(Some("Fix the thing".to_string())),
-        tool_name: Set(None),
-        tool_input: Set(None),
-        tool_result: Set(None),
-        raw_json: Set(None),
-        timestamp: Set("2026-02-15T10:00:00Z".to_string()),
-        line_number: Set(1),
-        source_file_name: Set(None),
-        source_file_type: Set(None),
-        sentiment_score: Set(None),
-        sentiment_level: Set(None),
-        frustration_score: Set(None),
-        frustration_level: Set(None),
-        input_tokens: Set(Some(50)),
-        output_tokens: Set(Some(25)),
-        cache_read_tokens: Set(Some(5)),
-        cache_creation_tokens: Set(None),
-        lines_added: Set(Some(10)),
-        lines_removed: Set(Some(3)),
-        files_changed: Set(Some(2)),
-        human_time_ms: Set(None),
-        indexed_at: Set(None),
-    }];
-
-    messages::insert_batch(&db, msgs).await.unwrap();
+    let run = |id: &str, session: &str, passed: bool| hook_executions::Model {
+        id: id.to_string(),
+        orchestration_id: None,
+        session_id: Some(session.to_string()),
+        task_id: None,
+        hook_type: "Stop".to_string(),
+        hook_name: "lint".to_string(),
+        hook_source: None,
+        directory: None,
+        duration_ms: 10,
+        exit_code: if passed { 0 } else { 1 },
+        passed: passed as i32,
+        output: None,
+        error: None,
+        if_changed: None,
+        command: None,
+        executed_at: "2026-01-01T00:00:00Z".to_string(),
+        status: None,
+        consecutive_failures: None,
+        max_attempts: None,
+        pid: None,
+        plugin_root: None,
+    };
+    hooks::insert_executions(
+        &db,
+        vec![
+            run("h1", "wk-b", true),
+            run("h2", "wk-c", false),
+            run("h3", "wk-c", true),
+            run("h4", "wk-c", true),
+        ],
+    )
+    .await
+    .unwrap();
 
-    let agg = query_activity_aggregates(&db, "2020-01-01")
+    let today = NaiveDate::from_ymd_opt(2026, 1, 14).unwrap();
+    let weeks = weekly_summary_before(&db, Some("/home/user/app/"), 4, today)
         .await
-        .expect("Failed to query activity aggregates");
-
-    assert_eq!(agg.total_messages, 1);
-    assert_eq!(agg.total_sessions, 1);
-    assert_eq!(agg.total_input_tokens, 50);
-    assert_eq!(agg.daily_activity.len(), 1);
-    assert_eq!(agg.daily_activity[0].lines_added, 10);
-    assert_eq!(agg.hourly_activity.len(), 1);
-    assert_eq!(agg.hourly_activity[0].hour, 10); // 10:00 UTC
+        .unwrap();
+    let shape: Vec<_> = weeks
+        .iter()
+        .map(|w| (w.week_start.as_str(), w.week_number, w.session_count))
+        .collect();
+    assert_eq!(
+        shape,
+        vec![
+            ("2025-12-15", 51, 1),
+            ("2025-12-22", 52, 0),
+            ("2025-12-29", 1, 2),
+            ("2026-01-05", 2, 0),
+        ]
+    );
+    // ISO week 1 of 2026 starts in 2025
+    let years: Vec<_> = weeks.iter().map(|w| w.year).collect();
+    assert_eq!(years, vec![2025, 2025, 2026, 2026]);
+
+    assert_eq!(weeks[0].message_count, 3);
+    assert_eq!(weeks[0].total_tokens, 100);
+    assert_eq!(weeks[0].unique_tools, 1);
+    assert_eq!(weeks[0].hook_pass_rate, 0.0);
+
+    assert_eq!(weeks[1].message_count, 0);
+    assert_eq!(weeks[1].total_tokens, 0);
+
+    assert_eq!(weeks[2].message_count, 6);
+    assert_eq!(weeks[2].total_tokens, 6);
+    assert_eq!(weeks[2].unique_tools, 2);
+    assert_eq!(weeks[2].hook_pass_rate, 0.75);
+
+    // Without a project filter the other project's session is counted too
+    let all = weekly_summary_before(&db, None, 4, today).await.unwrap();
+    assert_eq!(all[2].session_count, 3);
+
+    assert!(
+        weekly_summary_before(&db, None, 0, today)
+            .await
+            .unwrap()
+            .is_empty()
+    );
 }
 
 // ============================================================================
@@ -1460,6 +4267,189 @@ async fn test_session_todos_crud() {
     assert!(missing.is_none());
 }
 
+#[tokio::test]
+async fn test_session_todos_replace_todos() {
+    let db = setup_db().await;
+    use han_db::crud::session_todos::{self, TodoInput, TodoSource};
+    use han_db::crud::sessions;
+
+    sessions::upsert(
+        &db,
+        "session-tdi".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let todos = vec![
+        TodoInput {
+            id: Some("1".to_string()),
+            content: "Write parser".to_string(),
+            status: "in_progress".to_string(),
+            active_form: Some("Writing parser".to_string()),
+            priority: Some("high".to_string()),
+        },
+        TodoInput {
+            id: None,
+            content: "Add tests".to_string(),
+            status: "pending".to_string(),
+            active_form: None,
+            priority: None,
+        },
+    ];
+
+    let count = session_todos::replace_todos(
+        &db,
+        "session-tdi".to_string(),
+        TodoSource {
+            message_id: "msg-tdi-001".to_string(),
+            tool_call_id: Some("toolu_tdi_001".to_string()),
+            timestamp: "2026-02-15T10:00:00Z".to_string(),
+            line_number: 4,
+        },
+        todos.clone(),
+    )
+    .await
+    .expect("Failed to replace todos");
+    assert_eq!(count, 2);
+
+    let stored = session_todos::get(&db, "session-tdi")
+        .await
+        .unwrap()
+        .expect("Todos should be stored");
+    assert_eq!(session_todos::parse_todos(&stored.todos_json), todos);
+    assert!(stored.todos_json.contains("\"activeForm\":\"Writing parser\""));
+    assert_eq!(stored.tool_call_id.as_deref(), Some("toolu_tdi_001"));
+
+    // A later TodoWrite replaces the list
+    let count = session_todos::replace_todos(
+        &db,
+        "session-tdi".to_string(),
+        TodoSource {
+            message_id: "msg-tdi-002".to_string(),
+            tool_call_id: Some("toolu_tdi_002".to_string()),
+            timestamp: "2026-02-15T10:05:00Z".to_string(),
+            line_number: 9,
+        },
+        vec![TodoInput {
+            status: "completed".to_string(),
+            ..todos[0].clone()
+        }],
+    )
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    let stored = session_todos::get(&db, "session-tdi").await.unwrap().unwrap();
+    let parsed = session_todos::parse_todos(&stored.todos_json);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].status, "completed");
+    assert_eq!(stored.message_id, "msg-tdi-002");
+    assert_eq!(stored.tool_call_id.as_deref(), Some("toolu_tdi_002"));
+}
+
+#[tokio::test]
+async fn test_session_todos_upsert_todos_merges_by_id() {
+    let db = setup_db().await;
+    use han_db::crud::session_todos::{self, TodoInput, TodoSource};
+    use han_db::crud::sessions;
+
+    sessions::upsert(
+        &db,
+        "session-tdm".to_string(),
+        None,
+        Some("active".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Merging needs the row to exist
+    let todo = |id: &str, content: &str, status: &str| TodoInput {
+        id: Some(id.to_string()),
+        content: content.to_string(),
+        status: status.to_string(),
+        active_form: None,
+        priority: None,
+    };
+    assert!(
+        session_todos::upsert_todos(
+            &db,
+            "session-tdm".to_string(),
+            vec![todo("a", "A", "pending")]
+        )
+        .await
+        .is_err()
+    );
+
+    session_todos::record_source(
+        &db,
+        "session-tdm".to_string(),
+        TodoSource {
+            message_id: "msg-tdm-001".to_string(),
+            tool_call_id: None,
+            timestamp: "2026-02-15T10:00:00Z".to_string(),
+            line_number: 1,
+        },
+    )
+    .await
+    .unwrap();
+    let count = session_todos::upsert_todos(
+        &db,
+        "session-tdm".to_string(),
+        vec![
+            todo("a", "Task A", "pending"),
+            todo("b", "Task B", "pending"),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(count, 2);
+
+    // Status-only update keeps the content; unknown ids without content are dropped
+    let count = session_todos::upsert_todos(
+        &db,
+        "session-tdm".to_string(),
+        vec![todo("b", "", "completed"), todo("zz", "", "completed")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    // Recording a new source keeps the list
+    session_todos::record_source(
+        &db,
+        "session-tdm".to_string(),
+        TodoSource {
+            message_id: "msg-tdm-002".to_string(),
+            tool_call_id: None,
+            timestamp: "2026-02-15T10:05:00Z".to_string(),
+            line_number: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    let stored = session_todos::get(&db, "session-tdm")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.message_id, "msg-tdm-002");
+    assert_eq!(
+        session_todos::parse_todos(&stored.todos_json),
+        vec![
+            todo("a", "Task A", "pending"),
+            todo("b", "Task B", "completed"),
+        ]
+    );
+}
+
 // ============================================================================
 // Generated Summaries CRUD Tests
 // ============================================================================
@@ -1745,6 +4735,305 @@ async fn test_file_validations_crud() {
     assert_eq!(after_delete.len(), 0);
 }
 
+// ============================================================================
+// Agent Sessions CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_agent_sessions_crud() {
+    let db = setup_db().await;
+    use han_db::crud::{agent_sessions, sessions};
+
+    for id in ["session-parent", "a1b2c3d4", "e5f6a7b8"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    // Link from the agent file side first (no tool call ID known yet)
+    let link = agent_sessions::link(
+        &db,
+        "session-parent".to_string(),
+        "a1b2c3d4".to_string(),
+        None,
+        "2026-04-10T10:00:00Z".to_string(),
+    )
+    .await
+    .expect("Failed to link agent session");
+    assert_eq!(link.parent_session_id, "session-parent");
+    assert!(link.task_tool_call_id.is_none());
+
+    // Parent's Task result fills in the tool call ID
+    let link = agent_sessions::link(
+        &db,
+        "session-parent".to_string(),
+        "a1b2c3d4".to_string(),
+        Some("toolu_001".to_string()),
+        "2026-04-10T10:00:00Z".to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(link.task_tool_call_id.as_deref(), Some("toolu_001"));
+
+    // A later link without a tool call ID must not erase it
+    let link = agent_sessions::link(
+        &db,
+        "session-parent".to_string(),
+        "a1b2c3d4".to_string(),
+        None,
+        "2026-04-10T10:00:00Z".to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(link.task_tool_call_id.as_deref(), Some("toolu_001"));
+
+    agent_sessions::link(
+        &db,
+        "session-parent".to_string(),
+        "e5f6a7b8".to_string(),
+        Some("toolu_002".to_string()),
+        "2026-04-10T10:05:00Z".to_string(),
+    )
+    .await
+    .unwrap();
+
+    // Parent → children
+    let children = agent_sessions::list_children(&db, "session-parent")
+        .await
+        .expect("Failed to list children");
+    let child_ids: Vec<_> = children.iter().map(|c| c.child_session_id.as_str()).collect();
+    assert_eq!(child_ids, vec!["a1b2c3d4", "e5f6a7b8"]);
+
+    // Child → parent
+    let parent = agent_sessions::get_parent(&db, "e5f6a7b8")
+        .await
+        .expect("Failed to get parent")
+        .expect("Parent link should exist");
+    assert_eq!(parent.parent_session_id, "session-parent");
+
+    assert!(agent_sessions::get_parent(&db, "session-parent")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(agent_sessions::list_children(&db, "a1b2c3d4")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_find_parent_by_agent_id() {
+    let db = setup_db().await;
+    use han_db::crud::{agent_sessions, messages, sessions};
+    use han_db::entities::messages as msg_entity;
+    use sea_orm::Set;
+
+    for id in ["session-parent", "session-other", "a1b2_3d4"] {
+        sessions::upsert(&db, id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    let make = |id: &str, session_id: &str, raw_json: &str, at: &str| msg_entity::ActiveModel {
+        id: Set(id.to_string()),
+        session_id: Set(session_id.to_string()),
+        agent_id: Set(None),
+        parent_id: Set(None),
+        message_type: Set("user".to_string()),
+        role: Set(None),
+        content: Set(None),
+        tool_name: Set(None),
+        tool_input: Set(None),
+        tool_result: Set(None),
+        raw_json: Set(Some(raw_json.to_string())),
+        timestamp: Set(at.to_string()),
+        line_number: Set(1),
+        byte_offset: Set(None),
+        source_file_name: Set(None),
+        source_file_type: Set(None),
+        sentiment_score: Set(None),
+        sentiment_level: Set(None),
+        frustration_score: Set(None),
+        frustration_level: Set(None),
+        input_tokens: Set(None),
+        output_tokens: Set(None),
+        cache_read_tokens: Set(None),
+        cache_creation_tokens: Set(None),
+        lines_added: Set(None),
+        lines_removed: Set(None),
+        files_changed: Set(None),
+        human_time_ms: Set(None),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
+        indexed_at: Set(None),
+    };
+    messages::insert_batch(
+        &db,
+        vec![
+            // `_` would match any character in a LIKE pattern
+            make(
+                "other-1",
+                "session-other",
+                r#"{"toolUseResult":{"agentId":"a1b2x3d4"}}"#,
+                "2026-04-10T09:00:00Z",
+            ),
+            // Mentions the ID outside the Task result
+            make(
+                "other-2",
+                "session-other",
+                r#"{"message":{"content":"agent a1b2_3d4 finished"}}"#,
+                "2026-04-10T09:30:00Z",
+            ),
+            // Spaced JSON as written by other serializers
+            make(
+                "parent-1",
+                "session-parent",
+                r#"{"toolUseResult": {"agentId": "a1b2_3d4"}}"#,
+                "2026-04-10T10:00:00Z",
+            ),
+            // The agent's own transcript never counts as its parent
+            make(
+                "agent-1",
+                "a1b2_3d4",
+                r#"{"toolUseResult":{"agentId":"a1b2_3d4"}}"#,
+                "2026-04-10T08:00:00Z",
+            ),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let parent = agent_sessions::find_parent_by_agent_id(&db, "a1b2_3d4")
+        .await
+        .unwrap();
+    assert_eq!(parent.as_deref(), Some("session-parent"));
+    assert!(agent_sessions::find_parent_by_agent_id(&db, "a1b2")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+// ============================================================================
+// Subscriptions CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_subscription_upsert_is_idempotent() {
+    let db = setup_db().await;
+    use han_db::crud::subscriptions::{
+        StripeSubscriptionData, get_subscription, upsert_subscription,
+    };
+    use han_db::entities::{subscriptions, teams, users};
+    use sea_orm::{EntityTrait, Set};
+
+    let now = "2026-04-25T00:00:00Z".to_string();
+    users::Entity::insert(users::ActiveModel {
+        id: Set("u1".to_string()),
+        role: Set("admin".to_string()),
+        created_at: Set(now.clone()),
+        updated_at: Set(now.clone()),
+        ..Default::default()
+    })
+    .exec(&db)
+    .await
+    .unwrap();
+    teams::Entity::insert(teams::ActiveModel {
+        id: Set("org-1".to_string()),
+        name: Set("Org".to_string()),
+        slug: Set("org".to_string()),
+        owner_id: Set("u1".to_string()),
+        created_at: Set(now.clone()),
+        updated_at: Set(now),
+    })
+    .exec(&db)
+    .await
+    .unwrap();
+
+    assert!(get_subscription(&db, "org-1").await.unwrap().is_none());
+
+    let data = StripeSubscriptionData {
+        stripe_subscription_id: "sub_123".to_string(),
+        plan: "pro".to_string(),
+        status: "active".to_string(),
+        seats: 5,
+        current_period_end: Some("2026-05-25T00:00:00+00:00".to_string()),
+        cancel_at: None,
+    };
+    let first = upsert_subscription(&db, "org-1", data.clone())
+        .await
+        .unwrap();
+    // A replayed event leaves a single, unchanged row
+    let replayed = upsert_subscription(&db, "org-1", data.clone())
+        .await
+        .unwrap();
+    assert_eq!(replayed.id, first.id);
+    assert_eq!(replayed.created_at, first.created_at);
+    assert_eq!(
+        subscriptions::Entity::find().all(&db).await.unwrap().len(),
+        1
+    );
+
+    // A later event for the same subscription updates it in place
+    let canceled = StripeSubscriptionData {
+        status: "canceled".to_string(),
+        seats: 3,
+        cancel_at: Some("2026-05-25T00:00:00+00:00".to_string()),
+        ..data
+    };
+    upsert_subscription(&db, "org-1", canceled).await.unwrap();
+    let sub = get_subscription(&db, "org-1").await.unwrap().unwrap();
+    assert_eq!(sub.id, first.id);
+    assert_eq!(sub.status, "canceled");
+    assert_eq!(sub.seats, 3);
+    assert_eq!(sub.plan, "pro");
+    assert!(sub.cancel_at.is_some());
+    assert_eq!(
+        subscriptions::Entity::find().all(&db).await.unwrap().len(),
+        1
+    );
+    assert!(get_subscription(&db, "org-2").await.unwrap().is_none());
+}
+
+// ============================================================================
+// Memories CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_memory_find_or_create_is_unique() {
+    let db = setup_db().await;
+    use han_db::crud::memories::find_or_create;
+    use han_db::entities::memories;
+    use sea_orm::{EntityTrait, Set};
+
+    let learn = |scope: Option<&str>| {
+        find_or_create(
+            &db,
+            scope.map(str::to_string),
+            "Prefer rg over grep".to_string(),
+            "2026-04-29T10:00:00Z".to_string(),
+        )
+    };
+    // Concurrent indexers resolve the same event to one row
+    let (a, b, c) = tokio::join!(learn(None), learn(None), learn(Some("project")));
+    let (a, b, c) = (a.unwrap(), b.unwrap(), c.unwrap());
+    assert_eq!(a.id, b.id);
+    assert_ne!(a.id, c.id);
+    assert_eq!(learn(Some("project")).await.unwrap().id, c.id);
+    assert_eq!(memories::Entity::find().all(&db).await.unwrap().len(), 2);
+
+    // The index rejects duplicates, including ones without a scope
+    let duplicate = memories::Entity::insert(memories::ActiveModel {
+        id: Set("dup".to_string()),
+        scope: Set(None),
+        content: Set(a.content.clone()),
+        created_at: Set(a.created_at.clone()),
+    })
+    .exec(&db)
+    .await;
+    assert!(duplicate.is_err());
+}
+
 // ============================================================================
 // Integration Test Against Real Database (Read-Only)
 // ============================================================================
@@ -0,0 +1,83 @@
+//! Compare `TaskTimeline` lookups against a plain linear scan.
+//!
+//! Run with `cargo bench -p han-indexer --bench task_timeline`.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use han_indexer::{TaskTimeRange, TaskTimeline};
+
+const SIZES: [usize; 3] = [10, 100, 1000];
+
+/// Ten-minute tasks started every ten minutes (`overlapping = false`) or
+/// every two minutes, so about five run at once (`overlapping = true`).
+fn ranges(n: usize, overlapping: bool) -> Vec<TaskTimeRange> {
+    let base = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let step = if overlapping { 2 } else { 10 };
+    (0..n)
+        .map(|i| {
+            let start_time = base + Duration::minutes((i * step) as i64);
+            TaskTimeRange {
+                task_id: format!("task-{i}"),
+                start_time,
+                end_time: Some(start_time + Duration::minutes(9)),
+            }
+        })
+        .collect()
+}
+
+/// Timestamps spread over the whole timeline, including gaps.
+fn queries(ranges: &[TaskTimeRange]) -> Vec<DateTime<Utc>> {
+    let first = ranges[0].start_time;
+    let last = ranges[ranges.len() - 1].end_time.unwrap();
+    let span = (last - first).num_seconds();
+    (0..64)
+        .map(|i| first + Duration::seconds(span * i / 63))
+        .collect()
+}
+
+/// The pre-binary-search lookup: newest matching task by reverse scan.
+fn linear_find<'a>(ranges: &'a [TaskTimeRange], ts: &DateTime<Utc>) -> Option<&'a str> {
+    ranges
+        .iter()
+        .rev()
+        .find(|t| t.start_time <= *ts && t.end_time.is_none_or(|end| end >= *ts))
+        .map(|t| t.task_id.as_str())
+}
+
+fn bench_find_active_task(c: &mut Criterion) {
+    for overlapping in [false, true] {
+        let name = if overlapping {
+            "find_active_task/overlapping"
+        } else {
+            "find_active_task/disjoint"
+        };
+        let mut group = c.benchmark_group(name);
+        for n in SIZES {
+            let ranges = ranges(n, overlapping);
+            let queries = queries(&ranges);
+            let mut timeline = TaskTimeline::new();
+            for range in ranges.iter().cloned() {
+                timeline.push(range);
+            }
+
+            group.bench_with_input(BenchmarkId::new("linear", n), &queries, |b, queries| {
+                b.iter(|| {
+                    for ts in queries {
+                        black_box(linear_find(&ranges, ts));
+                    }
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("binary", n), &queries, |b, queries| {
+                b.iter(|| {
+                    for ts in queries {
+                        black_box(timeline.find_active_task(ts));
+                    }
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_find_active_task);
+criterion_main!(benches);
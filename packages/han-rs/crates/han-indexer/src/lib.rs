@@ -21,12 +21,32 @@ pub mod types;
 pub mod watcher;
 
 // Re-export primary public API
-pub use parser::{jsonl_count_lines, jsonl_read_page, jsonl_read_reverse, JsonlLine, PaginatedResult};
+pub use parser::{
+    jsonl_count_lines, jsonl_read_page, jsonl_read_page_reversed, jsonl_read_reverse, JsonlLine,
+    PaginatedResult,
+};
 pub use processor::{
-    check_indexer_version, full_scan_and_index, handle_file_event, index_project_directory,
-    index_session_file, INDEXER_VERSION,
+    check_indexer_version, full_scan_and_index, full_scan_and_index_with_progress,
+    handle_file_event, index_all_sessions_parallel, index_project_directory, index_session_file,
+    index_session_file_from_line, reindex_session_file, sync_installed_plugins,
+    FULL_SCAN_CONCURRENCY, INDEXER_VERSION,
 };
 pub use sentiment::{analyze_sentiment, FrustrationLevel, SentimentLevel, SentimentResult};
 pub use task_timeline::{TaskTimeRange, TaskTimeline};
-pub use types::{FileEventType, IndexResult, MessageType, SessionFileType};
-pub use watcher::{FileEvent, WatcherService};
+pub use types::{
+    FileEventType, IndexProgress, IndexResult, IndexStatus, LineWarning, MessageType,
+    ReindexResult, SessionFileType, WarningKind,
+};
+pub use watcher::{FileEvent, IgnorePatterns, WatcherHandle, WatcherService};
+
+/// In-memory SQLite database with all migrations applied.
+#[cfg(test)]
+pub(crate) async fn test_db() -> sea_orm::DatabaseConnection {
+    let db = han_db::establish_connection(han_db::DbConfig::Sqlite {
+        path: ":memory:".to_string(),
+    })
+    .await
+    .unwrap();
+    han_db::migration::run_migrations(&db).await.unwrap();
+    db
+}
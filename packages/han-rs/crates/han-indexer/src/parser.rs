@@ -14,10 +14,14 @@ pub enum ParserError {
     EmptyFile(String),
     #[error("Offset {offset} exceeds total lines {total}")]
     OffsetOutOfBounds { offset: u32, total: u32 },
+    #[error("UTF-16 encoded files are not supported; convert to UTF-8")]
+    Utf16Encoding,
 }
 
 pub type ParserResult<T> = Result<T, ParserError>;
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 /// A single line from a JSONL file.
 #[derive(Debug, Clone)]
 pub struct JsonlLine {
@@ -50,10 +54,14 @@ pub fn jsonl_count_lines(file_path: &Path) -> ParserResult<u32> {
 
     // SAFETY: We only read the file and don't modify it.
     let mmap = unsafe { Mmap::map(&file)? };
-    let newline_count = count(&mmap, b'\n') as u32;
+    let data = &mmap[bom_len(&mmap)?..];
+    if data.is_empty() {
+        return Ok(0);
+    }
+    let newline_count = count(data, b'\n') as u32;
 
     // If the file doesn't end with newline, there's one more line
-    let has_trailing = mmap.last() == Some(&b'\n');
+    let has_trailing = data.last() == Some(&b'\n');
     if has_trailing {
         Ok(newline_count)
     } else {
@@ -83,7 +91,8 @@ pub fn jsonl_read_page(
     }
 
     let mmap = unsafe { Mmap::map(&file)? };
-    let total_lines = count_lines_in_mmap(&mmap);
+    let start = bom_len(&mmap)?;
+    let total_lines = count_lines_in_mmap(&mmap[start..]);
 
     if offset >= total_lines {
         return Ok(PaginatedResult {
@@ -94,14 +103,16 @@ pub fn jsonl_read_page(
         });
     }
 
+    // Callers read to EOF with `limit = u32::MAX`
+    let end_line = offset.saturating_add(limit);
     let mut lines = Vec::new();
     let mut current_line: u32 = 0;
-    let mut byte_offset: i64 = 0;
-    let mut line_start: usize = 0;
+    let mut byte_offset = start as i64;
+    let mut line_start = start;
 
     for (i, &byte) in mmap.iter().enumerate() {
         if byte == b'\n' {
-            if current_line >= offset && current_line < offset + limit {
+            if current_line >= offset && current_line < end_line {
                 let content = String::from_utf8_lossy(&mmap[line_start..i]).to_string();
                 if !content.trim().is_empty() {
                     lines.push(JsonlLine {
@@ -115,14 +126,14 @@ pub fn jsonl_read_page(
             byte_offset = (i + 1) as i64;
             line_start = i + 1;
 
-            if current_line >= offset + limit {
+            if current_line >= end_line {
                 break;
             }
         }
     }
 
     // Handle last line without trailing newline
-    if line_start < mmap.len() && current_line >= offset && current_line < offset + limit {
+    if line_start < mmap.len() && current_line >= offset && current_line < end_line {
         let content = String::from_utf8_lossy(&mmap[line_start..]).to_string();
         if !content.trim().is_empty() {
             lines.push(JsonlLine {
@@ -134,7 +145,7 @@ pub fn jsonl_read_page(
         let _ = current_line; // suppress unused assignment warning
     }
 
-    let next_offset = offset + limit;
+    let next_offset = end_line;
     let has_more = next_offset < total_lines;
 
     Ok(PaginatedResult {
@@ -162,7 +173,7 @@ pub fn jsonl_read_reverse(file_path: &Path, limit: u32) -> ParserResult<Vec<Json
     // Collect all line start positions
     let mut line_starts: Vec<(u32, usize)> = Vec::new();
     let mut line_num: u32 = 0;
-    line_starts.push((0, 0));
+    line_starts.push((0, bom_len(&mmap)?));
 
     for (i, &byte) in mmap.iter().enumerate() {
         if byte == b'\n' && i + 1 < mmap.len() {
@@ -205,8 +216,99 @@ pub fn jsonl_read_reverse(file_path: &Path, limit: u32) -> ParserResult<Vec<Json
     Ok(lines)
 }
 
+/// Read a page of lines from the end of a JSONL file, newest first.
+///
+/// `offset` counts lines skipped from the bottom of the file, so paging with
+/// `next_offset` walks back toward the start. The file is scanned backward
+/// from the end and stops once the page is full.
+pub fn jsonl_read_page_reversed(
+    file_path: &Path,
+    offset: u32,
+    limit: u32,
+) -> ParserResult<PaginatedResult> {
+    use memmap2::Mmap;
+    use std::fs::File;
+
+    let file = File::open(file_path)?;
+    let metadata = file.metadata()?;
+
+    if metadata.len() == 0 {
+        return Ok(PaginatedResult {
+            lines: vec![],
+            total_lines: 0,
+            has_more: false,
+            next_offset: 0,
+        });
+    }
+
+    let mmap = unsafe { Mmap::map(&file)? };
+    let start = bom_len(&mmap)?;
+    let total_lines = count_lines_in_mmap(&mmap[start..]);
+
+    let mut lines = Vec::new();
+    let mut skipped: u32 = 0;
+    let mut taken: u32 = 0;
+    let mut line_number = total_lines;
+    // Exclusive end of the current line, before its newline
+    let mut end = mmap.len();
+    if end > start && mmap[end - 1] == b'\n' {
+        end -= 1;
+    }
+
+    while line_number > 0 && taken < limit {
+        line_number -= 1;
+        let line_start = match mmap[start..end].iter().rposition(|&b| b == b'\n') {
+            Some(pos) => start + pos + 1,
+            None => start,
+        };
+
+        if skipped < offset {
+            skipped += 1;
+        } else {
+            taken += 1;
+            let content = String::from_utf8_lossy(&mmap[line_start..end]).to_string();
+            if !content.trim().is_empty() {
+                lines.push(JsonlLine {
+                    line_number,
+                    byte_offset: line_start as i64,
+                    content,
+                });
+            }
+        }
+
+        end = line_start.saturating_sub(1);
+    }
+
+    let next_offset = offset + limit;
+    let has_more = next_offset < total_lines;
+
+    Ok(PaginatedResult {
+        lines,
+        total_lines,
+        has_more,
+        next_offset,
+    })
+}
+
+/// Length of a leading UTF-8 byte order mark, which is skipped when reading.
+///
+/// UTF-16 files are rejected outright: every other byte would be NUL.
+fn bom_len(data: &[u8]) -> ParserResult<usize> {
+    if data.starts_with(b"\xFF\xFE") || data.starts_with(b"\xFE\xFF") {
+        return Err(ParserError::Utf16Encoding);
+    }
+    Ok(if data.starts_with(UTF8_BOM) {
+        UTF8_BOM.len()
+    } else {
+        0
+    })
+}
+
 /// Count lines in an mmap using SIMD-accelerated byte counting.
 fn count_lines_in_mmap(mmap: &[u8]) -> u32 {
+    if mmap.is_empty() {
+        return 0;
+    }
     let newline_count = bytecount::count(mmap, b'\n') as u32;
     let has_trailing = mmap.last() == Some(&b'\n');
     if has_trailing {
@@ -231,6 +333,13 @@ mod tests {
         f
     }
 
+    fn write_temp_bytes(bytes: &[u8]) -> NamedTempFile {
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(bytes).unwrap();
+        f.flush().unwrap();
+        f
+    }
+
     #[test]
     fn test_count_lines_empty_file() {
         let f = NamedTempFile::new().unwrap();
@@ -286,6 +395,16 @@ mod tests {
         assert!(!result.has_more);
     }
 
+    #[test]
+    fn test_read_page_offset_to_eof() {
+        let f = write_temp_jsonl(&[r#"{"line":0}"#, r#"{"line":1}"#, r#"{"line":2}"#]);
+
+        let result = jsonl_read_page(f.path(), 1, u32::MAX).unwrap();
+        assert_eq!(result.lines.len(), 2);
+        assert_eq!(result.lines[0].line_number, 1);
+        assert!(!result.has_more);
+    }
+
     #[test]
     fn test_read_page_empty() {
         let f = NamedTempFile::new().unwrap();
@@ -315,6 +434,73 @@ mod tests {
         assert!(lines.is_empty());
     }
 
+    #[test]
+    fn test_read_page_reversed() {
+        let f = write_temp_jsonl(&[
+            r#"{"line":0}"#,
+            r#"{"line":1}"#,
+            r#"{"line":2}"#,
+            r#"{"line":3}"#,
+            r#"{"line":4}"#,
+        ]);
+
+        let result = jsonl_read_page_reversed(f.path(), 1, 2).unwrap();
+        assert_eq!(result.total_lines, 5);
+        assert!(result.has_more);
+        assert_eq!(result.next_offset, 3);
+        let numbers: Vec<u32> = result.lines.iter().map(|l| l.line_number).collect();
+        assert_eq!(numbers, vec![3, 2]);
+        assert_eq!(result.lines[0].content, r#"{"line":3}"#);
+        assert_eq!(result.lines[0].byte_offset, 33);
+
+        let last = jsonl_read_page_reversed(f.path(), 3, 10).unwrap();
+        assert!(!last.has_more);
+        assert_eq!(last.lines.len(), 2);
+        assert_eq!(last.lines[1].line_number, 0);
+
+        let past_end = jsonl_read_page_reversed(f.path(), 5, 10).unwrap();
+        assert!(past_end.lines.is_empty());
+    }
+
+    #[test]
+    fn test_forward_and_reversed_pages_cover_every_line_once() {
+        let contents: Vec<String> = (0..23).map(|i| format!(r#"{{"line":{i}}}"#)).collect();
+        let refs: Vec<&str> = contents.iter().map(String::as_str).collect();
+        // No trailing newline, so the last line ends at EOF
+        let f = write_temp_bytes(refs.join("\n").as_bytes());
+
+        let mut forward = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = jsonl_read_page(f.path(), offset, 5).unwrap();
+            forward.extend(page.lines);
+            if !page.has_more {
+                break;
+            }
+            offset = page.next_offset;
+        }
+
+        let mut reversed = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = jsonl_read_page_reversed(f.path(), offset, 4).unwrap();
+            reversed.extend(page.lines);
+            if !page.has_more {
+                break;
+            }
+            offset = page.next_offset;
+        }
+
+        assert_eq!(forward.len(), 23);
+        reversed.reverse();
+        assert_eq!(reversed.len(), forward.len());
+        for (fwd, rev) in forward.iter().zip(&reversed) {
+            assert_eq!(fwd.line_number, rev.line_number);
+            assert_eq!(fwd.byte_offset, rev.byte_offset);
+            assert_eq!(fwd.content, rev.content);
+        }
+    }
+
     #[test]
     fn test_read_page_skips_empty_lines() {
         let mut f = NamedTempFile::new().unwrap();
@@ -327,4 +513,56 @@ mod tests {
         // Empty lines are skipped in output
         assert_eq!(result.lines.len(), 2);
     }
+
+    #[test]
+    fn test_utf8_bom_is_stripped() {
+        let f = write_temp_bytes(b"\xEF\xBB\xBF{\"line\":0}\n{\"line\":1}\n");
+        assert_eq!(jsonl_count_lines(f.path()).unwrap(), 2);
+
+        let result = jsonl_read_page(f.path(), 0, 10).unwrap();
+        assert_eq!(result.total_lines, 2);
+        let first: serde_json::Value = serde_json::from_str(&result.lines[0].content).unwrap();
+        assert_eq!(first["line"], 0);
+        // Offsets stay relative to the file so they can be seeked to
+        assert_eq!(result.lines[0].byte_offset, 3);
+        assert_eq!(result.lines[1].byte_offset, 14);
+
+        let reversed = jsonl_read_reverse(f.path(), 10).unwrap();
+        assert_eq!(reversed[1].content, r#"{"line":0}"#);
+
+        let page = jsonl_read_page_reversed(f.path(), 0, 10).unwrap();
+        assert_eq!(page.lines[1].content, r#"{"line":0}"#);
+        assert_eq!(page.lines[1].byte_offset, 3);
+    }
+
+    #[test]
+    fn test_bom_less_file_unaffected() {
+        let f = write_temp_jsonl(&[r#"{"line":0}"#]);
+        let result = jsonl_read_page(f.path(), 0, 10).unwrap();
+        assert_eq!(result.lines[0].byte_offset, 0);
+        assert_eq!(result.lines[0].content, r#"{"line":0}"#);
+    }
+
+    #[test]
+    fn test_bom_only_file_is_empty() {
+        let f = write_temp_bytes(UTF8_BOM);
+        assert_eq!(jsonl_count_lines(f.path()).unwrap(), 0);
+        assert_eq!(jsonl_read_page(f.path(), 0, 10).unwrap().total_lines, 0);
+        assert!(jsonl_read_reverse(f.path(), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_utf16_bom_rejected() {
+        for bom in [&b"\xFF\xFE"[..], &b"\xFE\xFF"[..]] {
+            let f = write_temp_bytes(bom);
+            let err = jsonl_read_page(f.path(), 0, 10).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "UTF-16 encoded files are not supported; convert to UTF-8"
+            );
+            assert!(jsonl_count_lines(f.path()).is_err());
+            assert!(jsonl_read_reverse(f.path(), 10).is_err());
+            assert!(jsonl_read_page_reversed(f.path(), 0, 10).is_err());
+        }
+    }
 }
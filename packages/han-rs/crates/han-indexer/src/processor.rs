@@ -13,13 +13,16 @@
 //! - Han events from `-han.jsonl` files
 //! - Task events (task_start/task_complete/task_fail)
 //! - File validation cache events
+//! - Linked git worktrees the session ran in
 
 use crate::parser::{jsonl_read_page, JsonlLine};
 use crate::sentiment;
 #[allow(unused_imports)]
 use crate::task_timeline::{build_task_timeline, TaskTimeline};
+use crate::types::session_file_stem;
 use crate::types::{
-    FileEventType, IndexResult, IntermediateParsedLine, MessageType, ParsedHanEvent, ParsedMessage,
+    FileEventType, IndexProgress, IndexResult, IndexStatus, IntermediateParsedLine, LineWarning,
+    MessageType, ParsedHanEvent, ParsedMessage, ReindexResult, SessionFileType, WarningKind,
 };
 #[allow(unused_imports)]
 use chrono::{DateTime, Duration, Utc};
@@ -27,9 +30,12 @@ use han_db::crud;
 use han_db::entities::messages;
 use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Set, Statement};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
 #[derive(Error, Debug)]
@@ -124,10 +130,28 @@ fn extract_timestamp(
     }
 }
 
-/// Parse a single JSONL line into an intermediate representation.
-fn parse_jsonl_line_intermediate(line: &JsonlLine) -> Option<IntermediateParsedLine> {
-    let json: Value = serde_json::from_str(&line.content).ok()?;
+/// Outcome of parsing a single JSONL line in the first indexing pass.
+enum LineParse {
+    Parsed(IntermediateParsedLine),
+    /// Valid JSON without the fields every message has (e.g. no `type`).
+    Unrecognized,
+    /// Not valid JSON.
+    Malformed(String),
+}
+
+/// Parse a single JSONL line, distinguishing bad JSON from unexpected structure.
+fn parse_jsonl_line(line: &JsonlLine) -> LineParse {
+    match serde_json::from_str::<Value>(&line.content) {
+        Ok(json) => match intermediate_from_json(line, json) {
+            Some(parsed) => LineParse::Parsed(parsed),
+            None => LineParse::Unrecognized,
+        },
+        Err(e) => LineParse::Malformed(e.to_string()),
+    }
+}
 
+/// Build the intermediate representation of an already-parsed JSONL line.
+fn intermediate_from_json(line: &JsonlLine, json: Value) -> Option<IntermediateParsedLine> {
     let msg_type = json.get("type")?.as_str()?;
     let message_type = MessageType::from_str(msg_type);
 
@@ -149,6 +173,7 @@ fn parse_jsonl_line_intermediate(line: &JsonlLine) -> Option<IntermediateParsedL
 
     Some(IntermediateParsedLine {
         line_number: line.line_number as i32,
+        byte_offset: line.byte_offset,
         json,
         raw_content: line.content.clone(),
         message_type,
@@ -158,6 +183,85 @@ fn parse_jsonl_line_intermediate(line: &JsonlLine) -> Option<IntermediateParsedL
     })
 }
 
+/// Message types the indexer doesn't model but the API still displays.
+const API_ONLY_MESSAGE_TYPES: &[&str] = &["hook_run", "hook_result", "queue-operation"];
+
+/// Content size above which a message is flagged.
+const MAX_CONTENT_BYTES: usize = 1024 * 1024;
+
+/// Sanity checks for a line that parsed as a message.
+///
+/// Only conversation messages are expected to carry a UUID; summaries and
+/// snapshots key on `leafUuid`/`messageId` instead.
+fn line_warnings(parsed: &IntermediateParsedLine, now: DateTime<Utc>) -> Vec<LineWarning> {
+    let json = &parsed.json;
+    let mut warnings = Vec::new();
+    let mut warn = |kind, detail: String| warnings.push(LineWarning { kind, detail });
+
+    let msg_type = json
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or_default();
+    if parsed.message_type == MessageType::Unknown && !API_ONLY_MESSAGE_TYPES.contains(&msg_type) {
+        warn(
+            WarningKind::UnknownType,
+            format!("unknown message type {msg_type:?}"),
+        );
+    }
+
+    let expects_uuid = matches!(
+        parsed.message_type,
+        MessageType::User
+            | MessageType::Assistant
+            | MessageType::System
+            | MessageType::Progress
+            | MessageType::ToolUse
+            | MessageType::ToolResult
+    );
+    if expects_uuid {
+        match json.get("uuid").and_then(|v| v.as_str()) {
+            None => warn(WarningKind::MissingUuid, "message has no uuid".to_string()),
+            Some(id) if Uuid::parse_str(id).is_err() => warn(
+                WarningKind::InvalidUuid,
+                format!("uuid {id:?} is not a valid UUID"),
+            ),
+            Some(_) => {}
+        }
+    }
+
+    if let Some(ts) = parsed.direct_timestamp.as_deref() {
+        if let Ok(at) = DateTime::parse_from_rfc3339(ts) {
+            if at.with_timezone(&Utc) > now + Duration::hours(1) {
+                warn(
+                    WarningKind::FutureTimestamp,
+                    format!("timestamp {ts} is more than an hour in the future"),
+                );
+            }
+        }
+    }
+
+    // Content can't exceed the line, so only measure it on long lines
+    if parsed.raw_content.len() > MAX_CONTENT_BYTES {
+        let content = json
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .or_else(|| json.get("content"));
+        let size = match content {
+            Some(Value::String(s)) => s.len(),
+            Some(v) => v.to_string().len(),
+            None => 0,
+        };
+        if size > MAX_CONTENT_BYTES {
+            warn(
+                WarningKind::OversizedContent,
+                format!("content is {size} bytes"),
+            );
+        }
+    }
+
+    warnings
+}
+
 /// Convert intermediate parsed line to final ParsedMessage.
 fn finalize_parsed_message(
     parsed: IntermediateParsedLine,
@@ -497,7 +601,7 @@ fn extract_line_changes(json: &Value) -> (Option<i32>, Option<i32>, Option<i32>)
 
 /// Indexer version — bump this to trigger automatic re-indexing of all sessions.
 /// The coordinator checks this against `han_metadata.indexer_version` at startup.
-pub const INDEXER_VERSION: &str = "2";
+pub const INDEXER_VERSION: &str = "7";
 
 /// Estimate human-equivalent time in milliseconds for a single message.
 ///
@@ -566,57 +670,39 @@ pub enum ClassifiedFile {
     Unknown,
 }
 
-fn is_valid_uuid(s: &str) -> bool {
-    s.len() >= 32 && s.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
-}
-
-fn is_valid_cli_session_id(s: &str) -> bool {
-    s.strip_prefix("cli-").map_or(false, is_valid_uuid)
-}
-
 pub fn classify_file(file_path: &Path) -> ClassifiedFile {
-    let filename = match file_path.file_stem().and_then(|s| s.to_str()) {
-        Some(f) => f,
-        None => return ClassifiedFile::Unknown,
+    let Some(stem) = session_file_stem(file_path) else {
+        return ClassifiedFile::Unknown;
     };
 
-    if let Some(agent_id) = filename.strip_prefix("agent-") {
-        if !agent_id.is_empty() && agent_id.len() <= 16 {
-            return ClassifiedFile::Agent {
-                agent_id: agent_id.to_string(),
-            };
-        }
-    }
-
-    if let Some(session_id) = filename.strip_suffix("-han") {
-        if is_valid_uuid(session_id) || is_valid_cli_session_id(session_id) {
-            return ClassifiedFile::HanEvents {
-                session_id: session_id.to_string(),
-            };
-        }
-    }
-
-    let session_id = filename.strip_suffix("_messages").unwrap_or(filename);
-    if is_valid_uuid(session_id) {
-        return ClassifiedFile::Main {
-            session_id: session_id.to_string(),
-        };
+    match SessionFileType::detect_from_path(file_path) {
+        SessionFileType::Main | SessionFileType::AgentSession => ClassifiedFile::Main {
+            session_id: stem.strip_suffix("_messages").unwrap_or(stem).to_string(),
+        },
+        SessionFileType::Agent => ClassifiedFile::Agent {
+            agent_id: stem.strip_prefix("agent-").unwrap_or(stem).to_string(),
+        },
+        SessionFileType::HanEvents => ClassifiedFile::HanEvents {
+            session_id: stem.strip_suffix("-han").unwrap_or(stem).to_string(),
+        },
+        SessionFileType::Unknown => ClassifiedFile::Unknown,
     }
-
-    ClassifiedFile::Unknown
 }
 
 /// Extract session ID from file by classification.
+///
+/// Agent files are indexed as their own session keyed by agent ID; the link
+/// to the spawning session lives in `agent_sessions`.
 fn extract_session_id(file_path: &Path) -> Option<String> {
     match classify_file(file_path) {
         ClassifiedFile::Main { session_id } => Some(session_id),
         ClassifiedFile::HanEvents { session_id } => Some(session_id),
-        ClassifiedFile::Agent { .. } => extract_session_id_from_agent_file(file_path),
+        ClassifiedFile::Agent { agent_id } => Some(agent_id),
         ClassifiedFile::Unknown => None,
     }
 }
 
-/// Read first line of an agent file to extract the session ID.
+/// Read first line of an agent file to extract the parent session ID.
 fn extract_session_id_from_agent_file(file_path: &Path) -> Option<String> {
     use std::io::{BufRead, BufReader};
     let file = std::fs::File::open(file_path).ok()?;
@@ -780,6 +866,104 @@ fn decode_project_path(slug: &str) -> String {
     path
 }
 
+/// Path segments that hold linked worktrees inside a main checkout: git's
+/// own admin directory, and where Claude Code creates `--worktree` checkouts.
+const WORKTREE_DIRS: &[&str] = &["/.git/worktrees/", "/.claude/worktrees/"];
+
+/// A linked git worktree that a session's project directory is checked out as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    pub main_project_dir: String,
+    pub worktree_name: String,
+    pub branch_name: Option<String>,
+}
+
+/// Split a path inside `{main}/.git/worktrees/{name}` (or
+/// `{main}/.claude/worktrees/{name}`) into `(main, name)`.
+fn split_worktree_path(path: &str) -> Option<(String, String)> {
+    WORKTREE_DIRS.iter().find_map(|marker| {
+        let (main, rest) = path.split_once(marker)?;
+        let name = rest.split('/').next().filter(|n| !n.is_empty())?;
+        (!main.is_empty()).then(|| (main.to_string(), name.to_string()))
+    })
+}
+
+/// Detect whether `project_dir` is a linked git worktree.
+///
+/// A linked worktree's `.git` is a file reading `gitdir:
+/// {main}/.git/worktrees/{name}`. Directories that no longer exist are still
+/// recognised when the path itself lies under a worktrees directory. The
+/// branch comes from the worktree's `HEAD` and is `None` when detached.
+pub fn detect_worktree(project_dir: &str) -> Option<WorktreeInfo> {
+    let dir = Path::new(project_dir);
+    let gitdir = std::fs::read_to_string(dir.join(".git"))
+        .ok()
+        .and_then(|s| s.trim().strip_prefix("gitdir:").map(|p| dir.join(p.trim())));
+    let (main_project_dir, worktree_name) = gitdir
+        .as_deref()
+        .and_then(|g| split_worktree_path(&g.to_string_lossy()))
+        .or_else(|| split_worktree_path(project_dir))?;
+
+    let head = Path::new(&main_project_dir)
+        .join(".git/worktrees")
+        .join(&worktree_name)
+        .join("HEAD");
+    let branch_name = std::fs::read_to_string(head).ok().and_then(|h| {
+        h.trim()
+            .strip_prefix("ref: refs/heads/")
+            .map(|b| b.to_string())
+    });
+
+    Some(WorktreeInfo {
+        main_project_dir,
+        worktree_name,
+        branch_name,
+    })
+}
+
+/// Lines read from the start of a transcript looking for its `cwd`.
+const CWD_SCAN_LINES: usize = 20;
+
+/// Working directory recorded in the first transcript lines that carry one.
+///
+/// Unlike the directory decoded from the project slug, this keeps the `.`
+/// in paths such as `{main}/.claude/worktrees/{name}`.
+fn transcript_cwd(path: &Path) -> Option<String> {
+    use std::io::BufRead;
+
+    let file = std::fs::File::open(path).ok()?;
+    std::io::BufReader::new(file)
+        .lines()
+        .take(CWD_SCAN_LINES)
+        .map_while(Result::ok)
+        .find_map(|line| {
+            let json: Value = serde_json::from_str(&line).ok()?;
+            json.get("cwd")?.as_str().map(str::to_string)
+        })
+}
+
+/// Record the worktree `project_dir` is checked out as and link the session
+/// to it. Returns `None` when `project_dir` isn't a linked worktree.
+async fn link_worktree(
+    db: &DatabaseConnection,
+    session_id: &str,
+    project_dir: &str,
+) -> ProcessorResult<Option<han_db::entities::worktrees::Model>> {
+    let Some(info) = detect_worktree(project_dir) else {
+        return Ok(None);
+    };
+    let worktree = crud::worktrees::upsert(
+        db,
+        project_dir.to_string(),
+        info.main_project_dir,
+        info.branch_name,
+        info.worktree_name,
+    )
+    .await?;
+    crud::sessions::update_worktree(db, session_id, &worktree.id).await?;
+    Ok(Some(worktree))
+}
+
 // ============================================================================
 // Han Events
 // ============================================================================
@@ -820,6 +1004,7 @@ fn parse_han_event_line(line: &JsonlLine, ref_base_dir: Option<&Path>) -> Option
         agent_id,
         data,
         raw_json,
+        byte_offset: line.byte_offset,
     })
 }
 
@@ -888,12 +1073,93 @@ fn read_han_events(han_file: &Path, session_id: &str) -> Vec<ParsedHanEvent> {
     events
 }
 
+/// Round-trip time of each MCP and exposed tool call, keyed by the call
+/// event's id. A call is matched to the result of the same kind sharing its
+/// `call_id`; calls without a result, or whose result predates them, are
+/// skipped.
+fn tool_call_durations(events: &[ParsedHanEvent]) -> Vec<(String, i32)> {
+    fn call_id(event: &ParsedHanEvent) -> Option<&str> {
+        event.data.get("call_id").and_then(|v| v.as_str())
+    }
+
+    let results: HashMap<(&str, &str), &str> = events
+        .iter()
+        .filter_map(|e| {
+            let kind = e.event_type.strip_suffix("_tool_result")?;
+            Some(((kind, call_id(e)?), e.timestamp.as_str()))
+        })
+        .collect();
+
+    events
+        .iter()
+        .filter_map(|e| {
+            let kind = e
+                .event_type
+                .strip_suffix("_tool_call")
+                .filter(|k| matches!(*k, "mcp" | "exposed"))?;
+            let completed = results.get(&(kind, call_id(e)?))?;
+            let completed = DateTime::parse_from_rfc3339(completed).ok()?;
+            let started = DateTime::parse_from_rfc3339(&e.timestamp).ok()?;
+            let ms = (completed - started).num_milliseconds();
+            (ms >= 0).then(|| (e.id.clone(), ms.min(i32::MAX as i64) as i32))
+        })
+        .collect()
+}
+
+/// A memory recorded by a `memory_learn` event.
+struct LearnedMemory {
+    message_id: String,
+    scope: Option<String>,
+    content: String,
+    created_at: String,
+}
+
+/// Memories learned in `events`. Older events name the scope `domain`;
+/// events without content are skipped.
+fn learned_memories(events: &[ParsedHanEvent]) -> Vec<LearnedMemory> {
+    events
+        .iter()
+        .filter(|e| e.event_type == "memory_learn")
+        .filter_map(|e| {
+            let field = |name: &str| e.data.get(name).and_then(|v| v.as_str());
+            Some(LearnedMemory {
+                message_id: e.id.clone(),
+                scope: field("scope")
+                    .or_else(|| field("domain"))
+                    .map(|s| s.to_string()),
+                content: field("content")?.to_string(),
+                created_at: e.timestamp.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Record each learned memory and point its `memory_learn` message at it.
+async fn link_learned_memories(
+    db: &DatabaseConnection,
+    learned: Vec<LearnedMemory>,
+) -> ProcessorResult<()> {
+    let mut links = Vec::with_capacity(learned.len());
+    for memory in learned {
+        let row =
+            crud::memories::find_or_create(db, memory.scope, memory.content, memory.created_at)
+                .await?;
+        links.push((memory.message_id, row.id));
+    }
+    crud::messages::set_memory_ids(db, &links).await?;
+    Ok(())
+}
+
 // ============================================================================
 // Side-effect helpers
 // ============================================================================
 
-fn is_file_modification_tool(tool_name: &str) -> bool {
-    matches!(tool_name, "Write" | "Edit" | "NotebookEdit")
+/// Tools whose calls are recorded in `session_file_changes`.
+fn is_file_tool(tool_name: &str) -> bool {
+    matches!(
+        tool_name,
+        "Write" | "Edit" | "MultiEdit" | "NotebookEdit" | "Read"
+    )
 }
 
 fn detect_compact_type(json: &Value, content: Option<&str>) -> Option<String> {
@@ -934,7 +1200,7 @@ fn detect_compact_type(json: &Value, content: Option<&str>) -> Option<String> {
 fn extract_file_path_from_tool_input(tool_name: &str, tool_input: &str) -> Option<String> {
     let input: Value = serde_json::from_str(tool_input).ok()?;
     match tool_name {
-        "Write" | "Edit" => input
+        "Write" | "Edit" | "MultiEdit" | "Read" => input
             .get("file_path")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
@@ -946,9 +1212,48 @@ fn extract_file_path_from_tool_input(tool_name: &str, tool_input: &str) -> Optio
     }
 }
 
+/// A file tool call found in an assistant message.
+#[derive(Debug, Clone, PartialEq)]
+struct ToolFileAccess {
+    tool_call_id: String,
+    tool_name: String,
+    tool_input: String,
+}
+
+/// File tool calls among an assistant message's `tool_use` blocks, in order.
+fn extract_file_changes_from_tool_use(json: &Value) -> Vec<ToolFileAccess> {
+    let Some(content) = json
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+    else {
+        return Vec::new();
+    };
+    content
+        .iter()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+        .filter_map(|item| {
+            let tool_name = item.get("name").and_then(|n| n.as_str())?;
+            if !is_file_tool(tool_name) {
+                return None;
+            }
+            Some(ToolFileAccess {
+                tool_call_id: item.get("id").and_then(|v| v.as_str())?.to_string(),
+                tool_name: tool_name.to_string(),
+                tool_input: item.get("input")?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Record the file a tool call touched, keyed by the call's ID.
+///
+/// `Read` is recorded as a read. `Write` counts as created unless the session
+/// already touched the path, since indexing runs in transcript order.
 async fn record_file_change_from_tool(
     db: &DatabaseConnection,
     session_id: &str,
+    tool_call_id: &str,
     tool_name: &str,
     tool_input: &str,
     agent_id: Option<&str>,
@@ -960,23 +1265,34 @@ async fn record_file_change_from_tool(
             .unwrap_or(raw_path);
 
         let action = match tool_name {
-            "Write" => "created",
-            "Edit" | "NotebookEdit" => "modified",
+            "Read" => crud::file_changes::ACTION_READ,
+            "Write" => {
+                let seen = crud::file_changes::has_path(db, session_id, &file_path)
+                    .await
+                    .unwrap_or(false);
+                if seen {
+                    "modified"
+                } else {
+                    "created"
+                }
+            }
             _ => "modified",
         };
 
         // Compute SHA256 hash of file after change
         let file_hash_after = compute_file_hash(&file_path);
 
-        let _ = crud::file_changes::record(
+        let _ = crud::file_changes::upsert_file_change(
             db,
-            session_id.to_string(),
-            file_path,
-            action.to_string(),
-            None,
-            file_hash_after,
-            Some(tool_name.to_string()),
-            agent_id.map(|s| s.to_string()),
+            crud::file_changes::FileChangeInput {
+                id: tool_call_id.to_string(),
+                session_id: session_id.to_string(),
+                file_path,
+                action: action.to_string(),
+                file_hash_after,
+                tool_name: Some(tool_name.to_string()),
+                agent_id: agent_id.map(|s| s.to_string()),
+            },
         )
         .await;
     }
@@ -1003,6 +1319,7 @@ async fn extract_and_save_todos(
     db: &DatabaseConnection,
     session_id: &str,
     message_id: &str,
+    tool_call_id: Option<&str>,
     tool_input: &str,
     timestamp: &str,
     line_number: i32,
@@ -1011,23 +1328,51 @@ async fn extract_and_save_todos(
         Ok(v) => v,
         Err(_) => return,
     };
-    let todos = match input.get("todos") {
-        Some(t) if t.is_array() => t,
-        _ => return,
+    let todos = match parse_todo_write_input(&input) {
+        Some(t) => t,
+        None => return,
     };
-    let todos_json = match serde_json::to_string(todos) {
-        Ok(s) => s,
-        Err(_) => return,
+    let source = crud::session_todos::TodoSource {
+        message_id: message_id.to_string(),
+        tool_call_id: tool_call_id.map(|s| s.to_string()),
+        timestamp: timestamp.to_string(),
+        line_number,
     };
-    let _ = crud::session_todos::upsert(
-        db,
-        session_id.to_string(),
-        message_id.to_string(),
-        todos_json,
-        timestamp.to_string(),
+    let _ = crud::session_todos::replace_todos(db, session_id.to_string(), source, todos).await;
+}
+
+/// Merge a TaskCreate/TaskUpdate change into the session's todo list.
+async fn merge_task_todo(
+    db: &DatabaseConnection,
+    session_id: &str,
+    message_id: &str,
+    timestamp: &str,
+    line_number: i32,
+    todo: crud::session_todos::TodoInput,
+) {
+    let source = crud::session_todos::TodoSource {
+        message_id: message_id.to_string(),
+        tool_call_id: None,
+        timestamp: timestamp.to_string(),
         line_number,
+    };
+    if crud::session_todos::record_source(db, session_id.to_string(), source)
+        .await
+        .is_ok()
+    {
+        let _ = crud::session_todos::upsert_todos(db, session_id.to_string(), vec![todo]).await;
+    }
+}
+
+/// Parse the `todos` array from a TodoWrite tool input, skipping malformed items.
+fn parse_todo_write_input(input: &Value) -> Option<Vec<crud::session_todos::TodoInput>> {
+    let todos = input.get("todos")?.as_array()?;
+    Some(
+        todos
+            .iter()
+            .filter_map(|t| serde_json::from_value(t.clone()).ok())
+            .collect(),
     )
-    .await;
 }
 
 fn md5_hash(input: &str) -> u64 {
@@ -1082,6 +1427,75 @@ fn extract_tool_call_results(
     }
 }
 
+/// Extract the spawned agent from a Task tool result.
+///
+/// Returns `(agent_id, tool_call_id)` when the user message carries
+/// `toolUseResult.agentId` alongside a `tool_result` block.
+fn extract_spawned_agent(raw_json: &str) -> Option<(String, String)> {
+    let json: Value = serde_json::from_str(raw_json).ok()?;
+    let agent_id = json
+        .get("toolUseResult")
+        .and_then(|r| r.get("agentId"))
+        .and_then(|a| a.as_str())?;
+    let tool_call_id = json
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())?
+        .iter()
+        .find(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+        .and_then(|b| b.get("tool_use_id"))
+        .and_then(|v| v.as_str())?;
+    Some((agent_id.to_string(), tool_call_id.to_string()))
+}
+
+/// Extract the sub-agent session ID a progress line reports as
+/// `data.agent_task_id`.
+fn extract_agent_task_id(raw_json: &str) -> Option<String> {
+    // Cheap check before parsing every line a second time
+    if !raw_json.contains("\"agent_task_id\"") {
+        return None;
+    }
+    let json: Value = serde_json::from_str(raw_json).ok()?;
+    json.get("data")
+        .and_then(|d| d.get("agent_task_id"))
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+/// Link an agent session to its parent if it isn't linked yet.
+///
+/// The parent is taken from the `sessionId` on the agent file's first line,
+/// falling back to whichever session's Task result references the agent ID.
+async fn link_agent_session_to_parent(
+    db: &DatabaseConnection,
+    file_path: &Path,
+    agent_id: &str,
+    spawned_at: &str,
+) -> ProcessorResult<()> {
+    if crud::agent_sessions::get_parent(db, agent_id)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+    let parent_id = match extract_session_id_from_agent_file(file_path) {
+        Some(id) if id != agent_id => Some(id),
+        _ => crud::agent_sessions::find_parent_by_agent_id(db, agent_id).await?,
+    };
+    if let Some(parent_id) = parent_id {
+        crud::agent_sessions::link(
+            db,
+            parent_id,
+            agent_id.to_string(),
+            None,
+            spawned_at.to_string(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
 /// Extract text content from a tool_result block's content field.
 fn extract_tool_result_text(block: &Value) -> String {
     if let Some(content) = block.get("content") {
@@ -1135,6 +1549,15 @@ async fn extract_and_save_task_create(
     // Track sequential position → hash ID for TaskUpdate resolution
     task_create_ids.push(task_id.clone());
 
+    let todo = crud::session_todos::TodoInput {
+        id: Some(task_id.clone()),
+        content: subject.clone(),
+        status: "pending".to_string(),
+        active_form: active_form.clone(),
+        priority: None,
+    };
+    merge_task_todo(db, session_id, message_id, timestamp, line_number, todo).await;
+
     let _ = crud::native_tasks::create(
         db,
         task_id,
@@ -1217,6 +1640,15 @@ async fn extract_and_save_task_update(
         })
     });
 
+    let todo = crud::session_todos::TodoInput {
+        id: Some(task_id.clone()),
+        content: subject.clone().unwrap_or_default(),
+        status: status.clone().unwrap_or_default(),
+        active_form: active_form.clone(),
+        priority: None,
+    };
+    merge_task_todo(db, session_id, message_id, timestamp, line_number, todo).await;
+
     let _ = crud::native_tasks::update(
         db,
         &task_id,
@@ -1331,6 +1763,7 @@ fn to_active_model(
     raw_json: Option<String>,
     timestamp: String,
     line_number: i32,
+    byte_offset: Option<i64>,
     source_file_name: Option<String>,
     source_file_type: Option<String>,
     sentiment_score: Option<f64>,
@@ -1360,6 +1793,7 @@ fn to_active_model(
         raw_json: Set(raw_json),
         timestamp: Set(timestamp),
         line_number: Set(line_number),
+        byte_offset: Set(byte_offset),
         source_file_name: Set(source_file_name),
         source_file_type: Set(source_file_type),
         sentiment_score: Set(sentiment_score),
@@ -1374,6 +1808,9 @@ fn to_active_model(
         lines_removed: Set(lines_removed),
         files_changed: Set(files_changed),
         human_time_ms: Set(human_time_ms),
+        duration_ms: Set(None),
+        memory_id: Set(None),
+        plugin_name: Set(None),
         indexed_at: Set(Some(Utc::now().to_rfc3339())),
     }
 }
@@ -1383,24 +1820,49 @@ fn to_active_model(
 // ============================================================================
 
 /// Index a single JSONL file incrementally.
+///
+/// Starts after the last line of the previous pass, or after the checkpoint
+/// of a pass that was cut short.
 pub async fn index_session_file(
     db: &DatabaseConnection,
     file_path: &str,
     source_config_dir: Option<&str>,
 ) -> ProcessorResult<IndexResult> {
-    let path = Path::new(file_path);
+    index_file(db, Path::new(file_path), None, source_config_dir, None).await
+}
+
+/// Index a session's JSONL file starting at `from_line`, skipping the lines
+/// before it without reading them.
+pub async fn index_session_file_from_line(
+    db: &DatabaseConnection,
+    session_id: &str,
+    file_path: &Path,
+    from_line: u32,
+) -> ProcessorResult<IndexResult> {
+    index_file(db, file_path, Some(session_id), None, Some(from_line)).await
+}
+
+/// Shared body of [`index_session_file`] and
+/// [`index_session_file_from_line`]. The session ID is taken from the file
+/// name unless given.
+async fn index_file(
+    db: &DatabaseConnection,
+    path: &Path,
+    session_id: Option<&str>,
+    source_config_dir: Option<&str>,
+    from_line: Option<u32>,
+) -> ProcessorResult<IndexResult> {
+    let file_path = path.to_string_lossy();
     let source_file_name = path
         .file_name()
         .and_then(|n| n.to_str())
         .map(|s| s.to_string());
-    let source_file_type = match classify_file(path) {
-        ClassifiedFile::Main { .. } => Some("main".to_string()),
-        ClassifiedFile::Agent { .. } => Some("agent".to_string()),
-        ClassifiedFile::HanEvents { .. } => Some("han_events".to_string()),
-        ClassifiedFile::Unknown => None,
-    };
+    let file_type = SessionFileType::detect_from_path(path);
 
-    let session_id = match extract_session_id(path) {
+    let session_id = session_id
+        .map(str::to_string)
+        .or_else(|| extract_session_id(path));
+    let session_id = match session_id {
         Some(id) => id,
         None => {
             return Ok(IndexResult {
@@ -1409,13 +1871,48 @@ pub async fn index_session_file(
                 total_messages: 0,
                 is_new_session: false,
                 error: Some("Could not extract session ID from filename".to_string()),
+                errors: Vec::new(),
+                error_count: 0,
+                warning_count: 0,
+                warnings: Vec::new(),
+                frustrated_messages: Vec::new(),
             });
         }
     };
 
+    // Archived sessions stay out of the live tables until restored
+    if crud::is_archived(db, &session_id).await? {
+        return Ok(IndexResult {
+            session_id,
+            messages_indexed: 0,
+            total_messages: 0,
+            is_new_session: false,
+            error: None,
+            errors: Vec::new(),
+            error_count: 0,
+            warning_count: 0,
+            warnings: Vec::new(),
+            frustrated_messages: Vec::new(),
+        });
+    }
+
+    // A main transcript that another session launched via the Task tool
+    let file_type = match file_type {
+        SessionFileType::Main
+            if crud::agent_sessions::get_parent(db, &session_id)
+                .await?
+                .is_some() =>
+        {
+            SessionFileType::AgentSession
+        }
+        other => other,
+    };
+    let source_file_type = Some(file_type.as_str().to_string());
+
     // Get or create project
     let project_slug = extract_project_slug(path);
-    let project_id = if let Some(slug) = &project_slug {
+    let working_dir = transcript_cwd(path);
+    let project = if let Some(slug) = &project_slug {
         let decoded_path = decode_project_path(slug);
         // Derive human-readable name from the decoded filesystem path
         // e.g. "/Volumes/dev/src/github.com/thebushidocollective/han" → "han"
@@ -1427,6 +1924,8 @@ pub async fn index_session_file(
 
         // Detect git repo from project path
         let repo_id = detect_repo_for_path(db, &decoded_path).await;
+        let worktree_dir = working_dir.as_deref().unwrap_or(&decoded_path);
+        let is_worktree = detect_worktree(worktree_dir).is_some();
 
         let project = crud::projects::upsert(
             db,
@@ -1435,22 +1934,27 @@ pub async fn index_session_file(
             decoded_path,
             None,
             project_name,
-            Some(false),
+            Some(is_worktree),
             source_config_dir.map(|s| s.to_string()),
         )
         .await?;
-        Some(project.id)
+        Some(project)
     } else {
         None
     };
+    let project_dir = project.as_ref().map(|p| p.path.clone());
+    let project_id = project.map(|p| p.id);
 
     // Check existing session
     let existing_session = crud::sessions::get(db, &session_id).await?;
     let is_new_session = existing_session.is_none();
-    let last_line = existing_session
+    let indexed_line = existing_session
         .as_ref()
         .and_then(|s| s.last_indexed_line)
         .unwrap_or(0);
+    // A pass that was cut short left off at its checkpoint
+    let checkpoint = crud::indexing_checkpoints::get(db, &session_id).await?;
+    let last_line = indexed_line.max(checkpoint.unwrap_or(0));
 
     // Upsert session
     crud::sessions::upsert(
@@ -1463,12 +1967,15 @@ pub async fn index_session_file(
         source_config_dir.map(|s| s.to_string()),
     )
     .await?;
+    if let Some(dir) = working_dir.as_ref().or(project_dir.as_ref()) {
+        link_worktree(db, &session_id, dir).await?;
+    }
 
     // Read new lines from JSONL file
-    let start_line = if last_line > 0 {
-        (last_line + 1) as u32
-    } else {
-        0
+    let start_line = match from_line {
+        Some(line) => line,
+        None if last_line > 0 => (last_line + 1) as u32,
+        None => 0,
     };
 
     // Pass 1: Read all new lines in a single pass (file is mmapped, so this is efficient).
@@ -1478,45 +1985,91 @@ pub async fn index_session_file(
     let mut uuid_to_timestamp: HashMap<String, String> = HashMap::new();
     let mut max_line = last_line;
     let mut session_slug: Option<String> = None;
+    // Bad lines are recorded and skipped so one corrupt line doesn't cost the rest
+    let mut line_errors: Vec<(u32, String)> = Vec::new();
+    let mut warning_count = 0u32;
+    // Messages that index fine but look wrong are kept and flagged
+    let mut warnings: Vec<(u32, LineWarning)> = Vec::new();
+    let mut frustrated_messages: Vec<String> = Vec::new();
+    let now = Utc::now();
 
     let result = jsonl_read_page(path, start_line, u32::MAX)?;
     for line in &result.lines {
-        if let Some(parsed) = parse_jsonl_line_intermediate(line) {
-            if let Some(ref ts) = parsed.direct_timestamp {
-                uuid_to_timestamp.insert(parsed.uuid.clone(), ts.clone());
+        let parsed = match parse_jsonl_line(line) {
+            LineParse::Parsed(parsed) => parsed,
+            LineParse::Unrecognized => {
+                warning_count += 1;
+                continue;
             }
-            if parsed.message_type == MessageType::FileHistorySnapshot {
-                if let Some(ts) = parsed
-                    .json
-                    .get("snapshot")
-                    .and_then(|s| s.get("timestamp"))
-                    .and_then(|t| t.as_str())
-                {
-                    uuid_to_timestamp.insert(parsed.uuid.clone(), ts.to_string());
-                }
+            LineParse::Malformed(e) => {
+                line_errors.push((line.line_number, e));
+                continue;
             }
-            if session_slug.is_none() {
-                if let Some(slug) = parsed.json.get("slug").and_then(|s| s.as_str()) {
-                    session_slug = Some(slug.to_string());
-                }
+        };
+        for warning in line_warnings(&parsed, now) {
+            tracing::warn!(
+                "[{}] line {}: {}: {}",
+                session_id,
+                line.line_number,
+                warning.kind.as_str(),
+                warning.detail
+            );
+            warnings.push((line.line_number, warning));
+        }
+        if let Some(ref ts) = parsed.direct_timestamp {
+            uuid_to_timestamp.insert(parsed.uuid.clone(), ts.clone());
+        }
+        if parsed.message_type == MessageType::FileHistorySnapshot {
+            if let Some(ts) = parsed
+                .json
+                .get("snapshot")
+                .and_then(|s| s.get("timestamp"))
+                .and_then(|t| t.as_str())
+            {
+                uuid_to_timestamp.insert(parsed.uuid.clone(), ts.to_string());
             }
-            if line.line_number as i32 > max_line {
-                max_line = line.line_number as i32;
+        }
+        if session_slug.is_none() {
+            if let Some(slug) = parsed.json.get("slug").and_then(|s| s.as_str()) {
+                session_slug = Some(slug.to_string());
+            }
+        }
+        if line.line_number as i32 > max_line {
+            max_line = line.line_number as i32;
+        }
+        intermediate_lines.push(parsed);
+    }
+
+    // Agent transcripts are their own session; link them to the spawning session
+    if let ClassifiedFile::Agent { agent_id } = classify_file(path) {
+        let spawned_at = intermediate_lines
+            .iter()
+            .find_map(|l| l.direct_timestamp.clone())
+            .unwrap_or_else(|| Utc::now().to_rfc3339());
+        link_agent_session_to_parent(db, path, &agent_id, &spawned_at).await?;
+        // Older indexer versions stored agent messages under the parent session
+        if last_line == 0 {
+            if let Some(ref name) = source_file_name {
+                crud::messages::delete_by_source_file_outside_session(db, name, &agent_id).await?;
             }
-            intermediate_lines.push(parsed);
         }
     }
 
     // Pass 2: Finalize messages and insert in batches
+    // New rows (reported to callers) vs. all rows written including re-index updates
     let mut total_indexed = 0u32;
+    let mut rows_written = 0usize;
     let mut messages_batch: Vec<messages::ActiveModel> = Vec::new();
     let mut tool_call_results_batch: Vec<han_db::entities::tool_call_results::ActiveModel> = Vec::new();
     let mut last_known_timestamp: Option<String> = None;
     // Track sequential TaskCreate positions for TaskUpdate ID resolution
     let mut task_create_ids: Vec<String> = Vec::new();
+    // Sub-agent sessions already linked in this pass
+    let mut linked_agent_sessions: HashSet<String> = HashSet::new();
 
     for parsed in intermediate_lines {
         let line_number = parsed.line_number;
+        let byte_offset = parsed.byte_offset;
         if let Some(finalized) =
             finalize_parsed_message(parsed, &uuid_to_timestamp, last_known_timestamp.as_deref())
         {
@@ -1530,10 +2083,11 @@ pub async fn index_session_file(
             if finalized.message_type == MessageType::ToolUse {
                 if let (Some(ref tn), Some(ref ti)) = (&finalized.tool_name, &finalized.tool_input)
                 {
-                    if is_file_modification_tool(tn) {
+                    if is_file_tool(tn) {
                         record_file_change_from_tool(
                             db,
                             &session_id,
+                            &message_id,
                             tn,
                             ti,
                             finalized.agent_id.as_deref(),
@@ -1545,6 +2099,7 @@ pub async fn index_session_file(
                             db,
                             &session_id,
                             &message_id,
+                            None,
                             ti,
                             &message_timestamp,
                             line_number,
@@ -1596,6 +2151,17 @@ pub async fn index_session_file(
             // Process assistant message tool_use content blocks
             if finalized.message_type == MessageType::Assistant {
                 if let Ok(json) = serde_json::from_str::<Value>(&finalized.raw_json) {
+                    for access in extract_file_changes_from_tool_use(&json) {
+                        record_file_change_from_tool(
+                            db,
+                            &session_id,
+                            &access.tool_call_id,
+                            &access.tool_name,
+                            &access.tool_input,
+                            finalized.agent_id.as_deref(),
+                        )
+                        .await;
+                    }
                     if let Some(content) = json
                         .get("message")
                         .and_then(|m| m.get("content"))
@@ -1604,25 +2170,13 @@ pub async fn index_session_file(
                         for item in content {
                             if item.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
                                 if let Some(tool_name) = item.get("name").and_then(|n| n.as_str()) {
-                                    if is_file_modification_tool(tool_name) {
-                                        if let Some(input) = item.get("input") {
-                                            let input_str = input.to_string();
-                                            record_file_change_from_tool(
-                                                db,
-                                                &session_id,
-                                                tool_name,
-                                                &input_str,
-                                                finalized.agent_id.as_deref(),
-                                            )
-                                            .await;
-                                        }
-                                    }
                                     if tool_name == "TodoWrite" {
                                         if let Some(input) = item.get("input") {
                                             extract_and_save_todos(
                                                 db,
                                                 &session_id,
                                                 &message_id,
+                                                item.get("id").and_then(|v| v.as_str()),
                                                 &input.to_string(),
                                                 &message_timestamp,
                                                 line_number,
@@ -1714,6 +2268,7 @@ pub async fn index_session_file(
                                             Some(finalized.raw_json.clone()),
                                             finalized.timestamp.clone(),
                                             line_number,
+                                            Some(byte_offset),
                                             source_file_name.clone(),
                                             source_file_type.clone(),
                                             None, None, None, None,
@@ -1775,6 +2330,18 @@ pub async fn index_session_file(
                     }
                 }
 
+                // Task results carry the spawned agent's ID; link it as a child session
+                if let Some((agent_id, tool_call_id)) = extract_spawned_agent(&finalized.raw_json) {
+                    crud::agent_sessions::link(
+                        db,
+                        session_id.clone(),
+                        agent_id,
+                        Some(tool_call_id),
+                        message_timestamp.clone(),
+                    )
+                    .await?;
+                }
+
                 // Extract tool_result content blocks for the tool_call_results index
                 extract_tool_call_results(
                     &finalized.raw_json,
@@ -1784,6 +2351,27 @@ pub async fn index_session_file(
                 );
             }
 
+            // Task sub-agents running as their own session report its ID as
+            // `data.agent_task_id`; link it and mark its messages
+            if let Some(child_id) = extract_agent_task_id(&finalized.raw_json) {
+                if child_id != session_id && linked_agent_sessions.insert(child_id.clone()) {
+                    crud::agent_sessions::link(
+                        db,
+                        session_id.clone(),
+                        child_id.clone(),
+                        None,
+                        message_timestamp.clone(),
+                    )
+                    .await?;
+                    crud::messages::set_source_file_type(
+                        db,
+                        &child_id,
+                        SessionFileType::AgentSession.as_str(),
+                    )
+                    .await?;
+                }
+            }
+
             // Compute sentiment for real human user messages only
             let (sentiment_score, sentiment_level, frustration_score, frustration_level) =
                 if is_user_message && should_compute_sentiment(&finalized.raw_json) {
@@ -1795,6 +2383,9 @@ pub async fn index_session_file(
                 } else {
                     (None, None, None, None)
                 };
+            if frustration_level.as_deref() == Some(sentiment::FrustrationLevel::High.as_str()) {
+                frustrated_messages.push(finalized.uuid.clone());
+            }
 
             messages_batch.push(to_active_model(
                 finalized.uuid.clone(),
@@ -1810,6 +2401,7 @@ pub async fn index_session_file(
                 Some(finalized.raw_json.clone()),
                 finalized.timestamp.clone(),
                 line_number,
+                Some(byte_offset),
                 source_file_name.clone(),
                 source_file_type.clone(),
                 sentiment_score,
@@ -1829,25 +2421,29 @@ pub async fn index_session_file(
             // NOTE: Separate sentiment_analysis events are no longer generated.
             // Sentiment data is stored as columns on user message rows directly.
 
-            // Batch insert every 100 messages
+            // Batch upsert every 100 messages, then checkpoint so a crash
+            // resumes after this line
             if messages_batch.len() >= 100 {
+                rows_written += messages_batch.len();
                 let count =
-                    crud::messages::insert_batch(db, std::mem::take(&mut messages_batch)).await?;
+                    crud::messages::bulk_upsert(db, std::mem::take(&mut messages_batch)).await?;
                 total_indexed += count as u32;
+                let results = std::mem::take(&mut tool_call_results_batch);
+                crud::tool_call_results::insert_batch(db, results).await?;
+                crud::indexing_checkpoints::save(db, &session_id, line_number).await?;
             }
         }
     }
 
-    // Insert remaining Claude messages
+    // Upsert remaining Claude messages
     if !messages_batch.is_empty() {
-        let count = crud::messages::insert_batch(db, std::mem::take(&mut messages_batch)).await?;
+        rows_written += messages_batch.len();
+        let count = crud::messages::bulk_upsert(db, std::mem::take(&mut messages_batch)).await?;
         total_indexed += count as u32;
     }
 
     // Insert tool call results index
-    if !tool_call_results_batch.is_empty() {
-        let _ = crud::tool_call_results::insert_batch(db, std::mem::take(&mut tool_call_results_batch)).await;
-    }
+    crud::tool_call_results::insert_batch(db, std::mem::take(&mut tool_call_results_batch)).await?;
 
     // =========================================================================
     // Han Events: Read and insert from -han.jsonl file
@@ -1856,22 +2452,32 @@ pub async fn index_session_file(
 
     if let Some(han_file) = get_han_events_path(path) {
         let han_events = read_han_events(&han_file, &session_id);
+        let durations = tool_call_durations(&han_events);
+        let learned = learned_memories(&han_events);
         let han_file_name = han_file
             .file_name()
             .and_then(|n| n.to_str())
             .map(|s| s.to_string());
 
+        let mut tasks_changed = false;
         for (idx, event) in han_events.into_iter().enumerate() {
             // Process task events from Han events
-            let _ = process_task_event(db, &event, &session_id).await;
+            if let Ok(true) = process_task_event(db, &event, &session_id).await {
+                tasks_changed = true;
+            }
             // Process validation cache events
             let _ = process_validation_cache_event(db, &event, &session_id).await;
 
             let ln = HAN_LINE_OFFSET + (idx as i32);
             let content = serde_json::to_string(&event.data).ok();
             let tool_name = Some(event.event_type.clone());
+            let plugin_name = event
+                .data
+                .get("plugin")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
 
-            messages_batch.push(to_active_model(
+            let mut model = to_active_model(
                 event.id,
                 &session_id,
                 event.agent_id,
@@ -1885,6 +2491,7 @@ pub async fn index_session_file(
                 Some(event.raw_json),
                 event.timestamp,
                 ln,
+                Some(event.byte_offset),
                 han_file_name.clone(),
                 Some("han_events".to_string()),
                 None,
@@ -1899,25 +2506,51 @@ pub async fn index_session_file(
                 None,
                 None,
                 None, // human_time_ms
-            ));
+            );
+            model.plugin_name = Set(plugin_name);
+            messages_batch.push(model);
 
             if messages_batch.len() >= 100 {
+                rows_written += messages_batch.len();
                 let count =
-                    crud::messages::insert_batch(db, std::mem::take(&mut messages_batch)).await?;
+                    crud::messages::bulk_upsert(db, std::mem::take(&mut messages_batch)).await?;
                 total_indexed += count as u32;
             }
         }
 
         if !messages_batch.is_empty() {
-            let count = crud::messages::insert_batch(db, messages_batch).await?;
+            rows_written += messages_batch.len();
+            let count = crud::messages::bulk_upsert(db, messages_batch).await?;
             total_indexed += count as u32;
         }
+
+        crud::messages::set_duration_ms(db, &durations).await?;
+        link_learned_memories(db, learned).await?;
+
+        // Refresh the cached timeline so session loads skip the rebuild
+        if tasks_changed {
+            if let Ok(timeline) = TaskTimeline::from_tasks(db, &session_id).await {
+                let _ = timeline.store(db, &session_id).await;
+            }
+        }
     }
 
     // Update last indexed line
-    if max_line > last_line {
+    if max_line > indexed_line {
         crud::sessions::update_last_indexed_line(db, &session_id, max_line).await?;
     }
+    // The pass completed, so the next one starts from last_indexed_line
+    crud::indexing_checkpoints::clear(db, &session_id).await?;
+
+    // Lines read in this pass replace whatever was recorded for them before
+    crud::index_errors::delete_from_line(db, &session_id, start_line as i32).await?;
+    crud::index_errors::record(db, &session_id, &line_errors).await?;
+    crud::index_warnings::delete_from_line(db, &session_id, start_line as i32).await?;
+    let warning_rows: Vec<(u32, String, String)> = warnings
+        .iter()
+        .map(|(line, w)| (*line, w.kind.as_str().to_string(), w.detail.clone()))
+        .collect();
+    crud::index_warnings::record(db, &session_id, &warning_rows).await?;
 
     // Update session slug if found
     if session_slug.is_some() {
@@ -1935,9 +2568,22 @@ pub async fn index_session_file(
 
     let total_messages = crud::messages::get_count(db, &session_id).await?;
 
-    // Update pre-aggregated tables if new messages were indexed
-    if total_indexed > 0 {
+    // Update pre-aggregated tables if any messages were written
+    if rows_written > 0 {
         update_aggregates(db, &session_id).await;
+        // Project stats now include stale numbers for this session
+        crud::project_stats::invalidate_for_session(db, &session_id).await?;
+    }
+
+    let error_count = line_errors.len() as u32;
+    let summary = format!(
+        "[{}]: indexed {} messages, {} errors, {} warnings",
+        session_id, total_indexed, error_count, warning_count
+    );
+    if error_count > 0 {
+        tracing::warn!("{}", summary);
+    } else {
+        tracing::debug!("{}", summary);
     }
 
     Ok(IndexResult {
@@ -1946,14 +2592,104 @@ pub async fn index_session_file(
         total_messages: total_messages as u32,
         is_new_session,
         error: None,
+        errors: line_errors,
+        error_count,
+        warning_count,
+        warnings,
+        frustrated_messages,
     })
 }
 
-/// Update pre-aggregated daily/hourly/global tables after indexing new messages.
-/// Uses INSERT OR REPLACE to rebuild affected rows from the messages table.
-/// This is fast because it only touches dates/hours that this session contributed to.
-async fn update_aggregates(db: &DatabaseConnection, session_id: &str) {
-    // Rebuild daily aggregates for dates this session has messages on
+/// Re-index a session transcript that may have been edited out-of-band.
+///
+/// With `force`, every message read from the file is deleted and the file is
+/// indexed from the start. Otherwise stored messages are checked against the
+/// file: the first line whose byte offset moved or whose content no longer
+/// matches the stored raw JSON is re-indexed along with everything after it.
+/// Messages before that line are kept and reported as skipped.
+pub async fn reindex_session_file(
+    db: &DatabaseConnection,
+    file_path: &str,
+    source_config_dir: Option<&str>,
+    force: bool,
+) -> ProcessorResult<ReindexResult> {
+    let path = Path::new(file_path);
+    let (Some(session_id), Some(source_file_name)) = (
+        extract_session_id(path),
+        path.file_name().and_then(|n| n.to_str()),
+    ) else {
+        return Ok(ReindexResult {
+            session_id: String::new(),
+            messages_indexed: 0,
+            messages_skipped: 0,
+            error: Some("Could not extract session ID from filename".to_string()),
+        });
+    };
+
+    let stored = crud::messages::list_by_source_file(db, &session_id, source_file_name).await?;
+    let from_line = if force {
+        Some(0)
+    } else {
+        first_changed_line(path, &stored)?
+    };
+    let messages_skipped = match from_line {
+        Some(line) => stored.iter().filter(|m| m.line_number < line).count(),
+        None => stored.len(),
+    } as u32;
+
+    if let Some(line) = from_line {
+        crud::messages::delete_by_source_file_from_line(db, &session_id, source_file_name, line)
+            .await?;
+        crud::sessions::update_last_indexed_line(db, &session_id, (line - 1).max(0)).await?;
+        crud::indexing_checkpoints::clear(db, &session_id).await?;
+    }
+
+    let result = index_session_file(db, file_path, source_config_dir).await?;
+    Ok(ReindexResult {
+        session_id: result.session_id,
+        messages_indexed: result.messages_indexed,
+        messages_skipped,
+        error: result.error,
+    })
+}
+
+/// First line of `path` whose stored messages are out of date, if any.
+///
+/// Stored rows are grouped by line. A line is unchanged when it still exists
+/// at the recorded byte offset (rows indexed before offsets were recorded skip
+/// that check) and one of its rows holds the line verbatim as raw JSON.
+fn first_changed_line(path: &Path, stored: &[messages::Model]) -> ProcessorResult<Option<i32>> {
+    let lines = jsonl_read_page(path, 0, u32::MAX)?.lines;
+    let lines_by_number: HashMap<i32, &JsonlLine> =
+        lines.iter().map(|l| (l.line_number as i32, l)).collect();
+
+    let mut rows_by_line: BTreeMap<i32, Vec<&messages::Model>> = BTreeMap::new();
+    for row in stored {
+        rows_by_line.entry(row.line_number).or_default().push(row);
+    }
+
+    for (line_number, rows) in rows_by_line {
+        let unchanged = lines_by_number.get(&line_number).is_some_and(|line| {
+            let same_offset = rows
+                .iter()
+                .all(|m| m.byte_offset.is_none_or(|o| o == line.byte_offset));
+            let same_content = rows
+                .iter()
+                .any(|m| m.raw_json.as_deref() == Some(line.content.as_str()));
+            same_offset && same_content
+        });
+        if !unchanged {
+            return Ok(Some(line_number));
+        }
+    }
+    Ok(None)
+}
+
+/// Update pre-aggregated daily/hourly/global tables after indexing new messages.
+/// Uses INSERT OR REPLACE to rebuild affected rows from the messages table.
+/// This is fast because it only touches dates/hours that this session contributed to.
+async fn update_aggregates(db: &DatabaseConnection, session_id: &str) {
+    // Rebuild daily aggregates for dates this session has messages on
     let _ = db.execute(Statement::from_sql_and_values(
         DbBackend::Sqlite,
         "INSERT OR REPLACE INTO daily_aggregates (date, session_count, message_count, input_tokens, output_tokens, cache_read_tokens, lines_added, lines_removed, files_changed) \
@@ -1995,6 +2731,9 @@ async fn update_aggregates(db: &DatabaseConnection, session_id: &str) {
                 (SELECT COUNT(*) FROM tasks WHERE completed_at IS NOT NULL) + (SELECT COUNT(*) FROM native_tasks WHERE status = 'completed'), \
                 datetime('now')".to_string(),
     )).await;
+
+    // Keep cross-session topic search in step with the summary and first prompt
+    let _ = han_db::search::refresh_session_fts(db, session_id).await;
 }
 
 /// Generate a sentiment analysis event message for a user message.
@@ -2066,6 +2805,7 @@ fn generate_sentiment_event(
         event_timestamp,
         line_number + 500_000,
         None,
+        None,
         Some("generated".to_string()),
         None,
         None,
@@ -2284,6 +3024,11 @@ pub async fn handle_file_event(
     let path = Path::new(file_path);
     let filename = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
 
+    // The watcher also reports sidecar files, which aren't transcripts
+    if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+        return Ok(None);
+    }
+
     match event_type {
         FileEventType::Created | FileEventType::Modified => {
             // If this is a Han events file, find and index the main session file
@@ -2318,12 +3063,214 @@ pub async fn handle_file_event(
     }
 }
 
+/// Number of files a full scan indexes at once.
+pub const FULL_SCAN_CONCURRENCY: usize = 4;
+
+/// Send a progress update, ignoring a receiver that has gone away.
+async fn report_progress(
+    progress_tx: &Option<mpsc::Sender<IndexProgress>>,
+    session_id: &str,
+    file_path: &str,
+    status: IndexStatus,
+    messages_indexed: u32,
+) {
+    if let Some(tx) = progress_tx {
+        let _ = tx
+            .send(IndexProgress {
+                session_id: session_id.to_string(),
+                file_path: file_path.to_string(),
+                status,
+                messages_indexed,
+            })
+            .await;
+    }
+}
+
+/// Config directory of a `{config_dir}/projects/{project}` directory.
+fn config_dir_of(project_dir: &Path) -> Option<String> {
+    let projects_dir = project_dir.parent()?;
+    if projects_dir.file_name()? != "projects" {
+        return None;
+    }
+    Some(projects_dir.parent()?.to_string_lossy().to_string())
+}
+
+/// Index every JSONL session file in `session_dirs` (project directories),
+/// with at most `concurrency` files in flight.
+///
+/// Main transcripts are all indexed before agent transcripts, as in
+/// [`index_project_directory`]. Each file reports `Pending` when queued,
+/// `InProgress` when started, then `Done` or `Error`. A file that fails to
+/// index is logged and returned with `error` set instead of aborting the run.
+pub async fn index_all_sessions_parallel(
+    db: &DatabaseConnection,
+    session_dirs: Vec<PathBuf>,
+    concurrency: usize,
+    progress_tx: Option<mpsc::Sender<IndexProgress>>,
+) -> ProcessorResult<Vec<IndexResult>> {
+    let mut main_files = Vec::new();
+    let mut agent_files = Vec::new();
+
+    for dir in &session_dirs {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::warn!("Failed to read project directory {:?}: {}", dir, e);
+                continue;
+            }
+        };
+        let config_dir = config_dir_of(dir);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            match classify_file(&path) {
+                ClassifiedFile::Main { session_id } => {
+                    main_files.push((session_id, path, config_dir.clone()))
+                }
+                ClassifiedFile::Agent { agent_id } => {
+                    agent_files.push((agent_id, path, config_dir.clone()))
+                }
+                ClassifiedFile::HanEvents { .. } => {} // Processed with main file
+                ClassifiedFile::Unknown => {}
+            }
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut results = Vec::new();
+
+    for files in [main_files, agent_files] {
+        for (session_id, path, _) in &files {
+            let file_path = path.to_string_lossy();
+            let status = IndexStatus::Pending;
+            report_progress(&progress_tx, session_id, &file_path, status, 0).await;
+        }
+
+        let mut tasks = JoinSet::new();
+        for (session_id, path, config_dir) in files {
+            let db = db.clone();
+            let progress_tx = progress_tx.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                // The semaphore is never closed
+                let _permit = semaphore.acquire_owned().await.ok();
+                let file_path = path.to_string_lossy().to_string();
+                let status = IndexStatus::InProgress;
+                report_progress(&progress_tx, &session_id, &file_path, status, 0).await;
+
+                let result = match index_session_file(&db, &file_path, config_dir.as_deref()).await
+                {
+                    Ok(result) => result,
+                    Err(e) => {
+                        tracing::warn!("Failed to index {}: {}", file_path, e);
+                        IndexResult {
+                            session_id: session_id.clone(),
+                            messages_indexed: 0,
+                            total_messages: 0,
+                            is_new_session: false,
+                            error: Some(e.to_string()),
+                            errors: Vec::new(),
+                            error_count: 0,
+                            warning_count: 0,
+                            warnings: Vec::new(),
+                            frustrated_messages: Vec::new(),
+                        }
+                    }
+                };
+                let status = if result.error.is_some() {
+                    IndexStatus::Error
+                } else {
+                    IndexStatus::Done
+                };
+                let indexed = result.messages_indexed;
+                report_progress(&progress_tx, &session_id, &file_path, status, indexed).await;
+                result
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(e) => tracing::warn!("Index task failed: {}", e),
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Record the plugins listed in `config_dir`'s
+/// `plugins/installed_plugins.json`, returning how many were recorded.
+///
+/// Entries are keyed `name@marketplace`, while hook events name the plugin
+/// alone, so the marketplace is dropped. Descriptions come from the
+/// plugin's `.claude-plugin/plugin.json` when its install path has one. A
+/// missing or unreadable file records nothing.
+pub async fn sync_installed_plugins(
+    db: &DatabaseConnection,
+    config_dir: &Path,
+) -> ProcessorResult<u32> {
+    let path = config_dir.join("plugins").join("installed_plugins.json");
+    let Some(parsed) = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str::<Value>(&data).ok())
+    else {
+        return Ok(0);
+    };
+    let Some(plugins) = parsed.get("plugins").and_then(|p| p.as_object()) else {
+        return Ok(0);
+    };
+
+    let mut recorded = 0;
+    for (key, installs) in plugins {
+        // Version 2 lists one install per scope; version 1 has a single one
+        let install = match installs {
+            Value::Array(installs) => installs.first(),
+            other => Some(other),
+        };
+        let Some(install) = install else {
+            continue;
+        };
+        let field = |name: &str| install.get(name).and_then(|v| v.as_str());
+        let name = key.split('@').next().unwrap_or(key).to_string();
+        let description = field("installPath").and_then(|dir| {
+            let manifest = Path::new(dir).join(".claude-plugin").join("plugin.json");
+            let manifest: Value =
+                serde_json::from_str(&std::fs::read_to_string(manifest).ok()?).ok()?;
+            manifest.get("description")?.as_str().map(str::to_string)
+        });
+        crud::plugins::upsert(
+            db,
+            name,
+            field("version").unwrap_or("unknown").to_string(),
+            description,
+            field("installedAt")
+                .or_else(|| field("lastUpdated"))
+                .unwrap_or_default()
+                .to_string(),
+        )
+        .await?;
+        recorded += 1;
+    }
+    Ok(recorded)
+}
+
 /// Perform a full scan and index of all Claude Code sessions.
 pub async fn full_scan_and_index(db: &DatabaseConnection) -> ProcessorResult<Vec<IndexResult>> {
+    full_scan_and_index_with_progress(db, None).await
+}
+
+/// [`full_scan_and_index`], reporting per-file progress on `progress_tx`.
+pub async fn full_scan_and_index_with_progress(
+    db: &DatabaseConnection,
+    progress_tx: Option<mpsc::Sender<IndexProgress>>,
+) -> ProcessorResult<Vec<IndexResult>> {
     // Check if indexer version changed — triggers full re-index if needed
     let _ = check_indexer_version(db).await;
 
-    let mut results = Vec::new();
+    let mut project_dirs = Vec::new();
 
     let config_dirs = crud::config_dirs::list(db).await?;
 
@@ -2347,6 +3294,7 @@ pub async fn full_scan_and_index(db: &DatabaseConnection) -> ProcessorResult<Vec
     );
 
     for config_dir in dirs_to_scan {
+        sync_installed_plugins(db, &config_dir).await?;
         let projects_dir = config_dir.join("projects");
         if !projects_dir.exists() {
             continue;
@@ -2369,24 +3317,14 @@ pub async fn full_scan_and_index(db: &DatabaseConnection) -> ProcessorResult<Vec
         for entry in entries.flatten() {
             let path = entry.path();
             if path.is_dir() {
-                let config_dir_str = config_dir.to_string_lossy().to_string();
-                match index_project_directory(
-                    db,
-                    &path.to_string_lossy(),
-                    Some(&config_dir_str),
-                )
-                .await
-                {
-                    Ok(project_results) => results.extend(project_results),
-                    Err(e) => {
-                        tracing::warn!("Failed to index project {:?}: {}", path, e);
-                    }
-                }
-                tokio::task::yield_now().await;
+                project_dirs.push(path);
             }
         }
     }
 
+    let results =
+        index_all_sessions_parallel(db, project_dirs, FULL_SCAN_CONCURRENCY, progress_tx).await?;
+
     tracing::info!(
         "Full scan complete: indexed {} sessions, {} total messages",
         results.len(),
@@ -2399,6 +3337,15 @@ pub async fn full_scan_and_index(db: &DatabaseConnection) -> ProcessorResult<Vec
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_db;
+
+    /// Write `lines` as the transcript `{session_id}.jsonl` in `dir`.
+    fn write_transcript(dir: &Path, session_id: &str, lines: &[impl std::fmt::Display]) -> PathBuf {
+        let path = dir.join(format!("{session_id}.jsonl"));
+        let body: String = lines.iter().map(|line| format!("{line}\n")).collect();
+        std::fs::write(&path, body).unwrap();
+        path
+    }
 
     #[test]
     fn test_classify_file_main() {
@@ -2436,19 +3383,6 @@ mod tests {
         assert!(matches!(classify_file(path), ClassifiedFile::Unknown));
     }
 
-    #[test]
-    fn test_is_valid_uuid() {
-        assert!(is_valid_uuid("abc12345-1234-5678-9abc-def012345678"));
-        assert!(!is_valid_uuid("short"));
-    }
-
-    #[test]
-    fn test_is_valid_cli_session_id() {
-        assert!(is_valid_cli_session_id("cli-abc12345-1234-5678-9abc-def012345678"));
-        assert!(!is_valid_cli_session_id("abc12345-1234-5678-9abc-def012345678"));
-        assert!(!is_valid_cli_session_id("cli-short"));
-    }
-
     #[test]
     fn test_extract_project_slug() {
         let path = Path::new("/home/user/.claude/projects/Volumes-dev-src-myproject/session.jsonl");
@@ -2559,6 +3493,1305 @@ mod tests {
         assert_eq!(detect_compact_type(&json, None), None);
     }
 
+    #[test]
+    fn test_extract_spawned_agent() {
+        let raw = r#"{"type":"user","toolUseResult":{"agentId":"a1b2c3d4","status":"completed"},"message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_01","content":"done"}]}}"#;
+        assert_eq!(
+            extract_spawned_agent(raw),
+            Some(("a1b2c3d4".to_string(), "toolu_01".to_string()))
+        );
+        let raw = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_02","content":"ok"}]}}"#;
+        assert_eq!(extract_spawned_agent(raw), None);
+    }
+
+    #[test]
+    fn test_parse_todo_write_input() {
+        let input: Value = serde_json::from_str(
+            r#"{"todos":[{"content":"Fix bug","status":"in_progress","activeForm":"Fixing bug"},{"status":"pending"},{"id":"3","content":"Ship","status":"pending","priority":"low"}]}"#,
+        )
+        .unwrap();
+        let todos = parse_todo_write_input(&input).unwrap();
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].active_form.as_deref(), Some("Fixing bug"));
+        assert_eq!(todos[1].id.as_deref(), Some("3"));
+        assert_eq!(todos[1].priority.as_deref(), Some("low"));
+
+        let input: Value = serde_json::from_str(r#"{"todos":"nope"}"#).unwrap();
+        assert!(parse_todo_write_input(&input).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_index_todo_write_persists_todos() {
+        let db = test_db().await;
+
+        let session_id = "5f0c2a9e-1b3d-4c8e-9f7a-2d6b8e4c1a3f";
+        let dir = tempfile::tempdir().unwrap();
+        let line = serde_json::json!({
+            "type": "assistant",
+            "uuid": "msg-todo-1",
+            "sessionId": session_id,
+            "timestamp": "2026-04-12T09:00:00Z",
+            "message": {
+                "role": "assistant",
+                "content": [{
+                    "type": "tool_use",
+                    "id": "toolu_todo_1",
+                    "name": "TodoWrite",
+                    "input": {"todos": [
+                        {"content": "Parse input", "status": "completed", "activeForm": "Parsing input"},
+                        {"content": "Persist todos", "status": "in_progress", "activeForm": "Persisting todos"}
+                    ]}
+                }]
+            }
+        });
+        let path = write_transcript(dir.path(), session_id, &[line]);
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+
+        let row = crud::session_todos::get(&db, session_id)
+            .await
+            .unwrap()
+            .expect("TodoWrite should be persisted");
+        assert_eq!(row.message_id, "msg-todo-1");
+        assert_eq!(row.tool_call_id.as_deref(), Some("toolu_todo_1"));
+        let todos = crud::session_todos::parse_todos(&row.todos_json);
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[1].content, "Persist todos");
+        assert_eq!(todos[1].status, "in_progress");
+    }
+
+    #[tokio::test]
+    async fn test_index_task_create_and_update_merge_todos() {
+        let db = test_db().await;
+
+        let session_id = "9a1c3e5b-7d2f-4b6a-8c0e-1f3a5c7e9b2d";
+        let dir = tempfile::tempdir().unwrap();
+        let tool_use = |uuid: &str, ts: &str, id: &str, name: &str, input: Value| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "sessionId": session_id,
+                "timestamp": ts,
+                "message": {
+                    "role": "assistant",
+                    "content": [{"type": "tool_use", "id": id, "name": name, "input": input}]
+                }
+            })
+        };
+        let lines = [
+            tool_use(
+                "msg-task-1",
+                "2026-04-12T09:00:00Z",
+                "toolu_task_1",
+                "TaskCreate",
+                serde_json::json!({"subject": "Write parser", "activeForm": "Writing parser"}),
+            ),
+            tool_use(
+                "msg-task-2",
+                "2026-04-12T09:01:00Z",
+                "toolu_task_2",
+                "TaskCreate",
+                serde_json::json!({"subject": "Add tests"}),
+            ),
+            tool_use(
+                "msg-task-3",
+                "2026-04-12T09:02:00Z",
+                "toolu_task_3",
+                "TaskUpdate",
+                serde_json::json!({"taskId": "1", "status": "completed"}),
+            ),
+        ];
+        let path = write_transcript(dir.path(), session_id, &lines);
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+
+        let row = crud::session_todos::get(&db, session_id)
+            .await
+            .unwrap()
+            .expect("TaskCreate should be persisted");
+        assert_eq!(row.message_id, "msg-task-3");
+        let todos = crud::session_todos::parse_todos(&row.todos_json);
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].content, "Write parser");
+        assert_eq!(todos[0].status, "completed");
+        assert_eq!(todos[0].active_form.as_deref(), Some("Writing parser"));
+        assert_eq!(todos[1].content, "Add tests");
+        assert_eq!(todos[1].status, "pending");
+    }
+
+    #[tokio::test]
+    async fn test_index_records_file_tool_calls() {
+        let db = test_db().await;
+
+        let session_id = "8b2d4f6a-0c1e-4a3b-9d5f-7e9a1c3b5d7f";
+        let dir = tempfile::tempdir().unwrap();
+        let work = std::fs::canonicalize(dir.path()).unwrap();
+        let existing = work.join("lib.rs").to_string_lossy().to_string();
+        let created = work.join("new.rs").to_string_lossy().to_string();
+        let notebook = work.join("analysis.ipynb").to_string_lossy().to_string();
+        std::fs::write(&existing, "fn main() {}\n").unwrap();
+        std::fs::write(&created, "pub fn added() {}\n").unwrap();
+
+        let assistant = |uuid: &str, at: &str, blocks: Value| {
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "sessionId": session_id,
+                "timestamp": format!("2026-04-15T09:00:{at}Z"),
+                "message": {"role": "assistant", "content": blocks}
+            })
+            .to_string()
+        };
+        let lines = [
+            assistant(
+                "msg-files-1",
+                "00",
+                serde_json::json!([
+                    {"type": "text", "text": "Reading first."},
+                    {"type": "tool_use", "id": "toolu_read", "name": "Read",
+                     "input": {"file_path": existing, "limit": 200}},
+                    {"type": "tool_use", "id": "toolu_write_new", "name": "Write",
+                     "input": {"file_path": created, "content": "pub fn added() {}\n"}},
+                ]),
+            ),
+            assistant(
+                "msg-files-2",
+                "01",
+                serde_json::json!([
+                    {"type": "tool_use", "id": "toolu_edit", "name": "Edit",
+                     "input": {"file_path": existing, "old_string": "fn main() {}",
+                               "new_string": "fn main() { run() }", "replace_all": false}},
+                    {"type": "tool_use", "id": "toolu_multi", "name": "MultiEdit",
+                     "input": {"file_path": created, "edits": [
+                         {"old_string": "added", "new_string": "renamed"}
+                     ]}},
+                    {"type": "tool_use", "id": "toolu_rewrite", "name": "Write",
+                     "input": {"file_path": existing, "content": "fn main() {}\n"}},
+                    {"type": "tool_use", "id": "toolu_nb", "name": "NotebookEdit",
+                     "input": {"notebook_path": notebook, "new_source": "print(1)"}},
+                    {"type": "tool_use", "id": "toolu_bash", "name": "Bash",
+                     "input": {"command": "cargo test"}},
+                ]),
+            ),
+        ];
+        let path = write_transcript(dir.path(), session_id, &lines);
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+
+        let rows = |db: &DatabaseConnection| {
+            let db = db.clone();
+            async move {
+                let mut rows = crud::file_changes::get_by_session(&db, session_id, None)
+                    .await
+                    .unwrap();
+                rows.sort_by(|a, b| a.id.cmp(&b.id));
+                rows
+            }
+        };
+        let changes = rows(&db).await;
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                (
+                    c.id.as_str(),
+                    c.tool_name.as_deref().unwrap(),
+                    c.file_path.as_str(),
+                    c.action.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("toolu_edit", "Edit", existing.as_str(), "modified"),
+                ("toolu_multi", "MultiEdit", created.as_str(), "modified"),
+                ("toolu_nb", "NotebookEdit", notebook.as_str(), "modified"),
+                ("toolu_read", "Read", existing.as_str(), "read"),
+                ("toolu_rewrite", "Write", existing.as_str(), "modified"),
+                ("toolu_write_new", "Write", created.as_str(), "created"),
+            ]
+        );
+        // Files that still exist are hashed; the notebook never did
+        assert_eq!(changes[0].file_hash_after, compute_file_hash(&existing));
+        assert!(changes[0].file_hash_after.is_some());
+        assert_eq!(changes[2].file_hash_after, None);
+
+        // Indexing the same calls again updates rows rather than adding them
+        std::fs::write(&existing, "fn main() { run() }\n").unwrap();
+        reindex_session_file(&db, &path.to_string_lossy(), None, true)
+            .await
+            .unwrap();
+        let again = rows(&db).await;
+        assert_eq!(again.len(), 6);
+        assert_eq!(again[5].action, "created");
+        assert_eq!(again[0].file_hash_after, compute_file_hash(&existing));
+        assert_ne!(again[0].file_hash_after, changes[0].file_hash_after);
+    }
+
+    #[test]
+    fn test_detect_worktree_from_path() {
+        let info = detect_worktree("/nonexistent/han/.git/worktrees/feature-x").unwrap();
+        assert_eq!(info.main_project_dir, "/nonexistent/han");
+        assert_eq!(info.worktree_name, "feature-x");
+        assert_eq!(info.branch_name, None);
+
+        let info = detect_worktree("/nonexistent/han/.claude/worktrees/fix-bug/src").unwrap();
+        assert_eq!(info.main_project_dir, "/nonexistent/han");
+        assert_eq!(info.worktree_name, "fix-bug");
+
+        assert_eq!(detect_worktree("/nonexistent/han"), None);
+        assert_eq!(detect_worktree("/nonexistent/han/.git/worktrees/"), None);
+    }
+
+    #[test]
+    fn test_detect_worktree_from_git_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("han");
+        let admin = main.join(".git/worktrees/feature-x");
+        std::fs::create_dir_all(&admin).unwrap();
+        std::fs::write(admin.join("HEAD"), "ref: refs/heads/feature/x\n").unwrap();
+        let checkout = dir.path().join("han-feature-x");
+        std::fs::create_dir_all(&checkout).unwrap();
+        std::fs::write(
+            checkout.join(".git"),
+            format!("gitdir: {}\n", admin.display()),
+        )
+        .unwrap();
+
+        let info = detect_worktree(checkout.to_str().unwrap()).unwrap();
+        assert_eq!(info.main_project_dir, main.to_string_lossy());
+        assert_eq!(info.worktree_name, "feature-x");
+        assert_eq!(info.branch_name.as_deref(), Some("feature/x"));
+
+        // The main checkout has a `.git` directory, not a file
+        assert_eq!(detect_worktree(main.to_str().unwrap()), None);
+    }
+
+    #[tokio::test]
+    async fn test_worktree_sessions_share_main_project_dir() {
+        let db = test_db().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("han");
+        let mut worktree_ids = Vec::new();
+        for name in ["alpha", "beta"] {
+            let admin = main.join(".git/worktrees").join(name);
+            std::fs::create_dir_all(&admin).unwrap();
+            std::fs::write(admin.join("HEAD"), format!("ref: refs/heads/{name}\n")).unwrap();
+            let checkout = dir.path().join(format!("han-{name}"));
+            std::fs::create_dir_all(&checkout).unwrap();
+            std::fs::write(
+                checkout.join(".git"),
+                format!("gitdir: {}\n", admin.display()),
+            )
+            .unwrap();
+
+            let session_id = format!("session-{name}");
+            crud::sessions::upsert(&db, session_id.clone(), None, None, None, None, None)
+                .await
+                .unwrap();
+            let worktree = link_worktree(&db, &session_id, checkout.to_str().unwrap())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(worktree.main_project_dir, main.to_string_lossy());
+            assert_eq!(worktree.branch_name.as_deref(), Some(name));
+
+            let session = crud::sessions::get(&db, &session_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(session.worktree_id.as_ref(), Some(&worktree.id));
+            worktree_ids.push(worktree.id);
+        }
+        assert_ne!(worktree_ids[0], worktree_ids[1]);
+
+        let listed = crud::worktrees::list_for_main_project(&db, &main.to_string_lossy())
+            .await
+            .unwrap();
+        let names: Vec<&str> = listed.iter().map(|w| w.worktree_name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+
+        // A session in the main checkout isn't linked
+        crud::sessions::upsert(&db, "session-main".into(), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(link_worktree(&db, "session-main", main.to_str().unwrap())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_index_links_claude_worktree_from_cwd() {
+        let db = test_db().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("han");
+        let admin = main.join(".git/worktrees/fix-bug");
+        std::fs::create_dir_all(&admin).unwrap();
+        std::fs::write(admin.join("HEAD"), "ref: refs/heads/fix-bug\n").unwrap();
+        let checkout = main.join(".claude/worktrees/fix-bug");
+        std::fs::create_dir_all(&checkout).unwrap();
+        std::fs::write(
+            checkout.join(".git"),
+            format!("gitdir: {}\n", admin.display()),
+        )
+        .unwrap();
+
+        // The slug loses the `.` in `.claude`, so only `cwd` names the checkout
+        let cwd = checkout.to_string_lossy().to_string();
+        let slug = cwd.replace(['/', '.'], "-");
+        let session_id = "6b8d0f2a-4c6e-4a8b-9d1f-3e5a7c9b1d3f";
+        let project_dir = dir.path().join("projects").join(&slug);
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-worktree-0",
+            "sessionId": session_id,
+            "cwd": cwd,
+            "timestamp": "2026-04-20T10:00:00Z",
+            "message": {"role": "user", "content": "fix the bug"}
+        });
+        let path = write_transcript(&project_dir, session_id, &[line]);
+
+        index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+
+        let session = crud::sessions::get(&db, session_id).await.unwrap().unwrap();
+        let worktrees = crud::worktrees::list_for_main_project(&db, &main.to_string_lossy())
+            .await
+            .unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert_eq!(session.worktree_id.as_ref(), Some(&worktrees[0].id));
+        assert_eq!(worktrees[0].project_dir, cwd);
+        assert_eq!(worktrees[0].worktree_name, "fix-bug");
+        assert_eq!(worktrees[0].branch_name.as_deref(), Some("fix-bug"));
+    }
+
+    #[tokio::test]
+    async fn test_reads_alone_are_not_changes() {
+        let db = test_db().await;
+
+        let session_id = "reads-only";
+        crud::sessions::upsert(&db, session_id.to_string(), None, None, None, None, None)
+            .await
+            .unwrap();
+        let read = serde_json::json!({"file_path": "/repo/src/main.rs"}).to_string();
+        record_file_change_from_tool(&db, session_id, "toolu_1", "Read", &read, None).await;
+        assert!(!crud::file_changes::has_changes(&db, session_id, None)
+            .await
+            .unwrap());
+
+        record_file_change_from_tool(&db, session_id, "toolu_2", "Edit", &read, None).await;
+        assert!(crud::file_changes::has_changes(&db, session_id, None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_index_records_byte_offsets() {
+        let db = test_db().await;
+
+        let session_id = "8a1d3c5e-7f9b-4d2a-8c6e-1b3f5a7d9e0c";
+        let dir = tempfile::tempdir().unwrap();
+        let lines: Vec<String> = ["first", "second"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                serde_json::json!({
+                    "type": "user",
+                    "uuid": format!("msg-offset-{i}"),
+                    "sessionId": session_id,
+                    "timestamp": format!("2026-04-14T09:00:0{i}Z"),
+                    "message": {"role": "user", "content": text}
+                })
+                .to_string()
+            })
+            .collect();
+        let path = write_transcript(dir.path(), session_id, &lines);
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+
+        let first = crud::messages::get(&db, "msg-offset-0")
+            .await
+            .unwrap()
+            .unwrap();
+        let second = crud::messages::get(&db, "msg-offset-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.byte_offset, Some(0));
+        assert_eq!(second.byte_offset, Some(lines[0].len() as i64 + 1));
+        assert!(second.line_number > first.line_number);
+
+        // Search hits carry the offset, which seeks straight to their line
+        let hits = han_db::search::fts_search_messages(&db, "second", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        let offset = hits[0].byte_offset.unwrap() as usize;
+        let file = std::fs::read_to_string(&path).unwrap();
+        let line = file[offset..].lines().next().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(parsed["uuid"], hits[0].message_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_index_pairs_tool_call_durations() {
+        let db = test_db().await;
+
+        let session_id = "3c7e9a1b-5d2f-4e8a-b6c4-9f1d3e5a7b2c";
+        let dir = tempfile::tempdir().unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-pair-0",
+            "sessionId": session_id,
+            "timestamp": "2026-04-20T10:00:00Z",
+            "message": {"role": "user", "content": "look it up"}
+        });
+        let path = write_transcript(dir.path(), session_id, &[line]);
+
+        // `at` is the seconds part of the event's timestamp
+        let event = |id: &str, kind: &str, call_id: &str, at: &str| {
+            serde_json::json!({
+                "id": id,
+                "type": kind,
+                "timestamp": format!("2026-04-20T10:00:{at}Z"),
+                "data": {"call_id": call_id}
+            })
+            .to_string()
+        };
+        let events = [
+            event("mcp-call", "mcp_tool_call", "c1", "01"),
+            event("exposed-call", "exposed_tool_call", "c2", "02.250"),
+            event("orphan-call", "mcp_tool_call", "c3", "03"),
+            // Shares c2 with the exposed call but must not pair with it
+            event("mcp-result-c2", "mcp_tool_result", "c2", "02.300"),
+            event("mcp-result", "mcp_tool_result", "c1", "01.480"),
+            event("exposed-result", "exposed_tool_result", "c2", "05"),
+        ];
+        let han_path = dir.path().join(format!("{session_id}-han.jsonl"));
+        std::fs::write(&han_path, events.join("\n") + "\n").unwrap();
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+
+        let duration = |id: &'static str| {
+            let db = &db;
+            async move {
+                crud::messages::get(db, id)
+                    .await
+                    .unwrap()
+                    .expect("event should be indexed")
+                    .duration_ms
+            }
+        };
+        assert_eq!(duration("mcp-call").await, Some(480));
+        assert_eq!(duration("exposed-call").await, Some(2750));
+        assert_eq!(duration("orphan-call").await, None);
+        assert_eq!(duration("mcp-result").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_index_links_learned_memories() {
+        let db = test_db().await;
+
+        let session_id = "7b3d5f9a-1c2e-4a6b-8d0f-4e6a8c0b2d4f";
+        let dir = tempfile::tempdir().unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-memory-0",
+            "sessionId": session_id,
+            "timestamp": "2026-04-20T10:00:00Z",
+            "message": {"role": "user", "content": "remember this"}
+        });
+        let path = write_transcript(dir.path(), session_id, &[line]);
+        let events = [
+            serde_json::json!({
+                "id": "learn-scoped",
+                "type": "memory_learn",
+                "timestamp": "2026-04-20T10:00:01Z",
+                "data": {"scope": "testing", "content": "Run tests with --workspace"}
+            }),
+            serde_json::json!({
+                "id": "learn-legacy",
+                "type": "memory_learn",
+                "timestamp": "2026-04-20T10:00:02Z",
+                "data": {"domain": "style", "content": "Prefer early returns"}
+            }),
+            serde_json::json!({
+                "id": "learn-empty",
+                "type": "memory_learn",
+                "timestamp": "2026-04-20T10:00:03Z",
+                "data": {"scope": "testing"}
+            }),
+            serde_json::json!({
+                "id": "hook-run",
+                "type": "hook_run",
+                "timestamp": "2026-04-20T10:00:04Z",
+                "data": {"plugin": "biome", "hook": "lint"}
+            }),
+        ]
+        .map(|e| e.to_string());
+        let han_path = dir.path().join(format!("{session_id}-han.jsonl"));
+        std::fs::write(&han_path, events.join("\n") + "\n").unwrap();
+
+        let memory_of = |id: &'static str| {
+            let db = &db;
+            async move {
+                let message = crud::messages::get(db, id)
+                    .await
+                    .unwrap()
+                    .expect("event should be indexed");
+                match message.memory_id {
+                    Some(memory_id) => crud::memories::get(db, &memory_id).await.unwrap(),
+                    None => None,
+                }
+            }
+        };
+
+        index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+
+        let scoped = memory_of("learn-scoped")
+            .await
+            .expect("memory should be linked");
+        assert_eq!(scoped.scope.as_deref(), Some("testing"));
+        assert_eq!(scoped.content, "Run tests with --workspace");
+        assert_eq!(scoped.created_at, "2026-04-20T10:00:01Z");
+        let legacy = memory_of("learn-legacy")
+            .await
+            .expect("memory should be linked");
+        assert_eq!(legacy.scope.as_deref(), Some("style"));
+        assert!(memory_of("learn-empty").await.is_none());
+        let hook_run = crud::messages::get(&db, "hook-run").await.unwrap().unwrap();
+        assert_eq!(hook_run.plugin_name.as_deref(), Some("biome"));
+        assert_eq!(hook_run.memory_id, None);
+
+        // Han events are re-read on every pass; the memory must not be duplicated
+        index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        let again = memory_of("learn-scoped").await.unwrap();
+        assert_eq!(again.id, scoped.id);
+    }
+
+    #[tokio::test]
+    async fn test_index_stores_task_timeline() {
+        let db = test_db().await;
+
+        let session_id = "5e1a7c3d-9b2f-4d6e-8a1c-3f5b7d9e1a2c";
+        let dir = tempfile::tempdir().unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-timeline-0",
+            "sessionId": session_id,
+            "timestamp": "2026-04-20T10:00:00Z",
+            "message": {"role": "user", "content": "start a task"}
+        });
+        let path = write_transcript(dir.path(), session_id, &[line]);
+        let event = serde_json::json!({
+            "id": "task-start-1",
+            "type": "task_start",
+            "timestamp": "2026-04-20T10:00:01Z",
+            "data": {"task_id": "timeline-task", "description": "demo", "task_type": "fix"}
+        });
+        let han_path = dir.path().join(format!("{session_id}-han.jsonl"));
+        std::fs::write(&han_path, format!("{event}\n")).unwrap();
+
+        index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+
+        let json = crud::session_timelines::get_task_timeline(&db, session_id)
+            .await
+            .unwrap()
+            .expect("timeline should be cached after indexing");
+        let cached = TaskTimeline::from_json(&json).unwrap();
+        let rebuilt = TaskTimeline::from_tasks(&db, session_id).await.unwrap();
+        assert_eq!(cached.to_json(), rebuilt.to_json());
+        assert!(json.contains("timeline-task"));
+    }
+
+    #[tokio::test]
+    async fn test_index_resumes_from_checkpoint() {
+        use sea_orm::EntityTrait;
+
+        let db = test_db().await;
+
+        let session_id = "9a4c6e8f-0b2d-4f6a-8c0e-2b4d6f8a0c2e";
+        let dir = tempfile::tempdir().unwrap();
+        let line = |i: usize, content: &str| {
+            serde_json::json!({
+                "type": "user",
+                "uuid": format!("msg-{i:03}"),
+                "sessionId": session_id,
+                "timestamp": format!("2026-04-20T10:{:02}:{:02}Z", i / 60, i % 60),
+                "message": {"role": "user", "content": content}
+            })
+            .to_string()
+        };
+        let mut lines: Vec<String> = (0..100).map(|i| line(i, "original")).collect();
+
+        // Simulate a crash after 50 of 100 lines: their messages and the
+        // checkpoint are written, but the pass never completed
+        let path = write_transcript(dir.path(), session_id, &lines[..50]);
+        let file_path = path.to_string_lossy().to_string();
+        index_session_file(&db, &file_path, None).await.unwrap();
+        crud::sessions::update_last_indexed_line(&db, session_id, 0)
+            .await
+            .unwrap();
+        crud::indexing_checkpoints::save(&db, session_id, 49)
+            .await
+            .unwrap();
+
+        // Lines before the checkpoint are skipped, so this edit is never read
+        lines[10] = line(10, "edited");
+        write_transcript(dir.path(), session_id, &lines);
+        let result = index_session_file(&db, &file_path, None).await.unwrap();
+        assert_eq!(result.messages_indexed, 50);
+        let count = crud::messages::get_count(&db, session_id).await.unwrap();
+        assert_eq!(count, 100);
+        let skipped = messages::Entity::find_by_id("msg-010")
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(skipped.content.as_deref(), Some("original"));
+
+        // Completing the pass clears the checkpoint
+        let checkpoint = crud::indexing_checkpoints::get(&db, session_id).await;
+        assert_eq!(checkpoint.unwrap(), None);
+        let session = crud::sessions::get(&db, session_id).await.unwrap().unwrap();
+        assert_eq!(session.last_indexed_line, Some(99));
+
+        // Indexing from an explicit line re-reads it and everything after
+        let result = index_session_file_from_line(&db, session_id, &path, 10)
+            .await
+            .unwrap();
+        assert_eq!(result.messages_indexed, 0);
+        let count = crud::messages::get_count(&db, session_id).await.unwrap();
+        assert_eq!(count, 100);
+        let reread = messages::Entity::find_by_id("msg-010")
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reread.content.as_deref(), Some("edited"));
+    }
+
+    #[tokio::test]
+    async fn test_index_reports_frustrated_messages() {
+        let db = test_db().await;
+
+        let session_id = "7c2e4a9b-1d3f-4b5a-9e8c-6a4f2d1b3c5e";
+        let dir = tempfile::tempdir().unwrap();
+        let lines = [
+            ("msg-calm", "Thanks, that looks great!"),
+            ("msg-angry", "STOP. THIS IS TERRIBLE AND BROKEN!! forget it"),
+        ]
+        .map(|(uuid, content)| {
+            serde_json::json!({
+                "type": "user",
+                "uuid": uuid,
+                "sessionId": session_id,
+                "timestamp": "2026-04-20T10:00:00Z",
+                "message": {"role": "user", "content": content}
+            })
+            .to_string()
+        });
+        let path = write_transcript(dir.path(), session_id, &lines);
+
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.frustrated_messages, vec!["msg-angry".to_string()]);
+
+        // Nothing new on a second pass
+        let result = index_session_file(&db, &path.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert!(result.frustrated_messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_indexing_invalidates_project_stats() {
+        use sea_orm::EntityTrait;
+
+        let db = test_db().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("projects").join("-tmp-stats-demo");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let write_session = |session_id: &str, day: u32| {
+            let line = serde_json::json!({
+                "type": "user",
+                "uuid": format!("{session_id}-msg"),
+                "sessionId": session_id,
+                "timestamp": format!("2026-04-{day:02}T09:00:00Z"),
+                "message": {"role": "user", "content": "hello"}
+            });
+            let path = write_transcript(&project_dir, &session_id, &[line]);
+            path.to_string_lossy().to_string()
+        };
+
+        let first = write_session("0b4e6d8f-2a1c-4e3b-9d5f-7c8a1b2e3f40", 1);
+        index_session_file(&db, &first, None).await.unwrap();
+        let session = crud::sessions::get(&db, "0b4e6d8f-2a1c-4e3b-9d5f-7c8a1b2e3f40")
+            .await
+            .unwrap()
+            .unwrap();
+        let project_id = session.project_id.expect("session should have a project");
+
+        let stats = crud::project_stats::get_or_refresh(&db, &project_id)
+            .await
+            .unwrap();
+        assert_eq!(stats.total_sessions, 1);
+        assert_eq!(stats.active_days, 1);
+
+        // A new session in the same project drops the cached row
+        let second = write_session("6f1a3c5e-7b9d-4f2a-8c4e-0d2f4a6b8c91", 3);
+        index_session_file(&db, &second, None).await.unwrap();
+        let cached = han_db::entities::project_stats_cache::Entity::find_by_id(project_id.as_str())
+            .one(&db)
+            .await
+            .unwrap();
+        assert!(cached.is_none());
+
+        let stats = crud::project_stats::get_or_refresh(&db, &project_id)
+            .await
+            .unwrap();
+        assert_eq!(stats.total_sessions, 2);
+        assert_eq!(stats.total_messages, 2);
+        assert_eq!(stats.active_days, 2);
+        assert_eq!(
+            stats.last_session_at.as_deref(),
+            Some("2026-04-03T09:00:00Z")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_index_all_sessions_parallel() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("han.db").to_string_lossy().to_string();
+        let db = han_db::connection::establish_connection(han_db::connection::DbConfig::Sqlite {
+            path: db_path,
+        })
+        .await
+        .unwrap();
+        han_db::migration::run_migrations(&db).await.unwrap();
+
+        // 20 sessions of 3 messages each, split across two projects
+        let projects = dir.path().join("projects");
+        let project_dirs = vec![projects.join("-tmp-alpha"), projects.join("-tmp-beta")];
+        let mut session_ids = Vec::new();
+        for i in 0..20 {
+            let session_id = format!("5e1f0c2a-7d3b-4c9e-8a6f-{i:012x}");
+            let project_dir = &project_dirs[i % 2];
+            std::fs::create_dir_all(project_dir).unwrap();
+            let lines: Vec<String> = (0..3)
+                .map(|n| {
+                    serde_json::json!({
+                        "type": "user",
+                        "uuid": format!("{session_id}-{n}"),
+                        "sessionId": session_id,
+                        "timestamp": format!("2026-04-22T10:{i:02}:{n:02}Z"),
+                        "message": {"role": "user", "content": format!("message {n}")}
+                    })
+                    .to_string()
+                })
+                .collect();
+            write_transcript(project_dir, &session_id, &lines);
+            session_ids.push(session_id);
+        }
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let collector = tokio::spawn(async move {
+            let mut updates = Vec::new();
+            while let Some(update) = rx.recv().await {
+                updates.push(update);
+            }
+            updates
+        });
+
+        let results = index_all_sessions_parallel(&db, project_dirs, 4, Some(tx))
+            .await
+            .unwrap();
+        let updates: Vec<IndexProgress> = collector.await.unwrap();
+
+        assert_eq!(results.len(), 20);
+        assert!(results.iter().all(|r| r.error.is_none()));
+        assert!(results.iter().all(|r| r.messages_indexed == 3));
+
+        for session_id in &session_ids {
+            let statuses: Vec<IndexStatus> = updates
+                .iter()
+                .filter(|u| &u.session_id == session_id)
+                .map(|u| u.status)
+                .collect();
+            let expected = [
+                IndexStatus::Pending,
+                IndexStatus::InProgress,
+                IndexStatus::Done,
+            ];
+            assert_eq!(statuses, expected);
+
+            let stored = crud::messages::get_count(&db, session_id).await.unwrap();
+            assert_eq!(stored, 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reindex_session_file() {
+        let db = test_db().await;
+
+        let session_id = "3c7e9a1b-5d2f-4b8a-9e6c-0f1a3b5c7d9e";
+        let dir = tempfile::tempdir().unwrap();
+        let write = |texts: [&str; 3]| {
+            let lines: Vec<String> = texts
+                .iter()
+                .enumerate()
+                .map(|(i, text)| {
+                    serde_json::json!({
+                        "type": "user",
+                        "uuid": format!("msg-reindex-{i}"),
+                        "sessionId": session_id,
+                        "timestamp": format!("2026-04-14T09:00:0{i}Z"),
+                        "message": {"role": "user", "content": text}
+                    })
+                    .to_string()
+                })
+                .collect();
+            write_transcript(dir.path(), session_id, &lines)
+        };
+
+        let file_path = write(["first", "second", "third"])
+            .to_string_lossy()
+            .to_string();
+        index_session_file(&db, &file_path, None).await.unwrap();
+
+        // Nothing changed: every stored message is skipped
+        let result = reindex_session_file(&db, &file_path, None, false)
+            .await
+            .unwrap();
+        assert_eq!(result.messages_skipped, 3);
+        assert_eq!(result.messages_indexed, 0);
+
+        // Editing the middle line keeps the first and re-indexes the rest
+        write(["first", "second, edited", "third"]);
+        let result = reindex_session_file(&db, &file_path, None, false)
+            .await
+            .unwrap();
+        assert!(result.error.is_none());
+        assert_eq!(result.messages_skipped, 1);
+        assert_eq!(result.messages_indexed, 2);
+        let edited = crud::messages::get(&db, "msg-reindex-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(edited.content.as_deref(), Some("second, edited"));
+        assert_eq!(crud::messages::get_count(&db, session_id).await.unwrap(), 3);
+
+        // Force re-indexes everything
+        let result = reindex_session_file(&db, &file_path, None, true)
+            .await
+            .unwrap();
+        assert_eq!(result.messages_skipped, 0);
+        assert_eq!(result.messages_indexed, 3);
+        assert_eq!(crud::messages::get_count(&db, session_id).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_index_session_file_recovers_from_bad_lines() {
+        let db = test_db().await;
+
+        let session_id = "8d2b4f6a-1c3e-4a5b-9d7f-2e4c6a8b0d1f";
+        let dir = tempfile::tempdir().unwrap();
+        let message = |i: usize| {
+            serde_json::json!({
+                "type": "user",
+                "uuid": format!("msg-recover-{i}"),
+                "sessionId": session_id,
+                "timestamp": format!("2026-04-19T09:00:0{i}Z"),
+                "message": {"role": "user", "content": "hello"}
+            })
+            .to_string()
+        };
+
+        // Line 1 is not JSON, line 2 is JSON but not a message
+        let lines = [
+            message(0),
+            "{not json".to_string(),
+            r#"{"foo":1}"#.to_string(),
+            message(3),
+        ];
+        let path = write_transcript(dir.path(), session_id, &lines);
+        let file_path = path.to_string_lossy().to_string();
+        let result = index_session_file(&db, &file_path, None).await.unwrap();
+        assert_eq!(result.messages_indexed, 2);
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.warning_count, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, 1);
+
+        let stored = crud::index_errors::list_by_session(&db, session_id)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].line_number, 1);
+
+        // Fixing the line and re-indexing clears the stored error
+        let lines = [
+            message(0),
+            message(1),
+            r#"{"foo":1}"#.to_string(),
+            message(3),
+        ];
+        write_transcript(dir.path(), session_id, &lines);
+        let result = reindex_session_file(&db, &file_path, None, true)
+            .await
+            .unwrap();
+        assert_eq!(result.messages_indexed, 3);
+        assert!(crud::index_errors::list_by_session(&db, session_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_archived_session_is_not_reindexed() {
+        let db = test_db().await;
+
+        let session_id = "3f1e5c7a-9b2d-4e6f-8a1c-5d7e9f0b2c4a";
+        let dir = tempfile::tempdir().unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "uuid": "msg-archived-0",
+            "sessionId": session_id,
+            "timestamp": "2020-01-01T09:00:00Z",
+            "message": {"role": "user", "content": "hello"}
+        });
+        let path = write_transcript(dir.path(), session_id, &[line]);
+        let file_path = path.to_string_lossy().to_string();
+        index_session_file(&db, &file_path, None).await.unwrap();
+        assert_eq!(
+            crud::archive_stale_sessions(&db, 30).await.unwrap(),
+            [session_id]
+        );
+
+        // The transcript is still on disk, but the session stays archived
+        let result = index_session_file(&db, &file_path, None).await.unwrap();
+        assert_eq!(result.messages_indexed, 0);
+        assert!(crud::sessions::get(&db, session_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(crud::archive_stale_sessions(&db, 30)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Once restored it is indexed and can be archived again
+        crud::restore_session(&db, session_id).await.unwrap();
+        index_session_file(&db, &file_path, None).await.unwrap();
+        assert_eq!(
+            crud::archive_stale_sessions(&db, 30).await.unwrap(),
+            [session_id]
+        );
+        assert!(crud::is_archived(&db, session_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sync_installed_plugins() {
+        let db = test_db().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(sync_installed_plugins(&db, dir.path()).await.unwrap(), 0);
+
+        let install_dir = dir.path().join("biome");
+        std::fs::create_dir_all(install_dir.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            install_dir.join(".claude-plugin").join("plugin.json"),
+            r#"{"name":"biome","description":"Biome lint hooks"}"#,
+        )
+        .unwrap();
+        let installed = serde_json::json!({
+            "version": 2,
+            "plugins": {
+                "biome@han": [{
+                    "scope": "user",
+                    "installPath": install_dir,
+                    "version": "1.2.0",
+                    "installedAt": "2026-04-01T00:00:00Z"
+                }],
+                "typescript@han": [{"scope": "project", "version": "0.3.1"}]
+            }
+        });
+        std::fs::create_dir_all(dir.path().join("plugins")).unwrap();
+        std::fs::write(
+            dir.path().join("plugins").join("installed_plugins.json"),
+            installed.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(sync_installed_plugins(&db, dir.path()).await.unwrap(), 2);
+        let plugins = crud::plugins::list(&db).await.unwrap();
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].name, "biome");
+        assert_eq!(plugins[0].version, "1.2.0");
+        assert_eq!(plugins[0].description.as_deref(), Some("Biome lint hooks"));
+        assert_eq!(plugins[0].installed_at, "2026-04-01T00:00:00Z");
+        assert_eq!(plugins[1].name, "typescript");
+        assert_eq!(plugins[1].description, None);
+    }
+
+    /// A message line that passes every sanity check.
+    fn clean_message() -> Value {
+        serde_json::json!({
+            "type": "user",
+            "uuid": Uuid::new_v4().to_string(),
+            "timestamp": "2026-04-19T09:00:00Z",
+            "message": {"role": "user", "content": "hello"}
+        })
+    }
+
+    fn warning_kinds(json: &Value, now: DateTime<Utc>) -> Vec<WarningKind> {
+        let line = JsonlLine {
+            line_number: 0,
+            byte_offset: 0,
+            content: json.to_string(),
+        };
+        let LineParse::Parsed(parsed) = parse_jsonl_line(&line) else {
+            panic!("not a message: {json}");
+        };
+        line_warnings(&parsed, now)
+            .into_iter()
+            .map(|w| w.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_line_warnings_ignore_clean_and_uuidless_types() {
+        let now = Utc::now();
+        assert!(warning_kinds(&clean_message(), now).is_empty());
+
+        // Summaries and API-displayed types legitimately have no uuid
+        let summary = serde_json::json!({"type": "summary", "leafUuid": "x", "summary": "s"});
+        assert!(warning_kinds(&summary, now).is_empty());
+        let queued = serde_json::json!({"type": "queue-operation", "operation": "enqueue"});
+        assert!(warning_kinds(&queued, now).is_empty());
+    }
+
+    proptest::proptest! {
+        // Oversized cases parse megabyte lines, so keep the run short
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_line_warnings_flag_each_condition(
+            kind in proptest::sample::select(vec![
+                WarningKind::MissingUuid,
+                WarningKind::InvalidUuid,
+                WarningKind::FutureTimestamp,
+                WarningKind::UnknownType,
+                WarningKind::OversizedContent,
+            ]),
+            bad_uuid in "[g-z][a-z0-9-]{0,40}",
+            hours_ahead in 2i64..100_000,
+            unknown_type in "x_[a-z_]{1,20}",
+            extra_bytes in 1usize..4096,
+        ) {
+            let now = Utc::now();
+            let mut json = clean_message();
+            match kind {
+                WarningKind::MissingUuid => {
+                    json.as_object_mut().unwrap().remove("uuid");
+                }
+                WarningKind::InvalidUuid => json["uuid"] = Value::from(bad_uuid),
+                WarningKind::FutureTimestamp => {
+                    let at = now + Duration::hours(hours_ahead);
+                    json["timestamp"] = Value::from(at.to_rfc3339());
+                }
+                WarningKind::UnknownType => json["type"] = Value::from(unknown_type),
+                WarningKind::OversizedContent => {
+                    let content = "a".repeat(MAX_CONTENT_BYTES + extra_bytes);
+                    json["message"]["content"] = Value::from(content);
+                }
+            }
+            proptest::prop_assert_eq!(warning_kinds(&json, now), vec![kind]);
+        }
+
+        #[test]
+        fn prop_line_warnings_allow_near_future_and_large_lines(
+            minutes_ahead in 0i64..60,
+            content_len in 0usize..4096,
+        ) {
+            let now = Utc::now();
+            let mut json = clean_message();
+            json["timestamp"] = Value::from((now + Duration::minutes(minutes_ahead)).to_rfc3339());
+            // Content under the limit, padded elsewhere so the line is over it
+            json["message"]["content"] = Value::from("a".repeat(content_len));
+            json["padding"] = Value::from("b".repeat(MAX_CONTENT_BYTES));
+            proptest::prop_assert!(warning_kinds(&json, now).is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_session_file_records_warnings() {
+        let db = test_db().await;
+
+        let session_id = "3f9a1c2e-5b7d-4e8f-a0b1-c2d3e4f5a6b7";
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut missing_uuid = clean_message();
+        missing_uuid.as_object_mut().unwrap().remove("uuid");
+        let mut unknown = clean_message();
+        unknown["type"] = Value::from("mystery");
+        unknown["timestamp"] = Value::from("2999-01-01T00:00:00Z");
+        let lines = [clean_message(), missing_uuid, unknown];
+        let path = write_transcript(dir.path(), session_id, &lines);
+        let file_path = path.to_string_lossy().to_string();
+
+        let result = index_session_file(&db, &file_path, None).await.unwrap();
+        // Flagged lines are still indexed
+        assert_eq!(result.messages_indexed, 3);
+        let flagged: Vec<(u32, WarningKind)> = result
+            .warnings
+            .iter()
+            .map(|(line, w)| (*line, w.kind))
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                (1, WarningKind::MissingUuid),
+                (2, WarningKind::UnknownType),
+                (2, WarningKind::FutureTimestamp),
+            ]
+        );
+
+        let stored = crud::index_warnings::list_by_session(&db, session_id)
+            .await
+            .unwrap();
+        let stored: Vec<(i32, &str)> = stored
+            .iter()
+            .map(|w| (w.line_number, w.warning_type.as_str()))
+            .collect();
+        assert_eq!(
+            stored,
+            [
+                (1, "missing_uuid"),
+                (2, "future_timestamp"),
+                (2, "unknown_type"),
+            ]
+        );
+
+        // Re-indexing fixed lines clears their warnings
+        let content: Vec<String> = (0..3).map(|_| clean_message().to_string()).collect();
+        write_transcript(dir.path(), session_id, &content);
+        reindex_session_file(&db, &file_path, None, true)
+            .await
+            .unwrap();
+        assert!(crud::index_warnings::list_by_session(&db, session_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_project_discovers_agent_sessions() {
+        let db = test_db().await;
+
+        let parent_id = "1c9e4b2a-6d8f-4a3c-b5e7-9f1d2c4a6b8e";
+        let child_id = "7e2a5c9b-3f1d-4b6e-a8c2-5d9f1e3b7a4c";
+        let root = tempfile::tempdir().unwrap();
+        let project_dir = root.path().join("projects").join("-tmp-agent-demo");
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        let parent_lines = [
+            serde_json::json!({
+                "type": "assistant",
+                "uuid": "parent-task-call",
+                "sessionId": parent_id,
+                "timestamp": "2026-04-20T09:00:00Z",
+                "message": {
+                    "role": "assistant",
+                    "content": [{
+                        "type": "tool_use",
+                        "id": "toolu_task_1",
+                        "name": "Task",
+                        "input": {"description": "Explore", "prompt": "Find the parser"}
+                    }]
+                }
+            }),
+            serde_json::json!({
+                "type": "progress",
+                "uuid": "parent-task-progress",
+                "sessionId": parent_id,
+                "timestamp": "2026-04-20T09:00:01Z",
+                "data": {"type": "agent_progress", "agent_task_id": child_id}
+            }),
+        ];
+        let child_line = serde_json::json!({
+            "type": "user",
+            "uuid": "child-prompt",
+            "sessionId": child_id,
+            "timestamp": "2026-04-20T09:00:02Z",
+            "message": {"role": "user", "content": "Find the parser"}
+        });
+        write_transcript(&project_dir, parent_id, &parent_lines);
+        write_transcript(&project_dir, child_id, &[child_line]);
+
+        let results = index_project_directory(&db, &project_dir.to_string_lossy(), None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let link = crud::agent_sessions::get_parent(&db, child_id)
+            .await
+            .unwrap()
+            .expect("sub-agent session should be linked to its parent");
+        assert_eq!(link.parent_session_id, parent_id);
+        assert_eq!(link.spawned_at, "2026-04-20T09:00:01Z");
+
+        let child_msg = crud::messages::get(&db, "child-prompt")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(child_msg.source_file_type.as_deref(), Some("agent_session"));
+        let parent_msg = crud::messages::get(&db, "parent-task-call")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(parent_msg.source_file_type.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_extract_agent_task_id() {
+        let raw =
+            r#"{"type":"progress","data":{"type":"agent_progress","agent_task_id":"7e2a5c9b"}}"#;
+        assert_eq!(extract_agent_task_id(raw), Some("7e2a5c9b".to_string()));
+        let raw = r#"{"type":"progress","data":{"type":"hook_progress"}}"#;
+        assert_eq!(extract_agent_task_id(raw), None);
+        let raw = r#"{"type":"user","message":{"content":"agent_task_id"}}"#;
+        assert_eq!(extract_agent_task_id(raw), None);
+    }
+
     #[test]
     fn test_parse_han_event_line() {
         let line = JsonlLine {
@@ -39,7 +39,7 @@ impl SentimentLevel {
 }
 
 /// Frustration level categorization.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FrustrationLevel {
     Low,
@@ -55,6 +55,16 @@ impl FrustrationLevel {
             FrustrationLevel::High => "high",
         }
     }
+
+    /// Parse a level stored by [`FrustrationLevel::as_str`].
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "low" => Some(FrustrationLevel::Low),
+            "moderate" => Some(FrustrationLevel::Moderate),
+            "high" => Some(FrustrationLevel::High),
+            _ => None,
+        }
+    }
 }
 
 /// Result of sentiment analysis on a message.
@@ -227,6 +237,122 @@ pub fn analyze_sentiment(message: &str) -> Option<SentimentResult> {
     })
 }
 
+/// Drop in frustration score after the peak that counts as recovery.
+pub const RECOVERY_DROP: f64 = 0.3;
+
+/// Frustration of one analyzed user message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrustrationPoint {
+    pub timestamp: String,
+    /// 0 when no frustration was detected.
+    pub frustration_score: f64,
+    pub frustration_level: Option<FrustrationLevel>,
+}
+
+/// How frustration evolved over the course of a session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrustrationTrend {
+    /// Least-squares slope of frustration score per hour; positive means
+    /// the user got more frustrated as the session went on.
+    pub slope: f64,
+    /// Highest per-message frustration score.
+    pub peak_frustration_score: f64,
+    /// Timestamp of the first message that reached the peak.
+    pub peak_timestamp: Option<String>,
+    /// Frustration fell by at least [`RECOVERY_DROP`] after the peak.
+    pub recovery_detected: bool,
+    /// Highest per-message frustration level, `None` if no message showed
+    /// frustration.
+    pub session_level: Option<FrustrationLevel>,
+}
+
+/// Compute the trend of `points` in chronological order.
+///
+/// The slope is fitted against hours since the first point. If timestamps
+/// are missing or identical, message order is used instead.
+pub fn frustration_trend(points: &[FrustrationPoint]) -> FrustrationTrend {
+    let Some((peak_idx, peak_score)) = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.frustration_score))
+        .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+            Some((_, b)) if b >= score => best,
+            _ => Some((i, score)),
+        })
+    else {
+        return FrustrationTrend::default();
+    };
+
+    let recovery_detected = points[peak_idx + 1..]
+        .iter()
+        .any(|p| peak_score - p.frustration_score >= RECOVERY_DROP);
+
+    let times: Option<Vec<f64>> = points
+        .iter()
+        .map(|p| {
+            chrono::DateTime::parse_from_rfc3339(&p.timestamp)
+                .ok()
+                .map(|t| t.timestamp_millis() as f64 / 3_600_000.0)
+        })
+        .collect();
+    let xs: Vec<f64> = match times {
+        Some(t) if t.iter().any(|x| *x != t[0]) => t.iter().map(|x| x - t[0]).collect(),
+        _ => (0..points.len()).map(|i| i as f64).collect(),
+    };
+    let ys: Vec<f64> = points.iter().map(|p| p.frustration_score).collect();
+
+    FrustrationTrend {
+        slope: least_squares_slope(&xs, &ys),
+        peak_frustration_score: peak_score,
+        peak_timestamp: Some(points[peak_idx].timestamp.clone()),
+        recovery_detected,
+        session_level: points.iter().filter_map(|p| p.frustration_level).max(),
+    }
+}
+
+fn least_squares_slope(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+    if n < 2.0 {
+        return 0.0;
+    }
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (cov, var) = xs.iter().zip(ys).fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x).powi(2),
+        )
+    });
+    if var == 0.0 {
+        0.0
+    } else {
+        cov / var
+    }
+}
+
+/// Analyze the frustration trend of `(timestamp, message)` pairs in
+/// chronological order.
+///
+/// Each message is scored with [`analyze_sentiment`]; messages without
+/// detected frustration count as 0.
+pub fn analyze_frustration_trend(messages: &[(String, &str)]) -> FrustrationTrend {
+    let points: Vec<FrustrationPoint> = messages
+        .iter()
+        .map(|(timestamp, text)| {
+            let result = analyze_sentiment(text);
+            FrustrationPoint {
+                timestamp: timestamp.clone(),
+                frustration_score: result
+                    .as_ref()
+                    .and_then(|r| r.frustration_score)
+                    .unwrap_or(0.0),
+                frustration_level: result.and_then(|r| r.frustration_level),
+            }
+        })
+        .collect();
+    frustration_trend(&points)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -348,4 +474,112 @@ mod tests {
         );
         assert!(result.frustration_level.is_some());
     }
+
+    fn timestamps(n: usize) -> Vec<String> {
+        (0..n)
+            .map(|i| format!("2026-01-01T{:02}:00:00Z", i))
+            .collect()
+    }
+
+    fn points(scores: &[f64]) -> Vec<FrustrationPoint> {
+        timestamps(scores.len())
+            .into_iter()
+            .zip(scores)
+            .map(|(timestamp, &frustration_score)| FrustrationPoint {
+                timestamp,
+                frustration_score,
+                frustration_level: None,
+            })
+            .collect()
+    }
+
+    // Messages ordered from calm to increasingly frustrated.
+    const ESCALATING: &[&str] = &[
+        "Thanks, that looks great!",
+        "ok",
+        "This is wrong.",
+        "This is wrong, stop!!",
+        "STOP. THIS IS TERRIBLE AND BROKEN!! forget it",
+    ];
+
+    #[test]
+    fn test_frustration_trend_fits_hours() {
+        let mut points = points(&[0.0, 6.5, 2.0]);
+        // Two hours apart: (0, 0) (2, 6.5) (4, 2) -> slope 0.5
+        for (point, hour) in points.iter_mut().zip(["10", "12", "14"]) {
+            point.timestamp = format!("2026-04-14T{hour}:00:00Z");
+        }
+        let trend = frustration_trend(&points);
+        assert!((trend.slope - 0.5).abs() < 1e-9);
+        assert_eq!(trend.peak_frustration_score, 6.5);
+        assert_eq!(
+            trend.peak_timestamp.as_deref(),
+            Some("2026-04-14T12:00:00Z")
+        );
+        assert!(trend.recovery_detected);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_frustration_trend_rising(
+            mut scores in proptest::collection::vec(0.0f64..10.0, 1..24),
+        ) {
+            scores.sort_by(f64::total_cmp);
+            let trend = frustration_trend(&points(&scores));
+            let (first, last) = (scores[0], scores[scores.len() - 1]);
+            proptest::prop_assert!(trend.slope >= -1e-9, "slope {}", trend.slope);
+            if last - first > 1e-6 {
+                proptest::prop_assert!(trend.slope > 0.0, "slope {}", trend.slope);
+            }
+            proptest::prop_assert!(!trend.recovery_detected);
+            proptest::prop_assert_eq!(trend.peak_frustration_score, last);
+        }
+
+        #[test]
+        fn prop_frustration_trend_falling(
+            mut scores in proptest::collection::vec(0.0f64..10.0, 1..24),
+        ) {
+            scores.sort_by(|a, b| b.total_cmp(a));
+            let trend = frustration_trend(&points(&scores));
+            let (first, last) = (scores[0], scores[scores.len() - 1]);
+            proptest::prop_assert!(trend.slope <= 1e-9, "slope {}", trend.slope);
+            proptest::prop_assert_eq!(trend.peak_timestamp, Some(timestamps(1).remove(0)));
+            proptest::prop_assert_eq!(trend.recovery_detected, first - last >= RECOVERY_DROP);
+        }
+
+        #[test]
+        fn prop_frustration_trend_session_level_is_highest(
+            levels in proptest::collection::vec(
+                proptest::option::of(proptest::sample::select(vec![
+                    FrustrationLevel::Low,
+                    FrustrationLevel::Moderate,
+                    FrustrationLevel::High,
+                ])),
+                0..12,
+            ),
+        ) {
+            let mut points = points(&vec![1.0; levels.len()]);
+            for (point, level) in points.iter_mut().zip(&levels) {
+                point.frustration_level = *level;
+            }
+            let trend = frustration_trend(&points);
+            proptest::prop_assert_eq!(trend.session_level, levels.iter().flatten().max().copied());
+        }
+    }
+
+    #[test]
+    fn test_frustration_trend_session_level() {
+        let ts = timestamps(ESCALATING.len());
+        let messages: Vec<(String, &str)> =
+            ts.iter().cloned().zip(ESCALATING.iter().copied()).collect();
+        let trend = analyze_frustration_trend(&messages);
+        assert_eq!(trend.session_level, Some(FrustrationLevel::High));
+        assert!(trend.slope > 0.0);
+        assert!(!trend.recovery_detected);
+
+        let empty = analyze_frustration_trend(&[]);
+        assert_eq!(empty.session_level, None);
+        assert_eq!(empty.slope, 0.0);
+        assert!(empty.peak_timestamp.is_none());
+    }
 }
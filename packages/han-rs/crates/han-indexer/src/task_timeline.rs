@@ -2,59 +2,185 @@
 //!
 //! Queries the tasks table to find which task was active at a given timestamp.
 //! Used during indexing to associate sentiment events with tasks.
+//!
+//! Lookups binary-search the start times, then walk back only as far as a
+//! still-running task could reach, so they stay O(log n) unless many tasks
+//! overlap the queried time.
+//!
+//! Per-session timelines are cached as JSON in the `session_timelines` table
+//! so large sessions are not rebuilt from `tasks` on every load.
 
 use chrono::{DateTime, Utc};
+use han_db::crud::session_timelines;
+use han_db::error::DbResult;
 use sea_orm::*;
+use serde::{Deserialize, Serialize};
 
 /// A task's time range (start to end).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TaskTimeRange {
     pub task_id: String,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
 }
 
+impl TaskTimeRange {
+    fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.start_time <= *timestamp && self.end_time.is_none_or(|end| end >= *timestamp)
+    }
+}
+
 /// Timeline of tasks for lookups.
 #[derive(Debug, Default)]
 pub struct TaskTimeline {
     /// All task time ranges, sorted by start time.
     tasks: Vec<TaskTimeRange>,
+    /// `reach[i]` is the latest end time among `tasks[..=i]`, or `None` if
+    /// any of them is still running. Bounds how far back a lookup scans.
+    reach: Vec<Option<DateTime<Utc>>>,
 }
 
 impl TaskTimeline {
     /// Create a new empty timeline.
     pub fn new() -> Self {
-        Self { tasks: Vec::new() }
+        Self::default()
+    }
+
+    /// Load the timeline for one session, from the cache when present.
+    ///
+    /// A missing or unreadable cache entry falls back to [`Self::from_tasks`].
+    pub async fn from_db(db: &DatabaseConnection, session_id: &str) -> Result<Self, DbErr> {
+        if let Ok(Some(json)) = session_timelines::get_task_timeline(db, session_id).await {
+            if let Ok(timeline) = Self::from_json(&json) {
+                return Ok(timeline);
+            }
+        }
+        Self::from_tasks(db, session_id).await
+    }
+
+    /// Build the timeline for one session from the tasks table in a single
+    /// query, bypassing the cache.
+    pub async fn from_tasks(db: &DatabaseConnection, session_id: &str) -> Result<Self, DbErr> {
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                "SELECT task_id, started_at, completed_at FROM tasks \
+                 WHERE session_id = $1 ORDER BY started_at ASC",
+                [session_id.into()],
+            ))
+            .await?;
+        Ok(Self::from_rows(rows))
+    }
+
+    /// Cache this timeline as the one for `session_id`.
+    pub async fn store(&self, db: &DatabaseConnection, session_id: &str) -> DbResult<()> {
+        session_timelines::store_task_timeline(db, session_id, self.to_json()).await
     }
 
-    /// Add a task time range. Caller is responsible for maintaining sorted order.
+    /// Serialize the task ranges as a JSON array.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.tasks).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Rebuild a timeline from [`Self::to_json`] output.
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        let mut tasks: Vec<TaskTimeRange> = serde_json::from_str(s)?;
+        // `push` requires start order; a stable sort keeps ties as stored
+        tasks.sort_by_key(|t| t.start_time);
+        let mut timeline = Self::new();
+        for task in tasks {
+            timeline.push(task);
+        }
+        Ok(timeline)
+    }
+
+    /// Build a timeline from `task_id, started_at, completed_at` rows ordered
+    /// by `started_at`. Rows with a missing or unparseable start are skipped.
+    fn from_rows(rows: Vec<QueryResult>) -> Self {
+        let mut timeline = Self::new();
+        for row in rows {
+            let task_id: String = match row.try_get("", "task_id") {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            let started_at: String = match row.try_get("", "started_at") {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            let completed_at: Option<String> = row.try_get("", "completed_at").ok();
+
+            let start_time = match DateTime::parse_from_rfc3339(&started_at) {
+                Ok(ts) => ts.with_timezone(&Utc),
+                Err(_) => continue,
+            };
+
+            let end_time = completed_at.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|ts| ts.with_timezone(&Utc))
+            });
+
+            timeline.push(TaskTimeRange {
+                task_id,
+                start_time,
+                end_time,
+            });
+        }
+        timeline
+    }
+
+    /// Add a task time range. Ranges must be pushed in start time order.
     pub fn push(&mut self, range: TaskTimeRange) {
+        debug_assert!(
+            self.tasks
+                .last()
+                .is_none_or(|last| last.start_time <= range.start_time),
+            "task ranges must be pushed in start time order"
+        );
+        let reach = match (self.reach.last(), range.end_time) {
+            // A running task keeps everything before it reachable
+            (Some(None), _) | (_, None) => None,
+            (Some(Some(prev)), Some(end)) => Some((*prev).max(end)),
+            (None, Some(end)) => Some(end),
+        };
         self.tasks.push(range);
+        self.reach.push(reach);
     }
 
     /// Find the active task at a given timestamp.
     /// Returns the task_id if a task was in progress at that time.
-    /// Uses reverse iteration so the most recently started task wins.
+    /// When tasks overlap, the most recently started one wins.
     pub fn find_active_task(&self, timestamp: &DateTime<Utc>) -> Option<&str> {
-        for task in self.tasks.iter().rev() {
-            if task.start_time <= *timestamp {
-                match &task.end_time {
-                    None => return Some(&task.task_id),
-                    Some(end) if end >= timestamp => return Some(&task.task_id),
-                    _ => continue,
-                }
-            }
-        }
-        None
+        self.candidates(*timestamp)
+            .find(|task| task.contains(timestamp))
+            .map(|task| task.task_id.as_str())
+    }
+
+    /// All tasks in progress at a given timestamp, most recently started first.
+    pub fn find_active_tasks(&self, timestamp: &DateTime<Utc>) -> Vec<&str> {
+        self.candidates(*timestamp)
+            .filter(|task| task.contains(timestamp))
+            .map(|task| task.task_id.as_str())
+            .collect()
+    }
+
+    /// Tasks started at or before `timestamp`, newest first, stopping once no
+    /// earlier task can still be running.
+    fn candidates(&self, timestamp: DateTime<Utc>) -> impl Iterator<Item = &TaskTimeRange> {
+        let started = self.tasks.partition_point(|t| t.start_time <= timestamp);
+        self.tasks[..started]
+            .iter()
+            .zip(&self.reach[..started])
+            .rev()
+            .take_while(move |(_, reach)| reach.is_none_or(|end| end >= timestamp))
+            .map(|(task, _)| task)
     }
 }
 
 /// Build a task timeline from the database.
 pub async fn build_task_timeline(db: &DatabaseConnection) -> TaskTimeline {
-    let mut timeline = TaskTimeline::new();
-
     // Query all tasks ordered by started_at ASC
-    let rows = match db
+    match db
         .query_all(Statement::from_string(
             db.get_database_backend(),
             "SELECT task_id, started_at, completed_at FROM tasks ORDER BY started_at ASC"
@@ -62,40 +188,9 @@ pub async fn build_task_timeline(db: &DatabaseConnection) -> TaskTimeline {
         ))
         .await
     {
-        Ok(rows) => rows,
-        Err(_) => return timeline,
-    };
-
-    for row in rows {
-        let task_id: String = match row.try_get("", "task_id") {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let started_at: String = match row.try_get("", "started_at") {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let completed_at: Option<String> = row.try_get("", "completed_at").ok();
-
-        let start_time = match DateTime::parse_from_rfc3339(&started_at) {
-            Ok(ts) => ts.with_timezone(&Utc),
-            Err(_) => continue,
-        };
-
-        let end_time = completed_at.and_then(|s| {
-            DateTime::parse_from_rfc3339(&s)
-                .ok()
-                .map(|ts| ts.with_timezone(&Utc))
-        });
-
-        timeline.push(TaskTimeRange {
-            task_id,
-            start_time,
-            end_time,
-        });
+        Ok(rows) => TaskTimeline::from_rows(rows),
+        Err(_) => TaskTimeline::new(),
     }
-
-    timeline
 }
 
 #[cfg(test)]
@@ -490,4 +585,163 @@ mod tests {
             Some("task-99")
         );
     }
+
+    #[test]
+    fn test_find_active_task_long_running_spans_later_tasks() {
+        let mut timeline = TaskTimeline::new();
+        timeline.push(TaskTimeRange {
+            task_id: "epic".to_string(),
+            start_time: parse_time("2024-01-01T09:00:00Z"),
+            end_time: Some(parse_time("2024-01-01T18:00:00Z")),
+        });
+        for i in 0..5u32 {
+            timeline.push(TaskTimeRange {
+                task_id: format!("step-{i}"),
+                start_time: parse_time(&format!("2024-01-01T1{i}:00:00Z")),
+                end_time: Some(parse_time(&format!("2024-01-01T1{i}:30:00Z"))),
+            });
+        }
+
+        // Between steps, only the long-running task is active
+        let query = parse_time("2024-01-01T12:45:00Z");
+        assert_eq!(timeline.find_active_task(&query), Some("epic"));
+        assert_eq!(timeline.find_active_tasks(&query), vec!["epic"]);
+
+        // During a step, both are active and the step started last
+        let query = parse_time("2024-01-01T12:15:00Z");
+        assert_eq!(timeline.find_active_task(&query), Some("step-2"));
+        assert_eq!(timeline.find_active_tasks(&query), vec!["step-2", "epic"]);
+
+        assert!(timeline
+            .find_active_tasks(&parse_time("2024-01-01T18:30:00Z"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_json_round_trip_preserves_ranges() {
+        let mut timeline = TaskTimeline::new();
+        timeline.push(TaskTimeRange {
+            task_id: "task-1".to_string(),
+            start_time: parse_time("2024-01-01T10:00:00.123456789Z"),
+            end_time: Some(parse_time("2024-01-01T10:30:00Z")),
+        });
+        timeline.push(TaskTimeRange {
+            task_id: "task-2".to_string(),
+            start_time: parse_time("2024-01-01T10:00:00.123456789Z"),
+            end_time: None,
+        });
+        timeline.push(TaskTimeRange {
+            task_id: "task-3".to_string(),
+            start_time: parse_time("2024-01-01T12:05:00+02:00"),
+            end_time: Some(parse_time("2024-01-01T13:00:00Z")),
+        });
+
+        let restored = TaskTimeline::from_json(&timeline.to_json()).unwrap();
+        assert_eq!(restored.tasks, timeline.tasks);
+        assert_eq!(restored.reach, timeline.reach);
+        let query = parse_time("2024-01-01T10:15:00Z");
+        assert_eq!(
+            restored.find_active_tasks(&query),
+            vec!["task-3", "task-2", "task-1"]
+        );
+
+        assert!(TaskTimeline::from_json("[]").unwrap().tasks.is_empty());
+        assert!(TaskTimeline::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_from_json_sorts_by_start_time() {
+        let json = r#"[
+            {"task_id": "late", "start_time": "2024-01-01T11:00:00Z", "end_time": null},
+            {"task_id": "early", "start_time": "2024-01-01T10:00:00Z", "end_time": "2024-01-01T10:30:00Z"}
+        ]"#;
+        let timeline = TaskTimeline::from_json(json).unwrap();
+        assert_eq!(timeline.tasks[0].task_id, "early");
+        assert_eq!(
+            timeline.find_active_task(&parse_time("2024-01-01T10:15:00Z")),
+            Some("early")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_db_prefers_cached_timeline() {
+        let db = crate::test_db().await;
+
+        // No tasks rows: only the cache can produce this range
+        let mut cached = TaskTimeline::new();
+        cached.push(TaskTimeRange {
+            task_id: "cached".to_string(),
+            start_time: parse_time("2024-01-01T10:00:00Z"),
+            end_time: None,
+        });
+        cached.store(&db, "session-a").await.unwrap();
+
+        let timeline = TaskTimeline::from_db(&db, "session-a").await.unwrap();
+        assert_eq!(timeline.tasks, cached.tasks);
+        let rebuilt = TaskTimeline::from_tasks(&db, "session-a").await.unwrap();
+        assert!(rebuilt.tasks.is_empty());
+
+        // A corrupt entry falls back to the tasks table
+        han_db::crud::session_timelines::store_task_timeline(&db, "session-a", "{".to_string())
+            .await
+            .unwrap();
+        let timeline = TaskTimeline::from_db(&db, "session-a").await.unwrap();
+        assert!(timeline.tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_db_loads_session_tasks() {
+        let db = crate::test_db().await;
+        for session_id in ["session-a", "session-b"] {
+            han_db::crud::sessions::upsert(
+                &db,
+                session_id.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        for (session_id, task_id, started_at, completed_at) in [
+            ("session-a", "task-a1", "10:00", Some("10:30")),
+            ("session-a", "task-a2", "11:00", None),
+            ("session-b", "task-b1", "10:00", None),
+        ] {
+            let started_at = format!("2024-01-01T{started_at}:00Z");
+            let completed_at = completed_at.map(|t| format!("2024-01-01T{t}:00Z"));
+            db.execute(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                "INSERT INTO tasks (id, session_id, task_id, description, task_type, started_at, completed_at) \
+                 VALUES ($1, $2, $3, 'test', 'implementation', $4, $5)",
+                [
+                    task_id.into(),
+                    session_id.into(),
+                    task_id.into(),
+                    started_at.into(),
+                    completed_at.into(),
+                ],
+            ))
+            .await
+            .unwrap();
+        }
+
+        let timeline = TaskTimeline::from_db(&db, "session-a").await.unwrap();
+        assert_eq!(timeline.tasks.len(), 2);
+        assert_eq!(
+            timeline.find_active_task(&parse_time("2024-01-01T10:15:00Z")),
+            Some("task-a1")
+        );
+        assert_eq!(
+            timeline.find_active_task(&parse_time("2024-01-01T10:45:00Z")),
+            None
+        );
+        assert_eq!(
+            timeline.find_active_task(&parse_time("2024-01-02T00:00:00Z")),
+            Some("task-a2")
+        );
+    }
 }
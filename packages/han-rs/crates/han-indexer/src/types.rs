@@ -1,6 +1,7 @@
 //! Shared types used across the indexer crate.
 
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Result of indexing a single JSONL file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +16,85 @@ pub struct IndexResult {
     pub is_new_session: bool,
     /// Any error message encountered during indexing.
     pub error: Option<String>,
+    /// Lines that failed to parse as JSON, as `(line_number, error)`.
+    pub errors: Vec<(u32, String)>,
+    /// Number of lines that failed to parse as JSON.
+    pub error_count: u32,
+    /// Number of lines that parsed but were not recognizable messages.
+    pub warning_count: u32,
+    /// Messages that failed a sanity check, as `(line_number, warning)`. A
+    /// line appears once per kind of warning it triggered.
+    pub warnings: Vec<(u32, LineWarning)>,
+    /// IDs of messages indexed in this pass with high frustration, in file
+    /// order.
+    pub frustrated_messages: Vec<String>,
+}
+
+/// Sanity check failed by a message line that otherwise indexed fine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WarningKind {
+    MissingUuid,
+    InvalidUuid,
+    /// Timestamp more than an hour ahead of the indexer's clock.
+    FutureTimestamp,
+    /// `type` the indexer and API don't know how to display.
+    UnknownType,
+    /// Message content larger than 1 MiB.
+    OversizedContent,
+}
+
+impl WarningKind {
+    /// Value stored in `session_index_warnings.warning_type`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarningKind::MissingUuid => "missing_uuid",
+            WarningKind::InvalidUuid => "invalid_uuid",
+            WarningKind::FutureTimestamp => "future_timestamp",
+            WarningKind::UnknownType => "unknown_type",
+            WarningKind::OversizedContent => "oversized_content",
+        }
+    }
+}
+
+/// A warning raised for one line during indexing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineWarning {
+    pub kind: WarningKind,
+    pub detail: String,
+}
+
+/// Result of re-indexing a session transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexResult {
+    /// Session ID that was re-indexed.
+    pub session_id: String,
+    /// Number of messages written by the re-index.
+    pub messages_indexed: u32,
+    /// Stored messages before the first changed line, left as they were.
+    pub messages_skipped: u32,
+    /// Any error message encountered during re-indexing.
+    pub error: Option<String>,
+}
+
+/// Stage of a file in a parallel index run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexStatus {
+    /// Queued, waiting for a free slot.
+    Pending,
+    InProgress,
+    Done,
+    Error,
+}
+
+/// Progress update for one file during a parallel index run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexProgress {
+    /// Session ID, or agent ID for agent transcripts.
+    pub session_id: String,
+    pub file_path: String,
+    pub status: IndexStatus,
+    /// New messages indexed; only set once the file is done.
+    pub messages_indexed: u32,
 }
 
 /// Claude Code JSONL message types.
@@ -71,12 +151,76 @@ pub enum SessionFileType {
     Main,
     /// Agent sub-transcript: `agent-{id}.jsonl`
     Agent,
+    /// Sub-agent session launched by the Task tool: a `{uuid}.jsonl` whose
+    /// session ID a parent transcript reports as `data.agent_task_id`
+    AgentSession,
     /// Han events: `{date}-{uuid}-han.jsonl`
     HanEvents,
     /// Unrecognized file format
     Unknown,
 }
 
+impl SessionFileType {
+    /// Classify a file by its name alone.
+    ///
+    /// Both `/` and `\\` count as separators, so Windows paths from a
+    /// synced machine classify the same. The path is not resolved, so a file
+    /// reached through a symlinked directory classifies by the link's name.
+    /// [`SessionFileType::AgentSession`] is never returned: telling it apart
+    /// from `Main` needs the parent link recorded in the database.
+    pub fn detect_from_path(path: &Path) -> SessionFileType {
+        let Some(stem) = session_file_stem(path) else {
+            return SessionFileType::Unknown;
+        };
+
+        if let Some(agent_id) = stem.strip_prefix("agent-") {
+            if !agent_id.is_empty() && agent_id.len() <= 16 {
+                return SessionFileType::Agent;
+            }
+        }
+
+        if let Some(session_id) = stem.strip_suffix("-han") {
+            if is_valid_uuid(session_id) || is_valid_cli_session_id(session_id) {
+                return SessionFileType::HanEvents;
+            }
+        }
+
+        if is_valid_uuid(stem.strip_suffix("_messages").unwrap_or(stem)) {
+            return SessionFileType::Main;
+        }
+
+        SessionFileType::Unknown
+    }
+
+    /// Value stored in `messages.source_file_type`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionFileType::Main => "main",
+            SessionFileType::Agent => "agent",
+            SessionFileType::AgentSession => "agent_session",
+            SessionFileType::HanEvents => "han_events",
+            SessionFileType::Unknown => "unknown",
+        }
+    }
+}
+
+/// File name of `path` without its extension, splitting on `/` and `\\`.
+pub(crate) fn session_file_stem(path: &Path) -> Option<&str> {
+    let name = path.to_str()?.rsplit(['/', '\\']).next()?;
+    match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => Some(stem),
+        _ => Some(name).filter(|n| !n.is_empty()),
+    }
+}
+
+pub(crate) fn is_valid_uuid(s: &str) -> bool {
+    s.len() >= 32 && s.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+pub(crate) fn is_valid_cli_session_id(s: &str) -> bool {
+    s.strip_prefix("cli-").is_some_and(is_valid_uuid)
+}
+
 /// File event types from the watcher.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FileEventType {
@@ -114,6 +258,7 @@ pub struct ParsedMessage {
 #[derive(Debug, Clone)]
 pub struct IntermediateParsedLine {
     pub line_number: i32,
+    pub byte_offset: i64,
     pub json: serde_json::Value,
     pub raw_content: String,
     pub message_type: MessageType,
@@ -133,6 +278,7 @@ pub struct ParsedHanEvent {
     pub agent_id: Option<String>,
     pub data: serde_json::Value,
     pub raw_json: String,
+    pub byte_offset: i64,
 }
 
 #[cfg(test)]
@@ -166,4 +312,115 @@ mod tests {
         assert_eq!(MessageType::from_str("garbage"), MessageType::Unknown);
         assert_eq!(MessageType::from_str(""), MessageType::Unknown);
     }
+
+    #[test]
+    fn test_is_valid_uuid() {
+        assert!(is_valid_uuid("abc12345-1234-5678-9abc-def012345678"));
+        assert!(!is_valid_uuid("short"));
+    }
+
+    #[test]
+    fn test_is_valid_cli_session_id() {
+        assert!(is_valid_cli_session_id(
+            "cli-abc12345-1234-5678-9abc-def012345678"
+        ));
+        assert!(!is_valid_cli_session_id(
+            "abc12345-1234-5678-9abc-def012345678"
+        ));
+        assert!(!is_valid_cli_session_id("cli-short"));
+    }
+
+    const UUID: &str = "abc12345-1234-5678-9abc-def012345678";
+
+    fn detect(path: &str) -> SessionFileType {
+        SessionFileType::detect_from_path(Path::new(path))
+    }
+
+    #[test]
+    fn test_detect_from_path() {
+        let dir = "/home/user/.claude/projects/-home-user-app";
+        assert_eq!(
+            detect(&format!("{dir}/{UUID}.jsonl")),
+            SessionFileType::Main
+        );
+        assert_eq!(
+            detect(&format!("{dir}/{UUID}_messages.jsonl")),
+            SessionFileType::Main
+        );
+        assert_eq!(
+            detect(&format!("{dir}/agent-a1b2c3.jsonl")),
+            SessionFileType::Agent
+        );
+        assert_eq!(
+            detect(&format!("{dir}/{UUID}-han.jsonl")),
+            SessionFileType::HanEvents
+        );
+        assert_eq!(
+            detect(&format!("{dir}/cli-{UUID}-han.jsonl")),
+            SessionFileType::HanEvents
+        );
+        assert_eq!(
+            detect(&format!("{dir}/short.jsonl")),
+            SessionFileType::Unknown
+        );
+        assert_eq!(
+            detect(&format!("{dir}/agent-.jsonl")),
+            SessionFileType::Unknown
+        );
+        assert_eq!(
+            detect(&format!("{dir}/agent-0123456789abcdef0.jsonl")),
+            SessionFileType::Unknown
+        );
+    }
+
+    #[test]
+    fn test_detect_from_path_edge_cases() {
+        // No parent directory
+        assert_eq!(detect(&format!("{UUID}.jsonl")), SessionFileType::Main);
+        assert_eq!(detect("agent-a1b2c3"), SessionFileType::Agent);
+        assert_eq!(detect(""), SessionFileType::Unknown);
+        assert_eq!(detect("/"), SessionFileType::Unknown);
+
+        // UUIDs are case-insensitive hex
+        assert_eq!(
+            detect("/p/ABC12345-1234-5678-9ABC-DEF012345678.jsonl"),
+            SessionFileType::Main
+        );
+        assert_eq!(
+            detect("/p/AbC12345-1234-5678-9aBc-dEf012345678-han.jsonl"),
+            SessionFileType::HanEvents
+        );
+
+        // Windows separators, including a dot in a directory name
+        assert_eq!(
+            detect(&format!(r"C:\Users\me\.claude\projects\app\{UUID}.jsonl")),
+            SessionFileType::Main
+        );
+        assert_eq!(
+            detect(r"C:\Users\me\.claude\projects\app\agent-a1b2c3.jsonl"),
+            SessionFileType::Agent
+        );
+        assert_eq!(
+            detect(r"C:\Users\me\.claude\projects\app.v2\notes"),
+            SessionFileType::Unknown
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detect_from_path_through_symlinked_dir() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let real = tmp.path().join("real");
+        std::fs::create_dir(&real).unwrap();
+        std::fs::write(real.join(format!("{UUID}.jsonl")), "").unwrap();
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let via_link = link.join(format!("{UUID}.jsonl"));
+        assert!(via_link.exists());
+        assert_eq!(
+            SessionFileType::detect_from_path(&via_link),
+            SessionFileType::Main
+        );
+    }
 }
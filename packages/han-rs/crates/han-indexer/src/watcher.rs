@@ -1,6 +1,8 @@
 //! File watcher module for monitoring Claude Code session JSONL files.
 //!
 //! Monitors `~/.claude/projects/` for JSONL file changes using the `notify` crate.
+//! Changes to other files are filtered out by [`IgnorePatterns`], and bursts
+//! of modifications to one file are debounced into a single event.
 //! Unlike the NAPI version, this uses tokio channels and owned structs instead
 //! of global statics and ThreadsafeFunction callbacks.
 
@@ -9,12 +11,13 @@ use notify::{
     event::{CreateKind, ModifyKind, RemoveKind},
     Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
 
 #[derive(Error, Debug)]
 pub enum WatcherError {
@@ -52,15 +55,7 @@ pub fn extract_session_id(path: &Path) -> Option<String> {
     } else {
         filename
     };
-    if session_id.len() >= 32
-        && session_id
-            .chars()
-            .all(|c| c.is_ascii_hexdigit() || c == '-')
-    {
-        Some(session_id.to_string())
-    } else {
-        None
-    }
+    is_session_id(session_id).then(|| session_id.to_string())
 }
 
 /// Extract project slug from the directory structure.
@@ -81,8 +76,85 @@ pub fn extract_project_path(path: &Path) -> Option<String> {
     None
 }
 
-/// Convert a notify event to our FileEvent type.
-fn convert_event(event: &Event) -> Option<FileEvent> {
+/// Files the watcher never reports unless an include pattern matches them:
+/// hidden files (`.DS_Store`, editor swap files), swap and temp files, and
+/// anything under `node_modules`.
+const DEFAULT_IGNORE_PATTERNS: &[&str] = &[".*", "*.swp", "*.tmp", "*~", "*/node_modules/*"];
+
+/// How long modifications to one file are collected before a single
+/// `Modified` event is sent for them.
+const MODIFY_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Glob patterns deciding which changed files the watcher reports.
+///
+/// A pattern matches a file if it matches either the file name or the whole
+/// path. Files matching `include` are always reported; otherwise files
+/// matching `patterns` are skipped, and the rest are reported only if they
+/// look like session data (see [`is_session_file`]).
+#[derive(Debug, Clone)]
+pub struct IgnorePatterns {
+    pub patterns: Vec<glob::Pattern>,
+    pub include: Vec<glob::Pattern>,
+}
+
+impl Default for IgnorePatterns {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_IGNORE_PATTERNS
+                .iter()
+                .map(|p| glob::Pattern::new(p).expect("default ignore pattern is valid"))
+                .collect(),
+            include: Vec::new(),
+        }
+    }
+}
+
+impl IgnorePatterns {
+    /// The default ignore patterns plus `exclude`, with `include` overriding
+    /// both.
+    pub fn new(include: Vec<glob::Pattern>, exclude: Vec<glob::Pattern>) -> Self {
+        let mut ignore = Self::default();
+        ignore.patterns.extend(exclude);
+        ignore.include = include;
+        ignore
+    }
+
+    /// Whether a change to `path` should be reported.
+    pub fn should_watch(&self, path: &Path) -> bool {
+        if matches_any(&self.include, path) {
+            return true;
+        }
+        !matches_any(&self.patterns, path) && is_session_file(path)
+    }
+}
+
+fn matches_any(patterns: &[glob::Pattern], path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str());
+    patterns
+        .iter()
+        .any(|p| p.matches_path(path) || name.is_some_and(|n| p.matches(n)))
+}
+
+/// Whether `path` looks like session data: a `.jsonl` transcript, its
+/// `.jsonl.metrics` sidecar, or a file in a directory named after a session.
+pub fn is_session_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.ends_with(".jsonl") || name.ends_with(".jsonl.metrics") {
+        return true;
+    }
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|n| n.to_str())
+        .is_some_and(is_session_id)
+}
+
+fn is_session_id(s: &str) -> bool {
+    s.len() >= 32 && s.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Convert a notify event to our FileEvent type, dropping files `ignore`
+/// filters out.
+fn convert_event(event: &Event, ignore: &IgnorePatterns) -> Option<FileEvent> {
     let event_type = match &event.kind {
         EventKind::Create(CreateKind::File) => FileEventType::Created,
         EventKind::Modify(ModifyKind::Data(_)) | EventKind::Modify(ModifyKind::Any) => {
@@ -94,7 +166,7 @@ fn convert_event(event: &Event) -> Option<FileEvent> {
 
     let path = event.paths.first()?;
 
-    if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+    if !ignore.should_watch(path) {
         return None;
     }
 
@@ -109,22 +181,232 @@ fn convert_event(event: &Event) -> Option<FileEvent> {
     })
 }
 
+/// Collapses bursts of `Modified` events for a file into one.
+///
+/// The first modification of a file opens a window of `window`; later
+/// modifications within it are absorbed, and one event is released when the
+/// window closes. Creates and removes pass straight through, and a remove
+/// drops any modification still pending for that file.
+struct Debouncer {
+    window: Duration,
+    pending: HashMap<String, (FileEvent, Instant)>,
+}
+
+impl Debouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Take in an event, returning it if it should be sent right away.
+    fn push(&mut self, event: FileEvent, now: Instant) -> Option<FileEvent> {
+        match event.event_type {
+            FileEventType::Modified => {
+                let due = now + self.window;
+                self.pending
+                    .entry(event.path.clone())
+                    .or_insert((event, due));
+                None
+            }
+            FileEventType::Removed => {
+                self.pending.remove(&event.path);
+                Some(event)
+            }
+            FileEventType::Created => Some(event),
+        }
+    }
+
+    /// Remove and return the events whose window has closed by `now`.
+    fn take_due(&mut self, now: Instant) -> Vec<FileEvent> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, at))| *at <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+        due.into_iter()
+            .filter_map(|path| self.pending.remove(&path))
+            .map(|(event, _)| event)
+            .collect()
+    }
+
+    /// When the next pending event is due, if any are pending.
+    fn next_due(&self) -> Option<Instant> {
+        self.pending.values().map(|(_, at)| *at).min()
+    }
+}
+
+/// Directories being watched, keyed by config directory (or by the path
+/// itself for directories added with [`WatcherHandle::add_path`]).
+struct WatchSet {
+    watcher: Mutex<RecommendedWatcher>,
+    paths: Mutex<HashMap<String, PathBuf>>,
+}
+
+impl WatchSet {
+    fn paths(&self) -> MutexGuard<'_, HashMap<String, PathBuf>> {
+        lock(&self.paths)
+    }
+
+    /// Start watching `path` under `key`. Returns false if either is already
+    /// in the set.
+    fn add(&self, key: String, path: PathBuf) -> WatcherResult<bool> {
+        let mut paths = self.paths();
+        if paths.contains_key(&key) || paths.values().any(|p| *p == path) {
+            return Ok(false);
+        }
+        lock(&self.watcher).watch(&path, RecursiveMode::Recursive)?;
+        paths.insert(key, path);
+        Ok(true)
+    }
+
+    /// Stop watching the path registered under `key`.
+    fn remove_key(&self, key: &str) -> WatcherResult<bool> {
+        let path = match self.paths().remove(key) {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+        self.unwatch(&path)?;
+        Ok(true)
+    }
+
+    /// Stop watching `path`, whichever key it was registered under.
+    fn remove_path(&self, path: &Path) -> WatcherResult<bool> {
+        let mut paths = self.paths();
+        let before = paths.len();
+        paths.retain(|_, p| p != path);
+        if paths.len() == before {
+            return Ok(false);
+        }
+        self.unwatch(path)?;
+        Ok(true)
+    }
+
+    fn unwatch(&self, path: &Path) -> WatcherResult<()> {
+        lock(&self.watcher).unwatch(path)?;
+        Ok(())
+    }
+}
+
+/// Lock a mutex, recovering the data if a previous holder panicked.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Watch-set change handled by the watcher's command thread.
+enum WatcherCommand {
+    Watch {
+        path: PathBuf,
+        reply: oneshot::Sender<WatcherResult<bool>>,
+    },
+    Unwatch {
+        path: PathBuf,
+        reply: oneshot::Sender<WatcherResult<bool>>,
+    },
+}
+
+/// Apply watch-set changes until every sender is dropped.
+fn run_commands(
+    mut command_rx: mpsc::Receiver<WatcherCommand>,
+    watch_set: Arc<WatchSet>,
+    running: Arc<AtomicBool>,
+) {
+    while let Some(command) = command_rx.blocking_recv() {
+        let is_running = running.load(Ordering::Relaxed);
+        match command {
+            WatcherCommand::Watch { path, reply } => {
+                let result = if is_running {
+                    watch_set.add(path.to_string_lossy().to_string(), path)
+                } else {
+                    Err(WatcherError::NotRunning)
+                };
+                let _ = reply.send(result);
+            }
+            WatcherCommand::Unwatch { path, reply } => {
+                let result = if is_running {
+                    watch_set.remove_path(&path)
+                } else {
+                    Err(WatcherError::NotRunning)
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+/// Cloneable handle for changing a running watcher's watch set.
+///
+/// Lets other tasks add and remove directories while the watcher itself
+/// is owned by the loop consuming its events.
+#[derive(Clone)]
+pub struct WatcherHandle {
+    command_tx: mpsc::Sender<WatcherCommand>,
+    watch_set: Arc<WatchSet>,
+}
+
+impl WatcherHandle {
+    /// Start watching a directory recursively. Returns false if it is
+    /// already watched.
+    pub async fn add_path(&self, path: PathBuf) -> WatcherResult<bool> {
+        self.send(|reply| WatcherCommand::Watch { path, reply })
+            .await
+    }
+
+    /// Stop watching a directory. Returns false if it wasn't watched.
+    pub async fn remove_path(&self, path: &Path) -> WatcherResult<bool> {
+        let path = path.to_path_buf();
+        self.send(|reply| WatcherCommand::Unwatch { path, reply })
+            .await
+    }
+
+    /// Get all currently watched paths, sorted.
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.watch_set.paths().values().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    async fn send(
+        &self,
+        command: impl FnOnce(oneshot::Sender<WatcherResult<bool>>) -> WatcherCommand,
+    ) -> WatcherResult<bool> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.command_tx
+            .send(command(reply_tx))
+            .await
+            .map_err(|_| WatcherError::NotRunning)?;
+        reply_rx.await.map_err(|_| WatcherError::NotRunning)?
+    }
+}
+
 /// File system watcher service.
 ///
 /// Owns a `notify::RecommendedWatcher` and exposes an async `next_event()` method.
 /// No global statics or NAPI callbacks.
 pub struct WatcherService {
-    watcher: RecommendedWatcher,
-    event_rx: tokio::sync::mpsc::Receiver<FileEvent>,
+    handle: WatcherHandle,
+    event_rx: mpsc::Receiver<FileEvent>,
     running: Arc<AtomicBool>,
     _thread: Option<std::thread::JoinHandle<()>>,
-    watched_paths: HashMap<String, String>,
 }
 
 impl WatcherService {
     /// Create a new watcher service.
     /// Defaults to `~/.claude/projects` if no path is given.
     pub fn new(watch_path: Option<PathBuf>) -> WatcherResult<Self> {
+        Self::with_ignore_patterns(watch_path, IgnorePatterns::default())
+    }
+
+    /// Create a new watcher service that reports only files `ignore` lets
+    /// through.
+    pub fn with_ignore_patterns(
+        watch_path: Option<PathBuf>,
+        ignore: IgnorePatterns,
+    ) -> WatcherResult<Self> {
         let path = if let Some(p) = watch_path {
             p
         } else {
@@ -140,7 +422,7 @@ impl WatcherService {
         let running_clone = running.clone();
 
         let (notify_tx, notify_rx) = std::sync::mpsc::channel::<Event>();
-        let (event_tx, event_rx) = tokio::sync::mpsc::channel::<FileEvent>(1024);
+        let (event_tx, event_rx) = mpsc::channel::<FileEvent>(1024);
 
         let mut watcher = RecommendedWatcher::new(
             move |res: notify::Result<Event>| {
@@ -154,31 +436,29 @@ impl WatcherService {
         watcher.watch(&path, RecursiveMode::Recursive)?;
 
         let thread = std::thread::spawn(move || {
-            let mut seen_paths: HashSet<String> = HashSet::new();
-            let debounce_duration = Duration::from_millis(100);
-            let mut last_event_time = std::time::Instant::now();
+            let mut debouncer = Debouncer::new(MODIFY_DEBOUNCE);
 
             while running_clone.load(Ordering::Relaxed) {
-                match notify_rx.recv_timeout(Duration::from_secs(1)) {
+                let timeout = debouncer
+                    .next_due()
+                    .map(|due| due.saturating_duration_since(Instant::now()))
+                    .unwrap_or(Duration::from_secs(1));
+                match notify_rx.recv_timeout(timeout) {
                     Ok(event) => {
-                        if let Some(file_event) = convert_event(&event) {
-                            let now = std::time::Instant::now();
-                            if now.duration_since(last_event_time) > debounce_duration
-                                || !seen_paths.contains(&file_event.path)
-                            {
-                                seen_paths.insert(file_event.path.clone());
-                                last_event_time = now;
-                                let _ = event_tx.try_send(file_event);
+                        if let Some(file_event) = convert_event(&event, &ignore) {
+                            if let Some(ready) = debouncer.push(file_event, Instant::now()) {
+                                let _ = event_tx.try_send(ready);
                             }
                         }
                     }
-                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                        seen_paths.clear();
-                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
                     Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
                         break;
                     }
                 }
+                for ready in debouncer.take_due(Instant::now()) {
+                    let _ = event_tx.try_send(ready);
+                }
             }
         });
 
@@ -187,14 +467,26 @@ impl WatcherService {
             .parent()
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| path.to_string_lossy().to_string());
-        watched_paths.insert(config_dir, path.to_string_lossy().to_string());
+        watched_paths.insert(config_dir, path);
+
+        let watch_set = Arc::new(WatchSet {
+            watcher: Mutex::new(watcher),
+            paths: Mutex::new(watched_paths),
+        });
+
+        let (command_tx, command_rx) = mpsc::channel::<WatcherCommand>(16);
+        let command_watch_set = watch_set.clone();
+        let command_running = running.clone();
+        std::thread::spawn(move || run_commands(command_rx, command_watch_set, command_running));
 
         Ok(Self {
-            watcher,
+            handle: WatcherHandle {
+                command_tx,
+                watch_set,
+            },
             event_rx,
             running,
             _thread: Some(thread),
-            watched_paths,
         })
     }
 
@@ -210,7 +502,7 @@ impl WatcherService {
             PathBuf::from(config_dir).join("projects")
         };
 
-        if self.watched_paths.contains_key(config_dir) {
+        if self.handle.watch_set.paths().contains_key(config_dir) {
             return Ok(false);
         }
 
@@ -218,22 +510,28 @@ impl WatcherService {
             std::fs::create_dir_all(&path)?;
         }
 
-        self.watcher.watch(&path, RecursiveMode::Recursive)?;
-        self.watched_paths
-            .insert(config_dir.to_string(), path.to_string_lossy().to_string());
-        Ok(true)
+        self.handle.watch_set.add(config_dir.to_string(), path)
     }
 
     /// Remove a watch path.
     pub fn remove_watch_path(&mut self, config_dir: &str) -> WatcherResult<bool> {
-        let path_str = match self.watched_paths.remove(config_dir) {
-            Some(p) => p,
-            None => return Ok(false),
-        };
+        self.handle.watch_set.remove_key(config_dir)
+    }
 
-        let path = PathBuf::from(&path_str);
-        self.watcher.unwatch(&path)?;
-        Ok(true)
+    /// Start watching a directory recursively. Returns false if it is
+    /// already watched.
+    pub async fn add_path(&self, path: PathBuf) -> WatcherResult<bool> {
+        self.handle.add_path(path).await
+    }
+
+    /// Stop watching a directory. Returns false if it wasn't watched.
+    pub async fn remove_path(&self, path: &Path) -> WatcherResult<bool> {
+        self.handle.remove_path(path).await
+    }
+
+    /// Handle for changing the watch set from other tasks.
+    pub fn handle(&self) -> WatcherHandle {
+        self.handle.clone()
     }
 
     /// Receive the next file event (async). Returns None if the watcher is stopped.
@@ -241,9 +539,9 @@ impl WatcherService {
         self.event_rx.recv().await
     }
 
-    /// Get all currently watched paths.
-    pub fn watched_paths(&self) -> Vec<String> {
-        self.watched_paths.values().cloned().collect()
+    /// Get all currently watched paths, sorted.
+    pub fn watched_paths(&self) -> Vec<PathBuf> {
+        self.handle.watched_paths()
     }
 
     /// Stop the watcher.
@@ -315,4 +613,150 @@ mod tests {
         let path = Path::new("/home/user/random/session.jsonl");
         assert_eq!(extract_project_path(path), None);
     }
+
+    #[test]
+    fn test_ignore_patterns_default() {
+        let ignore = IgnorePatterns::default();
+        let dir = Path::new("/home/user/.claude/projects/-src-han");
+        let session = "abc12345-1234-5678-9abc-def012345678";
+
+        assert!(ignore.should_watch(&dir.join(format!("{session}.jsonl"))));
+        assert!(ignore.should_watch(&dir.join(format!("{session}.jsonl.metrics"))));
+        assert!(ignore.should_watch(&dir.join(session).join("tool-output.txt")));
+
+        assert!(!ignore.should_watch(&dir.join(format!("{session}.jsonl.swp"))));
+        assert!(!ignore.should_watch(&dir.join(format!(".{session}.jsonl.swp"))));
+        assert!(!ignore.should_watch(&dir.join("index.js")));
+        assert!(!ignore.should_watch(&dir.join(".DS_Store")));
+        assert!(!ignore.should_watch(&dir.join(session).join(".DS_Store")));
+        assert!(!ignore.should_watch(&dir.join("node_modules/pkg/log.jsonl")));
+    }
+
+    #[test]
+    fn test_ignore_patterns_overrides() {
+        let pattern = |p: &str| glob::Pattern::new(p).unwrap();
+        let ignore = IgnorePatterns::new(vec![pattern("*.ndjson")], vec![pattern("*/scratch/*")]);
+        let dir = Path::new("/home/user/.claude/projects/-src-han");
+
+        assert!(ignore.should_watch(&dir.join("export.ndjson")));
+        assert!(!ignore.should_watch(&dir.join("scratch/notes.jsonl")));
+        assert!(ignore.should_watch(&dir.join("session.jsonl")));
+        assert!(!ignore.should_watch(&dir.join(".DS_Store")));
+    }
+
+    fn file_event(event_type: FileEventType, path: &str) -> FileEvent {
+        FileEvent {
+            event_type,
+            path: path.to_string(),
+            session_id: None,
+            project_path: None,
+        }
+    }
+
+    #[test]
+    fn test_debouncer_collapses_modifies() {
+        let window = Duration::from_millis(500);
+        let mut debouncer = Debouncer::new(window);
+        let start = Instant::now();
+
+        assert!(debouncer
+            .push(file_event(FileEventType::Modified, "a.jsonl"), start)
+            .is_none());
+        let later = start + Duration::from_millis(300);
+        assert!(debouncer
+            .push(file_event(FileEventType::Modified, "a.jsonl"), later)
+            .is_none());
+        debouncer.push(file_event(FileEventType::Modified, "b.jsonl"), later);
+        assert_eq!(debouncer.next_due(), Some(start + window));
+        assert!(debouncer.take_due(later).is_empty());
+
+        let due = debouncer.take_due(start + window);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].path, "a.jsonl");
+        assert_eq!(debouncer.next_due(), Some(later + window));
+
+        // A modify after the window closes opens a new one
+        let next = start + window + Duration::from_millis(1);
+        assert!(debouncer
+            .push(file_event(FileEventType::Modified, "a.jsonl"), next)
+            .is_none());
+        assert_eq!(debouncer.take_due(next + window).len(), 2);
+    }
+
+    #[test]
+    fn test_debouncer_passes_creates_and_removes() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(500));
+        let now = Instant::now();
+
+        assert!(debouncer
+            .push(file_event(FileEventType::Created, "a.jsonl"), now)
+            .is_some());
+        debouncer.push(file_event(FileEventType::Modified, "a.jsonl"), now);
+        let removed = debouncer.push(file_event(FileEventType::Removed, "a.jsonl"), now);
+        assert!(matches!(
+            removed.map(|e| e.event_type),
+            Some(FileEventType::Removed)
+        ));
+        assert_eq!(debouncer.next_due(), None);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_ignores_noise() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut watcher = WatcherService::new(Some(dir.path().to_path_buf())).unwrap();
+
+        for noise in [
+            "index.js",
+            ".DS_Store",
+            "abc12345-1234-5678-9abc-def012345678.jsonl.swp",
+        ] {
+            std::fs::write(dir.path().join(noise), "x").unwrap();
+        }
+        let file = dir
+            .path()
+            .join("abc12345-1234-5678-9abc-def012345678.jsonl");
+        std::fs::write(&file, "{}\n").unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), watcher.next_event())
+            .await
+            .expect("no event for the transcript")
+            .unwrap();
+        assert_eq!(event.path, file.to_string_lossy());
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, watcher.next_event()).await {
+            assert_eq!(event.path, file.to_string_lossy());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_path() {
+        let initial = tempfile::TempDir::new().unwrap();
+        let extra = tempfile::TempDir::new().unwrap();
+        let extra_path = extra.path().to_path_buf();
+        let mut watcher = WatcherService::new(Some(initial.path().to_path_buf())).unwrap();
+
+        assert!(watcher.add_path(extra_path.clone()).await.unwrap());
+        assert!(!watcher.add_path(extra_path.clone()).await.unwrap());
+        assert!(watcher.watched_paths().contains(&extra_path));
+
+        let file = extra_path.join("abc12345-1234-5678-9abc-def012345678.jsonl");
+        std::fs::write(&file, "{}\n").unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(5), watcher.next_event())
+            .await
+            .expect("no event from the added path")
+            .unwrap();
+        assert_eq!(event.path, file.to_string_lossy());
+
+        assert!(watcher.remove_path(&extra_path).await.unwrap());
+        assert!(!watcher.remove_path(&extra_path).await.unwrap());
+        assert!(!watcher.watched_paths().contains(&extra_path));
+
+        // Files created after the path is removed produce no events
+        let ignored = extra_path.join("def12345-1234-5678-9abc-def012345678.jsonl");
+        std::fs::write(&ignored, "{}\n").unwrap();
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, watcher.next_event()).await {
+            assert_ne!(event.path, ignored.to_string_lossy());
+        }
+    }
 }
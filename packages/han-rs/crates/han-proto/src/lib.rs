@@ -8,3 +8,8 @@ pub mod coordinator {
 }
 
 pub use coordinator::*;
+
+/// Encoded `FileDescriptorSet` for `coordinator.proto`, for gRPC server
+/// reflection.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    tonic::include_file_descriptor_set!("coordinator_descriptor");
@@ -6,8 +6,9 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use han_db::crud::subscriptions::{upsert_subscription, StripeSubscriptionData};
 use hmac::{Hmac, Mac};
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
 use sha2::Sha256;
 use serde_json::json;
 use tracing::{error, info, warn};
@@ -182,6 +183,8 @@ async fn handle_subscription_change(
         .map_err(|e| format!("DB error: {e}"))?
         .ok_or_else(|| format!("No user found for Stripe customer {customer_id}"))?;
 
+    record_subscription(db, &user, object, subscription_data(object)?).await?;
+
     // Update subscription info
     let mut active: users::ActiveModel = user.into();
     active.subscription_id = Set(Some(subscription_id.to_string()));
@@ -215,6 +218,14 @@ async fn handle_subscription_deleted(
         .map_err(|e| format!("DB error: {e}"))?
         .ok_or_else(|| format!("No user found for Stripe customer {customer_id}"))?;
 
+    if object["id"].is_string() {
+        let data = StripeSubscriptionData {
+            status: "canceled".to_string(),
+            ..subscription_data(object)?
+        };
+        record_subscription(db, &user, object, data).await?;
+    }
+
     let mut active: users::ActiveModel = user.into();
     active.subscription_id = Set(None);
     active.subscription_status = Set(Some("canceled".to_string()));
@@ -227,6 +238,76 @@ async fn handle_subscription_deleted(
     Ok(())
 }
 
+/// Extract the stored subscription fields from a Stripe subscription object.
+///
+/// The plan is the first item's price lookup key (or price id), and the seat
+/// count is its quantity. Stripe timestamps are Unix seconds and are stored
+/// as RFC 3339.
+fn subscription_data(object: &serde_json::Value) -> Result<StripeSubscriptionData, String> {
+    let item = &object["items"]["data"][0];
+    let timestamp = |value: &serde_json::Value| {
+        value
+            .as_i64()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|t| t.to_rfc3339())
+    };
+
+    Ok(StripeSubscriptionData {
+        stripe_subscription_id: object["id"]
+            .as_str()
+            .ok_or("missing subscription ID")?
+            .to_string(),
+        plan: item["price"]["lookup_key"]
+            .as_str()
+            .or_else(|| item["price"]["id"].as_str())
+            .or_else(|| object["plan"]["id"].as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        status: object["status"].as_str().unwrap_or("canceled").to_string(),
+        seats: item["quantity"]
+            .as_i64()
+            .or_else(|| object["quantity"].as_i64())
+            .unwrap_or(1) as i32,
+        current_period_end: timestamp(&object["current_period_end"])
+            .or_else(|| timestamp(&item["current_period_end"])),
+        cancel_at: timestamp(&object["cancel_at"]),
+    })
+}
+
+/// Store the subscription against its team.
+///
+/// The team comes from the subscription's `team_id` metadata, falling back
+/// to the oldest team owned by the paying user. Subscriptions without a team
+/// are only recorded on the user.
+async fn record_subscription(
+    db: &DatabaseConnection,
+    user: &han_db::entities::users::Model,
+    object: &serde_json::Value,
+    data: StripeSubscriptionData,
+) -> Result<(), String> {
+    use han_db::entities::teams;
+
+    let org_id = match object["metadata"]["team_id"].as_str() {
+        Some(team_id) => Some(team_id.to_string()),
+        None => teams::Entity::find()
+            .filter(teams::Column::OwnerId.eq(&user.id))
+            .order_by_asc(teams::Column::CreatedAt)
+            .one(db)
+            .await
+            .map_err(|e| format!("DB error: {e}"))?
+            .map(|team| team.id),
+    };
+    let Some(org_id) = org_id else {
+        warn!(user_id = %user.id, "No team for subscription, skipping");
+        return Ok(());
+    };
+
+    upsert_subscription(db, &org_id, data)
+        .await
+        .map_err(|e| format!("Failed to store subscription: {e}"))?;
+    Ok(())
+}
+
 /// Handle invoice payment failed event (log only).
 fn handle_payment_failed(object: &serde_json::Value) {
     let customer_id = object["customer"].as_str().unwrap_or("unknown");
@@ -284,6 +365,37 @@ mod tests {
         assert!(!constant_time_eq("abc", "ab"));
     }
 
+    #[test]
+    fn test_subscription_data() {
+        let object = json!({
+            "id": "sub_123",
+            "customer": "cus_123",
+            "status": "active",
+            "current_period_end": 1_777_680_000,
+            "cancel_at": null,
+            "items": {"data": [{"quantity": 5, "price": {"id": "price_1", "lookup_key": "pro"}}]}
+        });
+        let data = subscription_data(&object).unwrap();
+        assert_eq!(data.stripe_subscription_id, "sub_123");
+        assert_eq!(data.plan, "pro");
+        assert_eq!(data.status, "active");
+        assert_eq!(data.seats, 5);
+        assert_eq!(
+            data.current_period_end.as_deref(),
+            Some("2026-05-02T00:00:00+00:00")
+        );
+        assert!(data.cancel_at.is_none());
+
+        // The same event parses to the same row, so replays are idempotent
+        assert_eq!(subscription_data(&object).unwrap(), data);
+
+        let bare = json!({"id": "sub_456", "plan": {"id": "plan_basic"}});
+        let data = subscription_data(&bare).unwrap();
+        assert_eq!(data.plan, "plan_basic");
+        assert_eq!(data.seats, 1);
+        assert!(subscription_data(&json!({"status": "active"})).is_err());
+    }
+
     #[test]
     fn test_map_stripe_status_in_webhook() {
         // Test the mapping function used in webhook processing
@@ -11,7 +11,7 @@ use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use serde_json::json;
 
-use han_api::context::GraphQLContext;
+use han_api::context::{GraphQLContext, UserContext, UserRole};
 
 use crate::auth::middleware::AuthUser;
 use crate::billing::stripe::webhook_handler;
@@ -47,11 +47,22 @@ async fn health_handler() -> impl IntoResponse {
 /// GraphQL query/mutation handler with optional auth.
 async fn graphql_handler(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
     req: GraphQLRequest,
 ) -> GraphQLResponse {
-    // Extract auth from the request headers would need manual handling here.
-    // For simplicity, create context without user - auth is added via GraphQL directives.
-    let ctx = GraphQLContext::new(state.db.clone(), state.event_sender.clone());
+    // Unauthenticated requests still reach the schema; guarded fields reject them.
+    let mut ctx = GraphQLContext::new(state.db.clone(), state.event_sender.clone());
+    if let Some(auth_user) = extract_auth(&state, &headers).await {
+        match user_context(&state.db, auth_user).await {
+            Ok(user) => ctx = ctx.with_user(user),
+            Err(e) => {
+                // Falling back to a default role would demote admins to ICs
+                tracing::error!("Failed to load user context: {e}");
+                let error = async_graphql::ServerError::new("Failed to load user", None);
+                return async_graphql::Response::from_errors(vec![error]).into();
+            }
+        }
+    }
     let request = req.into_inner().data(ctx);
     state.schema.execute(request).await.into()
 }
@@ -269,6 +280,51 @@ async fn extract_auth(state: &AppState, headers: &axum::http::HeaderMap) -> Opti
     None
 }
 
+/// Build the GraphQL user for an authenticated request.
+///
+/// The team owner and members with the `admin` role are admins, `manager`
+/// and `lead` members are managers, and everyone else is an IC. Lookup
+/// failures are returned rather than treated as a missing user or team.
+async fn user_context(
+    db: &sea_orm::DatabaseConnection,
+    auth_user: AuthUser,
+) -> Result<UserContext, sea_orm::DbErr> {
+    use han_db::entities::{team_members, teams, users};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    let display_name = users::Entity::find_by_id(&auth_user.user_id)
+        .one(db)
+        .await?
+        .and_then(|u| u.display_name);
+
+    let mut role = UserRole::Ic;
+    if let Some(team_id) = &auth_user.team_id {
+        let owner = teams::Entity::find_by_id(team_id).one(db).await?;
+        let member = team_members::Entity::find()
+            .filter(team_members::Column::TeamId.eq(team_id))
+            .filter(team_members::Column::UserId.eq(&auth_user.user_id))
+            .one(db)
+            .await?;
+        role = if owner.is_some_and(|t| t.owner_id == auth_user.user_id) {
+            UserRole::Admin
+        } else {
+            match member.as_ref().map(|m| m.role.as_str()) {
+                Some("admin") => UserRole::Admin,
+                Some("manager" | "lead") => UserRole::Manager,
+                _ => UserRole::Ic,
+            }
+        };
+    }
+
+    Ok(UserContext {
+        id: auth_user.user_id,
+        display_name,
+        role,
+        org_id: auth_user.team_id,
+        project_ids: None,
+    })
+}
+
 /// Upsert a user from GitHub OAuth data.
 async fn upsert_user(
     db: &sea_orm::DatabaseConnection,
@@ -366,4 +422,20 @@ mod tests {
 
         assert_eq!(json["version"].as_str().unwrap(), env!("CARGO_PKG_VERSION"));
     }
+
+    #[tokio::test]
+    async fn test_user_context_propagates_db_errors() {
+        let auth_user = AuthUser {
+            user_id: "user-1".to_string(),
+            team_id: Some("team-1".to_string()),
+            auth_method: crate::auth::middleware::AuthMethod::Jwt,
+        };
+        // Nothing listens on port 1, so every query fails
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_secs(1))
+            .connect_lazy("postgres://han@127.0.0.1:1/han")
+            .unwrap();
+        let db = sea_orm::SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+        assert!(user_context(&db, auth_user).await.is_err());
+    }
 }
@@ -10,6 +10,7 @@
  *   han coordinator restart  - Restart the coordinator daemon
  *   han coordinator status   - Check coordinator status
  *   han coordinator logs     - View coordinator logs
+ *   han coordinator version  - Show coordinator build info
  */
 
 import { spawn } from 'node:child_process';
@@ -178,6 +179,41 @@ export function registerCoordinatorCommands(program: Command): void {
       }
     });
 
+  // coordinator version
+  coordinator
+    .command('version')
+    .description('Show build info of the running coordinator')
+    .option('-p, --port <port>', `Port number (default: ${defaultPort})`)
+    .action(async (options: { port?: string }) => {
+      try {
+        const port = options.port
+          ? parseInt(options.port, 10)
+          : getCoordinatorPort();
+        const { createCoordinatorClients } = await import(
+          '../../grpc/client.ts'
+        );
+        const clients = createCoordinatorClients(port);
+        const info = await clients.coordinator.getVersion(
+          {},
+          { timeoutMs: 2000 }
+        );
+
+        console.log(`Coordinator: ${info.version}`);
+        console.log(`  Git commit: ${info.gitCommit}`);
+        console.log(`  Build date: ${info.buildDate}`);
+        console.log(`  Rust: ${info.rustVersion}`);
+        console.log(
+          `  Features: ${info.features.length > 0 ? info.features.join(', ') : 'none'}`
+        );
+      } catch (error: unknown) {
+        console.error(
+          'Error getting coordinator version:',
+          error instanceof Error ? error.message : error
+        );
+        process.exit(1);
+      }
+    });
+
   // coordinator ensure
   coordinator
     .command('ensure')
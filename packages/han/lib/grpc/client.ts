@@ -111,6 +111,10 @@ export type {
   ScanResponse,
   SessionData,
   SessionResponse,
+  SlotEvent,
   SlotInfo,
+  SlotListResponse,
+  SlotResponse,
   StatusResponse,
+  VersionResponse,
 } from './generated/coordinator_pb.js';
@@ -20,7 +20,7 @@ import {
 export const file_coordinator: GenFile =
   /*@__PURE__*/
   fileDesc(
-    'ChFjb29yZGluYXRvci5wcm90bxIPaGFuLmNvb3JkaW5hdG9yIgcKBUVtcHR5IqcBCg5TdGF0dXNSZXNwb25zZRIPCgd2ZXJzaW9uGAEgASgJEhYKDnVwdGltZV9zZWNvbmRzGAIgASgJEg8KB2RiX3BhdGgYAyABKAkSFQoNc2Vzc2lvbl9jb3VudBgEIAEoAxIVCg1tZXNzYWdlX2NvdW50GAUgASgDEhYKDndhdGNoZXJfYWN0aXZlGAYgASgIEhUKDXdhdGNoZWRfcGF0aHMYByADKAkiRQoOSGVhbHRoUmVzcG9uc2USDwoHaGVhbHRoeRgBIAEoCBIPCgd2ZXJzaW9uGAIgASgJEhEKCXVwdGltZV9tcxgDIAEoAyI8Cg9TaHV0ZG93blJlcXVlc3QSEAoIZ3JhY2VmdWwYASABKAgSFwoPdGltZW91dF9zZWNvbmRzGAIgASgFIi8KF0dldEFjdGl2ZVNlc3Npb25SZXF1ZXN0EhQKDHByb2plY3RfcGF0aBgBIAEoCSInChFHZXRTZXNzaW9uUmVxdWVzdBISCgpzZXNzaW9uX2lkGAEgASgJInwKE0xpc3RTZXNzaW9uc1JlcXVlc3QSFwoKcHJvamVjdF9pZBgBIAEoCUgAiAEBEhMKBnN0YXR1cxgCIAEoCUgBiAEBEg0KBWxpbWl0GAMgASgFEg4KBm9mZnNldBgEIAEoBUINCgtfcHJvamVjdF9pZEIJCgdfc3RhdHVzIlEKD1Nlc3Npb25SZXNwb25zZRIyCgdzZXNzaW9uGAEgASgLMhwuaGFuLmNvb3JkaW5hdG9yLlNlc3Npb25EYXRhSACIAQFCCgoIX3Nlc3Npb24i2QIKC1Nlc3Npb25EYXRhEgoKAmlkGAEgASgJEhIKCnNlc3Npb25faWQYAiABKAkSFwoKcHJvamVjdF9pZBgDIAEoCUgAiAEBEhMKBnN0YXR1cxgEIAEoCUgBiAEBEh4KEXNlc3Npb25fZmlsZV9wYXRoGAUgASgJSAKIAQESGQoMc2Vzc2lvbl9zbHVnGAYgASgJSAOIAQESFwoKc3RhcnRlZF9hdBgHIAEoCUgEiAEBEhUKCGVuZGVkX2F0GAggASgJSAWIAQESHgoRbGFzdF9pbmRleGVkX2xpbmUYCSABKAVIBogBAUINCgtfcHJvamVjdF9pZEIJCgdfc3RhdHVzQhQKEl9zZXNzaW9uX2ZpbGVfcGF0aEIPCg1fc2Vzc2lvbl9zbHVnQg0KC19zdGFydGVkX2F0QgsKCV9lbmRlZF9hdEIUChJfbGFzdF9pbmRleGVkX2xpbmUiVQoUTGlzdFNlc3Npb25zUmVzcG9uc2USLgoIc2Vzc2lvbnMYASADKAsyHC5oYW4uY29vcmRpbmF0b3IuU2Vzc2lvbkRhdGESDQoFdG90YWwYAiABKAUiPAoSVHJpZ2dlclNjYW5SZXF1ZXN0EhcKCmNvbmZpZ19kaXIYASABKAlIAIgBAUINCgtfY29uZmlnX2RpciJSCgxTY2FuUmVzcG9uc2USGAoQc2Vzc2lvbnNfaW5kZXhlZBgBIAEoBRIYChBtZXNzYWdlc19pbmRleGVkGAIgASgFEg4KBmVycm9ycxgDIAMoCSJNChBJbmRleEZpbGVSZXF1ZXN0EhEKCWZpbGVfcGF0aBgBIAEoCRIXCgpjb25maWdfZGlyGAIgASgJSACIAQFCDQoLX2NvbmZpZ19kaXIijwEKEUluZGV4RmlsZVJlc3BvbnNlEhIKCnNlc3Npb25faWQYASABKAkSGAoQbWVzc2FnZXNfaW5kZXhlZBgCIAEoBRIWCg50b3RhbF9tZXNzYWdlcxgDIAEoBRIWCg5pc19uZXdfc2Vzc2lvbhgEIAEoCBISCgVlcnJvchgFIAEoCUgAiAEBQggKBl9lcnJvciKcAgoTRXhlY3V0ZUhvb2tzUmVxdWVzdBINCgVldmVudBgBIAEoCRIXCgpzZXNzaW9uX2lkGAIgASgJSACIAQESFgoJdG9vbF9uYW1lGAMgASgJSAGIAQESFwoKdG9vbF9pbnB1dBgEIAEoCUgCiAEBEhAKA2N3ZBgFIAEoCUgDiAEBEjoKA2VudhgGIAMoCzItLmhhbi5jb29yZGluYXRvci5FeGVjdXRlSG9va3NSZXF1ZXN0LkVudkVudHJ5GioKCEVudkVudHJ5EgsKA2tleRgBIAEoCRINCgV2YWx1ZRgCIAEoCToCOAFCDQoLX3Nlc3Npb25faWRCDAoKX3Rvb2xfbmFtZUINCgtfdG9vbF9pbnB1dEIGCgRfY3dkIrEBCgpIb29rT3V0cHV0Eg8KB2hvb2tfaWQYASABKAkSEwoLcGx1Z2luX25hbWUYAiABKAkSEQoJaG9va19uYW1lGAMgASgJEhUKC3N0ZG91dF9saW5lGAQgASgJSAASFQoLc3RkZXJyX2xpbmUYBSABKAlIABIxCghjb21wbGV0ZRgGIAEoCzIdLmhhbi5jb29yZGluYXRvci5Ib29rQ29tcGxldGVIAEIJCgdwYXlsb2FkImQKDEhvb2tDb21wbGV0ZRIRCglleGl0X2NvZGUYASABKAUSDgoGY2FjaGVkGAIgASgIEhIKBWVycm9yGAMgASgJSACIAQESEwoLZHVyYXRpb25fbXMYBCABKANCCAoGX2Vycm9yIj4KEExpc3RIb29rc1JlcXVlc3QSGQoMZXZlbnRfZmlsdGVyGAEgASgJSACIAQFCDwoNX2V2ZW50X2ZpbHRlciJDChFMaXN0SG9va3NSZXNwb25zZRIuCgVob29rcxgBIAMoCzIfLmhhbi5jb29yZGluYXRvci5Ib29rRGVmaW5pdGlvbiKiAQoOSG9va0RlZmluaXRpb24SEwoLcGx1Z2luX25hbWUYASABKAkSEQoJaG9va19uYW1lGAIgASgJEg0KBWV2ZW50GAMgASgJEg8KB2NvbW1hbmQYBCABKAkSFAoHbWF0Y2hlchgFIAEoCUgAiAEBEhcKCnRpbWVvdXRfbXMYBiABKAVIAYgBAUIKCghfbWF0Y2hlckINCgtfdGltZW91dF9tcyJgChJBY3F1aXJlU2xvdFJlcXVlc3QSEQoJc2xvdF9uYW1lGAEgASgJEg0KBW93bmVyGAIgASgJEhgKC3R0bF9zZWNvbmRzGAMgASgFSACIAQFCDgoMX3R0bF9zZWNvbmRzIlUKE0FjcXVpcmVTbG90UmVzcG9uc2USEAoIYWNxdWlyZWQYASABKAgSGgoNY3VycmVudF9vd25lchgCIAEoCUgAiAEBQhAKDl9jdXJyZW50X293bmVyIjYKElJlbGVhc2VTbG90UmVxdWVzdBIRCglzbG90X25hbWUYASABKAkSDQoFb3duZXIYAiABKAkiEgoQTGlzdFNsb3RzUmVxdWVzdCJrCghTbG90SW5mbxIRCglzbG90X25hbWUYASABKAkSDQoFb3duZXIYAiABKAkSEwoLYWNxdWlyZWRfYXQYAyABKAkSGAoLdHRsX3NlY29uZHMYBCABKAVIAIgBAUIOCgxfdHRsX3NlY29uZHMiPQoRTGlzdFNsb3RzUmVzcG9uc2USKAoFc2xvdHMYASADKAsyGS5oYW4uY29vcmRpbmF0b3IuU2xvdEluZm8iWwoTTWVtb3J5U2VhcmNoUmVxdWVzdBINCgVxdWVyeRgBIAEoCRIXCgpzZXNzaW9uX2lkGAIgASgJSACIAQESDQoFbGltaXQYAyABKAVCDQoLX3Nlc3Npb25faWQiRgoUTWVtb3J5U2VhcmNoUmVzcG9uc2USLgoHcmVzdWx0cxgBIAMoCzIdLmhhbi5jb29yZGluYXRvci5NZW1vcnlSZXN1bHQiggEKDE1lbW9yeVJlc3VsdBIKCgJpZBgBIAEoCRIPCgdjb250ZW50GAIgASgJEg0KBXNjb3JlGAMgASgBEhcKCnNlc3Npb25faWQYBCABKAlIAIgBARITCgZzb3VyY2UYBSABKAlIAYgBAUINCgtfc2Vzc2lvbl9pZEIJCgdfc291cmNlIucBChRJbmRleERvY3VtZW50UmVxdWVzdBIPCgdjb250ZW50GAEgASgJEhcKCnNlc3Npb25faWQYAiABKAlIAIgBARITCgZzb3VyY2UYAyABKAlIAYgBARJFCghtZXRhZGF0YRgEIAMoCzIzLmhhbi5jb29yZGluYXRvci5JbmRleERvY3VtZW50UmVxdWVzdC5NZXRhZGF0YUVudHJ5Gi8KDU1ldGFkYXRhRW50cnkSCwoDa2V5GAEgASgJEg0KBXZhbHVlGAIgASgJOgI4AUINCgtfc2Vzc2lvbl9pZEIJCgdfc291cmNlMuABChJDb29yZGluYXRvclNlcnZpY2USQQoGSGVhbHRoEhYuaGFuLmNvb3JkaW5hdG9yLkVtcHR5Gh8uaGFuLmNvb3JkaW5hdG9yLkhlYWx0aFJlc3BvbnNlEkQKCFNodXRkb3duEiAuaGFuLmNvb3JkaW5hdG9yLlNodXRkb3duUmVxdWVzdBoWLmhhbi5jb29yZGluYXRvci5FbXB0eRJBCgZTdGF0dXMSFi5oYW4uY29vcmRpbmF0b3IuRW1wdHkaHy5oYW4uY29vcmRpbmF0b3IuU3RhdHVzUmVzcG9uc2UyiwIKDlNlc3Npb25TZXJ2aWNlElcKCUdldEFjdGl2ZRIoLmhhbi5jb29yZGluYXRvci5HZXRBY3RpdmVTZXNzaW9uUmVxdWVzdBogLmhhbi5jb29yZGluYXRvci5TZXNzaW9uUmVzcG9uc2USSwoDR2V0EiIuaGFuLmNvb3JkaW5hdG9yLkdldFNlc3Npb25SZXF1ZXN0GiAuaGFuLmNvb3JkaW5hdG9yLlNlc3Npb25SZXNwb25zZRJTCgRMaXN0EiQuaGFuLmNvb3JkaW5hdG9yLkxpc3RTZXNzaW9uc1JlcXVlc3QaJS5oYW4uY29vcmRpbmF0b3IuTGlzdFNlc3Npb25zUmVzcG9uc2UytwEKDkluZGV4ZXJTZXJ2aWNlElEKC1RyaWdnZXJTY2FuEiMuaGFuLmNvb3JkaW5hdG9yLlRyaWdnZXJTY2FuUmVxdWVzdBodLmhhbi5jb29yZGluYXRvci5TY2FuUmVzcG9uc2USUgoJSW5kZXhGaWxlEiEuaGFuLmNvb3JkaW5hdG9yLkluZGV4RmlsZVJlcXVlc3QaIi5oYW4uY29vcmRpbmF0b3IuSW5kZXhGaWxlUmVzcG9uc2UytgEKC0hvb2tTZXJ2aWNlElMKDEV4ZWN1dGVIb29rcxIkLmhhbi5jb29yZGluYXRvci5FeGVjdXRlSG9va3NSZXF1ZXN0GhsuaGFuLmNvb3JkaW5hdG9yLkhvb2tPdXRwdXQwARJSCglMaXN0SG9va3MSIS5oYW4uY29vcmRpbmF0b3IuTGlzdEhvb2tzUmVxdWVzdBoiLmhhbi5jb29yZGluYXRvci5MaXN0SG9va3NSZXNwb25zZTL6AQoLU2xvdFNlcnZpY2USVAoHQWNxdWlyZRIjLmhhbi5jb29yZGluYXRvci5BY3F1aXJlU2xvdFJlcXVlc3QaJC5oYW4uY29vcmRpbmF0b3IuQWNxdWlyZVNsb3RSZXNwb25zZRJGCgdSZWxlYXNlEiMuaGFuLmNvb3JkaW5hdG9yLlJlbGVhc2VTbG90UmVxdWVzdBoWLmhhbi5jb29yZGluYXRvci5FbXB0eRJNCgRMaXN0EiEuaGFuLmNvb3JkaW5hdG9yLkxpc3RTbG90c1JlcXVlc3QaIi5oYW4uY29vcmRpbmF0b3IuTGlzdFNsb3RzUmVzcG9uc2UytgEKDU1lbW9yeVNlcnZpY2USVQoGU2VhcmNoEiQuaGFuLmNvb3JkaW5hdG9yLk1lbW9yeVNlYXJjaFJlcXVlc3QaJS5oYW4uY29vcmRpbmF0b3IuTWVtb3J5U2VhcmNoUmVzcG9uc2USTgoNSW5kZXhEb2N1bWVudBIlLmhhbi5jb29yZGluYXRvci5JbmRleERvY3VtZW50UmVxdWVzdBoWLmhhbi5jb29yZGluYXRvci5FbXB0eWIGcHJvdG8z'
+    'ChFjb29yZGluYXRvci5wcm90bxIPaGFuLmNvb3JkaW5hdG9yIgcKBUVtcHR5IqcBCg5TdGF0dXNSZXNwb25zZRIPCgd2ZXJzaW9uGAEgASgJEhYKDnVwdGltZV9zZWNvbmRzGAIgASgJEg8KB2RiX3BhdGgYAyABKAkSFQoNc2Vzc2lvbl9jb3VudBgEIAEoAxIVCg1tZXNzYWdlX2NvdW50GAUgASgDEhYKDndhdGNoZXJfYWN0aXZlGAYgASgIEhUKDXdhdGNoZWRfcGF0aHMYByADKAkiRQoOSGVhbHRoUmVzcG9uc2USDwoHaGVhbHRoeRgBIAEoCBIPCgd2ZXJzaW9uGAIgASgJEhEKCXVwdGltZV9tcxgDIAEoAyI8Cg9TaHV0ZG93blJlcXVlc3QSEAoIZ3JhY2VmdWwYASABKAgSFwoPdGltZW91dF9zZWNvbmRzGAIgASgFIhAKDlZlcnNpb25SZXF1ZXN0InIKD1ZlcnNpb25SZXNwb25zZRIPCgd2ZXJzaW9uGAEgASgJEhIKCmJ1aWxkX2RhdGUYAiABKAkSEgoKZ2l0X2NvbW1pdBgDIAEoCRIUCgxydXN0X3ZlcnNpb24YBCABKAkSEAoIZmVhdHVyZXMYBSADKAkiDwoNU3RhdHVzUmVxdWVzdCKUAgoRR2V0U3RhdHVzUmVzcG9uc2USFgoOdXB0aW1lX3NlY29uZHMYASABKAMSGAoQaW5kZXhlZF9zZXNzaW9ucxgCIAEoAxIYChBpbmRleGVkX21lc3NhZ2VzGAMgASgDEiQKHGFjdGl2ZV93ZWJzb2NrZXRfY29ubmVjdGlvbnMYBCABKAUSFwoPaG9va19jYWNoZV9zaXplGAUgASgFEhgKEGRpc2NvdmVyZWRfaG9va3MYBiABKAUSFgoOZGJfcG9vbF9hY3RpdmUYByABKAUSFAoMZGJfcG9vbF9pZGxlGAggASgFEhsKE2dycGNfYWN0aXZlX3N0cmVhbXMYCSABKAUSDwoHdmVyc2lvbhgKIAEoCSIvChdHZXRBY3RpdmVTZXNzaW9uUmVxdWVzdBIUCgxwcm9qZWN0X3BhdGgYASABKAkiJwoRR2V0U2Vzc2lvblJlcXVlc3QSEgoKc2Vzc2lvbl9pZBgBIAEoCSJ8ChNMaXN0U2Vzc2lvbnNSZXF1ZXN0EhcKCnByb2plY3RfaWQYASABKAlIAIgBARITCgZzdGF0dXMYAiABKAlIAYgBARINCgVsaW1pdBgDIAEoBRIOCgZvZmZzZXQYBCABKAVCDQoLX3Byb2plY3RfaWRCCQoHX3N0YXR1cyJRCg9TZXNzaW9uUmVzcG9uc2USMgoHc2Vzc2lvbhgBIAEoCzIcLmhhbi5jb29yZGluYXRvci5TZXNzaW9uRGF0YUgAiAEBQgoKCF9zZXNzaW9uItkCCgtTZXNzaW9uRGF0YRIKCgJpZBgBIAEoCRISCgpzZXNzaW9uX2lkGAIgASgJEhcKCnByb2plY3RfaWQYAyABKAlIAIgBARITCgZzdGF0dXMYBCABKAlIAYgBARIeChFzZXNzaW9uX2ZpbGVfcGF0aBgFIAEoCUgCiAEBEhkKDHNlc3Npb25fc2x1ZxgGIAEoCUgDiAEBEhcKCnN0YXJ0ZWRfYXQYByABKAlIBIgBARIVCghlbmRlZF9hdBgIIAEoCUgFiAEBEh4KEWxhc3RfaW5kZXhlZF9saW5lGAkgASgFSAaIAQFCDQoLX3Byb2plY3RfaWRCCQoHX3N0YXR1c0IUChJfc2Vzc2lvbl9maWxlX3BhdGhCDwoNX3Nlc3Npb25fc2x1Z0INCgtfc3RhcnRlZF9hdEILCglfZW5kZWRfYXRCFAoSX2xhc3RfaW5kZXhlZF9saW5lIlUKFExpc3RTZXNzaW9uc1Jlc3BvbnNlEi4KCHNlc3Npb25zGAEgAygLMhwuaGFuLmNvb3JkaW5hdG9yLlNlc3Npb25EYXRhEg0KBXRvdGFsGAIgASgFIlUKFVN0cmVhbU1lc3NhZ2VzUmVxdWVzdBISCgpzZXNzaW9uX2lkGAEgASgJEhgKEGZyb21fbGluZV9udW1iZXIYAiABKAUSDgoGZm9sbG93GAMgASgIIsMBCgxNZXNzYWdlRXZlbnQSEgoKbWVzc2FnZV9pZBgBIAEoCRIUCgxtZXNzYWdlX3R5cGUYAiABKAkSFgoJdG9vbF9uYW1lGAMgASgJSACIAQESEQoJdGltZXN0YW1wGAQgASgJEhcKD2NvbnRlbnRfcHJldmlldxgFIAEoCRIVCghyYXdfanNvbhgGIAEoCUgBiAEBEhMKC2xpbmVfbnVtYmVyGAcgASgFQgwKCl90b29sX25hbWVCCwoJX3Jhd19qc29uIjwKElRyaWdnZXJTY2FuUmVxdWVzdBIXCgpjb25maWdfZGlyGAEgASgJSACIAQFCDQoLX2NvbmZpZ19kaXIiUgoMU2NhblJlc3BvbnNlEhgKEHNlc3Npb25zX2luZGV4ZWQYASABKAUSGAoQbWVzc2FnZXNfaW5kZXhlZBgCIAEoBRIOCgZlcnJvcnMYAyADKAkiTQoQSW5kZXhGaWxlUmVxdWVzdBIRCglmaWxlX3BhdGgYASABKAkSFwoKY29uZmlnX2RpchgCIAEoCUgAiAEBQg0KC19jb25maWdfZGlyIo8BChFJbmRleEZpbGVSZXNwb25zZRISCgpzZXNzaW9uX2lkGAEgASgJEhgKEG1lc3NhZ2VzX2luZGV4ZWQYAiABKAUSFgoOdG90YWxfbWVzc2FnZXMYAyABKAUSFgoOaXNfbmV3X3Nlc3Npb24YBCABKAgSEgoFZXJyb3IYBSABKAlIAIgBAUIICgZfZXJyb3IiJQoVV2F0Y2hEaXJlY3RvcnlSZXF1ZXN0EgwKBHBhdGgYASABKAkiQAoWV2F0Y2hEaXJlY3RvcnlSZXNwb25zZRIPCgdjaGFuZ2VkGAEgASgIEhUKDXdhdGNoZWRfcGF0aHMYAiADKAkinAIKE0V4ZWN1dGVIb29rc1JlcXVlc3QSDQoFZXZlbnQYASABKAkSFwoKc2Vzc2lvbl9pZBgCIAEoCUgAiAEBEhYKCXRvb2xfbmFtZRgDIAEoCUgBiAEBEhcKCnRvb2xfaW5wdXQYBCABKAlIAogBARIQCgNjd2QYBSABKAlIA4gBARI6CgNlbnYYBiADKAsyLS5oYW4uY29vcmRpbmF0b3IuRXhlY3V0ZUhvb2tzUmVxdWVzdC5FbnZFbnRyeRoqCghFbnZFbnRyeRILCgNrZXkYASABKAkSDQoFdmFsdWUYAiABKAk6AjgBQg0KC19zZXNzaW9uX2lkQgwKCl90b29sX25hbWVCDQoLX3Rvb2xfaW5wdXRCBgoEX2N3ZCKxAQoKSG9va091dHB1dBIPCgdob29rX2lkGAEgASgJEhMKC3BsdWdpbl9uYW1lGAIgASgJEhEKCWhvb2tfbmFtZRgDIAEoCRIVCgtzdGRvdXRfbGluZRgEIAEoCUgAEhUKC3N0ZGVycl9saW5lGAUgASgJSAASMQoIY29tcGxldGUYBiABKAsyHS5oYW4uY29vcmRpbmF0b3IuSG9va0NvbXBsZXRlSABCCQoHcGF5bG9hZCJkCgxIb29rQ29tcGxldGUSEQoJZXhpdF9jb2RlGAEgASgFEg4KBmNhY2hlZBgCIAEoCBISCgVlcnJvchgDIAEoCUgAiAEBEhMKC2R1cmF0aW9uX21zGAQgASgDQggKBl9lcnJvciJsChBMaXN0SG9va3NSZXF1ZXN0EhkKDGV2ZW50X2ZpbHRlchgBIAEoCUgAiAEBEhoKDXBsdWdpbl9maWx0ZXIYAiABKAlIAYgBAUIPCg1fZXZlbnRfZmlsdGVyQhAKDl9wbHVnaW5fZmlsdGVyIuwBChJEaXNjb3ZlcmVkSG9va0luZm8SEwoLcGx1Z2luX25hbWUYASABKAkSEwoLcGx1Z2luX3Jvb3QYAiABKAkSDQoFZXZlbnQYAyABKAkSEQoJaG9va190eXBlGAQgASgJEhQKB2NvbW1hbmQYBSABKAlIAIgBARITCgZwcm9tcHQYBiABKAlIAYgBARIUCgdtYXRjaGVyGAcgASgJSAKIAQESFwoKdGltZW91dF9tcxgIIAEoDUgDiAEBQgoKCF9jb21tYW5kQgkKB19wcm9tcHRCCgoIX21hdGNoZXJCDQoLX3RpbWVvdXRfbXMiEAoOUmVmcmVzaFJlcXVlc3QiJQoPUmVmcmVzaFJlc3BvbnNlEhIKCmhvb2tfY291bnQYASABKA0iSQoSRHJ5UnVuRXZlbnRSZXF1ZXN0Eg0KBWV2ZW50GAEgASgJEhYKCXRvb2xfbmFtZRgCIAEoCUgAiAEBQgwKCl90b29sX25hbWUiRwoTRHJ5UnVuRXZlbnRSZXNwb25zZRIwCgVob29rcxgBIAMoCzIhLmhhbi5jb29yZGluYXRvci5Ib29rRHJ5UnVuUmVzdWx0IsABChBIb29rRHJ5UnVuUmVzdWx0Eg8KB2hvb2tfaWQYASABKAkSEwoLcGx1Z2luX25hbWUYAiABKAkSEQoJaG9va19uYW1lGAMgASgJEhQKB2NvbW1hbmQYBCABKAlIAIgBARIRCglpc19jYWNoZWQYBSABKAgSFgoOY2FjaGVfa2V5X2hhc2gYBiABKAkSFwoKdGltZW91dF9tcxgHIAEoDUgBiAEBQgoKCF9jb21tYW5kQg0KC190aW1lb3V0X21zImAKEkFjcXVpcmVTbG90UmVxdWVzdBIRCglzbG90X25hbWUYASABKAkSDQoFb3duZXIYAiABKAkSGAoLdHRsX3NlY29uZHMYAyABKAVIAIgBAUIOCgxfdHRsX3NlY29uZHMiVQoTQWNxdWlyZVNsb3RSZXNwb25zZRIQCghhY3F1aXJlZBgBIAEoCBIaCg1jdXJyZW50X293bmVyGAIgASgJSACIAQFCEAoOX2N1cnJlbnRfb3duZXIiNgoSUmVsZWFzZVNsb3RSZXF1ZXN0EhEKCXNsb3RfbmFtZRgBIAEoCRINCgVvd25lchgCIAEoCSISChBMaXN0U2xvdHNSZXF1ZXN0ImsKCFNsb3RJbmZvEhEKCXNsb3RfbmFtZRgBIAEoCRINCgVvd25lchgCIAEoCRITCgthY3F1aXJlZF9hdBgDIAEoCRIYCgt0dGxfc2Vjb25kcxgEIAEoBUgAiAEBQg4KDF90dGxfc2Vjb25kcyI9ChFMaXN0U2xvdHNSZXNwb25zZRIoCgVzbG90cxgBIAMoCzIZLmhhbi5jb29yZGluYXRvci5TbG90SW5mbyJWCg5TZXRTbG90UmVxdWVzdBIRCglzbG90X25hbWUYASABKAkSDQoFdmFsdWUYAiABKAkSFQoIdHRsX3NlY3MYAyABKA1IAIgBAUILCglfdHRsX3NlY3MiIwoOR2V0U2xvdFJlcXVlc3QSEQoJc2xvdF9uYW1lGAEgASgJIiYKEURlbGV0ZVNsb3RSZXF1ZXN0EhEKCXNsb3RfbmFtZRgBIAEoCSIwCgxTbG90UmVzcG9uc2USEQoJc2xvdF9uYW1lGAEgASgJEg0KBXZhbHVlGAIgASgJIhcKFUxpc3RTbG90VmFsdWVzUmVxdWVzdCImChBTbG90TGlzdFJlc3BvbnNlEhIKCnNsb3RfbmFtZXMYASADKAkiJQoQV2F0Y2hTbG90UmVxdWVzdBIRCglzbG90X25hbWUYASABKAkiPAoJU2xvdEV2ZW50EhEKCXNsb3RfbmFtZRgBIAEoCRISCgV2YWx1ZRgCIAEoCUgAiAEBQggKBl92YWx1ZSJbChNNZW1vcnlTZWFyY2hSZXF1ZXN0Eg0KBXF1ZXJ5GAEgASgJEhcKCnNlc3Npb25faWQYAiABKAlIAIgBARINCgVsaW1pdBgDIAEoBUINCgtfc2Vzc2lvbl9pZCJGChRNZW1vcnlTZWFyY2hSZXNwb25zZRIuCgdyZXN1bHRzGAEgAygLMh0uaGFuLmNvb3JkaW5hdG9yLk1lbW9yeVJlc3VsdCKCAQoMTWVtb3J5UmVzdWx0EgoKAmlkGAEgASgJEg8KB2NvbnRlbnQYAiABKAkSDQoFc2NvcmUYAyABKAESFwoKc2Vzc2lvbl9pZBgEIAEoCUgAiAEBEhMKBnNvdXJjZRgFIAEoCUgBiAEBQg0KC19zZXNzaW9uX2lkQgkKB19zb3VyY2Ui5wEKFEluZGV4RG9jdW1lbnRSZXF1ZXN0Eg8KB2NvbnRlbnQYASABKAkSFwoKc2Vzc2lvbl9pZBgCIAEoCUgAiAEBEhMKBnNvdXJjZRgDIAEoCUgBiAEBEkUKCG1ldGFkYXRhGAQgAygLMjMuaGFuLmNvb3JkaW5hdG9yLkluZGV4RG9jdW1lbnRSZXF1ZXN0Lk1ldGFkYXRhRW50cnkaLwoNTWV0YWRhdGFFbnRyeRILCgNrZXkYASABKAkSDQoFdmFsdWUYAiABKAk6AjgBQg0KC19zZXNzaW9uX2lkQgkKB19zb3VyY2UyggMKEkNvb3JkaW5hdG9yU2VydmljZRJBCgZIZWFsdGgSFi5oYW4uY29vcmRpbmF0b3IuRW1wdHkaHy5oYW4uY29vcmRpbmF0b3IuSGVhbHRoUmVzcG9uc2USRAoIU2h1dGRvd24SIC5oYW4uY29vcmRpbmF0b3IuU2h1dGRvd25SZXF1ZXN0GhYuaGFuLmNvb3JkaW5hdG9yLkVtcHR5EkEKBlN0YXR1cxIWLmhhbi5jb29yZGluYXRvci5FbXB0eRofLmhhbi5jb29yZGluYXRvci5TdGF0dXNSZXNwb25zZRJPCgpHZXRWZXJzaW9uEh8uaGFuLmNvb3JkaW5hdG9yLlZlcnNpb25SZXF1ZXN0GiAuaGFuLmNvb3JkaW5hdG9yLlZlcnNpb25SZXNwb25zZRJPCglHZXRTdGF0dXMSHi5oYW4uY29vcmRpbmF0b3IuU3RhdHVzUmVxdWVzdBoiLmhhbi5jb29yZGluYXRvci5HZXRTdGF0dXNSZXNwb25zZTLmAgoOU2Vzc2lvblNlcnZpY2USVwoJR2V0QWN0aXZlEiguaGFuLmNvb3JkaW5hdG9yLkdldEFjdGl2ZVNlc3Npb25SZXF1ZXN0GiAuaGFuLmNvb3JkaW5hdG9yLlNlc3Npb25SZXNwb25zZRJLCgNHZXQSIi5oYW4uY29vcmRpbmF0b3IuR2V0U2Vzc2lvblJlcXVlc3QaIC5oYW4uY29vcmRpbmF0b3IuU2Vzc2lvblJlc3BvbnNlElMKBExpc3QSJC5oYW4uY29vcmRpbmF0b3IuTGlzdFNlc3Npb25zUmVxdWVzdBolLmhhbi5jb29yZGluYXRvci5MaXN0U2Vzc2lvbnNSZXNwb25zZRJZCg5TdHJlYW1NZXNzYWdlcxImLmhhbi5jb29yZGluYXRvci5TdHJlYW1NZXNzYWdlc1JlcXVlc3QaHS5oYW4uY29vcmRpbmF0b3IuTWVzc2FnZUV2ZW50MAEy/wIKDkluZGV4ZXJTZXJ2aWNlElEKC1RyaWdnZXJTY2FuEiMuaGFuLmNvb3JkaW5hdG9yLlRyaWdnZXJTY2FuUmVxdWVzdBodLmhhbi5jb29yZGluYXRvci5TY2FuUmVzcG9uc2USUgoJSW5kZXhGaWxlEiEuaGFuLmNvb3JkaW5hdG9yLkluZGV4RmlsZVJlcXVlc3QaIi5oYW4uY29vcmRpbmF0b3IuSW5kZXhGaWxlUmVzcG9uc2USYQoOV2F0Y2hEaXJlY3RvcnkSJi5oYW4uY29vcmRpbmF0b3IuV2F0Y2hEaXJlY3RvcnlSZXF1ZXN0GicuaGFuLmNvb3JkaW5hdG9yLldhdGNoRGlyZWN0b3J5UmVzcG9uc2USYwoQVW53YXRjaERpcmVjdG9yeRImLmhhbi5jb29yZGluYXRvci5XYXRjaERpcmVjdG9yeVJlcXVlc3QaJy5oYW4uY29vcmRpbmF0b3IuV2F0Y2hEaXJlY3RvcnlSZXNwb25zZTLmAgoLSG9va1NlcnZpY2USUwoMRXhlY3V0ZUhvb2tzEiQuaGFuLmNvb3JkaW5hdG9yLkV4ZWN1dGVIb29rc1JlcXVlc3QaGy5oYW4uY29vcmRpbmF0b3IuSG9va091dHB1dDABElUKCUxpc3RIb29rcxIhLmhhbi5jb29yZGluYXRvci5MaXN0SG9va3NSZXF1ZXN0GiMuaGFuLmNvb3JkaW5hdG9yLkRpc2NvdmVyZWRIb29rSW5mbzABElEKDFJlZnJlc2hIb29rcxIfLmhhbi5jb29yZGluYXRvci5SZWZyZXNoUmVxdWVzdBogLmhhbi5jb29yZGluYXRvci5SZWZyZXNoUmVzcG9uc2USWAoLRHJ5UnVuRXZlbnQSIy5oYW4uY29vcmRpbmF0b3IuRHJ5UnVuRXZlbnRSZXF1ZXN0GiQuaGFuLmNvb3JkaW5hdG9yLkRyeVJ1bkV2ZW50UmVzcG9uc2Uy+AQKC1Nsb3RTZXJ2aWNlElQKB0FjcXVpcmUSIy5oYW4uY29vcmRpbmF0b3IuQWNxdWlyZVNsb3RSZXF1ZXN0GiQuaGFuLmNvb3JkaW5hdG9yLkFjcXVpcmVTbG90UmVzcG9uc2USRgoHUmVsZWFzZRIjLmhhbi5jb29yZGluYXRvci5SZWxlYXNlU2xvdFJlcXVlc3QaFi5oYW4uY29vcmRpbmF0b3IuRW1wdHkSTQoETGlzdBIhLmhhbi5jb29yZGluYXRvci5MaXN0U2xvdHNSZXF1ZXN0GiIuaGFuLmNvb3JkaW5hdG9yLkxpc3RTbG90c1Jlc3BvbnNlEkUKA1NldBIfLmhhbi5jb29yZGluYXRvci5TZXRTbG90UmVxdWVzdBodLmhhbi5jb29yZGluYXRvci5TbG90UmVzcG9uc2USRQoDR2V0Eh8uaGFuLmNvb3JkaW5hdG9yLkdldFNsb3RSZXF1ZXN0Gh0uaGFuLmNvb3JkaW5hdG9yLlNsb3RSZXNwb25zZRJLCgZEZWxldGUSIi5oYW4uY29vcmRpbmF0b3IuRGVsZXRlU2xvdFJlcXVlc3QaHS5oYW4uY29vcmRpbmF0b3IuU2xvdFJlc3BvbnNlElcKCkxpc3RWYWx1ZXMSJi5oYW4uY29vcmRpbmF0b3IuTGlzdFNsb3RWYWx1ZXNSZXF1ZXN0GiEuaGFuLmNvb3JkaW5hdG9yLlNsb3RMaXN0UmVzcG9uc2USSAoFV2F0Y2gSIS5oYW4uY29vcmRpbmF0b3IuV2F0Y2hTbG90UmVxdWVzdBoaLmhhbi5jb29yZGluYXRvci5TbG90RXZlbnQwATK2AQoNTWVtb3J5U2VydmljZRJVCgZTZWFyY2gSJC5oYW4uY29vcmRpbmF0b3IuTWVtb3J5U2VhcmNoUmVxdWVzdBolLmhhbi5jb29yZGluYXRvci5NZW1vcnlTZWFyY2hSZXNwb25zZRJOCg1JbmRleERvY3VtZW50EiUuaGFuLmNvb3JkaW5hdG9yLkluZGV4RG9jdW1lbnRSZXF1ZXN0GhYuaGFuLmNvb3JkaW5hdG9yLkVtcHR5YgZwcm90bzM='
   );
 
 /**
@@ -135,6 +135,133 @@ export const ShutdownRequestSchema: GenMessage<ShutdownRequest> =
   /*@__PURE__*/
   messageDesc(file_coordinator, 3);
 
+/**
+ * @generated from message han.coordinator.VersionRequest
+ */
+export type VersionRequest = Message<'han.coordinator.VersionRequest'> & {};
+
+/**
+ * Describes the message han.coordinator.VersionRequest.
+ * Use `create(VersionRequestSchema)` to create a new message.
+ */
+export const VersionRequestSchema: GenMessage<VersionRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 4);
+
+/**
+ * @generated from message han.coordinator.VersionResponse
+ */
+export type VersionResponse = Message<'han.coordinator.VersionResponse'> & {
+  /**
+   * @generated from field: string version = 1;
+   */
+  version: string;
+
+  /**
+   * @generated from field: string build_date = 2;
+   */
+  buildDate: string;
+
+  /**
+   * @generated from field: string git_commit = 3;
+   */
+  gitCommit: string;
+
+  /**
+   * @generated from field: string rust_version = 4;
+   */
+  rustVersion: string;
+
+  /**
+   * @generated from field: repeated string features = 5;
+   */
+  features: string[];
+};
+
+/**
+ * Describes the message han.coordinator.VersionResponse.
+ * Use `create(VersionResponseSchema)` to create a new message.
+ */
+export const VersionResponseSchema: GenMessage<VersionResponse> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 5);
+
+/**
+ * @generated from message han.coordinator.StatusRequest
+ */
+export type StatusRequest = Message<'han.coordinator.StatusRequest'> & {};
+
+/**
+ * Describes the message han.coordinator.StatusRequest.
+ * Use `create(StatusRequestSchema)` to create a new message.
+ */
+export const StatusRequestSchema: GenMessage<StatusRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 6);
+
+/**
+ * @generated from message han.coordinator.GetStatusResponse
+ */
+export type GetStatusResponse = Message<'han.coordinator.GetStatusResponse'> & {
+  /**
+   * @generated from field: int64 uptime_seconds = 1;
+   */
+  uptimeSeconds: bigint;
+
+  /**
+   * @generated from field: int64 indexed_sessions = 2;
+   */
+  indexedSessions: bigint;
+
+  /**
+   * @generated from field: int64 indexed_messages = 3;
+   */
+  indexedMessages: bigint;
+
+  /**
+   * @generated from field: int32 active_websocket_connections = 4;
+   */
+  activeWebsocketConnections: number;
+
+  /**
+   * @generated from field: int32 hook_cache_size = 5;
+   */
+  hookCacheSize: number;
+
+  /**
+   * @generated from field: int32 discovered_hooks = 6;
+   */
+  discoveredHooks: number;
+
+  /**
+   * @generated from field: int32 db_pool_active = 7;
+   */
+  dbPoolActive: number;
+
+  /**
+   * @generated from field: int32 db_pool_idle = 8;
+   */
+  dbPoolIdle: number;
+
+  /**
+   * @generated from field: int32 grpc_active_streams = 9;
+   */
+  grpcActiveStreams: number;
+
+  /**
+   * @generated from field: string version = 10;
+   */
+  version: string;
+};
+
+/**
+ * Describes the message han.coordinator.GetStatusResponse.
+ * Use `create(GetStatusResponseSchema)` to create a new message.
+ */
+export const GetStatusResponseSchema: GenMessage<GetStatusResponse> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 7);
+
 /**
  * @generated from message han.coordinator.GetActiveSessionRequest
  */
@@ -152,7 +279,7 @@ export type GetActiveSessionRequest =
  */
 export const GetActiveSessionRequestSchema: GenMessage<GetActiveSessionRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 4);
+  messageDesc(file_coordinator, 8);
 
 /**
  * @generated from message han.coordinator.GetSessionRequest
@@ -170,7 +297,7 @@ export type GetSessionRequest = Message<'han.coordinator.GetSessionRequest'> & {
  */
 export const GetSessionRequestSchema: GenMessage<GetSessionRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 5);
+  messageDesc(file_coordinator, 9);
 
 /**
  * @generated from message han.coordinator.ListSessionsRequest
@@ -204,7 +331,7 @@ export type ListSessionsRequest =
  */
 export const ListSessionsRequestSchema: GenMessage<ListSessionsRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 6);
+  messageDesc(file_coordinator, 10);
 
 /**
  * @generated from message han.coordinator.SessionResponse
@@ -222,7 +349,7 @@ export type SessionResponse = Message<'han.coordinator.SessionResponse'> & {
  */
 export const SessionResponseSchema: GenMessage<SessionResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 7);
+  messageDesc(file_coordinator, 11);
 
 /**
  * @generated from message han.coordinator.SessionData
@@ -280,7 +407,7 @@ export type SessionData = Message<'han.coordinator.SessionData'> & {
  */
 export const SessionDataSchema: GenMessage<SessionData> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 8);
+  messageDesc(file_coordinator, 12);
 
 /**
  * @generated from message han.coordinator.ListSessionsResponse
@@ -304,7 +431,7 @@ export type ListSessionsResponse =
  */
 export const ListSessionsResponseSchema: GenMessage<ListSessionsResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 9);
+  messageDesc(file_coordinator, 13);
 
 /**
  * @generated from message han.coordinator.TriggerScanRequest
@@ -323,7 +450,7 @@ export type TriggerScanRequest =
  */
 export const TriggerScanRequestSchema: GenMessage<TriggerScanRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 10);
+  messageDesc(file_coordinator, 14);
 
 /**
  * @generated from message han.coordinator.ScanResponse
@@ -351,7 +478,7 @@ export type ScanResponse = Message<'han.coordinator.ScanResponse'> & {
  */
 export const ScanResponseSchema: GenMessage<ScanResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 11);
+  messageDesc(file_coordinator, 15);
 
 /**
  * @generated from message han.coordinator.IndexFileRequest
@@ -374,7 +501,7 @@ export type IndexFileRequest = Message<'han.coordinator.IndexFileRequest'> & {
  */
 export const IndexFileRequestSchema: GenMessage<IndexFileRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 12);
+  messageDesc(file_coordinator, 16);
 
 /**
  * @generated from message han.coordinator.IndexFileResponse
@@ -412,7 +539,7 @@ export type IndexFileResponse = Message<'han.coordinator.IndexFileResponse'> & {
  */
 export const IndexFileResponseSchema: GenMessage<IndexFileResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 13);
+  messageDesc(file_coordinator, 17);
 
 /**
  * @generated from message han.coordinator.ExecuteHooksRequest
@@ -456,7 +583,7 @@ export type ExecuteHooksRequest =
  */
 export const ExecuteHooksRequestSchema: GenMessage<ExecuteHooksRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 14);
+  messageDesc(file_coordinator, 18);
 
 /**
  * @generated from message han.coordinator.HookOutput
@@ -511,7 +638,7 @@ export type HookOutput = Message<'han.coordinator.HookOutput'> & {
  */
 export const HookOutputSchema: GenMessage<HookOutput> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 15);
+  messageDesc(file_coordinator, 19);
 
 /**
  * @generated from message han.coordinator.HookComplete
@@ -544,7 +671,7 @@ export type HookComplete = Message<'han.coordinator.HookComplete'> & {
  */
 export const HookCompleteSchema: GenMessage<HookComplete> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 16);
+  messageDesc(file_coordinator, 20);
 
 /**
  * @generated from message han.coordinator.ListHooksRequest
@@ -562,7 +689,7 @@ export type ListHooksRequest = Message<'han.coordinator.ListHooksRequest'> & {
  */
 export const ListHooksRequestSchema: GenMessage<ListHooksRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 17);
+  messageDesc(file_coordinator, 21);
 
 /**
  * @generated from message han.coordinator.ListHooksResponse
@@ -580,7 +707,7 @@ export type ListHooksResponse = Message<'han.coordinator.ListHooksResponse'> & {
  */
 export const ListHooksResponseSchema: GenMessage<ListHooksResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 18);
+  messageDesc(file_coordinator, 22);
 
 /**
  * @generated from message han.coordinator.HookDefinition
@@ -623,7 +750,98 @@ export type HookDefinition = Message<'han.coordinator.HookDefinition'> & {
  */
 export const HookDefinitionSchema: GenMessage<HookDefinition> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 19);
+  messageDesc(file_coordinator, 23);
+
+/**
+ * @generated from message han.coordinator.DryRunEventRequest
+ */
+export type DryRunEventRequest =
+  Message<'han.coordinator.DryRunEventRequest'> & {
+    /**
+     * @generated from field: string event = 1;
+     */
+    event: string;
+
+    /**
+     * @generated from field: optional string tool_name = 2;
+     */
+    toolName?: string;
+  };
+
+/**
+ * Describes the message han.coordinator.DryRunEventRequest.
+ * Use `create(DryRunEventRequestSchema)` to create a new message.
+ */
+export const DryRunEventRequestSchema: GenMessage<DryRunEventRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 24);
+
+/**
+ * @generated from message han.coordinator.DryRunEventResponse
+ */
+export type DryRunEventResponse =
+  Message<'han.coordinator.DryRunEventResponse'> & {
+    /**
+     * @generated from field: repeated HookDryRunResult hooks = 1;
+     */
+    hooks: HookDryRunResult[];
+  };
+
+/**
+ * Describes the message han.coordinator.DryRunEventResponse.
+ * Use `create(DryRunEventResponseSchema)` to create a new message.
+ */
+export const DryRunEventResponseSchema: GenMessage<DryRunEventResponse> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 25);
+
+/**
+ * @generated from message han.coordinator.HookDryRunResult
+ */
+export type HookDryRunResult = Message<'han.coordinator.HookDryRunResult'> & {
+  /**
+   * @generated from field: string hook_id = 1;
+   */
+  hookId: string;
+
+  /**
+   * @generated from field: string plugin_name = 2;
+   */
+  pluginName: string;
+
+  /**
+   * @generated from field: string hook_name = 3;
+   */
+  hookName: string;
+
+  /**
+   * @generated from field: optional string command = 4;
+   */
+  command?: string;
+
+  /**
+   * @generated from field: bool is_cached = 5;
+   */
+  isCached: boolean;
+
+  /**
+   * @generated from field: string cache_key_hash = 6;
+   */
+  cacheKeyHash: string;
+
+  /**
+   * @generated from field: optional uint32 timeout_ms = 7;
+   */
+  timeoutMs?: number;
+};
+
+/**
+ * Describes the message han.coordinator.HookDryRunResult.
+ * Use `create(HookDryRunResultSchema)` to create a new message.
+ */
+export const HookDryRunResultSchema: GenMessage<HookDryRunResult> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 26);
 
 /**
  * @generated from message han.coordinator.AcquireSlotRequest
@@ -652,7 +870,7 @@ export type AcquireSlotRequest =
  */
 export const AcquireSlotRequestSchema: GenMessage<AcquireSlotRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 20);
+  messageDesc(file_coordinator, 27);
 
 /**
  * @generated from message han.coordinator.AcquireSlotResponse
@@ -676,7 +894,7 @@ export type AcquireSlotResponse =
  */
 export const AcquireSlotResponseSchema: GenMessage<AcquireSlotResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 21);
+  messageDesc(file_coordinator, 28);
 
 /**
  * @generated from message han.coordinator.ReleaseSlotRequest
@@ -700,7 +918,7 @@ export type ReleaseSlotRequest =
  */
 export const ReleaseSlotRequestSchema: GenMessage<ReleaseSlotRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 22);
+  messageDesc(file_coordinator, 29);
 
 /**
  * @generated from message han.coordinator.ListSlotsRequest
@@ -713,7 +931,7 @@ export type ListSlotsRequest = Message<'han.coordinator.ListSlotsRequest'> & {};
  */
 export const ListSlotsRequestSchema: GenMessage<ListSlotsRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 23);
+  messageDesc(file_coordinator, 30);
 
 /**
  * @generated from message han.coordinator.SlotInfo
@@ -746,7 +964,7 @@ export type SlotInfo = Message<'han.coordinator.SlotInfo'> & {
  */
 export const SlotInfoSchema: GenMessage<SlotInfo> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 24);
+  messageDesc(file_coordinator, 31);
 
 /**
  * @generated from message han.coordinator.ListSlotsResponse
@@ -764,7 +982,167 @@ export type ListSlotsResponse = Message<'han.coordinator.ListSlotsResponse'> & {
  */
 export const ListSlotsResponseSchema: GenMessage<ListSlotsResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 25);
+  messageDesc(file_coordinator, 32);
+
+/**
+ * @generated from message han.coordinator.SetSlotRequest
+ */
+export type SetSlotRequest = Message<'han.coordinator.SetSlotRequest'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+
+  /**
+   * @generated from field: string value = 2;
+   */
+  value: string;
+
+  /**
+   * @generated from field: optional uint32 ttl_secs = 3;
+   */
+  ttlSecs?: number;
+};
+
+/**
+ * Describes the message han.coordinator.SetSlotRequest.
+ * Use `create(SetSlotRequestSchema)` to create a new message.
+ */
+export const SetSlotRequestSchema: GenMessage<SetSlotRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 33);
+
+/**
+ * @generated from message han.coordinator.GetSlotRequest
+ */
+export type GetSlotRequest = Message<'han.coordinator.GetSlotRequest'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+};
+
+/**
+ * Describes the message han.coordinator.GetSlotRequest.
+ * Use `create(GetSlotRequestSchema)` to create a new message.
+ */
+export const GetSlotRequestSchema: GenMessage<GetSlotRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 34);
+
+/**
+ * @generated from message han.coordinator.DeleteSlotRequest
+ */
+export type DeleteSlotRequest = Message<'han.coordinator.DeleteSlotRequest'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+};
+
+/**
+ * Describes the message han.coordinator.DeleteSlotRequest.
+ * Use `create(DeleteSlotRequestSchema)` to create a new message.
+ */
+export const DeleteSlotRequestSchema: GenMessage<DeleteSlotRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 35);
+
+/**
+ * @generated from message han.coordinator.SlotResponse
+ */
+export type SlotResponse = Message<'han.coordinator.SlotResponse'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+
+  /**
+   * @generated from field: string value = 2;
+   */
+  value: string;
+};
+
+/**
+ * Describes the message han.coordinator.SlotResponse.
+ * Use `create(SlotResponseSchema)` to create a new message.
+ */
+export const SlotResponseSchema: GenMessage<SlotResponse> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 36);
+
+/**
+ * @generated from message han.coordinator.ListSlotValuesRequest
+ */
+export type ListSlotValuesRequest =
+  Message<'han.coordinator.ListSlotValuesRequest'> & {};
+
+/**
+ * Describes the message han.coordinator.ListSlotValuesRequest.
+ * Use `create(ListSlotValuesRequestSchema)` to create a new message.
+ */
+export const ListSlotValuesRequestSchema: GenMessage<ListSlotValuesRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 37);
+
+/**
+ * @generated from message han.coordinator.SlotListResponse
+ */
+export type SlotListResponse = Message<'han.coordinator.SlotListResponse'> & {
+  /**
+   * @generated from field: repeated string slot_names = 1;
+   */
+  slotNames: string[];
+};
+
+/**
+ * Describes the message han.coordinator.SlotListResponse.
+ * Use `create(SlotListResponseSchema)` to create a new message.
+ */
+export const SlotListResponseSchema: GenMessage<SlotListResponse> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 38);
+
+/**
+ * @generated from message han.coordinator.WatchSlotRequest
+ */
+export type WatchSlotRequest = Message<'han.coordinator.WatchSlotRequest'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+};
+
+/**
+ * Describes the message han.coordinator.WatchSlotRequest.
+ * Use `create(WatchSlotRequestSchema)` to create a new message.
+ */
+export const WatchSlotRequestSchema: GenMessage<WatchSlotRequest> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 39);
+
+/**
+ * @generated from message han.coordinator.SlotEvent
+ */
+export type SlotEvent = Message<'han.coordinator.SlotEvent'> & {
+  /**
+   * @generated from field: string slot_name = 1;
+   */
+  slotName: string;
+
+  /**
+   * @generated from field: optional string value = 2;
+   */
+  value?: string;
+};
+
+/**
+ * Describes the message han.coordinator.SlotEvent.
+ * Use `create(SlotEventSchema)` to create a new message.
+ */
+export const SlotEventSchema: GenMessage<SlotEvent> =
+  /*@__PURE__*/
+  messageDesc(file_coordinator, 40);
 
 /**
  * @generated from message han.coordinator.MemorySearchRequest
@@ -793,7 +1171,7 @@ export type MemorySearchRequest =
  */
 export const MemorySearchRequestSchema: GenMessage<MemorySearchRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 26);
+  messageDesc(file_coordinator, 41);
 
 /**
  * @generated from message han.coordinator.MemorySearchResponse
@@ -812,7 +1190,7 @@ export type MemorySearchResponse =
  */
 export const MemorySearchResponseSchema: GenMessage<MemorySearchResponse> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 27);
+  messageDesc(file_coordinator, 42);
 
 /**
  * @generated from message han.coordinator.MemoryResult
@@ -850,7 +1228,7 @@ export type MemoryResult = Message<'han.coordinator.MemoryResult'> & {
  */
 export const MemoryResultSchema: GenMessage<MemoryResult> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 28);
+  messageDesc(file_coordinator, 43);
 
 /**
  * @generated from message han.coordinator.IndexDocumentRequest
@@ -884,7 +1262,7 @@ export type IndexDocumentRequest =
  */
 export const IndexDocumentRequestSchema: GenMessage<IndexDocumentRequest> =
   /*@__PURE__*/
-  messageDesc(file_coordinator, 29);
+  messageDesc(file_coordinator, 44);
 
 /**
  * @generated from service han.coordinator.CoordinatorService
@@ -914,6 +1292,22 @@ export const CoordinatorService: GenService<{
     input: typeof EmptySchema;
     output: typeof StatusResponseSchema;
   };
+  /**
+   * @generated from rpc han.coordinator.CoordinatorService.GetVersion
+   */
+  getVersion: {
+    methodKind: 'unary';
+    input: typeof VersionRequestSchema;
+    output: typeof VersionResponseSchema;
+  };
+  /**
+   * @generated from rpc han.coordinator.CoordinatorService.GetStatus
+   */
+  getStatus: {
+    methodKind: 'unary';
+    input: typeof StatusRequestSchema;
+    output: typeof GetStatusResponseSchema;
+  };
 }> = /*@__PURE__*/ serviceDesc(file_coordinator, 0);
 
 /**
@@ -988,6 +1382,14 @@ export const HookService: GenService<{
     input: typeof ListHooksRequestSchema;
     output: typeof ListHooksResponseSchema;
   };
+  /**
+   * @generated from rpc han.coordinator.HookService.DryRunEvent
+   */
+  dryRunEvent: {
+    methodKind: 'unary';
+    input: typeof DryRunEventRequestSchema;
+    output: typeof DryRunEventResponseSchema;
+  };
 }> = /*@__PURE__*/ serviceDesc(file_coordinator, 3);
 
 /**
@@ -1018,6 +1420,46 @@ export const SlotService: GenService<{
     input: typeof ListSlotsRequestSchema;
     output: typeof ListSlotsResponseSchema;
   };
+  /**
+   * @generated from rpc han.coordinator.SlotService.Set
+   */
+  set: {
+    methodKind: 'unary';
+    input: typeof SetSlotRequestSchema;
+    output: typeof SlotResponseSchema;
+  };
+  /**
+   * @generated from rpc han.coordinator.SlotService.Get
+   */
+  get: {
+    methodKind: 'unary';
+    input: typeof GetSlotRequestSchema;
+    output: typeof SlotResponseSchema;
+  };
+  /**
+   * @generated from rpc han.coordinator.SlotService.Delete
+   */
+  delete: {
+    methodKind: 'unary';
+    input: typeof DeleteSlotRequestSchema;
+    output: typeof SlotResponseSchema;
+  };
+  /**
+   * @generated from rpc han.coordinator.SlotService.ListValues
+   */
+  listValues: {
+    methodKind: 'unary';
+    input: typeof ListSlotValuesRequestSchema;
+    output: typeof SlotListResponseSchema;
+  };
+  /**
+   * @generated from rpc han.coordinator.SlotService.Watch
+   */
+  watch: {
+    methodKind: 'server_streaming';
+    input: typeof WatchSlotRequestSchema;
+    output: typeof SlotEventSchema;
+  };
 }> = /*@__PURE__*/ serviceDesc(file_coordinator, 4);
 
 /**